@@ -0,0 +1,10 @@
+/// Boolean operations between two closed shells
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BooleanOperation {
+    /// The region inside either of the shells
+    Union,
+    /// The region inside both of the shells
+    Intersection,
+    /// The region inside the first shell and outside the second shell
+    Difference,
+}
@@ -0,0 +1,348 @@
+use nalgebra::{ComplexField, Point2, Vector2};
+
+use crate::{
+    curve::{CompoundCurve2D, NurbsCurve2D},
+    misc::{is_point_inside_polygon, FloatingPoint, Invertible},
+    surface::{NurbsSurface3D, TrimmedSurface},
+};
+
+/// A span of the boundary of a region in the parameter space
+#[derive(Clone, Debug)]
+enum Span<T: FloatingPoint> {
+    /// A straight segment on the boundary of the parameter domain
+    Segment(Point2<T>, Point2<T>),
+    /// A splitting curve
+    Curve(NurbsCurve2D<T>),
+}
+
+impl<T: FloatingPoint> Span<T> {
+    fn start(&self) -> Point2<T> {
+        match self {
+            Span::Segment(a, _) => *a,
+            Span::Curve(c) => c.point_at(c.knots_domain().0),
+        }
+    }
+
+    fn into_curve(self) -> NurbsCurve2D<T> {
+        match self {
+            Span::Segment(a, b) => NurbsCurve2D::polyline(&[a, b]),
+            Span::Curve(c) => c,
+        }
+    }
+}
+
+/// Get the start & end points of a chain of curves
+fn chain_ends<T: FloatingPoint>(chain: &[NurbsCurve2D<T>]) -> (Point2<T>, Point2<T>) {
+    let first = &chain[0];
+    let last = &chain[chain.len() - 1];
+    (
+        first.point_at(first.knots_domain().0),
+        last.point_at(last.knots_domain().1),
+    )
+}
+
+/// Tessellate the closed sequence of spans into a polygon
+fn polygon<T: FloatingPoint>(spans: &[Span<T>]) -> Vec<Point2<T>> {
+    spans
+        .iter()
+        .flat_map(|s| match s {
+            Span::Segment(a, _) => vec![*a],
+            Span::Curve(c) => {
+                let mut points = c.tessellate(None);
+                points.pop();
+                points
+            }
+        })
+        .collect()
+}
+
+/// Compute the unsigned area of the polygon
+fn polygon_area<T: FloatingPoint>(polygon: &[Point2<T>]) -> T {
+    let area = polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(a, b)| a.x * b.y - b.x * a.y)
+        .fold(T::zero(), |a, b| a + b);
+    ComplexField::abs(area) * T::from_f64(0.5).unwrap()
+}
+
+/// A region of the parameter domain bounded by spans
+#[derive(Clone, Debug)]
+struct Region<T: FloatingPoint> {
+    /// The outer boundary, `None` means the boundary of the whole domain
+    boundary: Option<Vec<Span<T>>>,
+    holes: Vec<Vec<Span<T>>>,
+}
+
+/// Split the face of a surface into the regions bounded by the curves in its parameter space
+/// The curves must not cross each other. Open curves are joined at their end points into chains
+/// which run from the boundary of the domain to the boundary, and closed chains become loops.
+pub(crate) struct FaceSplitter<'a, T: FloatingPoint> {
+    surface: &'a NurbsSurface3D<T>,
+    tolerance: T,
+}
+
+impl<'a, T: FloatingPoint> FaceSplitter<'a, T> {
+    pub fn new(surface: &'a NurbsSurface3D<T>) -> Self {
+        let (u0, u1) = surface.u_knots_domain();
+        let (v0, v1) = surface.v_knots_domain();
+        let tolerance = Vector2::new(u1 - u0, v1 - v0).norm() * T::from_f64(1e-5).unwrap();
+        Self { surface, tolerance }
+    }
+
+    fn domain_spans(&self) -> Vec<Span<T>> {
+        let (u0, u1) = self.surface.u_knots_domain();
+        let (v0, v1) = self.surface.v_knots_domain();
+        let corners = [
+            Point2::new(u0, v0),
+            Point2::new(u1, v0),
+            Point2::new(u1, v1),
+            Point2::new(u0, v1),
+        ];
+        (0..4)
+            .map(|i| Span::Segment(corners[i], corners[(i + 1) % 4]))
+            .collect()
+    }
+
+    fn is_on_boundary(&self, p: &Point2<T>) -> bool {
+        let (u0, u1) = self.surface.u_knots_domain();
+        let (v0, v1) = self.surface.v_knots_domain();
+        let d = ComplexField::abs(p.x - u0)
+            .min(ComplexField::abs(p.x - u1))
+            .min(ComplexField::abs(p.y - v0))
+            .min(ComplexField::abs(p.y - v1));
+        d < self.tolerance
+    }
+
+    /// Check if the chain runs along the boundary of the domain (e.g. the seam of a periodic surface)
+    fn is_along_boundary(&self, chain: &[NurbsCurve2D<T>]) -> bool {
+        chain.iter().all(|c| {
+            let (start, end) = c.knots_domain();
+            (0..=4).all(|i| {
+                let t =
+                    start + (end - start) * T::from_usize(i).unwrap() / T::from_usize(4).unwrap();
+                self.is_on_boundary(&c.point_at(t))
+            })
+        })
+    }
+
+    fn contains(&self, region: &Region<T>, p: &Point2<T>) -> bool {
+        let inside = match region.boundary.as_ref() {
+            Some(boundary) => is_point_inside_polygon(p, &polygon(boundary)),
+            None => {
+                let (u0, u1) = self.surface.u_knots_domain();
+                let (v0, v1) = self.surface.v_knots_domain();
+                u0 <= p.x && p.x <= u1 && v0 <= p.y && p.y <= v1
+            }
+        };
+        inside
+            && !region
+                .holes
+                .iter()
+                .any(|h| is_point_inside_polygon(p, &polygon(h)))
+    }
+
+    /// Join the curves at their end points into open chains ending on the domain boundary & closed loops
+    #[allow(clippy::type_complexity)]
+    fn chain(
+        &self,
+        curves: Vec<NurbsCurve2D<T>>,
+    ) -> anyhow::Result<(Vec<Vec<NurbsCurve2D<T>>>, Vec<Vec<NurbsCurve2D<T>>>)> {
+        let mut chains = vec![];
+        let mut loops = vec![];
+        let mut pool = curves;
+        while let Some(curve) = pool.pop() {
+            let mut chain = vec![curve];
+            loop {
+                let (head, tail) = chain_ends(&chain);
+                if (head - tail).norm() < self.tolerance {
+                    loops.push(chain);
+                    break;
+                }
+
+                let (end, prepend) = if !self.is_on_boundary(&tail) {
+                    (tail, false)
+                } else if !self.is_on_boundary(&head) {
+                    (head, true)
+                } else {
+                    chains.push(chain);
+                    break;
+                };
+                let found = pool.iter().position(|c| {
+                    let (s, e) = chain_ends(std::slice::from_ref(c));
+                    (s - end).norm() < self.tolerance || (e - end).norm() < self.tolerance
+                });
+                let Some(index) = found else {
+                    anyhow::bail!("A splitting curve ends in the interior of the face");
+                };
+                let mut next = pool.swap_remove(index);
+                let (s, _) = chain_ends(std::slice::from_ref(&next));
+                let starts_at_end = (s - end).norm() < self.tolerance;
+                if prepend {
+                    if starts_at_end {
+                        next.invert();
+                    }
+                    chain.insert(0, next);
+                } else {
+                    if !starts_at_end {
+                        next.invert();
+                    }
+                    chain.push(next);
+                }
+            }
+        }
+        Ok((chains, loops))
+    }
+
+    /// Insert the point as a vertex of the boundary spans & return the index of the span starting at the point
+    fn insert_vertex(&self, spans: &mut Vec<Span<T>>, p: &Point2<T>) -> anyhow::Result<usize> {
+        if let Some(i) = spans
+            .iter()
+            .position(|s| (s.start() - p).norm() < self.tolerance)
+        {
+            return Ok(i);
+        }
+        let found = spans.iter().position(|s| match s {
+            Span::Segment(a, b) => {
+                let ab = b - a;
+                let t = (p - a).dot(&ab) / ab.norm_squared();
+                T::zero() < t && t < T::one() && (a + ab * t - p).norm() < self.tolerance
+            }
+            Span::Curve(_) => false,
+        });
+        let Some(i) = found else {
+            anyhow::bail!("The end of a splitting curve is not on the boundary of the region");
+        };
+        let Span::Segment(a, b) = spans[i].clone() else {
+            unreachable!()
+        };
+        spans[i] = Span::Segment(a, *p);
+        spans.insert(i + 1, Span::Segment(*p, b));
+        Ok(i + 1)
+    }
+
+    /// Split the region into two regions by the chain running between the points on its boundary
+    fn split(
+        &self,
+        region: Region<T>,
+        chain: Vec<NurbsCurve2D<T>>,
+    ) -> anyhow::Result<(Region<T>, Region<T>)> {
+        let (p, q) = chain_ends(&chain);
+        let mut spans = region.boundary.unwrap_or_else(|| self.domain_spans());
+        self.insert_vertex(&mut spans, &p)?;
+        let iq = self.insert_vertex(&mut spans, &q)?;
+        // the index of the span starting at `p` may be shifted by the insertion of `q`
+        let ip = self.insert_vertex(&mut spans, &p)?;
+
+        let n = spans.len();
+        let along = |from: usize, to: usize| {
+            let mut path = vec![];
+            let mut i = from;
+            while i != to {
+                path.push(spans[i].clone());
+                i = (i + 1) % n;
+            }
+            path
+        };
+
+        let mut first = along(ip, iq);
+        first.extend(
+            chain
+                .iter()
+                .rev()
+                .map(|c| Span::Curve(c.inverse()))
+                .collect::<Vec<_>>(),
+        );
+        let mut second = along(iq, ip);
+        second.extend(chain.into_iter().map(Span::Curve));
+
+        Ok((
+            Region {
+                boundary: Some(first),
+                holes: vec![],
+            },
+            Region {
+                boundary: Some(second),
+                holes: vec![],
+            },
+        ))
+    }
+
+    /// Split the face by the curves in the parameter space into trimmed surfaces
+    pub fn split_by_curves(
+        &self,
+        curves: Vec<NurbsCurve2D<T>>,
+    ) -> anyhow::Result<Vec<TrimmedSurface<T>>> {
+        let (chains, mut loops) = self.chain(curves)?;
+
+        let mut regions = vec![Region {
+            boundary: None,
+            holes: vec![],
+        }];
+
+        // split the regions by the chains crossing them, where the chains along the boundary split nothing
+        for chain in chains {
+            if self.is_along_boundary(&chain) {
+                continue;
+            }
+            let middle = &chain[chain.len() / 2];
+            let (start, end) = middle.knots_domain();
+            let inv = T::from_f64(0.5).unwrap();
+            let mid = middle.point_at((start + end) * inv);
+            let Some(index) = regions.iter().position(|r| self.contains(r, &mid)) else {
+                anyhow::bail!("A splitting curve does not lie in the face");
+            };
+            let region = regions.swap_remove(index);
+            let (a, b) = self.split(region, chain)?;
+            regions.push(a);
+            regions.push(b);
+        }
+
+        // cut the holes by the loops from the outermost ones
+        loops.sort_by_cached_key(|l| {
+            let spans: Vec<_> = l.iter().cloned().map(Span::Curve).collect();
+            std::cmp::Reverse(
+                (polygon_area(&polygon(&spans)) / self.tolerance)
+                    .to_u64()
+                    .unwrap_or(0),
+            )
+        });
+        for l in loops {
+            let (start, _) = chain_ends(&l);
+            let spans: Vec<_> = l.into_iter().map(Span::Curve).collect();
+            let Some(index) = regions.iter().position(|r| self.contains(r, &start)) else {
+                anyhow::bail!("A splitting loop does not lie in the face");
+            };
+            regions[index].holes.push(spans.clone());
+            regions.push(Region {
+                boundary: Some(spans),
+                holes: vec![],
+            });
+        }
+
+        let to_loop = |spans: Vec<Span<T>>| {
+            CompoundCurve2D::try_new_with_tolerance(
+                spans.into_iter().map(|s| s.into_curve()).collect(),
+                self.tolerance,
+            )
+        };
+        regions
+            .into_iter()
+            .filter(|r| {
+                r.boundary
+                    .as_ref()
+                    .map(|b| polygon_area(&polygon(b)) > self.tolerance * self.tolerance)
+                    .unwrap_or(true)
+            })
+            .map(|r| {
+                let exterior = r.boundary.map(to_loop).transpose()?;
+                let interiors = r
+                    .holes
+                    .into_iter()
+                    .map(to_loop)
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                TrimmedSurface::try_new(self.surface.clone(), exterior, interiors)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,6 @@
+pub mod boolean_operation;
+pub(crate) mod face_splitter;
+pub mod shell_boolean;
+
+pub use boolean_operation::*;
+pub use shell_boolean::*;
@@ -0,0 +1,197 @@
+use nalgebra::{Const, Point3};
+
+use crate::{
+    boolean::{face_splitter::FaceSplitter, BooleanOperation},
+    bounding_box::SurfaceBvh,
+    brep::point_in_shell::is_point_inside_shell,
+    curve::{NurbsCurve2D, NurbsCurve3D},
+    intersection::SurfaceIntersectionSolverOptions,
+    misc::{FloatingPoint, Invertible},
+    surface::{NurbsSurface3D, TrimmedSurface},
+};
+
+/// The result of a boolean operation between two closed shells
+/// The faces are trimmed surfaces bounded by the intersection edges between the shells.
+#[derive(Clone, Debug)]
+pub struct ShellBoolean<T: FloatingPoint> {
+    faces: Vec<TrimmedSurface<T>>,
+    edges: Vec<NurbsCurve3D<T>>,
+}
+
+impl<T: FloatingPoint> ShellBoolean<T> {
+    pub fn faces(&self) -> &[TrimmedSurface<T>] {
+        &self.faces
+    }
+
+    pub fn into_faces(self) -> Vec<TrimmedSurface<T>> {
+        self.faces
+    }
+
+    /// Get the intersection edges between the shells
+    pub fn edges(&self) -> &[NurbsCurve3D<T>] {
+        &self.edges
+    }
+}
+
+/// Check if the point is inside the closed shell by the parity of the ray crossings
+fn is_inside_shell<T: FloatingPoint>(
+    point: &Point3<T>,
+    shell: &[SurfaceBvh<T, Const<4>>],
+    tolerance: T,
+) -> anyhow::Result<bool> {
+    is_point_inside_shell(point, tolerance, |ray| {
+        let mut crossings = vec![];
+        for bvh in shell.iter() {
+            for hit in bvh.cast_ray(ray, None)? {
+                crossings.push((*hit.point(), hit.parameter()));
+            }
+        }
+        Ok(crossings)
+    })
+}
+
+/// Find a point in the interior of the trimmed surface
+fn interior_point<T: FloatingPoint>(face: &TrimmedSurface<T>) -> Option<Point3<T>> {
+    let tess = face.tessellate(None);
+    let uvs = tess.uvs();
+    let three = T::from_usize(3).unwrap();
+    tess.faces()
+        .iter()
+        .map(|f| {
+            let (a, b, c) = (uvs[f[0]], uvs[f[1]], uvs[f[2]]);
+            let area = (b - a).perp(&(c - a)).abs();
+            (area, (a + b + c) / three)
+        })
+        .max_by(|x, y| x.0.partial_cmp(&y.0).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(_, uv)| face.point_at(uv.x, uv.y))
+}
+
+/// Split the faces by the curves & keep the regions inside or outside the other shell, flipping them if necessary
+fn classify_faces<T: FloatingPoint>(
+    faces: &[NurbsSurface3D<T>],
+    curves: Vec<Vec<NurbsCurve2D<T>>>,
+    other: &[SurfaceBvh<T, Const<4>>],
+    keep_inside: bool,
+    flip: bool,
+    tolerance: T,
+) -> anyhow::Result<Vec<TrimmedSurface<T>>> {
+    let mut kept = vec![];
+    for (face, curves) in faces.iter().zip(curves) {
+        for region in FaceSplitter::new(face).split_by_curves(curves)? {
+            let Some(point) = interior_point(&region) else {
+                continue;
+            };
+            if is_inside_shell(&point, other, tolerance)? == keep_inside {
+                kept.push(if flip { region.inverse() } else { region });
+            }
+        }
+    }
+    Ok(kept)
+}
+
+/// Compute the boolean operation between two closed shells of NURBS surfaces
+/// The faces of each shell are split by the intersection curves with the other shell,
+/// and the split faces are kept or discarded by whether they are inside the other shell.
+/// The faces of the second shell kept by the difference are flipped to face outward of the result.
+/// * `a` - The faces of the first closed shell
+/// * `b` - The faces of the second closed shell
+/// * `operation` - The boolean operation
+/// * `options` - Hyperparameters for the surface intersection solver
+/// # Example
+/// ```
+/// use curvo::prelude::*;
+/// use nalgebra::{Point3, Vector3};
+///
+/// // Build the six faces of a cube
+/// let cube = |o: Point3<f64>| {
+///     let p = |x: f64, y: f64, z: f64| o + Vector3::new(x, y, z);
+///     let face = |a: Point3<f64>, b: Point3<f64>, d: Vector3<f64>| {
+///         NurbsSurface::extrude(&NurbsCurve3D::polyline(&[a, b]), &d)
+///     };
+///     vec![
+///         face(p(0., 0., 0.), p(1., 0., 0.), Vector3::y()),
+///         face(p(0., 0., 1.), p(1., 0., 1.), Vector3::y()),
+///         face(p(0., 0., 0.), p(0., 1., 0.), Vector3::z()),
+///         face(p(1., 0., 0.), p(1., 1., 0.), Vector3::z()),
+///         face(p(0., 0., 0.), p(0., 0., 1.), Vector3::x()),
+///         face(p(0., 1., 0.), p(0., 1., 1.), Vector3::x()),
+///     ]
+/// };
+/// let a = cube(Point3::origin());
+/// let b = cube(Point3::new(0.5, 0.5, 0.5));
+///
+/// let area = |result: &ShellBoolean<f64>| -> f64 {
+///     result.faces().iter().map(|face| {
+///         let tess = face.tessellate(None);
+///         let p = tess.points();
+///         tess.faces().iter().map(|f| (p[f[1]] - p[f[0]]).cross(&(p[f[2]] - p[f[0]])).norm() * 0.5).sum::<f64>()
+///     }).sum()
+/// };
+///
+/// let union = shell_boolean(&a, &b, BooleanOperation::Union, None).unwrap();
+/// assert_eq!(union.edges().len(), 6);
+/// assert!((area(&union) - 10.5).abs() < 1e-3);
+///
+/// let intersection = shell_boolean(&a, &b, BooleanOperation::Intersection, None).unwrap();
+/// assert!((area(&intersection) - 1.5).abs() < 1e-3);
+///
+/// let difference = shell_boolean(&a, &b, BooleanOperation::Difference, None).unwrap();
+/// assert!((area(&difference) - 6.).abs() < 1e-3);
+///
+/// // The cut by the sphere centered on the face of the cube crosses the seam & the poles of the sphere
+/// let sphere = vec![NurbsSurface::try_sphere(&Point3::new(1., 0.5, 0.5), &Vector3::z(), 0.4).unwrap()];
+/// let area = |result: &ShellBoolean<f64>| -> f64 {
+///     result.faces().iter().map(|face| face.area(1e-6).area()).sum()
+/// };
+/// let disk = std::f64::consts::PI * 0.4 * 0.4;
+///
+/// let difference = shell_boolean(&a, &sphere, BooleanOperation::Difference, None).unwrap();
+/// assert!((area(&difference) - (6. + disk)).abs() < 1e-4);
+///
+/// let intersection = shell_boolean(&a, &sphere, BooleanOperation::Intersection, None).unwrap();
+/// assert!((area(&intersection) - 3. * disk).abs() < 1e-4);
+/// ```
+pub fn shell_boolean<T: FloatingPoint>(
+    a: &[NurbsSurface3D<T>],
+    b: &[NurbsSurface3D<T>],
+    operation: BooleanOperation,
+    options: Option<SurfaceIntersectionSolverOptions<T>>,
+) -> anyhow::Result<ShellBoolean<T>> {
+    let options = options.unwrap_or_default();
+
+    // collect the intersection curves in the parameter spaces of the faces
+    let mut a_curves: Vec<Vec<NurbsCurve2D<T>>> = vec![vec![]; a.len()];
+    let mut b_curves: Vec<Vec<NurbsCurve2D<T>>> = vec![vec![]; b.len()];
+    let mut edges = vec![];
+    for (i, fa) in a.iter().enumerate() {
+        for (j, fb) in b.iter().enumerate() {
+            for it in fa.find_intersection(fb, Some(options.clone()))? {
+                a_curves[i].push(it.a().clone());
+                b_curves[j].push(it.b().clone());
+                edges.push(it.into_curve());
+            }
+        }
+    }
+
+    let a_bvh = a
+        .iter()
+        .map(SurfaceBvh::try_new)
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let b_bvh = b
+        .iter()
+        .map(SurfaceBvh::try_new)
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let tolerance = options.minimum_distance * T::from_usize(10).unwrap();
+    let (a_inside, b_inside, b_flip) = match operation {
+        BooleanOperation::Union => (false, false, false),
+        BooleanOperation::Intersection => (true, true, false),
+        BooleanOperation::Difference => (false, true, true),
+    };
+    let mut faces = classify_faces(a, a_curves, &b_bvh, a_inside, false, tolerance)?;
+    faces.extend(classify_faces(
+        b, b_curves, &a_bvh, b_inside, b_flip, tolerance,
+    )?);
+
+    Ok(ShellBoolean { faces, edges })
+}
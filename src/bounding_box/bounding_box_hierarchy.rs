@@ -0,0 +1,20 @@
+use nalgebra::{allocator::Allocator, DefaultAllocator, DimName};
+
+use crate::{bounding_box::BoundingBox, misc::FloatingPoint};
+
+/// A trait for geometry nodes that can be recursively divided into smaller nodes
+/// enclosed by bounding boxes in D space.
+/// Used as a sub-problem to find candidate pairs before running numerical solvers.
+pub trait BoundingBoxHierarchy<T: FloatingPoint, D: DimName>: Sized
+where
+    DefaultAllocator: Allocator<D>,
+{
+    /// Check if the node is dividable or not.
+    fn is_dividable(&self) -> bool;
+
+    /// Try to divide the node into two parts.
+    fn try_divide(&self) -> anyhow::Result<(Self, Self)>;
+
+    /// Get the bounding box enclosing the node.
+    fn bounding_box(&self) -> BoundingBox<T, D>;
+}
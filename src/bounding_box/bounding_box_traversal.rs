@@ -1,15 +1,18 @@
 use nalgebra::{allocator::Allocator, DefaultAllocator, DimName, DimNameDiff, DimNameSub, U1};
 
-use crate::{curve::nurbs_curve::NurbsCurve, misc::FloatingPoint, prelude::BoundingBoxTree};
+use crate::{
+    curve::nurbs_curve::NurbsCurve,
+    misc::FloatingPoint,
+    prelude::{BoundingBoxHierarchy, BoundingBoxTree},
+};
 
-pub struct BoundingBoxTraversal<'a, T: FloatingPoint, D: DimName>
-where
-    DefaultAllocator: Allocator<D>,
-{
-    pairs: Vec<(BoundingBoxTree<'a, T, D>, BoundingBoxTree<'a, T, D>)>,
+/// A struct containing pairs of bounding box hierarchy nodes whose bounding boxes intersect.
+pub struct BoundingBoxTraversal<A, B> {
+    pairs: Vec<(A, B)>,
 }
 
-impl<'a, T: FloatingPoint, D: DimName> BoundingBoxTraversal<'a, T, D>
+impl<'a, T: FloatingPoint, D: DimName>
+    BoundingBoxTraversal<BoundingBoxTree<'a, T, D>, BoundingBoxTree<'a, T, D>>
 where
     D: DimNameSub<U1>,
     DefaultAllocator: Allocator<D>,
@@ -24,8 +27,26 @@ where
     ) -> anyhow::Result<Self> {
         let ta = BoundingBoxTree::new(a, a_knot_tolerance);
         let tb = BoundingBoxTree::new(b, b_knot_tolerance);
+        Self::try_traverse_hierarchies(ta, tb)
+    }
+}
 
-        let mut trees = vec![(ta, tb)];
+impl<A, B> BoundingBoxTraversal<A, B>
+where
+    A: Clone,
+    B: Clone,
+{
+    /// Try to traverse a pair of bounding box hierarchies to find pairs of intersecting leaf nodes.
+    pub fn try_traverse_hierarchies<T: FloatingPoint, D: DimName>(
+        a: A,
+        b: B,
+    ) -> anyhow::Result<Self>
+    where
+        A: BoundingBoxHierarchy<T, D>,
+        B: BoundingBoxHierarchy<T, D>,
+        DefaultAllocator: Allocator<D>,
+    {
+        let mut trees = vec![(a, b)];
         let mut pairs = vec![];
 
         let tol = Some(T::zero());
@@ -66,24 +87,19 @@ where
         Ok(Self { pairs })
     }
 
-    #[allow(clippy::type_complexity)]
-    pub fn pairs(&self) -> &[(BoundingBoxTree<'a, T, D>, BoundingBoxTree<'a, T, D>)] {
+    pub fn pairs(&self) -> &[(A, B)] {
         &self.pairs
     }
 
-    pub fn pairs_iter(
-        &self,
-    ) -> impl Iterator<Item = &(BoundingBoxTree<'a, T, D>, BoundingBoxTree<'a, T, D>)> {
+    pub fn pairs_iter(&self) -> impl Iterator<Item = &(A, B)> {
         self.pairs.iter()
     }
 
-    pub fn into_pairs(self) -> Vec<(BoundingBoxTree<'a, T, D>, BoundingBoxTree<'a, T, D>)> {
+    pub fn into_pairs(self) -> Vec<(A, B)> {
         self.pairs
     }
 
-    pub fn into_pairs_iter(
-        self,
-    ) -> impl Iterator<Item = (BoundingBoxTree<'a, T, D>, BoundingBoxTree<'a, T, D>)> {
+    pub fn into_pairs_iter(self) -> impl Iterator<Item = (A, B)> {
         self.pairs.into_iter()
     }
 }
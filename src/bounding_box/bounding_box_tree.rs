@@ -3,7 +3,11 @@ use std::borrow::Cow;
 use nalgebra::{allocator::Allocator, DefaultAllocator, DimName, DimNameDiff, DimNameSub, U1};
 use rand::Rng;
 
-use crate::{bounding_box::BoundingBox, curve::nurbs_curve::NurbsCurve, misc::FloatingPoint};
+use crate::{
+    bounding_box::{BoundingBox, BoundingBoxHierarchy},
+    curve::nurbs_curve::NurbsCurve,
+    misc::FloatingPoint,
+};
 
 /// A struct representing a bounding box tree in D space.
 #[derive(Clone)]
@@ -76,3 +80,23 @@ where
         self.curve.as_ref().into()
     }
 }
+
+impl<'a, T: FloatingPoint, D: DimName> BoundingBoxHierarchy<T, DimNameDiff<D, U1>>
+    for BoundingBoxTree<'a, T, D>
+where
+    D: DimNameSub<U1>,
+    DefaultAllocator: Allocator<D>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    fn is_dividable(&self) -> bool {
+        BoundingBoxTree::is_dividable(self)
+    }
+
+    fn try_divide(&self) -> anyhow::Result<(Self, Self)> {
+        BoundingBoxTree::try_divide(self)
+    }
+
+    fn bounding_box(&self) -> BoundingBox<T, DimNameDiff<D, U1>> {
+        BoundingBoxTree::bounding_box(self)
+    }
+}
@@ -0,0 +1,205 @@
+use nalgebra::{allocator::Allocator, DefaultAllocator, DimName, OPoint};
+
+use crate::{bounding_box::BoundingBox, misc::FloatingPoint};
+
+/// A node of the bounding volume hierarchy
+#[derive(Clone, Debug)]
+enum BoundingVolumeNode<T: FloatingPoint, D: DimName>
+where
+    DefaultAllocator: Allocator<D>,
+{
+    Leaf {
+        bounding_box: BoundingBox<T, D>,
+        index: usize,
+    },
+    Branch {
+        bounding_box: BoundingBox<T, D>,
+        left: usize,
+        right: usize,
+    },
+}
+
+impl<T: FloatingPoint, D: DimName> BoundingVolumeNode<T, D>
+where
+    DefaultAllocator: Allocator<D>,
+{
+    fn bounding_box(&self) -> &BoundingBox<T, D> {
+        match self {
+            Self::Leaf { bounding_box, .. } => bounding_box,
+            Self::Branch { bounding_box, .. } => bounding_box,
+        }
+    }
+}
+
+/// A bounding volume hierarchy over a set of bounding boxes
+/// Each leaf node refers to the index of the bounding box given at the construction
+#[derive(Clone, Debug)]
+pub struct BoundingVolumeHierarchy<T: FloatingPoint, D: DimName>
+where
+    DefaultAllocator: Allocator<D>,
+{
+    nodes: Vec<BoundingVolumeNode<T, D>>,
+}
+
+impl<T: FloatingPoint, D: DimName> BoundingVolumeHierarchy<T, D>
+where
+    DefaultAllocator: Allocator<D>,
+{
+    /// Build a bounding volume hierarchy by splitting the set of bounding boxes at the median along the longest axis
+    /// # Example
+    /// ```
+    /// use nalgebra::{Point2, Vector2};
+    /// use curvo::prelude::*;
+    ///
+    /// let boxes: Vec<_> = (0..8).map(|i| {
+    ///     let min = Vector2::new(i as f64, 0.);
+    ///     BoundingBox::new(min, min + Vector2::new(0.5, 0.5))
+    /// }).collect();
+    /// let bvh = BoundingVolumeHierarchy::new(&boxes);
+    ///
+    /// // The leaves intersecting with the box
+    /// let query = BoundingBox::new(Vector2::new(2.2, 0.), Vector2::new(3.2, 1.));
+    /// let mut found = bvh.find(|bb| bb.intersects(&query, None));
+    /// found.sort();
+    /// assert_eq!(found, vec![2, 3]);
+    /// ```
+    pub fn new(boxes: &[BoundingBox<T, D>]) -> Self {
+        let mut bvh = Self { nodes: vec![] };
+        if !boxes.is_empty() {
+            let mut indices: Vec<_> = (0..boxes.len()).collect();
+            bvh.build(boxes, &mut indices);
+        }
+        bvh
+    }
+
+    /// Build the nodes recursively and return the index of the created node
+    fn build(&mut self, boxes: &[BoundingBox<T, D>], indices: &mut [usize]) -> usize {
+        if indices.len() == 1 {
+            self.nodes.push(BoundingVolumeNode::Leaf {
+                bounding_box: boxes[indices[0]].clone(),
+                index: indices[0],
+            });
+            return self.nodes.len() - 1;
+        }
+
+        let bounding_box = indices
+            .iter()
+            .skip(1)
+            .fold(boxes[indices[0]].clone(), |acc, i| acc.union(&boxes[*i]));
+
+        // split at the median along the longest axis
+        let size = bounding_box.size();
+        let axis = (0..D::dim())
+            .max_by(|a, b| {
+                size[*a]
+                    .partial_cmp(&size[*b])
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .unwrap_or(0);
+        indices.sort_by(|a, b| {
+            boxes[*a].center()[axis]
+                .partial_cmp(&boxes[*b].center()[axis])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        // reserve the node to be filled after the children are built
+        let node = self.nodes.len();
+        self.nodes.push(BoundingVolumeNode::Leaf {
+            bounding_box: bounding_box.clone(),
+            index: 0,
+        });
+
+        let mid = indices.len() / 2;
+        let (l, r) = indices.split_at_mut(mid);
+        let left = self.build(boxes, l);
+        let right = self.build(boxes, r);
+        self.nodes[node] = BoundingVolumeNode::Branch {
+            bounding_box,
+            left,
+            right,
+        };
+        node
+    }
+
+    /// Check if the hierarchy has no leaves
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Get the bounding box enclosing all the leaves
+    pub fn bounding_box(&self) -> Option<&BoundingBox<T, D>> {
+        self.nodes.first().map(|n| n.bounding_box())
+    }
+
+    /// Find the indices of the leaves whose bounding boxes (and the ancestors' ones) satisfy the predicate
+    pub fn find<F>(&self, predicate: F) -> Vec<usize>
+    where
+        F: Fn(&BoundingBox<T, D>) -> bool,
+    {
+        let mut found = vec![];
+        if self.nodes.is_empty() {
+            return found;
+        }
+
+        let mut stack = vec![0];
+        while let Some(i) = stack.pop() {
+            let node = &self.nodes[i];
+            if !predicate(node.bounding_box()) {
+                continue;
+            }
+            match node {
+                BoundingVolumeNode::Leaf { index, .. } => found.push(*index),
+                BoundingVolumeNode::Branch { left, right, .. } => {
+                    stack.push(*right);
+                    stack.push(*left);
+                }
+            }
+        }
+        found
+    }
+
+    /// Find the nearest leaf to the point by branch & bound
+    /// `distance` computes the exact distance from the point to the leaf, and returns the result with the distance.
+    /// The leaves whose bounding boxes are farther than the best distance found so far are pruned.
+    pub fn nearest<R, F>(&self, point: &OPoint<T, D>, distance: F) -> Option<(R, T)>
+    where
+        F: Fn(usize) -> Option<(R, T)>,
+    {
+        if self.nodes.is_empty() {
+            return None;
+        }
+
+        let mut best: Option<(R, T)> = None;
+        let mut stack = vec![(0, self.nodes[0].bounding_box().distance(point))];
+        while let Some((i, bound)) = stack.pop() {
+            if let Some((_, d)) = best.as_ref() {
+                if bound > *d {
+                    continue;
+                }
+            }
+
+            match &self.nodes[i] {
+                BoundingVolumeNode::Leaf { index, .. } => {
+                    if let Some((r, d)) = distance(*index) {
+                        if best.as_ref().map(|(_, min)| d < *min).unwrap_or(true) {
+                            best = Some((r, d));
+                        }
+                    }
+                }
+                BoundingVolumeNode::Branch { left, right, .. } => {
+                    let dl = self.nodes[*left].bounding_box().distance(point);
+                    let dr = self.nodes[*right].bounding_box().distance(point);
+                    // visit the nearer child first
+                    if dl < dr {
+                        stack.push((*right, dr));
+                        stack.push((*left, dl));
+                    } else {
+                        stack.push((*left, dl));
+                        stack.push((*right, dr));
+                    }
+                }
+            }
+        }
+        best
+    }
+}
@@ -0,0 +1,120 @@
+use argmin::core::ArgminFloat;
+use nalgebra::{
+    allocator::Allocator, DefaultAllocator, DimName, DimNameDiff, DimNameSub, OPoint, U1,
+};
+
+use crate::{
+    bounding_box::{BoundingBox, BoundingVolumeHierarchy},
+    curve::NurbsCurve,
+    misc::FloatingPoint,
+};
+
+/// A bounding volume hierarchy over the Bézier segments of a curve
+/// to answer many closest point queries efficiently
+#[derive(Clone, Debug)]
+pub struct CurveBvh<T: FloatingPoint, D: DimName>
+where
+    D: DimNameSub<U1>,
+    DefaultAllocator: Allocator<D>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    segments: Vec<NurbsCurve<T, D>>,
+    hierarchy: BoundingVolumeHierarchy<T, DimNameDiff<D, U1>>,
+}
+
+impl<T: FloatingPoint, D: DimName> CurveBvh<T, D>
+where
+    D: DimNameSub<U1>,
+    DefaultAllocator: Allocator<D>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    /// Build a bounding volume hierarchy over the Bézier segments of the curve
+    pub fn try_new(curve: &NurbsCurve<T, D>) -> anyhow::Result<Self> {
+        let segments = curve.try_decompose_bezier_segments()?;
+        let boxes: Vec<BoundingBox<T, DimNameDiff<D, U1>>> =
+            segments.iter().map(|s| s.into()).collect();
+        Ok(Self {
+            segments,
+            hierarchy: BoundingVolumeHierarchy::new(&boxes),
+        })
+    }
+
+    pub fn segments(&self) -> &[NurbsCurve<T, D>] {
+        &self.segments
+    }
+
+    pub fn hierarchy(&self) -> &BoundingVolumeHierarchy<T, DimNameDiff<D, U1>> {
+        &self.hierarchy
+    }
+
+    /// Find the closest segment and its parameter to the given point
+    fn find_closest(&self, point: &OPoint<T, DimNameDiff<D, U1>>) -> anyhow::Result<(usize, T)>
+    where
+        T: ArgminFloat,
+    {
+        self.hierarchy
+            .nearest(point, |i| {
+                let segment = &self.segments[i];
+                segment
+                    .find_closest_parameter(point)
+                    .ok()
+                    .map(|t| ((i, t), (segment.point_at(t) - point).norm()))
+            })
+            .map(|(r, _)| r)
+            .ok_or(anyhow::anyhow!("No closest parameter found"))
+    }
+
+    /// Find the closest parameter on the curve to the given point
+    pub fn find_closest_parameter(&self, point: &OPoint<T, DimNameDiff<D, U1>>) -> anyhow::Result<T>
+    where
+        T: ArgminFloat,
+    {
+        self.find_closest(point).map(|(_, t)| t)
+    }
+
+    /// Find the closest parameters on the curve to the given points
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let circle = NurbsCurve3D::try_circle(&Point3::origin(), &Vector3::x(), &Vector3::y(), 1.).unwrap();
+    /// let bvh = CurveBvh::try_new(&circle).unwrap();
+    ///
+    /// let points: Vec<_> = (0..32).map(|i| {
+    ///     let t = i as f64 / 32. * std::f64::consts::TAU;
+    ///     Point3::new(t.cos() * 2., t.sin() * 2., 0.5)
+    /// }).collect();
+    /// let closest = bvh.closest_points(&points).unwrap();
+    /// closest.iter().zip(points.iter()).for_each(|((_, p), q)| {
+    ///     let expected = Point3::new(q.x * 0.5, q.y * 0.5, 0.);
+    ///     assert_relative_eq!(p, &expected, epsilon = 1e-6);
+    /// });
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn closest_points(
+        &self,
+        points: &[OPoint<T, DimNameDiff<D, U1>>],
+    ) -> anyhow::Result<Vec<(T, OPoint<T, DimNameDiff<D, U1>>)>>
+    where
+        T: ArgminFloat,
+    {
+        points
+            .iter()
+            .map(|p| {
+                let (i, t) = self.find_closest(p)?;
+                Ok((t, self.segments[i].point_at(t)))
+            })
+            .collect()
+    }
+
+    /// Compute the distance from the given point to the curve
+    pub fn distance(&self, point: &OPoint<T, DimNameDiff<D, U1>>) -> anyhow::Result<T>
+    where
+        T: ArgminFloat,
+    {
+        let (i, t) = self.find_closest(point)?;
+        Ok((self.segments[i].point_at(t) - point).norm())
+    }
+}
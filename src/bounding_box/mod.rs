@@ -1,15 +1,30 @@
+pub mod bounding_box_hierarchy;
 pub mod bounding_box_traversal;
 pub mod bounding_box_tree;
+pub mod bounding_volume_hierarchy;
+pub mod curve_bvh;
+pub mod surface_bounding_box_tree;
+pub mod surface_bvh;
 
+pub use bounding_box_hierarchy::*;
 pub use bounding_box_traversal::*;
 pub use bounding_box_tree::*;
+pub use bounding_volume_hierarchy::*;
+pub use curve_bvh::*;
+pub use surface_bounding_box_tree::*;
+pub use surface_bvh::*;
 
 use nalgebra::{
-    allocator::Allocator, DefaultAllocator, DimName, DimNameDiff, DimNameSub, OPoint, OVector, U1,
+    allocator::Allocator, DefaultAllocator, DimName, DimNameDiff, DimNameSub, OPoint, OVector,
+    RealField, U1,
 };
 use simba::scalar::SupersetOf;
 
-use crate::{curve::nurbs_curve::NurbsCurve, misc::FloatingPoint};
+use crate::{
+    curve::nurbs_curve::NurbsCurve,
+    misc::{FloatingPoint, Ray},
+    surface::NurbsSurface,
+};
 
 /// A struct representing a bounding box in D space.
 #[derive(Clone, Debug)]
@@ -116,6 +131,84 @@ where
         true
     }
 
+    /// Find the interval of the ray parameter inside the bounding box by the slab method.
+    /// Returns `None` if the ray does not hit the bounding box.
+    ///
+    /// # Examples
+    /// ```
+    /// use nalgebra::{Point3, Vector3};
+    /// use curvo::prelude::{BoundingBox, Ray};
+    ///
+    /// let bb = BoundingBox::new(Vector3::from_element(0.), Vector3::from_element(1.));
+    /// let ray = Ray::new(Point3::new(0.5, 0.5, -1.), Vector3::z());
+    /// assert_eq!(bb.intersects_ray(&ray), Some((1., 2.)));
+    ///
+    /// // The bounding box behind the ray is not hit
+    /// let ray = Ray::new(Point3::new(0.5, 0.5, -1.), -Vector3::z());
+    /// assert_eq!(bb.intersects_ray(&ray), None);
+    /// ```
+    pub fn intersects_ray(&self, ray: &Ray<T, D>) -> Option<(T, T)> {
+        let mut t0 = T::zero();
+        let mut t1 = <T as RealField>::max_value().unwrap();
+        let (origin, direction) = (ray.origin(), ray.direction());
+        for i in 0..D::dim() {
+            if direction[i].abs() < T::default_epsilon() {
+                // The ray is parallel to the slab
+                if origin[i] < self.min[i] || self.max[i] < origin[i] {
+                    return None;
+                }
+                continue;
+            }
+            let inv = T::one() / direction[i];
+            let a = (self.min[i] - origin[i]) * inv;
+            let b = (self.max[i] - origin[i]) * inv;
+            t0 = t0.max(a.min(b));
+            t1 = t1.min(a.max(b));
+            if t0 > t1 {
+                return None;
+            }
+        }
+        Some((t0, t1))
+    }
+
+    /// Compute the distance from the point to the bounding box.
+    /// Returns zero if the point is inside the bounding box.
+    ///
+    /// # Examples
+    /// ```
+    /// use nalgebra::{Point2, Vector2};
+    /// use curvo::prelude::BoundingBox;
+    ///
+    /// let bb = BoundingBox::new(Vector2::from_element(0.), Vector2::from_element(1.));
+    /// assert_eq!(bb.distance(&Point2::new(0.5, 0.5)), 0.);
+    /// assert_eq!(bb.distance(&Point2::new(2., 0.5)), 1.);
+    /// ```
+    pub fn distance(&self, point: &OPoint<T, D>) -> T {
+        let mut sum = T::zero();
+        for i in 0..D::dim() {
+            let d = if point[i] < self.min[i] {
+                self.min[i] - point[i]
+            } else if point[i] > self.max[i] {
+                point[i] - self.max[i]
+            } else {
+                T::zero()
+            };
+            sum += d * d;
+        }
+        sum.sqrt()
+    }
+
+    /// Compute the union of two bounding boxes.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut min = self.min.clone();
+        let mut max = self.max.clone();
+        for i in 0..D::dim() {
+            min[i] = min[i].min(other.min[i]);
+            max[i] = max[i].max(other.max[i]);
+        }
+        Self { min, max }
+    }
+
     /// Cast the bounding box to a curve with another floating point type
     pub fn cast<F: FloatingPoint + SupersetOf<T>>(&self) -> BoundingBox<F, D>
     where
@@ -182,3 +275,16 @@ where
         Self::new_with_points(pts)
     }
 }
+
+impl<'a, T: FloatingPoint, D: DimName> From<&'a NurbsSurface<T, D>>
+    for BoundingBox<T, DimNameDiff<D, U1>>
+where
+    DefaultAllocator: Allocator<D>,
+    D: DimNameSub<U1>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    fn from(value: &'a NurbsSurface<T, D>) -> Self {
+        let pts = value.dehomogenized_control_points();
+        Self::new_with_points(pts.into_iter().flatten())
+    }
+}
@@ -0,0 +1,118 @@
+use std::borrow::Cow;
+
+use nalgebra::{allocator::Allocator, DefaultAllocator, DimName, DimNameDiff, DimNameSub, U1};
+use rand::Rng;
+
+use crate::{
+    bounding_box::{BoundingBox, BoundingBoxHierarchy},
+    misc::FloatingPoint,
+    surface::NurbsSurface,
+};
+
+/// A struct representing a bounding box tree of a surface in D space.
+#[derive(Clone)]
+pub struct SurfaceBoundingBoxTree<'a, T: FloatingPoint, D: DimName>
+where
+    DefaultAllocator: Allocator<D>,
+{
+    surface: Cow<'a, NurbsSurface<T, D>>,
+    /// knot tolerances in u & v directions
+    tolerance: (T, T),
+}
+
+impl<'a, T: FloatingPoint, D: DimName> SurfaceBoundingBoxTree<'a, T, D>
+where
+    D: DimNameSub<U1>,
+    DefaultAllocator: Allocator<D>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    /// Create a new bounding box tree from a surface.
+    /// `tolerance` is the knot tolerances in u & v directions to stop the division.
+    pub fn new(surface: &'a NurbsSurface<T, D>, tolerance: Option<(T, T)>) -> Self {
+        let tol = tolerance.unwrap_or_else(|| {
+            let div = T::from_usize(16).unwrap();
+            let (u0, u1) = surface.u_knots_domain();
+            let (v0, v1) = surface.v_knots_domain();
+            ((u1 - u0) / div, (v1 - v0) / div)
+        });
+        Self {
+            surface: Cow::Borrowed(surface),
+            tolerance: tol,
+        }
+    }
+
+    pub fn surface(&self) -> &NurbsSurface<T, D> {
+        self.surface.as_ref()
+    }
+
+    pub fn surface_owned(self) -> NurbsSurface<T, D> {
+        self.surface.into_owned()
+    }
+
+    /// Get the intervals of the knot domain in u & v directions.
+    fn intervals(&self) -> (T, T) {
+        let (u0, u1) = self.surface.u_knots_domain();
+        let (v0, v1) = self.surface.v_knots_domain();
+        (u1 - u0, v1 - v0)
+    }
+
+    /// Check if the surface is dividable or not.
+    pub fn is_dividable(&self) -> bool {
+        let (u, v) = self.intervals();
+        u > self.tolerance.0 || v > self.tolerance.1
+    }
+
+    /// Try to divide the surface into two parts.
+    /// The surface is divided in the direction where the knot domain is larger relative to the tolerance.
+    pub fn try_divide(&self) -> anyhow::Result<(Self, Self)> {
+        let (u, v) = self.intervals();
+        let v_direction = v / self.tolerance.1 > u / self.tolerance.0;
+        let (min, max) = if v_direction {
+            self.surface.v_knots_domain()
+        } else {
+            self.surface.u_knots_domain()
+        };
+        let interval = max - min;
+        let mid = (min + max) / T::from_usize(2).unwrap();
+
+        let mut rng = rand::thread_rng();
+        let r = interval * T::from_f64(1e-1 * rng.gen::<f64>()).unwrap();
+
+        let (head, tail) = self.surface.try_split(mid + r, v_direction)?;
+        Ok((
+            Self {
+                surface: Cow::Owned(head),
+                tolerance: self.tolerance,
+            },
+            Self {
+                surface: Cow::Owned(tail),
+                tolerance: self.tolerance,
+            },
+        ))
+    }
+
+    /// Get the bounding box of the surface.
+    pub fn bounding_box(&self) -> BoundingBox<T, DimNameDiff<D, U1>> {
+        self.surface.as_ref().into()
+    }
+}
+
+impl<'a, T: FloatingPoint, D: DimName> BoundingBoxHierarchy<T, DimNameDiff<D, U1>>
+    for SurfaceBoundingBoxTree<'a, T, D>
+where
+    D: DimNameSub<U1>,
+    DefaultAllocator: Allocator<D>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    fn is_dividable(&self) -> bool {
+        SurfaceBoundingBoxTree::is_dividable(self)
+    }
+
+    fn try_divide(&self) -> anyhow::Result<(Self, Self)> {
+        SurfaceBoundingBoxTree::try_divide(self)
+    }
+
+    fn bounding_box(&self) -> BoundingBox<T, DimNameDiff<D, U1>> {
+        SurfaceBoundingBoxTree::bounding_box(self)
+    }
+}
@@ -0,0 +1,250 @@
+use argmin::core::ArgminFloat;
+use nalgebra::{
+    allocator::Allocator, Const, DefaultAllocator, DimName, DimNameDiff, DimNameSub, Matrix3,
+    OPoint, Vector3, U1,
+};
+
+use crate::{
+    bounding_box::{BoundingBox, BoundingVolumeHierarchy},
+    intersection::{CurveSurfaceIntersectionSolverOptions, RaySurfaceIntersection},
+    misc::{FloatingPoint, Ray},
+    surface::NurbsSurface,
+};
+
+/// A bounding volume hierarchy over the Bézier patches of a surface
+/// to answer many closest point queries efficiently
+#[derive(Clone, Debug)]
+pub struct SurfaceBvh<T: FloatingPoint, D: DimName>
+where
+    D: DimNameSub<U1>,
+    DefaultAllocator: Allocator<D>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    patches: Vec<NurbsSurface<T, D>>,
+    hierarchy: BoundingVolumeHierarchy<T, DimNameDiff<D, U1>>,
+}
+
+impl<T: FloatingPoint, D: DimName> SurfaceBvh<T, D>
+where
+    D: DimNameSub<U1>,
+    DefaultAllocator: Allocator<D>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    /// Build a bounding volume hierarchy over the Bézier patches of the surface
+    /// The patches keep the parameters of the original surface
+    pub fn try_new(surface: &NurbsSurface<T, D>) -> anyhow::Result<Self> {
+        let patches = surface.try_decompose_bezier_patches()?;
+        let boxes: Vec<BoundingBox<T, DimNameDiff<D, U1>>> =
+            patches.iter().map(|s| s.into()).collect();
+        Ok(Self {
+            patches,
+            hierarchy: BoundingVolumeHierarchy::new(&boxes),
+        })
+    }
+
+    pub fn patches(&self) -> &[NurbsSurface<T, D>] {
+        &self.patches
+    }
+
+    pub fn hierarchy(&self) -> &BoundingVolumeHierarchy<T, DimNameDiff<D, U1>> {
+        &self.hierarchy
+    }
+
+    /// Find the closest patch and its (u, v) parameter to the given point
+    fn find_closest(&self, point: &OPoint<T, DimNameDiff<D, U1>>) -> anyhow::Result<(usize, (T, T))>
+    where
+        T: ArgminFloat,
+    {
+        self.hierarchy
+            .nearest(point, |i| {
+                let patch = &self.patches[i];
+                patch
+                    .find_closest_parameter(point)
+                    .ok()
+                    .map(|(u, v)| ((i, (u, v)), (patch.point_at(u, v) - point).norm()))
+            })
+            .map(|(r, _)| r)
+            .ok_or(anyhow::anyhow!("No closest parameter found"))
+    }
+
+    /// Find the closest (u, v) parameter on the surface to the given point
+    pub fn find_closest_parameter(
+        &self,
+        point: &OPoint<T, DimNameDiff<D, U1>>,
+    ) -> anyhow::Result<(T, T)>
+    where
+        T: ArgminFloat,
+    {
+        self.find_closest(point).map(|(_, uv)| uv)
+    }
+
+    /// Find the closest parameters on the surface to the given points
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let circle = NurbsCurve3D::try_circle(&Point3::origin(), &Vector3::x(), &Vector3::y(), 1.).unwrap();
+    /// let cylinder = NurbsSurface::extrude(&circle, &(Vector3::z() * 2.));
+    /// let bvh = SurfaceBvh::try_new(&cylinder).unwrap();
+    ///
+    /// let points: Vec<_> = (0..32).map(|i| {
+    ///     let t = i as f64 / 32. * std::f64::consts::TAU;
+    ///     Point3::new(t.cos() * 2., t.sin() * 2., 1.5)
+    /// }).collect();
+    /// let closest = bvh.closest_points(&points).unwrap();
+    /// closest.iter().zip(points.iter()).for_each(|((_, p), q)| {
+    ///     let expected = Point3::new(q.x * 0.5, q.y * 0.5, 1.5);
+    ///     assert_relative_eq!(p, &expected, epsilon = 1e-6);
+    /// });
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn closest_points(
+        &self,
+        points: &[OPoint<T, DimNameDiff<D, U1>>],
+    ) -> anyhow::Result<Vec<((T, T), OPoint<T, DimNameDiff<D, U1>>)>>
+    where
+        T: ArgminFloat,
+    {
+        points
+            .iter()
+            .map(|p| {
+                let (i, (u, v)) = self.find_closest(p)?;
+                Ok(((u, v), self.patches[i].point_at(u, v)))
+            })
+            .collect()
+    }
+
+    /// Compute the distance from the given point to the surface
+    pub fn distance(&self, point: &OPoint<T, DimNameDiff<D, U1>>) -> anyhow::Result<T>
+    where
+        T: ArgminFloat,
+    {
+        let (i, (u, v)) = self.find_closest(point)?;
+        Ok((self.patches[i].point_at(u, v) - point).norm())
+    }
+}
+
+impl<T: FloatingPoint> SurfaceBvh<T, Const<4>> {
+    /// Cast a ray to the surface and find the intersections sorted by the parameter along the ray
+    /// The patches whose bounding boxes are hit by the ray are subdivided and the intersections are solved by Newton's method.
+    /// * `ray` - The ray to cast
+    /// * `options` - Hyperparameters for the intersection solver
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let circle = NurbsCurve3D::try_circle(&Point3::origin(), &Vector3::x(), &Vector3::y(), 1.).unwrap();
+    /// let cylinder = NurbsSurface::extrude(&circle, &(Vector3::z() * 2.));
+    /// let bvh = SurfaceBvh::try_new(&cylinder).unwrap();
+    ///
+    /// // The ray passes through the cylinder
+    /// let ray = Ray::new(Point3::new(-3., 0., 1.), Vector3::x());
+    /// let hits = bvh.cast_ray(&ray, None).unwrap();
+    /// assert_eq!(hits.len(), 2);
+    /// assert_relative_eq!(hits[0].point(), &Point3::new(-1., 0., 1.), epsilon = 1e-6);
+    /// assert_relative_eq!(hits[0].parameter(), 2., epsilon = 1e-6);
+    /// assert_relative_eq!(hits[1].point(), &Point3::new(1., 0., 1.), epsilon = 1e-6);
+    /// assert_relative_eq!(f64::abs(hits[1].normal().x), 1., epsilon = 1e-6);
+    ///
+    /// // The ray misses the cylinder
+    /// let ray = Ray::new(Point3::new(-3., 0., 3.), Vector3::x());
+    /// assert!(bvh.cast_ray(&ray, None).unwrap().is_empty());
+    /// ```
+    pub fn cast_ray(
+        &self,
+        ray: &Ray<T, Const<3>>,
+        options: Option<CurveSurfaceIntersectionSolverOptions<T>>,
+    ) -> anyhow::Result<Vec<RaySurfaceIntersection<T>>> {
+        let options = options.unwrap_or_default();
+        let Some(bb) = self.hierarchy.bounding_box() else {
+            return Ok(vec![]);
+        };
+        let div = T::from_usize(options.knot_domain_division.max(1)).unwrap();
+        let threshold = bb.size().norm() / div;
+        let half = T::from_f64(0.5).unwrap();
+
+        // subdivide the patches hit by the ray into the small ones
+        let mut leaves = vec![];
+        let mut stack: Vec<_> = self
+            .hierarchy
+            .find(|bb| bb.intersects_ray(ray).is_some())
+            .into_iter()
+            .map(|i| (self.patches[i].clone(), 0))
+            .collect();
+        while let Some((patch, depth)) = stack.pop() {
+            let bb: BoundingBox<T, Const<3>> = (&patch).into();
+            let Some((t0, t1)) = bb.intersects_ray(ray) else {
+                continue;
+            };
+            if bb.size().norm() < threshold || depth >= 16 {
+                leaves.push((patch, (t0 + t1) * half));
+                continue;
+            }
+            let (u0, u1) = patch.u_knots_domain();
+            let (v0, v1) = patch.v_knots_domain();
+            let (l, r) = patch.try_split((u0 + u1) * half, false)?;
+            for p in [l, r] {
+                let (a, b) = p.try_split((v0 + v1) * half, true)?;
+                stack.push((a, depth + 1));
+                stack.push((b, depth + 1));
+            }
+        }
+
+        // solve S(u, v) - (O + tD) = 0 by Newton's method from the center of each leaf
+        let direction = ray.direction();
+        let mut intersections: Vec<RaySurfaceIntersection<T>> = leaves
+            .into_iter()
+            .filter_map(|(patch, t)| {
+                let (u0, u1) = patch.u_knots_domain();
+                let (v0, v1) = patch.v_knots_domain();
+                let mut x = Vector3::new((u0 + u1) * half, (v0 + v1) * half, t);
+                for _ in 0..options.max_iters {
+                    let s = patch.rational_derivatives(x.x, x.y, 1);
+                    let f = s[0][0] - ray.point_at(x.z).coords;
+                    if f.norm() < options.minimum_distance {
+                        // reject the solutions outside the patch or behind the ray
+                        if x.x < u0 || u1 < x.x || x.y < v0 || v1 < x.y || x.z < T::zero() {
+                            return None;
+                        }
+                        let normal = s[0][1].cross(&s[1][0]).normalize();
+                        return Some(RaySurfaceIntersection::new(
+                            ray.point_at(x.z),
+                            x.z,
+                            (x.x, x.y),
+                            normal,
+                        ));
+                    }
+                    let jacobian = Matrix3::from_columns(&[s[1][0], s[0][1], -direction]);
+                    let delta = jacobian.lu().solve(&f)?;
+                    x -= delta;
+                    // keep the parameter in the proximity of the leaf
+                    x.x = nalgebra::clamp(x.x, u0 - (u1 - u0), u1 + (u1 - u0));
+                    x.y = nalgebra::clamp(x.y, v0 - (v1 - v0), v1 + (v1 - v0));
+                }
+                None
+            })
+            .collect();
+
+        intersections.sort_by(|a, b| {
+            a.parameter()
+                .partial_cmp(&b.parameter())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        // merge intersections found in the adjacent leaves
+        let eps = options.minimum_distance * T::from_f64(5.).unwrap();
+        let mut merged: Vec<RaySurfaceIntersection<T>> = vec![];
+        for it in intersections.into_iter() {
+            let duplicated = merged.iter().any(|m| (m.point() - it.point()).norm() < eps);
+            if !duplicated {
+                merged.push(it);
+            }
+        }
+
+        Ok(merged)
+    }
+}
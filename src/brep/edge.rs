@@ -0,0 +1,42 @@
+use crate::{curve::NurbsCurve3D, misc::FloatingPoint};
+
+/// An edge of a B-rep shell shared by the faces
+/// The edge runs along the curve from the start vertex to the end vertex.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Edge<T: FloatingPoint> {
+    curve: NurbsCurve3D<T>,
+    /// The index of the start vertex in the shell
+    start: usize,
+    /// The index of the end vertex in the shell
+    end: usize,
+    /// Whether the edge collapses to a point (e.g., at the pole of a sphere)
+    degenerate: bool,
+}
+
+impl<T: FloatingPoint> Edge<T> {
+    pub fn new(curve: NurbsCurve3D<T>, start: usize, end: usize, degenerate: bool) -> Self {
+        Self {
+            curve,
+            start,
+            end,
+            degenerate,
+        }
+    }
+
+    pub fn curve(&self) -> &NurbsCurve3D<T> {
+        &self.curve
+    }
+
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    pub fn is_degenerate(&self) -> bool {
+        self.degenerate
+    }
+}
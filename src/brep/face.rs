@@ -0,0 +1,53 @@
+use crate::{
+    brep::Loop,
+    misc::FloatingPoint,
+    surface::{NurbsSurface3D, TrimmedSurface},
+};
+
+/// A face of a B-rep shell bounded by an outer loop & inner loops (holes)
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Face<T: FloatingPoint> {
+    surface: NurbsSurface3D<T>,
+    outer: Loop<T>,
+    inners: Vec<Loop<T>>,
+}
+
+impl<T: FloatingPoint> Face<T> {
+    pub fn new(surface: NurbsSurface3D<T>, outer: Loop<T>, inners: Vec<Loop<T>>) -> Self {
+        Self {
+            surface,
+            outer,
+            inners,
+        }
+    }
+
+    pub fn surface(&self) -> &NurbsSurface3D<T> {
+        &self.surface
+    }
+
+    pub fn outer(&self) -> &Loop<T> {
+        &self.outer
+    }
+
+    pub fn inners(&self) -> &[Loop<T>] {
+        &self.inners
+    }
+
+    /// Iterate over the outer loop & the inner loops
+    pub fn loops(&self) -> impl Iterator<Item = &Loop<T>> {
+        std::iter::once(&self.outer).chain(self.inners.iter())
+    }
+
+    /// Convert the face into a trimmed surface bounded by the loops in the parameter space
+    pub fn try_to_trimmed_surface(&self, tolerance: T) -> anyhow::Result<TrimmedSurface<T>> {
+        TrimmedSurface::try_new(
+            self.surface.clone(),
+            Some(self.outer.try_to_compound_curve(tolerance)?),
+            self.inners
+                .iter()
+                .map(|l| l.try_to_compound_curve(tolerance))
+                .collect::<anyhow::Result<Vec<_>>>()?,
+        )
+    }
+}
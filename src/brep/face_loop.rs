@@ -0,0 +1,59 @@
+use crate::{
+    curve::{CompoundCurve2D, NurbsCurve2D},
+    misc::FloatingPoint,
+};
+
+/// A use of an edge in a loop of a face
+/// The edge is traversed in the reversed direction of its curve if `reversed` is true,
+/// and `uv` is the curve of the edge in the (u, v) parameter space of the face in the direction of the loop.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Trim<T: FloatingPoint> {
+    edge: usize,
+    reversed: bool,
+    uv: NurbsCurve2D<T>,
+}
+
+impl<T: FloatingPoint> Trim<T> {
+    pub fn new(edge: usize, reversed: bool, uv: NurbsCurve2D<T>) -> Self {
+        Self { edge, reversed, uv }
+    }
+
+    /// Get the index of the edge in the shell
+    pub fn edge(&self) -> usize {
+        self.edge
+    }
+
+    pub fn is_reversed(&self) -> bool {
+        self.reversed
+    }
+
+    pub fn uv(&self) -> &NurbsCurve2D<T> {
+        &self.uv
+    }
+}
+
+/// A closed loop of trims bounding a face
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Loop<T: FloatingPoint> {
+    trims: Vec<Trim<T>>,
+}
+
+impl<T: FloatingPoint> Loop<T> {
+    pub fn new(trims: Vec<Trim<T>>) -> Self {
+        Self { trims }
+    }
+
+    pub fn trims(&self) -> &[Trim<T>] {
+        &self.trims
+    }
+
+    /// Convert the loop into a compound curve in the parameter space of the face
+    pub fn try_to_compound_curve(&self, tolerance: T) -> anyhow::Result<CompoundCurve2D<T>> {
+        CompoundCurve2D::try_new_with_tolerance(
+            self.trims.iter().map(|t| t.uv.clone()).collect(),
+            tolerance,
+        )
+    }
+}
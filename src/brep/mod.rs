@@ -0,0 +1,16 @@
+pub mod edge;
+pub mod face;
+pub mod face_loop;
+pub(crate) mod point_in_shell;
+pub mod shell;
+pub mod shell_mass_properties;
+pub mod shell_tessellation;
+pub mod vertex;
+
+pub use edge::*;
+pub use face::*;
+pub use face_loop::*;
+pub use shell::*;
+pub use shell_mass_properties::*;
+pub use shell_tessellation::*;
+pub use vertex::*;
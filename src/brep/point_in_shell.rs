@@ -0,0 +1,32 @@
+use nalgebra::{Point3, Vector3, U3};
+
+use crate::misc::{FloatingPoint, Ray};
+
+/// Check if the point is inside the closed shell by the parity of the crossings of a ray cast from the point
+/// * `cast` - Find the crossing points of the ray with the faces of the shell & their parameters along the ray
+pub(crate) fn is_point_inside_shell<T, F>(
+    point: &Point3<T>,
+    tolerance: T,
+    cast: F,
+) -> anyhow::Result<bool>
+where
+    T: FloatingPoint,
+    F: FnOnce(&Ray<T, U3>) -> anyhow::Result<Vec<(Point3<T>, T)>>,
+{
+    // an oblique direction to avoid hitting the edges of the faces exactly
+    let direction = Vector3::new(
+        T::from_f64(0.5377).unwrap(),
+        T::from_f64(0.6143).unwrap(),
+        T::from_f64(0.5773).unwrap(),
+    )
+    .normalize();
+    let ray = Ray::new(*point, direction);
+    let mut crossings: Vec<Point3<T>> = vec![];
+    for (p, t) in cast(&ray)? {
+        // skip the crossings duplicated on the shared edges of the faces
+        if t > tolerance && !crossings.iter().any(|c| (c - p).norm() < tolerance) {
+            crossings.push(p);
+        }
+    }
+    Ok(crossings.len() % 2 == 1)
+}
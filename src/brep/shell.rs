@@ -0,0 +1,832 @@
+use std::collections::{hash_map::Entry, HashMap, HashSet};
+
+use argmin::core::ArgminFloat;
+use nalgebra::{ComplexField, Matrix3, Point2, Point3, Vector2, Vector3};
+use spade::{ConstrainedDelaunayTriangulation, Point2 as SPoint2, Triangulation};
+
+use crate::{
+    brep::{Edge, Face, Loop, ShellTessellation, Trim, Vertex},
+    curve::{CompoundCurve2D, NurbsCurve2D, NurbsCurve3D},
+    misc::{is_point_inside_polygon, FloatingPoint, Invertible, Transformable},
+    surface::{NurbsSurface3D, TrimmedSurface},
+    tessellation::adaptive_tessellation_option::AdaptiveTessellationOptions,
+};
+
+/// A B-rep shell consisting of faces bounded by loops of edges shared between the faces
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Shell<T: FloatingPoint> {
+    vertices: Vec<Vertex<T>>,
+    edges: Vec<Edge<T>>,
+    faces: Vec<Face<T>>,
+}
+
+impl<T: FloatingPoint> Shell<T> {
+    pub fn new(vertices: Vec<Vertex<T>>, edges: Vec<Edge<T>>, faces: Vec<Face<T>>) -> Self {
+        Self {
+            vertices,
+            edges,
+            faces,
+        }
+    }
+
+    pub fn vertices(&self) -> &[Vertex<T>] {
+        &self.vertices
+    }
+
+    pub fn edges(&self) -> &[Edge<T>] {
+        &self.edges
+    }
+
+    pub fn faces(&self) -> &[Face<T>] {
+        &self.faces
+    }
+
+    /// Build a shell from the untrimmed surfaces
+    /// The boundary curves of the surfaces are shared as the edges if they coincide within the tolerance.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    ///
+    /// // The six faces of a cube
+    /// let p = |x: f64, y: f64, z: f64| Point3::new(x, y, z);
+    /// let face = |a: Point3<f64>, b: Point3<f64>, d: Vector3<f64>| {
+    ///     NurbsSurface::extrude(&NurbsCurve3D::polyline(&[a, b]), &d)
+    /// };
+    /// let cube = vec![
+    ///     face(p(0., 0., 0.), p(1., 0., 0.), Vector3::y()),
+    ///     face(p(0., 0., 1.), p(1., 0., 1.), Vector3::y()),
+    ///     face(p(0., 0., 0.), p(0., 1., 0.), Vector3::z()),
+    ///     face(p(1., 0., 0.), p(1., 1., 0.), Vector3::z()),
+    ///     face(p(0., 0., 0.), p(0., 0., 1.), Vector3::x()),
+    ///     face(p(0., 1., 0.), p(0., 1., 1.), Vector3::x()),
+    /// ];
+    /// let shell = Shell::try_from_surfaces(&cube, 1e-6).unwrap();
+    /// assert_eq!(shell.vertices().len(), 8);
+    /// assert_eq!(shell.edges().len(), 12);
+    /// assert_eq!(shell.faces().len(), 6);
+    /// assert!(shell.is_closed());
+    /// assert_eq!(shell.euler_characteristic(), 2);
+    ///
+    /// // Removing a face opens the shell
+    /// let open = Shell::try_from_surfaces(&cube[1..], 1e-6).unwrap();
+    /// assert!(!open.is_closed());
+    /// assert_eq!(open.boundary_edges().len(), 4);
+    /// ```
+    pub fn try_from_surfaces(surfaces: &[NurbsSurface3D<T>], tolerance: T) -> anyhow::Result<Self>
+    where
+        T: ArgminFloat,
+    {
+        let mut builder = ShellBuilder::new(tolerance);
+        for surface in surfaces {
+            let outer = domain_loop(surface);
+            builder.add_face(surface.clone(), outer, vec![])?;
+        }
+        Ok(builder.build())
+    }
+
+    /// Build a shell from the trimmed surfaces
+    /// The trim loops are lifted onto the surfaces as the edges, which are shared if they coincide within the tolerance.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    ///
+    /// let cube = |o: Point3<f64>| {
+    ///     let p = |x: f64, y: f64, z: f64| o + Vector3::new(x, y, z);
+    ///     let face = |a: Point3<f64>, b: Point3<f64>, d: Vector3<f64>| {
+    ///         NurbsSurface::extrude(&NurbsCurve3D::polyline(&[a, b]), &d)
+    ///     };
+    ///     vec![
+    ///         face(p(0., 0., 0.), p(1., 0., 0.), Vector3::y()),
+    ///         face(p(0., 0., 1.), p(1., 0., 1.), Vector3::y()),
+    ///         face(p(0., 0., 0.), p(0., 1., 0.), Vector3::z()),
+    ///         face(p(1., 0., 0.), p(1., 1., 0.), Vector3::z()),
+    ///         face(p(0., 0., 0.), p(0., 0., 1.), Vector3::x()),
+    ///         face(p(0., 1., 0.), p(0., 1., 1.), Vector3::x()),
+    ///     ]
+    /// };
+    ///
+    /// // The union of two cubes is a closed shell
+    /// let union = shell_boolean(&cube(Point3::origin()), &cube(Point3::new(0.5, 0.5, 0.5)), BooleanOperation::Union, None).unwrap();
+    /// let shell = Shell::try_from_trimmed_surfaces(union.faces(), 1e-4).unwrap();
+    /// assert!(shell.is_closed());
+    /// assert_eq!(shell.euler_characteristic(), 2);
+    /// ```
+    pub fn try_from_trimmed_surfaces(
+        surfaces: &[TrimmedSurface<T>],
+        tolerance: T,
+    ) -> anyhow::Result<Self>
+    where
+        T: ArgminFloat,
+    {
+        let mut builder = ShellBuilder::new(tolerance);
+        for trimmed in surfaces {
+            let surface = trimmed.surface();
+            let outer = trimmed
+                .exterior()
+                .map(|l| l.spans().to_vec())
+                .unwrap_or_else(|| domain_loop(surface));
+            let inners = trimmed
+                .interiors()
+                .iter()
+                .map(|l| l.spans().to_vec())
+                .collect();
+            builder.add_face(surface.clone(), outer, inners)?;
+        }
+        Ok(builder.build())
+    }
+
+    /// Thicken the face into a closed shell by offsetting its surface along the normal & connecting the boundaries with the side walls
+    /// The solid lies on the side of the normal if the thickness is positive, and the faces are oriented outward.
+    /// The boundaries coinciding with each other, such as the seam of the closed surface, are not walled.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point2, Point3, Vector2, Vector3};
+    ///
+    /// let line = NurbsCurve3D::polyline(&[Point3::new(0., 0., 0.), Point3::new(1., 0., 0.)]);
+    /// let plane = NurbsSurface::extrude(&line, &Vector3::y());
+    ///
+    /// // the plate has the top, the bottom & the four side walls
+    /// let face = TrimmedSurface::try_new(plane.clone(), None, vec![]).unwrap();
+    /// let shell = Shell::try_thicken(&face, 0.1, 1e-6).unwrap();
+    /// assert_eq!(shell.faces().len(), 6);
+    /// assert!(shell.is_closed());
+    /// assert!(shell.is_oriented());
+    /// assert_eq!(shell.euler_characteristic(), 2);
+    ///
+    /// // the plate with a hole has the wall inside the hole
+    /// let hole = NurbsCurve2D::try_circle(&Point2::new(0.5, 0.5), &Vector2::x(), &-Vector2::y(), 0.25).unwrap();
+    /// let face = TrimmedSurface::try_new(plane, None, vec![hole.into()]).unwrap();
+    /// let shell = Shell::try_thicken(&face, -0.1, 1e-6).unwrap();
+    /// assert_eq!(shell.faces().len(), 7);
+    /// assert!(shell.is_closed());
+    /// assert!(shell.is_oriented());
+    ///
+    /// // the tube has no wall along the seam of the cylinder
+    /// let cylinder = NurbsSurface::try_cylinder(&Point3::origin(), &Vector3::z(), 1.).unwrap();
+    /// let face = TrimmedSurface::try_new(cylinder, None, vec![]).unwrap();
+    /// let shell = Shell::try_thicken(&face, 0.1, 1e-4).unwrap();
+    /// assert_eq!(shell.faces().len(), 4);
+    /// assert!(shell.is_closed());
+    /// ```
+    pub fn try_thicken(face: &TrimmedSurface<T>, thickness: T, tolerance: T) -> anyhow::Result<Self>
+    where
+        T: ArgminFloat,
+    {
+        anyhow::ensure!(thickness != T::zero(), "The thickness must not be zero");
+        let surface = face.surface();
+        let offset = surface.try_offset(thickness, tolerance)?;
+        let loops = face
+            .exterior()
+            .map(|l| l.spans().to_vec())
+            .into_iter()
+            .chain(face.interiors().iter().map(|l| l.spans().to_vec()))
+            .collect::<Vec<_>>();
+        let loops = if face.exterior().is_none() {
+            [vec![domain_loop(surface)], loops].concat()
+        } else {
+            loops
+        };
+
+        // the side walls ruled between the boundaries lifted onto both surfaces
+        let spans = loops.iter().flatten().collect::<Vec<_>>();
+        let lower = spans
+            .iter()
+            .map(|uv| lift(surface, uv, tolerance))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let half = T::from_f64(0.5).unwrap();
+        let middle = |c: &NurbsCurve3D<T>| {
+            let (t0, t1) = c.knots_domain();
+            c.point_at((t0 + t1) * half)
+        };
+        let ends = |c: &NurbsCurve3D<T>| {
+            let (t0, t1) = c.knots_domain();
+            (c.point_at(t0), c.point_at(t1))
+        };
+        let seam = |i: usize| {
+            let (a0, a1) = ends(&lower[i]);
+            let m = middle(&lower[i]);
+            lower.iter().enumerate().any(|(j, other)| {
+                let (b0, b1) = ends(other);
+                i != j
+                    && (a0 - b1).norm() < tolerance
+                    && (a1 - b0).norm() < tolerance
+                    && (middle(other) - m).norm() < tolerance
+            })
+        };
+        let step = tolerance * T::from_f64(10.).unwrap();
+        let mut walls = vec![];
+        for (i, uv) in spans.iter().enumerate() {
+            let degenerate = lower[i]
+                .dehomogenized_control_points()
+                .iter()
+                .all(|p| (p - ends(&lower[i]).0).norm() < tolerance);
+            if degenerate || seam(i) {
+                continue;
+            }
+            let upper = lift(&offset, uv, tolerance)?;
+            let mut wall = NurbsSurface3D::try_ruled(&lower[i], &upper)?;
+
+            // orient the wall away from the inside of the face
+            let (t0, t1) = uv.knots_domain();
+            let t = (t0 + t1) * half;
+            let (p, tangent) = (uv.point_at(t), uv.tangent_at(t));
+            let mut inward = Vector2::new(-tangent.y, tangent.x).normalize();
+            if !face.contains(p.x + inward.x * step, p.y + inward.y * step) {
+                inward = -inward;
+            }
+            let derivs = surface.rational_derivatives(p.x, p.y, 1);
+            let inward = derivs[1][0] * inward.x + derivs[0][1] * inward.y;
+            let (u0, u1) = wall.u_knots_domain();
+            let (v0, v1) = wall.v_knots_domain();
+            let normal = wall.normal_at((u0 + u1) * half, (v0 + v1) * half);
+            if normal.dot(&inward) > T::zero() {
+                wall.flip(false);
+            }
+            walls.push(TrimmedSurface::try_new(wall, None, vec![])?);
+        }
+
+        // the caps facing outward with the loops mirrored along with the flipped surface
+        let (u0, u1) = surface.u_knots_domain();
+        let mirror = |l: &CompoundCurve2D<T>| {
+            let mut l = l.clone();
+            l.transform(&Matrix3::new(
+                -T::one(),
+                T::zero(),
+                u0 + u1,
+                T::zero(),
+                T::one(),
+                T::zero(),
+                T::zero(),
+                T::zero(),
+                T::one(),
+            ));
+            l.invert();
+            l
+        };
+        let flipped = |s: &NurbsSurface3D<T>| {
+            let mut flipped = s.clone();
+            flipped.flip(false);
+            TrimmedSurface::try_new(
+                flipped,
+                face.exterior().map(mirror),
+                face.interiors().iter().map(mirror).collect(),
+            )
+        };
+        let same = |s: &NurbsSurface3D<T>| {
+            TrimmedSurface::try_new(
+                s.clone(),
+                face.exterior().cloned(),
+                face.interiors().to_vec(),
+            )
+        };
+        let (bottom, top) = if thickness > T::zero() {
+            (flipped(surface)?, same(&offset)?)
+        } else {
+            (same(surface)?, flipped(&offset)?)
+        };
+
+        let faces = [vec![bottom, top], walls].concat();
+        Self::try_from_trimmed_surfaces(&faces, tolerance)
+    }
+
+    /// Get the uses of each edge by the faces as the pairs of the face index & whether the edge is reversed
+    pub fn edge_uses(&self) -> Vec<Vec<(usize, bool)>> {
+        let mut uses = vec![vec![]; self.edges.len()];
+        self.faces.iter().enumerate().for_each(|(i, face)| {
+            face.loops().flat_map(|l| l.trims()).for_each(|t| {
+                uses[t.edge()].push((i, t.is_reversed()));
+            });
+        });
+        uses
+    }
+
+    /// Get the indices of the edges used by only one face
+    pub fn boundary_edges(&self) -> Vec<usize> {
+        self.edge_uses()
+            .iter()
+            .enumerate()
+            .filter(|(i, uses)| !self.edges[*i].is_degenerate() && uses.len() == 1)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Check if the shell is closed, where every non-degenerate edge is shared by exactly two uses
+    pub fn is_closed(&self) -> bool {
+        self.edge_uses()
+            .iter()
+            .enumerate()
+            .all(|(i, uses)| self.edges[i].is_degenerate() || uses.len() == 2)
+    }
+
+    /// Check if the faces are consistently oriented, where every shared edge is traversed once in each direction
+    pub fn is_oriented(&self) -> bool {
+        self.edge_uses()
+            .iter()
+            .all(|uses| uses.len() != 2 || uses[0].1 != uses[1].1)
+    }
+
+    /// Compute the Euler characteristic `V - E + F` of the shell
+    pub fn euler_characteristic(&self) -> i64 {
+        self.vertices.len() as i64 - self.edges.len() as i64 + self.faces.len() as i64
+    }
+
+    /// Tessellate the shell into a watertight mesh
+    /// Each edge is tessellated once and its vertices are shared by the faces using it,
+    /// so there are no cracks or T-junctions along the shared edges.
+    /// The faces are triangulated in the parameter space by constrained Delaunay triangulation with the edge vertices as the boundary
+    /// and the vertices of the (adaptive) tessellation of the surfaces as the interior points.
+    /// # Example
+    /// ```
+    /// use std::collections::HashMap;
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    ///
+    /// // Count the triangles adjacent to each edge of the mesh
+    /// let edge_counts = |tess: &ShellTessellation<f64>| {
+    ///     let mut counts = HashMap::new();
+    ///     tess.faces().iter().for_each(|f| {
+    ///         for i in 0..3 {
+    ///             let (a, b) = (f[i], f[(i + 1) % 3]);
+    ///             *counts.entry((a.min(b), a.max(b))).or_insert(0) += 1;
+    ///         }
+    ///     });
+    ///     counts
+    /// };
+    ///
+    /// // A lofted surface split into two patches
+    /// let sections: Vec<_> = (0..4).map(|i| {
+    ///     let z = i as f64;
+    ///     NurbsCurve3D::try_interpolate(&[
+    ///         Point3::new(0., 0., z),
+    ///         Point3::new(1., 0.5 + 0.2 * z, z),
+    ///         Point3::new(2., 0., z),
+    ///         Point3::new(3., -0.5, z),
+    ///     ], 3).unwrap()
+    /// }).collect();
+    /// let lofted = NurbsSurface::try_loft(&sections, Some(3)).unwrap();
+    /// let (u0, u1) = lofted.u_knots_domain();
+    /// let (a, b) = lofted.try_split_u((u0 + u1) * 0.37).unwrap();
+    ///
+    /// let shell = Shell::try_from_surfaces(&[a, b], 1e-6).unwrap();
+    /// assert_eq!(shell.edges().len(), 7);
+    /// let options = AdaptiveTessellationOptions { min_divs_u: 8, min_divs_v: 8, ..Default::default() };
+    /// let tess = shell.tessellate(Some(options));
+    /// assert!(tess.face_indices().contains(&0) && tess.face_indices().contains(&1));
+    ///
+    /// // The mesh edges used by only one triangle lie on the outer boundary of the patches, not on the shared edge
+    /// let boundary: Vec<_> = edge_counts(&tess).into_iter().filter(|(_, c)| *c == 1).map(|(e, _)| e).collect();
+    /// let on_split_line = |p: &Point3<f64>| (lofted.find_closest_parameter(p).unwrap().0 - (u0 + u1) * 0.37).abs() < 1e-6;
+    /// assert!(boundary.iter().all(|(a, b)| {
+    ///     let (pa, pb) = (&tess.points()[*a], &tess.points()[*b]);
+    ///     !(on_split_line(pa) && on_split_line(pb))
+    /// }));
+    ///
+    /// // The mesh of a closed cube is watertight
+    /// let p = |x: f64, y: f64, z: f64| Point3::new(x, y, z);
+    /// let face = |a: Point3<f64>, b: Point3<f64>, d: Vector3<f64>| {
+    ///     NurbsSurface::extrude(&NurbsCurve3D::polyline(&[a, b]), &d)
+    /// };
+    /// let cube = vec![
+    ///     face(p(0., 0., 0.), p(1., 0., 0.), Vector3::y()),
+    ///     face(p(0., 0., 1.), p(1., 0., 1.), Vector3::y()),
+    ///     face(p(0., 0., 0.), p(0., 1., 0.), Vector3::z()),
+    ///     face(p(1., 0., 0.), p(1., 1., 0.), Vector3::z()),
+    ///     face(p(0., 0., 0.), p(0., 0., 1.), Vector3::x()),
+    ///     face(p(0., 1., 0.), p(0., 1., 1.), Vector3::x()),
+    /// ];
+    /// let tess = Shell::try_from_surfaces(&cube, 1e-6).unwrap().tessellate(None);
+    /// assert_eq!(tess.points().len(), 8);
+    /// assert!(edge_counts(&tess).values().all(|c| *c == 2));
+    /// ```
+    pub fn tessellate(
+        &self,
+        adaptive_options: Option<AdaptiveTessellationOptions<T>>,
+    ) -> ShellTessellation<T> {
+        let mut points: Vec<Point3<T>> = self.vertices.iter().map(|v| *v.point()).collect();
+
+        // tessellate each edge once & share the vertices
+        let edges: Vec<(Vec<usize>, Vec<Point3<T>>)> = self
+            .edges
+            .iter()
+            .map(|edge| {
+                if edge.is_degenerate() {
+                    return (vec![edge.start()], vec![points[edge.start()]]);
+                }
+                let samples = edge.curve().tessellate(None);
+                let n = samples.len();
+                let mut ids = vec![edge.start()];
+                let mut pts = vec![points[edge.start()]];
+                samples.iter().take(n - 1).skip(1).for_each(|p| {
+                    ids.push(points.len());
+                    pts.push(*p);
+                    points.push(*p);
+                });
+                ids.push(edge.end());
+                pts.push(points[edge.end()]);
+                (ids, pts)
+            })
+            .collect();
+
+        let mut normals = vec![Vector3::zeros(); points.len()];
+        let mut faces = vec![];
+        let mut face_indices = vec![];
+
+        for (index, face) in self.faces.iter().enumerate() {
+            let surface = face.surface();
+
+            // the boundary polygons in the parameter space with the indices of the shared vertices
+            let polygons: Vec<Vec<(Point2<T>, usize)>> = face
+                .loops()
+                .map(|l| {
+                    l.trims()
+                        .iter()
+                        .flat_map(|trim| {
+                            let (ids, pts) = &edges[trim.edge()];
+                            let mut boundary = if self.edges[trim.edge()].is_degenerate() {
+                                trim.uv()
+                                    .tessellate(None)
+                                    .into_iter()
+                                    .map(|uv| (uv, ids[0]))
+                                    .collect()
+                            } else {
+                                let uvs = parameters_on_trim(surface, trim, pts);
+                                uvs.into_iter().zip(ids.iter().cloned()).collect::<Vec<_>>()
+                            };
+                            if trim.is_reversed() && !self.edges[trim.edge()].is_degenerate() {
+                                boundary.reverse();
+                            }
+                            // the last vertex is shared with the next trim
+                            boundary.pop();
+                            boundary
+                        })
+                        .collect()
+                })
+                .collect();
+
+            let uv_polygons: Vec<Vec<Point2<T>>> = polygons
+                .iter()
+                .map(|p| p.iter().map(|(uv, _)| *uv).collect())
+                .collect();
+            let contains = |uv: &Point2<T>| {
+                is_point_inside_polygon(uv, &uv_polygons[0])
+                    && !uv_polygons[1..]
+                        .iter()
+                        .any(|hole| is_point_inside_polygon(uv, hole))
+            };
+
+            let mut cdt = ConstrainedDelaunayTriangulation::<SPoint2<f64>>::new();
+            let to_spade =
+                |p: &Point2<T>| SPoint2::new(p.x.to_f64().unwrap(), p.y.to_f64().unwrap());
+            let mut ids: HashMap<usize, usize> = HashMap::new();
+            let mut uvs: HashMap<usize, Point2<T>> = HashMap::new();
+            for polygon in polygons.iter() {
+                let handles: Vec<_> = polygon
+                    .iter()
+                    .filter_map(|(uv, id)| {
+                        let handle = cdt.insert(to_spade(uv)).ok()?;
+                        ids.insert(handle.index(), *id);
+                        uvs.insert(handle.index(), *uv);
+                        Some(handle)
+                    })
+                    .collect();
+                let n = handles.len();
+                for i in 0..n {
+                    let (a, b) = (handles[i], handles[(i + 1) % n]);
+                    if a != b {
+                        cdt.try_add_constraint(a, b);
+                    }
+                }
+            }
+
+            // insert the interior points kept away from the boundary not to split the constraint edges
+            let segments: Vec<(Point2<T>, Point2<T>)> = uv_polygons
+                .iter()
+                .flat_map(|p| {
+                    let n = p.len();
+                    (0..n).map(move |i| (p[i], p[(i + 1) % n]))
+                })
+                .collect();
+            let margin = T::from_f64(0.25).unwrap();
+            surface
+                .tessellate(adaptive_options.clone())
+                .uvs
+                .iter()
+                .for_each(|uv| {
+                    let uv: Point2<T> = (*uv).into();
+                    let away = segments.iter().all(|(a, b)| {
+                        let ab = b - a;
+                        let len = ab.norm();
+                        if len <= T::zero() {
+                            return true;
+                        }
+                        let t =
+                            nalgebra::clamp((uv - a).dot(&ab) / (len * len), T::zero(), T::one());
+                        (a + ab * t - uv).norm() > len * margin
+                    });
+                    if away && contains(&uv) {
+                        if let Ok(handle) = cdt.insert(to_spade(&uv)) {
+                            if let Entry::Vacant(e) = ids.entry(handle.index()) {
+                                e.insert(points.len());
+                                uvs.insert(handle.index(), uv);
+                                points.push(surface.point_at(uv.x, uv.y));
+                                normals.push(Vector3::zeros());
+                            }
+                        }
+                    }
+                });
+
+            let mut used = HashSet::new();
+            let three = T::from_usize(3).unwrap();
+            cdt.inner_faces().for_each(|f| {
+                let [a, b, c] = f.vertices().map(|v| v.fix().index());
+                let center = (uvs[&a].coords + uvs[&b].coords + uvs[&c].coords) / three;
+                if !contains(&center.into()) {
+                    return;
+                }
+                let (a, b, c) = (ids[&a], ids[&b], ids[&c]);
+                if a == b || b == c || c == a {
+                    return;
+                }
+                // flip the counter-clockwise triangle in the parameter space to align with the surface normal
+                faces.push([a, c, b]);
+                face_indices.push(index);
+                used.extend([a, b, c]);
+            });
+
+            // accumulate the normals of the faces at the shared vertices
+            ids.iter().for_each(|(handle, id)| {
+                if used.contains(id) {
+                    let uv = uvs[handle];
+                    if let Some(n) = surface.normal_at(uv.x, uv.y).try_normalize(T::zero()) {
+                        normals[*id] += n;
+                    }
+                }
+            });
+        }
+
+        ShellTessellation {
+            normals: normals
+                .into_iter()
+                .map(|n| n.try_normalize(T::zero()).unwrap_or(n))
+                .collect(),
+            points,
+            faces,
+            face_indices,
+        }
+    }
+}
+
+/// Find the parameters on the surface of the points along the edge by projecting them onto the lifted trim curve
+/// The end points of the edge are mapped to the end points of the trim to resolve the closed edges.
+fn parameters_on_trim<T: FloatingPoint>(
+    surface: &NurbsSurface3D<T>,
+    trim: &Trim<T>,
+    points: &[Point3<T>],
+) -> Vec<Point2<T>> {
+    let uv = trim.uv();
+    let (t0, t1) = uv.knots_domain();
+    let divs = (points.len() * 4).max(32);
+    let samples: Vec<_> = (0..=divs)
+        .map(|i| {
+            let p = uv.point_at(
+                t0 + (t1 - t0) * T::from_usize(i).unwrap() / T::from_usize(divs).unwrap(),
+            );
+            (p, surface.point_at(p.x, p.y))
+        })
+        .collect();
+
+    let n = points.len();
+    let (head, tail) = if trim.is_reversed() {
+        (samples[divs].0, samples[0].0)
+    } else {
+        (samples[0].0, samples[divs].0)
+    };
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, point)| {
+            if i == 0 {
+                return head;
+            }
+            if i == n - 1 {
+                return tail;
+            }
+            samples
+                .windows(2)
+                .map(|w| {
+                    let ((a, pa), (b, pb)) = (w[0], w[1]);
+                    let d = pb - pa;
+                    let t = if d.norm_squared() > T::zero() {
+                        nalgebra::clamp(
+                            (point - pa).dot(&d) / d.norm_squared(),
+                            T::zero(),
+                            T::one(),
+                        )
+                    } else {
+                        T::zero()
+                    };
+                    ((pa + d * t - point).norm(), a + (b - a) * t)
+                })
+                .min_by(|x, y| x.0.partial_cmp(&y.0).unwrap_or(std::cmp::Ordering::Equal))
+                .map(|(_, uv)| uv)
+                .unwrap_or(head)
+        })
+        .collect()
+}
+
+/// The boundary of the parameter domain of the surface as a loop of segments
+fn domain_loop<T: FloatingPoint>(surface: &NurbsSurface3D<T>) -> Vec<NurbsCurve2D<T>> {
+    let (u0, u1) = surface.u_knots_domain();
+    let (v0, v1) = surface.v_knots_domain();
+    let corners = [
+        Point2::new(u0, v0),
+        Point2::new(u1, v0),
+        Point2::new(u1, v1),
+        Point2::new(u0, v1),
+    ];
+    (0..4)
+        .map(|i| NurbsCurve2D::polyline(&[corners[i], corners[(i + 1) % 4]]))
+        .collect()
+}
+
+/// Extract the part of the curve between the parameters in the direction from `t0` to `t1`
+fn sub_curve<T: FloatingPoint>(
+    curve: &NurbsCurve3D<T>,
+    t0: T,
+    t1: T,
+    tolerance: T,
+) -> anyhow::Result<NurbsCurve3D<T>> {
+    let (lo, hi) = if t0 < t1 { (t0, t1) } else { (t1, t0) };
+    let (start, end) = curve.knots_domain();
+    let mut sub = curve.clone();
+    if lo > start + tolerance {
+        sub = sub.try_trim(lo)?.1;
+    }
+    if hi < end - tolerance {
+        sub = sub.try_trim(hi)?.0;
+    }
+    if t0 > t1 {
+        sub.invert();
+    }
+    Ok(sub)
+}
+
+/// Lift the curve in the parameter space onto the surface
+/// The segments along the iso parameters are lifted exactly, and the others are interpolated through the samples.
+fn lift<T: FloatingPoint>(
+    surface: &NurbsSurface3D<T>,
+    uv: &NurbsCurve2D<T>,
+    tolerance: T,
+) -> anyhow::Result<NurbsCurve3D<T>> {
+    if uv.degree() == 1 && uv.control_points().len() == 2 {
+        // clamp the end points into the domain to evaluate the exact iso curves
+        let (u0, u1) = surface.u_knots_domain();
+        let (v0, v1) = surface.v_knots_domain();
+        let clamp =
+            |p: &Point2<T>| Point2::new(nalgebra::clamp(p.x, u0, u1), nalgebra::clamp(p.y, v0, v1));
+        let pts = uv.dehomogenized_control_points();
+        let (a, b) = (clamp(&pts[0]), clamp(&pts[1]));
+        if ComplexField::abs(a.x - b.x) < tolerance {
+            let iso = surface.try_isocurve(a.x, false)?;
+            return sub_curve(&iso, a.y, b.y, tolerance);
+        }
+        if ComplexField::abs(a.y - b.y) < tolerance {
+            let iso = surface.try_isocurve(a.y, true)?;
+            return sub_curve(&iso, a.x, b.x, tolerance);
+        }
+    }
+
+    let mut points: Vec<_> = uv
+        .tessellate(None)
+        .iter()
+        .map(|p| surface.point_at(p.x, p.y))
+        .collect();
+    points.dedup_by(|a, b| (*a - *b).norm() < tolerance);
+    if points.len() < 2 {
+        // the curve collapses to a point on the surface
+        let p = points[0].to_homogeneous().into();
+        return NurbsCurve3D::try_new(
+            1,
+            vec![p, p],
+            vec![T::zero(), T::zero(), T::one(), T::one()],
+        );
+    }
+    let degree = (points.len() - 1).min(3);
+    NurbsCurve3D::try_interpolate(&points, degree)
+}
+
+/// A builder of a shell sharing the vertices & edges coinciding within the tolerance
+struct ShellBuilder<T: FloatingPoint> {
+    vertices: Vec<Vertex<T>>,
+    edges: Vec<Edge<T>>,
+    faces: Vec<Face<T>>,
+    tolerance: T,
+}
+
+impl<T: FloatingPoint + ArgminFloat> ShellBuilder<T> {
+    fn new(tolerance: T) -> Self {
+        Self {
+            vertices: vec![],
+            edges: vec![],
+            faces: vec![],
+            tolerance,
+        }
+    }
+
+    fn build(self) -> Shell<T> {
+        Shell::new(self.vertices, self.edges, self.faces)
+    }
+
+    fn add_vertex(&mut self, point: Point3<T>) -> usize {
+        match self
+            .vertices
+            .iter()
+            .position(|v| (v.point() - point).norm() < self.tolerance)
+        {
+            Some(i) => i,
+            None => {
+                self.vertices.push(Vertex::new(point));
+                self.vertices.len() - 1
+            }
+        }
+    }
+
+    /// Add the edge along the curve or find the existing edge coinciding with it
+    /// Returns the index of the edge & whether the curve runs in the reversed direction of the edge.
+    fn add_edge(&mut self, curve: NurbsCurve3D<T>) -> anyhow::Result<(usize, bool)> {
+        let (t0, t1) = curve.knots_domain();
+        let (a, b) = (curve.point_at(t0), curve.point_at(t1));
+        let degenerate = curve
+            .dehomogenized_control_points()
+            .iter()
+            .all(|p| (p - a).norm() < self.tolerance);
+        let (start, end) = (self.add_vertex(a), self.add_vertex(b));
+
+        let middle = curve.point_at((t0 + t1) * T::from_f64(0.5).unwrap());
+        for (i, edge) in self.edges.iter().enumerate() {
+            let reversed = if edge.start() == start && edge.end() == end {
+                false
+            } else if edge.start() == end && edge.end() == start {
+                true
+            } else {
+                continue;
+            };
+            if edge.is_degenerate() != degenerate {
+                continue;
+            }
+            let coincident = degenerate
+                || edge
+                    .curve()
+                    .find_closest_point(&middle)
+                    .map(|p| (p - middle).norm() < self.tolerance)
+                    .unwrap_or(false);
+            if coincident {
+                if start == end && !degenerate {
+                    // the direction of the closed edge is told by the tangents at the middle
+                    let tangent = curve.tangent_at((t0 + t1) * T::from_f64(0.5).unwrap());
+                    let u = edge.curve().find_closest_parameter(&middle)?;
+                    return Ok((i, edge.curve().tangent_at(u).dot(&tangent) < T::zero()));
+                }
+                return Ok((i, reversed));
+            }
+        }
+
+        self.edges.push(Edge::new(curve, start, end, degenerate));
+        Ok((self.edges.len() - 1, false))
+    }
+
+    fn add_loop(
+        &mut self,
+        surface: &NurbsSurface3D<T>,
+        spans: Vec<NurbsCurve2D<T>>,
+    ) -> anyhow::Result<Loop<T>> {
+        let trims = spans
+            .into_iter()
+            .map(|uv| {
+                let curve = lift(surface, &uv, self.tolerance)?;
+                let (edge, reversed) = self.add_edge(curve)?;
+                Ok(Trim::new(edge, reversed, uv))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(Loop::new(trims))
+    }
+
+    fn add_face(
+        &mut self,
+        surface: NurbsSurface3D<T>,
+        outer: Vec<NurbsCurve2D<T>>,
+        inners: Vec<Vec<NurbsCurve2D<T>>>,
+    ) -> anyhow::Result<()> {
+        let outer = self.add_loop(&surface, outer)?;
+        let inners = inners
+            .into_iter()
+            .map(|l| self.add_loop(&surface, l))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        self.faces.push(Face::new(surface, outer, inners));
+        Ok(())
+    }
+}
@@ -0,0 +1,431 @@
+use std::collections::VecDeque;
+
+use gauss_quad::GaussLegendre;
+use nalgebra::{Matrix3, Point3, Vector3, U3};
+
+use crate::{
+    brep::{point_in_shell::is_point_inside_shell, Loop, Shell},
+    misc::{FloatingPoint, Ray},
+    surface::NurbsSurface3D,
+};
+
+/// The maximum depth of the subdivision in the adaptive quadratures
+const MAX_DEPTH: usize = 16;
+
+/// The number of the integrals of the volume, the first & the second moments over the solid
+const MOMENTS: usize = 10;
+
+/// The volume, the centroid & the inertia tensor of a solid of the unit density
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MassProperties<T: FloatingPoint> {
+    volume: T,
+    centroid: Point3<T>,
+    /// The inertia tensor about the centroid
+    inertia: Matrix3<T>,
+}
+
+impl<T: FloatingPoint> MassProperties<T> {
+    pub fn new(volume: T, centroid: Point3<T>, inertia: Matrix3<T>) -> Self {
+        Self {
+            volume,
+            centroid,
+            inertia,
+        }
+    }
+
+    pub fn volume(&self) -> T {
+        self.volume
+    }
+
+    pub fn centroid(&self) -> &Point3<T> {
+        &self.centroid
+    }
+
+    /// The inertia tensor about the centroid of the unit density
+    pub fn inertia(&self) -> &Matrix3<T> {
+        &self.inertia
+    }
+
+    /// The mass of the solid of the density
+    pub fn mass(&self, density: T) -> T {
+        self.volume * density
+    }
+
+    /// The principal moments of the inertia about the centroid in the ascending order with the axes as the columns
+    pub fn principal_moments(&self) -> (Vector3<T>, Matrix3<T>) {
+        let eigen = self.inertia.symmetric_eigen();
+        let mut order = [0, 1, 2];
+        order.sort_by(|a, b| {
+            eigen.eigenvalues[*a]
+                .partial_cmp(&eigen.eigenvalues[*b])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        (
+            Vector3::from_fn(|i, _| eigen.eigenvalues[order[i]]),
+            Matrix3::from_fn(|r, c| eigen.eigenvectors[(r, order[c])]),
+        )
+    }
+}
+
+/// The integrands of the divergence theorem at the parameter of the surface
+/// Each is the flux of the field whose divergence is the density of the moment through the area element along S_u x S_v:
+/// the volume, the integrals of x, y, z, x^2, y^2, z^2, xy, yz & zx.
+fn fluxes<T: FloatingPoint>(surface: &NurbsSurface3D<T>, u: T, v: T) -> [T; MOMENTS] {
+    let d = surface.rational_derivatives(u, v, 1);
+    let (p, n) = (&d[0][0], d[1][0].cross(&d[0][1]));
+    let (x, y, z) = (p.x, p.y, p.z);
+    let half = T::from_f64(0.5).unwrap();
+    let third = T::one() / T::from_usize(3).unwrap();
+    [
+        p.dot(&n) * third,
+        x * x * n.x * half,
+        y * y * n.y * half,
+        z * z * n.z * half,
+        x * x * x * n.x * third,
+        y * y * y * n.y * third,
+        z * z * z * n.z * third,
+        x * x * y * n.x * half,
+        y * y * z * n.y * half,
+        z * z * x * n.z * half,
+    ]
+}
+
+/// Integrate the vector function over the interval by the Gauss-Legendre quadrature
+fn quadrature<T: FloatingPoint, const N: usize, F: Fn(T) -> [T; N]>(
+    gauss: &GaussLegendre,
+    f: &F,
+    a: T,
+    b: T,
+) -> [T; N] {
+    let half = (b - a) * T::from_f64(0.5).unwrap();
+    let mid = (a + b) * T::from_f64(0.5).unwrap();
+    gauss
+        .as_node_weight_pairs()
+        .iter()
+        .fold([T::zero(); N], |mut acc, &(x, w)| {
+            let w = T::from_f64(w).unwrap() * half;
+            let f = f(mid + half * T::from_f64(x).unwrap());
+            acc.iter_mut().zip(f.iter()).for_each(|(a, f)| *a += *f * w);
+            acc
+        })
+}
+
+/// Integrate the vector function over the interval split at the breaks,
+/// subdividing each interval adaptively until the halves agree with the whole within its share of the tolerance
+fn adaptive<T: FloatingPoint, const N: usize, F: Fn(T) -> [T; N]>(
+    gauss: &GaussLegendre,
+    f: &F,
+    breaks: &[T],
+    tolerance: T,
+) -> [T; N] {
+    let half = T::from_f64(0.5).unwrap();
+    let (start, end) = (breaks[0], breaks[breaks.len() - 1]);
+    let domain = (end - start).abs();
+    let mut sum = [T::zero(); N];
+    if domain <= T::zero() {
+        return sum;
+    }
+    for w in breaks.windows(2) {
+        let share = tolerance * (w[1] - w[0]).abs() / domain;
+        let mut stack = vec![(w[0], w[1], quadrature(gauss, f, w[0], w[1]), share, 0)];
+        while let Some((a, b, whole, tolerance, depth)) = stack.pop() {
+            let mid = (a + b) * half;
+            let (left, right) = (quadrature(gauss, f, a, mid), quadrature(gauss, f, mid, b));
+            let difference = (0..N).fold(T::zero(), |acc, i| {
+                acc.max((left[i] + right[i] - whole[i]).abs())
+            });
+            if difference <= tolerance || depth >= MAX_DEPTH {
+                (0..N).for_each(|i| sum[i] += left[i] + right[i]);
+            } else {
+                stack.push((mid, b, right, tolerance * half, depth + 1));
+                stack.push((a, mid, left, tolerance * half, depth + 1));
+            }
+        }
+    }
+    sum
+}
+
+/// The distinct knots strictly between the ends with the ends in the order from `a` to `b`
+fn breaks<T: FloatingPoint>(knots: &[T], a: T, b: T) -> Vec<T> {
+    let (lo, hi) = (a.min(b), a.max(b));
+    let mut breaks = knots
+        .iter()
+        .filter(|k| **k > lo && **k < hi)
+        .copied()
+        .collect::<Vec<_>>();
+    breaks.dedup();
+    breaks.insert(0, lo);
+    breaks.push(hi);
+    if a > b {
+        breaks.reverse();
+    }
+    breaks
+}
+
+/// Integrate the fluxes along the loop by the Green's theorem in the parameter space
+/// Returns the signed area enclosed by the loop in the parameter space & the integrals inside it, both positive if it turns counterclockwise.
+fn loop_integrals<T: FloatingPoint>(
+    surface: &NurbsSurface3D<T>,
+    face_loop: &Loop<T>,
+    gauss: &GaussLegendre,
+    tolerance: T,
+) -> (T, [T; MOMENTS]) {
+    let (u0, _) = surface.u_knots_domain();
+    let u_knots = surface.u_knots().as_slice();
+    let inner = tolerance * T::from_f64(1e-2).unwrap();
+    let outer = tolerance / T::from_usize(face_loop.trims().len().max(1)).unwrap();
+
+    // the integrals of the fluxes from the start of the domain to the parameter in the u direction
+    let potential = |u: T, v: T| {
+        adaptive(
+            gauss,
+            &|s| fluxes(surface, s, v),
+            &breaks(u_knots, u0, u),
+            inner,
+        )
+    };
+
+    face_loop.trims().iter().fold(
+        (T::zero(), [T::zero(); MOMENTS]),
+        |(area, mut sum), trim| {
+            let curve = trim.uv();
+            let (start, end) = curve.knots_domain();
+            let integrand = |t: T| {
+                let d = curve.rational_derivatives(t, 1);
+                let (uv, dv) = (&d[0], d[1].y);
+                let g = potential(uv.x, uv.y);
+                let mut values = [T::zero(); MOMENTS + 1];
+                values[0] = uv.x * dv;
+                (0..MOMENTS).for_each(|i| values[i + 1] = g[i] * dv);
+                values
+            };
+            let values = adaptive(
+                gauss,
+                &integrand,
+                &breaks(curve.knots().as_slice(), start, end),
+                outer,
+            );
+            (0..MOMENTS).for_each(|i| sum[i] += values[i + 1]);
+            (area + values[0], sum)
+        },
+    )
+}
+
+/// Count the parts of the shell enclosing each part by the ray cast from a point on the tessellation of the part
+fn nesting_depths<T: FloatingPoint>(
+    shell: &Shell<T>,
+    parts: &[Vec<usize>],
+    tolerance: T,
+) -> anyhow::Result<Vec<usize>> {
+    let tess = shell.tessellate(None);
+    let mut owners = vec![0; shell.faces().len()];
+    parts.iter().enumerate().for_each(|(i, part)| {
+        part.iter().for_each(|f| owners[*f] = i);
+    });
+    let mut triangles = vec![vec![]; parts.len()];
+    tess.faces()
+        .iter()
+        .zip(tess.face_indices())
+        .for_each(|(t, f)| triangles[owners[*f]].push(t.map(|v| tess.points()[v])));
+
+    let third = T::from_f64(1. / 3.).unwrap();
+    (0..parts.len())
+        .map(|i| {
+            let Some([a, b, c]) = triangles[i].first() else {
+                return Ok(0);
+            };
+            let origin = Point3::from((a.coords + b.coords + c.coords) * third);
+            let mut depth = 0;
+            for (j, other) in triangles.iter().enumerate() {
+                if j != i
+                    && is_point_inside_shell(&origin, tolerance, |ray| {
+                        Ok(other.iter().filter_map(|t| cross(ray, t)).collect())
+                    })?
+                {
+                    depth += 1;
+                }
+            }
+            Ok(depth)
+        })
+        .collect()
+}
+
+/// Find the crossing point & its parameter of the ray with the triangle by the Möller-Trumbore algorithm
+fn cross<T: FloatingPoint>(ray: &Ray<T, U3>, [a, b, c]: &[Point3<T>; 3]) -> Option<(Point3<T>, T)> {
+    let (e1, e2) = (b - a, c - a);
+    let p = ray.direction().cross(&e2);
+    let det = e1.dot(&p);
+    if det == T::zero() {
+        return None;
+    }
+    let s = ray.origin() - a;
+    let u = s.dot(&p) / det;
+    let q = s.cross(&e1);
+    let v = ray.direction().dot(&q) / det;
+    let t = e2.dot(&q) / det;
+    (u >= T::zero() && v >= T::zero() && u + v <= T::one()).then(|| (ray.point_at(t), t))
+}
+
+impl<T: FloatingPoint> Shell<T> {
+    /// Try to compute the volume, the centroid & the inertia tensor of the solid bounded by the closed shell
+    /// The integrals over the solid are converted into the fluxes through the faces by the divergence theorem,
+    /// which are integrated over the trimmed domains of the faces by the Green's theorem with the adaptive Gauss-Legendre quadrature.
+    /// The faces are oriented consistently across the shared edges and outward from each connected part of the shell,
+    /// and the part nested inside another part is subtracted as a cavity.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Matrix3, Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// // the box of 2 x 1 x 1 from the faces extruded in the arbitrary directions
+    /// let p = |x: f64, y: f64, z: f64| Point3::new(x * 2., y, z);
+    /// let face = |a: Point3<f64>, b: Point3<f64>, d: Vector3<f64>| {
+    ///     NurbsSurface::extrude(&NurbsCurve3D::polyline(&[a, b]), &d)
+    /// };
+    /// let faces = vec![
+    ///     face(p(0., 0., 0.), p(1., 0., 0.), Vector3::y()),
+    ///     face(p(0., 0., 1.), p(1., 0., 1.), Vector3::y()),
+    ///     face(p(0., 0., 0.), p(0., 1., 0.), Vector3::z()),
+    ///     face(p(1., 0., 0.), p(1., 1., 0.), Vector3::z()),
+    ///     face(p(0., 0., 0.), p(0., 0., 1.), Vector3::x() * 2.),
+    ///     face(p(0., 1., 0.), p(0., 1., 1.), Vector3::x() * 2.),
+    /// ];
+    /// let shell = Shell::try_from_surfaces(&faces, 1e-6).unwrap();
+    /// let properties = shell.try_mass_properties(1e-8).unwrap();
+    /// assert_relative_eq!(properties.volume(), 2., epsilon = 1e-8);
+    /// assert_relative_eq!(*properties.centroid(), Point3::new(1., 0.5, 0.5), epsilon = 1e-8);
+    /// // the inertia of the box is m (b^2 + c^2) / 12 about each axis
+    /// let inertia = Matrix3::from_diagonal(&Vector3::new(2., 5., 5.)) * (2. / 12.);
+    /// assert_relative_eq!(*properties.inertia(), inertia, epsilon = 1e-8);
+    ///
+    /// // the ball bounded by the sphere
+    /// let sphere = NurbsSurface3D::<f64>::try_sphere(&Point3::new(1., 2., 3.), &Vector3::z(), 2.).unwrap();
+    /// let shell = Shell::try_from_surfaces(&[sphere], 1e-6).unwrap();
+    /// let properties = shell.try_mass_properties(1e-8).unwrap();
+    /// let volume = std::f64::consts::PI * 32. / 3.;
+    /// assert_relative_eq!(properties.volume(), volume, epsilon = 1e-6);
+    /// assert_relative_eq!(*properties.centroid(), Point3::new(1., 2., 3.), epsilon = 1e-6);
+    /// // the inertia of the ball is 2 m r^2 / 5 about each axis
+    /// let (moments, _) = properties.principal_moments();
+    /// assert_relative_eq!(moments, Vector3::repeat(volume * 8. / 5.), epsilon = 1e-5);
+    ///
+    /// // the hollow ball bounded by the outer sphere & the inner sphere of the cavity
+    /// let inner = NurbsSurface3D::<f64>::try_sphere(&Point3::new(1.5, 2., 3.), &Vector3::z(), 1.).unwrap();
+    /// let sphere = NurbsSurface3D::<f64>::try_sphere(&Point3::new(1., 2., 3.), &Vector3::z(), 2.).unwrap();
+    /// let shell = Shell::try_from_surfaces(&[sphere, inner], 1e-6).unwrap();
+    /// let properties = shell.try_mass_properties(1e-8).unwrap();
+    /// let cavity = std::f64::consts::PI * 4. / 3.;
+    /// assert_relative_eq!(properties.volume(), volume - cavity, epsilon = 1e-6);
+    /// let centroid = (Point3::new(1., 2., 3.) * volume - Point3::new(1.5, 2., 3.).coords * cavity) / (volume - cavity);
+    /// assert_relative_eq!(*properties.centroid(), centroid, epsilon = 1e-6);
+    /// ```
+    pub fn try_mass_properties(&self, tolerance: T) -> anyhow::Result<MassProperties<T>> {
+        anyhow::ensure!(
+            tolerance > T::zero(),
+            "The tolerance must be greater than zero"
+        );
+        anyhow::ensure!(self.is_closed(), "The shell must be closed");
+        let gauss = GaussLegendre::new(8)?;
+        let share = tolerance / T::from_usize(self.faces().len().max(1)).unwrap();
+
+        // the integrals over the trimmed domain of each face along S_u x S_v,
+        // & the uses of the edges in the directions of the loops turning counterclockwise around S_u x S_v
+        let mut uses = vec![vec![]; self.edges().len()];
+        let integrals = self
+            .faces()
+            .iter()
+            .enumerate()
+            .map(|(f, face)| {
+                let surface = face.surface();
+                face.loops()
+                    .enumerate()
+                    .fold([T::zero(); MOMENTS], |mut sum, (l, face_loop)| {
+                        let (area, values) = loop_integrals(surface, face_loop, &gauss, share);
+                        // the outer loop turns counterclockwise & the inner loops turn clockwise
+                        let counterclockwise = area >= T::zero();
+                        let flipped = (l == 0) != counterclockwise;
+                        let sign = if flipped { -T::one() } else { T::one() };
+                        (0..MOMENTS).for_each(|i| sum[i] += values[i] * sign);
+                        face_loop.trims().iter().for_each(|trim| {
+                            uses[trim.edge()].push((f, trim.is_reversed() != flipped));
+                        });
+                        sum
+                    })
+            })
+            .collect::<Vec<_>>();
+
+        // propagate the orientations of the faces across the shared edges,
+        // where the consistent faces traverse the edge once in each direction
+        let mut adjacency = vec![vec![]; self.faces().len()];
+        for (e, edge_uses) in uses.iter().enumerate() {
+            if self.edges()[e].is_degenerate() {
+                continue;
+            }
+            if let [(f0, r0), (f1, r1)] = edge_uses[..] {
+                adjacency[f0].push((f1, r0 != r1));
+                adjacency[f1].push((f0, r0 != r1));
+            }
+        }
+        let mut signs: Vec<Option<T>> = vec![None; self.faces().len()];
+        let (mut parts, mut oriented) = (vec![], vec![]);
+        for seed in 0..self.faces().len() {
+            if signs[seed].is_some() {
+                continue;
+            }
+            let mut part = vec![seed];
+            signs[seed] = Some(T::one());
+            let mut queue = VecDeque::from([seed]);
+            while let Some(f) = queue.pop_front() {
+                let sign = signs[f].unwrap();
+                for &(g, consistent) in adjacency[f].iter() {
+                    if signs[g].is_none() {
+                        signs[g] = Some(if consistent { sign } else { -sign });
+                        part.push(g);
+                        queue.push_back(g);
+                    }
+                }
+            }
+
+            // orient the integrals outward to bound the solid of the positive volume
+            let mut integral = [T::zero(); MOMENTS];
+            part.iter().for_each(|f| {
+                let sign = signs[*f].unwrap();
+                (0..MOMENTS).for_each(|i| integral[i] += integrals[*f][i] * sign);
+            });
+            if integral[0] < T::zero() {
+                integral.iter_mut().for_each(|x| *x = -*x);
+            }
+            parts.push(part);
+            oriented.push(integral);
+        }
+
+        // the part nested inside the odd number of the other parts bounds a cavity
+        let mut sum = [T::zero(); MOMENTS];
+        let depths = if parts.len() > 1 {
+            nesting_depths(self, &parts, tolerance)?
+        } else {
+            vec![0]
+        };
+        oriented.iter().zip(depths).for_each(|(integral, depth)| {
+            let sign = if depth % 2 == 0 { T::one() } else { -T::one() };
+            (0..MOMENTS).for_each(|i| sum[i] += integral[i] * sign);
+        });
+
+        let volume = sum[0];
+        anyhow::ensure!(volume > T::zero(), "The shell bounds no volume");
+        let c = Point3::new(sum[1], sum[2], sum[3]) / volume;
+        let (xx, yy, zz) = (
+            sum[4] - volume * c.x * c.x,
+            sum[5] - volume * c.y * c.y,
+            sum[6] - volume * c.z * c.z,
+        );
+        let (xy, yz, zx) = (
+            sum[7] - volume * c.x * c.y,
+            sum[8] - volume * c.y * c.z,
+            sum[9] - volume * c.z * c.x,
+        );
+        let inertia = Matrix3::new(yy + zz, -xy, -zx, -xy, zz + xx, -yz, -zx, -yz, xx + yy);
+        Ok(MassProperties::new(volume, c, inertia))
+    }
+}
@@ -0,0 +1,34 @@
+use nalgebra::{Point3, Vector3};
+
+use crate::misc::FloatingPoint;
+
+/// Tessellation of a shell into a single mesh
+/// The vertices on the shared edges are shared between the faces, so the mesh of a closed shell is watertight.
+#[derive(Clone, Debug)]
+pub struct ShellTessellation<T: FloatingPoint> {
+    pub(crate) points: Vec<Point3<T>>,
+    /// The normals averaged over the faces sharing the vertices
+    pub(crate) normals: Vec<Vector3<T>>,
+    pub(crate) faces: Vec<[usize; 3]>,
+    /// The index of the face of the shell for each triangle
+    pub(crate) face_indices: Vec<usize>,
+}
+
+impl<T: FloatingPoint> ShellTessellation<T> {
+    pub fn points(&self) -> &[Point3<T>] {
+        &self.points
+    }
+
+    pub fn normals(&self) -> &[Vector3<T>] {
+        &self.normals
+    }
+
+    pub fn faces(&self) -> &[[usize; 3]] {
+        &self.faces
+    }
+
+    /// Get the index of the face of the shell for each triangle
+    pub fn face_indices(&self) -> &[usize] {
+        &self.face_indices
+    }
+}
@@ -0,0 +1,20 @@
+use nalgebra::Point3;
+
+use crate::misc::FloatingPoint;
+
+/// A vertex of a B-rep shell
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Vertex<T: FloatingPoint> {
+    point: Point3<T>,
+}
+
+impl<T: FloatingPoint> Vertex<T> {
+    pub fn new(point: Point3<T>) -> Self {
+        Self { point }
+    }
+
+    pub fn point(&self) -> &Point3<T> {
+        &self.point
+    }
+}
@@ -1,5 +1,7 @@
 use argmin::{argmin_error, argmin_error_closure, core::*, float};
 
+use super::ClosestParameterResidual;
+
 /// Customized Newton's method for finding the closest parameter on a NURBS curve
 /// Original source: https://argmin-rs.github.io/argmin/argmin/solver/newton/struct.Newton.html
 #[derive(Clone, Copy)]
@@ -10,6 +12,14 @@ pub struct ClosestParameterNewton<F, P> {
     knot_domain: (P, P),
     /// the target curve is closed or not
     closed: bool,
+    /// tolerance for the distance between the point and the curve
+    point_tolerance: F,
+    /// tolerance for the cosine between the tangent and the vector from the point to the curve
+    cosine_tolerance: F,
+    /// tolerance for the change of the parameter
+    parameter_tolerance: F,
+    /// the residuals satisfy the tolerances or not
+    converged: bool,
 }
 
 impl<F, P> ClosestParameterNewton<F, P>
@@ -23,9 +33,31 @@ where
             gamma: float!(1.0),
             knot_domain: domain,
             closed,
+            point_tolerance: F::epsilon(),
+            cosine_tolerance: F::epsilon(),
+            parameter_tolerance: F::epsilon(),
+            converged: false,
         }
     }
 
+    /// Set the tolerance for the distance between the point and the curve
+    pub fn with_point_tolerance(mut self, tolerance: F) -> Self {
+        self.point_tolerance = tolerance;
+        self
+    }
+
+    /// Set the tolerance for the cosine between the tangent and the vector from the point to the curve
+    pub fn with_cosine_tolerance(mut self, tolerance: F) -> Self {
+        self.cosine_tolerance = tolerance;
+        self
+    }
+
+    /// Set the tolerance for the change of the parameter
+    pub fn with_parameter_tolerance(mut self, tolerance: F) -> Self {
+        self.parameter_tolerance = tolerance;
+        self
+    }
+
     /// Set step size gamma
     ///
     /// Gamma must be in `(0, 1]` and defaults to `1`.
@@ -44,7 +76,9 @@ where
 
 impl<O, F> Solver<O, IterState<F, F, (), F, (), F>> for ClosestParameterNewton<F, F>
 where
-    O: Gradient<Param = F, Gradient = F> + Hessian<Param = F, Hessian = F>,
+    O: Gradient<Param = F, Gradient = F>
+        + Hessian<Param = F, Hessian = F>
+        + ClosestParameterResidual<Param = F, Float = F>,
     F: Clone + ArgminFloat,
 {
     const NAME: &'static str = "Closest parameter newton method";
@@ -84,6 +118,9 @@ where
             new_param
         };
 
+        let (distance, cosine) = problem.problem("residual_count", |p| p.residual(&new_param))?;
+        self.converged = distance < self.point_tolerance || cosine < self.cosine_tolerance;
+
         Ok((state.param(new_param), None))
     }
 
@@ -92,10 +129,14 @@ where
             return TerminationStatus::Terminated(TerminationReason::MaxItersReached);
         }
 
+        if self.converged {
+            return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+        }
+
         match (state.get_param(), state.get_prev_param()) {
             (Some(current_param), Some(prev_param)) => {
                 let delta = (*current_param - *prev_param).abs();
-                if delta < F::epsilon() {
+                if delta < self.parameter_tolerance {
                     TerminationStatus::Terminated(TerminationReason::SolverConverged)
                 } else {
                     TerminationStatus::NotTerminated
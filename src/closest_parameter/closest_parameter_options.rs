@@ -0,0 +1,59 @@
+use crate::misc::FloatingPoint;
+
+/// Hyperparameters for the closest parameter solver.
+#[derive(Clone, Debug)]
+pub struct ClosestParameterOptions<T: FloatingPoint> {
+    /// Tolerance for the distance between the given point and the point on the geometry.
+    /// The solver terminates if the point on the geometry coincides with the given point within this tolerance.
+    pub point_tolerance: T,
+    /// Tolerance for the cosine between the derivatives and the vector from the given point to the point on the geometry.
+    /// The solver terminates if the vector is perpendicular to the geometry within this tolerance.
+    pub cosine_tolerance: T,
+    /// Tolerance for the change of the parameter between iterations.
+    pub parameter_tolerance: T,
+    /// Maximum number of iterations for the Newton method.
+    pub max_iters: u64,
+    /// Number of initial guesses to run the Newton method from.
+    /// The Newton method is run from the best seeds found by the subdivision and the global minimum is returned,
+    /// which is robust for the geometry with multiple near-closest regions.
+    pub seeds: usize,
+}
+
+impl<T: FloatingPoint> Default for ClosestParameterOptions<T> {
+    fn default() -> Self {
+        Self {
+            point_tolerance: T::from_f64(1e-10).unwrap(),
+            cosine_tolerance: T::from_f64(1e-10).unwrap(),
+            parameter_tolerance: T::default_epsilon(),
+            max_iters: 16,
+            seeds: 1,
+        }
+    }
+}
+
+impl<T: FloatingPoint> ClosestParameterOptions<T> {
+    pub fn with_point_tolerance(mut self, point_tolerance: T) -> Self {
+        self.point_tolerance = point_tolerance;
+        self
+    }
+
+    pub fn with_cosine_tolerance(mut self, cosine_tolerance: T) -> Self {
+        self.cosine_tolerance = cosine_tolerance;
+        self
+    }
+
+    pub fn with_parameter_tolerance(mut self, parameter_tolerance: T) -> Self {
+        self.parameter_tolerance = parameter_tolerance;
+        self
+    }
+
+    pub fn with_max_iters(mut self, max_iters: u64) -> Self {
+        self.max_iters = max_iters;
+        self
+    }
+
+    pub fn with_seeds(mut self, seeds: usize) -> Self {
+        self.seeds = seeds;
+        self
+    }
+}
@@ -5,6 +5,8 @@ use nalgebra::{
 
 use crate::{curve::nurbs_curve::NurbsCurve, misc::FloatingPoint};
 
+use super::ClosestParameterResidual;
+
 /// Gradient & Hessian provider for finding the closest parameter on a curve to a given point.
 pub struct ClosestParameterProblem<'a, T: FloatingPoint, D: DimName>
 where
@@ -65,3 +67,28 @@ where
         Ok(s0 + s1)
     }
 }
+
+impl<'a, T: FloatingPoint, D: DimName> ClosestParameterResidual
+    for ClosestParameterProblem<'a, T, D>
+where
+    DefaultAllocator: Allocator<D>,
+    D: DimNameSub<U1>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    type Param = T;
+    type Float = T;
+
+    /// | C(u) - P | & | C'(u) * ( C(u) - P ) | / | C'(u) | | C(u) - P |
+    fn residual(&self, param: &Self::Param) -> anyhow::Result<(Self::Float, Self::Float)> {
+        let e = self.curve.rational_derivatives(*param, 1);
+        let d = &e[0] - &self.point.coords;
+        let distance = d.norm();
+        let denom = e[1].norm() * distance;
+        let cosine = if denom > T::zero() {
+            e[1].dot(&d).abs() / denom
+        } else {
+            T::zero()
+        };
+        Ok((distance, cosine))
+    }
+}
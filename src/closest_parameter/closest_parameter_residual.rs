@@ -0,0 +1,9 @@
+/// Trait for the closest parameter problems to evaluate the residuals for the termination criteria.
+pub trait ClosestParameterResidual {
+    type Param;
+    type Float;
+
+    /// Evaluate the distance between the given point and the point on the geometry at the parameter,
+    /// and the cosine between the derivatives and the vector from the given point to the point on the geometry.
+    fn residual(&self, param: &Self::Param) -> anyhow::Result<(Self::Float, Self::Float)>;
+}
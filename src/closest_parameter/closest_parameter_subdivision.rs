@@ -0,0 +1,580 @@
+use nalgebra::{
+    allocator::Allocator, DefaultAllocator, DimName, DimNameDiff, DimNameSub, OPoint, U1,
+};
+
+use crate::{
+    bounding_box::BoundingBox,
+    curve::NurbsCurve,
+    misc::{segment_closest_point, FloatingPoint},
+    surface::NurbsSurface,
+};
+
+/// Maximum depth of the recursive subdivision
+const MAX_SUBDIVISION_DEPTH: usize = 24;
+
+/// A candidate of the closest parameter on a curve
+#[derive(Clone, Debug)]
+pub(crate) struct ClosestParameterCandidate<T> {
+    /// The parameter on the curve
+    pub parameter: T,
+    /// The distance between the given point and the point on the curve at the parameter
+    pub distance: T,
+}
+
+/// Compute the lower bound of the distance between the point and the curve segment
+/// by the distance to the bounding box of the control points (convex hull property)
+fn lower_bound<T: FloatingPoint, D>(
+    point: &OPoint<T, DimNameDiff<D, U1>>,
+    control_points: &[OPoint<T, DimNameDiff<D, U1>>],
+) -> T
+where
+    D: DimName + DimNameSub<U1>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    let n = DimNameDiff::<D, U1>::dim();
+    let mut sum = T::zero();
+    for i in 0..n {
+        let (min, max) = control_points.iter().fold(
+            (control_points[0][i], control_points[0][i]),
+            |(min, max), p| (min.min(p[i]), max.max(p[i])),
+        );
+        let d = if point[i] < min {
+            min - point[i]
+        } else if point[i] > max {
+            point[i] - max
+        } else {
+            T::zero()
+        };
+        sum += d * d;
+    }
+    sum.sqrt()
+}
+
+/// Find candidates of the closest parameter on the curve to the given point
+/// by recursive subdivision of the Bézier segments of the curve.
+/// Segments whose convex hull is farther than the best distance found so far are pruned,
+/// and the remaining flat segments are returned as candidates sorted by the distance.
+pub(crate) fn find_closest_parameter_candidates<T: FloatingPoint, D>(
+    curve: &NurbsCurve<T, D>,
+    point: &OPoint<T, DimNameDiff<D, U1>>,
+) -> anyhow::Result<Vec<ClosestParameterCandidate<T>>>
+where
+    D: DimName + DimNameSub<U1>,
+    DefaultAllocator: Allocator<D>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    let segments = curve.try_decompose_bezier_segments()?;
+
+    let bb = BoundingBox::new_with_points(curve.dehomogenized_control_points());
+    let flatness = bb.size().norm() * T::from_f64(1e-4).unwrap();
+
+    let mut best = T::max_value().unwrap();
+    let mut candidates = vec![];
+    let mut stack: Vec<_> = segments.into_iter().map(|s| (s, 0)).collect();
+
+    while let Some((segment, depth)) = stack.pop() {
+        let control_points = segment.dehomogenized_control_points();
+        if lower_bound::<T, D>(point, &control_points) > best {
+            continue;
+        }
+
+        let (start, end) = segment.knots_domain();
+        let head = &control_points[0];
+        let tail = &control_points[control_points.len() - 1];
+
+        // the end points of the segment are on the curve
+        best = best.min((head - point).norm()).min((tail - point).norm());
+
+        // the deviation of the control polygon from the chord
+        let deviation = control_points
+            .iter()
+            .map(|p| {
+                let (_, q) = segment_closest_point(p, head, tail, start, end);
+                (p - q).norm()
+            })
+            .fold(T::zero(), |a, b| a.max(b));
+
+        if deviation < flatness || depth >= MAX_SUBDIVISION_DEPTH {
+            let (t, _) = segment_closest_point(point, head, tail, start, end);
+            let distance = (curve.point_at(t) - point).norm();
+            best = best.min(distance);
+            candidates.push(ClosestParameterCandidate {
+                parameter: t,
+                distance,
+            });
+        } else {
+            let mid = (start + end) * T::from_f64(0.5).unwrap();
+            let (s0, s1) = segment.try_trim(mid)?;
+
+            // push the farther segment first to visit the nearer one first
+            let d0 = (s0.point_at(s0.knots_domain().0) - point).norm();
+            let d1 = (s1.point_at(s1.knots_domain().1) - point).norm();
+            if d0 < d1 {
+                stack.push((s1, depth + 1));
+                stack.push((s0, depth + 1));
+            } else {
+                stack.push((s0, depth + 1));
+                stack.push((s1, depth + 1));
+            }
+        }
+    }
+
+    candidates.sort_by(|a, b| {
+        a.distance
+            .partial_cmp(&b.distance)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    Ok(candidates)
+}
+
+/// A candidate of the pair of closest parameters on two curves
+#[derive(Clone, Debug)]
+pub(crate) struct ClosestParameterPairCandidate<T> {
+    /// The parameters on the first & second curves
+    pub parameters: (T, T),
+    /// The distance between the points on the curves at the parameters
+    pub distance: T,
+}
+
+/// Compute the lower bound of the distance between two curve segments
+/// by the distance between the bounding boxes of the control points (convex hull property)
+fn pair_lower_bound<T: FloatingPoint, D>(
+    a: &[OPoint<T, DimNameDiff<D, U1>>],
+    b: &[OPoint<T, DimNameDiff<D, U1>>],
+) -> T
+where
+    D: DimName + DimNameSub<U1>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    let n = DimNameDiff::<D, U1>::dim();
+    let bounds = |points: &[OPoint<T, DimNameDiff<D, U1>>], i: usize| {
+        points
+            .iter()
+            .fold((points[0][i], points[0][i]), |(min, max), p| {
+                (min.min(p[i]), max.max(p[i]))
+            })
+    };
+    let mut sum = T::zero();
+    for i in 0..n {
+        let (amin, amax) = bounds(a, i);
+        let (bmin, bmax) = bounds(b, i);
+        let d = (amin - bmax).max(bmin - amax).max(T::zero());
+        sum += d * d;
+    }
+    sum.sqrt()
+}
+
+/// Compute the deviation of the control polygon from the chord of the segment
+pub(crate) fn deviation<T: FloatingPoint, D>(control_points: &[OPoint<T, DimNameDiff<D, U1>>]) -> T
+where
+    D: DimName + DimNameSub<U1>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    let head = &control_points[0];
+    let tail = &control_points[control_points.len() - 1];
+    control_points
+        .iter()
+        .map(|p| {
+            let (_, q) = segment_closest_point(p, head, tail, T::zero(), T::one());
+            (p - q).norm()
+        })
+        .fold(T::zero(), |a, b| a.max(b))
+}
+
+/// Find the closest normalized parameters between two line segments (p0, p1) and (q0, q1)
+pub(crate) fn segments_closest_parameters<T: FloatingPoint, D>(
+    p0: &OPoint<T, D>,
+    p1: &OPoint<T, D>,
+    q0: &OPoint<T, D>,
+    q1: &OPoint<T, D>,
+) -> (T, T)
+where
+    D: DimName,
+    DefaultAllocator: Allocator<D>,
+{
+    let d1 = p1 - p0;
+    let d2 = q1 - q0;
+    let r = p0 - q0;
+    let a = d1.dot(&d1);
+    let e = d2.dot(&d2);
+    let f = d2.dot(&r);
+    let eps = T::default_epsilon();
+    let unit = |x: T| x.max(T::zero()).min(T::one());
+
+    if a <= eps && e <= eps {
+        return (T::zero(), T::zero());
+    }
+    if a <= eps {
+        return (T::zero(), unit(f / e));
+    }
+
+    let c = d1.dot(&r);
+    if e <= eps {
+        return (unit(-c / a), T::zero());
+    }
+
+    let b = d1.dot(&d2);
+    let denom = a * e - b * b;
+    let mut s = if denom > eps {
+        unit((b * f - c * e) / denom)
+    } else {
+        T::zero()
+    };
+    let mut t = (b * s + f) / e;
+    if t < T::zero() {
+        t = T::zero();
+        s = unit(-c / a);
+    } else if t > T::one() {
+        t = T::one();
+        s = unit((b - c) / a);
+    }
+    (s, t)
+}
+
+/// Find candidates of the pair of closest parameters on two curves
+/// by recursive subdivision of the pairs of the Bézier segments of the curves.
+/// Pairs of segments whose convex hulls cannot be closer than the best distance found so far are pruned,
+/// and the candidates are returned sorted by the distance.
+pub(crate) fn find_closest_parameter_pair_candidates<T: FloatingPoint, D>(
+    a: &NurbsCurve<T, D>,
+    b: &NurbsCurve<T, D>,
+) -> anyhow::Result<Vec<ClosestParameterPairCandidate<T>>>
+where
+    D: DimName + DimNameSub<U1>,
+    DefaultAllocator: Allocator<D>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    let sa = a.try_decompose_bezier_segments()?;
+    let sb = b.try_decompose_bezier_segments()?;
+
+    let bb = BoundingBox::new_with_points(
+        a.dehomogenized_control_points()
+            .into_iter()
+            .chain(b.dehomogenized_control_points()),
+    );
+    let flatness = bb.size().norm() * T::from_f64(1e-4).unwrap();
+
+    let mut best = T::max_value().unwrap();
+    let mut candidates = vec![];
+    let mut stack = vec![];
+    for s0 in sa.iter() {
+        for s1 in sb.iter() {
+            stack.push((s0.clone(), s1.clone(), 0));
+        }
+    }
+
+    while let Some((s0, s1, depth)) = stack.pop() {
+        let c0 = s0.dehomogenized_control_points();
+        let c1 = s1.dehomogenized_control_points();
+        // skip the pair which cannot improve the best distance more than the flatness
+        if pair_lower_bound::<T, D>(&c0, &c1) + flatness > best {
+            continue;
+        }
+
+        let (t0, t1) = s0.knots_domain();
+        let (u0, u1) = s1.knots_domain();
+
+        // the end points of the segments are on the curves
+        for (t, p) in [(t0, &c0[0]), (t1, &c0[c0.len() - 1])] {
+            for (u, q) in [(u0, &c1[0]), (u1, &c1[c1.len() - 1])] {
+                let distance = (p - q).norm();
+                if distance < best {
+                    best = distance;
+                    candidates.push(ClosestParameterPairCandidate {
+                        parameters: (t, u),
+                        distance,
+                    });
+                }
+            }
+        }
+
+        let (dev0, dev1) = (deviation::<T, D>(&c0), deviation::<T, D>(&c1));
+        if (dev0 < flatness && dev1 < flatness) || depth >= MAX_SUBDIVISION_DEPTH * 2 {
+            let (s, u) =
+                segments_closest_parameters(&c0[0], &c0[c0.len() - 1], &c1[0], &c1[c1.len() - 1]);
+            let (t, u) = (t0 + (t1 - t0) * s, u0 + (u1 - u0) * u);
+            let distance = (a.point_at(t) - b.point_at(u)).norm();
+            best = best.min(distance);
+            candidates.push(ClosestParameterPairCandidate {
+                parameters: (t, u),
+                distance,
+            });
+        } else {
+            // split the segment deviating more from its chord
+            let (p, q) = if dev0 >= dev1 {
+                let mid = (t0 + t1) * T::from_f64(0.5).unwrap();
+                let (l, r) = s0.try_trim(mid)?;
+                ((l, s1.clone()), (r, s1))
+            } else {
+                let mid = (u0 + u1) * T::from_f64(0.5).unwrap();
+                let (l, r) = s1.try_trim(mid)?;
+                ((s0.clone(), l), (s0, r))
+            };
+
+            // push the farther pair first to visit the nearer one first
+            let dp = pair_lower_bound::<T, D>(
+                &p.0.dehomogenized_control_points(),
+                &p.1.dehomogenized_control_points(),
+            );
+            let dq = pair_lower_bound::<T, D>(
+                &q.0.dehomogenized_control_points(),
+                &q.1.dehomogenized_control_points(),
+            );
+            if dp < dq {
+                stack.push((q.0, q.1, depth + 1));
+                stack.push((p.0, p.1, depth + 1));
+            } else {
+                stack.push((p.0, p.1, depth + 1));
+                stack.push((q.0, q.1, depth + 1));
+            }
+        }
+    }
+
+    candidates.sort_by(|a, b| {
+        a.distance
+            .partial_cmp(&b.distance)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    Ok(candidates)
+}
+
+/// Maximum depth of the recursive subdivision of the pairs of a curve segment and a surface patch
+const MAX_CURVE_SURFACE_SUBDIVISION_DEPTH: usize = 16;
+
+/// A candidate of the pair of closest parameters on a curve and a surface
+#[derive(Clone, Debug)]
+pub(crate) struct CurveSurfaceClosestParameterCandidate<T> {
+    /// The parameter on the curve & the (u, v) parameter on the surface
+    pub parameters: (T, (T, T)),
+    /// The distance between the points on the curve and the surface at the parameters
+    pub distance: T,
+}
+
+/// Compute the deviation of the control net from the bilinear patch spanned by the corners
+fn patch_deviation<T: FloatingPoint, D>(control_points: &[Vec<OPoint<T, DimNameDiff<D, U1>>>]) -> T
+where
+    D: DimName + DimNameSub<U1>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    let rows = control_points.len();
+    let columns = control_points[0].len();
+    let p00 = &control_points[0][0].coords;
+    let p10 = &control_points[rows - 1][0].coords;
+    let p01 = &control_points[0][columns - 1].coords;
+    let p11 = &control_points[rows - 1][columns - 1].coords;
+    let ratio = |i: usize, n: usize| {
+        if n > 1 {
+            T::from_usize(i).unwrap() / T::from_usize(n - 1).unwrap()
+        } else {
+            T::zero()
+        }
+    };
+
+    let mut max = T::zero();
+    for (i, row) in control_points.iter().enumerate() {
+        let s = ratio(i, rows);
+        for (j, p) in row.iter().enumerate() {
+            let t = ratio(j, columns);
+            let bilinear = (p00 * (T::one() - t) + p01 * t) * (T::one() - s)
+                + (p10 * (T::one() - t) + p11 * t) * s;
+            max = max.max((&p.coords - bilinear).norm());
+        }
+    }
+    max
+}
+
+/// Find candidates of the pair of closest parameters on a curve and a surface
+/// by recursive subdivision of the pairs of the Bézier segments of the curve and the Bézier patches of the surface.
+/// Pairs whose convex hulls cannot be closer than the best distance found so far are pruned,
+/// and the candidates are returned sorted by the distance.
+pub(crate) fn find_curve_surface_closest_parameter_candidates<T: FloatingPoint, D>(
+    curve: &NurbsCurve<T, D>,
+    surface: &NurbsSurface<T, D>,
+) -> anyhow::Result<Vec<CurveSurfaceClosestParameterCandidate<T>>>
+where
+    D: DimName + DimNameSub<U1>,
+    DefaultAllocator: Allocator<D>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    let segments = curve.try_decompose_bezier_segments()?;
+    let patches = surface.try_decompose_bezier_patches()?;
+
+    let bb = BoundingBox::new_with_points(
+        curve
+            .dehomogenized_control_points()
+            .into_iter()
+            .chain(surface.dehomogenized_control_points().into_iter().flatten()),
+    );
+    // the candidates are refined by the newton method, so the coarse flatness is enough
+    let flatness = bb.size().norm() * T::from_f64(1e-2).unwrap();
+    let half = T::from_f64(0.5).unwrap();
+
+    let mut best = T::max_value().unwrap();
+    let mut candidates = vec![];
+    let mut stack = vec![];
+    for s in segments.iter() {
+        for p in patches.iter() {
+            stack.push((s.clone(), p.clone(), 0));
+        }
+    }
+
+    while let Some((segment, patch, depth)) = stack.pop() {
+        let c0 = segment.dehomogenized_control_points();
+        let c1 = patch.dehomogenized_control_points();
+        let flat1: Vec<_> = c1.iter().flatten().cloned().collect();
+        if pair_lower_bound::<T, D>(&c0, &flat1) + flatness > best {
+            continue;
+        }
+
+        let (t0, t1) = segment.knots_domain();
+        let (u0, u1) = patch.u_knots_domain();
+        let (v0, v1) = patch.v_knots_domain();
+
+        // sample the corners & the centers as the points on the curve & the surface
+        let ts = [t0, (t0 + t1) * half, t1];
+        let uvs = [
+            (u0, v0),
+            (u1, v0),
+            (u0, v1),
+            (u1, v1),
+            ((u0 + u1) * half, (v0 + v1) * half),
+        ];
+        for t in ts {
+            let p = segment.point_at(t);
+            for (u, v) in uvs {
+                let distance = (&p - patch.point_at(u, v)).norm();
+                if distance < best {
+                    best = distance;
+                    candidates.push(CurveSurfaceClosestParameterCandidate {
+                        parameters: (t, (u, v)),
+                        distance,
+                    });
+                }
+            }
+        }
+
+        let (dev0, dev1) = (deviation::<T, D>(&c0), patch_deviation::<T, D>(&c1));
+        if (dev0 < flatness && dev1 < flatness) || depth >= MAX_CURVE_SURFACE_SUBDIVISION_DEPTH {
+            continue;
+        }
+
+        // split the curve segment or the surface patch deviating more
+        let children = if dev0 >= dev1 {
+            let (l, r) = segment.try_trim((t0 + t1) * half)?;
+            vec![(l, patch.clone()), (r, patch)]
+        } else {
+            let (l, r) = patch.try_split((u0 + u1) * half, false)?;
+            let (ll, lr) = l.try_split((v0 + v1) * half, true)?;
+            let (rl, rr) = r.try_split((v0 + v1) * half, true)?;
+            vec![
+                (segment.clone(), ll),
+                (segment.clone(), lr),
+                (segment.clone(), rl),
+                (segment, rr),
+            ]
+        };
+
+        // push the farther pairs first to visit the nearer ones first
+        let mut children: Vec<_> = children
+            .into_iter()
+            .map(|(s, p)| {
+                let lb = pair_lower_bound::<T, D>(
+                    &s.dehomogenized_control_points(),
+                    &p.dehomogenized_control_points()
+                        .into_iter()
+                        .flatten()
+                        .collect::<Vec<_>>(),
+                );
+                (s, p, lb)
+            })
+            .collect();
+        children.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal));
+        stack.extend(children.into_iter().map(|(s, p, _)| (s, p, depth + 1)));
+    }
+
+    candidates.sort_by(|a, b| {
+        a.distance
+            .partial_cmp(&b.distance)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    Ok(candidates)
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::Point2;
+
+    use crate::curve::NurbsCurve2D;
+
+    #[test]
+    fn closest_parameter_on_tight_loop() {
+        // a curve with a tight loop near the query point
+        let points = vec![
+            Point2::new(-2.0, 0.0),
+            Point2::new(0.0, 0.0),
+            Point2::new(0.1, 0.1),
+            Point2::new(0.0, 0.2),
+            Point2::new(-0.1, 0.1),
+            Point2::new(0.0, 0.0),
+            Point2::new(2.0, 0.0),
+        ];
+        let curve = NurbsCurve2D::try_interpolate(&points, 3).unwrap();
+        let query = Point2::new(0.02, 0.12);
+
+        let (start, end) = curve.knots_domain();
+        let samples = 20000;
+        let brute = (0..=samples)
+            .map(|i| {
+                let t = start + (end - start) * (i as f64) / (samples as f64);
+                (curve.point_at(t) - query).norm()
+            })
+            .fold(f64::MAX, f64::min);
+
+        let t = curve.find_closest_parameter(&query).unwrap();
+        let d = (curve.point_at(t) - query).norm();
+        assert!(d <= brute + 1e-6);
+    }
+
+    #[test]
+    fn closest_parameters_between_curves() {
+        let a = NurbsCurve2D::try_interpolate(
+            &[
+                Point2::new(-2.0, 0.0),
+                Point2::new(-1.0, 1.0),
+                Point2::new(0.0, 0.3),
+                Point2::new(1.0, 1.2),
+                Point2::new(2.0, 0.0),
+            ],
+            3,
+        )
+        .unwrap();
+        let b = NurbsCurve2D::try_interpolate(
+            &[
+                Point2::new(-2.0, 2.5),
+                Point2::new(-0.5, 1.6),
+                Point2::new(0.5, 2.4),
+                Point2::new(2.0, 1.8),
+            ],
+            3,
+        )
+        .unwrap();
+
+        let samples = 1000;
+        let sample = |c: &NurbsCurve2D<f64>| {
+            let (start, end) = c.knots_domain();
+            (0..=samples)
+                .map(|i| c.point_at(start + (end - start) * (i as f64) / (samples as f64)))
+                .collect::<Vec<_>>()
+        };
+        let (pa, pb) = (sample(&a), sample(&b));
+        let brute = pa
+            .iter()
+            .flat_map(|p| pb.iter().map(move |q| (p - q).norm()))
+            .fold(f64::MAX, f64::min);
+
+        let closest = a.closest_parameters(&b).unwrap();
+        assert!(*closest.distance() <= brute + 1e-9);
+        let (p, t) = closest.a();
+        assert!((a.point_at(*t) - p).norm() < 1e-12);
+    }
+}
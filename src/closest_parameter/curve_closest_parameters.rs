@@ -0,0 +1,28 @@
+/// A struct representing the pair of closest points between two curves.
+#[derive(Debug, Clone)]
+pub struct CurveClosestParameters<P, T> {
+    /// The point & parameter of the first curve.
+    a: (P, T),
+    /// The point & parameter of the second curve.
+    b: (P, T),
+    /// The distance between the points.
+    distance: T,
+}
+
+impl<P, T> CurveClosestParameters<P, T> {
+    pub fn new(a: (P, T), b: (P, T), distance: T) -> Self {
+        Self { a, b, distance }
+    }
+
+    pub fn a(&self) -> &(P, T) {
+        &self.a
+    }
+
+    pub fn b(&self) -> &(P, T) {
+        &self.b
+    }
+
+    pub fn distance(&self) -> &T {
+        &self.distance
+    }
+}
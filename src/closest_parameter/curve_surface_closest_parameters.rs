@@ -0,0 +1,32 @@
+/// A struct representing the pair of closest points between a curve and a surface.
+#[derive(Debug, Clone)]
+pub struct CurveSurfaceClosestParameters<P, T> {
+    /// The point & parameter of the curve.
+    curve: (P, T),
+    /// The point & (u, v) parameter of the surface.
+    surface: (P, (T, T)),
+    /// The distance between the points.
+    distance: T,
+}
+
+impl<P, T> CurveSurfaceClosestParameters<P, T> {
+    pub fn new(curve: (P, T), surface: (P, (T, T)), distance: T) -> Self {
+        Self {
+            curve,
+            surface,
+            distance,
+        }
+    }
+
+    pub fn curve(&self) -> &(P, T) {
+        &self.curve
+    }
+
+    pub fn surface(&self) -> &(P, (T, T)) {
+        &self.surface
+    }
+
+    pub fn distance(&self) -> &T {
+        &self.distance
+    }
+}
@@ -1,4 +1,17 @@
 pub mod closest_parameter_newton;
+pub mod closest_parameter_options;
 pub mod closest_parameter_problem;
+pub mod closest_parameter_residual;
+pub(crate) mod closest_parameter_subdivision;
+pub mod curve_closest_parameters;
+pub mod curve_surface_closest_parameters;
+pub mod surface_closest_parameter_newton;
+pub mod surface_closest_parameter_problem;
 pub use closest_parameter_newton::*;
+pub use closest_parameter_options::*;
 pub use closest_parameter_problem::*;
+pub use closest_parameter_residual::*;
+pub use curve_closest_parameters::*;
+pub use curve_surface_closest_parameters::*;
+pub use surface_closest_parameter_newton::*;
+pub use surface_closest_parameter_problem::*;
@@ -0,0 +1,160 @@
+use argmin::{argmin_error_closure, core::*, float};
+use nalgebra::{Matrix2, Vector2};
+
+use crate::misc::FloatingPoint;
+
+use super::ClosestParameterResidual;
+
+/// Customized Newton's method for finding the closest parameter on a NURBS surface
+#[derive(Clone, Copy)]
+pub struct SurfaceClosestParameterNewton<F> {
+    /// gamma
+    gamma: F,
+    /// domain of the parameter in u & v directions
+    knot_domain: ((F, F), (F, F)),
+    /// the target surface is closed or not in u & v directions
+    closed: (bool, bool),
+    /// tolerance for the distance between the point and the surface
+    point_tolerance: F,
+    /// tolerance for the cosine between the derivatives and the vector from the point to the surface
+    cosine_tolerance: F,
+    /// tolerance for the change of the parameter
+    parameter_tolerance: F,
+    /// the residuals satisfy the tolerances or not
+    converged: bool,
+}
+
+impl<F> SurfaceClosestParameterNewton<F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`SurfaceClosestParameterNewton`]
+    pub fn new(domain: ((F, F), (F, F)), closed: (bool, bool)) -> Self {
+        SurfaceClosestParameterNewton {
+            gamma: float!(1.0),
+            knot_domain: domain,
+            closed,
+            point_tolerance: F::epsilon(),
+            cosine_tolerance: F::epsilon(),
+            parameter_tolerance: F::epsilon(),
+            converged: false,
+        }
+    }
+
+    /// Set the tolerance for the distance between the point and the surface
+    pub fn with_point_tolerance(mut self, tolerance: F) -> Self {
+        self.point_tolerance = tolerance;
+        self
+    }
+
+    /// Set the tolerance for the cosine between the derivatives and the vector from the point to the surface
+    pub fn with_cosine_tolerance(mut self, tolerance: F) -> Self {
+        self.cosine_tolerance = tolerance;
+        self
+    }
+
+    /// Set the tolerance for the change of the parameter
+    pub fn with_parameter_tolerance(mut self, tolerance: F) -> Self {
+        self.parameter_tolerance = tolerance;
+        self
+    }
+}
+
+/// Constrain the parameter to the domain
+/// if the domain is closed, the parameter is wrapped around the domain
+fn constrain<F: ArgminFloat>(t: F, domain: (F, F), closed: bool) -> F {
+    if t < domain.0 {
+        if closed {
+            domain.1 - (domain.0 - t)
+        } else {
+            domain.0
+        }
+    } else if t > domain.1 {
+        if closed {
+            domain.0 + (t - domain.1)
+        } else {
+            domain.1
+        }
+    } else {
+        t
+    }
+}
+
+impl<O, F> Solver<O, IterState<Vector2<F>, Vector2<F>, (), Matrix2<F>, (), F>>
+    for SurfaceClosestParameterNewton<F>
+where
+    O: Gradient<Param = Vector2<F>, Gradient = Vector2<F>>
+        + Hessian<Param = Vector2<F>, Hessian = Matrix2<F>>
+        + ClosestParameterResidual<Param = Vector2<F>, Float = F>,
+    F: FloatingPoint + ArgminFloat,
+{
+    const NAME: &'static str = "Surface closest parameter newton method";
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<Vector2<F>, Vector2<F>, (), Matrix2<F>, (), F>,
+    ) -> Result<
+        (
+            IterState<Vector2<F>, Vector2<F>, (), Matrix2<F>, (), F>,
+            Option<KV>,
+        ),
+        Error,
+    > {
+        let param = state.get_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`SurfaceClosestParameterNewton` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+
+        let grad = problem.gradient(param)?;
+        let hessian = problem.hessian(param)?;
+        let delta = match hessian.lu().solve(&grad) {
+            Some(delta) => delta,
+            None => {
+                return Ok((
+                    state.terminate_with(TerminationReason::SolverConverged),
+                    None,
+                ))
+            }
+        };
+        let new_param = param - delta * self.gamma;
+
+        let new_param = Vector2::new(
+            constrain(new_param.x, self.knot_domain.0, self.closed.0),
+            constrain(new_param.y, self.knot_domain.1, self.closed.1),
+        );
+
+        let (distance, cosine) = problem.problem("residual_count", |p| p.residual(&new_param))?;
+        self.converged = distance < self.point_tolerance || cosine < self.cosine_tolerance;
+
+        Ok((state.param(new_param), None))
+    }
+
+    fn terminate(
+        &mut self,
+        state: &IterState<Vector2<F>, Vector2<F>, (), Matrix2<F>, (), F>,
+    ) -> TerminationStatus {
+        if state.iter > state.max_iters {
+            return TerminationStatus::Terminated(TerminationReason::MaxItersReached);
+        }
+
+        if self.converged {
+            return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+        }
+
+        match (state.get_param(), state.get_prev_param()) {
+            (Some(current_param), Some(prev_param)) => {
+                let delta = (current_param - prev_param).norm();
+                if delta < self.parameter_tolerance {
+                    TerminationStatus::Terminated(TerminationReason::SolverConverged)
+                } else {
+                    TerminationStatus::NotTerminated
+                }
+            }
+            _ => TerminationStatus::NotTerminated,
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use argmin::core::{Gradient, Hessian};
+use nalgebra::{
+    allocator::Allocator, DefaultAllocator, DimName, DimNameDiff, DimNameSub, Matrix2, OPoint,
+    Vector2, U1,
+};
+
+use crate::{misc::FloatingPoint, surface::NurbsSurface};
+
+use super::ClosestParameterResidual;
+
+/// Gradient & Hessian provider for finding the closest parameter on a surface to a given point.
+pub struct SurfaceClosestParameterProblem<'a, T: FloatingPoint, D: DimName>
+where
+    DefaultAllocator: Allocator<D>,
+    D: DimNameSub<U1>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    /// The point to find the closest parameter to.
+    point: &'a OPoint<T, DimNameDiff<D, U1>>,
+    /// The surface to find the closest parameter on.
+    surface: &'a NurbsSurface<T, D>,
+}
+
+impl<'a, T: FloatingPoint, D: DimName> SurfaceClosestParameterProblem<'a, T, D>
+where
+    DefaultAllocator: Allocator<D>,
+    D: DimNameSub<U1>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    pub fn new(point: &'a OPoint<T, DimNameDiff<D, U1>>, surface: &'a NurbsSurface<T, D>) -> Self {
+        SurfaceClosestParameterProblem { point, surface }
+    }
+}
+
+impl<'a, T: FloatingPoint, D: DimName> Gradient for SurfaceClosestParameterProblem<'a, T, D>
+where
+    DefaultAllocator: Allocator<D>,
+    D: DimNameSub<U1>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    type Param = Vector2<T>;
+    type Gradient = Vector2<T>;
+
+    /// ( Su(u, v) * ( S(u, v) - P ), Sv(u, v) * ( S(u, v) - P ) )
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, anyhow::Error> {
+        let e = self.surface.rational_derivatives(param.x, param.y, 1);
+        let d = &e[0][0] - &self.point.coords;
+        Ok(Vector2::new(e[1][0].dot(&d), e[0][1].dot(&d)))
+    }
+}
+
+impl<'a, T: FloatingPoint, D: DimName> Hessian for SurfaceClosestParameterProblem<'a, T, D>
+where
+    DefaultAllocator: Allocator<D>,
+    D: DimNameSub<U1>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    type Param = Vector2<T>;
+    type Hessian = Matrix2<T>;
+
+    /// | Suu * ( S - P ) + Su * Su, Suv * ( S - P ) + Su * Sv |
+    /// | Suv * ( S - P ) + Su * Sv, Svv * ( S - P ) + Sv * Sv |
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, anyhow::Error> {
+        let e = self.surface.rational_derivatives(param.x, param.y, 2);
+        let d = &e[0][0] - &self.point.coords;
+        let su = &e[1][0];
+        let sv = &e[0][1];
+        let uu = e[2][0].dot(&d) + su.dot(su);
+        let uv = e[1][1].dot(&d) + su.dot(sv);
+        let vv = e[0][2].dot(&d) + sv.dot(sv);
+        Ok(Matrix2::new(uu, uv, uv, vv))
+    }
+}
+
+impl<'a, T: FloatingPoint, D: DimName> ClosestParameterResidual
+    for SurfaceClosestParameterProblem<'a, T, D>
+where
+    DefaultAllocator: Allocator<D>,
+    D: DimNameSub<U1>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    type Param = Vector2<T>;
+    type Float = T;
+
+    /// | S(u, v) - P | & the larger cosine of Su & Sv against ( S(u, v) - P )
+    fn residual(&self, param: &Self::Param) -> anyhow::Result<(Self::Float, Self::Float)> {
+        let e = self.surface.rational_derivatives(param.x, param.y, 1);
+        let d = &e[0][0] - &self.point.coords;
+        let distance = d.norm();
+        let cosine = |v: &nalgebra::OVector<T, DimNameDiff<D, U1>>| {
+            let denom = v.norm() * distance;
+            if denom > T::zero() {
+                v.dot(&d).abs() / denom
+            } else {
+                T::zero()
+            }
+        };
+        Ok((distance, cosine(&e[1][0]).max(cosine(&e[0][1]))))
+    }
+}
@@ -0,0 +1,204 @@
+use gauss_quad::GaussLegendre;
+use nalgebra::{
+    allocator::Allocator, DefaultAllocator, DimName, DimNameDiff, DimNameSub, OPoint, U1,
+};
+
+use crate::{curve::NurbsCurve, misc::FloatingPoint};
+
+use super::CurveLengthParameter;
+
+/// A bidirectional mapping between the parameter & the arc length of a curve
+/// The domain is subdivided adaptively until the Gauss-Legendre quadrature of each interval agrees with the sum over its halves within the tolerance.
+/// # Example
+/// ```
+/// use curvo::prelude::*;
+/// use nalgebra::{Point2, Vector2};
+/// use approx::assert_relative_eq;
+///
+/// let circle = NurbsCurve2D::try_circle(&Point2::origin(), &Vector2::x(), &Vector2::y(), 1.).unwrap();
+/// let map = circle.try_arc_length_map(1e-8).unwrap();
+/// let total = std::f64::consts::TAU;
+/// assert_relative_eq!(map.length(), total, epsilon = 1e-8);
+///
+/// // the point at a quarter of the length is at 90 degrees
+/// let p = map.point_at_length(total / 4.);
+/// assert_relative_eq!(p, Point2::new(0., 1.), epsilon = 1e-6);
+///
+/// // the mappings are inverse to each other
+/// let t = map.parameter_at_length(1.);
+/// assert_relative_eq!(map.length_at(t), 1., epsilon = 1e-8);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ArcLengthMap<T: FloatingPoint, D: DimName>
+where
+    DefaultAllocator: Allocator<D>,
+{
+    curve: NurbsCurve<T, D>,
+    /// The breakpoints of the subdivided intervals with the accumulated lengths
+    samples: Vec<CurveLengthParameter<T>>,
+    /// The distinct knots bounding the spans with the accumulated lengths
+    spans: Vec<CurveLengthParameter<T>>,
+    tolerance: T,
+    gauss: GaussLegendre,
+}
+
+impl<T: FloatingPoint, D: DimName> ArcLengthMap<T, D>
+where
+    D: DimNameSub<U1>,
+    DefaultAllocator: Allocator<D>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    /// Build the mapping of the curve with the tolerance of the arc length
+    pub fn try_new(curve: &NurbsCurve<T, D>, tolerance: T) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            tolerance > T::zero(),
+            "The tolerance must be greater than zero"
+        );
+        let gauss = GaussLegendre::new(8)?;
+        let (start, end) = curve.knots_domain();
+        let domain = end - start;
+
+        // the knots are the breakpoints of the smoothness, so each span is integrated separately
+        let mut breaks = curve
+            .knots()
+            .iter()
+            .filter(|k| **k > start && **k < end)
+            .copied()
+            .collect::<Vec<_>>();
+        breaks.dedup();
+        breaks.insert(0, start);
+        breaks.push(end);
+
+        let mut map = Self {
+            curve: curve.clone(),
+            samples: vec![CurveLengthParameter::new(start, T::zero())],
+            spans: vec![CurveLengthParameter::new(start, T::zero())],
+            tolerance,
+            gauss,
+        };
+        for w in breaks.windows(2) {
+            let (a, b) = (w[0], w[1]);
+            let whole = map.integrate(a, b);
+            map.subdivide(a, b, whole, tolerance * (b - a) / domain, 0);
+            map.spans.push(*map.samples.last().unwrap());
+        }
+        Ok(map)
+    }
+
+    /// Integrate the speed of the curve over the interval
+    fn integrate(&self, a: T, b: T) -> T {
+        let sum = self
+            .gauss
+            .integrate(a.to_f64().unwrap(), b.to_f64().unwrap(), |x| {
+                let derivs = self.curve.rational_derivatives(T::from_f64(x).unwrap(), 1);
+                derivs[1].norm().to_f64().unwrap()
+            });
+        T::from_f64(sum).unwrap()
+    }
+
+    fn subdivide(&mut self, a: T, b: T, whole: T, tolerance: T, depth: usize) {
+        let mid = (a + b) * T::from_f64(0.5).unwrap();
+        let left = self.integrate(a, mid);
+        let right = self.integrate(mid, b);
+        // the tolerance is bounded below by the precision of the scalar type
+        let tolerance =
+            tolerance.max(whole.abs() * T::default_epsilon() * T::from_usize(64).unwrap());
+        if (left + right - whole).abs() <= tolerance || depth >= 24 {
+            let acc = self.samples.last().unwrap().length();
+            self.samples
+                .push(CurveLengthParameter::new(b, acc + left + right));
+        } else {
+            let half = tolerance * T::from_f64(0.5).unwrap();
+            self.subdivide(a, mid, left, half, depth + 1);
+            self.subdivide(mid, b, right, half, depth + 1);
+        }
+    }
+
+    pub fn curve(&self) -> &NurbsCurve<T, D> {
+        &self.curve
+    }
+
+    /// The breakpoints of the subdivided intervals with the arc lengths from the start of the curve
+    pub fn samples(&self) -> &[CurveLengthParameter<T>] {
+        &self.samples
+    }
+
+    /// The distinct knots bounding the spans of the curve with the arc lengths from the start of the curve
+    pub fn spans(&self) -> &[CurveLengthParameter<T>] {
+        &self.spans
+    }
+
+    /// The arc length of each span between the distinct knots
+    pub fn span_lengths(&self) -> Vec<T> {
+        self.spans
+            .windows(2)
+            .map(|w| w[1].length() - w[0].length())
+            .collect()
+    }
+
+    /// The total arc length of the curve
+    pub fn length(&self) -> T {
+        self.samples.last().unwrap().length()
+    }
+
+    /// Compute the arc length from the start of the curve to the parameter
+    pub fn length_at(&self, t: T) -> T {
+        let (start, end) = self.curve.knots_domain();
+        let t = t.clamp(start, end);
+        let i = self
+            .samples
+            .partition_point(|s| s.parameter() <= t)
+            .clamp(1, self.samples.len() - 1);
+        let s = &self.samples[i - 1];
+        s.length() + self.integrate(s.parameter(), t)
+    }
+
+    /// Compute the arc length between the parameters regardless of their order
+    pub fn length_between(&self, t0: T, t1: T) -> T {
+        (self.length_at(t1) - self.length_at(t0)).abs()
+    }
+
+    /// Find the parameter at the arc length from the start of the curve
+    /// The length is clamped to the total length of the curve.
+    pub fn parameter_at_length(&self, length: T) -> T {
+        let length = length.clamp(T::zero(), self.length());
+        let i = self
+            .samples
+            .partition_point(|s| s.length() <= length)
+            .clamp(1, self.samples.len() - 1);
+        let (s0, s1) = (&self.samples[i - 1], &self.samples[i]);
+        let (mut lo, mut hi) = (s0.parameter(), s1.parameter());
+        let interval = s1.length() - s0.length();
+        if interval <= T::zero() {
+            return lo;
+        }
+
+        // Newton's method safeguarded by the bisection
+        let target = length - s0.length();
+        let mut t = lo + (hi - lo) * target / interval;
+        for _ in 0..64 {
+            let f = self.integrate(s0.parameter(), t) - target;
+            if f.abs() <= self.tolerance {
+                break;
+            }
+            if f > T::zero() {
+                hi = t;
+            } else {
+                lo = t;
+            }
+            let speed = self.curve.rational_derivatives(t, 1)[1].norm();
+            let next = t - f / speed;
+            t = if speed > T::zero() && next > lo && next < hi {
+                next
+            } else {
+                (lo + hi) * T::from_f64(0.5).unwrap()
+            };
+        }
+        t
+    }
+
+    /// Evaluate the point at the arc length from the start of the curve
+    pub fn point_at_length(&self, length: T) -> OPoint<T, DimNameDiff<D, U1>> {
+        self.curve.point_at(self.parameter_at_length(length))
+    }
+}
@@ -0,0 +1,453 @@
+use nalgebra::{
+    allocator::Allocator, Const, DefaultAllocator, DimName, DimNameDiff, DimNameSub, OMatrix,
+    OPoint, U1,
+};
+
+use crate::{
+    curve::{curve_fillet::try_fillet_between, CurveChamfer, NurbsCurve},
+    misc::{Deformable, FloatingPoint, Invertible, SpaceDeformation, Transformable},
+};
+
+/// A struct representing a curve composed of connected NURBS curve spans
+/// The end point of each span is connected to the start point of the next span
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "NurbsCurve<T, D>: serde::Serialize",
+        deserialize = "NurbsCurve<T, D>: serde::Deserialize<'de>"
+    ))
+)]
+pub struct CompoundCurve<T: FloatingPoint, D: DimName>
+where
+    DefaultAllocator: Allocator<D>,
+{
+    spans: Vec<NurbsCurve<T, D>>,
+}
+
+/// 2D compound curve alias
+pub type CompoundCurve2D<T> = CompoundCurve<T, Const<3>>;
+/// 3D compound curve alias
+pub type CompoundCurve3D<T> = CompoundCurve<T, Const<4>>;
+
+impl<T: FloatingPoint, D: DimName> CompoundCurve<T, D>
+where
+    D: DimNameSub<U1>,
+    DefaultAllocator: Allocator<D>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    /// Create a new compound curve from connected spans
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::Point2;
+    ///
+    /// let s0 = NurbsCurve2D::polyline(&[Point2::new(0., 0.), Point2::new(1., 0.)]);
+    /// let s1 = NurbsCurve2D::polyline(&[Point2::new(1., 0.), Point2::new(1., 1.)]);
+    /// let compound = CompoundCurve2D::try_new(vec![s0.clone(), s1.clone()]);
+    /// assert!(compound.is_ok());
+    ///
+    /// // disconnected spans are rejected
+    /// let compound = CompoundCurve2D::try_new(vec![s1, s0]);
+    /// assert!(compound.is_err());
+    /// ```
+    pub fn try_new(spans: Vec<NurbsCurve<T, D>>) -> anyhow::Result<Self> {
+        Self::try_new_with_tolerance(spans, T::from_f64(1e-5).unwrap())
+    }
+
+    /// Create a new compound curve from connected spans with a tolerance for the connection
+    pub fn try_new_with_tolerance(
+        spans: Vec<NurbsCurve<T, D>>,
+        tolerance: T,
+    ) -> anyhow::Result<Self> {
+        if spans.is_empty() {
+            anyhow::bail!("Compound curve requires at least one span");
+        }
+
+        for (i, w) in spans.windows(2).enumerate() {
+            let end = w[0].point_at(w[0].knots_domain().1);
+            let start = w[1].point_at(w[1].knots_domain().0);
+            let d = (end - start).norm();
+            if d > tolerance {
+                anyhow::bail!(
+                    "Span {} is not connected to the next span (distance: {})",
+                    i,
+                    d
+                );
+            }
+        }
+
+        Ok(Self { spans })
+    }
+
+    pub fn spans(&self) -> &[NurbsCurve<T, D>] {
+        &self.spans
+    }
+
+    pub fn spans_mut(&mut self) -> &mut [NurbsCurve<T, D>] {
+        &mut self.spans
+    }
+
+    pub fn into_spans(self) -> Vec<NurbsCurve<T, D>> {
+        self.spans
+    }
+
+    /// Get the knot domain of the compound curve
+    /// The parameter of the compound curve is the concatenation of the parameters of the spans
+    pub fn knots_domain(&self) -> (T, T) {
+        let start = self.spans[0].knots_domain().0;
+        let interval = self
+            .spans
+            .iter()
+            .fold(T::zero(), |acc, s| acc + s.knots_domain_interval());
+        (start, start + interval)
+    }
+
+    /// Find the span index and the local parameter of the span at the given parameter
+    pub fn find_span(&self, t: T) -> (usize, T) {
+        let mut offset = self.spans[0].knots_domain().0;
+        let last = self.spans.len() - 1;
+        for (i, span) in self.spans.iter().enumerate() {
+            let (d0, d1) = span.knots_domain();
+            let interval = d1 - d0;
+            if t <= offset + interval || i == last {
+                let local = nalgebra::clamp(t - offset + d0, d0, d1);
+                return (i, local);
+            }
+            offset += interval;
+        }
+        unreachable!()
+    }
+
+    /// Evaluate the compound curve at the given parameter
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::Point2;
+    /// use approx::assert_relative_eq;
+    ///
+    /// let s0 = NurbsCurve2D::polyline(&[Point2::new(0., 0.), Point2::new(1., 0.)]);
+    /// let s1 = NurbsCurve2D::polyline(&[Point2::new(1., 0.), Point2::new(1., 1.)]);
+    /// let compound = CompoundCurve2D::try_new(vec![s0, s1]).unwrap();
+    /// let (start, end) = compound.knots_domain();
+    /// assert_relative_eq!(compound.point_at(start), Point2::new(0., 0.));
+    /// assert_relative_eq!(compound.point_at((start + end) * 0.5), Point2::new(1., 0.));
+    /// assert_relative_eq!(compound.point_at(end), Point2::new(1., 1.));
+    /// ```
+    pub fn point_at(&self, t: T) -> OPoint<T, DimNameDiff<D, U1>> {
+        let (i, local) = self.find_span(t);
+        self.spans[i].point_at(local)
+    }
+
+    /// Check if the compound curve is closed or not
+    pub fn is_closed(&self, tolerance: Option<T>) -> bool {
+        let tol = tolerance.unwrap_or(T::from_f64(1e-5).unwrap());
+        let first = &self.spans[0];
+        let last = &self.spans[self.spans.len() - 1];
+        let start = first.point_at(first.knots_domain().0);
+        let end = last.point_at(last.knots_domain().1);
+        (start - end).norm() < tol
+    }
+
+    /// Cut the corners of the compound curve with the straight chamfers
+    /// The corners are the joints of the spans & the knots inside the spans where the tangent of the curve turns,
+    /// including the joint of the ends if the curve is closed. The lengths of the chamfer are measured along the curves.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::Point2;
+    /// use approx::assert_relative_eq;
+    ///
+    /// let polyline = NurbsCurve2D::polyline(&[Point2::new(0., 0.), Point2::new(2., 0.), Point2::new(2., 2.)]);
+    /// let compound = CompoundCurve2D::from(polyline);
+    ///
+    /// // the corner is cut by the distances along both sides
+    /// let chamfered = compound.try_chamfer(&CurveChamfer::Distances(0.5, 1.)).unwrap();
+    /// let spans = chamfered.spans();
+    /// assert_eq!(spans.len(), 3);
+    /// let (s, e) = spans[1].knots_domain();
+    /// assert_relative_eq!(spans[1].point_at(s), Point2::new(1.5, 0.), epsilon = 1e-8);
+    /// assert_relative_eq!(spans[1].point_at(e), Point2::new(2., 1.), epsilon = 1e-8);
+    ///
+    /// // the chamfer at 45 degrees cuts the same length on both sides of the right angle
+    /// let chamfered = compound.try_chamfer(&CurveChamfer::DistanceAngle(0.5, std::f64::consts::FRAC_PI_4)).unwrap();
+    /// let spans = chamfered.spans();
+    /// let (_, e) = spans[1].knots_domain();
+    /// assert_relative_eq!(spans[1].point_at(e), Point2::new(2., 0.5), epsilon = 1e-8);
+    ///
+    /// // all the four corners of the closed rectangle are cut
+    /// let rectangle = NurbsCurve2D::polyline(&[
+    ///     Point2::new(0., 0.),
+    ///     Point2::new(2., 0.),
+    ///     Point2::new(2., 1.),
+    ///     Point2::new(0., 1.),
+    ///     Point2::new(0., 0.),
+    /// ]);
+    /// let chamfered = CompoundCurve2D::from(rectangle).try_chamfer(&CurveChamfer::Distances(0.25, 0.25)).unwrap();
+    /// assert_eq!(chamfered.spans().len(), 8);
+    /// assert!(chamfered.is_closed(None));
+    /// ```
+    pub fn try_chamfer(&self, chamfer: &CurveChamfer<T>) -> anyhow::Result<Self> {
+        let spans = self.try_split_at_corners()?;
+        let n = spans.len();
+        let length_tolerance = T::from_f64(1e-8).unwrap();
+        let maps = spans
+            .iter()
+            .map(|s| s.try_arc_length_map(length_tolerance))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        // the parameters of the ends of each span after cutting the corners
+        let mut ranges = spans.iter().map(|s| s.knots_domain()).collect::<Vec<_>>();
+        let mut corners = vec![false; n];
+        for (i, cos) in self.corners(&spans) {
+            let j = (i + 1) % n;
+            // the interior angle between the curves at the corner
+            let angle = T::pi() - cos.acos();
+            let (d0, d1) = chamfer.distances(angle);
+            anyhow::ensure!(
+                d0 > T::zero() && d1 > T::zero(),
+                "The chamfer at the corner {} has the non-positive distance",
+                i
+            );
+            ranges[i].1 = maps[i].parameter_at_length(maps[i].length() - d0);
+            ranges[j].0 = maps[j].parameter_at_length(d1);
+            corners[i] = true;
+        }
+        anyhow::ensure!(
+            ranges.iter().all(|(s, e)| s < e),
+            "The chamfers overlap each other on a span"
+        );
+
+        let line = |a: OPoint<T, DimNameDiff<D, U1>>, b: OPoint<T, DimNameDiff<D, U1>>| {
+            let homogeneous = |p: OPoint<T, DimNameDiff<D, U1>>| {
+                let mut h = OPoint::<T, D>::origin();
+                p.iter().enumerate().for_each(|(i, v)| h[i] = *v);
+                h[D::dim() - 1] = T::one();
+                h
+            };
+            NurbsCurve::try_new(
+                1,
+                vec![homogeneous(a), homogeneous(b)],
+                vec![T::zero(), T::zero(), T::one(), T::one()],
+            )
+        };
+        let joints = corners
+            .iter()
+            .enumerate()
+            .map(|(i, corner)| {
+                if *corner {
+                    let j = (i + 1) % n;
+                    line(
+                        spans[i].point_at(ranges[i].1),
+                        spans[j].point_at(ranges[j].0),
+                    )
+                    .map(Some)
+                } else {
+                    Ok(None)
+                }
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Self::try_join_trimmed(&spans, &ranges, joints)
+    }
+
+    /// Round the corners of the compound curve with the circular arcs of the radius
+    /// The corners are found in the same way as [`CompoundCurve::try_chamfer`].
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::Point2;
+    /// use approx::assert_relative_eq;
+    ///
+    /// let rectangle = NurbsCurve2D::polyline(&[
+    ///     Point2::new(0., 0.),
+    ///     Point2::new(2., 0.),
+    ///     Point2::new(2., 1.),
+    ///     Point2::new(0., 1.),
+    ///     Point2::new(0., 0.),
+    /// ]);
+    /// let filleted = CompoundCurve2D::from(rectangle).try_fillet(0.25).unwrap();
+    /// assert_eq!(filleted.spans().len(), 8);
+    /// assert!(filleted.is_closed(None));
+    /// let arc = &filleted.spans()[1];
+    /// let (s, e) = arc.knots_domain();
+    /// assert_relative_eq!(arc.point_at(s), Point2::new(1.75, 0.), epsilon = 1e-6);
+    /// assert_relative_eq!(arc.point_at(e), Point2::new(2., 0.25), epsilon = 1e-6);
+    /// ```
+    pub fn try_fillet(&self, radius: T) -> anyhow::Result<Self> {
+        anyhow::ensure!(radius > T::zero(), "The radius must be greater than zero");
+        self.try_fillet_with(|_, _| radius)
+    }
+
+    /// Round the corners of the compound curve with the radius varying over the corners
+    /// The function receives the index of the corner in the order along the curve & the point of the corner,
+    /// and the corner is left sharp if the returned radius is not positive.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::Point2;
+    /// use approx::assert_relative_eq;
+    ///
+    /// let polyline = NurbsCurve2D::polyline(&[
+    ///     Point2::new(0., 0.),
+    ///     Point2::new(2., 0.),
+    ///     Point2::new(2., 2.),
+    ///     Point2::new(4., 2.),
+    ///     Point2::new(4., 0.),
+    /// ]);
+    /// let compound = CompoundCurve2D::from(polyline);
+    ///
+    /// // the radius for each corner
+    /// let radii = [0.5, 0., 0.25];
+    /// let filleted = compound.try_fillet_with(|i, _| radii[i]).unwrap();
+    /// let spans = filleted.spans();
+    /// assert_eq!(spans.len(), 6);
+    /// let (s, _) = spans[1].knots_domain();
+    /// assert_relative_eq!(spans[1].point_at(s), Point2::new(1.5, 0.), epsilon = 1e-6);
+    /// // the second corner is left sharp
+    /// let (_, e) = spans[2].knots_domain();
+    /// assert_relative_eq!(spans[2].point_at(e), Point2::new(2., 2.), epsilon = 1e-6);
+    /// let (s, _) = spans[4].knots_domain();
+    /// assert_relative_eq!(spans[4].point_at(s), Point2::new(3.75, 2.), epsilon = 1e-6);
+    ///
+    /// // the radius as the function of the location of the corner
+    /// let filleted = compound.try_fillet_with(|_, p| 0.1 + p.x * 0.1).unwrap();
+    /// let spans = filleted.spans();
+    /// assert_eq!(spans.len(), 7);
+    /// let (s, _) = spans[5].knots_domain();
+    /// assert_relative_eq!(spans[5].point_at(s), Point2::new(3.5, 2.), epsilon = 1e-6);
+    /// ```
+    pub fn try_fillet_with<F>(&self, radius: F) -> anyhow::Result<Self>
+    where
+        F: Fn(usize, &OPoint<T, DimNameDiff<D, U1>>) -> T,
+    {
+        let spans = self.try_split_at_corners()?;
+        let n = spans.len();
+
+        // the parameters of the ends of each span after rounding the corners
+        let mut ranges = spans.iter().map(|s| s.knots_domain()).collect::<Vec<_>>();
+        let mut joints = vec![None; n];
+        for (index, (i, _)) in self.corners(&spans).into_iter().enumerate() {
+            let j = (i + 1) % n;
+            let (_, end) = spans[i].knots_domain();
+            let (start, _) = spans[j].knots_domain();
+            let r = radius(index, &spans[i].point_at(end));
+            if r <= T::zero() {
+                continue;
+            }
+            let (ta, tb, arc) = try_fillet_between(&spans[i], end, &spans[j], start, r)?;
+            ranges[i].1 = ta;
+            ranges[j].0 = tb;
+            joints[i] = Some(arc);
+        }
+        anyhow::ensure!(
+            ranges.iter().all(|(s, e)| s < e),
+            "The fillets overlap each other on a span"
+        );
+        Self::try_join_trimmed(&spans, &ranges, joints)
+    }
+
+    /// Split the spans at the knots where the curve may have a corner
+    fn try_split_at_corners(&self) -> anyhow::Result<Vec<NurbsCurve<T, D>>> {
+        let mut spans = vec![];
+        for span in self.spans.iter() {
+            let (start, end) = span.knots_domain();
+            let mut rest = span.clone();
+            for m in span.knots().multiplicity() {
+                let k = *m.knot();
+                if k > start && k < end && m.multiplicity() >= span.degree() {
+                    let (head, tail) = rest.try_trim(k)?;
+                    spans.push(head);
+                    rest = tail;
+                }
+            }
+            spans.push(rest);
+        }
+        Ok(spans)
+    }
+
+    /// Find the indices of the spans ending at the corners with the cosines of the turning angles,
+    /// including the joint of the last & first spans if the curve is closed
+    fn corners(&self, spans: &[NurbsCurve<T, D>]) -> Vec<(usize, T)> {
+        let n = spans.len();
+        let joints = if self.is_closed(None) { n } else { n - 1 };
+        (0..joints)
+            .filter_map(|i| {
+                let j = (i + 1) % n;
+                let incoming = spans[i].tangent_at(spans[i].knots_domain().1).normalize();
+                let outgoing = spans[j].tangent_at(spans[j].knots_domain().0).normalize();
+                let cos = incoming.dot(&outgoing).clamp(-T::one(), T::one());
+                (T::one() - cos > T::from_f64(1e-10).unwrap()).then_some((i, cos))
+            })
+            .collect()
+    }
+
+    /// Trim the spans to the ranges & connect them with the curves following the spans at the corners
+    fn try_join_trimmed(
+        spans: &[NurbsCurve<T, D>],
+        ranges: &[(T, T)],
+        joints: Vec<Option<NurbsCurve<T, D>>>,
+    ) -> anyhow::Result<Self> {
+        let mut curves = vec![];
+        for ((span, (s, e)), joint) in spans.iter().zip(ranges.iter()).zip(joints) {
+            let (start, end) = span.knots_domain();
+            let mut curve = span.clone();
+            if *e < end {
+                curve = curve.try_trim(*e)?.0;
+            }
+            if *s > start {
+                curve = curve.try_trim(*s)?.1;
+            }
+            curves.push(curve);
+            curves.extend(joint);
+        }
+        Self::try_new(curves)
+    }
+
+    /// Tessellate the compound curve into a polyline
+    pub fn tessellate(&self, tolerance: Option<T>) -> Vec<OPoint<T, DimNameDiff<D, U1>>> {
+        let mut points: Vec<OPoint<T, DimNameDiff<D, U1>>> = vec![];
+        self.spans.iter().for_each(|span| {
+            let pts = span.tessellate(tolerance);
+            let skip = if points.is_empty() { 0 } else { 1 };
+            points.extend(pts.into_iter().skip(skip));
+        });
+        points
+    }
+}
+
+impl<T: FloatingPoint, D: DimName> From<NurbsCurve<T, D>> for CompoundCurve<T, D>
+where
+    DefaultAllocator: Allocator<D>,
+{
+    fn from(value: NurbsCurve<T, D>) -> Self {
+        Self { spans: vec![value] }
+    }
+}
+
+impl<T: FloatingPoint, D: DimName> Invertible for CompoundCurve<T, D>
+where
+    DefaultAllocator: Allocator<D>,
+{
+    /// Reverse the direction of the compound curve
+    fn invert(&mut self) {
+        self.spans.reverse();
+        self.spans.iter_mut().for_each(|s| s.invert());
+    }
+}
+
+/// Enable to deform a compound curve by the deformation of the control points of its spans
+impl<T: FloatingPoint> Deformable<T> for CompoundCurve3D<T> {
+    fn deform<F: SpaceDeformation<T>>(&mut self, deformation: &F) {
+        self.spans.iter_mut().for_each(|s| s.deform(deformation));
+    }
+}
+
+/// Enable to transform a compound curve by a given DxD matrix
+impl<'a, T: FloatingPoint, const D: usize> Transformable<&'a OMatrix<T, Const<D>, Const<D>>>
+    for CompoundCurve<T, Const<D>>
+{
+    fn transform(&mut self, transform: &'a OMatrix<T, Const<D>, Const<D>>) {
+        self.spans.iter_mut().for_each(|s| s.transform(transform));
+    }
+}
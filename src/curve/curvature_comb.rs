@@ -0,0 +1,117 @@
+use nalgebra::{
+    allocator::Allocator, DefaultAllocator, DimName, DimNameDiff, DimNameSub, OPoint, OVector, U1,
+};
+
+use crate::{curve::NurbsCurve, misc::FloatingPoint};
+
+/// The polylines to display the curvature comb of a curve
+/// Each tooth runs from the point on the curve away from the center of the curvature by the length proportional to the curvature,
+/// and the envelope connects the tips of the teeth.
+#[derive(Clone, Debug)]
+pub struct CurvatureComb<T: FloatingPoint, D: DimName>
+where
+    DefaultAllocator: Allocator<D>,
+{
+    teeth: Vec<(OPoint<T, D>, OPoint<T, D>)>,
+    envelope: Vec<OPoint<T, D>>,
+}
+
+impl<T: FloatingPoint, D: DimName> CurvatureComb<T, D>
+where
+    DefaultAllocator: Allocator<D>,
+{
+    #[allow(clippy::type_complexity)]
+    pub fn teeth(&self) -> &[(OPoint<T, D>, OPoint<T, D>)] {
+        &self.teeth
+    }
+
+    pub fn envelope(&self) -> &[OPoint<T, D>] {
+        &self.envelope
+    }
+}
+
+impl<T: FloatingPoint, D> NurbsCurve<T, D>
+where
+    D: DimName + DimNameSub<U1>,
+    DefaultAllocator: Allocator<D>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    /// Sample the curvature at the parameters dividing the domain uniformly into the intervals of the number of samples minus one
+    /// Returns the parameter, the curvature & the unit normal towards the center of the curvature for each sample,
+    /// where the normal is zero if the curve is straight at the sample.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point2, Vector2};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let circle = NurbsCurve2D::try_circle(&Point2::origin(), &Vector2::x(), &Vector2::y(), 2.).unwrap();
+    /// let profile = circle.curvature_profile(9);
+    /// assert_eq!(profile.len(), 9);
+    /// for (t, curvature, normal) in profile {
+    ///     assert_relative_eq!(curvature, 0.5, epsilon = 1e-10);
+    ///     assert_relative_eq!(normal, -circle.point_at(t).coords / 2., epsilon = 1e-10);
+    /// }
+    ///
+    /// let line = NurbsCurve2D::polyline(&[Point2::origin(), Point2::new(1., 0.)]);
+    /// let (_, curvature, normal) = line.curvature_profile(2)[0];
+    /// assert_eq!(curvature, 0.);
+    /// assert_eq!(normal, Vector2::zeros());
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn curvature_profile(&self, samples: usize) -> Vec<(T, T, OVector<T, DimNameDiff<D, U1>>)> {
+        let (start, end) = self.knots_domain();
+        let n = samples.max(2) - 1;
+        (0..=n)
+            .map(|i| {
+                let t =
+                    start + (end - start) * T::from_usize(i).unwrap() / T::from_usize(n).unwrap();
+                let d = self.rational_derivatives(t, 2);
+                let speed = d[1].norm();
+                if speed <= T::default_epsilon() {
+                    return (t, T::zero(), OVector::<T, DimNameDiff<D, U1>>::zeros());
+                }
+                let tangent = &d[1] / speed;
+                let vector = (&d[2] - &tangent * d[2].dot(&tangent)) / (speed * speed);
+                let curvature = vector.norm();
+                if curvature <= T::default_epsilon() {
+                    (t, T::zero(), OVector::<T, DimNameDiff<D, U1>>::zeros())
+                } else {
+                    (t, curvature, vector / curvature)
+                }
+            })
+            .collect()
+    }
+
+    /// Build the curvature comb from the curvature profile of the samples
+    /// The length of each tooth is the curvature multiplied by the scale.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point2, Vector2};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let circle = NurbsCurve2D::try_circle(&Point2::origin(), &Vector2::x(), &Vector2::y(), 2.).unwrap();
+    /// let comb = circle.curvature_comb(16, 2.);
+    /// assert_eq!(comb.teeth().len(), 16);
+    /// assert_eq!(comb.envelope().len(), 16);
+    /// // the teeth of the length 1 point outwards
+    /// for (root, tip) in comb.teeth() {
+    ///     assert_relative_eq!(root.coords.norm(), 2., epsilon = 1e-10);
+    ///     assert_relative_eq!(tip.coords.norm(), 3., epsilon = 1e-10);
+    /// }
+    /// ```
+    pub fn curvature_comb(&self, samples: usize, scale: T) -> CurvatureComb<T, DimNameDiff<D, U1>> {
+        let teeth = self
+            .curvature_profile(samples)
+            .into_iter()
+            .map(|(t, curvature, normal)| {
+                let root = self.point_at(t);
+                let tip = &root - normal * (curvature * scale);
+                (root, tip)
+            })
+            .collect::<Vec<_>>();
+        let envelope = teeth.iter().map(|(_, tip)| tip.clone()).collect();
+        CurvatureComb { teeth, envelope }
+    }
+}
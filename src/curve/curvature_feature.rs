@@ -0,0 +1,214 @@
+use crate::{curve::NurbsCurve2D, misc::FloatingPoint};
+
+/// The maximum number of the bisections to locate the feature
+const MAX_BISECTIONS: usize = 64;
+
+/// The kind of the characteristic point of the curvature of a planar curve
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurvatureFeatureKind {
+    /// The signed curvature changes its sign
+    Inflection,
+    /// The signed curvature has the local maximum
+    Maximum,
+    /// The signed curvature has the local minimum
+    Minimum,
+}
+
+/// A characteristic point of the curvature of a planar curve
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CurvatureFeature<T: FloatingPoint> {
+    parameter: T,
+    /// The signed curvature, positive where the curve turns counterclockwise
+    curvature: T,
+    kind: CurvatureFeatureKind,
+}
+
+impl<T: FloatingPoint> CurvatureFeature<T> {
+    pub fn new(parameter: T, curvature: T, kind: CurvatureFeatureKind) -> Self {
+        Self {
+            parameter,
+            curvature,
+            kind,
+        }
+    }
+
+    pub fn parameter(&self) -> T {
+        self.parameter
+    }
+
+    pub fn curvature(&self) -> T {
+        self.curvature
+    }
+
+    pub fn kind(&self) -> CurvatureFeatureKind {
+        self.kind
+    }
+}
+
+impl<T: FloatingPoint> NurbsCurve2D<T> {
+    /// Evaluate the signed curvature at the parameter, positive where the curve turns counterclockwise
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point2, Vector2};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let circle = NurbsCurve2D::try_circle(&Point2::origin(), &Vector2::x(), &Vector2::y(), 2.).unwrap();
+    /// assert_relative_eq!(circle.signed_curvature(0.3), 0.5, epsilon = 1e-10);
+    /// assert_relative_eq!(circle.inverse().signed_curvature(0.3), -0.5, epsilon = 1e-10);
+    /// ```
+    pub fn signed_curvature(&self, t: T) -> T {
+        let d = self.rational_derivatives(t, 2);
+        let speed = d[1].norm();
+        if speed <= T::default_epsilon() {
+            return T::zero();
+        }
+        d[1].perp(&d[2]) / (speed * speed * speed)
+    }
+
+    /// The derivative of the signed curvature with respect to the parameter
+    fn signed_curvature_derivative(&self, t: T) -> T {
+        let d = self.rational_derivatives(t, 3);
+        let speed2 = d[1].norm_squared();
+        if speed2 <= T::default_epsilon() {
+            return T::zero();
+        }
+        let speed = speed2.sqrt();
+        let three = T::from_f64(3.).unwrap();
+        d[1].perp(&d[3]) / (speed2 * speed)
+            - three * d[1].perp(&d[2]) * d[1].dot(&d[2]) / (speed2 * speed2 * speed)
+    }
+
+    /// Find the inflections & the local extrema of the signed curvature by the bisection on the sign changes of the sampled functions
+    /// The curvature is sampled uniformly within each Bézier segment, so the features closer than the sampling interval may be missed.
+    /// The seam of the closed curve joined with G1 continuity is treated as an interior point, and the features are sorted by the parameters.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point2, Point3, Vector2};
+    /// use approx::assert_relative_eq;
+    ///
+    /// // the S shaped curve has the inflection at the center
+    /// let s = NurbsCurve2D::try_new(3, vec![
+    ///     Point3::new(0., 0., 1.),
+    ///     Point3::new(1., 1., 1.),
+    ///     Point3::new(2., -1., 1.),
+    ///     Point3::new(3., 0., 1.),
+    /// ], vec![0., 0., 0., 0., 1., 1., 1., 1.]).unwrap();
+    /// let features = s.find_curvature_features();
+    /// let inflections = features.iter().filter(|f| f.kind() == CurvatureFeatureKind::Inflection).collect::<Vec<_>>();
+    /// assert_eq!(inflections.len(), 1);
+    /// assert_relative_eq!(inflections[0].parameter(), 0.5, epsilon = 1e-10);
+    /// assert_relative_eq!(s.point_at(0.5), Point2::new(1.5, 0.), epsilon = 1e-10);
+    ///
+    /// // the ellipse has the maxima at the ends of the major axis & the minima at the ends of the minor axis
+    /// let ellipse = NurbsCurve2D::<f64>::try_ellipse(&Point2::origin(), &(Vector2::x() * 2.), &Vector2::y()).unwrap();
+    /// let features = ellipse.find_curvature_features();
+    /// assert_eq!(features.len(), 4);
+    /// for feature in features {
+    ///     let p = ellipse.point_at(feature.parameter());
+    ///     match feature.kind() {
+    ///         CurvatureFeatureKind::Maximum => {
+    ///             assert_relative_eq!(p.x.abs(), 2., epsilon = 1e-8);
+    ///             assert_relative_eq!(feature.curvature(), 2., epsilon = 1e-6);
+    ///         }
+    ///         CurvatureFeatureKind::Minimum => {
+    ///             assert_relative_eq!(p.y.abs(), 1., epsilon = 1e-8);
+    ///             assert_relative_eq!(feature.curvature(), 0.25, epsilon = 1e-6);
+    ///         }
+    ///         CurvatureFeatureKind::Inflection => unreachable!(),
+    ///     }
+    /// }
+    /// ```
+    pub fn find_curvature_features(&self) -> Vec<CurvatureFeature<T>> {
+        let (start, end) = self.knots_domain();
+        let interval = end - start;
+        let tangent = |t: T| self.tangent_at(t).normalize();
+        let closed = (self.point_at(start) - self.point_at(end)).norm()
+            <= interval * T::from_f64(1e-10).unwrap()
+            && (tangent(start) - tangent(end)).norm() <= T::from_f64(1e-8).unwrap();
+
+        // the parameters sampled uniformly within each span between the distinct knots
+        let divs = (self.degree() + 1) * 4;
+        let mut knots = self
+            .knots()
+            .iter()
+            .filter(|k| **k >= start && **k <= end)
+            .copied()
+            .collect::<Vec<_>>();
+        knots.dedup();
+        let mut samples = vec![];
+        for w in knots.windows(2) {
+            for i in 0..divs {
+                samples.push(
+                    w[0] + (w[1] - w[0]) * T::from_usize(i).unwrap() / T::from_usize(divs).unwrap(),
+                );
+            }
+        }
+        if closed {
+            // continue across the seam to the first interior sample
+            samples.push(end);
+            samples.push(end + (samples[1] - start));
+        } else {
+            samples.push(end);
+        }
+        let wrap = |t: T| if t > end { start + (t - end) } else { t };
+
+        // the roots of the function where its sign changes between the samples
+        let roots = |f: &dyn Fn(T) -> T| {
+            let eps = interval * T::from_f64(1e-14).unwrap();
+            let mut roots = vec![];
+            let mut last: Option<(T, T)> = None;
+            for t in samples.iter() {
+                let v = f(wrap(*t));
+                if v == T::zero() {
+                    continue;
+                }
+                if let Some((s, u)) = last {
+                    if u * v < T::zero() {
+                        let (mut lo, mut hi, mut flo) = (s, *t, u);
+                        for _ in 0..MAX_BISECTIONS {
+                            if hi - lo <= eps {
+                                break;
+                            }
+                            let mid = (lo + hi) * T::from_f64(0.5).unwrap();
+                            let fm = f(wrap(mid));
+                            if fm * flo > T::zero() {
+                                (lo, flo) = (mid, fm);
+                            } else {
+                                hi = mid;
+                            }
+                        }
+                        // the sign changes from the negative to the positive if the first value is negative
+                        roots.push((wrap((lo + hi) * T::from_f64(0.5).unwrap()), u < T::zero()));
+                    }
+                }
+                last = Some((*t, v));
+            }
+            roots
+        };
+
+        let numerator = |t: T| {
+            let d = self.rational_derivatives(t, 2);
+            d[1].perp(&d[2])
+        };
+        let mut features = roots(&numerator)
+            .into_iter()
+            .map(|(t, _)| CurvatureFeature::new(t, T::zero(), CurvatureFeatureKind::Inflection))
+            .collect::<Vec<_>>();
+        features.extend(
+            roots(&|t| self.signed_curvature_derivative(t))
+                .into_iter()
+                .map(|(t, increasing)| {
+                    let kind = if increasing {
+                        CurvatureFeatureKind::Minimum
+                    } else {
+                        CurvatureFeatureKind::Maximum
+                    };
+                    CurvatureFeature::new(t, self.signed_curvature(t), kind)
+                }),
+        );
+        features.sort_by(|a, b| a.parameter.partial_cmp(&b.parameter).unwrap());
+        features
+    }
+}
@@ -0,0 +1,33 @@
+use crate::misc::FloatingPoint;
+
+use super::KnotStyle;
+
+/// Options for approximating points by a curve in the least squares sense
+#[derive(Clone, Debug)]
+pub struct CurveApproximationOptions<T: FloatingPoint> {
+    /// The parameterization of the points
+    pub knot_style: KnotStyle,
+    /// The weight of the penalty on the second differences of the control points, which smooths the curve at the cost of the fitting error
+    pub smoothing: T,
+}
+
+impl<T: FloatingPoint> Default for CurveApproximationOptions<T> {
+    fn default() -> Self {
+        Self {
+            knot_style: KnotStyle::Chordal,
+            smoothing: T::zero(),
+        }
+    }
+}
+
+impl<T: FloatingPoint> CurveApproximationOptions<T> {
+    pub fn with_knot_style(mut self, knot_style: KnotStyle) -> Self {
+        self.knot_style = knot_style;
+        self
+    }
+
+    pub fn with_smoothing(mut self, smoothing: T) -> Self {
+        self.smoothing = smoothing;
+        self
+    }
+}
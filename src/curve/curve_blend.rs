@@ -0,0 +1,118 @@
+use nalgebra::{
+    allocator::Allocator, DefaultAllocator, DimName, DimNameDiff, DimNameSub, OPoint, OVector, U1,
+};
+
+use crate::{
+    curve::{CurveContinuity, NurbsCurve},
+    misc::FloatingPoint,
+};
+
+impl<T: FloatingPoint, D> NurbsCurve<T, D>
+where
+    D: DimName + DimNameSub<U1>,
+    DefaultAllocator: Allocator<D>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    /// Create the blend curve from the point of the curve `a` at `t_a` to the point of the curve `b` at `t_b`
+    /// The blend continues `a` in its direction & runs into `b` along its direction, meeting both curves with the continuity.
+    /// The blend is the Bézier curve of the degree 1, 3 or 5 for G0, G1 or G2, and the bulge scales the lengths of the tangents relative to the distance between the points.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point2, Vector2};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let circle = NurbsCurve2D::try_circle(&Point2::origin(), &Vector2::x(), &Vector2::y(), 1.).unwrap();
+    /// let line = NurbsCurve2D::polyline(&[Point2::new(3., 2.), Point2::new(4., 2.)]);
+    /// let curvature = |c: &NurbsCurve2D<f64>, t: f64| {
+    ///     let d = c.rational_derivatives(t, 2);
+    ///     d[1].perp(&d[2]) / d[1].norm().powi(3)
+    /// };
+    ///
+    /// let blend = NurbsCurve2D::try_blend(&circle, 0.25, &line, 0., CurveContinuity::G2, 1.).unwrap();
+    /// assert_eq!(blend.degree(), 5);
+    /// let (s, e) = blend.knots_domain();
+    /// assert_relative_eq!(blend.point_at(s), circle.point_at(0.25), epsilon = 1e-10);
+    /// assert_relative_eq!(blend.point_at(e), Point2::new(3., 2.), epsilon = 1e-10);
+    /// assert_relative_eq!(blend.tangent_at(s).normalize(), circle.tangent_at(0.25).normalize(), epsilon = 1e-10);
+    /// assert_relative_eq!(blend.tangent_at(e).normalize(), Vector2::x(), epsilon = 1e-10);
+    /// // the curvatures continue the circle & the line
+    /// assert_relative_eq!(curvature(&blend, s), 1., epsilon = 1e-8);
+    /// assert_relative_eq!(curvature(&blend, e), 0., epsilon = 1e-8);
+    ///
+    /// // the bulge lengthens the tangents
+    /// let g1 = NurbsCurve2D::try_blend(&circle, 0.25, &line, 0., CurveContinuity::G1, 1.).unwrap();
+    /// let bulged = NurbsCurve2D::try_blend(&circle, 0.25, &line, 0., CurveContinuity::G1, 2.).unwrap();
+    /// assert_eq!(g1.degree(), 3);
+    /// assert_relative_eq!(bulged.tangent_at(s).norm(), g1.tangent_at(s).norm() * 2., epsilon = 1e-10);
+    /// ```
+    pub fn try_blend(
+        a: &Self,
+        t_a: T,
+        b: &Self,
+        t_b: T,
+        continuity: CurveContinuity,
+        bulge: T,
+    ) -> anyhow::Result<Self> {
+        anyhow::ensure!(bulge > T::zero(), "The bulge must be greater than zero");
+        let order = continuity.order();
+        let da = a.rational_derivatives(t_a, order);
+        let db = b.rational_derivatives(t_b, order);
+        let (p0, p1) = (OPoint::from(da[0].clone()), OPoint::from(db[0].clone()));
+        let length = (&p1 - &p0).norm();
+        anyhow::ensure!(
+            length > T::default_epsilon(),
+            "The points to blend are coincident"
+        );
+
+        let degree = order * 2 + 1;
+        let n = T::from_usize(degree).unwrap();
+        // the speed of the blend at the ends with respect to its unit domain
+        let speed = length * bulge;
+        let frame = |d: &[OVector<T, DimNameDiff<D, U1>>]| -> anyhow::Result<_> {
+            let norm = d[1].norm();
+            anyhow::ensure!(
+                norm > T::default_epsilon(),
+                "The curve has the zero derivative at the blended point"
+            );
+            let tangent = &d[1] / norm;
+            // the curvature vector, normal to the tangent
+            let curvature = d
+                .get(2)
+                .map(|d2| (d2 - &tangent * d2.dot(&tangent)) / (norm * norm));
+            Ok((tangent, curvature))
+        };
+
+        let mut head = vec![p0.clone()];
+        let mut tail = vec![p1.clone()];
+        if order >= 1 {
+            let (ta, ka) = frame(&da)?;
+            let (tb, kb) = frame(&db)?;
+            // the first derivatives of the Bézier curve are n (P1 - P0) & n (Pn - Pn-1)
+            head.push(&p0 + &ta * (speed / n));
+            tail.push(&p1 - &tb * (speed / n));
+            if let (Some(ka), Some(kb)) = (ka, kb) {
+                // the second derivatives are n (n - 1) (P2 - 2 P1 + P0) & n (n - 1) (Pn - 2 Pn-1 + Pn-2)
+                let scale = speed * speed / (n * (n - T::one()));
+                let two = T::from_f64(2.).unwrap();
+                head.push(OPoint::from(
+                    &head[1].coords * two - &p0.coords + ka * scale,
+                ));
+                tail.push(OPoint::from(
+                    &tail[1].coords * two - &p1.coords + kb * scale,
+                ));
+            }
+        }
+        tail.reverse();
+        head.extend(tail);
+
+        let homogeneous = |p: &OPoint<T, DimNameDiff<D, U1>>| {
+            let mut h = OPoint::<T, D>::origin();
+            p.iter().enumerate().for_each(|(i, v)| h[i] = *v);
+            h[D::dim() - 1] = T::one();
+            h
+        };
+        let knots = [vec![T::zero(); degree + 1], vec![T::one(); degree + 1]].concat();
+        Self::try_new(degree, head.iter().map(homogeneous).collect(), knots)
+    }
+}
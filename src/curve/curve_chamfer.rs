@@ -0,0 +1,24 @@
+use crate::misc::FloatingPoint;
+
+/// The dimensions of the chamfer cutting the corner of a curve
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CurveChamfer<T: FloatingPoint> {
+    /// The lengths cut along the curves before & after the corner
+    Distances(T, T),
+    /// The length cut along the curve before the corner & the angle in radians between the chamfer & the curve before the corner
+    DistanceAngle(T, T),
+}
+
+impl<T: FloatingPoint> CurveChamfer<T> {
+    /// The lengths cut along the curves before & after the corner
+    /// * `angle` - The interior angle in radians between the curves at the corner
+    pub fn distances(&self, angle: T) -> (T, T) {
+        match *self {
+            CurveChamfer::Distances(d0, d1) => (d0, d1),
+            CurveChamfer::DistanceAngle(d, theta) => {
+                // the law of sines in the triangle cut off at the corner
+                (d, d * theta.sin() / (theta + angle).sin())
+            }
+        }
+    }
+}
@@ -0,0 +1,22 @@
+/// The geometric continuity at the joint of two curves
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum CurveContinuity {
+    /// The curves share the point
+    G0,
+    /// The curves share the point & the direction of the tangent
+    #[default]
+    G1,
+    /// The curves share the point, the direction of the tangent & the curvature
+    G2,
+}
+
+impl CurveContinuity {
+    /// The highest order of the derivatives matched at the joint
+    pub fn order(&self) -> usize {
+        match self {
+            CurveContinuity::G0 => 0,
+            CurveContinuity::G1 => 1,
+            CurveContinuity::G2 => 2,
+        }
+    }
+}
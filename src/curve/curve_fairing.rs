@@ -0,0 +1,168 @@
+use gauss_quad::GaussLegendre;
+use nalgebra::{
+    allocator::Allocator, DMatrix, DVector, DefaultAllocator, DimName, DimNameDiff, DimNameSub, U1,
+};
+
+use crate::{
+    curve::{CurveFairingOptions, FairingEnergy, NurbsCurve},
+    misc::FloatingPoint,
+};
+
+impl<T: FloatingPoint, D> NurbsCurve<T, D>
+where
+    D: DimName + DimNameSub<U1>,
+    DefaultAllocator: Allocator<D>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    /// Compute the integral of the squared derivative of the order of the energy over the domain
+    /// The integral is scaled by the length of the domain so that it does not depend on the parameterization range.
+    pub fn fairing_energy(&self, energy: FairingEnergy) -> T {
+        let order = energy.order();
+        let gauss = GaussLegendre::new(self.degree().max(2) + 1).unwrap();
+        let (start, end) = self.knots_domain();
+        let half = T::from_f64(0.5).unwrap();
+        let n = self.control_points().len();
+        let sum = (self.degree()..n)
+            .map(|span| (self.knots()[span], self.knots()[span + 1]))
+            .filter(|(a, b)| b > a)
+            .fold(T::zero(), |acc, (a, b)| {
+                let (mid, radius) = ((a + b) * half, (b - a) * half);
+                gauss
+                    .as_node_weight_pairs()
+                    .iter()
+                    .fold(acc, |acc, (x, w)| {
+                        let u = mid + radius * T::from_f64(*x).unwrap();
+                        let d = self.rational_derivatives(u, order);
+                        acc + d[order].norm_squared() * radius * T::from_f64(*w).unwrap()
+                    })
+            });
+        sum * (end - start).powi(2 * order as i32 - 1)
+    }
+
+    /// Try to fair the curve by minimizing the energy with the penalty on the deviation of the control points
+    /// The end points & the points at the interpolation parameters are kept, while the knots & the weights are preserved.
+    /// The energy is evaluated on the homogeneous coordinates, which is exact for non-rational curves.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::Point2;
+    /// use approx::assert_relative_eq;
+    ///
+    /// // the digitized points wobbling around a line
+    /// let points = (0..=12)
+    ///     .map(|i| Point2::new(i as f64, if i % 2 == 0 { 0.1 } else { -0.1 }))
+    ///     .collect::<Vec<_>>();
+    /// let curve = NurbsCurve2D::try_interpolate(&points, 3).unwrap();
+    /// let (start, end) = curve.knots_domain();
+    /// let middle = (start + end) / 2.;
+    ///
+    /// let options = CurveFairingOptions::default()
+    ///     .with_smoothing(1.)
+    ///     .with_interpolation(vec![middle]);
+    /// let faired = curve.try_fair(&options).unwrap();
+    /// let energy = FairingEnergy::Bending;
+    /// assert!(faired.fairing_energy(energy) < curve.fairing_energy(energy) * 1e-2);
+    ///
+    /// // the end points & the interpolated point are kept
+    /// assert_relative_eq!(faired.point_at(start), curve.point_at(start), epsilon = 1e-10);
+    /// assert_relative_eq!(faired.point_at(end), curve.point_at(end), epsilon = 1e-10);
+    /// assert_relative_eq!(faired.point_at(middle), curve.point_at(middle), epsilon = 1e-10);
+    ///
+    /// // the curve without the smoothing is unchanged
+    /// let same = curve.try_fair(&CurveFairingOptions::default().with_smoothing(0.)).unwrap();
+    /// assert_relative_eq!(same.point_at(middle * 0.3), curve.point_at(middle * 0.3), epsilon = 1e-10);
+    /// ```
+    pub fn try_fair(&self, options: &CurveFairingOptions<T>) -> anyhow::Result<Self> {
+        let order = options.energy.order();
+        anyhow::ensure!(
+            self.degree() >= order,
+            "The degree must be at least the order of the energy"
+        );
+        anyhow::ensure!(
+            options.smoothing >= T::zero(),
+            "The smoothing must not be negative"
+        );
+
+        let degree = self.degree();
+        let n = self.control_points().len();
+        let (start, end) = self.knots_domain();
+        let half = T::from_f64(0.5).unwrap();
+
+        // the stiffness matrix of the integral of the products of the derivatives of the basis functions
+        let gauss = GaussLegendre::new(degree.max(2))?;
+        let mut stiffness = DMatrix::<T>::zeros(n, n);
+        for span in degree..n {
+            let (a, b) = (self.knots()[span], self.knots()[span + 1]);
+            if b <= a {
+                continue;
+            }
+            let (mid, radius) = ((a + b) * half, (b - a) * half);
+            for (x, w) in gauss.as_node_weight_pairs().iter() {
+                let u = mid + radius * T::from_f64(*x).unwrap();
+                let weight = radius * T::from_f64(*w).unwrap();
+                let ders = self
+                    .knots()
+                    .derivative_basis_functions(span, u, degree, order);
+                for (r, dr) in ders[order].iter().enumerate() {
+                    for (s, ds) in ders[order].iter().enumerate() {
+                        stiffness[(span - degree + r, span - degree + s)] += weight * *dr * *ds;
+                    }
+                }
+            }
+        }
+        let stiffness = stiffness * (end - start).powi(2 * order as i32 - 1) * options.smoothing;
+
+        // the end points & the interpolation parameters inside the domain
+        let mut parameters = vec![start, end];
+        parameters.extend(
+            options
+                .interpolation
+                .iter()
+                .filter(|t| **t > start && **t < end),
+        );
+        parameters.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        parameters.dedup_by(|a, b| (*a - *b).abs() <= T::default_epsilon());
+        let m = parameters.len();
+        anyhow::ensure!(
+            m <= n,
+            "The number of the constraints exceeds the number of the control points"
+        );
+
+        // the Lagrange multipliers of the constraints on the points of the curve
+        let mut system = DMatrix::<T>::zeros(n + m, n + m);
+        system
+            .view_mut((0, 0), (n, n))
+            .copy_from(&(DMatrix::identity(n, n) + stiffness));
+        for (k, u) in parameters.iter().enumerate() {
+            let span = self.knots().find_knot_span_index(n - 1, degree, *u);
+            for (i, b) in self
+                .knots()
+                .basis_functions(span, *u, degree)
+                .into_iter()
+                .enumerate()
+            {
+                system[(n + k, span - degree + i)] = b;
+                system[(span - degree + i, n + k)] = b;
+            }
+        }
+        let constraints = system.view((n, 0), (m, n)).clone_owned();
+        let lu = system.lu();
+
+        let mut faired = self.clone();
+        for c in 0..D::dim() - 1 {
+            let original = DVector::from_iterator(n, self.control_points().iter().map(|p| p[c]));
+            let mut rhs = DVector::<T>::zeros(n + m);
+            rhs.rows_mut(0, n).copy_from(&original);
+            rhs.rows_mut(n, m).copy_from(&(&constraints * &original));
+            let x = lu
+                .solve(&rhs)
+                .ok_or(anyhow::anyhow!("Failed to solve the fairing system"))?;
+            faired
+                .control_points_iter_mut()
+                .enumerate()
+                .for_each(|(i, p)| p[c] = x[i]);
+        }
+
+        Ok(faired)
+    }
+}
@@ -0,0 +1,59 @@
+use crate::misc::FloatingPoint;
+
+/// The energy minimized by fairing a curve
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FairingEnergy {
+    /// The integral of the squared second derivative, which approximates the bending energy
+    #[default]
+    Bending,
+    /// The integral of the squared third derivative, which penalizes the variation of the curvature
+    Jerk,
+}
+
+impl FairingEnergy {
+    /// The order of the derivative in the energy
+    pub fn order(&self) -> usize {
+        match self {
+            FairingEnergy::Bending => 2,
+            FairingEnergy::Jerk => 3,
+        }
+    }
+}
+
+/// Options for fairing a curve by minimizing the energy
+#[derive(Clone, Debug)]
+pub struct CurveFairingOptions<T: FloatingPoint> {
+    /// The energy to minimize
+    pub energy: FairingEnergy,
+    /// The weight of the energy against the deviation from the original control points
+    pub smoothing: T,
+    /// The parameters where the faired curve keeps passing through the original curve in addition to the end points
+    pub interpolation: Vec<T>,
+}
+
+impl<T: FloatingPoint> Default for CurveFairingOptions<T> {
+    fn default() -> Self {
+        Self {
+            energy: FairingEnergy::default(),
+            smoothing: T::from_f64(1e-2).unwrap(),
+            interpolation: vec![],
+        }
+    }
+}
+
+impl<T: FloatingPoint> CurveFairingOptions<T> {
+    pub fn with_energy(mut self, energy: FairingEnergy) -> Self {
+        self.energy = energy;
+        self
+    }
+
+    pub fn with_smoothing(mut self, smoothing: T) -> Self {
+        self.smoothing = smoothing;
+        self
+    }
+
+    pub fn with_interpolation(mut self, interpolation: Vec<T>) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+}
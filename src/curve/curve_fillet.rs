@@ -0,0 +1,245 @@
+use nalgebra::{
+    allocator::Allocator, DefaultAllocator, DimName, DimNameDiff, DimNameSub, Matrix2, OPoint,
+    OVector, Vector2, U1,
+};
+
+use crate::{
+    curve::{CompoundCurve3D, NurbsCurve, NurbsCurve3D},
+    misc::FloatingPoint,
+};
+
+/// The maximum number of iterations to find the points of tangency of the fillet
+const MAX_ITERATIONS: usize = 64;
+
+impl<T: FloatingPoint> NurbsCurve3D<T> {
+    /// Fillet the corner where the curve runs into the other curve with the arc of the radius
+    /// The corner is located at the closest points of the curves, which need not be coplanar.
+    /// The points of tangency are found so that the centers of the circles tangent to both curves in the plane of their tangents coincide as closely as possible,
+    /// and the arc is exactly circular if the curves are coplanar around the corner, or else approximated by the cubic curve tangent to both curves.
+    /// Returns the compound curve of the part of the curve before the corner, the arc & the part of the other curve after the corner;
+    /// invert the curves to fillet the other sides of the corner.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// // the right angle corner is rounded by the quarter circle
+    /// let a = NurbsCurve3D::polyline(&[Point3::new(0., 0., 0.), Point3::new(2., 0., 0.)]);
+    /// let b = NurbsCurve3D::polyline(&[Point3::new(2., 0., 0.), Point3::new(2., 2., 0.)]);
+    /// let fillet = a.try_fillet(&b, 0.5).unwrap();
+    /// let spans = fillet.spans();
+    /// assert_eq!(spans.len(), 3);
+    /// let (s, e) = spans[1].knots_domain();
+    /// assert_relative_eq!(spans[1].point_at(s), Point3::new(1.5, 0., 0.), epsilon = 1e-6);
+    /// assert_relative_eq!(spans[1].point_at(e), Point3::new(2., 0.5, 0.), epsilon = 1e-6);
+    /// let center = Point3::new(1.5, 0.5, 0.);
+    /// assert_relative_eq!((spans[1].point_at((s + e) / 2.) - center).norm(), 0.5, epsilon = 1e-6);
+    ///
+    /// // the curves in the different planes crossing each other
+    /// let helix = NurbsCurve3D::try_helix(&Point3::origin(), &Vector3::z(), 2., 4., 1.).unwrap();
+    /// let (start, end) = helix.knots_domain();
+    /// let p = helix.point_at((start + end) / 2.);
+    /// let line = NurbsCurve3D::polyline(&[p, p + Vector3::new(0., 0., 3.)]);
+    /// let (head, _) = helix.try_trim((start + end) / 2.).unwrap();
+    /// let fillet = head.try_fillet(&line, 0.3).unwrap();
+    /// let spans = fillet.spans();
+    /// let arc = &spans[1];
+    /// let (s, e) = arc.knots_domain();
+    /// // the arc is tangent to the curves at its ends
+    /// let (_, t1) = spans[0].knots_domain();
+    /// assert_relative_eq!(arc.tangent_at(s).normalize(), spans[0].tangent_at(t1).normalize(), epsilon = 1e-3);
+    /// let (t0, _) = spans[2].knots_domain();
+    /// assert_relative_eq!(arc.tangent_at(e).normalize(), spans[2].tangent_at(t0).normalize(), epsilon = 1e-3);
+    /// ```
+    pub fn try_fillet(&self, other: &Self, radius: T) -> anyhow::Result<CompoundCurve3D<T>> {
+        anyhow::ensure!(radius > T::zero(), "The radius must be greater than zero");
+        let corner = self.closest_parameters(other)?;
+        let (ta, tb, arc) = try_fillet_between(self, corner.a().1, other, corner.b().1, radius)?;
+        let head = self.try_trim(ta)?.0;
+        let tail = other.try_trim(tb)?.1;
+        CompoundCurve3D::try_new_with_tolerance(
+            vec![head, arc, tail],
+            radius * T::from_f64(0.5).unwrap(),
+        )
+    }
+}
+
+/// Find the parameters of the points of tangency of the fillet around the corner at the parameters of the curves, and the arc between them
+/// The curve `a` runs into the corner and `b` leaves it.
+pub(crate) fn try_fillet_between<T: FloatingPoint, D>(
+    a: &NurbsCurve<T, D>,
+    ta0: T,
+    b: &NurbsCurve<T, D>,
+    tb0: T,
+    radius: T,
+) -> anyhow::Result<(T, T, NurbsCurve<T, D>)>
+where
+    D: DimName + DimNameSub<U1>,
+    DefaultAllocator: Allocator<D>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    let (da, db) = (a.knots_domain(), b.knots_domain());
+
+    // the centers of the circles tangent to each curve in the plane of the tangents
+    let centers = |ta: T, tb: T| {
+        let p = a.rational_derivatives(ta, 1);
+        let q = b.rational_derivatives(tb, 1);
+        let (ua, ub) = (p[1].normalize(), q[1].normalize());
+        let cos = ua.dot(&ub);
+        // the directions towards the inside of the turn, orthogonal to the tangents
+        let na = &ub - &ua * cos;
+        let nb = &ub * cos - &ua;
+        if na.norm() <= T::default_epsilon() || nb.norm() <= T::default_epsilon() {
+            return None;
+        }
+        Some((
+            OPoint::from(&p[0] + na.normalize() * radius),
+            OPoint::from(&q[0] + nb.normalize() * radius),
+        ))
+    };
+    let residual = |ta: T, tb: T| centers(ta, tb).map(|(ca, cb)| ca - cb);
+
+    // start from the set back of the tangent points from the corner
+    let (ua, ub) = (a.tangent_at(ta0).normalize(), b.tangent_at(tb0).normalize());
+    let angle = ua.dot(&ub).clamp(-T::one(), T::one()).acos();
+    let half = T::from_f64(0.5).unwrap();
+    let setback = radius * (angle * half).tan();
+    let mut ta = (ta0 - setback / a.tangent_at(ta0).norm()).clamp(da.0, da.1);
+    let mut tb = (tb0 + setback / b.tangent_at(tb0).norm()).clamp(db.0, db.1);
+
+    // the gauss newton method on the difference of the centers
+    let mut r = residual(ta, tb).ok_or(anyhow::anyhow!(
+        "The curves are tangent to each other at the corner"
+    ))?;
+    let h = T::from_f64(1e-7).unwrap();
+    for _ in 0..MAX_ITERATIONS {
+        let ha = (da.1 - da.0) * h;
+        let hb = (db.1 - db.0) * h;
+        let (Some(ra), Some(rb)) = (residual(ta + ha, tb), residual(ta, tb + hb)) else {
+            break;
+        };
+        let (ja, jb) = ((ra - &r) / ha, (rb - &r) / hb);
+        let m = Matrix2::new(ja.dot(&ja), ja.dot(&jb), ja.dot(&jb), jb.dot(&jb));
+        let g = Vector2::new(ja.dot(&r), jb.dot(&r));
+        let Some(delta) = m.lu().solve(&g) else {
+            break;
+        };
+        let mut step = T::one();
+        let mut improved = false;
+        while step > T::from_f64(1e-4).unwrap() {
+            let (na, nb) = (
+                (ta - delta.x * step).clamp(da.0, da.1),
+                (tb - delta.y * step).clamp(db.0, db.1),
+            );
+            if let Some(nr) = residual(na, nb) {
+                if nr.norm() < r.norm() {
+                    (ta, tb, r) = (na, nb, nr);
+                    improved = true;
+                    break;
+                }
+            }
+            step *= half;
+        }
+        if !improved || r.norm() <= radius * T::default_epsilon() {
+            break;
+        }
+    }
+    anyhow::ensure!(
+        ta > da.0 && tb < db.1,
+        "The fillet does not fit on the curves"
+    );
+
+    let (ca, cb) = centers(ta, tb).unwrap();
+    let center = OPoint::from((ca.coords + cb.coords) * half);
+    let arc = try_arc_between(
+        &a.point_at(ta),
+        &a.tangent_at(ta).normalize(),
+        &b.point_at(tb),
+        &b.tangent_at(tb).normalize(),
+        &center,
+    )?;
+    Ok((ta, tb, arc))
+}
+
+/// Create the arc from the start to the end tangent to the directions
+/// If the tangent lines intersect, the arc is the rational quadratic curve with the intersection as the middle control point;
+/// otherwise, it is approximated by the cubic Bézier curve keeping the tangents at the ends.
+fn try_arc_between<T: FloatingPoint, D>(
+    start: &OPoint<T, DimNameDiff<D, U1>>,
+    start_tangent: &OVector<T, DimNameDiff<D, U1>>,
+    end: &OPoint<T, DimNameDiff<D, U1>>,
+    end_tangent: &OVector<T, DimNameDiff<D, U1>>,
+    center: &OPoint<T, DimNameDiff<D, U1>>,
+) -> anyhow::Result<NurbsCurve<T, D>>
+where
+    D: DimName + DimNameSub<U1>,
+    DefaultAllocator: Allocator<D>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    // the closest points of the lines start + s * start_tangent & end - u * end_tangent
+    let w = start - end;
+    let b = start_tangent.dot(end_tangent);
+    let denominator = T::one() - b * b;
+    anyhow::ensure!(
+        denominator > T::default_epsilon(),
+        "The tangents at the ends of the arc are parallel"
+    );
+    let (d, e) = (start_tangent.dot(&w), end_tangent.dot(&w));
+    let s = (b * e - d) / denominator;
+    let u = (e - b * d) / denominator;
+    let (p, q) = (start + start_tangent * s, end + end_tangent * u);
+
+    let radius = (start - center).norm();
+    let (a, c) = ((start - center).normalize(), (end - center).normalize());
+    let cos = a.dot(&c).clamp(-T::one(), T::one());
+    let half = T::from_f64(0.5).unwrap();
+    let homogeneous = |p: &OPoint<T, DimNameDiff<D, U1>>, w: T| {
+        let mut h = OPoint::<T, D>::origin();
+        p.iter().enumerate().for_each(|(i, v)| h[i] = *v * w);
+        h[D::dim() - 1] = w;
+        h
+    };
+    if (&p - q).norm() <= radius * T::from_f64(1e-8).unwrap() {
+        // the weight is the cosine of the half of the sweep angle
+        let weight = ((T::one() + cos) * half).sqrt();
+        return NurbsCurve::try_new(
+            2,
+            vec![
+                homogeneous(start, T::one()),
+                homogeneous(&p, weight),
+                homogeneous(end, T::one()),
+            ],
+            vec![
+                T::zero(),
+                T::zero(),
+                T::zero(),
+                T::one(),
+                T::one(),
+                T::one(),
+            ],
+        );
+    }
+
+    // the length of the handles approximating the circular arc of the sweep angle
+    let handle = T::from_f64(4. / 3.).unwrap() * (cos.acos() * half * half).tan() * radius;
+    NurbsCurve::try_new(
+        3,
+        vec![
+            homogeneous(start, T::one()),
+            homogeneous(&(start + start_tangent * handle), T::one()),
+            homogeneous(&(end - end_tangent * handle), T::one()),
+            homogeneous(end, T::one()),
+        ],
+        vec![
+            T::zero(),
+            T::zero(),
+            T::zero(),
+            T::zero(),
+            T::one(),
+            T::one(),
+            T::one(),
+            T::one(),
+        ],
+    )
+}
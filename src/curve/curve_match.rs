@@ -0,0 +1,130 @@
+use nalgebra::{
+    allocator::Allocator, DefaultAllocator, DimName, DimNameDiff, DimNameSub, OPoint, OVector, U1,
+};
+
+use crate::{
+    curve::{nurbs_curve::dehomogenize, CurveContinuity, NurbsCurve},
+    misc::{FloatingPoint, Invertible},
+};
+
+impl<T: FloatingPoint, D> NurbsCurve<T, D>
+where
+    D: DimName + DimNameSub<U1>,
+    DefaultAllocator: Allocator<D>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    /// Match the start or the end of the curve to the point of the target curve at the parameter with the continuity
+    /// Only the first one, two or three control points from the end are moved for G0, G1 or G2 keeping their weights,
+    /// so the rest of the curve is preserved; insert knots near the end beforehand to confine the change.
+    /// The direction of the tangent at the end follows the target curve in the sense closer to the original tangent,
+    /// and the distance between the first two control points is kept.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point2, Vector2};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let circle = NurbsCurve2D::try_circle(&Point2::origin(), &Vector2::x(), &Vector2::y(), 1.).unwrap();
+    /// let (s, e) = circle.knots_domain();
+    /// let at = |f: f64| s + (e - s) * f;
+    /// let curve = NurbsCurve2D::try_interpolate(&[
+    ///     Point2::new(-2., 0.),
+    ///     Point2::new(-1., 1.),
+    ///     Point2::new(0., 0.5),
+    ///     Point2::new(1., 1.5),
+    ///     Point2::new(2., 1.2),
+    /// ], 3).unwrap();
+    /// let curvature = |c: &NurbsCurve2D<f64>, t: f64| {
+    ///     let d = c.rational_derivatives(t, 2);
+    ///     d[1].perp(&d[2]) / d[1].norm().powi(3)
+    /// };
+    ///
+    /// // the end of the curve runs into the top of the circle
+    /// let matched = curve.try_match(&circle, at(0.25), CurveContinuity::G2, true).unwrap();
+    /// let (start, end) = matched.knots_domain();
+    /// assert_relative_eq!(matched.point_at(end), Point2::new(0., 1.), epsilon = 1e-10);
+    /// assert_relative_eq!(matched.tangent_at(end).normalize(), Vector2::new(1., 0.), epsilon = 1e-10);
+    /// // the curvature of the circle turning the opposite way along the curve
+    /// assert_relative_eq!(curvature(&matched, end), -1., epsilon = 1e-8);
+    /// // the control points other than the last three are untouched
+    /// assert_eq!(matched.control_points()[..2], curve.control_points()[..2]);
+    ///
+    /// // the start of the curve meets the circle with G1 continuity
+    /// let matched = curve.try_match(&circle, at(0.5), CurveContinuity::G1, false).unwrap();
+    /// assert_relative_eq!(matched.point_at(start), Point2::new(-1., 0.), epsilon = 1e-10);
+    /// assert_relative_eq!(matched.tangent_at(start).normalize(), Vector2::new(0., 1.), epsilon = 1e-10);
+    /// ```
+    pub fn try_match(
+        &self,
+        target: &Self,
+        t: T,
+        continuity: CurveContinuity,
+        at_end: bool,
+    ) -> anyhow::Result<Self> {
+        let order = continuity.order();
+        anyhow::ensure!(
+            self.control_points().len() > order && self.degree() >= order,
+            "The curve has too few control points or too low degree to match with the continuity"
+        );
+        let original = if at_end { self.inverse() } else { self.clone() };
+        let mut curve = original.clone();
+        let d = target.rational_derivatives(t, order);
+        let point = |curve: &Self, i: usize| dehomogenize(&curve.control_points()[i]).unwrap();
+        let set = |curve: &mut Self, i: usize, p: &OPoint<T, DimNameDiff<D, U1>>| {
+            let h = curve.control_points_iter_mut().nth(i).unwrap();
+            let w = h[D::dim() - 1];
+            p.iter().enumerate().for_each(|(j, v)| h[j] = *v * w);
+        };
+
+        let p0 = OPoint::from(d[0].clone());
+        set(&mut curve, 0, &p0);
+
+        if order >= 1 {
+            let norm = d[1].norm();
+            anyhow::ensure!(
+                norm > T::default_epsilon(),
+                "The target curve has the zero derivative at the parameter"
+            );
+            let handle = point(&original, 1) - point(&original, 0);
+            let tangent = &d[1] / norm;
+            let sign = if handle.dot(&tangent) < T::zero() {
+                -T::one()
+            } else {
+                T::one()
+            };
+            set(&mut curve, 1, &(&p0 + tangent * (sign * handle.norm())));
+        }
+
+        if order >= 2 {
+            let (u0, _) = curve.knots_domain();
+            let curvature = |c: &Self| -> OVector<T, DimNameDiff<D, U1>> {
+                let d = c.rational_derivatives(u0, 2);
+                let norm = d[1].norm();
+                let tangent = &d[1] / norm;
+                (&d[2] - &tangent * d[2].dot(&tangent)) / (norm * norm)
+            };
+            let target_curvature =
+                (&d[2] - &d[1] * (d[2].dot(&d[1]) / d[1].norm_squared())) / d[1].norm_squared();
+
+            // the curvature vector at the end is affine in the third control point
+            let current = curvature(&curve);
+            let difference = target_curvature - &current;
+            if difference.norm() > T::default_epsilon() {
+                let p2 = point(&curve, 2);
+                set(&mut curve, 2, &(&p2 + &difference));
+                let rate =
+                    (curvature(&curve) - &current).dot(&difference) / difference.norm_squared();
+                anyhow::ensure!(
+                    rate.abs() > T::default_epsilon(),
+                    "The curvature at the end does not depend on the third control point"
+                );
+                set(&mut curve, 2, &(p2 + difference / rate));
+            }
+        }
+
+        if at_end {
+            curve.invert();
+        }
+        Ok(curve)
+    }
+}
@@ -0,0 +1,496 @@
+use argmin::core::ArgminFloat;
+use nalgebra::{ComplexField, Const, Point2, Point3, RealField, Vector2};
+
+use crate::{
+    bounding_box::BoundingBox,
+    curve::{CompoundCurve2D, NurbsCurve, NurbsCurve2D},
+    intersection::{CurveIntersectionKind, CurveIntersectionSolverOptions},
+    misc::FloatingPoint,
+};
+
+use super::{CurveOffsetCorner, CurveOffsetOptions};
+
+/// The maximum depth of the subdivision to approximate the offset of a span
+const MAX_SUBDIVISION_DEPTH: usize = 16;
+
+/// The sample of the offset curve with its derivative by the parameter of the original curve
+#[derive(Clone, Debug)]
+struct OffsetSample<T: FloatingPoint> {
+    parameter: T,
+    point: Point2<T>,
+    derivative: Vector2<T>,
+}
+
+/// Rotate the vector by 90 degrees counterclockwise
+fn left<T: FloatingPoint>(v: &Vector2<T>) -> Vector2<T> {
+    Vector2::new(-v.y, v.x)
+}
+
+impl<T: FloatingPoint + ArgminFloat> NurbsCurve2D<T> {
+    /// Offset the curve by the distance to the left of its direction, or to the right if the distance is negative
+    /// The offset of each smooth span is approximated by the piecewise cubic Hermite curve within the tolerance, and the gaps at the corners are filled as the options specify.
+    /// When the distance exceeds the radius of curvature or the curve folds back, the offset crosses itself; the loops closer to the curve than the distance are trimmed away, so the result may consist of several curves.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point2, Vector2};
+    /// use approx::assert_relative_eq;
+    ///
+    /// // the offset of the counterclockwise circle to the left shrinks it
+    /// let circle = NurbsCurve2D::try_circle(&Point2::origin(), &Vector2::x(), &Vector2::y(), 2.).unwrap();
+    /// let offset = circle.try_offset(0.5, None).unwrap();
+    /// assert_eq!(offset.len(), 1);
+    /// let (start, end) = offset[0].knots_domain();
+    /// for i in 0..=8 {
+    ///     let t = start + (end - start) * i as f64 / 8.;
+    ///     assert_relative_eq!(offset[0].point_at(t).coords.norm(), 1.5, epsilon = 1e-4);
+    /// }
+    ///
+    /// // the inner offset of the rectangle is trimmed at the corners
+    /// let rectangle = NurbsCurve2D::<f64>::polyline(&[
+    ///     Point2::new(0., 0.),
+    ///     Point2::new(4., 0.),
+    ///     Point2::new(4., 2.),
+    ///     Point2::new(0., 2.),
+    ///     Point2::new(0., 0.),
+    /// ]);
+    /// let offset = rectangle.try_offset(0.5, None).unwrap();
+    /// assert_eq!(offset.len(), 1);
+    /// assert!(offset[0].is_closed(Some(1e-3)));
+    /// let points = offset[0].tessellate(None);
+    /// assert!(points.iter().all(|p| p.x > 0.5 - 1e-3 && p.x < 3.5 + 1e-3 && p.y > 0.5 - 1e-3 && p.y < 1.5 + 1e-3));
+    ///
+    /// // the outer offset is rounded at the corners
+    /// let offset = rectangle.try_offset(-0.5, None).unwrap();
+    /// assert_eq!(offset.len(), 1);
+    /// let points = offset[0].tessellate(None);
+    /// assert!(points.iter().all(|p| {
+    ///     let q = Point2::new(p.x.clamp(0., 4.), p.y.clamp(0., 2.));
+    ///     ((p - q).norm() - 0.5).abs() < 1e-3
+    /// }));
+    /// ```
+    pub fn try_offset(
+        &self,
+        distance: T,
+        options: Option<CurveOffsetOptions<T>>,
+    ) -> anyhow::Result<Vec<CompoundCurve2D<T>>> {
+        if distance == T::zero() {
+            return Ok(vec![CompoundCurve2D::from(self.clone())]);
+        }
+        self.try_offset_by(&|_| (distance, T::zero()), options)
+    }
+
+    /// Offset the curve by the distance varying along the parameter of the curve
+    /// The derivative of the distance function is estimated by the central difference.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::Point2;
+    /// use approx::assert_relative_eq;
+    ///
+    /// // the width of the path grows linearly along the line
+    /// let line = NurbsCurve2D::polyline(&[Point2::new(0., 0.), Point2::new(4., 0.)]);
+    /// let (start, end) = line.knots_domain();
+    /// let offset = line
+    ///     .try_offset_with(|t| 0.5 + 0.5 * (t - start) / (end - start), None)
+    ///     .unwrap();
+    /// assert_eq!(offset.len(), 1);
+    /// let (s, e) = offset[0].knots_domain();
+    /// assert_relative_eq!(offset[0].point_at(s), Point2::new(0., 0.5), epsilon = 1e-6);
+    /// assert_relative_eq!(offset[0].point_at((s + e) / 2.), Point2::new(2., 0.75), epsilon = 1e-4);
+    /// assert_relative_eq!(offset[0].point_at(e), Point2::new(4., 1.), epsilon = 1e-6);
+    /// ```
+    pub fn try_offset_with<F>(
+        &self,
+        distance: F,
+        options: Option<CurveOffsetOptions<T>>,
+    ) -> anyhow::Result<Vec<CompoundCurve2D<T>>>
+    where
+        F: Fn(T) -> T,
+    {
+        let (start, end) = self.knots_domain();
+        let h = (end - start) * T::from_f64(1e-6).unwrap();
+        self.try_offset_by(
+            &|t| {
+                let (t0, t1) = (RealField::max(t - h, start), RealField::min(t + h, end));
+                (distance(t), (distance(t1) - distance(t0)) / (t1 - t0))
+            },
+            options,
+        )
+    }
+
+    /// Offset the curve by the distance given as the scalar curve, whose domain is mapped linearly onto the domain of the curve
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Const, Point2, Vector2};
+    /// use approx::assert_relative_eq;
+    ///
+    /// // the distance swelling in the middle of the circle
+    /// let circle = NurbsCurve2D::try_circle(&Point2::origin(), &Vector2::x(), &Vector2::y(), 2.).unwrap();
+    /// let distance = NurbsCurve::<f64, Const<2>>::try_new(
+    ///     2,
+    ///     vec![Point2::new(0.5, 1.), Point2::new(1.5, 1.), Point2::new(0.5, 1.)],
+    ///     vec![0., 0., 0., 1., 1., 1.],
+    /// ).unwrap();
+    /// let offset = circle.try_offset_with_curve(&distance, None).unwrap();
+    /// assert_eq!(offset.len(), 1);
+    /// let (s, e) = offset[0].knots_domain();
+    /// assert_relative_eq!(offset[0].point_at(s).coords.norm(), 1.5, epsilon = 1e-4);
+    /// // the distance at the middle of the domain is 1.0
+    /// assert_relative_eq!(offset[0].point_at((s + e) / 2.).coords.norm(), 1., epsilon = 1e-4);
+    /// ```
+    pub fn try_offset_with_curve(
+        &self,
+        distance: &NurbsCurve<T, Const<2>>,
+        options: Option<CurveOffsetOptions<T>>,
+    ) -> anyhow::Result<Vec<CompoundCurve2D<T>>> {
+        let (start, end) = self.knots_domain();
+        let (s, e) = distance.knots_domain();
+        let scale = (e - s) / (end - start);
+        self.try_offset_by(
+            &|t| {
+                let derivs = distance.rational_derivatives(s + (t - start) * scale, 1);
+                (derivs[0].x, derivs[1].x * scale)
+            },
+            options,
+        )
+    }
+
+    /// Offset the curve by the function of the parameter returning the distance & its derivative
+    fn try_offset_by<F>(
+        &self,
+        distance: &F,
+        options: Option<CurveOffsetOptions<T>>,
+    ) -> anyhow::Result<Vec<CompoundCurve2D<T>>>
+    where
+        F: Fn(T) -> (T, T),
+    {
+        let options = options.unwrap_or_default();
+        anyhow::ensure!(
+            options.tolerance > T::zero(),
+            "The tolerance must be greater than zero"
+        );
+        let spans = self.try_offset_spans(distance, &options)?;
+        if !options.trim {
+            return Ok(vec![CompoundCurve2D::try_new_with_tolerance(
+                spans,
+                options.tolerance,
+            )?]);
+        }
+        self.try_trim_offset_spans(spans, distance, &options)
+    }
+
+    /// Offset each Bézier segment of the curve, and connect the neighboring offsets at the corners
+    /// * `distance` - The function of the parameter returning the offset distance & its derivative
+    fn try_offset_spans<F>(
+        &self,
+        distance: &F,
+        options: &CurveOffsetOptions<T>,
+    ) -> anyhow::Result<Vec<NurbsCurve2D<T>>>
+    where
+        F: Fn(T) -> (T, T),
+    {
+        let segments = self.try_decompose_bezier_segments()?;
+        let (start, end) = self.knots_domain();
+        let closed = (self.point_at(start) - self.point_at(end)).norm() < options.tolerance;
+
+        let mut spans: Vec<NurbsCurve2D<T>> = vec![];
+        let offsets = segments
+            .iter()
+            .map(|s| try_offset_segment(s, distance, options.tolerance))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let n = segments.len();
+        for i in 0..n {
+            spans.push(offsets[i].clone());
+            let j = i + 1;
+            if j == n && !closed {
+                break;
+            }
+            let j = j % n;
+            let (_, t0) = segments[i].knots_domain();
+            let (t1, _) = segments[j].knots_domain();
+            let (_, e) = offsets[i].knots_domain();
+            let (s, _) = offsets[j].knots_domain();
+            let (q0, q1) = (offsets[i].point_at(e), offsets[j].point_at(s));
+            if (q1 - q0).norm() < options.tolerance {
+                continue;
+            }
+            let corner = segments[i].point_at(t0);
+            let tangents = (
+                segments[i].tangent_at(t0).normalize(),
+                segments[j].tangent_at(t1).normalize(),
+            );
+            spans.extend(try_offset_corner(
+                &corner,
+                &q0,
+                &q1,
+                tangents,
+                distance(t0).0,
+                options.corner,
+                options.tolerance,
+            )?);
+        }
+        Ok(spans)
+    }
+
+    /// Split the offset spans at their intersections, and keep the pieces not closer to the curve than the distance
+    fn try_trim_offset_spans<F>(
+        &self,
+        spans: Vec<NurbsCurve2D<T>>,
+        distance: &F,
+        options: &CurveOffsetOptions<T>,
+    ) -> anyhow::Result<Vec<CompoundCurve2D<T>>>
+    where
+        F: Fn(T) -> (T, T),
+    {
+        let tolerance = options.tolerance;
+        let intersection_options = CurveIntersectionSolverOptions::default()
+            .with_minimum_distance(tolerance)
+            .with_knot_domain_division(16);
+
+        let mut splits: Vec<Vec<T>> = vec![vec![]; spans.len()];
+        let boxes = spans.iter().map(BoundingBox::from).collect::<Vec<_>>();
+        for i in 0..spans.len() {
+            for it in spans[i].find_self_intersections(Some(intersection_options.clone()))? {
+                splits[i].extend([it.a().1, it.b().1]);
+            }
+            for j in (i + 1)..spans.len() {
+                if !boxes[i].intersects(&boxes[j], Some(tolerance)) {
+                    continue;
+                }
+                let (da, db) = (spans[i].knots_domain(), spans[j].knots_domain());
+                let found = spans[i]
+                    .find_intersections(&spans[j], Some(intersection_options.clone()))?
+                    .into_iter()
+                    // the solver may converge to the extrapolation beyond the end of the span
+                    .filter(|it| {
+                        let (ta, tb) = (it.a().1, it.b().1);
+                        da.0 <= ta && ta <= da.1 && db.0 <= tb && tb <= db.1
+                    });
+                for it in found {
+                    match *it.kind() {
+                        CurveIntersectionKind::Overlap { a, b } => {
+                            splits[i].extend([a.0, a.1]);
+                            splits[j].extend([b.0, b.1]);
+                        }
+                        _ => {
+                            splits[i].push(it.a().1);
+                            splits[j].push(it.b().1);
+                        }
+                    }
+                }
+            }
+        }
+
+        // the piece crossing into the band around the curve is a part of the loop to be removed
+        let mut pieces = vec![];
+        for (span, mut parameters) in spans.into_iter().zip(splits) {
+            parameters.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+            for piece in try_split_at(span, &parameters, tolerance)? {
+                let (s, e) = piece.knots_domain();
+                let mid = piece.point_at((s + e) * T::from_f64(0.5).unwrap());
+                let u = self.find_closest_parameter(&mid)?;
+                let gap = (mid - self.point_at(u)).norm();
+                if gap >= ComplexField::abs(distance(u).0) - tolerance * T::from_usize(10).unwrap()
+                {
+                    pieces.push(piece);
+                }
+            }
+        }
+
+        // connect the remaining pieces sharing the end points
+        let connection = tolerance * T::from_usize(10).unwrap();
+        let mut chains: Vec<Vec<NurbsCurve2D<T>>> = vec![];
+        for piece in pieces {
+            let (s, _) = piece.knots_domain();
+            let head = piece.point_at(s);
+            match chains.last_mut() {
+                Some(chain)
+                    if {
+                        let last = chain.last().unwrap();
+                        let (_, e) = last.knots_domain();
+                        (last.point_at(e) - head).norm() < connection
+                    } =>
+                {
+                    chain.push(piece)
+                }
+                _ => chains.push(vec![piece]),
+            }
+        }
+        if chains.len() > 1 {
+            let first = &chains[0][0];
+            let last = chains.last().unwrap().last().unwrap();
+            let (s, _) = first.knots_domain();
+            let (_, e) = last.knots_domain();
+            if (last.point_at(e) - first.point_at(s)).norm() < connection {
+                let mut tail = chains.pop().unwrap();
+                tail.append(&mut chains[0]);
+                chains[0] = tail;
+            }
+        }
+
+        chains
+            .into_iter()
+            .map(|chain| CompoundCurve2D::try_new_with_tolerance(chain, connection))
+            .collect()
+    }
+}
+
+/// Evaluate the offset point & its derivative at the parameter of the curve
+fn offset_sample<T: FloatingPoint, F: Fn(T) -> (T, T)>(
+    curve: &NurbsCurve2D<T>,
+    parameter: T,
+    distance: &F,
+) -> OffsetSample<T> {
+    let derivs = curve.rational_derivatives(parameter, 2);
+    let (p, d1, d2) = (&derivs[0], &derivs[1], &derivs[2]);
+    let speed = d1.norm();
+    let (d, dd) = distance(parameter);
+    if speed <= T::default_epsilon() {
+        return OffsetSample {
+            parameter,
+            point: Point2::from(*p),
+            derivative: *d1,
+        };
+    }
+    let normal = left(d1) / speed;
+    let normal_derivative = left(d2) / speed - left(d1) * (d1.dot(d2) / (speed * speed * speed));
+    OffsetSample {
+        parameter,
+        point: Point2::from(p + normal * d),
+        derivative: d1 + normal_derivative * d + normal * dd,
+    }
+}
+
+/// Approximate the offset of the smooth segment by the cubic Hermite pieces keeping the parameterization of the segment
+fn try_offset_segment<T: FloatingPoint, F: Fn(T) -> (T, T)>(
+    segment: &NurbsCurve2D<T>,
+    distance: &F,
+    tolerance: T,
+) -> anyhow::Result<NurbsCurve2D<T>> {
+    let (start, end) = segment.knots_domain();
+    let third = T::from_f64(1. / 3.).unwrap();
+    let handles = |a: &OffsetSample<T>, b: &OffsetSample<T>| {
+        let h = (b.parameter - a.parameter) * third;
+        (a.point + a.derivative * h, b.point - b.derivative * h)
+    };
+
+    let initial = 4;
+    let mut samples = vec![offset_sample(segment, start, distance)];
+    let mut stack = (0..initial)
+        .rev()
+        .map(|i| {
+            let t0 =
+                start + (end - start) * T::from_usize(i).unwrap() / T::from_usize(initial).unwrap();
+            let t1 = start
+                + (end - start) * T::from_usize(i + 1).unwrap() / T::from_usize(initial).unwrap();
+            (t0, t1, 0)
+        })
+        .collect::<Vec<_>>();
+    while let Some((t0, t1, depth)) = stack.pop() {
+        let a = samples.last().unwrap().clone();
+        let b = offset_sample(segment, t1, distance);
+        let mid = offset_sample(segment, (t0 + t1) * T::from_f64(0.5).unwrap(), distance);
+        let (h0, h1) = handles(&a, &b);
+        let estimated =
+            (a.point.coords + (h0.coords + h1.coords) * T::from_usize(3).unwrap() + b.point.coords)
+                / T::from_usize(8).unwrap();
+        if (estimated - mid.point.coords).norm() <= tolerance || depth >= MAX_SUBDIVISION_DEPTH {
+            samples.push(b);
+        } else {
+            stack.push((mid.parameter, t1, depth + 1));
+            stack.push((t0, mid.parameter, depth + 1));
+        }
+    }
+
+    let mut control_points = vec![samples[0].point];
+    let mut knots = vec![start; 4];
+    for w in samples.windows(2) {
+        let (h0, h1) = handles(&w[0], &w[1]);
+        control_points.extend([h0, h1, w[1].point]);
+        knots.extend([w[1].parameter; 3]);
+    }
+    knots.push(end);
+    NurbsCurve2D::try_new(
+        3,
+        control_points
+            .into_iter()
+            .map(|p| Point3::new(p.x, p.y, T::one()))
+            .collect(),
+        knots,
+    )
+}
+
+/// Fill the gap between the offsets at the corner, or connect the overlapping offsets by the line to be trimmed
+fn try_offset_corner<T: FloatingPoint>(
+    corner: &Point2<T>,
+    q0: &Point2<T>,
+    q1: &Point2<T>,
+    tangents: (Vector2<T>, Vector2<T>),
+    distance: T,
+    kind: CurveOffsetCorner,
+    tolerance: T,
+) -> anyhow::Result<Vec<NurbsCurve2D<T>>> {
+    let (t0, t1) = tangents;
+    let cross = t0.x * t1.y - t0.y * t1.x;
+    if cross * distance >= T::zero() {
+        return Ok(vec![NurbsCurve2D::polyline(&[*q0, *q1])]);
+    }
+
+    if kind == CurveOffsetCorner::Sharp {
+        // the intersection of the lines q0 + s * t0 & q1 - u * t1
+        let d = q1 - q0;
+        let s = (d.x * t1.y - d.y * t1.x) / cross;
+        if ComplexField::abs(cross) > T::from_f64(1e-3).unwrap() && s > T::zero() {
+            let miter = q0 + t0 * s;
+            return Ok(vec![
+                NurbsCurve2D::polyline(&[*q0, miter]),
+                NurbsCurve2D::polyline(&[miter, *q1]),
+            ]);
+        }
+    }
+
+    // the distances at the both sides of the seam of the closed curve may differ
+    let radius = (q0 - corner).norm();
+    let (a, b) = ((q0 - corner) / radius, (q1 - corner).normalize());
+    let sweep = RealField::atan2(a.x * b.y - a.y * b.x, a.dot(&b));
+    let y = if sweep > T::zero() {
+        left(&a)
+    } else {
+        -left(&a)
+    };
+    let arc = NurbsCurve2D::try_arc(corner, &a, &y, radius, T::zero(), ComplexField::abs(sweep))?;
+    let (_, end) = arc.knots_domain();
+    let tail = arc.point_at(end);
+    if (tail - q1).norm() < tolerance {
+        Ok(vec![arc])
+    } else {
+        Ok(vec![arc, NurbsCurve2D::polyline(&[tail, *q1])])
+    }
+}
+
+/// Split the curve at the parameters in ascending order, skipping the parameters near the ends of the pieces
+fn try_split_at<T: FloatingPoint>(
+    curve: NurbsCurve2D<T>,
+    parameters: &[T],
+    tolerance: T,
+) -> anyhow::Result<Vec<NurbsCurve2D<T>>> {
+    let mut pieces = vec![];
+    let mut rest = curve;
+    for t in parameters {
+        let (s, e) = rest.knots_domain();
+        if (rest.point_at(*t) - rest.point_at(s)).norm() < tolerance
+            || (rest.point_at(*t) - rest.point_at(e)).norm() < tolerance
+            || *t <= s
+            || *t >= e
+        {
+            continue;
+        }
+        let (head, tail) = rest.try_trim(*t)?;
+        pieces.push(head);
+        rest = tail;
+    }
+    pieces.push(rest);
+    Ok(pieces)
+}
@@ -0,0 +1,9 @@
+/// The shape to fill the gap of the offset curve at the corner where the tangent of the curve is discontinuous
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CurveOffsetCorner {
+    /// Connect the offset curves with the circular arc around the corner
+    #[default]
+    Round,
+    /// Extend the offset curves along their tangents until they meet, falling back to the arc if they are almost parallel
+    Sharp,
+}
@@ -0,0 +1,41 @@
+use crate::misc::FloatingPoint;
+
+use super::CurveOffsetCorner;
+
+/// Options for offsetting a curve
+#[derive(Clone, Debug)]
+pub struct CurveOffsetOptions<T: FloatingPoint> {
+    /// The maximum deviation of the approximated offset curve from the exact offset
+    pub tolerance: T,
+    /// The shape to fill the gap at the corners of the curve
+    pub corner: CurveOffsetCorner,
+    /// Whether to remove the loops of the offset curve closer to the original curve than the distance
+    pub trim: bool,
+}
+
+impl<T: FloatingPoint> Default for CurveOffsetOptions<T> {
+    fn default() -> Self {
+        Self {
+            tolerance: T::from_f64(1e-4).unwrap(),
+            corner: CurveOffsetCorner::default(),
+            trim: true,
+        }
+    }
+}
+
+impl<T: FloatingPoint> CurveOffsetOptions<T> {
+    pub fn with_tolerance(mut self, tolerance: T) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn with_corner(mut self, corner: CurveOffsetCorner) -> Self {
+        self.corner = corner;
+        self
+    }
+
+    pub fn with_trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+        self
+    }
+}
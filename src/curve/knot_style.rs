@@ -51,6 +51,28 @@ impl KnotStyle {
         }
     }
 
+    /// Compute the parameters of the open sequence of points normalized into the unit interval
+    pub fn parameters<T: FloatingPoint>(&self, points: &[DVector<T>]) -> Vec<T> {
+        let n = points.len();
+        if n < 2 {
+            return vec![T::zero(); n];
+        }
+        let intervals: Vec<T> = match self {
+            KnotStyle::Uniform => vec![T::one(); n - 1],
+            _ => self.parameterize(points, false),
+        };
+        let mut parameters = vec![T::zero()];
+        for d in intervals.iter() {
+            let last = parameters[parameters.len() - 1];
+            parameters.push(last + *d);
+        }
+        let total = parameters[n - 1];
+        if total > T::zero() {
+            parameters.iter_mut().for_each(|p| *p /= total);
+        }
+        parameters
+    }
+
     pub fn alpha<T: FloatingPoint>(&self) -> T {
         match self {
             KnotStyle::Chordal => T::one(),
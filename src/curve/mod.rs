@@ -1,6 +1,35 @@
+pub mod arc_length_map;
+pub mod compound_curve;
+pub mod curvature_comb;
+pub mod curvature_feature;
+pub mod curve_approximation_options;
+pub mod curve_blend;
+pub mod curve_chamfer;
+pub mod curve_continuity;
+pub mod curve_fairing;
+pub mod curve_fairing_options;
+pub mod curve_fillet;
 pub mod curve_length_parameter;
+pub mod curve_match;
+pub mod curve_offset;
+pub mod curve_offset_corner;
+pub mod curve_offset_options;
 pub mod knot_style;
 pub mod nurbs_curve;
+pub mod planar_moments;
+pub mod region;
+pub use arc_length_map::*;
+pub use compound_curve::*;
+pub use curvature_comb::*;
+pub use curvature_feature::*;
+pub use curve_approximation_options::*;
+pub use curve_chamfer::*;
+pub use curve_continuity::*;
+pub use curve_fairing_options::*;
 pub use curve_length_parameter::*;
+pub use curve_offset_corner::*;
+pub use curve_offset_options::*;
 pub use knot_style::*;
 pub use nurbs_curve::*;
+pub use planar_moments::*;
+pub use region::*;
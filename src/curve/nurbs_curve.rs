@@ -2,29 +2,46 @@ use std::f64::consts::{FRAC_PI_2, TAU};
 use std::vec;
 
 use argmin::core::{ArgminFloat, Executor, State};
-use gauss_quad::GaussLegendre;
 use itertools::Itertools;
 use nalgebra::allocator::Allocator;
 use nalgebra::{
     ComplexField, Const, DMatrix, DVector, DefaultAllocator, DimName, DimNameAdd, DimNameDiff,
-    DimNameSub, DimNameSum, Matrix2, OMatrix, OPoint, OVector, RealField, Rotation3, UnitVector3,
-    Vector2, Vector3, U1,
+    DimNameSub, DimNameSum, Dyn, Matrix2, Matrix3, OMatrix, OPoint, OVector, Point3, RealField,
+    Rotation3, UnitVector3, Vector2, Vector3, U1,
 };
 use rand::rngs::ThreadRng;
 use rand::Rng;
 use simba::scalar::SupersetOf;
 
+use crate::closest_parameter::closest_parameter_subdivision::{
+    deviation, find_closest_parameter_candidates, find_closest_parameter_pair_candidates,
+    find_curve_surface_closest_parameter_candidates, segments_closest_parameters,
+};
 use crate::intersection::curve_intersection::CurveIntersection;
+use crate::intersection::curve_intersection_bezier_clipping::{
+    bezier_clipping_intersections, BezierClip,
+};
 use crate::intersection::{
-    CurveIntersectionBFGS, CurveIntersectionProblem, CurveIntersectionSolverOptions,
+    CurveIntersectionBFGS, CurveIntersectionBackend, CurveIntersectionKind,
+    CurveIntersectionProblem, CurveIntersectionSolverOptions, CurveSurfaceIntersection,
+    CurveSurfaceIntersectionSolverOptions, RayCurveIntersection,
 };
 use crate::misc::binomial::Binomial;
-use crate::misc::frenet_frame::FrenetFrame;
+use crate::misc::deformable::{Deformable, SpaceDeformation};
+use crate::misc::frenet_frame::{CurvatureFrame, FrenetFrame};
 use crate::misc::transformable::Transformable;
-use crate::misc::trigonometry::{segment_closest_point, three_points_are_flat};
+use crate::misc::trigonometry::three_points_are_flat;
 use crate::misc::Ray;
-use crate::prelude::{BoundingBoxTraversal, CurveLengthParameter, Invertible, KnotVector};
-use crate::{misc::FloatingPoint, ClosestParameterNewton, ClosestParameterProblem};
+use crate::prelude::{
+    ArcLengthMap, BoundingBox, BoundingBoxTraversal, BoundingBoxTree, CurveApproximationOptions,
+    CurveFitOptions, CurveFitProblem, CurveLengthParameter, Invertible, KnotVector,
+    LevenbergMarquardt, SurfaceBoundingBoxTree,
+};
+use crate::surface::{try_unify_curve_knot_vectors, NurbsSurface, NurbsSurface3D};
+use crate::{
+    misc::FloatingPoint, ClosestParameterNewton, ClosestParameterOptions, ClosestParameterProblem,
+    CurveClosestParameters, CurveSurfaceClosestParameters,
+};
 
 use super::KnotStyle;
 
@@ -333,11 +350,8 @@ where
     }
 
     /// Evaluate the rational derivatives at a given parameter
-    pub(crate) fn rational_derivatives(
-        &self,
-        u: T,
-        derivs: usize,
-    ) -> Vec<OVector<T, DimNameDiff<D, U1>>>
+    /// The first element is the point itself, followed by the derivatives up to the given order.
+    pub fn rational_derivatives(&self, u: T, derivs: usize) -> Vec<OVector<T, DimNameDiff<D, U1>>>
     where
         D: DimNameSub<U1>,
         DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
@@ -436,7 +450,8 @@ where
         self.knots.constrain(self.degree, u)
     }
 
-    /// Compute the length of the curve by gauss-legendre quadrature
+    /// Compute the length of the curve by the adaptive gauss-legendre quadrature
+    /// The tolerance is relative to the length of the control polygon, which bounds the length of the curve.
     /// # Example
     /// ```
     /// use curvo::prelude::*;
@@ -457,36 +472,75 @@ where
         D: DimNameSub<U1>,
         DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
     {
-        let mult = self.knots.multiplicity();
-        let start = mult.first().unwrap().multiplicity();
-        let end = mult.last().unwrap().multiplicity();
+        let map = self.try_arc_length_map(self.default_length_tolerance())?;
+        Ok(map.length())
+    }
 
-        let segments = self.try_decompose_bezier_segments()?;
+    /// The tolerance of the arc length relative to the length of the control polygon
+    fn default_length_tolerance(&self) -> T
+    where
+        D: DimNameSub<U1>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+    {
+        let polygon = self
+            .dehomogenized_control_points()
+            .windows(2)
+            .map(|w| (&w[1] - &w[0]).norm())
+            .fold(T::zero(), |a, b| a + b);
+        (polygon * T::from_f64(1e-12).unwrap()).max(T::default_epsilon())
+    }
 
-        // If the start/end parts of the knot vector are not duplicated,
-        // the Bezier segments will not be generated correctly,
-        // so reduce the number of segments by the amount that falls below the required duplication degree.
-        let required_multiplicity = self.degree + 1;
-        let i = if start < required_multiplicity {
-            required_multiplicity - start
-        } else {
-            0
-        };
-        let j = if end < required_multiplicity {
-            segments.len() - (required_multiplicity - end)
-        } else {
-            segments.len()
-        };
-        let segments = &segments[i..j];
+    /// Compute the arc length between the parameters within the tolerance by the adaptive gauss-legendre quadrature
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point2, Vector2};
+    /// use approx::assert_relative_eq;
+    /// let unit_circle = NurbsCurve2D::try_circle(
+    ///     &Point2::origin(),
+    ///     &Vector2::x(),
+    ///     &Vector2::y(),
+    ///     1.
+    /// ).unwrap();
+    /// let (start, end) = unit_circle.knots_domain();
+    /// let mid = (start + end) / 2.;
+    /// let half = unit_circle.try_length_between(start, mid, 1e-10).unwrap();
+    /// assert_relative_eq!(half, std::f64::consts::PI, epsilon = 1e-10);
+    /// // the order of the parameters does not matter
+    /// assert_relative_eq!(unit_circle.try_length_between(mid, start, 1e-10).unwrap(), half);
+    /// ```
+    pub fn try_length_between(&self, t0: T, t1: T, tolerance: T) -> anyhow::Result<T>
+    where
+        D: DimNameSub<U1>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+    {
+        let map = self.try_arc_length_map(tolerance)?;
+        Ok(map.length_between(t0, t1))
+    }
 
-        let (_, u) = self.knots_domain();
-        let gauss = GaussLegendre::new(16 + self.degree).unwrap();
-        let length = segments
-            .iter()
-            .map(|s| compute_bezier_segment_length(s, u, &gauss))
-            .reduce(T::add)
-            .unwrap();
-        Ok(length)
+    /// Compute the arc length of each span between the distinct knots within the tolerance
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::Point3;
+    /// use approx::assert_relative_eq;
+    /// let polyline = NurbsCurve3D::polyline(&[
+    ///     Point3::origin(),
+    ///     Point3::new(3., 0., 0.),
+    ///     Point3::new(3., 4., 0.),
+    /// ]);
+    /// let lengths = polyline.try_span_lengths(1e-10).unwrap();
+    /// assert_eq!(lengths.len(), 2);
+    /// assert_relative_eq!(lengths[0], 3.);
+    /// assert_relative_eq!(lengths[1], 4.);
+    /// ```
+    pub fn try_span_lengths(&self, tolerance: T) -> anyhow::Result<Vec<T>>
+    where
+        D: DimNameSub<U1>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+    {
+        let map = self.try_arc_length_map(tolerance)?;
+        Ok(map.span_lengths())
     }
 
     /// Divide a NURBS curve by a given length
@@ -517,54 +571,62 @@ where
     {
         anyhow::ensure!(length > T::zero(), "The length must be greater than zero");
 
-        let segments = self.try_decompose_bezier_segments()?;
-        let lengthes = segments
-            .iter()
-            .map(|s| s.try_length())
-            .collect::<anyhow::Result<Vec<_>>>()?;
-        let total = lengthes.iter().fold(T::zero(), |a, b| a + *b);
-
+        let eps = self.default_length_tolerance();
+        let map = self.try_arc_length_map(eps)?;
+        let total = map.length();
         anyhow::ensure!(
-            total > length,
+            total + eps >= length,
             "The curve is too short to divide by the given length"
         );
 
-        let mut samples = vec![CurveLengthParameter::new(self.knots.first(), T::zero())];
-
-        let mut i = 0;
-        let mut lc = length;
-
-        let mut acc = T::zero();
-        let mut acc_prev = T::zero();
-
-        let gauss = GaussLegendre::new(16 + self.degree).unwrap();
-        let eps = T::from_f64(1e-6).unwrap();
-        let tolerance = T::from_f64(1e-3 * 2.5).unwrap();
-
-        while i < segments.len() {
-            let current_length = lengthes[i];
-            acc += current_length;
-
-            while lc < acc + eps {
-                let u = compute_bezier_segment_parameter_at_length(
-                    &segments[i],
-                    lc - acc_prev,
-                    tolerance,
-                    current_length,
-                    &gauss,
-                );
-                samples.push(CurveLengthParameter::new(u, lc));
-                lc += length;
-            }
-
-            acc_prev += current_length;
-            i += 1;
-        }
-
+        let count = ((total + eps) / length).floor().to_usize().unwrap();
+        let (_, end) = self.knots_domain();
+        let samples = (0..=count)
+            .map(|i| {
+                let l = length * T::from_usize(i).unwrap();
+                // snap the sample at the end of the curve to the end parameter
+                if (total - l).abs() <= eps {
+                    CurveLengthParameter::new(end, l)
+                } else {
+                    CurveLengthParameter::new(map.parameter_at_length(l), l)
+                }
+            })
+            .collect();
         Ok(samples)
     }
 
-    /// Divide the curve by a given number of segments
+    /// Divide the curve by a given number of segments of equal arc length
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::Point3;
+    /// use approx::assert_relative_eq;
+    ///
+    /// let points = vec![
+    ///     Point3::new(0., 0., 0.),
+    ///     Point3::new(1., 2., 0.),
+    ///     Point3::new(3., 1., 0.),
+    ///     Point3::new(4., 3., 1.),
+    /// ];
+    /// let curve = NurbsCurve3D::try_interpolate(&points, 3).unwrap();
+    /// let map = curve.try_arc_length_map(1e-9).unwrap();
+    /// let total = map.length();
+    /// for count in 1..6 {
+    ///     let params = curve.try_divide_by_count(count).unwrap();
+    ///     assert_eq!(params.len(), count + 1);
+    ///     assert_relative_eq!(params.last().unwrap().parameter(), curve.knots_domain().1);
+    ///     for (i, p) in params.iter().enumerate() {
+    ///         assert_relative_eq!(p.length(), total * i as f64 / count as f64, epsilon = 1e-8);
+    ///     }
+    /// }
+    ///
+    /// // the points are evenly spaced along the curve
+    /// let params = curve.try_divide_by_count(4).unwrap();
+    /// for w in params.windows(2) {
+    ///     let segment = map.length_at(w[1].parameter()) - map.length_at(w[0].parameter());
+    ///     assert_relative_eq!(segment, total / 4., epsilon = 1e-7);
+    /// }
+    /// ```
     pub fn try_divide_by_count(
         &self,
         segments: usize,
@@ -573,11 +635,35 @@ where
         D: DimNameSub<U1>,
         DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
     {
+        anyhow::ensure!(
+            segments > 0,
+            "The number of segments must be greater than zero"
+        );
         let length = self.try_length()?;
         let u = length / T::from_usize(segments).unwrap();
         self.try_divide_by_length(u)
     }
 
+    /// Build the bidirectional mapping between the parameter & the arc length within the tolerance
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::Point3;
+    /// use approx::assert_relative_eq;
+    ///
+    /// let line = NurbsCurve3D::polyline(&[Point3::origin(), Point3::new(3., 0., 0.), Point3::new(3., 4., 0.)]);
+    /// let map = line.try_arc_length_map(1e-6).unwrap();
+    /// assert_relative_eq!(map.length(), 7., epsilon = 1e-6);
+    /// assert_relative_eq!(map.point_at_length(5.), Point3::new(3., 2., 0.), epsilon = 1e-6);
+    /// ```
+    pub fn try_arc_length_map(&self, tolerance: T) -> anyhow::Result<ArcLengthMap<T, D>>
+    where
+        D: DimNameSub<U1>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+    {
+        ArcLengthMap::try_new(self, tolerance)
+    }
+
     /// Try to create a periodic NURBS curve from a set of points
     /// ```
     /// use curvo::prelude::*;
@@ -688,7 +774,412 @@ where
         })
     }
 
+    /// Try to interpolate the points with the optional tangent directions at the ends
+    /// The tangents are scaled by the total chord length of the points, which keeps the speed of the curve over the unit domain natural.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point2, Vector2};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let points = vec![Point2::new(0., 0.), Point2::new(1., 1.), Point2::new(2., 0.), Point2::new(3., 1.)];
+    /// let curve = NurbsCurve2D::try_interpolate_with_tangents(&points, 3, Some(&Vector2::x()), Some(&-Vector2::y())).unwrap();
+    /// let (start, end) = curve.knots_domain();
+    /// assert_relative_eq!(curve.point_at(start), points[0], epsilon = 1e-10);
+    /// assert_relative_eq!(curve.point_at(end), points[3], epsilon = 1e-10);
+    /// assert_relative_eq!(curve.tangent_at(start).normalize(), Vector2::x(), epsilon = 1e-10);
+    /// assert_relative_eq!(curve.tangent_at(end).normalize(), -Vector2::y(), epsilon = 1e-10);
+    /// ```
+    pub fn try_interpolate_with_tangents(
+        points: &[OPoint<T, DimNameDiff<D, U1>>],
+        degree: usize,
+        start_tangent: Option<&OVector<T, DimNameDiff<D, U1>>>,
+        end_tangent: Option<&OVector<T, DimNameDiff<D, U1>>>,
+    ) -> anyhow::Result<Self>
+    where
+        D: DimNameSub<U1>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+    {
+        let length = points
+            .windows(2)
+            .fold(T::zero(), |acc, w| acc + (&w[1] - &w[0]).norm());
+        let scale = |t: &OVector<T, DimNameDiff<D, U1>>| t.normalize() * length;
+        let start = start_tangent.map(scale).into_iter().collect::<Vec<_>>();
+        let end = end_tangent.map(scale).into_iter().collect::<Vec<_>>();
+        Self::try_interpolate_with_end_derivatives(points, degree, &start, &end)
+    }
+
+    /// Try to interpolate the points with the derivatives at the ends
+    /// `start_derivatives` & `end_derivatives` are the first, second & higher derivatives up to the degree with respect to the unit domain of the curve,
+    /// so the second derivatives fix the curvatures at the ends to continue other curves with G2 continuity.
+    /// The points are parameterized by the chord lengths, and the knots average the parameters with the constrained ends repeated.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point2, Vector2};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let points = vec![Point2::new(0., 0.), Point2::new(1., 0.5), Point2::new(2., 0.)];
+    /// let d1 = Vector2::new(2f64, 0.);
+    /// let d2 = Vector2::new(0., 4.);
+    /// let curve = NurbsCurve2D::try_interpolate_with_end_derivatives(&points, 3, &[d1, d2], &[d1]).unwrap();
+    /// let derivs = curve.rational_derivatives(0., 2);
+    /// assert_relative_eq!(derivs[1], d1, epsilon = 1e-10);
+    /// assert_relative_eq!(derivs[2], d2, epsilon = 1e-10);
+    /// // the curvature at the start is |d1 x d2| / |d1|^3
+    /// let curvature = derivs[1].perp(&derivs[2]) / derivs[1].norm().powi(3);
+    /// assert_relative_eq!(curvature, 1., epsilon = 1e-10);
+    /// assert_relative_eq!(curve.rational_derivatives(1., 1)[1], d1, epsilon = 1e-10);
+    /// assert_relative_eq!(curve.point_at(1.), points[2], epsilon = 1e-10);
+    /// ```
+    pub fn try_interpolate_with_end_derivatives(
+        points: &[OPoint<T, DimNameDiff<D, U1>>],
+        degree: usize,
+        start_derivatives: &[OVector<T, DimNameDiff<D, U1>>],
+        end_derivatives: &[OVector<T, DimNameDiff<D, U1>>],
+    ) -> anyhow::Result<Self>
+    where
+        D: DimNameSub<U1>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+    {
+        let m = points.len();
+        let (s, e) = (start_derivatives.len(), end_derivatives.len());
+        let n = m + s + e;
+        anyhow::ensure!(m >= 2, "Too few points to interpolate");
+        anyhow::ensure!(degree >= 1 && n > degree, "Too few points for the degree");
+        anyhow::ensure!(
+            s <= degree && e <= degree,
+            "The derivatives must not exceed the degree"
+        );
+
+        let vectors = points
+            .iter()
+            .map(|p| DVector::from_vec(p.iter().copied().collect()))
+            .collect::<Vec<_>>();
+        let parameters = KnotStyle::Chordal.parameters(&vectors);
+
+        // the constrained ends are repeated in the parameters to be averaged into the knots
+        let repeated = [vec![T::zero(); s], parameters.clone(), vec![T::one(); e]].concat();
+        let mut knots = vec![T::zero(); degree + 1];
+        for j in 1..(n - degree) {
+            let sum = repeated[j..(j + degree)]
+                .iter()
+                .fold(T::zero(), |acc, u| acc + *u);
+            knots.push(sum / T::from_usize(degree).unwrap());
+        }
+        knots.extend(vec![T::one(); degree + 1]);
+        let knots = KnotVector::new(knots);
+
+        let dim = D::dim() - 1;
+        let mut m_a = DMatrix::<T>::zeros(n, n);
+        let mut rhs = DMatrix::<T>::zeros(n, dim);
+        let mut row = 0;
+        let mut push = |u: T, order: usize, value: &[T]| {
+            let span = knots.find_knot_span_index(n - 1, degree, u);
+            let basis = knots.derivative_basis_functions(span, u, degree, order);
+            for (i, b) in basis[order].iter().enumerate() {
+                m_a[(row, span - degree + i)] = *b;
+            }
+            for (c, v) in value.iter().enumerate() {
+                rhs[(row, c)] = *v;
+            }
+            row += 1;
+        };
+        for (k, (u, v)) in parameters.iter().zip(vectors.iter()).enumerate() {
+            push(*u, 0, v.as_slice());
+            if k == 0 {
+                for (order, d) in start_derivatives.iter().enumerate() {
+                    push(T::zero(), order + 1, d.as_slice());
+                }
+            }
+        }
+        for (order, d) in end_derivatives.iter().enumerate() {
+            push(T::one(), order + 1, d.as_slice());
+        }
+
+        let x = m_a
+            .lu()
+            .solve(&rhs)
+            .ok_or(anyhow::anyhow!("Solve failed"))?;
+        Ok(Self {
+            degree,
+            control_points: x
+                .row_iter()
+                .map(|r| {
+                    let mut coords = r.iter().copied().collect::<Vec<_>>();
+                    coords.push(T::one());
+                    OPoint::from_slice(&coords)
+                })
+                .collect(),
+            knots,
+        })
+    }
+
+    /// Try to approximate the points by a curve with the number of control points in the least squares sense
+    /// The curve passes through the first & last points, and the knots are placed to distribute the parameters of the points evenly over the spans.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::Point2;
+    /// use approx::assert_relative_eq;
+    ///
+    /// // the noisy samples of a sine wave
+    /// let points = (0..=50)
+    ///     .map(|i| {
+    ///         let x = i as f64 / 50. * std::f64::consts::TAU;
+    ///         let noise = if i % 2 == 0 { 1e-3 } else { -1e-3 };
+    ///         Point2::new(x, x.sin() + noise)
+    ///     })
+    ///     .collect::<Vec<_>>();
+    /// let curve = NurbsCurve2D::try_approximate(&points, 3, 16).unwrap();
+    /// assert_eq!(curve.control_points().len(), 16);
+    ///
+    /// let (start, end) = curve.knots_domain();
+    /// assert_relative_eq!(curve.point_at(start), points[0]);
+    /// assert_relative_eq!(curve.point_at(end), points[50], epsilon = 1e-10);
+    /// for p in points.iter() {
+    ///     let closest = curve.find_closest_point(p).unwrap();
+    ///     assert!((closest - p).norm() < 1e-2);
+    /// }
+    /// ```
+    pub fn try_approximate(
+        points: &[OPoint<T, DimNameDiff<D, U1>>],
+        degree: usize,
+        control_points_count: usize,
+    ) -> anyhow::Result<Self>
+    where
+        D: DimNameSub<U1>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+    {
+        Self::try_approximate_with_options(points, degree, control_points_count, Default::default())
+    }
+
+    /// Try to approximate the points by a curve with the options of the parameterization & the smoothing
+    /// The smoothing penalizes the second differences of the control points to suppress the wiggles caused by the noise.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::Point2;
+    ///
+    /// let points = (0..=40)
+    ///     .map(|i| {
+    ///         let noise = if i % 2 == 0 { 0.05 } else { -0.05 };
+    ///         Point2::new(i as f64 / 10., noise)
+    ///     })
+    ///     .collect::<Vec<_>>();
+    /// let roughness = |c: &NurbsCurve2D<f64>| {
+    ///     c.control_points()
+    ///         .windows(3)
+    ///         .map(|w| (w[0].coords - w[1].coords * 2. + w[2].coords).norm())
+    ///         .sum::<f64>()
+    /// };
+    /// let rough = NurbsCurve2D::try_approximate(&points, 3, 20).unwrap();
+    /// let options = CurveApproximationOptions::default()
+    ///     .with_knot_style(KnotStyle::Centripetal)
+    ///     .with_smoothing(1.);
+    /// let smooth = NurbsCurve2D::try_approximate_with_options(&points, 3, 20, options).unwrap();
+    /// assert!(roughness(&smooth) < roughness(&rough) * 0.5);
+    /// ```
+    pub fn try_approximate_with_options(
+        points: &[OPoint<T, DimNameDiff<D, U1>>],
+        degree: usize,
+        control_points_count: usize,
+        options: CurveApproximationOptions<T>,
+    ) -> anyhow::Result<Self>
+    where
+        D: DimNameSub<U1>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+    {
+        let n = control_points_count;
+        let m = points.len();
+        anyhow::ensure!(
+            degree >= 1 && n > degree,
+            "The number of control points must be greater than the degree"
+        );
+        anyhow::ensure!(
+            m >= n,
+            "The number of points must not be less than the number of control points"
+        );
+        anyhow::ensure!(
+            options.smoothing >= T::zero(),
+            "The smoothing must not be negative"
+        );
+
+        let vectors = points
+            .iter()
+            .map(|p| DVector::from_vec(p.iter().copied().collect()))
+            .collect::<Vec<_>>();
+        let parameters = options.knot_style.parameters(&vectors);
+
+        // the knots averaging the parameters over the spans (The NURBS Book, eq. 9.69)
+        let d = T::from_usize(m).unwrap() / T::from_usize(n - degree).unwrap();
+        let mut knots = vec![T::zero(); degree + 1];
+        for j in 1..(n - degree) {
+            let jd = T::from_usize(j).unwrap() * d;
+            let i = jd.floor().to_usize().unwrap();
+            let alpha = jd - T::from_usize(i).unwrap();
+            knots.push(parameters[i - 1] * (T::one() - alpha) + parameters[i] * alpha);
+        }
+        knots.extend(vec![T::one(); degree + 1]);
+        let knots = KnotVector::new(knots);
+
+        Self::try_least_squares(&vectors, &parameters, degree, knots, options.smoothing)
+    }
+
+    /// Fit the non-rational curve with the knot vector to the points at the parameters in the least squares sense
+    /// The end control points are fixed at the end points, and the second differences of the others are penalized by the smoothing.
+    fn try_least_squares(
+        vectors: &[DVector<T>],
+        parameters: &[T],
+        degree: usize,
+        knots: KnotVector<T>,
+        smoothing: T,
+    ) -> anyhow::Result<Self>
+    where
+        D: DimNameSub<U1>,
+    {
+        let m = vectors.len();
+        let n = knots.len() - degree - 1;
+        // the basis functions at the parameters & the second differences of the control points
+        let mut basis = DMatrix::<T>::zeros(m, n);
+        for (k, u) in parameters.iter().enumerate() {
+            let span = knots.find_knot_span_index(n - 1, degree, *u);
+            for (i, b) in knots
+                .basis_functions(span, *u, degree)
+                .into_iter()
+                .enumerate()
+            {
+                basis[(k, span - degree + i)] = b;
+            }
+        }
+        let mut differences = DMatrix::<T>::zeros(n.saturating_sub(2), n);
+        for i in 0..n.saturating_sub(2) {
+            differences[(i, i)] = T::one();
+            differences[(i, i + 1)] = -T::from_f64(2.).unwrap();
+            differences[(i, i + 2)] = T::one();
+        }
+
+        // the end control points are fixed at the end points, and the others solve the normal equations
+        let dim = D::dim() - 1;
+        let mut control_points = vec![DVector::<T>::zeros(dim); n];
+        control_points[0] = vectors[0].clone();
+        control_points[n - 1] = vectors[m - 1].clone();
+        if n > 2 {
+            let free = n - 2;
+            let a = basis.columns(1, free);
+            let s = differences.columns(1, free);
+            let lhs = a.transpose() * a + s.transpose() * s * smoothing;
+            let lu = lhs.lu();
+            for c in 0..dim {
+                let fixed = DVector::from_vec(vec![vectors[0][c], vectors[m - 1][c]]);
+                let corner =
+                    |mat: &DMatrix<T>| mat.column(0) * fixed[0] + mat.column(n - 1) * fixed[1];
+                let q = DVector::from_iterator(m, vectors.iter().map(|v| v[c]));
+                let rhs = a.transpose() * (q - corner(&basis))
+                    - s.transpose() * corner(&differences) * smoothing;
+                let x = lu
+                    .solve::<Dyn, U1, _>(&rhs)
+                    .ok_or(anyhow::anyhow!("Solve failed"))?;
+                for i in 0..free {
+                    control_points[i + 1][c] = x[i];
+                }
+            }
+        }
+
+        Ok(Self {
+            degree,
+            control_points: control_points
+                .iter()
+                .map(|v| {
+                    let mut coords = v.iter().copied().collect::<Vec<_>>();
+                    coords.push(T::one());
+                    OPoint::from_slice(&coords)
+                })
+                .collect(),
+            knots,
+        })
+    }
+
+    /// Try to fit a curve to the points by minimizing the distances from the points to the curve
+    /// Both the control points & the parameters of the points are optimized by the Levenberg-Marquardt method,
+    /// starting from the least squares approximation with the parameters projected onto it, while the knots are kept.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::Point2;
+    ///
+    /// // the points on the quarter circle sampled unevenly
+    /// let points = (0..=30)
+    ///     .map(|i| {
+    ///         let t = (i as f64 / 30.).powi(2) * std::f64::consts::FRAC_PI_2;
+    ///         Point2::new(t.cos(), t.sin())
+    ///     })
+    ///     .collect::<Vec<_>>();
+    /// let error = |c: &NurbsCurve2D<f64>| {
+    ///     points
+    ///         .iter()
+    ///         .map(|p| (c.find_closest_point(p).unwrap() - p).norm())
+    ///         .fold(0., f64::max)
+    /// };
+    /// let approximated = NurbsCurve2D::try_approximate(&points, 3, 5).unwrap();
+    /// let fitted = NurbsCurve2D::try_fit(&points, 3, 5).unwrap();
+    /// assert!(error(&fitted) < error(&approximated) * 0.5);
+    /// assert!(error(&fitted) < 1e-4);
+    /// ```
+    pub fn try_fit(
+        points: &[OPoint<T, DimNameDiff<D, U1>>],
+        degree: usize,
+        control_points_count: usize,
+    ) -> anyhow::Result<Self>
+    where
+        D: DimNameSub<U1>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+        T: ArgminFloat,
+    {
+        Self::try_fit_with_options(points, degree, control_points_count, Default::default())
+    }
+
+    /// Try to fit a curve to the points with the options of the initial parameterization & the solver
+    pub fn try_fit_with_options(
+        points: &[OPoint<T, DimNameDiff<D, U1>>],
+        degree: usize,
+        control_points_count: usize,
+        options: CurveFitOptions<T>,
+    ) -> anyhow::Result<Self>
+    where
+        D: DimNameSub<U1>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+        T: ArgminFloat,
+    {
+        let initial = Self::try_approximate_with_options(
+            points,
+            degree,
+            control_points_count,
+            CurveApproximationOptions::default().with_knot_style(options.knot_style),
+        )?;
+        let parameters = points
+            .iter()
+            .map(|p| initial.find_closest_parameter(p))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let problem = CurveFitProblem::new(points, degree, initial.knots.clone());
+        let param = problem.param(&initial, &parameters);
+        let solver = LevenbergMarquardt::new().with_cost_tolerance(options.tolerance);
+        let res = Executor::new(problem, solver)
+            .configure(|state| state.param(param).max_iters(options.max_iters))
+            .run()?;
+        let best = res
+            .state()
+            .get_best_param()
+            .cloned()
+            .ok_or(anyhow::anyhow!("No best parameter found"))?;
+        CurveFitProblem::new(points, degree, initial.knots.clone()).curve(&best)
+    }
+
     /// Try to create an periodic interpolated NURBS curve from a set of points
+    /// The curve is C^(degree - 1) continuous at the seam, where the last `degree` control points repeat the first ones.
+    /// The knots are placed at the parameters of the points for an odd degree, so the seam is at the first point,
+    /// and midway between the parameters for an even degree, so the seam is between the last & the first points.
     /// # Example
     /// ```
     /// use curvo::prelude::*;
@@ -866,6 +1357,8 @@ where
     /// assert_eq!(end, std::f64::consts::FRAC_PI_2);
     /// assert_relative_eq!(ellipse_arc.point_at(start), Point2::new(2., 0.), epsilon = 1e-10);
     /// assert_relative_eq!(ellipse_arc.point_at(end), Point2::new(0., 1.), epsilon = 1e-10);
+    /// let p = ellipse_arc.point_at(0.4);
+    /// assert_relative_eq!((p.x / 2.).powi(2) + p.y.powi(2), 1., epsilon = 1e-10);
     /// ```
     pub fn try_ellipse_arc(
         center: &OPoint<T, DimNameDiff<D, U1>>,
@@ -900,7 +1393,9 @@ where
         let mut p0 = center
             + &x_axis * x_radius * start_angle.cos()
             + &y_axis * y_radius * start_angle.sin();
-        let mut t0 = &y_axis * start_angle.cos() - &x_axis * start_angle.sin();
+        // the tangents are scaled by the radii to stay tangent to the ellipse
+        let mut t0 =
+            &y_axis * (y_radius * start_angle.cos()) - &x_axis * (x_radius * start_angle.sin());
 
         let n = 2 * arcs + 1;
         let degree = 2;
@@ -915,14 +1410,14 @@ where
 
             let p2 = center + &x_axis * x_radius * angle.cos() + &y_axis * y_radius * angle.sin();
 
-            let t2 = &y_axis * angle.cos() - &x_axis * angle.sin();
+            let t2 = &y_axis * (y_radius * angle.cos()) - &x_axis * (x_radius * angle.sin());
             let ray = Ray::new(p0.clone(), t0.normalize());
             let other = Ray::new(p2.clone(), t2.normalize());
             let intersection = ray
                 .find_intersection(&other)
                 .ok_or(anyhow::anyhow!("No intersection"))?;
 
-            let p1 = &p0 + &t0 * intersection.intersection0.1;
+            let p1 = intersection.intersection0.0;
 
             control_points[index + 2] = p2.clone();
             weights[index + 2] = T::one();
@@ -993,14 +1488,243 @@ where
             knots: KnotVector::new(knots),
         })
     }
-
-    /// Elevate the dimension of the curve (e.g., 2D -> 3D)
+    /// Try to create a parabola arc curve
+    /// The point at the parameter `t` is `vertex + x_axis * t^2 / (4 * focal_length) + y_axis * t`, so the parabola opens along the x axis with the focus at the focal length from the vertex.
+    /// The arc is a single quadratic bezier segment parameterized by `t` from the start to the end.
     /// # Example
     /// ```
     /// use curvo::prelude::*;
-    /// use nalgebra::Point2;
-    /// let points: Vec<Point2<f64>> = vec![
-    ///     Point2::new(-1.0, -1.0),
+    /// use nalgebra::{Point2, Vector2};
+    /// use approx::assert_relative_eq;
+    /// let parabola = NurbsCurve2D::try_parabola_arc(&Point2::origin(), &Vector2::x(), &Vector2::y(), 0.25, -1., 2.).unwrap();
+    /// assert_eq!(parabola.knots_domain(), (-1., 2.));
+    /// for i in 0..=6 {
+    ///     let t = -1. + i as f64 / 2.;
+    ///     assert_relative_eq!(parabola.point_at(t), Point2::new(t * t, t), epsilon = 1e-10);
+    /// }
+    /// ```
+    pub fn try_parabola_arc(
+        vertex: &OPoint<T, DimNameDiff<D, U1>>,
+        x_axis: &OVector<T, DimNameDiff<D, U1>>,
+        y_axis: &OVector<T, DimNameDiff<D, U1>>,
+        focal_length: T,
+        start: T,
+        end: T,
+    ) -> anyhow::Result<Self>
+    where
+        D: DimNameSub<U1>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+    {
+        anyhow::ensure!(
+            focal_length > T::zero(),
+            "The focal length must be positive"
+        );
+        anyhow::ensure!(
+            end > start,
+            "`end` must be greater than `start`. {} <= {}",
+            end,
+            start
+        );
+        let x_axis = x_axis.normalize();
+        let y_axis = y_axis.normalize();
+        let four = T::from_f64(4.).unwrap();
+        let point = |t: T| vertex + &x_axis * (t * t / (four * focal_length)) + &y_axis * t;
+
+        // the middle control point is the intersection of the tangents at the ends
+        let tangent =
+            &x_axis * (start / (four * focal_length) * T::from_f64(2.).unwrap()) + &y_axis;
+        let p0 = point(start);
+        let p1 = &p0 + tangent * ((end - start) * T::from_f64(0.5).unwrap());
+        let p2 = point(end);
+
+        Ok(Self {
+            degree: 2,
+            control_points: [p0, p1, p2]
+                .iter()
+                .map(|p| {
+                    let mut coords = p.coords.iter().copied().collect::<Vec<_>>();
+                    coords.push(T::one());
+                    OPoint::from_slice(&coords)
+                })
+                .collect(),
+            knots: KnotVector::new(vec![start, start, start, end, end, end]),
+        })
+    }
+
+    /// Try to create an arc on the branch of a hyperbola
+    /// The point at the parameter `t` on the hyperbola is `center + x_axis * cosh(t) + y_axis * sinh(t)`, where the lengths of the axes are the semi-axes.
+    /// The arc is a single rational quadratic bezier segment from the start to the end, whose middle weight is `cosh((end - start) / 2)`.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point2, Vector2};
+    /// use approx::assert_relative_eq;
+    /// let hyperbola = NurbsCurve2D::try_hyperbola_arc(&Point2::origin(), &(Vector2::x() * 2.), &Vector2::y(), -1., 1.5).unwrap();
+    /// let (start, end) = hyperbola.knots_domain();
+    /// assert_relative_eq!(hyperbola.point_at(start), Point2::new(2. * 1f64.cosh(), -1f64.sinh()), epsilon = 1e-10);
+    /// assert_relative_eq!(hyperbola.point_at(end), Point2::new(2. * 1.5f64.cosh(), 1.5f64.sinh()), epsilon = 1e-10);
+    /// for i in 0..=8 {
+    ///     let p = hyperbola.point_at(start + (end - start) * i as f64 / 8.);
+    ///     assert_relative_eq!((p.x / 2.).powi(2) - p.y.powi(2), 1., epsilon = 1e-10);
+    /// }
+    /// ```
+    pub fn try_hyperbola_arc(
+        center: &OPoint<T, DimNameDiff<D, U1>>,
+        x_axis: &OVector<T, DimNameDiff<D, U1>>,
+        y_axis: &OVector<T, DimNameDiff<D, U1>>,
+        start: T,
+        end: T,
+    ) -> anyhow::Result<Self>
+    where
+        D: DimNameSub<U1>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+    {
+        anyhow::ensure!(
+            end > start,
+            "`end` must be greater than `start`. {} <= {}",
+            end,
+            start
+        );
+        let point = |t: T, scale: T| center + (x_axis * t.cosh() + y_axis * t.sinh()) * scale;
+        let half = (end - start) * T::from_f64(0.5).unwrap();
+        let weight = half.cosh();
+
+        // the intersection of the tangents at the ends lies on the hyperbola scaled by the inverse of the weight
+        let p0 = point(start, T::one());
+        let p1 = point(start + half, weight.recip());
+        let p2 = point(end, T::one());
+        let homogenize = |p: OPoint<T, DimNameDiff<D, U1>>, w: T| {
+            let mut coords = p.coords.iter().map(|c| *c * w).collect::<Vec<_>>();
+            coords.push(w);
+            OPoint::from_slice(&coords)
+        };
+
+        Ok(Self {
+            degree: 2,
+            control_points: vec![
+                homogenize(p0, T::one()),
+                homogenize(p1, weight),
+                homogenize(p2, T::one()),
+            ],
+            knots: KnotVector::new(vec![start, start, start, end, end, end]),
+        })
+    }
+
+    /// Try to create an Archimedean spiral whose radius grows linearly with the angle
+    /// The point at the angle `t` is `center + (x_axis * cos(t) + y_axis * sin(t)) * (start_radius + growth * t / 2pi)`, so the radius grows by `growth` per turn from the start radius at the x axis.
+    /// The spiral is approximated by a C1 cubic spline within 1e-6 times the end radius, parameterized by the angle.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point2, Vector2};
+    /// use approx::assert_relative_eq;
+    /// use std::f64::consts::TAU;
+    /// let spiral = NurbsCurve2D::try_archimedean_spiral(&Point2::origin(), &Vector2::x(), &Vector2::y(), 1., 0.5, 3.).unwrap();
+    /// assert_eq!(spiral.knots_domain(), (0., 3. * TAU));
+    /// for i in 0..=30 {
+    ///     let t = 3. * TAU * i as f64 / 30.;
+    ///     let r = 1. + 0.5 * t / TAU;
+    ///     assert_relative_eq!(spiral.point_at(t), Point2::new(r * t.cos(), r * t.sin()), epsilon = 1e-5);
+    /// }
+    /// ```
+    pub fn try_archimedean_spiral(
+        center: &OPoint<T, DimNameDiff<D, U1>>,
+        x_axis: &OVector<T, DimNameDiff<D, U1>>,
+        y_axis: &OVector<T, DimNameDiff<D, U1>>,
+        start_radius: T,
+        growth: T,
+        turns: T,
+    ) -> anyhow::Result<Self>
+    where
+        D: DimNameSub<U1>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+    {
+        anyhow::ensure!(turns > T::zero(), "The number of turns must be positive");
+        let end = turns * T::two_pi();
+        let rate = growth / T::two_pi();
+        let end_radius = start_radius + rate * end;
+        anyhow::ensure!(
+            start_radius >= T::zero() && end_radius >= T::zero(),
+            "The radius must not be negative"
+        );
+        let (x_axis, y_axis) = (x_axis.normalize(), y_axis.normalize());
+        // the fourth derivative of the spiral is bounded by the radius & four times the growth rate
+        let bound = end_radius.max(start_radius) + rate.abs() * T::from_f64(4.).unwrap();
+        try_hermite_spline(
+            |t| {
+                let (sin, cos) = t.sin_cos();
+                let r = start_radius + rate * t;
+                let radial = &x_axis * cos + &y_axis * sin;
+                let tangential = &y_axis * cos - &x_axis * sin;
+                (center + &radial * r, radial * rate + tangential * r)
+            },
+            T::zero(),
+            end,
+            bound,
+            bound * T::from_f64(1e-6).unwrap(),
+        )
+    }
+
+    /// Try to create a logarithmic spiral whose radius grows exponentially with the angle
+    /// The point at the angle `t` is `center + (x_axis * cos(t) + y_axis * sin(t)) * start_radius * exp(rate * t)`.
+    /// The spiral is approximated by a C1 cubic spline within 1e-6 times the largest radius, parameterized by the angle.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point2, Vector2};
+    /// use approx::assert_relative_eq;
+    /// use std::f64::consts::TAU;
+    /// let spiral = NurbsCurve2D::try_logarithmic_spiral(&Point2::origin(), &Vector2::x(), &Vector2::y(), 1., 0.2, 2.).unwrap();
+    /// for i in 0..=20 {
+    ///     let t = 2. * TAU * i as f64 / 20.;
+    ///     let r = (0.2 * t).exp();
+    ///     assert_relative_eq!(spiral.point_at(t), Point2::new(r * t.cos(), r * t.sin()), epsilon = 1e-4);
+    /// }
+    /// ```
+    pub fn try_logarithmic_spiral(
+        center: &OPoint<T, DimNameDiff<D, U1>>,
+        x_axis: &OVector<T, DimNameDiff<D, U1>>,
+        y_axis: &OVector<T, DimNameDiff<D, U1>>,
+        start_radius: T,
+        rate: T,
+        turns: T,
+    ) -> anyhow::Result<Self>
+    where
+        D: DimNameSub<U1>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+    {
+        anyhow::ensure!(turns > T::zero(), "The number of turns must be positive");
+        anyhow::ensure!(
+            start_radius > T::zero(),
+            "The start radius must be positive"
+        );
+        let end = turns * T::two_pi();
+        let (x_axis, y_axis) = (x_axis.normalize(), y_axis.normalize());
+        let max_radius = start_radius * (rate * end).exp().max(T::one());
+        // the fourth derivative of the spiral is the radius scaled by (1 + rate^2)^2
+        let bound = max_radius * (T::one() + rate * rate).powi(2);
+        try_hermite_spline(
+            |t| {
+                let (sin, cos) = t.sin_cos();
+                let r = start_radius * (rate * t).exp();
+                let radial = &x_axis * cos + &y_axis * sin;
+                let tangential = &y_axis * cos - &x_axis * sin;
+                (center + &radial * r, (radial * rate + tangential) * r)
+            },
+            T::zero(),
+            end,
+            bound,
+            max_radius * T::from_f64(1e-6).unwrap(),
+        )
+    }
+
+    /// Elevate the dimension of the curve (e.g., 2D -> 3D)
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::Point2;
+    /// let points: Vec<Point2<f64>> = vec![
+    ///     Point2::new(-1.0, -1.0),
     ///     Point2::new(1.0, -1.0),
     ///     Point2::new(1.0, 1.0),
     ///     Point2::new(-1.0, 1.0),
@@ -1041,67 +1765,91 @@ where
     }
 
     /// Try to elevate the degree of the curve
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point2, Vector2};
+    ///
+    /// let line = NurbsCurve2D::<f64>::polyline(&[Point2::new(0., 0.), Point2::new(2., 1.)]);
+    /// let elevated = line.try_elevate_degree(3).unwrap();
+    /// assert_eq!(elevated.degree(), 3);
+    /// assert_eq!(elevated.control_points().len(), 4);
+    /// assert_eq!(elevated.knots().len(), 8);
+    /// assert!((elevated.point_at(0.25) - line.point_at(0.25)).norm() < 1e-10);
+    ///
+    /// // the elevated curve traces the same points
+    /// let same = |a: &NurbsCurve2D<f64>, b: &NurbsCurve2D<f64>| {
+    ///     let (start, end) = b.knots_domain();
+    ///     (0..=32).all(|i| {
+    ///         let t = start + (end - start) * i as f64 / 32.;
+    ///         (a.point_at(t) - b.point_at(t)).norm() < 1e-10
+    ///     })
+    /// };
+    ///
+    /// // the rational curve with the multiple interior knots is elevated by more than one degree
+    /// let circle = NurbsCurve2D::try_circle(&Point2::origin(), &Vector2::x(), &Vector2::y(), 1.).unwrap();
+    /// let elevated = circle.try_elevate_degree(4).unwrap();
+    /// assert_eq!(elevated.degree(), 4);
+    /// assert_eq!(elevated.knots().len(), elevated.control_points().len() + 5);
+    /// assert!(same(&elevated, &circle));
+    ///
+    /// // each simple interior knot gains the multiplicity to keep the continuity
+    /// let points = [(0., 0.), (1., 2.), (2., -1.), (3., 1.), (4., 0.)].map(|(x, y)| Point2::new(x, y));
+    /// let cubic = NurbsCurve2D::try_interpolate(&points, 3).unwrap();
+    /// let elevated = cubic.try_elevate_degree(5).unwrap();
+    /// assert_eq!(elevated.knots().len(), elevated.control_points().len() + 6);
+    /// assert_eq!(elevated.control_points().len(), cubic.control_points().len() + 2 * 2);
+    /// assert!(same(&elevated, &cubic));
+    /// ```
     pub fn try_elevate_degree(&self, target_degree: usize) -> anyhow::Result<Self> {
         if target_degree <= self.degree {
             return Ok(self.clone());
         }
 
-        let n = self.knots.len() - self.degree - 2;
-        let new_degree = self.degree;
+        // the algorithm A5.9 of The NURBS Book
+        let p = self.degree;
+        let t = target_degree - p;
+        let ph = target_degree;
+        let ph2 = ph / 2;
         let knots = &self.knots;
         let control_points = &self.control_points;
-        let degree_inc = target_degree - self.degree;
-
-        //intermediate values
-        let mut bezalfs = vec![vec![T::zero(); new_degree + 1]; new_degree + degree_inc + 1];
-        let new_control_point_count = control_points.len() + degree_inc + 3;
-        let mut bpts = vec![OPoint::origin(); new_control_point_count];
-        let mut e_bpts = vec![OPoint::origin(); new_control_point_count];
-        let mut next_bpts = vec![OPoint::origin(); new_control_point_count];
-
-        let m = n + new_degree + 1;
-        let ph = target_degree;
-        let ph2: usize = (T::from_usize(ph).unwrap() / T::from_f64(2.0).unwrap())
-            .floor()
-            .to_usize()
-            .unwrap();
-
-        let mut q_w = vec![OPoint::origin(); new_control_point_count];
-        let mut u_h = vec![T::zero(); q_w.len() + target_degree + 1];
+        let m = knots.len() - 1;
 
+        // the coefficients to elevate the degree of the bezier segments
+        let mut bezalfs = vec![vec![T::zero(); p + 1]; ph + 1];
         bezalfs[0][0] = T::one();
-        bezalfs[ph][new_degree] = T::one();
+        bezalfs[ph][p] = T::one();
 
         let mut binom = Binomial::new();
-
         for i in 1..=ph2 {
             let inv = T::one() / binom.get(ph, i);
-            let mpi = new_degree.min(i);
-            for j in 0.max(i - degree_inc)..=mpi {
-                bezalfs[i][j] = inv * binom.get(new_degree, j) * binom.get(degree_inc, i - j);
+            for j in i.saturating_sub(t)..=p.min(i) {
+                bezalfs[i][j] = inv * binom.get(p, j) * binom.get(t, i - j);
             }
         }
-
         for i in (ph2 + 1)..ph {
-            let mpi = new_degree.min(i);
-            for j in 0.max(i - degree_inc)..=mpi {
-                bezalfs[i][j] = bezalfs[ph - i][new_degree - j];
+            for j in i.saturating_sub(t)..=p.min(i) {
+                bezalfs[i][j] = bezalfs[ph - i][p - j];
             }
         }
 
-        let mh = ph;
+        // each bezier segment adds t control points at most
+        let capacity = control_points.len() + t * knots.len();
+        let mut q_w = vec![OPoint::origin(); capacity];
+        let mut u_h = vec![T::zero(); capacity + ph + 1];
+        let mut bpts = control_points[..=p].to_vec();
+        let mut e_bpts = vec![OPoint::origin(); ph + 1];
+        let mut next_bpts = vec![OPoint::origin(); p.max(1)];
+        let mut alfs = vec![T::zero(); p.max(1)];
+
         let mut kind = ph + 1;
         let mut r: isize = -1;
-        let mut a = new_degree;
-        let mut b = new_degree + 1;
+        let mut a = p;
+        let mut b = p + 1;
         let mut cind = 1;
         let mut ua = knots[0];
         q_w[0] = control_points[0].clone();
-        for i in 0..=ph {
-            u_h[i] = ua;
-        }
-
-        bpts[..(new_degree + 1)].clone_from_slice(&control_points[..(new_degree + 1)]);
+        u_h[..=ph].fill(ua);
 
         while b < m {
             let i = b;
@@ -1109,92 +1857,73 @@ where
                 b += 1;
             }
             let mul = b - i + 1;
-            let _mh = mh + mul + degree_inc;
             let ub = knots[b];
             let oldr = r;
-            r = new_degree as isize - mul as isize;
-            let lbz = if oldr > 0 {
-                (T::from_isize(oldr + 2).unwrap() / T::from_f64(2.).unwrap())
-                    .floor()
-                    .to_usize()
-                    .unwrap()
-            } else {
-                1
-            };
+            r = p as isize - mul as isize;
+            let lbz = if oldr > 0 { oldr as usize / 2 + 1 } else { 1 };
             let rbz = if r > 0 {
-                (T::from_usize(ph).unwrap()
-                    - T::from_isize(r + 1).unwrap() / T::from_f64(2.).unwrap())
-                .floor()
-                .to_usize()
-                .unwrap()
+                ph - (r as usize).div_ceil(2)
             } else {
                 ph
             };
+
+            // insert the knot ub r times to split off the bezier segment
             if r > 0 {
                 let numer = ub - ua;
-                let mut alfs = vec![T::zero(); new_degree];
-                let mut k = new_degree;
-                while k > mul {
+                for k in ((mul + 1)..=p).rev() {
                     alfs[k - mul - 1] = numer / (knots[a + k] - ua);
-                    k -= 1;
                 }
                 for j in 1..=(r as usize) {
-                    let save = (r as usize) - j;
+                    let save = r as usize - j;
                     let s = mul + j;
-                    let mut k = new_degree;
-                    while k >= s {
+                    for k in (s..=p).rev() {
                         bpts[k] = bpts[k].lerp(&bpts[k - 1], T::one() - alfs[k - s]);
-                        k -= 1;
                     }
-                    next_bpts[save] = bpts[new_degree].clone();
+                    next_bpts[save] = bpts[p].clone();
                 }
             }
 
-            for i in lbz..=ph {
-                e_bpts[i] = OPoint::origin();
-                let mpi = new_degree.min(i);
-                for j in 0.max(i - degree_inc)..=mpi {
-                    e_bpts[i].coords = &e_bpts[i].coords + &bpts[j].coords * bezalfs[i][j];
+            // elevate the degree of the bezier segment
+            for (i, e) in e_bpts.iter_mut().enumerate().skip(lbz) {
+                e.coords.fill(T::zero());
+                for j in i.saturating_sub(t)..=p.min(i) {
+                    e.coords += &bpts[j].coords * bezalfs[i][j];
                 }
             }
 
+            // remove the knot ua oldr times
             if oldr > 1 {
-                let mut first = kind - 2;
-                let mut last = kind;
                 let den = ub - ua;
                 let bet = (ub - u_h[kind - 1]) / den;
-                for tr in 1..oldr {
-                    let mut i = first;
-                    let mut j = last;
+                for tr in 1..(oldr as usize) {
+                    // the range of the knot removal widens by one on both sides in each pass
+                    let mut i = kind - 1 - tr;
+                    let mut j = kind + tr - 1;
                     let mut kj = j - kind + 1;
-                    let utr = tr as usize;
-                    while (j as isize - i as isize) > tr {
+                    while j - i > tr {
                         if i < cind {
                             let alf = (ub - u_h[i]) / (ua - u_h[i]);
                             q_w[i] = q_w[i].lerp(&q_w[i - 1], T::one() - alf);
                         }
                         if j >= lbz {
-                            if (j as isize) - tr <= (kind as isize - ph as isize + oldr) {
-                                let gam = (ub - u_h[j - utr]) / den;
-                                e_bpts[kj] = e_bpts[kj].lerp(&e_bpts[kj + 1], T::one() - gam);
-                            }
-                        } else {
-                            e_bpts[kj] = e_bpts[kj].lerp(&e_bpts[kj + 1], T::one() - bet);
+                            let frac = if j <= kind - ph + oldr as usize + tr {
+                                (ub - u_h[j - tr]) / den
+                            } else {
+                                bet
+                            };
+                            e_bpts[kj] = e_bpts[kj].lerp(&e_bpts[kj + 1], T::one() - frac);
                         }
                         i += 1;
                         j -= 1;
-                        kj -= 1;
+                        kj = kj.wrapping_sub(1);
                     }
-                    first -= 1;
-                    last += 1;
                 }
             }
 
-            if a != new_degree {
-                for _i in 0..(ph as isize - oldr) {
-                    u_h[kind] = ua;
-                    kind += 1;
-                }
+            if a != p {
+                let count = (ph as isize - oldr) as usize;
+                u_h[kind..(kind + count)].fill(ua);
+                kind += count;
             }
 
             for j in lbz..=rbz {
@@ -1203,21 +1932,20 @@ where
             }
 
             if b < m {
-                let ur = r as usize;
-                bpts[..ur].clone_from_slice(&next_bpts[..ur]);
-                for j in (r as usize)..=new_degree {
-                    bpts[j] = control_points[b - new_degree + j].clone();
-                }
+                let r = r.max(0) as usize;
+                bpts[..r].clone_from_slice(&next_bpts[..r]);
+                bpts[r..=p].clone_from_slice(&control_points[(b - p + r)..=b]);
                 a = b;
                 b += 1;
                 ua = ub;
             } else {
-                for i in 0..=ph {
-                    u_h[kind + i] = ub;
-                }
+                u_h[kind..=(kind + ph)].fill(ub);
             }
         }
 
+        q_w.truncate(cind);
+        u_h.truncate(kind + ph + 1);
+
         Ok(Self {
             degree: target_degree,
             control_points: q_w,
@@ -1225,6 +1953,221 @@ where
         })
     }
 
+    /// Try to reduce the degree of the curve by one, refusing if the deviation exceeds the tolerance
+    /// The reduced curve is fitted to the curve in the homogeneous space by the least squares with the end points fixed,
+    /// where each multiplicity of the interior knots is decreased by one to keep the continuity at the knots.
+    /// Returns the reduced curve & the maximum deviation between the points of both curves at the same parameters.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::Point2;
+    ///
+    /// let points = vec![Point2::new(0., 0.), Point2::new(1., 2.), Point2::new(3., -1.), Point2::new(4., 1.)];
+    /// let quadratic = NurbsCurve2D::try_interpolate(&points, 2).unwrap();
+    ///
+    /// // the degree elevated curve is reduced exactly
+    /// let elevated = quadratic.try_elevate_degree(3).unwrap();
+    /// let (reduced, deviation) = elevated.try_reduce_degree(1e-8).unwrap();
+    /// assert_eq!(reduced.degree(), 2);
+    /// assert!(deviation < 1e-8);
+    ///
+    /// // the curve is not reduced if the deviation exceeds the tolerance
+    /// let cubic = NurbsCurve2D::try_interpolate(&points, 3).unwrap();
+    /// assert!(cubic.try_reduce_degree(1e-3).is_err());
+    /// let (_, deviation) = cubic.try_reduce_degree(f64::INFINITY).unwrap();
+    /// assert!(deviation > 1e-3);
+    /// ```
+    pub fn try_reduce_degree(&self, tolerance: T) -> anyhow::Result<(Self, T)>
+    where
+        D: DimNameSub<U1>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+    {
+        let reduced = self.try_reduce_degree_homogeneous()?;
+        let deviation = self.max_deviation(&reduced);
+        anyhow::ensure!(
+            deviation <= tolerance,
+            "The deviation {} of the degree reduction exceeds the tolerance {}",
+            deviation,
+            tolerance
+        );
+        Ok((reduced, deviation))
+    }
+
+    /// Try to rebuild the curve with the degree & the number of the control points on the uniform knot vector
+    /// The non-rational curve is fitted to the points sampled uniformly in the domain, which is kept along with the end points.
+    /// Returns the rebuilt curve & the maximum deviation between the points of both curves at the same parameters.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point2, Vector2};
+    ///
+    /// let circle = NurbsCurve2D::try_circle(&Point2::origin(), &Vector2::x(), &Vector2::y(), 1.).unwrap();
+    /// let arc = circle.try_trim(std::f64::consts::PI).unwrap().0;
+    /// let (rebuilt, deviation) = arc.try_rebuild(3, 8).unwrap();
+    /// assert_eq!(rebuilt.degree(), 3);
+    /// assert_eq!(rebuilt.control_points().len(), 8);
+    /// assert!(rebuilt.weights().iter().all(|w| *w == 1.));
+    /// assert_eq!(rebuilt.knots_domain(), arc.knots_domain());
+    /// assert!(deviation < 1e-2);
+    /// ```
+    pub fn try_rebuild(
+        &self,
+        degree: usize,
+        control_points_count: usize,
+    ) -> anyhow::Result<(Self, T)>
+    where
+        D: DimNameSub<U1>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+    {
+        let n = control_points_count;
+        anyhow::ensure!(
+            degree >= 1 && n > degree,
+            "The number of control points must be greater than the degree"
+        );
+        let (start, end) = self.knots_domain();
+        let interval = end - start;
+        let spans = n - degree;
+        let knots = [
+            vec![start; degree + 1],
+            (1..spans)
+                .map(|i| {
+                    start + interval * T::from_usize(i).unwrap() / T::from_usize(spans).unwrap()
+                })
+                .collect(),
+            vec![end; degree + 1],
+        ]
+        .concat();
+
+        // the samples dense enough for each span of both curves
+        let count = spans.max(self.knots.multiplicity().len() - 1) * (2 * degree + 2);
+        let parameters = (0..=count)
+            .map(|i| start + interval * T::from_usize(i).unwrap() / T::from_usize(count).unwrap())
+            .collect::<Vec<_>>();
+        let vectors = parameters
+            .iter()
+            .map(|t| DVector::from_vec(self.point_at(*t).iter().copied().collect()))
+            .collect::<Vec<_>>();
+        let rebuilt = Self::try_least_squares(
+            &vectors,
+            &parameters,
+            degree,
+            KnotVector::new(knots),
+            T::zero(),
+        )?;
+        let deviation = self
+            .max_deviation(&rebuilt)
+            .max(rebuilt.max_deviation(self));
+        Ok((rebuilt, deviation))
+    }
+
+    /// Fit the curve of the degree lower by one to the curve in the homogeneous space
+    pub(crate) fn try_reduce_degree_homogeneous(&self) -> anyhow::Result<Self> {
+        let p = self.degree;
+        anyhow::ensure!(p > 1, "The degree must be greater than one to reduce");
+        anyhow::ensure!(
+            self.knots.is_clamped(p),
+            "The knot vector must be clamped to reduce the degree"
+        );
+
+        let mult = self.knots.multiplicity();
+        let last = mult.len() - 1;
+        let knots = mult
+            .iter()
+            .enumerate()
+            .flat_map(|(i, m)| {
+                let count = if i == 0 || i == last {
+                    p
+                } else {
+                    (m.multiplicity() - 1).max(1)
+                };
+                vec![*m.knot(); count]
+            })
+            .collect::<Vec<_>>();
+        let knots = KnotVector::new(knots);
+        let degree = p - 1;
+        let n = knots.len() - degree - 1;
+
+        // the samples in each span, which are enough to determine the control points
+        let samples = 2 * p + 2;
+        let parameters = mult
+            .windows(2)
+            .flat_map(|w| {
+                let (a, b) = (*w[0].knot(), *w[1].knot());
+                (0..samples).map(move |i| {
+                    a + (b - a) * T::from_usize(i).unwrap() / T::from_usize(samples).unwrap()
+                })
+            })
+            .chain(std::iter::once(*mult[last].knot()))
+            .collect::<Vec<_>>();
+        let m = parameters.len();
+
+        let mut basis = DMatrix::<T>::zeros(m, n);
+        for (k, u) in parameters.iter().enumerate() {
+            let span = knots.find_knot_span_index(n - 1, degree, *u);
+            for (i, b) in knots
+                .basis_functions(span, *u, degree)
+                .into_iter()
+                .enumerate()
+            {
+                basis[(k, span - degree + i)] = b;
+            }
+        }
+        let targets = parameters
+            .iter()
+            .map(|u| self.point(*u))
+            .collect::<Vec<_>>();
+
+        // the end control points are fixed at the end points, and the others solve the normal equations
+        let first = self.control_points[0].clone();
+        let end = self.control_points[self.control_points.len() - 1].clone();
+        let mut control_points = vec![first.clone(); n];
+        control_points[n - 1] = end.clone();
+        if n > 2 {
+            let free = n - 2;
+            let a = basis.columns(1, free);
+            let normal: DMatrix<T> = a.transpose() * a;
+            let lu = normal.lu();
+            for c in 0..D::dim() {
+                let q = DVector::<T>::from_iterator(
+                    m,
+                    targets
+                        .iter()
+                        .enumerate()
+                        .map(|(k, t)| t[c] - basis[(k, 0)] * first[c] - basis[(k, n - 1)] * end[c]),
+                );
+                let rhs: DVector<T> = a.transpose() * q;
+                let x = lu
+                    .solve::<Dyn, U1, _>(&rhs)
+                    .ok_or(anyhow::anyhow!("Solve failed"))?;
+                for i in 0..free {
+                    control_points[i + 1][c] = x[i];
+                }
+            }
+        }
+
+        Self::try_new(degree, control_points, knots.to_vec())
+    }
+
+    /// The maximum distance between the points of the curves at the same parameters sampled in each span
+    fn max_deviation(&self, other: &Self) -> T
+    where
+        D: DimNameSub<U1>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+    {
+        let mult = self.knots.multiplicity();
+        let samples = 16;
+        mult.windows(2)
+            .flat_map(|w| {
+                let (a, b) = (*w[0].knot(), *w[1].knot());
+                (0..=samples).map(move |i| {
+                    a + (b - a) * T::from_usize(i).unwrap() / T::from_usize(samples).unwrap()
+                })
+            })
+            .fold(T::zero(), |acc, t| {
+                acc.max((self.point_at(t) - other.point_at(t)).norm())
+            })
+    }
+
     /// Try to add a knot to the curve
     pub fn try_add_knot(&mut self, knot: T) -> anyhow::Result<()> {
         anyhow::ensure!(
@@ -1283,6 +2226,82 @@ where
         self.knots.is_clamped(self.degree)
     }
 
+    /// Check if the curve is periodic, where the last control points repeat the first ones and the knot intervals repeat around the seam
+    /// The periodic curve of degree p is C^(p-1) continuous at the seam for the simple knots.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::Point2;
+    ///
+    /// let points = vec![Point2::new(0., 0.), Point2::new(1., 0.), Point2::new(1., 1.), Point2::new(0., 1.)];
+    /// let periodic = NurbsCurve2D::try_periodic_interpolate(&points, 3, KnotStyle::Chordal).unwrap();
+    /// assert!(periodic.is_periodic());
+    /// let clamped = NurbsCurve2D::try_interpolate(&[points.clone(), vec![points[0]]].concat(), 3).unwrap();
+    /// assert!(!clamped.is_periodic());
+    /// ```
+    pub fn is_periodic(&self) -> bool {
+        let p = self.degree;
+        let n = self.control_points.len();
+        if n <= p {
+            return false;
+        }
+        let eps = T::default_epsilon() * T::from_usize(64).unwrap();
+        let repeated = (0..p).all(|i| {
+            (&self.control_points[i].coords - &self.control_points[n - p + i].coords).norm() < eps
+        });
+        // the intervals of the knots around the start of the domain repeat around the end
+        let knots = &self.knots;
+        let period = knots[n] - knots[p];
+        let scale = period.abs().max(T::one());
+        let intervals = (0..(2 * p)).all(|j| {
+            let a = knots[j + 1] - knots[j];
+            let b = knots[n - p + j + 1] - knots[n - p + j];
+            (a - b).abs() < eps * scale
+        });
+        repeated && intervals
+    }
+
+    /// Try to make the curves compatible for skinning, so that they share the degree, the domain & the knot vector
+    /// The curves are elevated to the highest degree, their domains are shifted to start at zero & scaled to the longest one,
+    /// and the knot vectors are merged by the knot insertion, so the shapes of the curves are kept.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let line = NurbsCurve3D::polyline(&[Point3::new(0., 0., 0.), Point3::new(1., 0., 0.), Point3::new(1., 1., 0.)]);
+    /// let interpolated = NurbsCurve3D::try_interpolate(
+    ///     &[Point3::new(0., 0., 1.), Point3::new(1., 0.5, 1.), Point3::new(2., 0., 1.), Point3::new(3., 1., 1.)],
+    ///     3,
+    /// ).unwrap();
+    /// let circle = NurbsCurve3D::try_circle(&Point3::origin(), &Vector3::x(), &Vector3::y(), 1.).unwrap();
+    /// let originals = vec![line, interpolated, circle];
+    ///
+    /// let mut curves = originals.clone();
+    /// NurbsCurve3D::try_make_compatible(&mut curves).unwrap();
+    /// assert!(curves.iter().all(|c| c.degree() == 3));
+    /// assert!(curves.iter().all(|c| c.knots().as_slice() == curves[0].knots().as_slice()));
+    ///
+    /// for (original, compatible) in originals.iter().zip(curves.iter()) {
+    ///     let (s0, e0) = original.knots_domain();
+    ///     let (s1, e1) = compatible.knots_domain();
+    ///     for i in 0..=8 {
+    ///         let t = i as f64 / 8.;
+    ///         assert_relative_eq!(original.point_at(s0 + (e0 - s0) * t), compatible.point_at(s1 + (e1 - s1) * t), epsilon = 1e-8);
+    ///     }
+    /// }
+    /// ```
+    pub fn try_make_compatible(curves: &mut [Self]) -> anyhow::Result<()> {
+        anyhow::ensure!(!curves.is_empty(), "No curves to make compatible");
+        let unified = try_unify_curve_knot_vectors(curves)?;
+        curves
+            .iter_mut()
+            .zip(unified)
+            .for_each(|(curve, unified)| *curve = unified);
+        Ok(())
+    }
+
     /// Try to refine the curve by inserting knots
     pub fn try_refine_knot(&mut self, knots_to_insert: Vec<T>) -> anyhow::Result<()> {
         anyhow::ensure!(self.is_clamped(), "Curve must be clamped to refine knots");
@@ -1359,64 +2378,783 @@ where
         Ok(())
     }
 
-    /// Find the closest point on the curve to a given point
-    pub fn find_closest_point(
+    /// Try to remove the knot up to the given times while the curve is kept within the tolerance (The NURBS Book A5.8)
+    /// Returns the curve & the number of the removed times, which is zero if the knot is not removable.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::Point2;
+    ///
+    /// let points = vec![Point2::new(0., 0.), Point2::new(1., 2.), Point2::new(3., -1.), Point2::new(4., 1.)];
+    /// let curve = NurbsCurve2D::try_interpolate(&points, 3).unwrap();
+    /// let mut refined = curve.clone();
+    /// refined.try_refine_knot(vec![0.5, 0.5]).unwrap();
+    /// let (removed, times) = refined.try_remove_knot(0.5, 2, 1e-10).unwrap();
+    /// assert_eq!(times, 2);
+    /// assert_eq!(removed.control_points().len(), curve.control_points().len());
+    /// assert!((removed.point_at(0.3) - curve.point_at(0.3)).norm() < 1e-10);
+    /// ```
+    pub fn try_remove_knot(
         &self,
-        point: &OPoint<T, DimNameDiff<D, U1>>,
-    ) -> anyhow::Result<OPoint<T, DimNameDiff<D, U1>>>
+        knot: T,
+        times: usize,
+        tolerance: T,
+    ) -> anyhow::Result<(Self, usize)>
     where
         D: DimNameSub<U1>,
         DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
-        T: ArgminFloat,
     {
-        self.find_closest_parameter(point).map(|u| self.point_at(u))
-    }
+        let p = self.degree;
+        let (start, end) = self.knots_domain();
+        anyhow::ensure!(
+            knot > start && knot < end,
+            "The knot must be inside the domain of the curve"
+        );
+        let knots = self.knots.as_slice();
+        let Some(r) = knots
+            .iter()
+            .rposition(|k| (*k - knot).abs() <= T::default_epsilon())
+        else {
+            return Ok((self.clone(), 0));
+        };
+        let s = knots
+            .iter()
+            .filter(|k| (**k - knot).abs() <= T::default_epsilon())
+            .count();
+        let u = knots[r];
 
-    /// Find the closest parameter on the curve to a given point with Newton's method
+        // the tolerance in the homogeneous space bounding the deviation of the rational curve (The NURBS Book eq. 5.30)
+        let weights = self.weights();
+        let rational = weights
+            .iter()
+            .any(|w| (*w - T::one()).abs() > T::default_epsilon());
+        let tol = if rational {
+            let w_min = weights.iter().fold(weights[0], |acc, w| acc.min(*w));
+            let p_max = self
+                .dehomogenized_control_points()
+                .iter()
+                .fold(T::zero(), |acc, p| acc.max(p.coords.norm()));
+            tolerance * w_min / (T::one() + p_max)
+        } else {
+            tolerance
+        };
+
+        let mut pw = self.control_points.clone();
+        let n = pw.len() as isize - 1;
+        let ord = p + 1;
+        let fout = (2 * r - s - p) / 2;
+        let (mut first, mut last) = (r as isize - p as isize, (r - s) as isize);
+        let mut temp = vec![OPoint::<T, D>::origin(); 2 * p + 1];
+        let lerp = |a: &OPoint<T, D>, b: &OPoint<T, D>, t: T| {
+            OPoint::from(a.coords.clone() * (T::one() - t) + b.coords.clone() * t)
+        };
+
+        let mut t = 0;
+        while t < times.min(s) {
+            let ti = t as isize;
+            let off = first - 1;
+            temp[0] = pw[off as usize].clone();
+            temp[(last + 1 - off) as usize] = pw[(last + 1) as usize].clone();
+            let (mut i, mut j) = (first, last);
+            let (mut ii, mut jj) = (1, last - off);
+            while j - i > ti {
+                let (iu, ju) = (i as usize, j as usize);
+                let alfi = (u - knots[iu]) / (knots[iu + ord + t] - knots[iu]);
+                let alfj = (u - knots[ju - t]) / (knots[ju + ord] - knots[ju - t]);
+                temp[ii as usize] = OPoint::from(
+                    (pw[iu].coords.clone()
+                        - temp[(ii - 1) as usize].coords.clone() * (T::one() - alfi))
+                        / alfi,
+                );
+                temp[jj as usize] = OPoint::from(
+                    (pw[ju].coords.clone() - temp[(jj + 1) as usize].coords.clone() * alfj)
+                        / (T::one() - alfj),
+                );
+                i += 1;
+                ii += 1;
+                j -= 1;
+                jj -= 1;
+            }
+            let removable = if j - i < ti {
+                (&temp[(ii - 1) as usize] - &temp[(jj + 1) as usize]).norm() <= tol
+            } else {
+                let iu = i as usize;
+                let alfi = (u - knots[iu]) / (knots[iu + ord + t] - knots[iu]);
+                let q = lerp(
+                    &temp[(ii - 1) as usize],
+                    &temp[(ii + ti + 1) as usize],
+                    alfi,
+                );
+                (&pw[iu] - &q).norm() <= tol
+            };
+            if !removable {
+                break;
+            }
+            let (mut i, mut j) = (first, last);
+            while j - i > ti {
+                pw[i as usize] = temp[(i - off) as usize].clone();
+                pw[j as usize] = temp[(j - off) as usize].clone();
+                i += 1;
+                j -= 1;
+            }
+            first -= 1;
+            last += 1;
+            t += 1;
+        }
+        if t == 0 {
+            return Ok((self.clone(), 0));
+        }
+
+        let mut new_knots = knots.to_vec();
+        for k in (r + 1)..new_knots.len() {
+            new_knots[k - t] = new_knots[k];
+        }
+        new_knots.truncate(knots.len() - t);
+        let (mut i, mut j) = (fout, fout);
+        for k in 1..t {
+            if k % 2 == 1 {
+                i += 1;
+            } else {
+                j -= 1;
+            }
+        }
+        for k in (i + 1)..=(n as usize) {
+            pw[j] = pw[k].clone();
+            j += 1;
+        }
+        pw.truncate(pw.len() - t);
+
+        Ok((Self::try_new(p, pw, new_knots)?, t))
+    }
+
+    /// Try to remove as many interior knots as possible while the curve is kept within the tolerance
+    /// Each removal is accepted only if the deviation from the original curve at the same parameters stays within the tolerance,
+    /// so the accumulated error of the removals is bounded.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::Point2;
+    ///
+    /// let points = vec![Point2::new(0., 0.), Point2::new(1., 2.), Point2::new(3., -1.), Point2::new(4., 1.)];
+    /// let curve = NurbsCurve2D::try_interpolate(&points, 3).unwrap();
+    /// // the knots accumulated by the splits & the joins
+    /// let mut refined = curve.clone();
+    /// refined.try_refine_knot(vec![0.2, 0.4, 0.4, 0.7, 0.9]).unwrap();
+    /// let cleaned = refined.try_remove_knots(1e-8).unwrap();
+    /// assert_eq!(cleaned.knots().len(), curve.knots().len());
+    /// for i in 0..=10 {
+    ///     let t = i as f64 / 10.;
+    ///     assert!((cleaned.point_at(t) - curve.point_at(t)).norm() < 1e-8);
+    /// }
+    /// ```
+    pub fn try_remove_knots(&self, tolerance: T) -> anyhow::Result<Self>
+    where
+        D: DimNameSub<U1>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+    {
+        let mut curve = self.clone();
+        loop {
+            let mult = curve.knots.multiplicity();
+            let interior = &mult[1..(mult.len() - 1)];
+            let mut removed = false;
+            for m in interior.iter() {
+                // try to remove the knot as many times as possible, and fewer times if the deviation exceeds the tolerance
+                for times in (1..=m.multiplicity()).rev() {
+                    let (candidate, count) = curve.try_remove_knot(*m.knot(), times, tolerance)?;
+                    if count > 0 && self.max_deviation(&candidate) <= tolerance {
+                        curve = candidate;
+                        removed = true;
+                        break;
+                    }
+                }
+                if removed {
+                    break;
+                }
+            }
+            if !removed {
+                return Ok(curve);
+            }
+        }
+    }
+
+    /// Find the closest point on the curve to a given point
+    pub fn find_closest_point(
+        &self,
+        point: &OPoint<T, DimNameDiff<D, U1>>,
+    ) -> anyhow::Result<OPoint<T, DimNameDiff<D, U1>>>
+    where
+        D: DimNameSub<U1>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+        T: ArgminFloat,
+    {
+        self.find_closest_parameter(point).map(|u| self.point_at(u))
+    }
+
+    /// Find the closest point on the curve to a given point with the options for the solver
+    pub fn find_closest_point_with_options(
+        &self,
+        point: &OPoint<T, DimNameDiff<D, U1>>,
+        options: ClosestParameterOptions<T>,
+    ) -> anyhow::Result<OPoint<T, DimNameDiff<D, U1>>>
+    where
+        D: DimNameSub<U1>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+        T: ArgminFloat,
+    {
+        self.find_closest_parameter_with_options(point, options)
+            .map(|u| self.point_at(u))
+    }
+
+    /// Find the closest parameter on the curve to a given point with Newton's method
     pub fn find_closest_parameter(&self, point: &OPoint<T, DimNameDiff<D, U1>>) -> anyhow::Result<T>
     where
         D: DimNameSub<U1>,
         DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
         T: ArgminFloat,
     {
-        let (min_u, max_u) = self.knots_domain();
-        let samples = self.control_points.len() * self.degree;
-        let pts = self.sample_regular_range_with_parameter(min_u, max_u, samples);
+        self.find_closest_parameter_with_options(point, Default::default())
+    }
 
-        let mut min = <T as RealField>::max_value().unwrap();
-        let mut u = min_u;
+    /// Find the closest parameter on the curve to a given point with Newton's method
+    /// * `point` - The point to find the closest parameter to
+    /// * `options` - Tolerances & maximum number of iterations for the solver
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point2, Vector2};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let circle = NurbsCurve2D::try_circle(&Point2::origin(), &Vector2::x(), &Vector2::y(), 1.).unwrap();
+    /// let options = ClosestParameterOptions::default()
+    ///     .with_point_tolerance(1e-12)
+    ///     .with_cosine_tolerance(1e-12)
+    ///     .with_max_iters(32);
+    /// let t = circle.find_closest_parameter_with_options(&Point2::new(2., 2.), options).unwrap();
+    /// let expected = Point2::new(std::f64::consts::FRAC_1_SQRT_2, std::f64::consts::FRAC_1_SQRT_2);
+    /// assert_relative_eq!(circle.point_at(t), expected, epsilon = 1e-10);
+    ///
+    /// // A long curve with small wiggles near the point, where the newton method from the best seed converges to a local minimum
+    /// let curve = NurbsCurve2D::try_interpolate(&[
+    ///     Point2::new(-100., 0.),
+    ///     Point2::new(0., 0.5),
+    ///     Point2::new(1., -0.5),
+    ///     Point2::new(2., 1.5),
+    ///     Point2::new(3., 0.),
+    ///     Point2::new(4., -2.),
+    ///     Point2::new(100., 0.),
+    /// ], 3).unwrap();
+    /// let point = Point2::new(4., -0.5);
+    /// let (start, end) = curve.knots_domain();
+    /// let global = (0..=100000)
+    ///     .map(|i| (curve.point_at(start + (end - start) * i as f64 / 100000.) - point).norm())
+    ///     .fold(f64::MAX, f64::min);
+    ///
+    /// let t = curve.find_closest_parameter_with_options(&point, ClosestParameterOptions::default()).unwrap();
+    /// assert!((curve.point_at(t) - point).norm() > global + 1e-2);
+    ///
+    /// // Run the newton method from the 4 best seeds to find the global minimum
+    /// let options = ClosestParameterOptions::default().with_seeds(4);
+    /// let t = curve.find_closest_parameter_with_options(&point, options).unwrap();
+    /// assert_relative_eq!((curve.point_at(t) - point).norm(), global, epsilon = 1e-6);
+    /// ```
+    pub fn find_closest_parameter_with_options(
+        &self,
+        point: &OPoint<T, DimNameDiff<D, U1>>,
+        options: ClosestParameterOptions<T>,
+    ) -> anyhow::Result<T>
+    where
+        D: DimNameSub<U1>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+        T: ArgminFloat,
+    {
+        let (min_u, max_u) = self.knots_domain();
 
         let closed =
             (&self.control_points[0] - &self.control_points[self.control_points.len() - 1]).norm()
                 < T::default_epsilon();
 
-        for i in 0..pts.len() - 1 {
-            let u0 = pts[i].0;
-            let u1 = pts[i + 1].0;
+        // find the initial guesses by the subdivision of the curve
+        let candidates = find_closest_parameter_candidates(self, point)?;
+        let eps = (max_u - min_u) * T::from_f64(1e-3).unwrap();
+        let mut seeds: Vec<T> = vec![];
+        for c in candidates.iter() {
+            if seeds.len() >= options.seeds.max(1) {
+                break;
+            }
+            if seeds
+                .iter()
+                .all(|s| ComplexField::abs(*s - c.parameter) > eps)
+            {
+                seeds.push(c.parameter);
+            }
+        }
+        if seeds.is_empty() {
+            seeds.push(min_u);
+        }
 
-            let p0 = &pts[i].1;
-            let p1 = &pts[i + 1].1;
+        // run the newton method from each seed and take the global minimum,
+        // where the seeds themselves are kept because the newton method can leave the minimum at the end of the curve
+        let mut closest: Option<(T, T)> = seeds
+            .iter()
+            .map(|u| (*u, (self.point_at(*u) - point).norm()))
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+        for u in seeds {
+            let solver = ClosestParameterNewton::new((min_u, max_u), closed)
+                .with_point_tolerance(options.point_tolerance)
+                .with_cosine_tolerance(options.cosine_tolerance)
+                .with_parameter_tolerance(options.parameter_tolerance);
+            let res = Executor::new(ClosestParameterProblem::new(point, self), solver)
+                .configure(|state| state.param(u).max_iters(options.max_iters))
+                .run()?;
+            if let Some(t) = res.state().get_best_param().cloned() {
+                let d = (self.point_at(t) - point).norm();
+                if closest.map(|(_, min)| d < min).unwrap_or(true) {
+                    closest = Some((t, d));
+                }
+            }
+        }
 
-            let (proj_u, proj_pt) = segment_closest_point(point, p0, p1, u0, u1);
-            let d = (point - proj_pt).norm();
+        closest
+            .map(|(t, _)| t)
+            .ok_or(anyhow::anyhow!("No best parameter found"))
+    }
 
-            if d < min {
-                min = d;
-                u = proj_u;
+    /// Find the pair of parameters minimizing the distance between two curves
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let circle = NurbsCurve3D::try_circle(&Point3::origin(), &Vector3::x(), &Vector3::y(), 1.).unwrap();
+    /// let line = NurbsCurve3D::polyline(&[Point3::new(-3., 2., 1.), Point3::new(3., 2., 1.)]);
+    ///
+    /// let closest = circle.closest_parameters(&line).unwrap();
+    /// assert_relative_eq!(closest.a().0, Point3::new(0., 1., 0.), epsilon = 1e-6);
+    /// assert_relative_eq!(closest.b().0, Point3::new(0., 2., 1.), epsilon = 1e-6);
+    /// assert_relative_eq!(*closest.distance(), 2f64.sqrt(), epsilon = 1e-6);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn closest_parameters(
+        &self,
+        other: &Self,
+    ) -> anyhow::Result<CurveClosestParameters<OPoint<T, DimNameDiff<D, U1>>, T>>
+    where
+        D: DimNameSub<U1>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+    {
+        self.closest_parameters_with_options(other, Default::default())
+    }
+
+    /// Find the pair of parameters minimizing the distance between two curves with the options for the solver
+    /// The initial guesses are found by the subdivision of the pairs of Bézier segments,
+    /// and refined by Newton's method on the squared distance.
+    #[allow(clippy::type_complexity)]
+    pub fn closest_parameters_with_options(
+        &self,
+        other: &Self,
+        options: ClosestParameterOptions<T>,
+    ) -> anyhow::Result<CurveClosestParameters<OPoint<T, DimNameDiff<D, U1>>, T>>
+    where
+        D: DimNameSub<U1>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+    {
+        let candidates = find_closest_parameter_pair_candidates(self, other)?;
+
+        let (a0, a1) = self.knots_domain();
+        let (b0, b1) = other.knots_domain();
+        let eps = ((a1 - a0) + (b1 - b0)) * T::from_f64(1e-3).unwrap();
+        let mut seeds: Vec<(T, T)> = vec![];
+        for c in candidates.iter() {
+            if seeds.len() >= options.seeds.max(1) {
+                break;
+            }
+            let (t, u) = c.parameters;
+            if seeds
+                .iter()
+                .all(|(s, v)| ComplexField::abs(*s - t) + ComplexField::abs(*v - u) > eps)
+            {
+                seeds.push(c.parameters);
             }
         }
 
-        let solver = ClosestParameterNewton::new((min_u, max_u), closed);
-        let res = Executor::new(ClosestParameterProblem::new(point, self), solver)
-            .configure(|state| state.param(u).max_iters(5))
-            .run()?;
-        res.state()
-            .get_best_param()
-            .cloned()
-            .ok_or(anyhow::anyhow!("No best parameter found"))
+        let closest = seeds
+            .into_iter()
+            .map(|seed| {
+                let (t, u) = self.refine_closest_parameters(other, seed, &options);
+                let (p, q) = (self.point_at(t), other.point_at(u));
+                let d = (&p - &q).norm();
+                CurveClosestParameters::new((p, t), (q, u), d)
+            })
+            .min_by(|x, y| {
+                x.distance()
+                    .partial_cmp(y.distance())
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+
+        closest.ok_or(anyhow::anyhow!("No closest parameters found"))
+    }
+
+    /// Refine the pair of parameters minimizing the distance between two curves by Newton's method
+    /// The Gauss-Newton approximation of the hessian is used if the hessian is not positive definite.
+    fn refine_closest_parameters(
+        &self,
+        other: &Self,
+        seed: (T, T),
+        options: &ClosestParameterOptions<T>,
+    ) -> (T, T)
+    where
+        D: DimNameSub<U1>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+    {
+        let (a0, a1) = self.knots_domain();
+        let (b0, b1) = other.knots_domain();
+        let (mut t, mut u) = seed;
+
+        for _ in 0..options.max_iters {
+            let da = self.rational_derivatives(t, 2);
+            let db = other.rational_derivatives(u, 2);
+            let d = &da[0] - &db[0];
+            let distance = d.norm();
+            if distance < options.point_tolerance {
+                break;
+            }
+
+            // terminate if the vector between the points is perpendicular to both curves
+            let cosine = |v: &OVector<T, DimNameDiff<D, U1>>| {
+                let n = v.norm();
+                if n > T::zero() {
+                    ComplexField::abs(d.dot(v) / (n * distance))
+                } else {
+                    T::zero()
+                }
+            };
+            if cosine(&da[1]) < options.cosine_tolerance
+                && cosine(&db[1]) < options.cosine_tolerance
+            {
+                break;
+            }
+
+            let g = Vector2::new(d.dot(&da[1]), -d.dot(&db[1]));
+            let cross = -da[1].dot(&db[1]);
+            let (aa, bb) = (da[1].dot(&da[1]), db[1].dot(&db[1]));
+            let hessian = Matrix2::new(aa + d.dot(&da[2]), cross, cross, bb - d.dot(&db[2]));
+            let positive = hessian[(0, 0)] > T::zero() && hessian.determinant() > T::zero();
+            let hessian = if positive {
+                hessian
+            } else {
+                let damping = (aa + bb) * T::from_f64(1e-6).unwrap();
+                Matrix2::new(aa + damping, cross, cross, bb + damping)
+            };
+            let Some(step) = hessian.try_inverse().map(|inv| inv * g) else {
+                break;
+            };
+
+            let nt = nalgebra::clamp(t - step.x, a0, a1);
+            let nu = nalgebra::clamp(u - step.y, b0, b1);
+            let delta = ComplexField::abs(nt - t) + ComplexField::abs(nu - u);
+            (t, u) = (nt, nu);
+            if delta < options.parameter_tolerance {
+                break;
+            }
+        }
+
+        (t, u)
+    }
+
+    /// Find the pair of the curve parameter and the surface (u, v) parameter minimizing the distance between the curve and the surface
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// // A plane at z = 0
+    /// let line = NurbsCurve3D::polyline(&[Point3::new(-2., -2., 0.), Point3::new(2., -2., 0.)]);
+    /// let plane = NurbsSurface::extrude(&line, &(Vector3::y() * 4.));
+    ///
+    /// // A circle floating above the plane
+    /// let circle = NurbsCurve3D::try_circle(&Point3::new(0., 0., 1.5), &Vector3::x(), &Vector3::z(), 1.).unwrap();
+    ///
+    /// let closest = circle.closest_surface_parameters(&plane).unwrap();
+    /// assert_relative_eq!(closest.curve().0, Point3::new(0., 0., 0.5), epsilon = 1e-6);
+    /// assert_relative_eq!(closest.surface().0, Point3::new(0., 0., 0.), epsilon = 1e-6);
+    /// assert_relative_eq!(*closest.distance(), 0.5, epsilon = 1e-6);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn closest_surface_parameters(
+        &self,
+        surface: &NurbsSurface<T, D>,
+    ) -> anyhow::Result<CurveSurfaceClosestParameters<OPoint<T, DimNameDiff<D, U1>>, T>>
+    where
+        D: DimNameSub<U1>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+    {
+        self.closest_surface_parameters_with_options(surface, Default::default())
+    }
+
+    /// Find the pair of the curve parameter and the surface (u, v) parameter minimizing the distance with the options for the solver
+    /// The initial guesses are found by the subdivision of the pairs of Bézier segments & patches,
+    /// and refined by the coupled Newton's method on the squared distance.
+    #[allow(clippy::type_complexity)]
+    pub fn closest_surface_parameters_with_options(
+        &self,
+        surface: &NurbsSurface<T, D>,
+        options: ClosestParameterOptions<T>,
+    ) -> anyhow::Result<CurveSurfaceClosestParameters<OPoint<T, DimNameDiff<D, U1>>, T>>
+    where
+        D: DimNameSub<U1>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+    {
+        let candidates = find_curve_surface_closest_parameter_candidates(self, surface)?;
+
+        let (t0, t1) = self.knots_domain();
+        let (u0, u1) = surface.u_knots_domain();
+        let (v0, v1) = surface.v_knots_domain();
+        let eps = ((t1 - t0) + (u1 - u0) + (v1 - v0)) * T::from_f64(1e-3).unwrap();
+        let mut seeds: Vec<Vector3<T>> = vec![];
+        for c in candidates.iter() {
+            if seeds.len() >= options.seeds.max(1) {
+                break;
+            }
+            let (t, (u, v)) = c.parameters;
+            let x = Vector3::new(t, u, v);
+            if seeds.iter().all(|s| (s - x).abs().sum() > eps) {
+                seeds.push(x);
+            }
+        }
+
+        let clamp = |x: Vector3<T>| {
+            Vector3::new(
+                nalgebra::clamp(x.x, t0, t1),
+                nalgebra::clamp(x.y, u0, u1),
+                nalgebra::clamp(x.z, v0, v1),
+            )
+        };
+
+        let closest = seeds
+            .into_iter()
+            .map(|mut x| {
+                for _ in 0..options.max_iters {
+                    let c = self.rational_derivatives(x.x, 2);
+                    let s = surface.rational_derivatives(x.y, x.z, 2);
+                    let d = &c[0] - &s[0][0];
+                    let distance = d.norm();
+                    if distance < options.point_tolerance {
+                        break;
+                    }
+
+                    // terminate if the vector between the points is perpendicular to the curve & the surface
+                    let cosine = |v: &OVector<T, DimNameDiff<D, U1>>| {
+                        let n = v.norm();
+                        if n > T::zero() {
+                            ComplexField::abs(d.dot(v) / (n * distance))
+                        } else {
+                            T::zero()
+                        }
+                    };
+                    if [&c[1], &s[1][0], &s[0][1]]
+                        .iter()
+                        .all(|v| cosine(v) < options.cosine_tolerance)
+                    {
+                        break;
+                    }
+
+                    let (ct, su, sv) = (&c[1], &s[1][0], &s[0][1]);
+                    let g = Vector3::new(d.dot(ct), -d.dot(su), -d.dot(sv));
+                    let gauss_newton = Matrix3::new(
+                        ct.dot(ct),
+                        -ct.dot(su),
+                        -ct.dot(sv),
+                        -ct.dot(su),
+                        su.dot(su),
+                        su.dot(sv),
+                        -ct.dot(sv),
+                        su.dot(sv),
+                        sv.dot(sv),
+                    );
+                    let second = Matrix3::new(
+                        d.dot(&c[2]),
+                        T::zero(),
+                        T::zero(),
+                        T::zero(),
+                        -d.dot(&s[2][0]),
+                        -d.dot(&s[1][1]),
+                        T::zero(),
+                        -d.dot(&s[1][1]),
+                        -d.dot(&s[0][2]),
+                    );
+
+                    // use the Gauss-Newton approximation of the hessian if the hessian is not positive definite
+                    let step = (gauss_newton + second)
+                        .cholesky()
+                        .map(|h| h.solve(&g))
+                        .or_else(|| {
+                            let damping = gauss_newton.trace() * T::from_f64(1e-6).unwrap();
+                            (gauss_newton + Matrix3::identity() * damping)
+                                .cholesky()
+                                .map(|h| h.solve(&g))
+                        });
+                    let Some(step) = step else {
+                        break;
+                    };
+
+                    let next = clamp(x - step);
+                    let delta = (next - x).abs().sum();
+                    x = next;
+                    if delta < options.parameter_tolerance {
+                        break;
+                    }
+                }
+
+                let (pc, ps) = (self.point_at(x.x), surface.point_at(x.y, x.z));
+                let d = (&pc - &ps).norm();
+                CurveSurfaceClosestParameters::new((pc, x.x), (ps, (x.y, x.z)), d)
+            })
+            .min_by(|x, y| {
+                x.distance()
+                    .partial_cmp(y.distance())
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+
+        closest.ok_or(anyhow::anyhow!("No closest parameters found"))
+    }
+
+    /// Find the points where the ray passes within the distance tolerance from the curve
+    /// Returns the local closest points between the ray and the curve sorted by the parameter along the ray.
+    /// * `ray` - The ray to intersect with
+    /// * `tolerance` - The maximum distance between the ray and the curve to be considered as a hit
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point2, Vector2};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let circle = NurbsCurve2D::try_circle(&Point2::origin(), &Vector2::x(), &Vector2::y(), 1.).unwrap();
+    ///
+    /// // The ray crosses the circle twice
+    /// let ray = Ray::new(Point2::new(-3., 0.), Vector2::x());
+    /// let hits = circle.find_ray_intersections(&ray, 1e-6).unwrap();
+    /// assert_eq!(hits.len(), 2);
+    /// assert_relative_eq!(hits[0].curve().0, Point2::new(-1., 0.), epsilon = 1e-6);
+    /// assert_relative_eq!(hits[0].ray().1, 2., epsilon = 1e-6);
+    /// assert_relative_eq!(hits[1].curve().0, Point2::new(1., 0.), epsilon = 1e-6);
+    ///
+    /// // The ray passing near the circle is picked with the tolerance
+    /// let ray = Ray::new(Point2::new(-3., 1.05), Vector2::x());
+    /// assert!(circle.find_ray_intersections(&ray, 1e-2).unwrap().is_empty());
+    /// let hits = circle.find_ray_intersections(&ray, 1e-1).unwrap();
+    /// assert_eq!(hits.len(), 1);
+    /// assert_relative_eq!(hits[0].curve().0, Point2::new(0., 1.), epsilon = 1e-6);
+    /// assert_relative_eq!(*hits[0].distance(), 0.05, epsilon = 1e-6);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn find_ray_intersections(
+        &self,
+        ray: &Ray<T, DimNameDiff<D, U1>>,
+        tolerance: T,
+    ) -> anyhow::Result<Vec<RayCurveIntersection<OPoint<T, DimNameDiff<D, U1>>, T>>>
+    where
+        D: DimNameSub<U1>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+    {
+        let expand = |bb: BoundingBox<T, DimNameDiff<D, U1>>| {
+            let margin = OVector::<T, DimNameDiff<D, U1>>::from_element(tolerance);
+            BoundingBox::new(bb.min() - &margin, bb.max() + &margin)
+        };
+        let flatness = tolerance * T::from_f64(0.1).unwrap();
+        let half = T::from_f64(0.5).unwrap();
+
+        // subdivide the segments whose bounding boxes expanded by the tolerance are hit by the ray
+        let mut leaves = vec![];
+        let mut stack: Vec<_> = self
+            .try_decompose_bezier_segments()?
+            .into_iter()
+            .map(|s| (s, 0))
+            .collect();
+        while let Some((segment, depth)) = stack.pop() {
+            let control_points = segment.dehomogenized_control_points();
+            let bb = expand(BoundingBox::new_with_points(control_points.clone()));
+            let Some((r0, r1)) = bb.intersects_ray(ray) else {
+                continue;
+            };
+            if deviation::<T, D>(&control_points) < flatness || depth >= 32 {
+                let (s, r) = segments_closest_parameters(
+                    &control_points[0],
+                    &control_points[control_points.len() - 1],
+                    &ray.point_at(r0),
+                    &ray.point_at(r1),
+                );
+                let (t0, t1) = segment.knots_domain();
+                leaves.push((t0 + (t1 - t0) * s, r0 + (r1 - r0) * r));
+            } else {
+                let (t0, t1) = segment.knots_domain();
+                let (l, r) = segment.try_trim((t0 + t1) * half)?;
+                stack.push((l, depth + 1));
+                stack.push((r, depth + 1));
+            }
+        }
+
+        // refine the closest parameters between the ray and the curve by Newton's method
+        let (a0, a1) = self.knots_domain();
+        let direction = ray.direction();
+        let dd = direction.dot(direction);
+        let mut intersections: Vec<_> = leaves
+            .into_iter()
+            .filter_map(|(mut t, mut r)| {
+                for _ in 0..32 {
+                    let c = self.rational_derivatives(t, 2);
+                    let d = &c[0] - ray.point_at(r).coords;
+                    let g = Vector2::new(d.dot(&c[1]), -d.dot(direction));
+                    let cross = -c[1].dot(direction);
+                    let aa = c[1].dot(&c[1]);
+                    let hessian = Matrix2::new(aa + d.dot(&c[2]), cross, cross, dd);
+                    let hessian =
+                        if hessian[(0, 0)] > T::zero() && hessian.determinant() > T::zero() {
+                            hessian
+                        } else {
+                            let damping = (aa + dd) * T::from_f64(1e-6).unwrap();
+                            Matrix2::new(aa + damping, cross, cross, dd + damping)
+                        };
+                    let step = hessian.try_inverse()? * g;
+                    let nt = nalgebra::clamp(t - step.x, a0, a1);
+                    let nr = (r - step.y).max(T::zero());
+                    let delta = ComplexField::abs(nt - t) + ComplexField::abs(nr - r);
+                    (t, r) = (nt, nr);
+                    if delta < T::default_epsilon() {
+                        break;
+                    }
+                }
+                let (pc, pr) = (self.point_at(t), ray.point_at(r));
+                let distance = (&pc - &pr).norm();
+                (distance <= tolerance)
+                    .then(|| RayCurveIntersection::new((pc, t), (pr, r), distance))
+            })
+            .collect();
+
+        intersections.sort_by(|a, b| {
+            a.ray()
+                .1
+                .partial_cmp(&b.ray().1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        // merge intersections found in the adjacent segments
+        let mut merged: Vec<RayCurveIntersection<OPoint<T, DimNameDiff<D, U1>>, T>> = vec![];
+        for it in intersections.into_iter() {
+            let duplicated = merged
+                .iter()
+                .any(|m| (&m.curve().0 - &it.curve().0).norm() < tolerance);
+            if !duplicated {
+                merged.push(it);
+            }
+        }
+
+        Ok(merged)
     }
 
     /// Find the intersection points with another curve by gauss-newton line search
+    /// Each intersection is classified as transversal or tangential, and the coincident parts of the curves are returned as the overlap intervals of the parameters.
     /// * `other` - The other curve to intersect with
     /// * `options` - Hyperparameters for the intersection solver
     /// # Example
@@ -1447,7 +3185,7 @@ where
     ///     ..Default::default()
     /// };
     ///
-    /// let mut intersections = unit_circle.find_intersections(&line, Some(options)).unwrap();
+    /// let mut intersections = unit_circle.find_intersections(&line, Some(options.clone())).unwrap();
     /// assert_eq!(intersections.len(), 2);
     ///
     /// intersections.sort_by(|i0, i1| {
@@ -1457,7 +3195,27 @@ where
     /// assert_relative_eq!(p0.a().0, Point2::new(-1.0, 0.0), epsilon = 1e-5);
     /// let p1 = &intersections[1];
     /// assert_relative_eq!(p1.a().0, Point2::new(1.0, 0.0), epsilon = 1e-5);
+    /// assert!(intersections.iter().all(|it| it.kind() == &CurveIntersectionKind::Transversal));
+    ///
+    /// // the line touching the circle is classified as tangential, which the bezier clipping locates robustly
+    /// let tangent = NurbsCurve2D::polyline(&[Point2::new(-2., 1.), Point2::new(2., 1.)]);
+    /// let clipping = options.clone().with_backend(CurveIntersectionBackend::BezierClipping);
+    /// let intersections = unit_circle.find_intersections(&tangent, Some(clipping)).unwrap();
+    /// assert_eq!(intersections.len(), 1);
+    /// assert_eq!(intersections[0].kind(), &CurveIntersectionKind::Tangential);
     ///
+    /// // the partially coincident polylines overlap on the interval
+    /// let a = NurbsCurve2D::polyline(&[Point2::new(0., 0.), Point2::new(2., 0.), Point2::new(2., 1.)]);
+    /// let b = NurbsCurve2D::polyline(&[Point2::new(1., -1.), Point2::new(1., 0.), Point2::new(3., 0.)]);
+    /// let intersections = a.find_intersections(&b, Some(options)).unwrap();
+    /// assert_eq!(intersections.len(), 1);
+    /// let CurveIntersectionKind::Overlap { a: (a0, a1), b: (b0, b1) } = *intersections[0].kind() else {
+    ///     panic!("The intersection is not an overlap");
+    /// };
+    /// assert_relative_eq!(a.point_at(a0), Point2::new(1., 0.), epsilon = 1e-4);
+    /// assert_relative_eq!(a.point_at(a1), Point2::new(2., 0.), epsilon = 1e-4);
+    /// assert_relative_eq!(b.point_at(b0), Point2::new(1., 0.), epsilon = 1e-4);
+    /// assert_relative_eq!(b.point_at(b1), Point2::new(2., 0.), epsilon = 1e-4);
     /// ```
     #[allow(clippy::type_complexity)]
     pub fn find_intersections(
@@ -1471,7 +3229,26 @@ where
         T: ArgminFloat,
     {
         let options = options.unwrap_or_default();
+        let intersections = if options.backend == CurveIntersectionBackend::BezierClipping {
+            self.find_intersections_by_bezier_clipping(other, &options)?
+        } else {
+            self.find_intersections_by_bfgs(other, &options)?
+        };
+        Ok(self.classify_intersections(other, intersections, &options))
+    }
 
+    /// Find the intersections by minimizing the distance between the segments of the curves in the overlapping bounding boxes
+    #[allow(clippy::type_complexity)]
+    fn find_intersections_by_bfgs(
+        &self,
+        other: &Self,
+        options: &CurveIntersectionSolverOptions<T>,
+    ) -> anyhow::Result<Vec<CurveIntersection<OPoint<T, DimNameDiff<D, U1>>, T>>>
+    where
+        D: DimNameSub<U1>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+        T: ArgminFloat,
+    {
         let traversed = BoundingBoxTraversal::try_traverse(
             self,
             other,
@@ -1529,42 +3306,474 @@ where
                             CurveIntersection::new((p0, param[0]), (p1, param[1]))
                         })
                     }
-                    Err(_e) => {
-                        // println!("{}", e);
-                        None
+                    Err(_e) => {
+                        // println!("{}", e);
+                        None
+                    }
+                }
+            })
+            .filter(|it| {
+                // filter out intersections that are too close
+                let p0 = &it.a().0;
+                let p1 = &it.b().0;
+                let d = (p0 - p1).norm();
+                d < options.minimum_distance
+            })
+            .coalesce(|x, y| {
+                // merge intersections that are close in parameter space
+                let da = ComplexField::abs(x.a().1 - y.a().1);
+                let db = ComplexField::abs(x.b().1 - y.b().1);
+                if da < eps || db < eps {
+                    Ok(x)
+                } else {
+                    Err((x, y))
+                }
+            })
+            .collect();
+
+        Ok(pts)
+    }
+
+    /// Classify the intersections by the tangents of the curves, and replace the intersections on the coincident parts with the overlap intervals
+    #[allow(clippy::type_complexity)]
+    fn classify_intersections(
+        &self,
+        other: &Self,
+        intersections: Vec<CurveIntersection<OPoint<T, DimNameDiff<D, U1>>, T>>,
+        options: &CurveIntersectionSolverOptions<T>,
+    ) -> Vec<CurveIntersection<OPoint<T, DimNameDiff<D, U1>>, T>>
+    where
+        D: DimNameSub<U1>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+        T: ArgminFloat,
+    {
+        let (start, end) = self.knots_domain();
+        let half = T::from_f64(0.5).unwrap();
+        let closest_within = |t: T, distance: T| -> Option<T> {
+            let p = self.point_at(t);
+            other
+                .find_closest_parameter(&p)
+                .ok()
+                .filter(|u| (other.point_at(*u) - &p).norm() < distance)
+        };
+        let closest = |t: T| closest_within(t, options.minimum_distance);
+
+        // sample the curve uniformly & at the intersections to find the coincident parts
+        let division = options.knot_domain_division.max(1);
+        let mut samples = (0..=division)
+            .map(|i| {
+                start + (end - start) * T::from_usize(i).unwrap() / T::from_usize(division).unwrap()
+            })
+            .chain(
+                intersections
+                    .iter()
+                    .map(|it| it.a().1)
+                    .filter(|t| start <= *t && *t <= end),
+            )
+            .collect::<Vec<_>>();
+        samples.sort_by(|x, y| x.partial_cmp(y).unwrap_or(std::cmp::Ordering::Equal));
+        samples.dedup_by(|x, y| ComplexField::abs(*x - *y) < options.minimum_distance);
+        let coincident = samples.iter().map(|t| closest(*t).is_some()).collect_vec();
+
+        // bisect the boundary between the coincident & the separated parameters
+        let boundary = |inside: T, outside: T| -> T {
+            let (mut inside, mut outside) = (inside, outside);
+            for _ in 0..32 {
+                if ComplexField::abs(outside - inside) < options.minimum_distance {
+                    break;
+                }
+                let mid = (inside + outside) * half;
+                if closest(mid).is_some() {
+                    inside = mid;
+                } else {
+                    outside = mid;
+                }
+            }
+            inside
+        };
+
+        let mut overlaps: Vec<(T, T)> = vec![];
+        let mut i = 0;
+        while i < samples.len() {
+            if !coincident[i] {
+                i += 1;
+                continue;
+            }
+            // extend the run while the curves are also coincident between the samples
+            let mut j = i;
+            while j + 1 < samples.len()
+                && coincident[j + 1]
+                && closest((samples[j] + samples[j + 1]) * half).is_some()
+            {
+                j += 1;
+            }
+            if j > i {
+                let t0 = if i > 0 {
+                    boundary(samples[i], samples[i - 1])
+                } else {
+                    samples[i]
+                };
+                let t1 = if j + 1 < samples.len() {
+                    boundary(samples[j], samples[j + 1])
+                } else {
+                    samples[j]
+                };
+                // the curves touching tangentially separate quadratically from the contact, while the coincident parts stay close throughout
+                let quarter = (t1 - t0) * half * half;
+                let separation = options.minimum_distance * T::from_f64(0.1).unwrap();
+                if closest_within(t0 + quarter, separation).is_some()
+                    && closest_within(t1 - quarter, separation).is_some()
+                {
+                    overlaps.push((t0, t1));
+                }
+            }
+            i = j + 1;
+        }
+
+        let eps = options.minimum_distance * T::from_f64(5.).unwrap();
+        let mut classified = intersections
+            .into_iter()
+            .filter(|it| {
+                let t = it.a().1;
+                !overlaps
+                    .iter()
+                    .any(|(t0, t1)| *t0 - eps <= t && t <= *t1 + eps)
+            })
+            .map(|it| {
+                let ta = self.tangent_at(it.a().1);
+                let tb = other.tangent_at(it.b().1);
+                let (na, nb) = (ta.norm(), tb.norm());
+                if na <= T::zero() || nb <= T::zero() {
+                    return it;
+                }
+                let cos = ComplexField::abs(ta.dot(&tb) / (na * nb));
+                let sin = ComplexField::sqrt(RealField::max(T::one() - cos * cos, T::zero()));
+                if sin < options.tangency_tolerance {
+                    it.with_kind(CurveIntersectionKind::Tangential)
+                } else {
+                    it
+                }
+            })
+            .collect_vec();
+
+        classified.extend(overlaps.into_iter().filter_map(|(t0, t1)| {
+            let (u0, u1) = (closest(t0)?, closest(t1)?);
+            Some(
+                CurveIntersection::new((self.point_at(t0), t0), (other.point_at(u0), u0))
+                    .with_kind(CurveIntersectionKind::Overlap {
+                        a: (t0, t1),
+                        b: (u0, u1),
+                    }),
+            )
+        }));
+        classified.sort_by(|x, y| {
+            x.a()
+                .1
+                .partial_cmp(&y.a().1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        classified
+    }
+
+    /// Find the intersections by clipping the Bézier segments of the curves against each other
+    #[allow(clippy::type_complexity)]
+    fn find_intersections_by_bezier_clipping(
+        &self,
+        other: &Self,
+        options: &CurveIntersectionSolverOptions<T>,
+    ) -> anyhow::Result<Vec<CurveIntersection<OPoint<T, DimNameDiff<D, U1>>, T>>>
+    where
+        D: DimNameSub<U1>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+    {
+        let clips = |curve: &Self| -> anyhow::Result<Vec<BezierClip<T>>> {
+            Ok(curve
+                .try_decompose_bezier_segments()?
+                .into_iter()
+                .map(|s| {
+                    let control_points = s
+                        .control_points()
+                        .iter()
+                        .map(|p| DVector::from_iterator(D::dim(), p.iter().copied()))
+                        .collect();
+                    BezierClip::new(control_points, s.knots_domain())
+                })
+                .collect())
+        };
+        let (sa, sb) = (clips(self)?, clips(other)?);
+        let margin = options.minimum_distance * T::from_f64(1e-3).unwrap();
+
+        let mut candidates = sa
+            .iter()
+            .flat_map(|a| {
+                sb.iter().flat_map(|b| {
+                    bezier_clipping_intersections(
+                        a.clone(),
+                        b.clone(),
+                        margin,
+                        options.minimum_distance,
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
+        candidates.sort_by(|x, y| x.0.partial_cmp(&y.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        // the clusters of the candidates connected in the parameter space, which appear around the tangential intersections
+        let eps = options.minimum_distance * T::from_f64(5.).unwrap();
+        let mut clusters: Vec<Vec<(T, T)>> = vec![];
+        for c in candidates {
+            match clusters.last_mut() {
+                Some(cluster)
+                    if cluster.iter().any(|x| {
+                        ComplexField::abs(x.0 - c.0) < eps && ComplexField::abs(x.1 - c.1) < eps
+                    }) =>
+                {
+                    cluster.push(c)
+                }
+                _ => clusters.push(vec![c]),
+            }
+        }
+
+        // take the closest pair of each cluster refined by the gauss newton method
+        let mut intersections: Vec<CurveIntersection<OPoint<T, DimNameDiff<D, U1>>, T>> = vec![];
+        for cluster in clusters {
+            let best = cluster
+                .into_iter()
+                .map(|(ta, tb)| self.refine_intersection(other, ta, tb))
+                .map(|(ta, tb)| {
+                    CurveIntersection::new((self.point_at(ta), ta), (other.point_at(tb), tb))
+                })
+                .min_by(|x, y| {
+                    let dx = (&x.a().0 - &x.b().0).norm();
+                    let dy = (&y.a().0 - &y.b().0).norm();
+                    dx.partial_cmp(&dy).unwrap_or(std::cmp::Ordering::Equal)
+                });
+            let Some(it) = best else {
+                continue;
+            };
+            if (&it.a().0 - &it.b().0).norm() >= options.minimum_distance {
+                continue;
+            }
+            // the same point may be found across the seams of the closed curves
+            let duplicated = intersections
+                .iter()
+                .any(|x| (&x.a().0 - &it.a().0).norm() < options.minimum_distance);
+            if !duplicated {
+                intersections.push(it);
+            }
+        }
+        Ok(intersections)
+    }
+
+    /// Refine the pair of the parameters at the intersection by the gauss newton method minimizing the distance, keeping the initial pair if it does not improve
+    fn refine_intersection(&self, other: &Self, ta: T, tb: T) -> (T, T)
+    where
+        D: DimNameSub<U1>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+    {
+        let (da, db) = (self.knots_domain(), other.knots_domain());
+        let distance = |ta: T, tb: T| (self.point_at(ta) - other.point_at(tb)).norm();
+        let (mut best, mut best_distance) = ((ta, tb), distance(ta, tb));
+        let (mut ta, mut tb) = (ta, tb);
+        for _ in 0..8 {
+            let a = self.rational_derivatives(ta, 1);
+            let b = other.rational_derivatives(tb, 1);
+            let r = &a[0] - &b[0];
+            let (ja, jb) = (&a[1], -&b[1]);
+            let m = Matrix2::new(ja.dot(ja), ja.dot(&jb), ja.dot(&jb), jb.dot(&jb));
+            let g = Vector2::new(ja.dot(&r), jb.dot(&r));
+            let Some(delta) = m.lu().solve(&g) else {
+                break;
+            };
+            ta = (ta - delta.x).clamp(da.0, da.1);
+            tb = (tb - delta.y).clamp(db.0, db.1);
+            let d = distance(ta, tb);
+            if d < best_distance {
+                best = (ta, tb);
+                best_distance = d;
+            }
+        }
+        best
+    }
+
+    /// Find the points where the curve crosses itself
+    /// Each intersection holds the pair of the distinct parameters of the curve in ascending order as `a` & `b`.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point2, Point3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// // the cubic bezier curve making a loop symmetric about x = 1
+    /// let curve = NurbsCurve2D::try_new(
+    ///     3,
+    ///     vec![Point3::new(0., 0., 1.), Point3::new(3., 2., 1.), Point3::new(-1., 2., 1.), Point3::new(2., 0., 1.)],
+    ///     vec![0., 0., 0., 0., 1., 1., 1., 1.],
+    /// ).unwrap();
+    /// let intersections = curve.find_self_intersections(None).unwrap();
+    /// assert_eq!(intersections.len(), 1);
+    /// let it = &intersections[0];
+    /// assert!(it.a().1 < it.b().1);
+    /// assert_relative_eq!(it.a().1 + it.b().1, 1., epsilon = 1e-4);
+    /// assert_relative_eq!(curve.point_at(it.a().1).x, 1., epsilon = 1e-4);
+    ///
+    /// // the circle does not cross itself at the seam
+    /// let circle = NurbsCurve2D::try_circle(&Point2::origin(), &nalgebra::Vector2::x(), &nalgebra::Vector2::y(), 1.).unwrap();
+    /// assert!(circle.find_self_intersections(None).unwrap().is_empty());
+    ///
+    /// // the figure-eight crosses itself at the origin, which is the first point
+    /// let points: Vec<_> = (0..8).map(|i| {
+    ///     let t = std::f64::consts::TAU * i as f64 / 8.;
+    ///     Point2::new(t.sin(), t.sin() * t.cos())
+    /// }).collect();
+    ///
+    /// // the periodic interpolation is unclamped
+    /// let periodic = NurbsCurve2D::try_periodic_interpolate(&points, 3, KnotStyle::Centripetal).unwrap();
+    /// let intersections = periodic.find_self_intersections(None).unwrap();
+    /// assert_eq!(intersections.len(), 1);
+    /// assert_relative_eq!(intersections[0].a().0, Point2::origin(), epsilon = 1e-6);
+    ///
+    /// // the crossing at the both ends of the clamped closed curve is reported once
+    /// let closed = NurbsCurve2D::try_interpolate(&[points.clone(), vec![points[0]]].concat(), 3).unwrap();
+    /// let intersections = closed.find_self_intersections(None).unwrap();
+    /// assert_eq!(intersections.len(), 1);
+    /// assert_relative_eq!(intersections[0].a().0, Point2::origin(), epsilon = 1e-6);
+    /// assert_relative_eq!(intersections[0].a().1, 0., epsilon = 1e-6);
+    /// assert_relative_eq!(intersections[0].b().1, 0.5, epsilon = 1e-6);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn find_self_intersections(
+        &self,
+        options: Option<CurveIntersectionSolverOptions<T>>,
+    ) -> anyhow::Result<Vec<CurveIntersection<OPoint<T, DimNameDiff<D, U1>>, T>>>
+    where
+        D: DimNameSub<U1>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+        T: ArgminFloat,
+    {
+        let options = options.unwrap_or_default();
+        let eps = options.minimum_distance * T::from_f64(5.).unwrap();
+
+        // split the curve into the pieces which cannot cross themselves
+        let mut pieces = vec![];
+        let mut stack = self.try_decompose_bezier_segments()?;
+        stack.reverse();
+        while let Some(piece) = stack.pop() {
+            // the piece collapsing into a point cannot cross the other pieces
+            let points = piece.dehomogenized_control_points();
+            if points
+                .iter()
+                .all(|p| (p - &points[0]).norm() < options.minimum_distance)
+            {
+                continue;
+            }
+
+            let (start, end) = piece.knots_domain();
+            if piece.is_monotone() || end - start < eps {
+                pieces.push(piece);
+            } else {
+                let (head, tail) = piece.try_trim((start + end) * T::from_f64(0.5).unwrap())?;
+                stack.push(tail);
+                stack.push(head);
+            }
+        }
+
+        let (start, end) = self.knots_domain();
+        let closed = (self.point_at(start) - self.point_at(end)).norm() < options.minimum_distance;
+
+        // clamp the parameter into the domain, where the both ends of the closed curve are identified
+        let fold = |t: T| {
+            let t = RealField::clamp(t, start, end);
+            if closed && end - t < eps {
+                start
+            } else {
+                t
+            }
+        };
+
+        let boxes = pieces.iter().map(BoundingBox::from).collect_vec();
+
+        let mut intersections: Vec<CurveIntersection<OPoint<T, DimNameDiff<D, U1>>, T>> = vec![];
+        for i in 0..pieces.len() {
+            for j in (i + 1)..pieces.len() {
+                if !boxes[i].intersects(&boxes[j], Some(options.minimum_distance)) {
+                    continue;
+                }
+                let found = pieces[i].find_intersections(&pieces[j], Some(options.clone()))?;
+                for it in found {
+                    let (ta, tb) = (it.a().1, it.b().1);
+                    if !ComplexField::is_finite(&ta) || !ComplexField::is_finite(&tb) {
+                        continue;
+                    }
+                    let (ta, tb) = (fold(ta), fold(tb));
+                    let (ta, tb) = if ta <= tb { (ta, tb) } else { (tb, ta) };
+                    let (pa, pb) = (self.point_at(ta), self.point_at(tb));
+
+                    // the solution extrapolated beyond the domain does not meet on the curve
+                    let apart = (&pa - &pb).norm() > eps;
+                    // the adjacent pieces share the end points, as well as the both ends of the closed curve
+                    let joint = ComplexField::abs(ta - tb) < eps;
+                    let duplicated = intersections.iter().any(|x| {
+                        ComplexField::abs(x.a().1 - ta) < eps
+                            && ComplexField::abs(x.b().1 - tb) < eps
+                    });
+                    if !apart && !joint && !duplicated {
+                        intersections
+                            .push(CurveIntersection::new((pa, ta), (pb, tb)).with_kind(*it.kind()));
                     }
                 }
-            })
-            .filter(|it| {
-                // filter out intersections that are too close
-                let p0 = &it.a().0;
-                let p1 = &it.b().0;
-                let d = (p0 - p1).norm();
-                d < options.minimum_distance
-            })
-            .coalesce(|x, y| {
-                // merge intersections that are close in parameter space
-                let da = ComplexField::abs(x.a().1 - y.a().1);
-                let db = ComplexField::abs(x.b().1 - y.b().1);
-                if da < eps || db < eps {
-                    Ok(x)
-                } else {
-                    Err((x, y))
-                }
-            })
-            .collect();
+            }
+        }
+        Ok(intersections)
+    }
 
-        Ok(pts)
+    /// Check if the projection of the curve onto the direction from the first to the last control point increases monotonically, which implies the curve does not cross itself
+    /// The derivative of the rational Bézier curve is a positive combination of the differences of its control points, so the differences of the consecutive control points are tested.
+    fn is_monotone(&self) -> bool
+    where
+        D: DimNameSub<U1>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+    {
+        let points = self.dehomogenized_control_points();
+        let (Some(first), Some(last)) = (points.first(), points.last()) else {
+            return true;
+        };
+        let direction = last - first;
+        points
+            .windows(2)
+            .all(|w| (&w[1] - &w[0]).dot(&direction) > T::zero())
     }
 
     /// Trim the curve into two curves before and after the parameter
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::Point2;
+    /// use approx::assert_relative_eq;
+    ///
+    /// let polyline = NurbsCurve2D::polyline(&[Point2::new(0., 0.), Point2::new(1., 0.), Point2::new(1., 1.)]);
+    ///
+    /// // trim at the existing knot of the corner
+    /// let corner = polyline.knots()[2];
+    /// let (head, tail) = polyline.try_trim(corner).unwrap();
+    /// let (h0, h1) = head.knots_domain();
+    /// let (t0, t1) = tail.knots_domain();
+    /// assert_relative_eq!(head.point_at(h0), Point2::new(0., 0.));
+    /// assert_relative_eq!(head.point_at(h1), Point2::new(1., 0.));
+    /// assert_relative_eq!(tail.point_at(t0), Point2::new(1., 0.));
+    /// assert_relative_eq!(tail.point_at(t1), Point2::new(1., 1.));
+    /// ```
     pub fn try_trim(&self, u: T) -> anyhow::Result<(Self, Self)> {
-        let knots_to_insert: Vec<_> = (0..=self.degree).map(|_| u).collect();
+        // insert the knot until its multiplicity reaches degree + 1
+        let multiplicity = self.knots.iter().filter(|k| **k == u).count();
+        let knots_to_insert: Vec<_> = (multiplicity..=self.degree).map(|_| u).collect();
         let mut cloned = self.clone();
-        cloned.try_refine_knot(knots_to_insert)?;
+        if !knots_to_insert.is_empty() {
+            cloned.try_refine_knot(knots_to_insert)?;
+        }
 
-        let n = self.knots.len() - self.degree - 2;
-        let s = self.knots.find_knot_span_index(n, self.degree, u);
+        // the index of the last control point before the parameter
+        let s = cloned.knots.iter().filter(|k| **k < u).count() - 1;
         let knots0 = cloned.knots.as_slice()[0..=(s + self.degree + 1)].to_vec();
         let knots1 = cloned.knots.as_slice()[s + 1..].to_vec();
         let cpts0 = cloned.control_points[0..=s].to_vec();
@@ -1584,35 +3793,94 @@ where
     }
 
     /// Try to clamp knots of the curve
-    /// Multiplex the start/end part of the knot vector so that the knot has `degree + 1` overlap
+    /// Multiplex the knots at the ends of the knot domain so that the knot has `degree + 1` overlap,
+    /// and drop the parts of the unclamped knot vector outside the domain
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::Point2;
+    /// use approx::assert_relative_eq;
+    ///
+    /// // the periodic interpolation of the ellipse has 12 spans in the unclamped knot domain
+    /// let points: Vec<_> = (0..12).map(|i| {
+    ///     let a = std::f64::consts::TAU * i as f64 / 12.;
+    ///     Point2::new(2. * a.cos(), a.sin())
+    /// }).collect();
+    /// let ellipse = NurbsCurve2D::try_periodic_interpolate(&points, 3, KnotStyle::Centripetal).unwrap();
+    /// assert!(!ellipse.is_clamped());
+    ///
+    /// let mut clamped = ellipse.clone();
+    /// clamped.try_clamp().unwrap();
+    /// assert!(clamped.is_clamped());
+    /// assert_eq!(clamped.knots_domain(), ellipse.knots_domain());
+    /// let (start, end) = ellipse.knots_domain();
+    /// for i in 0..=8 {
+    ///     let t = start + (end - start) * i as f64 / 8.;
+    ///     assert_relative_eq!(clamped.point_at(t), ellipse.point_at(t), epsilon = 1e-10);
+    /// }
+    ///
+    /// // the decomposition yields a segment per span
+    /// let segments = ellipse.try_decompose_bezier_segments().unwrap();
+    /// assert_eq!(segments.len(), 12);
+    ///
+    /// // the offset of the periodic curve is a closed loop
+    /// let offset = ellipse.try_offset(0.2, None).unwrap();
+    /// assert_eq!(offset.len(), 1);
+    /// assert!(offset[0].is_closed(Some(1e-3)));
+    /// offset[0].tessellate(None).iter().for_each(|p| {
+    ///     let closest = ellipse.find_closest_point(p).unwrap();
+    ///     assert_relative_eq!((closest - p).norm(), 0.2, epsilon = 1e-6);
+    /// });
+    /// ```
     pub fn try_clamp(&mut self) -> anyhow::Result<()> {
         let degree = self.degree();
+        let (start, end) = self.knots_domain();
 
-        let start = self.knots.first();
-        let end = self.knots.last();
-        let multiplicity = self.knots.multiplicity();
-        let start_knot_count = multiplicity
-            .iter()
-            .find(|m| *m.knot() == start)
-            .ok_or(anyhow::anyhow!("Start knot not found"))?
-            .multiplicity();
-        let end_knot_count = multiplicity
-            .iter()
-            .find(|m| *m.knot() == end)
-            .ok_or(anyhow::anyhow!("End knot not found"))?
-            .multiplicity();
-
-        for _ in start_knot_count..=degree {
+        // insert the knots at the ends of the domain until the curve passes through a control point there
+        let count = |knots: &KnotVector<T>, u: T| knots.iter().filter(|k| **k == u).count();
+        for _ in count(&self.knots, start)..degree {
             self.try_add_knot(start)?;
         }
-        for _ in end_knot_count..=degree {
+        for _ in count(&self.knots, end)..degree {
             self.try_add_knot(end)?;
         }
 
+        // drop the control points & the knots outside the domain
+        let head = self.knots.iter().filter(|k| **k < start).count() + count(&self.knots, start);
+        let tail = self.knots.iter().filter(|k| **k > end).count() + count(&self.knots, end);
+        let head = head.saturating_sub(degree + 1);
+        let tail = tail.saturating_sub(degree + 1);
+        let knots = self.knots.as_slice();
+        let knots = knots[head..(knots.len() - tail)]
+            .iter()
+            .map(|k| k.max(start).min(end))
+            .collect();
+        self.control_points =
+            self.control_points[head..(self.control_points.len() - tail)].to_vec();
+        self.knots = KnotVector::new(knots);
+
         Ok(())
     }
 
     /// Decompose the curve into Bezier segments
+    /// Each segment has the degree + 1 control points with the end knots of full multiplicity, and keeps the parameters of the original curve.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::Point2;
+    /// use approx::assert_relative_eq;
+    ///
+    /// let points = vec![Point2::new(0., 0.), Point2::new(1., 2.), Point2::new(3., -1.), Point2::new(4., 1.), Point2::new(5., 0.)];
+    /// let curve = NurbsCurve2D::try_interpolate(&points, 3).unwrap();
+    /// let segments = curve.try_decompose_bezier_segments().unwrap();
+    /// assert_eq!(segments.len(), 2);
+    /// for segment in segments.iter() {
+    ///     assert_eq!(segment.control_points().len(), 4);
+    ///     let (start, end) = segment.knots_domain();
+    ///     let t = (start + end) / 2.;
+    ///     assert_relative_eq!(segment.point_at(t), curve.point_at(t), epsilon = 1e-10);
+    /// }
+    /// ```
     pub fn try_decompose_bezier_segments(&self) -> anyhow::Result<Vec<Self>> {
         /*
         anyhow::ensure!(
@@ -1672,6 +3940,15 @@ where
     }
 }
 
+/// Enable to deform a NURBS curve by the deformation of its control points
+impl<T: FloatingPoint> Deformable<T> for NurbsCurve3D<T> {
+    fn deform<F: SpaceDeformation<T>>(&mut self, deformation: &F) {
+        self.control_points
+            .iter_mut()
+            .for_each(|p| deformation.deform_control_point(p));
+    }
+}
+
 /// Enable to transform a NURBS curve by a given DxD matrix
 impl<'a, T: FloatingPoint, const D: usize> Transformable<&'a OMatrix<T, Const<D>, Const<D>>>
     for NurbsCurve<T, Const<D>>
@@ -1724,6 +4001,59 @@ where
 }
 
 impl<T: FloatingPoint> NurbsCurve3D<T> {
+    /// Try to create a helix winding counterclockwise around the axis through the center
+    /// The helix starts at the radius from the center perpendicular to the axis, and rises by the pitch along the axis per turn.
+    /// The helix is approximated by a C1 cubic spline within 1e-6 times the radius, parameterized by the angle around the axis.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    /// use std::f64::consts::TAU;
+    ///
+    /// let helix = NurbsCurve3D::try_helix(&Point3::origin(), &Vector3::z(), 2., 0.5, 4.).unwrap();
+    /// let (start, end) = helix.knots_domain();
+    /// assert_relative_eq!(end - start, 4. * TAU);
+    /// for i in 0..=40 {
+    ///     let t = start + (end - start) * i as f64 / 40.;
+    ///     let p = helix.point_at(t);
+    ///     assert_relative_eq!(p.coords.xy().norm(), 2., epsilon = 1e-5);
+    ///     assert_relative_eq!(p.z, 0.5 * t / TAU, epsilon = 1e-5);
+    /// }
+    /// ```
+    pub fn try_helix(
+        center: &Point3<T>,
+        axis: &Vector3<T>,
+        radius: T,
+        pitch: T,
+        turns: T,
+    ) -> anyhow::Result<Self> {
+        anyhow::ensure!(radius > T::zero(), "The radius must be positive");
+        anyhow::ensure!(turns > T::zero(), "The number of turns must be positive");
+        anyhow::ensure!(
+            axis.norm() > T::default_epsilon(),
+            "The axis must not be zero"
+        );
+        let axis = axis.normalize();
+        let x = initial_normal(&axis);
+        let y = axis.cross(&x);
+        let rise = pitch / T::two_pi();
+        // the linear rise along the axis has no fourth derivative
+        try_hermite_spline(
+            |t| {
+                let (sin, cos) = t.sin_cos();
+                (
+                    center + (x * cos + y * sin) * radius + axis * (rise * t),
+                    (y * cos - x * sin) * radius + axis * rise,
+                )
+            },
+            T::zero(),
+            turns * T::two_pi(),
+            radius,
+            radius * T::from_f64(1e-6).unwrap(),
+        )
+    }
+
     /// Compute the Frenet frames of the curve at given parameters
     /// based on the method described in the paper: http://www.cs.indiana.edu/pub/techreports/TR425.pdf
     pub fn compute_frenet_frames(&self, parameters: &[T]) -> Vec<FrenetFrame<T>> {
@@ -1731,30 +4061,8 @@ impl<T: FloatingPoint> NurbsCurve3D<T> {
             .iter()
             .map(|u| self.tangent_at(*u).normalize())
             .collect();
-        let mut normals = vec![Vector3::zeros()];
-        let mut binormals = vec![Vector3::zeros()];
-
-        let mut normal = Vector3::zeros();
-        let tx = tangents[0].x.abs();
-        let ty = tangents[0].y.abs();
-        let tz = tangents[0].z.abs();
-
-        let mut min = T::max_value().unwrap();
-        if tx <= min {
-            min = tx;
-            normal = Vector3::x();
-        }
-        if ty <= min {
-            min = ty;
-            normal = Vector3::y();
-        }
-        if tz <= min {
-            normal = Vector3::z();
-        }
-
-        let v = tangents[0].cross(&normal).normalize();
-        normals[0] = tangents[0].cross(&v).normalize();
-        binormals[0] = tangents[0].cross(&normals[0]).normalize();
+        let mut normals = vec![initial_normal(&tangents[0])];
+        let mut binormals = vec![tangents[0].cross(&normals[0]).normalize()];
 
         for i in 1..parameters.len() {
             let prev_normal = &normals[i - 1];
@@ -1783,78 +4091,450 @@ impl<T: FloatingPoint> NurbsCurve3D<T> {
             })
             .collect()
     }
-}
 
-/// Find the curve parameter at arc length on a Bezier segment of a NURBS curve
-/// by binary search
-fn compute_bezier_segment_parameter_at_length<T: FloatingPoint, D>(
-    s: &NurbsCurve<T, D>,
-    length: T,
-    tolerance: T,
-    total_length: T,
-    gauss: &GaussLegendre,
-) -> T
-where
-    D: DimName + DimNameSub<U1>,
-    DefaultAllocator: Allocator<D>,
-    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
-{
-    let (k0, k1) = s.knots_domain();
-    if length < T::zero() {
-        return k0;
-    } else if length > total_length {
-        return k1;
-    }
+    /// Compute the rotation minimizing frames of the curve at given parameters
+    /// by the double reflection method described in the paper: https://doi.org/10.1145/1330511.1330513
+    /// The frames do not flip at the inflection points unlike the Frenet frames.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::Point3;
+    /// use approx::assert_relative_eq;
+    ///
+    /// // a planar curve with an inflection point
+    /// let points = vec![
+    ///     Point3::new(0., 0., 0.),
+    ///     Point3::new(1., 1., 0.),
+    ///     Point3::new(2., 0., 0.),
+    ///     Point3::new(3., -1., 0.),
+    ///     Point3::new(4., 0., 0.),
+    /// ];
+    /// let curve = NurbsCurve3D::try_interpolate(&points, 3).unwrap();
+    /// let parameters = (0..=32).map(|i| i as f64 / 32.).collect::<Vec<_>>();
+    /// let frames = curve.compute_rotation_minimizing_frames(&parameters);
+    /// assert_eq!(frames.len(), parameters.len());
+    ///
+    /// // the normal stays perpendicular to the plane of the curve
+    /// for frame in frames.iter() {
+    ///     assert_relative_eq!(frame.normal().dot(frame.tangent()), 0., epsilon = 1e-10);
+    ///     assert_relative_eq!(frame.normal().dot(frames[0].normal()), 1., epsilon = 1e-10);
+    /// }
+    ///
+    /// // the binormal of the Frenet frame flips at the inflection point
+    /// let head = curve.try_frenet_frame(0.1).unwrap();
+    /// let tail = curve.try_frenet_frame(0.9).unwrap();
+    /// assert!(head.frame().binormal().dot(tail.frame().binormal()) < 0.);
+    /// ```
+    pub fn compute_rotation_minimizing_frames(&self, parameters: &[T]) -> Vec<FrenetFrame<T>> {
+        if parameters.is_empty() {
+            return vec![];
+        }
 
-    let mut start = (k0, T::zero());
-    let mut end = (k1, total_length);
+        let positions: Vec<_> = parameters.iter().map(|u| self.point_at(*u)).collect();
+        let tangents: Vec<_> = parameters
+            .iter()
+            .map(|u| self.tangent_at(*u).normalize())
+            .collect();
+        let two = T::from_f64(2.).unwrap();
+        let eps = T::default_epsilon();
+
+        let mut normals = vec![initial_normal(&tangents[0])];
+        for i in 0..parameters.len() - 1 {
+            let r = &normals[i];
+
+            // reflect the frame by the bisecting plane of the positions
+            let v1 = positions[i + 1] - positions[i];
+            let c1 = v1.dot(&v1);
+            if c1 <= eps {
+                normals.push(*r);
+                continue;
+            }
+            let rl = r - v1 * (two / c1 * v1.dot(r));
+            let tl = tangents[i] - v1 * (two / c1 * v1.dot(&tangents[i]));
+
+            // reflect the frame again to align the tangent
+            let v2 = tangents[i + 1] - tl;
+            let c2 = v2.dot(&v2);
+            let next = if c2 <= eps {
+                rl
+            } else {
+                rl - v2 * (two / c2 * v2.dot(&rl))
+            };
+            normals.push(next.normalize());
+        }
 
-    let inv = T::one() / T::from_usize(2).unwrap();
+        positions
+            .into_iter()
+            .zip(tangents)
+            .zip(normals)
+            .map(|((position, tangent), normal)| {
+                let binormal = tangent.cross(&normal).normalize();
+                FrenetFrame::new(position, tangent, normal, binormal)
+            })
+            .collect()
+    }
 
-    // binary search
-    while (end.1 - start.1) > tolerance {
-        let middle_parameter = (start.0 + end.0) * inv;
-        let mid = (
-            middle_parameter,
-            compute_bezier_segment_length(s, middle_parameter, gauss),
+    /// Evaluate the Frenet frame with the curvature & the torsion at the parameter
+    /// The normal points toward the center of the curvature, so the frame is undefined where the curvature vanishes.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let circle = NurbsCurve3D::try_circle(&Point3::origin(), &Vector3::x(), &Vector3::y(), 2.).unwrap();
+    /// let frame = circle.try_frenet_frame(0.3).unwrap();
+    /// assert_relative_eq!(frame.curvature(), 0.5, epsilon = 1e-10);
+    /// assert_relative_eq!(frame.torsion(), 0., epsilon = 1e-10);
+    /// assert_relative_eq!(*frame.frame().normal(), -frame.frame().position().coords / 2., epsilon = 1e-10);
+    /// assert_relative_eq!(*frame.frame().binormal(), Vector3::z(), epsilon = 1e-10);
+    ///
+    /// // the straight line has no Frenet frame
+    /// let line = NurbsCurve3D::polyline(&[Point3::origin(), Point3::new(1., 0., 0.)]);
+    /// assert!(line.try_frenet_frame(0.5).is_err());
+    /// ```
+    pub fn try_frenet_frame(&self, t: T) -> anyhow::Result<CurvatureFrame<T>> {
+        let derivs = self.rational_derivatives(t, 3);
+        let (d1, d2, d3) = (&derivs[1], &derivs[2], &derivs[3]);
+        let speed = d1.norm();
+        anyhow::ensure!(
+            speed > T::default_epsilon(),
+            "The tangent vanishes at the parameter {}",
+            t
         );
-        if mid.1 > length {
-            end = mid;
-        } else {
-            start = mid;
+        let b = d1.cross(d2);
+        let bn = b.norm();
+        anyhow::ensure!(
+            bn > T::default_epsilon() * speed * speed,
+            "The curvature vanishes at the parameter {}",
+            t
+        );
+        let tangent = d1 / speed;
+        let binormal = b / bn;
+        let normal = binormal.cross(&tangent);
+        let curvature = bn / (speed * speed * speed);
+        let torsion = b.dot(d3) / (bn * bn);
+        Ok(CurvatureFrame::new(
+            FrenetFrame::new(self.point_at(t), tangent, normal, binormal),
+            curvature,
+            torsion,
+        )
+        .with_derivatives([*d1, *d2, *d3]))
+    }
+
+    /// Evaluate the Frenet frames with the curvatures & the torsions at the parameters
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// // a helix of the radius 1 & the pitch 2π has the curvature & the torsion of 1/2
+    /// let points = (0..=64)
+    ///     .map(|i| {
+    ///         let t = i as f64 / 64. * std::f64::consts::TAU;
+    ///         Point3::new(t.cos(), t.sin(), t)
+    ///     })
+    ///     .collect::<Vec<_>>();
+    /// let helix = NurbsCurve3D::try_interpolate(&points, 5).unwrap();
+    /// let frames = helix.try_frenet_frames(&[0.3, 0.5, 0.7]).unwrap();
+    /// for frame in frames {
+    ///     assert_relative_eq!(frame.curvature(), 0.5, epsilon = 1e-3);
+    ///     assert_relative_eq!(frame.torsion(), 0.5, epsilon = 1e-2);
+    /// }
+    /// ```
+    pub fn try_frenet_frames(&self, parameters: &[T]) -> anyhow::Result<Vec<CurvatureFrame<T>>> {
+        parameters
+            .iter()
+            .map(|t| self.try_frenet_frame(*t))
+            .collect()
+    }
+
+    /// Evaluate the Frenet frames with the curvatures & the torsions at the samples dividing the range of the parameter uniformly
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let circle = NurbsCurve3D::try_circle(&Point3::origin(), &Vector3::x(), &Vector3::y(), 2.).unwrap();
+    /// let (start, end) = circle.knots_domain();
+    /// let frames = circle.try_frenet_frames_between(start, end, 8).unwrap();
+    /// assert_eq!(frames.len(), 8);
+    /// for frame in frames {
+    ///     // the curvature of the circle is constant
+    ///     assert_relative_eq!(frame.curvature_derivative(), 0., epsilon = 1e-8);
+    ///     assert_relative_eq!(frame.speed(), frame.derivatives()[0].norm());
+    /// }
+    /// ```
+    pub fn try_frenet_frames_between(
+        &self,
+        start: T,
+        end: T,
+        samples: usize,
+    ) -> anyhow::Result<Vec<CurvatureFrame<T>>> {
+        self.try_frenet_frames(&uniform_parameters(start, end, samples))
+    }
+
+    /// Evaluate the torsion at the parameter, which is zero where the curvature vanishes
+    /// The torsion is positive where the curve twists like the right handed helix.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let helix = NurbsCurve3D::try_helix(&Point3::origin(), &Vector3::z(), 1., std::f64::consts::TAU, 2.).unwrap();
+    /// let (start, end) = helix.knots_domain();
+    /// assert_relative_eq!(helix.torsion_at((start + end) / 2.), 0.5, epsilon = 1e-2);
+    ///
+    /// let line = NurbsCurve3D::polyline(&[Point3::origin(), Point3::new(1., 0., 0.)]);
+    /// assert_eq!(line.torsion_at(0.5), 0.);
+    /// ```
+    pub fn torsion_at(&self, t: T) -> T {
+        let derivs = self.rational_derivatives(t, 3);
+        let b = derivs[1].cross(&derivs[2]);
+        let bn = b.norm_squared();
+        if bn <= T::default_epsilon() * derivs[1].norm_squared().powi(2) {
+            return T::zero();
         }
+        b.dot(&derivs[3]) / bn
+    }
+
+    /// Sample the torsions at the parameters dividing the range uniformly
+    /// Returns the parameter & the torsion for each sample.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Matrix4, Point3, Vector3, Vector4};
+    /// use approx::assert_relative_eq;
+    ///
+    /// // the left handed helix mirrored from the right handed one has the negative torsion
+    /// let helix = NurbsCurve3D::try_helix(&Point3::origin(), &Vector3::z(), 1., std::f64::consts::TAU, 2.).unwrap();
+    /// let helix = helix.transformed(&Matrix4::from_diagonal(&Vector4::new(1., 1., -1., 1.)));
+    /// let (start, end) = helix.knots_domain();
+    /// let profile = helix.torsion_profile(start + 0.5, end - 0.5, 5);
+    /// assert_eq!(profile.len(), 5);
+    /// assert_relative_eq!(profile[0].0, start + 0.5);
+    /// for (_, torsion) in profile {
+    ///     assert!(torsion < 0.);
+    /// }
+    /// ```
+    pub fn torsion_profile(&self, start: T, end: T, samples: usize) -> Vec<(T, T)> {
+        uniform_parameters(start, end, samples)
+            .into_iter()
+            .map(|t| (t, self.torsion_at(t)))
+            .collect()
     }
 
-    (start.0 + end.0) * inv
+    /// Find the intersection points with a surface by newton method
+    /// * `surface` - The surface to intersect with
+    /// * `options` - Hyperparameters for the intersection solver
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// // A plane at z = 0
+    /// let line = NurbsCurve3D::polyline(&[Point3::new(-2., -2., 0.), Point3::new(2., -2., 0.)]);
+    /// let plane = NurbsSurface::extrude(&line, &(Vector3::y() * 4.));
+    ///
+    /// // A circle piercing the plane twice
+    /// let circle = NurbsCurve3D::try_circle(&Point3::origin(), &Vector3::x(), &Vector3::z(), 1.).unwrap();
+    ///
+    /// let mut intersections = circle.find_surface_intersections(&plane, None).unwrap();
+    /// assert_eq!(intersections.len(), 2);
+    ///
+    /// intersections.sort_by(|i0, i1| i0.curve().0.x.partial_cmp(&i1.curve().0.x).unwrap());
+    /// assert_relative_eq!(intersections[0].curve().0, Point3::new(-1., 0., 0.), epsilon = 1e-5);
+    /// assert_relative_eq!(intersections[1].curve().0, Point3::new(1., 0., 0.), epsilon = 1e-5);
+    ///
+    /// // The (u, v) parameter on the surface is also included
+    /// let (u, v) = intersections[0].surface().1;
+    /// assert_relative_eq!(plane.point_at(u, v), Point3::new(-1., 0., 0.), epsilon = 1e-5);
+    ///
+    /// // The intersections close in space but distinct in parameter are kept
+    /// let hairpin = NurbsCurve3D::polyline(&[Point3::new(0., 0., 1.), Point3::new(0., 0., -1.), Point3::new(2e-6, 0., 1.)]);
+    /// let intersections = hairpin.find_surface_intersections(&plane, None).unwrap();
+    /// assert_eq!(intersections.len(), 2);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn find_surface_intersections(
+        &self,
+        surface: &NurbsSurface3D<T>,
+        options: Option<CurveSurfaceIntersectionSolverOptions<T>>,
+    ) -> anyhow::Result<Vec<CurveSurfaceIntersection<Point3<T>, T>>> {
+        let options = options.unwrap_or_default();
+        let div = T::from_usize(options.knot_domain_division).unwrap();
+
+        let (u0, u1) = surface.u_knots_domain();
+        let (v0, v1) = surface.v_knots_domain();
+        let traversed = BoundingBoxTraversal::try_traverse_hierarchies(
+            BoundingBoxTree::new(self, Some(self.knots_domain_interval() / div)),
+            SurfaceBoundingBoxTree::new(surface, Some(((u1 - u0) / div, (v1 - v0) / div))),
+        )?;
+
+        let (t0, t1) = self.knots_domain();
+        let clamp = |x: Vector3<T>| {
+            Vector3::new(
+                nalgebra::clamp(x.x, t0, t1),
+                nalgebra::clamp(x.y, u0, u1),
+                nalgebra::clamp(x.z, v0, v1),
+            )
+        };
+
+        let inv = T::from_f64(0.5).unwrap();
+        let mut intersections: Vec<CurveSurfaceIntersection<Point3<T>, T>> = traversed
+            .into_pairs_iter()
+            .filter_map(|(a, b)| {
+                let (ct0, ct1) = a.curve().knots_domain();
+                let (su0, su1) = b.surface().u_knots_domain();
+                let (sv0, sv1) = b.surface().v_knots_domain();
+                let mut x = Vector3::new((ct0 + ct1) * inv, (su0 + su1) * inv, (sv0 + sv1) * inv);
+
+                // Solve C(t) - S(u, v) = 0 by newton method
+                for _ in 0..options.max_iters {
+                    let c = self.rational_derivatives(x.x, 1);
+                    let s = surface.rational_derivatives(x.y, x.z, 1);
+                    let f = c[0] - s[0][0];
+                    if f.norm() < options.minimum_distance {
+                        let pc = self.point_at(x.x);
+                        let ps = surface.point_at(x.y, x.z);
+                        return Some(CurveSurfaceIntersection::new((pc, x.x), (ps, (x.y, x.z))));
+                    }
+                    let jacobian = Matrix3::from_columns(&[c[1], -s[1][0], -s[0][1]]);
+                    let delta = jacobian.lu().solve(&f)?;
+                    x = clamp(x - delta);
+                }
+                None
+            })
+            .collect();
+
+        intersections.sort_by(|a, b| {
+            a.curve()
+                .1
+                .partial_cmp(&b.curve().1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        // merge intersections found in the adjacent segments by the closeness in parameter space
+        let interval = t1 - t0;
+        let eps = interval * options.parameter_tolerance;
+        // the start & end parameters of the closed curve are the same point
+        let closed = (self.point_at(t0) - self.point_at(t1)).norm() < options.minimum_distance;
+        let parameter_distance = |a: T, b: T| {
+            let d = ComplexField::abs(a - b);
+            if closed {
+                d.min(interval - d)
+            } else {
+                d
+            }
+        };
+        let mut merged: Vec<CurveSurfaceIntersection<Point3<T>, T>> = vec![];
+        for it in intersections.into_iter() {
+            let duplicated = merged
+                .iter()
+                .any(|m| parameter_distance(m.curve().1, it.curve().1) < eps);
+            if !duplicated {
+                merged.push(it);
+            }
+        }
+
+        Ok(merged)
+    }
 }
 
-/// Compute the length of a Bezier segment of a NURBS curve
-/// by gauss-legendre quadrature
-fn compute_bezier_segment_length<T: FloatingPoint, D>(
-    s: &NurbsCurve<T, D>,
-    u: T,
-    gauss: &GaussLegendre,
-) -> T
+/// Approximate the curve by the C1 cubic spline interpolating the points & the derivatives at the uniformly spaced parameters
+/// The spans are refined until the error bound of the cubic Hermite interpolation `h^4 / 384 * bound` falls within the tolerance,
+/// where `bound` is the maximum norm of the fourth derivative of the curve.
+fn try_hermite_spline<T, D, F>(
+    evaluate: F,
+    start: T,
+    end: T,
+    bound: T,
+    tolerance: T,
+) -> anyhow::Result<NurbsCurve<T, D>>
 where
+    T: FloatingPoint,
     D: DimName + DimNameSub<U1>,
     DefaultAllocator: Allocator<D>,
     DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+    F: Fn(
+        T,
+    ) -> (
+        OPoint<T, DimNameDiff<D, U1>>,
+        OVector<T, DimNameDiff<D, U1>>,
+    ),
 {
-    let (start, end) = s.knots_domain();
-    if start + T::default_epsilon() < u {
-        let t = end.min(u);
-        let left = start.to_f64().unwrap();
-        let right = t.to_f64().unwrap();
-        let sum = gauss.integrate(left, right, |x| {
-            let x = T::from_f64(x).unwrap();
-            let deriv = s.rational_derivatives(x, 1);
-            let tan = deriv[1].norm();
-            tan.to_f64().unwrap()
-        });
-        T::from_f64(sum).unwrap()
-    } else {
-        T::zero()
+    anyhow::ensure!(tolerance > T::zero(), "The tolerance must be positive");
+    let step = (T::from_usize(384).unwrap() * tolerance / bound.max(T::default_epsilon()))
+        .powf(T::from_f64(0.25).unwrap());
+    let segments = ((end - start) / step).ceil().to_usize().unwrap_or(1).max(1);
+    let h = (end - start) / T::from_usize(segments).unwrap();
+    let third = h / T::from_usize(3).unwrap();
+
+    // the inner bezier points either side of the junctions are shared by the double knots
+    let (first, d) = evaluate(start);
+    let mut points = vec![first.clone(), &first + &d * third];
+    for i in 1..segments {
+        let (p, d) = evaluate(start + h * T::from_usize(i).unwrap());
+        points.push(&p - &d * third);
+        points.push(&p + &d * third);
+    }
+    let (last, d) = evaluate(end);
+    points.push(&last - &d * third);
+    points.push(last);
+
+    let mut knots = vec![start; 4];
+    for i in 1..segments {
+        let k = start + h * T::from_usize(i).unwrap();
+        knots.push(k);
+        knots.push(k);
+    }
+    knots.extend(vec![end; 4]);
+
+    let control_points = points
+        .iter()
+        .map(|p| {
+            let mut coords = p.coords.iter().copied().collect::<Vec<_>>();
+            coords.push(T::one());
+            OPoint::from_slice(&coords)
+        })
+        .collect();
+    NurbsCurve::try_new(3, control_points, knots)
+}
+
+/// Choose the normal perpendicular to the tangent from the axis least aligned with the tangent
+pub(crate) fn initial_normal<T: FloatingPoint>(tangent: &Vector3<T>) -> Vector3<T> {
+    let mut normal = Vector3::zeros();
+    let tx = tangent.x.abs();
+    let ty = tangent.y.abs();
+    let tz = tangent.z.abs();
+
+    let mut min = T::max_value().unwrap();
+    if tx <= min {
+        min = tx;
+        normal = Vector3::x();
+    }
+    if ty <= min {
+        min = ty;
+        normal = Vector3::y();
     }
+    if tz <= min {
+        normal = Vector3::z();
+    }
+
+    let v = tangent.cross(&normal).normalize();
+    tangent.cross(&v).normalize()
+}
+
+/// Divide the range of the parameter uniformly into the samples including both ends, at least two
+fn uniform_parameters<T: FloatingPoint>(start: T, end: T, samples: usize) -> Vec<T> {
+    let n = samples.max(2) - 1;
+    (0..=n)
+        .map(|i| start + (end - start) * T::from_usize(i).unwrap() / T::from_usize(n).unwrap())
+        .collect()
 }
 
 /// Dehomogenize a point
@@ -1955,68 +4635,52 @@ pub fn try_periodic_interpolate_control_points<T: FloatingPoint>(
         anyhow::bail!("Too few control points for curve");
     }
 
-    // build periodic knot vector
-    let parameters = knot_style.parameterize(points, true);
-
-    let head = &parameters[0..(degree - 1)];
-    let tail = &parameters[(parameters.len() - degree + 1)..];
-    let start_parameters = tail.to_vec();
-
-    let knots = [start_parameters, parameters.clone(), head.to_vec()].concat();
-
-    let m = points.len() + degree;
-
-    let knots = [
-        vec![T::zero()],
-        knots
-            .iter()
-            .scan(T::zero(), |p, x| {
-                *p += *x;
-                Some(*p)
-            })
-            .collect::<Vec<_>>(),
-    ]
-    .concat();
-
-    // translate knot vectors to fit NURBS requirements
-    let k0 = if degree > 2 {
-        knots[0] - (knots[m + 1] - knots[m])
-    } else {
-        knots[0]
-    };
-    let k1 = if degree > 2 {
-        knots[knots.len() - 1] + (knots[degree + 1] - knots[degree])
-    } else {
-        knots[knots.len() - 1]
+    // the parameters of the points around the loop
+    let intervals = knot_style.parameterize(points, true);
+    anyhow::ensure!(
+        intervals.iter().all(|d| *d > T::zero()),
+        "The consecutive points must not coincide"
+    );
+    let mut parameters = vec![T::zero()];
+    for d in intervals.iter() {
+        let last = parameters[parameters.len() - 1];
+        parameters.push(last + *d);
+    }
+    let period = parameters[n];
+
+    // the knots coincide with the parameters for an odd degree,
+    // and lie midway between them for an even degree to keep the system well conditioned
+    let half = T::from_f64(0.5).unwrap();
+    let base = (0..n)
+        .map(|i| {
+            if degree % 2 == 1 {
+                parameters[i]
+            } else {
+                (parameters[i] + parameters[i + 1]) * half
+            }
+        })
+        .collect::<Vec<_>>();
+    let knot = |j: isize| {
+        let n = n as isize;
+        let turns = j.div_euclid(n);
+        base[j.rem_euclid(n) as usize] + period * T::from_isize(turns).unwrap()
     };
-    let knots = [vec![k0], knots, vec![k1]].concat();
-
-    let knots_vec = KnotVector::new(knots.clone());
-    let plen = points.len();
+    let knots_vec = KnotVector::new(
+        (0..(n + 2 * degree + 1))
+            .map(|j| knot(j as isize - degree as isize))
+            .collect(),
+    );
 
     // build basis function coefficients matrix
-    let mut m_a = DMatrix::<T>::zeros(plen, plen);
-    let zero_pad = vec![T::zero(); plen - (degree + 1)];
-
-    let n = knots_vec.len() - degree - 2;
-    for i in 0..plen {
-        let u = knots[i + degree]; // from start domain
-        let knot_span_index = knots_vec.find_knot_span_index(n, degree, u);
-
+    // the control points loop, so the columns of the duplicated control points are folded into the first ones
+    let mut m_a = DMatrix::<T>::zeros(n, n);
+    let (start, _) = knots_vec.domain(degree);
+    for (i, t) in parameters.iter().take(n).enumerate() {
+        let u = if *t < start { *t + period } else { *t };
+        let knot_span_index = knots_vec.find_knot_span_index(n + degree - 1, degree, u);
         let basis = knots_vec.basis_functions(knot_span_index, u, degree);
-        let basis_padded = [basis, zero_pad.clone()].concat();
-
-        let ls = knot_span_index - degree;
-
-        // In a closed periodic NURBS curve, the control points loop.
-        // The solver only solves for the control points that are not looping,
-        // so the coefficient matrix is looped to represent the duplicated control points.
-        let n = basis_padded.len() - ls;
-        let mut e = basis_padded[n..].to_vec();
-        e.extend_from_slice(&basis_padded[0..n]);
-
-        for j in 0..e.len() {
-            m_a[(i, j)] = e[j];
+        for (k, b) in basis.into_iter().enumerate() {
+            m_a[(i, (knot_span_index - degree + k) % n)] += b;
         }
     }
 
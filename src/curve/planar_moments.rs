@@ -0,0 +1,288 @@
+use gauss_quad::GaussLegendre;
+use nalgebra::{Matrix2, Point2, Vector2};
+
+use crate::{
+    curve::{CompoundCurve2D, NurbsCurve2D},
+    misc::FloatingPoint,
+};
+
+/// The maximum depth of the subdivision of the spans in the quadrature
+const MAX_DEPTH: usize = 24;
+
+/// The area, the first & the second moments of a planar region
+/// The moments of the region bounded by a curve are signed, positive if the curve turns counterclockwise.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PlanarMoments<T: FloatingPoint> {
+    area: T,
+    /// The integrals of x & y over the region
+    first: Vector2<T>,
+    /// The integrals of x^2, y^2 & xy over the region
+    second: (T, T, T),
+}
+
+impl<T: FloatingPoint> PlanarMoments<T> {
+    pub fn new(area: T, first: Vector2<T>, second: (T, T, T)) -> Self {
+        Self {
+            area,
+            first,
+            second,
+        }
+    }
+
+    pub fn area(&self) -> T {
+        self.area
+    }
+
+    /// The integrals of x & y over the region
+    pub fn first_moments(&self) -> &Vector2<T> {
+        &self.first
+    }
+
+    /// The integrals of x^2, y^2 & xy over the region about the origin
+    pub fn second_moments(&self) -> (T, T, T) {
+        self.second
+    }
+
+    pub fn centroid(&self) -> Point2<T> {
+        (self.first / self.area).into()
+    }
+
+    /// The integrals of x^2, y^2 & xy over the region about the centroid by the parallel axis theorem
+    pub fn centroid_second_moments(&self) -> (T, T, T) {
+        let c = self.centroid();
+        let (xx, yy, xy) = self.second;
+        (
+            xx - self.area * c.x * c.x,
+            yy - self.area * c.y * c.y,
+            xy - self.area * c.x * c.y,
+        )
+    }
+
+    /// The polar moment about the centroid
+    pub fn polar_moment(&self) -> T {
+        let (xx, yy, _) = self.centroid_second_moments();
+        xx + yy
+    }
+
+    /// The principal second moments about the centroid in the descending order,
+    /// with the angle from the x axis of the direction along which the larger one is measured
+    pub fn principal_moments(&self) -> (T, T, T) {
+        let (xx, yy, xy) = self.centroid_second_moments();
+        let eigen = Matrix2::new(xx, xy, xy, yy).symmetric_eigen();
+        let (i, j) = if eigen.eigenvalues[0] >= eigen.eigenvalues[1] {
+            (0, 1)
+        } else {
+            (1, 0)
+        };
+        let axis = eigen.eigenvectors.column(i);
+        (
+            eigen.eigenvalues[i],
+            eigen.eigenvalues[j],
+            axis[1].atan2(axis[0]),
+        )
+    }
+
+    /// The moments of the region with the opposite orientation
+    pub fn negated(&self) -> Self {
+        let (xx, yy, xy) = self.second;
+        Self::new(-self.area, -self.first, (-xx, -yy, -xy))
+    }
+
+    /// The moments of the union of the disjoint regions
+    pub fn sum(&self, other: &Self) -> Self {
+        Self::new(
+            self.area + other.area,
+            self.first + other.first,
+            (
+                self.second.0 + other.second.0,
+                self.second.1 + other.second.1,
+                self.second.2 + other.second.2,
+            ),
+        )
+    }
+
+    /// The moments of the counterclockwise orientation with the positive area
+    pub(crate) fn unsigned(&self) -> Self {
+        if self.area < T::zero() {
+            self.negated()
+        } else {
+            *self
+        }
+    }
+}
+
+/// The integrands of the Green's theorem for the area, the first & the second moments at the parameter of the curve
+/// Each is the integral of the form Q dy - P dx whose curl is the density of the moment.
+fn integrands<T: FloatingPoint>(curve: &NurbsCurve2D<T>, t: T) -> [T; 6] {
+    let derivs = curve.rational_derivatives(t, 1);
+    let (p, d) = (&derivs[0], &derivs[1]);
+    let (x, y) = (p.x, p.y);
+    let (dx, dy) = (d.x, d.y);
+    let half = T::from_f64(0.5).unwrap();
+    let third = T::one() / T::from_usize(3).unwrap();
+    [
+        (x * dy - y * dx) * half,
+        x * x * dy * half,
+        -y * y * dx * half,
+        x * x * x * dy * third,
+        -y * y * y * dx * third,
+        x * x * y * dy * half,
+    ]
+}
+
+/// Integrate the vector of the integrands over the interval by the Gauss-Legendre quadrature
+fn quadrature<T: FloatingPoint>(
+    curve: &NurbsCurve2D<T>,
+    gauss: &GaussLegendre,
+    a: T,
+    b: T,
+) -> [T; 6] {
+    let half = (b - a) * T::from_f64(0.5).unwrap();
+    let mid = (a + b) * T::from_f64(0.5).unwrap();
+    gauss
+        .as_node_weight_pairs()
+        .iter()
+        .fold([T::zero(); 6], |mut acc, &(x, w)| {
+            let w = T::from_f64(w).unwrap() * half;
+            let f = integrands(curve, mid + half * T::from_f64(x).unwrap());
+            acc.iter_mut().zip(f.iter()).for_each(|(a, f)| *a += *f * w);
+            acc
+        })
+}
+
+#[allow(clippy::too_many_arguments)]
+fn subdivide<T: FloatingPoint>(
+    curve: &NurbsCurve2D<T>,
+    gauss: &GaussLegendre,
+    a: T,
+    b: T,
+    whole: [T; 6],
+    tolerance: T,
+    depth: usize,
+    sum: &mut [T; 6],
+) {
+    let mid = (a + b) * T::from_f64(0.5).unwrap();
+    let left = quadrature(curve, gauss, a, mid);
+    let right = quadrature(curve, gauss, mid, b);
+    let error = (0..6).fold(T::zero(), |acc, i| {
+        acc.max((left[i] + right[i] - whole[i]).abs())
+    });
+    if error <= tolerance || depth >= MAX_DEPTH {
+        (0..6).for_each(|i| sum[i] += left[i] + right[i]);
+    } else {
+        let half = tolerance * T::from_f64(0.5).unwrap();
+        subdivide(curve, gauss, a, mid, left, half, depth + 1, sum);
+        subdivide(curve, gauss, mid, b, right, half, depth + 1, sum);
+    }
+}
+
+/// Integrate the moments along the curve span by span, subdividing the spans adaptively within the tolerance
+fn try_integrate<T: FloatingPoint>(
+    curve: &NurbsCurve2D<T>,
+    tolerance: T,
+) -> anyhow::Result<[T; 6]> {
+    anyhow::ensure!(
+        tolerance > T::zero(),
+        "The tolerance must be greater than zero"
+    );
+    let gauss = GaussLegendre::new(8)?;
+    let (start, end) = curve.knots_domain();
+    let domain = end - start;
+
+    // the rational spans are smooth between the distinct knots
+    let mut breaks = curve
+        .knots()
+        .iter()
+        .filter(|k| **k > start && **k < end)
+        .copied()
+        .collect::<Vec<_>>();
+    breaks.dedup();
+    breaks.insert(0, start);
+    breaks.push(end);
+
+    let mut sum = [T::zero(); 6];
+    for w in breaks.windows(2) {
+        let (a, b) = (w[0], w[1]);
+        let whole = quadrature(curve, &gauss, a, b);
+        subdivide(
+            curve,
+            &gauss,
+            a,
+            b,
+            whole,
+            tolerance * (b - a) / domain,
+            0,
+            &mut sum,
+        );
+    }
+    Ok(sum)
+}
+
+fn moments<T: FloatingPoint>(sum: [T; 6]) -> PlanarMoments<T> {
+    PlanarMoments::new(
+        sum[0],
+        Vector2::new(sum[1], sum[2]),
+        (sum[3], sum[4], sum[5]),
+    )
+}
+
+impl<T: FloatingPoint> NurbsCurve2D<T> {
+    /// Try to compute the signed moments of the region bounded by the closed curve by the Green's theorem
+    /// The integrals along the rational spans are evaluated by the adaptive Gauss-Legendre quadrature within the tolerance.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point2, Vector2};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let pi = std::f64::consts::PI;
+    /// let circle = NurbsCurve2D::try_circle(&Point2::new(1., 1.), &Vector2::x(), &Vector2::y(), 2.).unwrap();
+    /// let moments = circle.try_planar_moments(1e-10).unwrap();
+    /// assert_relative_eq!(moments.area(), pi * 4., epsilon = 1e-8);
+    /// assert_relative_eq!(moments.centroid(), Point2::new(1., 1.), epsilon = 1e-8);
+    /// // the second moment of the disk about its diameter is pi r^4 / 4
+    /// let (xx, yy, xy) = moments.centroid_second_moments();
+    /// assert_relative_eq!(xx, pi * 4., epsilon = 1e-8);
+    /// assert_relative_eq!(yy, pi * 4., epsilon = 1e-8);
+    /// assert_relative_eq!(xy, 0., epsilon = 1e-8);
+    ///
+    /// // the rectangle turning clockwise has the negative area
+    /// let rectangle = NurbsCurve2D::<f64>::polyline(&[
+    ///     Point2::new(0., 0.),
+    ///     Point2::new(0., 1.),
+    ///     Point2::new(3., 1.),
+    ///     Point2::new(3., 0.),
+    ///     Point2::new(0., 0.),
+    /// ]);
+    /// let moments = rectangle.try_planar_moments(1e-10).unwrap();
+    /// assert_relative_eq!(moments.area(), -3., epsilon = 1e-10);
+    /// assert_relative_eq!(moments.centroid(), Point2::new(1.5, 0.5), epsilon = 1e-10);
+    /// // the rectangle spreads the most along the x axis
+    /// let (major, minor, angle) = moments.negated().principal_moments();
+    /// assert_relative_eq!(major, 27. / 12., epsilon = 1e-10);
+    /// assert_relative_eq!(minor, 3. / 12., epsilon = 1e-10);
+    /// assert_relative_eq!(angle.sin(), 0., epsilon = 1e-8);
+    /// ```
+    pub fn try_planar_moments(&self, tolerance: T) -> anyhow::Result<PlanarMoments<T>> {
+        let (start, end) = self.knots_domain();
+        anyhow::ensure!(
+            (self.point_at(end) - self.point_at(start)).norm() < T::from_f64(1e-5).unwrap(),
+            "The curve must be closed"
+        );
+        try_integrate(self, tolerance).map(moments)
+    }
+}
+
+impl<T: FloatingPoint> CompoundCurve2D<T> {
+    /// Try to compute the signed moments of the region bounded by the closed compound curve by the Green's theorem
+    /// The tolerance is shared among the spans in proportion to their numbers.
+    pub fn try_planar_moments(&self, tolerance: T) -> anyhow::Result<PlanarMoments<T>> {
+        anyhow::ensure!(self.is_closed(None), "The curve must be closed");
+        let n = T::from_usize(self.spans().len()).unwrap();
+        self.spans()
+            .iter()
+            .try_fold(moments([T::zero(); 6]), |acc, span| {
+                try_integrate(span, tolerance / n).map(|sum| acc.sum(&moments(sum)))
+            })
+    }
+}
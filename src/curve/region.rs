@@ -0,0 +1,72 @@
+use crate::{
+    curve::{CompoundCurve2D, PlanarMoments},
+    misc::FloatingPoint,
+};
+
+/// A planar region bounded by the closed exterior curve & the closed interior curves of its holes
+/// The holes must lie inside the exterior without overlapping each other, and the orientations of the curves are arbitrary.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Region<T: FloatingPoint> {
+    exterior: CompoundCurve2D<T>,
+    interiors: Vec<CompoundCurve2D<T>>,
+}
+
+impl<T: FloatingPoint> Region<T> {
+    pub fn new(exterior: CompoundCurve2D<T>, interiors: Vec<CompoundCurve2D<T>>) -> Self {
+        Self {
+            exterior,
+            interiors,
+        }
+    }
+
+    pub fn exterior(&self) -> &CompoundCurve2D<T> {
+        &self.exterior
+    }
+
+    pub fn interiors(&self) -> &[CompoundCurve2D<T>] {
+        &self.interiors
+    }
+
+    pub fn into_exterior_interiors(self) -> (CompoundCurve2D<T>, Vec<CompoundCurve2D<T>>) {
+        (self.exterior, self.interiors)
+    }
+
+    /// Try to compute the moments of the region by subtracting the moments of the holes from the moments inside the exterior
+    /// The area of the region is positive regardless of the orientations of the curves.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point2, Vector2};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let rectangle = NurbsCurve2D::polyline(&[
+    ///     Point2::new(0., 0.),
+    ///     Point2::new(4., 0.),
+    ///     Point2::new(4., 2.),
+    ///     Point2::new(0., 2.),
+    ///     Point2::new(0., 0.),
+    /// ]);
+    /// let hole = NurbsCurve2D::try_circle(&Point2::new(1., 1.), &Vector2::x(), &Vector2::y(), 0.5).unwrap();
+    /// let region = Region::new(rectangle.into(), vec![hole.into()]);
+    /// let moments = region.try_planar_moments(1e-10).unwrap();
+    ///
+    /// let disk = std::f64::consts::PI / 4.;
+    /// let area = 8. - disk;
+    /// assert_relative_eq!(moments.area(), area, epsilon = 1e-8);
+    /// let centroid = (Point2::new(2., 1.) * 8. - Point2::new(1., 1.).coords * disk) / area;
+    /// assert_relative_eq!(moments.centroid(), centroid, epsilon = 1e-8);
+    ///
+    /// // the moment about the x axis through the centroid is symmetric regardless of the hole
+    /// let (_, yy, xy) = moments.centroid_second_moments();
+    /// assert_relative_eq!(yy, 4. * 8. / 12. - disk * 0.25 / 4., epsilon = 1e-8);
+    /// assert_relative_eq!(xy, 0., epsilon = 1e-8);
+    /// ```
+    pub fn try_planar_moments(&self, tolerance: T) -> anyhow::Result<PlanarMoments<T>> {
+        let exterior = self.exterior.try_planar_moments(tolerance)?.unsigned();
+        self.interiors.iter().try_fold(exterior, |acc, interior| {
+            let hole = interior.try_planar_moments(tolerance)?.unsigned();
+            Ok(acc.sum(&hole.negated()))
+        })
+    }
+}
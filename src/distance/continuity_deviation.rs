@@ -0,0 +1,308 @@
+use argmin::core::ArgminFloat;
+use nalgebra::{
+    allocator::Allocator, ComplexField, DefaultAllocator, DimName, DimNameDiff, DimNameSub,
+    Matrix2, OVector, Vector2, Vector3, U1,
+};
+
+use crate::{
+    curve::{CurveContinuity, NurbsCurve, NurbsCurve3D},
+    misc::FloatingPoint,
+    surface::NurbsSurface3D,
+};
+
+/// The tolerances to judge the continuity from the deviations across the joint
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ContinuityTolerance<T: FloatingPoint> {
+    /// The tolerance of the distance between the points
+    pub position: T,
+    /// The tolerance of the angle between the tangents or the normals in radians
+    pub angle: T,
+    /// The tolerance of the difference of the curvatures
+    pub curvature: T,
+}
+
+impl<T: FloatingPoint> Default for ContinuityTolerance<T> {
+    fn default() -> Self {
+        Self {
+            position: T::from_f64(1e-6).unwrap(),
+            angle: T::from_f64(1e-4).unwrap(),
+            curvature: T::from_f64(1e-4).unwrap(),
+        }
+    }
+}
+
+impl<T: FloatingPoint> ContinuityTolerance<T> {
+    pub fn with_position(mut self, position: T) -> Self {
+        self.position = position;
+        self
+    }
+
+    pub fn with_angle(mut self, angle: T) -> Self {
+        self.angle = angle;
+        self
+    }
+
+    pub fn with_curvature(mut self, curvature: T) -> Self {
+        self.curvature = curvature;
+        self
+    }
+}
+
+/// The deviations of the position, the tangent & the curvature across the joint at a sample
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ContinuityDeviation<T: FloatingPoint> {
+    /// The parameter of the sample
+    parameter: T,
+    /// The distance between the points
+    position: T,
+    /// The angle between the tangents or the normals in radians
+    angle: T,
+    /// The difference of the curvatures
+    curvature: T,
+}
+
+impl<T: FloatingPoint> ContinuityDeviation<T> {
+    pub fn new(parameter: T, position: T, angle: T, curvature: T) -> Self {
+        Self {
+            parameter,
+            position,
+            angle,
+            curvature,
+        }
+    }
+
+    pub fn parameter(&self) -> T {
+        self.parameter
+    }
+
+    pub fn position(&self) -> T {
+        self.position
+    }
+
+    pub fn angle(&self) -> T {
+        self.angle
+    }
+
+    pub fn curvature(&self) -> T {
+        self.curvature
+    }
+
+    /// The highest continuity achieved within the tolerance, or `None` if the points are apart
+    pub fn continuity(&self, tolerance: &ContinuityTolerance<T>) -> Option<CurveContinuity> {
+        if self.position > tolerance.position {
+            None
+        } else if self.angle > tolerance.angle {
+            Some(CurveContinuity::G0)
+        } else if self.curvature > tolerance.curvature {
+            Some(CurveContinuity::G1)
+        } else {
+            Some(CurveContinuity::G2)
+        }
+    }
+}
+
+/// The deviations sampled across the joint of two curves or along the shared edge of two surfaces
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContinuityAnalysis<T: FloatingPoint> {
+    samples: Vec<ContinuityDeviation<T>>,
+}
+
+impl<T: FloatingPoint> ContinuityAnalysis<T> {
+    pub fn new(samples: Vec<ContinuityDeviation<T>>) -> Self {
+        Self { samples }
+    }
+
+    pub fn samples(&self) -> &[ContinuityDeviation<T>] {
+        &self.samples
+    }
+
+    /// The maximum distance between the points
+    pub fn max_position(&self) -> T {
+        self.max_by(|s| s.position)
+    }
+
+    /// The maximum angle between the tangents or the normals
+    pub fn max_angle(&self) -> T {
+        self.max_by(|s| s.angle)
+    }
+
+    /// The maximum difference of the curvatures
+    pub fn max_curvature(&self) -> T {
+        self.max_by(|s| s.curvature)
+    }
+
+    fn max_by(&self, f: impl Fn(&ContinuityDeviation<T>) -> T) -> T {
+        self.samples.iter().fold(T::zero(), |acc, s| acc.max(f(s)))
+    }
+
+    /// The continuity achieved at all the samples within the tolerance
+    pub fn continuity(&self, tolerance: &ContinuityTolerance<T>) -> Option<CurveContinuity> {
+        self.samples
+            .iter()
+            .map(|s| s.continuity(tolerance))
+            .min()
+            .flatten()
+    }
+}
+
+/// The angle between the unit vectors
+fn angle_between<T: FloatingPoint, D: DimName>(a: &OVector<T, D>, b: &OVector<T, D>) -> T
+where
+    DefaultAllocator: Allocator<D>,
+{
+    // the half of the chord is stable for the small angles
+    let chord = (a - b).norm() * T::from_f64(0.5).unwrap();
+    chord.min(T::one()).asin() * T::from_f64(2.).unwrap()
+}
+
+/// Analyze the continuity at the joint of the end of the curve `a` & the start of the curve `b`
+/// The deviation of the curvature is the distance between the curvature vectors.
+/// # Example
+/// ```
+/// use curvo::prelude::*;
+/// use nalgebra::{Point2, Vector2};
+/// use approx::assert_relative_eq;
+///
+/// let arc = NurbsCurve2D::try_arc(&Point2::origin(), &Vector2::x(), &Vector2::y(), 1., 0., std::f64::consts::FRAC_PI_2).unwrap();
+/// let tolerance = ContinuityTolerance::default();
+///
+/// // the line tangent to the arc is G1 but not G2 as the curvature jumps from 1 to 0
+/// let line = NurbsCurve2D::polyline(&[Point2::new(0., 1.), Point2::new(-1., 1.)]);
+/// let analysis = curve_continuity(&arc, &line).unwrap();
+/// assert_eq!(analysis.continuity(&tolerance), Some(CurveContinuity::G1));
+/// assert_relative_eq!(analysis.max_curvature(), 1., epsilon = 1e-8);
+///
+/// // the corner
+/// let line = NurbsCurve2D::polyline(&[Point2::new(0., 1.), Point2::new(0., 2.)]);
+/// let analysis = curve_continuity(&arc, &line).unwrap();
+/// assert_eq!(analysis.continuity(&tolerance), Some(CurveContinuity::G0));
+/// assert_relative_eq!(analysis.max_angle(), std::f64::consts::FRAC_PI_2, epsilon = 1e-8);
+///
+/// // the gap
+/// let line = NurbsCurve2D::polyline(&[Point2::new(0., 1.1), Point2::new(-1., 1.1)]);
+/// let analysis = curve_continuity(&arc, &line).unwrap();
+/// assert_eq!(analysis.continuity(&tolerance), None);
+/// assert_relative_eq!(analysis.max_position(), 0.1, epsilon = 1e-8);
+/// ```
+pub fn curve_continuity<T, D>(
+    a: &NurbsCurve<T, D>,
+    b: &NurbsCurve<T, D>,
+) -> anyhow::Result<ContinuityAnalysis<T>>
+where
+    T: FloatingPoint,
+    D: DimName + DimNameSub<U1>,
+    DefaultAllocator: Allocator<D>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    let (_, end) = a.knots_domain();
+    let (start, _) = b.knots_domain();
+    let frame = |c: &NurbsCurve<T, D>, t: T| -> anyhow::Result<_> {
+        let d = c.rational_derivatives(t, 2);
+        let speed = d[1].norm();
+        anyhow::ensure!(
+            speed > T::default_epsilon(),
+            "The curve has the zero derivative at the joint"
+        );
+        let tangent = &d[1] / speed;
+        let curvature = (&d[2] - &tangent * d[2].dot(&tangent)) / (speed * speed);
+        Ok((d[0].clone(), tangent, curvature))
+    };
+    let (pa, ta, ka) = frame(a, end)?;
+    let (pb, tb, kb) = frame(b, start)?;
+    Ok(ContinuityAnalysis::new(vec![ContinuityDeviation::new(
+        end,
+        (pa - pb).norm(),
+        angle_between(&ta, &tb),
+        (ka - kb).norm(),
+    )]))
+}
+
+/// Compute the normal curvature of the surface at the parameter in the direction on the tangent plane
+fn normal_curvature<T: FloatingPoint>(
+    surface: &NurbsSurface3D<T>,
+    (u, v): (T, T),
+    direction: &Vector3<T>,
+    normal: &Vector3<T>,
+) -> Option<T> {
+    let d = surface.rational_derivatives(u, v, 2);
+    let (su, sv) = (&d[1][0], &d[0][1]);
+    // the direction in the parameter space mapped closest to the direction
+    let m = Matrix2::new(su.dot(su), su.dot(sv), su.dot(sv), sv.dot(sv));
+    let x = m
+        .lu()
+        .solve(&Vector2::new(su.dot(direction), sv.dot(direction)))?;
+    let first = (su * x.x + sv * x.y).norm_squared();
+    if first <= T::default_epsilon() {
+        return None;
+    }
+    let two = T::from_f64(2.).unwrap();
+    let second = d[2][0].dot(normal) * x.x * x.x
+        + d[1][1].dot(normal) * x.x * x.y * two
+        + d[0][2].dot(normal) * x.y * x.y;
+    Some(second / first)
+}
+
+/// Analyze the continuity along the shared edge of the surfaces `a` & `b` at the samples on the edge curve
+/// The angle is between the normals regardless of their orientations,
+/// and the deviation of the curvature is the difference of the normal curvatures in the direction across the edge.
+/// # Example
+/// ```
+/// use curvo::prelude::*;
+/// use nalgebra::{Point3, Vector3};
+/// use approx::assert_relative_eq;
+///
+/// let tolerance = ContinuityTolerance::default().with_position(1e-4).with_angle(1e-3);
+/// let edge = NurbsCurve3D::polyline(&[Point3::new(1., 0., 0.), Point3::new(1., 0., 2.)]);
+///
+/// // the plane tangent to the cylinder along the edge
+/// let cylinder = NurbsSurface::try_cylinder(&Point3::origin(), &(Vector3::z() * 2.), 1.).unwrap();
+/// let line = NurbsCurve3D::polyline(&[Point3::new(1., 0., 0.), Point3::new(1., 2., 0.)]);
+/// let plane = NurbsSurface::extrude(&line, &(Vector3::z() * 2.));
+/// let analysis = surface_continuity(&cylinder, &plane, &edge, 5).unwrap();
+/// assert_eq!(analysis.samples().len(), 5);
+/// assert_eq!(analysis.continuity(&tolerance), Some(CurveContinuity::G1));
+/// assert_relative_eq!(analysis.max_curvature(), 1., epsilon = 1e-3);
+///
+/// // the coplanar planes
+/// let line = NurbsCurve3D::polyline(&[Point3::new(1., -2., 0.), Point3::new(1., 0., 0.)]);
+/// let other = NurbsSurface::extrude(&line, &(Vector3::z() * 2.));
+/// let analysis = surface_continuity(&plane, &other, &edge, 5).unwrap();
+/// assert_eq!(analysis.continuity(&tolerance), Some(CurveContinuity::G2));
+/// ```
+pub fn surface_continuity<T: FloatingPoint + ArgminFloat>(
+    a: &NurbsSurface3D<T>,
+    b: &NurbsSurface3D<T>,
+    edge: &NurbsCurve3D<T>,
+    samples: usize,
+) -> anyhow::Result<ContinuityAnalysis<T>> {
+    anyhow::ensure!(samples > 1, "The number of samples must be greater than 1");
+    let (start, end) = edge.knots_domain();
+    let samples = (0..samples)
+        .map(|i| {
+            let t = start
+                + (end - start) * T::from_usize(i).unwrap() / T::from_usize(samples - 1).unwrap();
+            let p = edge.point_at(t);
+            let (uva, uvb) = (a.find_closest_parameter(&p)?, b.find_closest_parameter(&p)?);
+            let na = a.normal_at(uva.0, uva.1).normalize();
+            let mut nb = b.normal_at(uvb.0, uvb.1).normalize();
+            if na.dot(&nb) < T::zero() {
+                nb = -nb;
+            }
+            let across = edge.tangent_at(t).cross(&na).normalize();
+            let curvature = match (
+                normal_curvature(a, uva, &across, &na),
+                normal_curvature(b, uvb, &across, &nb),
+            ) {
+                (Some(ka), Some(kb)) => ComplexField::abs(ka - kb),
+                _ => anyhow::bail!("The surface is degenerate at the edge"),
+            };
+            Ok(ContinuityDeviation::new(
+                t,
+                (a.point_at(uva.0, uva.1) - b.point_at(uvb.0, uvb.1)).norm(),
+                angle_between(&na, &nb),
+                curvature,
+            ))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    Ok(ContinuityAnalysis::new(samples))
+}
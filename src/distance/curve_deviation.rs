@@ -0,0 +1,320 @@
+use argmin::core::ArgminFloat;
+use nalgebra::{
+    allocator::Allocator, ComplexField, DefaultAllocator, DimName, DimNameAdd, DimNameDiff,
+    DimNameSub, OPoint, U1,
+};
+
+use crate::{bounding_box::CurveBvh, curve::NurbsCurve, misc::FloatingPoint};
+
+/// Maximum depth of the adaptive sampling of the deviation between the curves
+const MAX_SAMPLING_DEPTH: usize = 12;
+
+/// A struct representing the deviation between two curves.
+/// The pair of points where the maximum distance is attained is held with their parameters.
+#[derive(Debug, Clone)]
+pub struct CurveDeviation<P, T> {
+    /// The point & parameter of the first curve.
+    a: (P, T),
+    /// The point & parameter of the second curve.
+    b: (P, T),
+    /// The maximum distance between the curves.
+    distance: T,
+}
+
+impl<P, T> CurveDeviation<P, T> {
+    pub fn new(a: (P, T), b: (P, T), distance: T) -> Self {
+        Self { a, b, distance }
+    }
+
+    pub fn a(&self) -> &(P, T) {
+        &self.a
+    }
+
+    pub fn b(&self) -> &(P, T) {
+        &self.b
+    }
+
+    pub fn distance(&self) -> &T {
+        &self.distance
+    }
+
+    /// Swap the first & second curves
+    fn swap(self) -> Self {
+        Self {
+            a: self.b,
+            b: self.a,
+            distance: self.distance,
+        }
+    }
+}
+
+/// A sample of the distance from a point on the source curve to the target curve
+struct DeviationSample<T: FloatingPoint, D: DimName>
+where
+    DefaultAllocator: Allocator<D>,
+{
+    parameter: T,
+    point: OPoint<T, D>,
+    closest: (OPoint<T, D>, T),
+    distance: T,
+}
+
+/// Compute the one-sided deviation from the source curve to the target curve,
+/// that is the maximum of the distances from the points on the source curve to the target curve.
+#[allow(clippy::type_complexity)]
+fn directed_deviation<T, D>(
+    source: &NurbsCurve<T, D>,
+    target: &CurveBvh<T, D>,
+    tolerance: T,
+) -> anyhow::Result<CurveDeviation<OPoint<T, DimNameDiff<D, U1>>, T>>
+where
+    T: FloatingPoint + ArgminFloat,
+    D: DimName + DimNameSub<U1>,
+    DefaultAllocator: Allocator<D>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    let sample = |t: T| -> anyhow::Result<DeviationSample<T, DimNameDiff<D, U1>>> {
+        let point = source.point_at(t);
+        let (s, closest) = target
+            .closest_points(std::slice::from_ref(&point))?
+            .pop()
+            .ok_or(anyhow::anyhow!("No closest point found"))?;
+        let distance = (&point - &closest).norm();
+        Ok(DeviationSample {
+            parameter: t,
+            point,
+            closest: (closest, s),
+            distance,
+        })
+    };
+
+    // sample the Bézier segments of the source curve uniformly
+    let half = T::from_f64(0.5).unwrap();
+    let divs = (source.degree() + 1) * 2;
+    let mut parameters = vec![];
+    for segment in source.try_decompose_bezier_segments()? {
+        let (t0, t1) = segment.knots_domain();
+        for i in 0..divs {
+            parameters
+                .push(t0 + (t1 - t0) * T::from_usize(i).unwrap() / T::from_usize(divs).unwrap());
+        }
+    }
+    parameters.push(source.knots_domain().1);
+
+    let initial = parameters
+        .into_iter()
+        .map(sample)
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    // refine the samples adaptively where the distance is not linear within the tolerance
+    let mut samples = vec![];
+    let mut iter = initial.into_iter();
+    let mut prev = iter.next().ok_or(anyhow::anyhow!("No samples"))?;
+    for next in iter {
+        let mut stack = vec![(next, 0)];
+        while let Some((end, depth)) = stack.pop() {
+            let mid = sample((prev.parameter + end.parameter) * half)?;
+            let linear = (prev.distance + end.distance) * half;
+            if depth < MAX_SAMPLING_DEPTH && ComplexField::abs(mid.distance - linear) > tolerance {
+                stack.push((end, depth + 1));
+                stack.push((mid, depth + 1));
+            } else {
+                samples.push(prev);
+                prev = end;
+            }
+        }
+    }
+    samples.push(prev);
+
+    let (index, _) = samples
+        .iter()
+        .enumerate()
+        .max_by(|a, b| {
+            a.1.distance
+                .partial_cmp(&b.1.distance)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .ok_or(anyhow::anyhow!("No samples"))?;
+
+    // maximize the distance around the maximum sample by golden section search
+    let mut lo = samples[index.saturating_sub(1)].parameter;
+    let mut hi = samples[(index + 1).min(samples.len() - 1)].parameter;
+    let ratio = T::from_f64((5f64.sqrt() - 1.) * 0.5).unwrap();
+    let eps = source.knots_domain_interval() * T::from_f64(1e-10).unwrap();
+    let mut best = samples.swap_remove(index);
+    let mut x0 = hi - (hi - lo) * ratio;
+    let mut x1 = lo + (hi - lo) * ratio;
+    let mut s0 = sample(x0)?;
+    let mut s1 = sample(x1)?;
+    for _ in 0..64 {
+        if hi - lo < eps {
+            break;
+        }
+        if s0.distance > s1.distance {
+            hi = x1;
+            x1 = x0;
+            s1 = s0;
+            x0 = hi - (hi - lo) * ratio;
+            s0 = sample(x0)?;
+        } else {
+            lo = x0;
+            x0 = x1;
+            s0 = s1;
+            x1 = lo + (hi - lo) * ratio;
+            s1 = sample(x1)?;
+        }
+    }
+    for s in [s0, s1] {
+        if s.distance > best.distance {
+            best = s;
+        }
+    }
+
+    Ok(CurveDeviation::new(
+        (best.point, best.parameter),
+        best.closest,
+        best.distance,
+    ))
+}
+
+/// Compute the one-sided deviation from the curve `a` to the curve `b`,
+/// that is the maximum of the distances from the points on `a` to the closest points on `b`.
+/// * `tolerance` - The tolerance of the distance to control the adaptive sampling & refinement
+/// # Example
+/// ```
+/// use curvo::prelude::*;
+/// use nalgebra::{Point2, Vector2};
+/// use approx::assert_relative_eq;
+///
+/// let circle = NurbsCurve2D::try_circle(&Point2::origin(), &Vector2::x(), &Vector2::y(), 1.).unwrap();
+/// let ellipse = NurbsCurve2D::try_ellipse(&Point2::origin(), &(Vector2::x() * 1.5), &Vector2::y()).unwrap();
+///
+/// // The farthest point on the ellipse is at its major vertex
+/// let deviation = curve_deviation(&ellipse, &circle, 1e-6).unwrap();
+/// assert_relative_eq!(*deviation.distance(), 0.5, epsilon = 1e-6);
+/// assert_relative_eq!(f64::abs(deviation.a().0.x), 1.5, epsilon = 1e-4);
+///
+/// // The deviation is not symmetric in general
+/// let line = NurbsCurve2D::polyline(&[Point2::new(0.5, 0.), Point2::new(1., 0.)]);
+/// assert_relative_eq!(*curve_deviation(&line, &circle, 1e-6).unwrap().distance(), 0.5, epsilon = 1e-6);
+/// assert_relative_eq!(*curve_deviation(&circle, &line, 1e-6).unwrap().distance(), 1.5, epsilon = 1e-6);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn curve_deviation<T, D>(
+    a: &NurbsCurve<T, D>,
+    b: &NurbsCurve<T, D>,
+    tolerance: T,
+) -> anyhow::Result<CurveDeviation<OPoint<T, DimNameDiff<D, U1>>, T>>
+where
+    T: FloatingPoint + ArgminFloat,
+    D: DimName + DimNameSub<U1>,
+    DefaultAllocator: Allocator<D>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    directed_deviation(a, &CurveBvh::try_new(b)?, tolerance)
+}
+
+/// Compute the symmetric Hausdorff distance between two curves,
+/// that is the larger one of the deviations from `a` to `b` and from `b` to `a`.
+/// * `tolerance` - The tolerance of the distance to control the adaptive sampling & refinement
+/// # Example
+/// ```
+/// use curvo::prelude::*;
+/// use nalgebra::Point3;
+/// use approx::assert_relative_eq;
+///
+/// let a = NurbsCurve3D::polyline(&[Point3::new(0., 0., 0.), Point3::new(1., 0., 0.)]);
+/// let b = NurbsCurve3D::polyline(&[Point3::new(0., 0.1, 0.), Point3::new(1.5, 0.1, 0.)]);
+///
+/// let hausdorff = curve_hausdorff_distance(&a, &b, 1e-6).unwrap();
+/// assert_relative_eq!(*hausdorff.distance(), (0.5f64.powi(2) + 0.1f64.powi(2)).sqrt(), epsilon = 1e-6);
+/// assert_relative_eq!(hausdorff.b().0, Point3::new(1.5, 0.1, 0.), epsilon = 1e-6);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn curve_hausdorff_distance<T, D>(
+    a: &NurbsCurve<T, D>,
+    b: &NurbsCurve<T, D>,
+    tolerance: T,
+) -> anyhow::Result<CurveDeviation<OPoint<T, DimNameDiff<D, U1>>, T>>
+where
+    T: FloatingPoint + ArgminFloat,
+    D: DimName + DimNameSub<U1>,
+    DefaultAllocator: Allocator<D>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    let ab = curve_deviation(a, b, tolerance)?;
+    let ba = curve_deviation(b, a, tolerance)?;
+    Ok(if ab.distance() >= ba.distance() {
+        ab
+    } else {
+        ba.swap()
+    })
+}
+
+/// Compute the one-sided deviation from the curve to the polyline
+/// The parameter on the polyline is the one of the polyline as a degree 1 curve (see `NurbsCurve::polyline`).
+#[allow(clippy::type_complexity)]
+pub fn curve_polyline_deviation<T, D>(
+    curve: &NurbsCurve<T, D>,
+    polyline: &[OPoint<T, DimNameDiff<D, U1>>],
+    tolerance: T,
+) -> anyhow::Result<CurveDeviation<OPoint<T, DimNameDiff<D, U1>>, T>>
+where
+    T: FloatingPoint + ArgminFloat,
+    D: DimName + DimNameSub<U1>,
+    <D as DimNameSub<U1>>::Output: DimNameAdd<U1>,
+    DefaultAllocator: Allocator<D>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+    DefaultAllocator: Allocator<<<D as DimNameSub<U1>>::Output as DimNameAdd<U1>>::Output>,
+{
+    curve_deviation(curve, &NurbsCurve::polyline(polyline), tolerance)
+}
+
+/// Compute the one-sided deviation from the polyline to the curve
+/// The parameter on the polyline is the one of the polyline as a degree 1 curve (see `NurbsCurve::polyline`).
+#[allow(clippy::type_complexity)]
+pub fn polyline_curve_deviation<T, D>(
+    polyline: &[OPoint<T, DimNameDiff<D, U1>>],
+    curve: &NurbsCurve<T, D>,
+    tolerance: T,
+) -> anyhow::Result<CurveDeviation<OPoint<T, DimNameDiff<D, U1>>, T>>
+where
+    T: FloatingPoint + ArgminFloat,
+    D: DimName + DimNameSub<U1>,
+    <D as DimNameSub<U1>>::Output: DimNameAdd<U1>,
+    DefaultAllocator: Allocator<D>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+    DefaultAllocator: Allocator<<<D as DimNameSub<U1>>::Output as DimNameAdd<U1>>::Output>,
+{
+    curve_deviation(&NurbsCurve::polyline(polyline), curve, tolerance)
+}
+
+/// Compute the symmetric Hausdorff distance between the curve and the polyline
+/// # Example
+/// ```
+/// use curvo::prelude::*;
+/// use nalgebra::{Point2, Vector2};
+///
+/// let circle = NurbsCurve2D::try_circle(&Point2::origin(), &Vector2::x(), &Vector2::y(), 1.).unwrap();
+/// let polyline = circle.tessellate(Some(1e-4));
+///
+/// // The tessellation deviates from the circle within the tolerance
+/// let hausdorff = curve_polyline_hausdorff_distance(&circle, &polyline, 1e-6).unwrap();
+/// assert!(*hausdorff.distance() < 1e-2);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn curve_polyline_hausdorff_distance<T, D>(
+    curve: &NurbsCurve<T, D>,
+    polyline: &[OPoint<T, DimNameDiff<D, U1>>],
+    tolerance: T,
+) -> anyhow::Result<CurveDeviation<OPoint<T, DimNameDiff<D, U1>>, T>>
+where
+    T: FloatingPoint + ArgminFloat,
+    D: DimName + DimNameSub<U1>,
+    <D as DimNameSub<U1>>::Output: DimNameAdd<U1>,
+    DefaultAllocator: Allocator<D>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+    DefaultAllocator: Allocator<<<D as DimNameSub<U1>>::Output as DimNameAdd<U1>>::Output>,
+{
+    curve_hausdorff_distance(curve, &NurbsCurve::polyline(polyline), tolerance)
+}
@@ -0,0 +1,5 @@
+pub mod continuity_deviation;
+pub mod curve_deviation;
+
+pub use continuity_deviation::*;
+pub use curve_deviation::*;
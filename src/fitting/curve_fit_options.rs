@@ -0,0 +1,39 @@
+use crate::{curve::KnotStyle, misc::FloatingPoint};
+
+/// Options for fitting a curve to points by optimizing both the control points & the parameters of the points
+#[derive(Clone, Debug)]
+pub struct CurveFitOptions<T: FloatingPoint> {
+    /// The parameterization of the points for the initial approximation
+    pub knot_style: KnotStyle,
+    /// The maximum number of the iterations of the solver
+    pub max_iters: u64,
+    /// The tolerance for the relative decrease of the sum of the squared distances
+    pub tolerance: T,
+}
+
+impl<T: FloatingPoint> Default for CurveFitOptions<T> {
+    fn default() -> Self {
+        Self {
+            knot_style: KnotStyle::Chordal,
+            max_iters: 100,
+            tolerance: T::from_f64(1e-10).unwrap(),
+        }
+    }
+}
+
+impl<T: FloatingPoint> CurveFitOptions<T> {
+    pub fn with_knot_style(mut self, knot_style: KnotStyle) -> Self {
+        self.knot_style = knot_style;
+        self
+    }
+
+    pub fn with_max_iters(mut self, max_iters: u64) -> Self {
+        self.max_iters = max_iters;
+        self
+    }
+
+    pub fn with_tolerance(mut self, tolerance: T) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+}
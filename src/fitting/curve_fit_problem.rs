@@ -0,0 +1,163 @@
+use argmin::core::{Jacobian, Operator};
+use nalgebra::{
+    allocator::Allocator, DMatrix, DVector, DefaultAllocator, DimName, DimNameDiff, DimNameSub,
+    OPoint, U1,
+};
+
+use crate::{curve::NurbsCurve, knot::KnotVector, misc::FloatingPoint};
+
+use super::ParameterProjection;
+
+/// Residual & jacobian provider for fitting a non-rational curve to points
+/// The parameter vector is the coordinates of the control points followed by the parameters of the points on the curve,
+/// and the residual vector is the differences between the points on the curve & the given points.
+pub struct CurveFitProblem<'a, T: FloatingPoint, D: DimName>
+where
+    DefaultAllocator: Allocator<D>,
+    D: DimNameSub<U1>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    /// The points to fit
+    points: &'a [OPoint<T, DimNameDiff<D, U1>>],
+    /// The degree of the curve
+    degree: usize,
+    /// The fixed knot vector of the curve
+    knots: KnotVector<T>,
+}
+
+impl<'a, T: FloatingPoint, D: DimName> CurveFitProblem<'a, T, D>
+where
+    DefaultAllocator: Allocator<D>,
+    D: DimNameSub<U1>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    pub fn new(
+        points: &'a [OPoint<T, DimNameDiff<D, U1>>],
+        degree: usize,
+        knots: KnotVector<T>,
+    ) -> Self {
+        CurveFitProblem {
+            points,
+            degree,
+            knots,
+        }
+    }
+
+    fn dim() -> usize {
+        D::dim() - 1
+    }
+
+    fn control_points_count(&self) -> usize {
+        self.knots.len() - self.degree - 1
+    }
+
+    /// Pack the control points of the curve & the parameters of the points into the parameter vector
+    pub fn param(&self, curve: &NurbsCurve<T, D>, parameters: &[T]) -> DVector<T> {
+        let coords = curve
+            .dehomogenized_control_points()
+            .into_iter()
+            .flat_map(|p| p.coords.iter().copied().collect::<Vec<_>>());
+        DVector::from_iterator(
+            self.control_points_count() * Self::dim() + parameters.len(),
+            coords.chain(parameters.iter().copied()),
+        )
+    }
+
+    /// Unpack the curve from the parameter vector
+    pub fn curve(&self, param: &DVector<T>) -> anyhow::Result<NurbsCurve<T, D>> {
+        let dim = Self::dim();
+        let control_points = (0..self.control_points_count())
+            .map(|i| {
+                let mut p = OPoint::<T, D>::origin();
+                for c in 0..dim {
+                    p[c] = param[i * dim + c];
+                }
+                p[dim] = T::one();
+                p
+            })
+            .collect();
+        NurbsCurve::try_new(self.degree, control_points, self.knots.to_vec())
+    }
+
+    /// Unpack the parameters of the points from the parameter vector
+    pub fn parameters(&self, param: &DVector<T>) -> Vec<T> {
+        let offset = self.control_points_count() * Self::dim();
+        param.iter().skip(offset).copied().collect()
+    }
+}
+
+impl<'a, T: FloatingPoint, D: DimName> Operator for CurveFitProblem<'a, T, D>
+where
+    DefaultAllocator: Allocator<D>,
+    D: DimNameSub<U1>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    type Param = DVector<T>;
+    type Output = DVector<T>;
+
+    /// ( C(t_k) - Q_k ) for each point Q_k
+    fn apply(&self, param: &Self::Param) -> Result<Self::Output, anyhow::Error> {
+        let dim = Self::dim();
+        let curve = self.curve(param)?;
+        let parameters = self.parameters(param);
+        let mut residual = DVector::zeros(self.points.len() * dim);
+        for (k, (t, q)) in parameters.iter().zip(self.points.iter()).enumerate() {
+            let d = curve.point_at(*t) - q;
+            for c in 0..dim {
+                residual[k * dim + c] = d[c];
+            }
+        }
+        Ok(residual)
+    }
+}
+
+impl<'a, T: FloatingPoint, D: DimName> Jacobian for CurveFitProblem<'a, T, D>
+where
+    DefaultAllocator: Allocator<D>,
+    D: DimNameSub<U1>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    type Param = DVector<T>;
+    type Jacobian = DMatrix<T>;
+
+    /// The basis functions N_i(t_k) for the control points & the derivatives C'(t_k) for the parameters
+    fn jacobian(&self, param: &Self::Param) -> Result<Self::Jacobian, anyhow::Error> {
+        let dim = Self::dim();
+        let n = self.control_points_count();
+        let curve = self.curve(param)?;
+        let parameters = self.parameters(param);
+        let mut jacobian = DMatrix::zeros(self.points.len() * dim, n * dim + parameters.len());
+        for (k, t) in parameters.iter().enumerate() {
+            let span = self.knots.find_knot_span_index(n - 1, self.degree, *t);
+            let basis = self.knots.basis_functions(span, *t, self.degree);
+            let derivative = &curve.rational_derivatives(*t, 1)[1];
+            for c in 0..dim {
+                for (i, b) in basis.iter().enumerate() {
+                    jacobian[(k * dim + c, (span - self.degree + i) * dim + c)] = *b;
+                }
+                jacobian[(k * dim + c, n * dim + k)] = derivative[c];
+            }
+        }
+        Ok(jacobian)
+    }
+}
+
+impl<'a, T: FloatingPoint, D: DimName> ParameterProjection for CurveFitProblem<'a, T, D>
+where
+    DefaultAllocator: Allocator<D>,
+    D: DimNameSub<U1>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    type Param = DVector<T>;
+
+    /// Clamp the parameters of the points to the domain of the curve
+    fn project(&self, mut param: Self::Param) -> Self::Param {
+        let offset = self.control_points_count() * Self::dim();
+        let (start, end) = self.knots.domain(self.degree);
+        param
+            .rows_mut(offset, self.points.len())
+            .iter_mut()
+            .for_each(|t| *t = (*t).clamp(start, end));
+        param
+    }
+}
@@ -0,0 +1,134 @@
+use argmin::{argmin_error_closure, core::*, float};
+use nalgebra::{DMatrix, DVector, RealField};
+
+use crate::misc::FloatingPoint;
+
+/// Projection of the parameter vector onto its feasible set, applied after each step of the solver
+pub trait ParameterProjection {
+    type Param;
+
+    fn project(&self, param: Self::Param) -> Self::Param;
+}
+
+/// Levenberg-Marquardt method minimizing the sum of the squared residuals
+/// The problem provides the residual vector by [`Operator`] & its jacobian matrix by [`Jacobian`],
+/// and the damping is decreased after each successful step & increased after each rejected one.
+#[derive(Clone, Copy)]
+pub struct LevenbergMarquardt<F> {
+    /// the damping of the normal equations
+    lambda: F,
+    /// the factor to scale the damping
+    lambda_factor: F,
+    /// the maximum number of the rejected steps in an iteration
+    max_rejections: usize,
+    /// tolerance for the relative decrease of the cost
+    cost_tolerance: F,
+    /// the decrease of the cost satisfies the tolerance or no step decreases the cost
+    converged: bool,
+}
+
+impl<F> LevenbergMarquardt<F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`LevenbergMarquardt`]
+    pub fn new() -> Self {
+        LevenbergMarquardt {
+            lambda: float!(1e-3),
+            lambda_factor: float!(10.0),
+            max_rejections: 16,
+            cost_tolerance: F::epsilon(),
+            converged: false,
+        }
+    }
+
+    /// Set the initial damping of the normal equations
+    pub fn with_lambda(mut self, lambda: F) -> Self {
+        self.lambda = lambda;
+        self
+    }
+
+    /// Set the tolerance for the relative decrease of the cost
+    pub fn with_cost_tolerance(mut self, tolerance: F) -> Self {
+        self.cost_tolerance = tolerance;
+        self
+    }
+}
+
+impl<F> Default for LevenbergMarquardt<F>
+where
+    F: ArgminFloat,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<O, F> Solver<O, IterState<DVector<F>, (), (), (), (), F>> for LevenbergMarquardt<F>
+where
+    O: Operator<Param = DVector<F>, Output = DVector<F>>
+        + Jacobian<Param = DVector<F>, Jacobian = DMatrix<F>>
+        + ParameterProjection<Param = DVector<F>>,
+    F: FloatingPoint + ArgminFloat,
+{
+    const NAME: &'static str = "Levenberg-Marquardt method";
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<DVector<F>, (), (), (), (), F>,
+    ) -> Result<(IterState<DVector<F>, (), (), (), (), F>, Option<KV>), Error> {
+        let param = state.get_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`LevenbergMarquardt` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+
+        let half = F::from_f64(0.5).unwrap();
+        let residual = problem.apply(param)?;
+        let cost = residual.norm_squared() * half;
+        let jacobian = problem.jacobian(param)?;
+        let gradient = jacobian.transpose() * &residual;
+        let normal = jacobian.transpose() * &jacobian;
+
+        for _ in 0..self.max_rejections {
+            // the damping scaled by the diagonal keeps the step invariant to the scales of the parameters
+            let mut damped = normal.clone();
+            for i in 0..damped.nrows() {
+                let d = RealField::max(normal[(i, i)], F::epsilon());
+                damped[(i, i)] += d * self.lambda;
+            }
+            let Some(delta) = damped.cholesky().map(|c| c.solve(&gradient)) else {
+                self.lambda *= self.lambda_factor;
+                continue;
+            };
+            let candidate =
+                problem.problem("projection_count", |p| Ok(p.project(param - delta)))?;
+            let candidate_cost = problem.apply(&candidate)?.norm_squared() * half;
+            if candidate_cost < cost {
+                self.lambda /= self.lambda_factor;
+                self.converged = cost - candidate_cost <= cost * self.cost_tolerance;
+                return Ok((state.param(candidate).cost(candidate_cost), None));
+            }
+            self.lambda *= self.lambda_factor;
+        }
+
+        // no step decreases the cost, so the parameter is at a local minimum
+        self.converged = true;
+        Ok((state.cost(cost), None))
+    }
+
+    fn terminate(&mut self, state: &IterState<DVector<F>, (), (), (), (), F>) -> TerminationStatus {
+        if state.iter > state.max_iters {
+            return TerminationStatus::Terminated(TerminationReason::MaxItersReached);
+        }
+
+        if self.converged {
+            return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+        }
+
+        TerminationStatus::NotTerminated
+    }
+}
@@ -0,0 +1,6 @@
+pub mod curve_fit_options;
+pub mod curve_fit_problem;
+pub mod levenberg_marquardt;
+pub use curve_fit_options::*;
+pub use curve_fit_problem::*;
+pub use levenberg_marquardt::*;
@@ -1,3 +1,15 @@
+/// The classification of the intersection of two curves.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CurveIntersectionKind<T> {
+    /// The curves cross each other at the point.
+    Transversal,
+    /// The curves touch each other at the point with the parallel tangents.
+    Tangential,
+    /// The curves are coincident over the intervals of the parameters of the first & the second curve.
+    /// The intervals are ordered by the first curve, so the interval of the second curve is reversed if the curves run in the opposite directions.
+    Overlap { a: (T, T), b: (T, T) },
+}
+
 /// A struct representing the intersection of two curves.
 #[derive(Debug, Clone)]
 pub struct CurveIntersection<P, T> {
@@ -5,11 +17,22 @@ pub struct CurveIntersection<P, T> {
     a: (P, T),
     /// The point & parameter of the second curve at the intersection.
     b: (P, T),
+    /// The classification of the intersection.
+    kind: CurveIntersectionKind<T>,
 }
 
 impl<P, T> CurveIntersection<P, T> {
     pub fn new(a: (P, T), b: (P, T)) -> Self {
-        Self { a, b }
+        Self {
+            a,
+            b,
+            kind: CurveIntersectionKind::Transversal,
+        }
+    }
+
+    pub fn with_kind(mut self, kind: CurveIntersectionKind<T>) -> Self {
+        self.kind = kind;
+        self
     }
 
     pub fn a(&self) -> &(P, T) {
@@ -19,4 +42,8 @@ impl<P, T> CurveIntersection<P, T> {
     pub fn b(&self) -> &(P, T) {
         &self.b
     }
+
+    pub fn kind(&self) -> &CurveIntersectionKind<T> {
+        &self.kind
+    }
 }
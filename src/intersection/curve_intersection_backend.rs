@@ -0,0 +1,26 @@
+/// The algorithm to find the intersections between two curves
+/// # Example
+/// ```
+/// use curvo::prelude::*;
+/// use nalgebra::{Point2, Vector2};
+/// use approx::assert_relative_eq;
+///
+/// let circle = NurbsCurve2D::try_circle(&Point2::origin(), &Vector2::x(), &Vector2::y(), 1.).unwrap();
+/// // the line touching the circle at the top
+/// let line = NurbsCurve2D::polyline(&[Point2::new(-2., 1.), Point2::new(2., 1.)]);
+///
+/// let options = CurveIntersectionSolverOptions::default()
+///     .with_backend(CurveIntersectionBackend::BezierClipping);
+/// let intersections = circle.find_intersections(&line, Some(options)).unwrap();
+/// assert_eq!(intersections.len(), 1);
+/// assert_relative_eq!(intersections[0].a().0, Point2::new(0., 1.), epsilon = 1e-6);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CurveIntersectionBackend {
+    /// Refine the candidates of the overlapping bounding boxes by the BFGS minimization of the distance
+    #[default]
+    Bfgs,
+    /// Clip the Bézier segments of the curves against the fat regions of each other,
+    /// which is robust for the tangential & the high curvature intersections
+    BezierClipping,
+}
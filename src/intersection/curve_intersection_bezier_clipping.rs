@@ -0,0 +1,248 @@
+use nalgebra::DVector;
+
+use crate::misc::FloatingPoint;
+
+/// A Bézier segment of a curve with the homogeneous control points & the range of the parameter in the original curve
+#[derive(Clone, Debug)]
+pub(crate) struct BezierClip<T: FloatingPoint> {
+    control_points: Vec<DVector<T>>,
+    range: (T, T),
+}
+
+impl<T: FloatingPoint> BezierClip<T> {
+    pub fn new(control_points: Vec<DVector<T>>, range: (T, T)) -> Self {
+        Self {
+            control_points,
+            range,
+        }
+    }
+
+    /// The parameter of the original curve at the local parameter in [0, 1]
+    pub fn parameter(&self, s: T) -> T {
+        self.range.0 + (self.range.1 - self.range.0) * s
+    }
+
+    /// The dehomogenized control points
+    fn points(&self) -> Vec<DVector<T>> {
+        self.control_points
+            .iter()
+            .map(|p| {
+                let dim = p.len() - 1;
+                p.rows(0, dim) / p[dim]
+            })
+            .collect()
+    }
+
+    /// The length of the diagonal of the bounding box of the control points
+    fn extent(&self) -> T {
+        let points = self.points();
+        let dim = points[0].len();
+        (0..dim)
+            .map(|d| {
+                let (min, max) = points
+                    .iter()
+                    .fold((points[0][d], points[0][d]), |(a, b), p| {
+                        (a.min(p[d]), b.max(p[d]))
+                    });
+                (max - min) * (max - min)
+            })
+            .fold(T::zero(), |acc, d| acc + d)
+            .sqrt()
+    }
+
+    /// Split the segment at the local parameter by de Casteljau's algorithm
+    fn split(&self, s: T) -> (Self, Self) {
+        let mut points = self.control_points.clone();
+        let n = points.len();
+        let mut left = vec![points[0].clone()];
+        let mut right = vec![points[n - 1].clone()];
+        for k in 1..n {
+            for i in 0..(n - k) {
+                points[i] = &points[i] * (T::one() - s) + &points[i + 1] * s;
+            }
+            left.push(points[0].clone());
+            right.push(points[n - k - 1].clone());
+        }
+        right.reverse();
+        let mid = self.parameter(s);
+        (
+            Self::new(left, (self.range.0, mid)),
+            Self::new(right, (mid, self.range.1)),
+        )
+    }
+
+    /// The sub segment in the local range
+    fn sub(&self, (s0, s1): (T, T)) -> Self {
+        let head = if s1 < T::one() {
+            self.split(s1).0
+        } else {
+            self.clone()
+        };
+        if s0 > T::zero() && s1 > T::zero() {
+            head.split(s0 / s1).1
+        } else {
+            head
+        }
+    }
+}
+
+/// Find the range of the local parameter where the Bézier function with the coefficients can be non-negative
+/// The range is bounded by the convex hull of the control polygon of the function.
+fn non_negative_range<T: FloatingPoint>(coefficients: &[T]) -> Option<(T, T)> {
+    let n = T::from_usize(coefficients.len() - 1).unwrap();
+    let t = |i: usize| T::from_usize(i).unwrap() / n;
+    let mut range: Option<(T, T)> = None;
+    let mut extend = |s: T| {
+        range = Some(match range {
+            Some((a, b)) => (a.min(s), b.max(s)),
+            None => (s, s),
+        });
+    };
+    for (i, c) in coefficients.iter().enumerate() {
+        if *c >= T::zero() {
+            extend(t(i));
+        }
+        for (j, d) in coefficients.iter().enumerate().skip(i + 1) {
+            if (*c < T::zero()) != (*d < T::zero()) {
+                extend(t(i) + (t(j) - t(i)) * *c / (*c - *d));
+            }
+        }
+    }
+    range
+}
+
+/// Find the range of the local parameter of the target where it can be inside the fat region of the clipper
+/// The fat region is bounded by the slabs along the normals of the chord of the clipper, which enclose its control points with the margin.
+fn clip_range<T: FloatingPoint>(
+    clipper: &BezierClip<T>,
+    target: &BezierClip<T>,
+    margin: T,
+) -> Option<(T, T)> {
+    let points = clipper.points();
+    let origin = &points[0];
+    let chord = &points[points.len() - 1] - origin;
+    let far = points
+        .iter()
+        .map(|p| p - origin)
+        .fold(DVector::zeros(origin.len()), |acc, d| {
+            if d.norm() > acc.norm() {
+                d
+            } else {
+                acc
+            }
+        });
+    let direction = if chord.norm() > far.norm() * T::from_f64(1e-3).unwrap() {
+        chord
+    } else {
+        far
+    };
+    // the orthonormal complement of the direction, or all the axes if the clipper is degenerated into a point
+    let dim = origin.len();
+    let length = direction.norm();
+    let direction = if length > T::default_epsilon() {
+        direction / length
+    } else {
+        DVector::zeros(dim)
+    };
+    let mut normals: Vec<DVector<T>> = vec![];
+    for axis in 0..dim {
+        let mut n = DVector::zeros(dim);
+        n[axis] = T::one();
+        n -= &direction * direction[axis];
+        for m in normals.iter() {
+            let dot = n.dot(m);
+            n -= m * dot;
+        }
+        if n.norm() > T::from_f64(1e-3).unwrap() {
+            normals.push(n.normalize());
+        }
+    }
+
+    let weights = target
+        .control_points
+        .iter()
+        .map(|p| p[p.len() - 1])
+        .collect::<Vec<_>>();
+    let target_points = target.points();
+    let mut range = (T::zero(), T::one());
+    for n in normals.iter() {
+        let distances = points
+            .iter()
+            .map(|p| (p - origin).dot(n))
+            .collect::<Vec<_>>();
+        let (min, max) = distances
+            .iter()
+            .fold((distances[0], distances[0]), |(a, b), d| {
+                (a.min(*d), b.max(*d))
+            });
+        let (min, max) = (min - margin, max + margin);
+        // the numerators of the rational distance functions share the sign with them for the positive weights
+        let (lower, upper): (Vec<_>, Vec<_>) = target_points
+            .iter()
+            .zip(weights.iter())
+            .map(|(q, w)| {
+                let d = (q - origin).dot(n);
+                (*w * (d - min), *w * (max - d))
+            })
+            .unzip();
+        let (a0, a1) = non_negative_range(&lower)?;
+        let (b0, b1) = non_negative_range(&upper)?;
+        range = (range.0.max(a0).max(b0), range.1.min(a1).min(b1));
+        if range.0 > range.1 {
+            return None;
+        }
+    }
+    Some(range)
+}
+
+/// Find the pairs of the parameters at the intersections of the Bézier segments by the Bézier clipping
+/// The segments are clipped against each other alternately with the fat regions widened by the margin,
+/// and the longer one is subdivided if the clipping does not reduce the ranges enough.
+/// The clipping stops when both segments are smaller than the tolerance, so a tangential intersection results in a cluster of the pairs.
+pub(crate) fn bezier_clipping_intersections<T: FloatingPoint>(
+    a: BezierClip<T>,
+    b: BezierClip<T>,
+    margin: T,
+    tolerance: T,
+) -> Vec<(T, T)> {
+    let max_depth = 64;
+    let max_steps = 1 << 14;
+    let threshold = T::from_f64(0.8).unwrap();
+    let half = T::from_f64(0.5).unwrap();
+
+    let mut intersections = vec![];
+    let mut stack = vec![(a, b, 0)];
+    let mut steps = 0;
+    while let Some((a, b, depth)) = stack.pop() {
+        steps += 1;
+        if steps > max_steps {
+            break;
+        }
+        if (a.extent() <= tolerance && b.extent() <= tolerance) || depth >= max_depth {
+            intersections.push((a.parameter(half), b.parameter(half)));
+            continue;
+        }
+        let Some(range_b) = clip_range(&a, &b, margin) else {
+            continue;
+        };
+        let b = b.sub(range_b);
+        let Some(range_a) = clip_range(&b, &a, margin) else {
+            continue;
+        };
+        let a = a.sub(range_a);
+        if range_a.1 - range_a.0 > threshold && range_b.1 - range_b.0 > threshold {
+            if a.extent() > b.extent() {
+                let (a0, a1) = a.split(half);
+                stack.push((a0, b.clone(), depth + 1));
+                stack.push((a1, b, depth + 1));
+            } else {
+                let (b0, b1) = b.split(half);
+                stack.push((a.clone(), b0, depth + 1));
+                stack.push((a, b1, depth + 1));
+            }
+        } else {
+            stack.push((a, b, depth + 1));
+        }
+    }
+    intersections
+}
@@ -1,6 +1,9 @@
 use crate::misc::FloatingPoint;
 
+use super::CurveIntersectionBackend;
+
 /// Hyperparameters for the curve intersection solver.
+#[derive(Clone, Debug)]
 pub struct CurveIntersectionSolverOptions<T: FloatingPoint> {
     /// Minimum distance between two points to consider them as intersecting.
     pub minimum_distance: T,
@@ -14,6 +17,10 @@ pub struct CurveIntersectionSolverOptions<T: FloatingPoint> {
     pub cost_tolerance: T,
     /// Maximum number of iterations for the Newton method.
     pub max_iters: u64,
+    /// The algorithm to find the intersections.
+    pub backend: CurveIntersectionBackend,
+    /// Maximum sine of the angle between the tangents to classify the intersection as tangential.
+    pub tangency_tolerance: T,
 }
 
 impl<T: FloatingPoint> Default for CurveIntersectionSolverOptions<T> {
@@ -24,6 +31,8 @@ impl<T: FloatingPoint> Default for CurveIntersectionSolverOptions<T> {
             step_size_tolerance: T::from_f64(1e-8).unwrap(),
             cost_tolerance: T::from_f64(1e-10).unwrap(),
             max_iters: 200,
+            backend: CurveIntersectionBackend::default(),
+            tangency_tolerance: T::from_f64(1e-2).unwrap(),
         }
     }
 }
@@ -53,4 +62,14 @@ impl<T: FloatingPoint> CurveIntersectionSolverOptions<T> {
         self.max_iters = max_iters;
         self
     }
+
+    pub fn with_backend(mut self, backend: CurveIntersectionBackend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    pub fn with_tangency_tolerance(mut self, tangency_tolerance: T) -> Self {
+        self.tangency_tolerance = tangency_tolerance;
+        self
+    }
 }
@@ -0,0 +1,22 @@
+/// A struct representing the intersection of a curve and a surface.
+#[derive(Debug, Clone)]
+pub struct CurveSurfaceIntersection<P, T> {
+    /// The point & parameter of the curve at the intersection.
+    curve: (P, T),
+    /// The point & (u, v) parameter of the surface at the intersection.
+    surface: (P, (T, T)),
+}
+
+impl<P, T> CurveSurfaceIntersection<P, T> {
+    pub fn new(curve: (P, T), surface: (P, (T, T))) -> Self {
+        Self { curve, surface }
+    }
+
+    pub fn curve(&self) -> &(P, T) {
+        &self.curve
+    }
+
+    pub fn surface(&self) -> &(P, (T, T)) {
+        &self.surface
+    }
+}
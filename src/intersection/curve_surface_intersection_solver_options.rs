@@ -0,0 +1,49 @@
+use crate::misc::FloatingPoint;
+
+/// Hyperparameters for the curve & surface intersection solver.
+#[derive(Clone, Debug)]
+pub struct CurveSurfaceIntersectionSolverOptions<T: FloatingPoint> {
+    /// Minimum distance between two points to consider them as intersecting.
+    pub minimum_distance: T,
+    /// Tolerance relative to the knot domain of the curve under which two intersections are merged.
+    /// The intersections are duplicated in the adjacent segments, so the parameters closer than `parameter_tolerance * (knot domain interval)` are considered as the same.
+    pub parameter_tolerance: T,
+    /// Knot domain division for the threshold of the bounding box trees.
+    /// Before detecting intersections, perform intersection detection between the bounding boxes that enclose the curve segments & surface patches as a sub-problem.
+    pub knot_domain_division: usize,
+    /// Maximum number of iterations for the Newton method.
+    pub max_iters: u64,
+}
+
+impl<T: FloatingPoint> Default for CurveSurfaceIntersectionSolverOptions<T> {
+    fn default() -> Self {
+        Self {
+            minimum_distance: T::from_f64(1e-6).unwrap(),
+            parameter_tolerance: T::from_f64(1e-6).unwrap(),
+            knot_domain_division: 16,
+            max_iters: 32,
+        }
+    }
+}
+
+impl<T: FloatingPoint> CurveSurfaceIntersectionSolverOptions<T> {
+    pub fn with_minimum_distance(mut self, minimum_distance: T) -> Self {
+        self.minimum_distance = minimum_distance;
+        self
+    }
+
+    pub fn with_parameter_tolerance(mut self, parameter_tolerance: T) -> Self {
+        self.parameter_tolerance = parameter_tolerance;
+        self
+    }
+
+    pub fn with_knot_domain_division(mut self, knot_domain_division: usize) -> Self {
+        self.knot_domain_division = knot_domain_division;
+        self
+    }
+
+    pub fn with_max_iters(mut self, max_iters: u64) -> Self {
+        self.max_iters = max_iters;
+        self
+    }
+}
@@ -1,9 +1,25 @@
 pub mod curve_intersection;
+pub mod curve_intersection_backend;
+pub(crate) mod curve_intersection_bezier_clipping;
 pub mod curve_intersection_bfgs;
 pub mod curve_intersection_problem;
 pub mod curve_intersection_solver_options;
+pub mod curve_surface_intersection;
+pub mod curve_surface_intersection_solver_options;
+pub mod ray_curve_intersection;
+pub mod ray_surface_intersection;
+pub mod surface_intersection;
+pub(crate) mod surface_intersection_marcher;
+pub mod surface_intersection_solver_options;
 
 pub use curve_intersection::*;
+pub use curve_intersection_backend::*;
 pub use curve_intersection_bfgs::*;
 pub use curve_intersection_problem::*;
 pub use curve_intersection_solver_options::*;
+pub use curve_surface_intersection::*;
+pub use curve_surface_intersection_solver_options::*;
+pub use ray_curve_intersection::*;
+pub use ray_surface_intersection::*;
+pub use surface_intersection::*;
+pub use surface_intersection_solver_options::*;
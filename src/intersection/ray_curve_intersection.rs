@@ -0,0 +1,32 @@
+/// A struct representing the intersection of a ray and a curve within a distance tolerance.
+#[derive(Debug, Clone)]
+pub struct RayCurveIntersection<P, T> {
+    /// The point & parameter of the curve at the intersection.
+    curve: (P, T),
+    /// The point & parameter along the ray at the intersection.
+    ray: (P, T),
+    /// The distance between the points.
+    distance: T,
+}
+
+impl<P, T> RayCurveIntersection<P, T> {
+    pub fn new(curve: (P, T), ray: (P, T), distance: T) -> Self {
+        Self {
+            curve,
+            ray,
+            distance,
+        }
+    }
+
+    pub fn curve(&self) -> &(P, T) {
+        &self.curve
+    }
+
+    pub fn ray(&self) -> &(P, T) {
+        &self.ray
+    }
+
+    pub fn distance(&self) -> &T {
+        &self.distance
+    }
+}
@@ -0,0 +1,43 @@
+use nalgebra::{Point3, Vector3};
+
+use crate::misc::FloatingPoint;
+
+/// A struct representing the intersection of a ray and a surface.
+#[derive(Debug, Clone)]
+pub struct RaySurfaceIntersection<T: FloatingPoint> {
+    /// The point at the intersection.
+    point: Point3<T>,
+    /// The parameter along the ray at the intersection.
+    parameter: T,
+    /// The (u, v) parameter of the surface at the intersection.
+    uv: (T, T),
+    /// The normal of the surface at the intersection.
+    normal: Vector3<T>,
+}
+
+impl<T: FloatingPoint> RaySurfaceIntersection<T> {
+    pub fn new(point: Point3<T>, parameter: T, uv: (T, T), normal: Vector3<T>) -> Self {
+        Self {
+            point,
+            parameter,
+            uv,
+            normal,
+        }
+    }
+
+    pub fn point(&self) -> &Point3<T> {
+        &self.point
+    }
+
+    pub fn parameter(&self) -> T {
+        self.parameter
+    }
+
+    pub fn uv(&self) -> (T, T) {
+        self.uv
+    }
+
+    pub fn normal(&self) -> &Vector3<T> {
+        &self.normal
+    }
+}
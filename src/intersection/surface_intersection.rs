@@ -0,0 +1,52 @@
+use crate::{curve::NurbsCurve2D, curve::NurbsCurve3D, misc::FloatingPoint};
+
+/// A struct representing an intersection curve between two surfaces.
+#[derive(Debug, Clone)]
+pub struct SurfaceIntersection<T: FloatingPoint> {
+    /// The intersection curve in 3D space.
+    curve: NurbsCurve3D<T>,
+    /// The intersection curve in the (u, v) parameter space of the first surface.
+    a: NurbsCurve2D<T>,
+    /// The intersection curve in the (u, v) parameter space of the second surface.
+    b: NurbsCurve2D<T>,
+    /// The intersection curve is closed or not.
+    closed: bool,
+}
+
+impl<T: FloatingPoint> SurfaceIntersection<T> {
+    pub fn new(
+        curve: NurbsCurve3D<T>,
+        a: NurbsCurve2D<T>,
+        b: NurbsCurve2D<T>,
+        closed: bool,
+    ) -> Self {
+        Self {
+            curve,
+            a,
+            b,
+            closed,
+        }
+    }
+
+    pub fn curve(&self) -> &NurbsCurve3D<T> {
+        &self.curve
+    }
+
+    pub fn into_curve(self) -> NurbsCurve3D<T> {
+        self.curve
+    }
+
+    /// Get the intersection curve in the parameter space of the first surface.
+    pub fn a(&self) -> &NurbsCurve2D<T> {
+        &self.a
+    }
+
+    /// Get the intersection curve in the parameter space of the second surface.
+    pub fn b(&self) -> &NurbsCurve2D<T> {
+        &self.b
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+}
@@ -0,0 +1,568 @@
+use nalgebra::{
+    ComplexField, Matrix2, Matrix3, Matrix3x4, Matrix4, Point2, Point3, Vector2, Vector3, Vector4,
+};
+
+use crate::{misc::FloatingPoint, surface::NurbsSurface3D};
+
+/// A sample point on an intersection curve between two surfaces.
+#[derive(Clone, Debug)]
+pub(crate) struct SurfaceIntersectionSample<T: FloatingPoint> {
+    /// The point in 3D space
+    pub point: Point3<T>,
+    /// The parameter on the first surface
+    pub a: Vector2<T>,
+    /// The parameter on the second surface
+    pub b: Vector2<T>,
+}
+
+impl<T: FloatingPoint> SurfaceIntersectionSample<T> {
+    pub fn a_uv(&self) -> Point2<T> {
+        Point2::new(self.a.x, self.a.y)
+    }
+
+    pub fn b_uv(&self) -> Point2<T> {
+        Point2::new(self.b.x, self.b.y)
+    }
+}
+
+/// A traced polyline of an intersection curve between two surfaces.
+#[derive(Clone, Debug)]
+pub(crate) struct SurfaceIntersectionPolyline<T: FloatingPoint> {
+    pub samples: Vec<SurfaceIntersectionSample<T>>,
+    pub closed: bool,
+}
+
+/// Local differential geometry of a surface at a parameter
+struct SurfaceFrame<T: FloatingPoint> {
+    point: Point3<T>,
+    du: Vector3<T>,
+    dv: Vector3<T>,
+}
+
+impl<T: FloatingPoint> SurfaceFrame<T> {
+    fn new(surface: &NurbsSurface3D<T>, uv: &Vector2<T>) -> Self {
+        let deriv = surface.rational_derivatives(uv.x, uv.y, 1);
+        Self {
+            point: deriv[0][0].into(),
+            du: deriv[1][0],
+            dv: deriv[0][1],
+        }
+    }
+
+    fn normal(&self) -> Vector3<T> {
+        self.du.cross(&self.dv)
+    }
+
+    /// Project a displacement in 3D space to the parameter space
+    fn project(&self, displacement: &Vector3<T>) -> Option<Vector2<T>> {
+        let m = Matrix2::new(
+            self.du.dot(&self.du),
+            self.du.dot(&self.dv),
+            self.du.dot(&self.dv),
+            self.dv.dot(&self.dv),
+        );
+        let rhs = Vector2::new(self.du.dot(displacement), self.dv.dot(displacement));
+        m.lu().solve(&rhs)
+    }
+}
+
+/// Parameter domain of a surface
+#[derive(Clone, Debug)]
+struct Domain<T: FloatingPoint> {
+    u: (T, T),
+    v: (T, T),
+    /// The boundaries collapsed into a point (e.g. the poles of a sphere) as the index of the parameter, its value & the point
+    poles: Vec<(usize, T, Point3<T>)>,
+}
+
+impl<T: FloatingPoint> Domain<T> {
+    fn new(surface: &NurbsSurface3D<T>) -> Self {
+        let u = surface.u_knots_domain();
+        let v = surface.v_knots_domain();
+        let ranges = [u, v];
+        let mut poles = vec![];
+        for i in 0..2 {
+            let (min, max) = ranges[1 - i];
+            for bound in [ranges[i].0, ranges[i].1] {
+                let points: Vec<_> = (0..=4)
+                    .map(|k| {
+                        let t = min
+                            + (max - min) * T::from_usize(k).unwrap() / T::from_usize(4).unwrap();
+                        if i == 0 {
+                            surface.point_at(bound, t)
+                        } else {
+                            surface.point_at(t, bound)
+                        }
+                    })
+                    .collect();
+                let scale = points[0].coords.norm() + T::one();
+                if points
+                    .iter()
+                    .all(|p| (p - points[0]).norm() < scale * T::default_epsilon().sqrt())
+                {
+                    poles.push((i, bound, points[0]));
+                }
+            }
+        }
+        Self { u, v, poles }
+    }
+
+    /// Move the parameter of the point on the pole onto the boundary,
+    /// taking the arbitrary parameter along the pole from the previous one approaching it.
+    fn settle(&self, uv: &mut Vector2<T>, point: &Point3<T>, previous: &Vector2<T>, tolerance: T) {
+        if let Some((i, bound, _)) = self
+            .poles
+            .iter()
+            .find(|(_, _, pole)| (pole - point).norm() < tolerance)
+        {
+            uv[*i] = *bound;
+            uv[1 - *i] = previous[1 - *i];
+        }
+    }
+
+    fn contains(&self, uv: &Vector2<T>) -> bool {
+        self.u.0 <= uv.x && uv.x <= self.u.1 && self.v.0 <= uv.y && uv.y <= self.v.1
+    }
+
+    fn clamp(&self, uv: &Vector2<T>) -> Vector2<T> {
+        Vector2::new(
+            nalgebra::clamp(uv.x, self.u.0, self.u.1),
+            nalgebra::clamp(uv.y, self.v.0, self.v.1),
+        )
+    }
+
+    /// Check if the parameters are close to each other relative to the size of the domain,
+    /// which tells apart the parameters on the opposite sides of the seam of a periodic surface.
+    fn is_near(&self, a: &Vector2<T>, b: &Vector2<T>) -> bool {
+        let ratio = T::from_f64(0.1).unwrap();
+        ComplexField::abs(a.x - b.x) < (self.u.1 - self.u.0) * ratio
+            && ComplexField::abs(a.y - b.y) < (self.v.1 - self.v.0) * ratio
+    }
+
+    /// Find the boundary crossed by the segment from `inside` to `outside`.
+    /// The boundary on which `inside` lies is ignored.
+    /// Returns the index of the crossed parameter (0: u, 1: v), the boundary value and the ratio along the segment.
+    fn crossing(&self, inside: &Vector2<T>, outside: &Vector2<T>) -> Option<(usize, T, T)> {
+        let mut crossing: Option<(usize, T, T)> = None;
+        let ranges = [self.u, self.v];
+        for i in 0..2 {
+            let (min, max) = ranges[i];
+            let bound = if outside[i] < min {
+                min
+            } else if outside[i] > max {
+                max
+            } else {
+                continue;
+            };
+            // the segment running along the boundary does not cross it
+            if ComplexField::abs(inside[i] - bound) <= (max - min) * T::from_f64(1e-10).unwrap() {
+                continue;
+            }
+            let delta = outside[i] - inside[i];
+            let ratio = if delta != T::zero() {
+                (bound - inside[i]) / delta
+            } else {
+                T::zero()
+            };
+            match crossing {
+                Some((_, _, r)) if r <= ratio => {}
+                _ => crossing = Some((i, bound, ratio)),
+            }
+        }
+        crossing
+    }
+}
+
+/// A marching solver to trace intersection curves between two surfaces
+pub(crate) struct SurfaceIntersectionMarcher<'a, T: FloatingPoint> {
+    a: &'a NurbsSurface3D<T>,
+    b: &'a NurbsSurface3D<T>,
+    a_domain: Domain<T>,
+    b_domain: Domain<T>,
+    /// Step length of the marching
+    step: T,
+    /// Tolerance distance for the convergence of the Newton method
+    tolerance: T,
+    max_iters: u64,
+    max_steps: usize,
+}
+
+impl<'a, T: FloatingPoint> SurfaceIntersectionMarcher<'a, T> {
+    pub fn new(
+        a: &'a NurbsSurface3D<T>,
+        b: &'a NurbsSurface3D<T>,
+        step: T,
+        tolerance: T,
+        max_iters: u64,
+        max_steps: usize,
+    ) -> Self {
+        Self {
+            a,
+            b,
+            a_domain: Domain::new(a),
+            b_domain: Domain::new(b),
+            step,
+            tolerance,
+            max_iters,
+            max_steps,
+        }
+    }
+
+    pub fn step(&self) -> T {
+        self.step
+    }
+
+    fn sample(&self, a: Vector2<T>, b: Vector2<T>) -> SurfaceIntersectionSample<T> {
+        let pa = SurfaceFrame::new(self.a, &a).point;
+        let pb = SurfaceFrame::new(self.b, &b).point;
+        SurfaceIntersectionSample {
+            point: nalgebra::center(&pa, &pb),
+            a,
+            b,
+        }
+    }
+
+    /// Refine an initial guess to a point on both surfaces by the Gauss-Newton method with the minimum norm update.
+    pub fn try_refine(&self, a: Vector2<T>, b: Vector2<T>) -> Option<SurfaceIntersectionSample<T>> {
+        let mut a = a;
+        let mut b = b;
+        for _ in 0..self.max_iters {
+            let fa = SurfaceFrame::new(self.a, &a);
+            let fb = SurfaceFrame::new(self.b, &b);
+            let residual = fa.point - fb.point;
+            if residual.norm() < self.tolerance {
+                return Some(self.sample(a, b));
+            }
+
+            let jacobian = Matrix3x4::from_columns(&[fa.du, fa.dv, -fb.du, -fb.dv]);
+            let jjt: Matrix3<T> = jacobian * jacobian.transpose();
+            let y = jjt.lu().solve(&residual)?;
+            let delta: Vector4<T> = jacobian.transpose() * y;
+
+            a = self.a_domain.clamp(&(a - Vector2::new(delta[0], delta[1])));
+            b = self.b_domain.clamp(&(b - Vector2::new(delta[2], delta[3])));
+        }
+
+        let fa = SurfaceFrame::new(self.a, &a);
+        let fb = SurfaceFrame::new(self.b, &b);
+        if (fa.point - fb.point).norm() < self.tolerance {
+            Some(self.sample(a, b))
+        } else {
+            None
+        }
+    }
+
+    /// Compute the unit tangent direction of the intersection curve at the sample.
+    /// Returns `None` if the surfaces are tangent to each other at the sample.
+    fn tangent(&self, sample: &SurfaceIntersectionSample<T>) -> Option<Vector3<T>> {
+        let na = SurfaceFrame::new(self.a, &sample.a)
+            .normal()
+            .try_normalize(T::zero())?;
+        let nb = SurfaceFrame::new(self.b, &sample.b)
+            .normal()
+            .try_normalize(T::zero())?;
+        na.cross(&nb).try_normalize(T::from_f64(1e-8).unwrap())
+    }
+
+    /// Correct a predicted sample onto the intersection curve,
+    /// constrained on the plane whose normal is `direction` and passing through `origin + direction * distance`.
+    fn try_correct(
+        &self,
+        a: Vector2<T>,
+        b: Vector2<T>,
+        origin: &Point3<T>,
+        direction: &Vector3<T>,
+        distance: T,
+    ) -> Option<(Vector2<T>, Vector2<T>)> {
+        let mut a = a;
+        let mut b = b;
+        for _ in 0..self.max_iters {
+            let fa = SurfaceFrame::new(self.a, &a);
+            let fb = SurfaceFrame::new(self.b, &b);
+            let residual = fa.point - fb.point;
+            let plane = (fa.point - origin).dot(direction) - distance;
+            if residual.norm() < self.tolerance && ComplexField::abs(plane) < self.tolerance {
+                return Some((a, b));
+            }
+
+            let jacobian = Matrix4::new(
+                fa.du.x,
+                fa.dv.x,
+                -fb.du.x,
+                -fb.dv.x,
+                fa.du.y,
+                fa.dv.y,
+                -fb.du.y,
+                -fb.dv.y,
+                fa.du.z,
+                fa.dv.z,
+                -fb.du.z,
+                -fb.dv.z,
+                fa.du.dot(direction),
+                fa.dv.dot(direction),
+                T::zero(),
+                T::zero(),
+            );
+            let f = Vector4::new(residual.x, residual.y, residual.z, plane);
+            let delta = jacobian.lu().solve(&f)?;
+            a -= Vector2::new(delta[0], delta[1]);
+            b -= Vector2::new(delta[2], delta[3]);
+        }
+        None
+    }
+
+    /// Correct a sample onto the intersection curve with a parameter fixed on the boundary of a surface.
+    /// `index` is the index of the fixed parameter in (ua, va, ub, vb).
+    fn try_correct_on_boundary(
+        &self,
+        a: Vector2<T>,
+        b: Vector2<T>,
+        index: usize,
+        value: T,
+    ) -> Option<SurfaceIntersectionSample<T>> {
+        let mut x = Vector4::new(a.x, a.y, b.x, b.y);
+        x[index] = value;
+        for _ in 0..self.max_iters {
+            let (a, b) = (Vector2::new(x[0], x[1]), Vector2::new(x[2], x[3]));
+            let fa = SurfaceFrame::new(self.a, &a);
+            let fb = SurfaceFrame::new(self.b, &b);
+            let residual = fa.point - fb.point;
+            if residual.norm() < self.tolerance {
+                return Some(self.sample(a, b));
+            }
+
+            let columns = [fa.du, fa.dv, -fb.du, -fb.dv];
+            let free: Vec<_> = (0..4).filter(|i| *i != index).collect();
+            let jacobian =
+                Matrix3::from_columns(&[columns[free[0]], columns[free[1]], columns[free[2]]]);
+            // the damped least squares keeps the update bounded at the degenerate boundary such as the pole of a sphere
+            let jtj = jacobian.transpose() * jacobian;
+            let damping = Matrix3::identity() * (jtj.trace() * T::from_f64(1e-12).unwrap());
+            let delta = (jtj + damping)
+                .lu()
+                .solve(&(jacobian.transpose() * residual))?;
+            for (i, j) in free.iter().enumerate() {
+                x[*j] -= delta[i];
+            }
+        }
+        None
+    }
+
+    /// Settle the sample on the poles of the surfaces (e.g. the poles of a sphere),
+    /// where the parameter along the pole is arbitrary
+    fn settle(
+        &self,
+        mut sample: SurfaceIntersectionSample<T>,
+        previous: &SurfaceIntersectionSample<T>,
+    ) -> SurfaceIntersectionSample<T> {
+        self.a_domain
+            .settle(&mut sample.a, &sample.point, &previous.a, self.tolerance);
+        self.b_domain
+            .settle(&mut sample.b, &sample.point, &previous.b, self.tolerance);
+        sample
+    }
+
+    /// March from the seed in the forward or backward direction along the intersection curve.
+    /// Returns the marched samples (including the seed) and whether the curve is closed.
+    fn march(
+        &self,
+        seed: &SurfaceIntersectionSample<T>,
+        forward: bool,
+    ) -> (Vec<SurfaceIntersectionSample<T>>, bool) {
+        let mut samples = vec![seed.clone()];
+        let Some(mut prev_direction) = self.tangent(seed) else {
+            return (samples, false);
+        };
+        if !forward {
+            prev_direction = -prev_direction;
+        }
+
+        let min_step = self.step * T::from_f64(1e-3).unwrap();
+        let cos_tolerance = T::from_f64(0.985).unwrap();
+        let mut h = self.step;
+        let mut departed = false;
+
+        for _ in 0..self.max_steps {
+            let current = samples.last().unwrap().clone();
+            let Some(mut direction) = self.tangent(&current) else {
+                break;
+            };
+            if direction.dot(&prev_direction) < T::zero() {
+                direction = -direction;
+            }
+
+            let fa = SurfaceFrame::new(self.a, &current.a);
+            let fb = SurfaceFrame::new(self.b, &current.b);
+            let displacement = direction * h;
+            let (Some(da), Some(db)) = (fa.project(&displacement), fb.project(&displacement))
+            else {
+                break;
+            };
+
+            let corrected = self.try_correct(
+                current.a + da,
+                current.b + db,
+                &current.point,
+                &direction,
+                h,
+            );
+            let Some((na, nb)) = corrected else {
+                if h * T::from_f64(0.5).unwrap() < min_step {
+                    break;
+                }
+                h *= T::from_f64(0.5).unwrap();
+                continue;
+            };
+
+            // reached the boundary of the parameter domain
+            if !self.a_domain.contains(&na) || !self.b_domain.contains(&nb) {
+                // keep marching along the boundary if clamping the sample into the domain does not move it (e.g. the curve running on the seam of a periodic surface)
+                let (ca, cb) = (self.a_domain.clamp(&na), self.b_domain.clamp(&nb));
+                let on_boundary = self.sample(ca, cb);
+                if (on_boundary.point - self.sample(na, nb).point).norm() < self.tolerance
+                    && (on_boundary.point - current.point).norm() > self.tolerance
+                {
+                    samples.push(self.settle(on_boundary, &current));
+                    prev_direction = direction;
+                    continue;
+                }
+
+                let a_crossing = self.a_domain.crossing(&current.a, &na);
+                let b_crossing = self.b_domain.crossing(&current.b, &nb);
+                let crossing = match (a_crossing, b_crossing) {
+                    (Some((i, v, r0)), Some((j, w, r1))) => {
+                        if r0 <= r1 {
+                            Some((i, v, r0))
+                        } else {
+                            Some((j + 2, w, r1))
+                        }
+                    }
+                    (Some(c), None) => Some(c),
+                    (None, Some((j, w, r))) => Some((j + 2, w, r)),
+                    (None, None) => None,
+                };
+                if let Some((index, value, ratio)) = crossing {
+                    let ia = current.a + (na - current.a) * ratio;
+                    let ib = current.b + (nb - current.b) * ratio;
+                    if let Some(end) = self.try_correct_on_boundary(ia, ib, index, value) {
+                        if (end.point - current.point).norm() > self.tolerance {
+                            let end = SurfaceIntersectionSample {
+                                a: self.a_domain.clamp(&end.a),
+                                b: self.b_domain.clamp(&end.b),
+                                ..end
+                            };
+                            samples.push(self.settle(end, &current));
+                        }
+                    }
+                }
+                return (samples, false);
+            }
+
+            let next = self.settle(self.sample(na, nb), &current);
+
+            // shrink the step if the curve turns too much
+            match self.tangent(&next) {
+                Some(t) if ComplexField::abs(t.dot(&direction)) < cos_tolerance && h > min_step => {
+                    h *= T::from_f64(0.5).unwrap();
+                    continue;
+                }
+                _ => {}
+            }
+
+            // detect the loop closure, which is not across the seam of a periodic surface
+            let distance = (next.point - seed.point).norm();
+            if departed
+                && distance < h
+                && self.a_domain.is_near(&next.a, &seed.a)
+                && self.b_domain.is_near(&next.b, &seed.b)
+            {
+                return (samples, true);
+            }
+            if distance > self.step * T::from_usize(2).unwrap() {
+                departed = true;
+            }
+
+            samples.push(next);
+            prev_direction = direction;
+            h = (h * T::from_f64(1.5).unwrap()).min(self.step);
+        }
+
+        (samples, false)
+    }
+
+    /// Check if the parameters of the samples are close to each other on the first & second surfaces
+    pub fn is_near(
+        &self,
+        x: &SurfaceIntersectionSample<T>,
+        y: &SurfaceIntersectionSample<T>,
+    ) -> (bool, bool) {
+        (
+            self.a_domain.is_near(&x.a, &y.a),
+            self.b_domain.is_near(&x.b, &y.b),
+        )
+    }
+
+    /// Trace the intersection curve passing through the seed in both directions.
+    pub fn trace(&self, seed: &SurfaceIntersectionSample<T>) -> SurfaceIntersectionPolyline<T> {
+        // the seed near the pole of a surface has the ill-conditioned parameter, so leave the curve to the other seeds marching into the pole
+        let near_pole = |domain: &Domain<T>| {
+            domain
+                .poles
+                .iter()
+                .any(|(_, _, pole)| (pole - seed.point).norm() < self.step)
+        };
+        if near_pole(&self.a_domain) || near_pole(&self.b_domain) {
+            return SurfaceIntersectionPolyline {
+                samples: vec![seed.clone()],
+                closed: false,
+            };
+        }
+
+        let (forward, closed) = self.march(seed, true);
+        if closed {
+            return SurfaceIntersectionPolyline {
+                samples: forward,
+                closed,
+            };
+        }
+        let (backward, closed) = self.march(seed, false);
+        if closed {
+            return SurfaceIntersectionPolyline {
+                samples: backward.into_iter().rev().collect(),
+                closed,
+            };
+        }
+        let samples: Vec<_> = backward.into_iter().skip(1).rev().chain(forward).collect();
+
+        // the curve is also closed if both ends meet on the boundaries (e.g. the seam of a periodic surface)
+        let closed = samples.len() > 2
+            && (samples[0].point - samples[samples.len() - 1].point).norm()
+                < self.tolerance * T::from_usize(10).unwrap();
+        SurfaceIntersectionPolyline { samples, closed }
+    }
+}
+
+impl<T: FloatingPoint> SurfaceIntersectionPolyline<T> {
+    /// Compute the minimum distance from the point to the polyline
+    pub fn distance(&self, point: &Point3<T>) -> T {
+        let n = self.samples.len();
+        if n == 1 {
+            return (self.samples[0].point - point).norm();
+        }
+        let segments = if self.closed { n } else { n - 1 };
+        (0..segments)
+            .map(|i| {
+                let p0 = &self.samples[i].point;
+                let p1 = &self.samples[(i + 1) % n].point;
+                let d = p1 - p0;
+                let l2 = d.norm_squared();
+                let t = if l2 > T::zero() {
+                    nalgebra::clamp((point - p0).dot(&d) / l2, T::zero(), T::one())
+                } else {
+                    T::zero()
+                };
+                (p0 + d * t - point).norm()
+            })
+            .fold(T::max_value().unwrap(), |a, b| a.min(b))
+    }
+}
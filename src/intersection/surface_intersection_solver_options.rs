@@ -0,0 +1,58 @@
+use crate::misc::FloatingPoint;
+
+/// Hyperparameters for the surface intersection solver.
+#[derive(Clone, Debug)]
+pub struct SurfaceIntersectionSolverOptions<T: FloatingPoint> {
+    /// Minimum distance between two points to consider them as intersecting.
+    pub minimum_distance: T,
+    /// Knot domain division for the threshold of the bounding box tree in each direction.
+    /// Before marching along intersection curves, perform intersection detection between the bounding boxes that enclose the patches of surfaces as a sub-problem
+    /// to find initial points of the intersection curves.
+    pub knot_domain_division: usize,
+    /// Step length of the marching along an intersection curve.
+    /// If `None`, the step length is determined by the size of the surfaces.
+    pub marching_step: Option<T>,
+    /// Maximum number of marching steps for each intersection curve.
+    pub max_marching_steps: usize,
+    /// Maximum number of iterations for the Newton method.
+    pub max_iters: u64,
+}
+
+impl<T: FloatingPoint> Default for SurfaceIntersectionSolverOptions<T> {
+    fn default() -> Self {
+        Self {
+            minimum_distance: T::from_f64(1e-6).unwrap(),
+            knot_domain_division: 8,
+            marching_step: None,
+            max_marching_steps: 2048,
+            max_iters: 32,
+        }
+    }
+}
+
+impl<T: FloatingPoint> SurfaceIntersectionSolverOptions<T> {
+    pub fn with_minimum_distance(mut self, minimum_distance: T) -> Self {
+        self.minimum_distance = minimum_distance;
+        self
+    }
+
+    pub fn with_knot_domain_division(mut self, knot_domain_division: usize) -> Self {
+        self.knot_domain_division = knot_domain_division;
+        self
+    }
+
+    pub fn with_marching_step(mut self, marching_step: T) -> Self {
+        self.marching_step = Some(marching_step);
+        self
+    }
+
+    pub fn with_max_marching_steps(mut self, max_marching_steps: usize) -> Self {
+        self.max_marching_steps = max_marching_steps;
+        self
+    }
+
+    pub fn with_max_iters(mut self, max_iters: u64) -> Self {
+        self.max_iters = max_iters;
+        self
+    }
+}
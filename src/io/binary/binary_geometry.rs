@@ -0,0 +1,318 @@
+use nalgebra::{
+    allocator::Allocator, DefaultAllocator, DimName, DimNameDiff, DimNameSub, OPoint, U1,
+};
+
+use crate::{
+    curve::{CompoundCurve, NurbsCurve},
+    misc::FloatingPoint,
+    surface::{NurbsSurface, TrimmedSurface},
+};
+
+/// The magic bytes at the head of the binary geometry
+pub const BINARY_GEOMETRY_MAGIC: &[u8; 4] = b"CRVB";
+
+/// The version of the binary geometry layout written by this crate
+pub const BINARY_GEOMETRY_VERSION: u16 = 1;
+
+fn write_u32(bytes: &mut Vec<u8>, value: usize) {
+    bytes.extend((value as u32).to_le_bytes());
+}
+
+fn write_real<T: FloatingPoint>(bytes: &mut Vec<u8>, value: T) {
+    bytes.extend(value.to_f64().unwrap().to_le_bytes());
+}
+
+fn take<'a>(bytes: &mut &'a [u8], n: usize) -> anyhow::Result<&'a [u8]> {
+    anyhow::ensure!(bytes.len() >= n, "Unexpected end of the binary geometry");
+    let (head, tail) = bytes.split_at(n);
+    *bytes = tail;
+    Ok(head)
+}
+
+fn read_u32(bytes: &mut &[u8]) -> anyhow::Result<usize> {
+    Ok(u32::from_le_bytes(take(bytes, 4)?.try_into()?) as usize)
+}
+
+fn read_real<T: FloatingPoint>(bytes: &mut &[u8]) -> anyhow::Result<T> {
+    let value = f64::from_le_bytes(take(bytes, 8)?.try_into()?);
+    T::from_f64(value).ok_or(anyhow::anyhow!("Invalid real number {}", value))
+}
+
+fn write_reals<T: FloatingPoint>(bytes: &mut Vec<u8>, values: &[T]) {
+    write_u32(bytes, values.len());
+    values.iter().for_each(|v| write_real(bytes, *v));
+}
+
+fn read_reals<T: FloatingPoint>(bytes: &mut &[u8]) -> anyhow::Result<Vec<T>> {
+    let n = read_u32(bytes)?;
+    (0..n).map(|_| read_real(bytes)).collect()
+}
+
+fn write_point<T: FloatingPoint, D: DimName>(bytes: &mut Vec<u8>, point: &OPoint<T, D>)
+where
+    DefaultAllocator: Allocator<D>,
+{
+    point.iter().for_each(|v| write_real(bytes, *v));
+}
+
+fn read_point<T: FloatingPoint, D: DimName>(bytes: &mut &[u8]) -> anyhow::Result<OPoint<T, D>>
+where
+    DefaultAllocator: Allocator<D>,
+{
+    let mut point = OPoint::<T, D>::origin();
+    for i in 0..D::dim() {
+        point[i] = read_real(bytes)?;
+    }
+    Ok(point)
+}
+
+/// Check the dimension of the homogeneous control points stored in the body
+fn read_dimension<D: DimName>(bytes: &mut &[u8]) -> anyhow::Result<()> {
+    let dimension = read_u32(bytes)?;
+    anyhow::ensure!(
+        dimension == D::dim(),
+        "The dimension of the control points is {}, expected {}",
+        dimension,
+        D::dim()
+    );
+    Ok(())
+}
+
+/// A geometry encoded into the compact binary layout for fast caching
+/// The layout starts with the magic bytes, the version as little endian u16 & the kind of the geometry,
+/// followed by the body where the integers are little endian u32 & the real numbers are little endian f64 regardless of the scalar type.
+/// # Example
+/// ```
+/// use curvo::prelude::*;
+/// use nalgebra::{Point2, Point3, Vector2, Vector3};
+///
+/// let line = NurbsCurve3D::polyline(&[Point3::origin(), Point3::new(1., 0., 0.)]);
+/// let plane = NurbsSurface::extrude(&line, &Vector3::y());
+/// let hole = NurbsCurve2D::try_circle(&Point2::new(0.5, 0.5), &Vector2::x(), &Vector2::y(), 0.25).unwrap();
+/// let trimmed = TrimmedSurface::try_new(plane, None, vec![hole.into()]).unwrap();
+///
+/// let bytes = trimmed.to_binary();
+/// assert_eq!(&bytes[0..4], BINARY_GEOMETRY_MAGIC);
+/// let decoded = TrimmedSurface::<f64>::try_from_binary(&bytes).unwrap();
+/// assert_eq!(decoded.surface().control_points(), trimmed.surface().control_points());
+/// assert_eq!(decoded.interiors(), trimmed.interiors());
+///
+/// // the kind of the geometry is checked
+/// assert!(NurbsCurve3D::<f64>::try_from_binary(&bytes).is_err());
+/// ```
+pub trait BinaryGeometry: Sized {
+    /// The tag identifying the kind of the geometry in the header
+    const KIND: u8;
+
+    /// Append the body of the geometry to the bytes
+    fn encode_body(&self, bytes: &mut Vec<u8>);
+
+    /// Decode the body of the geometry, advancing the bytes
+    fn decode_body(bytes: &mut &[u8]) -> anyhow::Result<Self>;
+
+    /// Encode the geometry with the header
+    fn to_binary(&self) -> Vec<u8> {
+        let mut bytes = BINARY_GEOMETRY_MAGIC.to_vec();
+        bytes.extend(BINARY_GEOMETRY_VERSION.to_le_bytes());
+        bytes.push(Self::KIND);
+        self.encode_body(&mut bytes);
+        bytes
+    }
+
+    /// Decode the geometry, checking the header
+    fn try_from_binary(bytes: &[u8]) -> anyhow::Result<Self> {
+        let mut bytes = bytes;
+        anyhow::ensure!(
+            take(&mut bytes, 4)? == BINARY_GEOMETRY_MAGIC,
+            "The bytes are not a binary geometry"
+        );
+        let version = u16::from_le_bytes(take(&mut bytes, 2)?.try_into()?);
+        anyhow::ensure!(
+            version <= BINARY_GEOMETRY_VERSION,
+            "The binary geometry version {} is not supported",
+            version
+        );
+        let kind = take(&mut bytes, 1)?[0];
+        anyhow::ensure!(
+            kind == Self::KIND,
+            "The kind of the binary geometry is {}, expected {}",
+            kind,
+            Self::KIND
+        );
+        let geometry = Self::decode_body(&mut bytes)?;
+        anyhow::ensure!(
+            bytes.is_empty(),
+            "{} bytes remain after the binary geometry",
+            bytes.len()
+        );
+        Ok(geometry)
+    }
+
+    /// Write the encoded geometry into the writer
+    fn write_binary<W: std::io::Write>(&self, mut writer: W) -> anyhow::Result<()> {
+        writer.write_all(&self.to_binary())?;
+        Ok(())
+    }
+
+    /// Read the whole reader & decode the geometry
+    fn read_binary<R: std::io::Read>(mut reader: R) -> anyhow::Result<Self> {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes)?;
+        Self::try_from_binary(&bytes)
+    }
+}
+
+impl<T: FloatingPoint, D: DimName> BinaryGeometry for NurbsCurve<T, D>
+where
+    DefaultAllocator: Allocator<D>,
+    D: DimNameSub<U1>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    const KIND: u8 = 1;
+
+    fn encode_body(&self, bytes: &mut Vec<u8>) {
+        write_u32(bytes, D::dim());
+        write_u32(bytes, self.degree());
+        write_reals(bytes, self.knots().as_slice());
+        write_u32(bytes, self.control_points().len());
+        self.control_points()
+            .iter()
+            .for_each(|p| write_point(bytes, p));
+    }
+
+    fn decode_body(bytes: &mut &[u8]) -> anyhow::Result<Self> {
+        read_dimension::<D>(bytes)?;
+        let degree = read_u32(bytes)?;
+        let knots = read_reals(bytes)?;
+        let n = read_u32(bytes)?;
+        let control_points = (0..n)
+            .map(|_| read_point(bytes))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Self::try_new(degree, control_points, knots)
+    }
+}
+
+impl<T: FloatingPoint, D: DimName> BinaryGeometry for NurbsSurface<T, D>
+where
+    DefaultAllocator: Allocator<D>,
+    D: DimNameSub<U1>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    const KIND: u8 = 2;
+
+    fn encode_body(&self, bytes: &mut Vec<u8>) {
+        write_u32(bytes, D::dim());
+        write_u32(bytes, self.u_degree());
+        write_u32(bytes, self.v_degree());
+        write_reals(bytes, self.u_knots().as_slice());
+        write_reals(bytes, self.v_knots().as_slice());
+        let control_points = self.control_points();
+        write_u32(bytes, control_points.len());
+        write_u32(bytes, control_points.first().map_or(0, |row| row.len()));
+        control_points
+            .iter()
+            .flatten()
+            .for_each(|p| write_point(bytes, p));
+    }
+
+    fn decode_body(bytes: &mut &[u8]) -> anyhow::Result<Self> {
+        read_dimension::<D>(bytes)?;
+        let u_degree = read_u32(bytes)?;
+        let v_degree = read_u32(bytes)?;
+        let u_knots: Vec<T> = read_reals(bytes)?;
+        let v_knots: Vec<T> = read_reals(bytes)?;
+        let rows = read_u32(bytes)?;
+        let columns = read_u32(bytes)?;
+        anyhow::ensure!(
+            u_knots.len() == rows + u_degree + 1 && v_knots.len() == columns + v_degree + 1,
+            "Invalid number of knots for {}x{} control points",
+            rows,
+            columns
+        );
+        let control_points = (0..rows)
+            .map(|_| (0..columns).map(|_| read_point(bytes)).collect())
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(Self::new(
+            u_degree,
+            v_degree,
+            u_knots,
+            v_knots,
+            control_points,
+        ))
+    }
+}
+
+impl<T: FloatingPoint, D: DimName> BinaryGeometry for CompoundCurve<T, D>
+where
+    DefaultAllocator: Allocator<D>,
+    D: DimNameSub<U1>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    const KIND: u8 = 3;
+
+    fn encode_body(&self, bytes: &mut Vec<u8>) {
+        write_u32(bytes, self.spans().len());
+        self.spans().iter().for_each(|s| s.encode_body(bytes));
+    }
+
+    fn decode_body(bytes: &mut &[u8]) -> anyhow::Result<Self> {
+        let n = read_u32(bytes)?;
+        let spans = (0..n)
+            .map(|_| NurbsCurve::decode_body(bytes))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Self::try_new(spans)
+    }
+}
+
+impl<T: FloatingPoint> BinaryGeometry for TrimmedSurface<T> {
+    const KIND: u8 = 4;
+
+    fn encode_body(&self, bytes: &mut Vec<u8>) {
+        self.surface().encode_body(bytes);
+        match self.exterior() {
+            Some(exterior) => {
+                bytes.push(1);
+                exterior.encode_body(bytes);
+            }
+            None => bytes.push(0),
+        }
+        write_u32(bytes, self.interiors().len());
+        self.interiors().iter().for_each(|l| l.encode_body(bytes));
+    }
+
+    fn decode_body(bytes: &mut &[u8]) -> anyhow::Result<Self> {
+        let surface = NurbsSurface::decode_body(bytes)?;
+        let exterior = match take(bytes, 1)?[0] {
+            0 => None,
+            _ => Some(CompoundCurve::decode_body(bytes)?),
+        };
+        let n = read_u32(bytes)?;
+        let interiors = (0..n)
+            .map(|_| CompoundCurve::decode_body(bytes))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Self::try_new(surface, exterior, interiors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::Point2;
+
+    use super::*;
+    use crate::curve::NurbsCurve2D;
+
+    #[test]
+    fn invalid_bytes() {
+        let curve = NurbsCurve2D::polyline(&[Point2::new(0., 0.), Point2::new(1., 2.)]);
+        let bytes = curve.to_binary();
+        assert_eq!(NurbsCurve2D::<f64>::try_from_binary(&bytes).unwrap(), curve);
+        // truncated
+        assert!(NurbsCurve2D::<f64>::try_from_binary(&bytes[..bytes.len() - 1]).is_err());
+        // wrong magic
+        let mut wrong = bytes.clone();
+        wrong[0] = b'X';
+        assert!(NurbsCurve2D::<f64>::try_from_binary(&wrong).is_err());
+        // wrong dimension
+        assert!(crate::curve::NurbsCurve3D::<f64>::try_from_binary(&bytes).is_err());
+    }
+}
@@ -0,0 +1,3 @@
+pub mod binary_geometry;
+
+pub use binary_geometry::*;
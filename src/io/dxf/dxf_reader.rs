@@ -0,0 +1,297 @@
+use nalgebra::{Point3, Point4, Vector3};
+
+use crate::{
+    curve::{CompoundCurve3D, NurbsCurve3D},
+    misc::{FloatingPoint, Invertible},
+};
+
+/// An entity in the ENTITIES section with its group code & value pairs
+#[derive(Clone, Debug)]
+struct Entity {
+    kind: String,
+    pairs: Vec<(i32, String)>,
+}
+
+impl Entity {
+    fn value(&self, code: i32) -> Option<&str> {
+        self.pairs
+            .iter()
+            .find(|(c, _)| *c == code)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn real<T: FloatingPoint>(&self, code: i32) -> anyhow::Result<T> {
+        let v = self.value(code).ok_or(anyhow::anyhow!(
+            "The group code {} of {} is missing",
+            code,
+            self.kind
+        ))?;
+        Ok(T::from_f64(v.parse()?).unwrap())
+    }
+
+    fn real_or<T: FloatingPoint>(&self, code: i32, default: T) -> anyhow::Result<T> {
+        match self.value(code) {
+            Some(v) => Ok(T::from_f64(v.parse()?).unwrap()),
+            None => Ok(default),
+        }
+    }
+
+    fn integer(&self, code: i32) -> anyhow::Result<i64> {
+        Ok(self
+            .value(code)
+            .map(|v| v.parse())
+            .transpose()?
+            .unwrap_or(0))
+    }
+
+    fn reals<T: FloatingPoint>(&self, code: i32) -> anyhow::Result<Vec<T>> {
+        self.pairs
+            .iter()
+            .filter(|(c, _)| *c == code)
+            .map(|(_, v)| Ok(T::from_f64(v.parse()?).unwrap()))
+            .collect()
+    }
+
+    /// Collect the points starting with the x group code, each followed by the y & z group codes
+    fn points<T: FloatingPoint>(&self, code: i32) -> anyhow::Result<Vec<Point3<T>>> {
+        let mut points: Vec<Point3<T>> = vec![];
+        for (c, v) in self.pairs.iter() {
+            let value = || -> anyhow::Result<T> { Ok(T::from_f64(v.parse()?).unwrap()) };
+            if *c == code {
+                points.push(Point3::new(value()?, T::zero(), T::zero()));
+            } else if let Some(p) = points.last_mut() {
+                if *c == code + 10 {
+                    p.y = value()?;
+                } else if *c == code + 20 {
+                    p.z = value()?;
+                }
+            }
+        }
+        Ok(points)
+    }
+
+    fn center<T: FloatingPoint>(&self) -> anyhow::Result<Point3<T>> {
+        Ok(Point3::new(
+            self.real(10)?,
+            self.real(20)?,
+            self.real_or(30, T::zero())?,
+        ))
+    }
+}
+
+/// Create the arc on a plane parallel to the XY plane, turning counterclockwise if the sweep is positive
+fn arc<T: FloatingPoint>(
+    center: &Point3<T>,
+    radius: T,
+    start: T,
+    sweep: T,
+) -> anyhow::Result<NurbsCurve3D<T>> {
+    let (x, y) = (Vector3::x(), Vector3::y());
+    if sweep > T::zero() {
+        NurbsCurve3D::try_arc(center, &x, &y, radius, start, start + sweep)
+    } else {
+        NurbsCurve3D::try_arc(center, &x, &y, radius, start + sweep, start).map(|c| c.inverse())
+    }
+}
+
+/// A reader to load the SPLINE, LWPOLYLINE, ARC & CIRCLE entities from an ASCII DXF file
+/// Each entity is converted into a compound curve, and the object coordinate systems other than the world coordinate system are not supported.
+/// # Example
+/// ```
+/// use curvo::prelude::*;
+/// use nalgebra::{Point3, Vector3};
+///
+/// let circle = NurbsCurve3D::try_circle(&Point3::origin(), &Vector3::x(), &Vector3::y(), 1.).unwrap();
+/// let mut writer = DxfWriter::new();
+/// writer.add_curve(&circle);
+/// writer.add_polyline(&[Point3::new(0., 0., 0.), Point3::new(1., 0., 0.), Point3::new(1., 1., 0.)], true);
+/// writer.add_arc(&Point3::origin(), 2., 0., 90.);
+/// writer.add_circle(&Point3::origin(), 3.);
+///
+/// let reader = DxfReader::try_parse(&writer.to_dxf_string()).unwrap();
+/// let curves = reader.try_curves::<f64>().unwrap();
+/// assert_eq!(curves.len(), 4);
+/// assert!((curves[0].point_at(0.3) - circle.point_at(0.3)).norm() < 1e-8);
+/// // the closed polyline has 3 segments
+/// assert_eq!(curves[1].spans().len(), 3);
+/// assert!(curves[1].is_closed(None));
+/// let (_, end) = curves[2].knots_domain();
+/// assert!((curves[2].point_at(end) - Point3::new(0., 2., 0.)).norm() < 1e-8);
+/// assert!(curves[3].is_closed(None));
+/// ```
+#[derive(Clone, Debug)]
+pub struct DxfReader {
+    entities: Vec<Entity>,
+}
+
+impl DxfReader {
+    /// Parse the group code & value pairs of the entities in the ENTITIES section
+    pub fn try_parse(dxf: &str) -> anyhow::Result<Self> {
+        let lines = dxf.lines().collect::<Vec<_>>();
+        let mut entities: Vec<Entity> = vec![];
+        let mut in_entities = false;
+        let mut section_name = false;
+        for pair in lines.chunks(2) {
+            let [code, value] = pair else {
+                break;
+            };
+            let code = code.trim().parse::<i32>()?;
+            let value = value.trim();
+            if code == 0 {
+                if value == "ENDSEC" {
+                    in_entities = false;
+                }
+                section_name = value == "SECTION";
+                if in_entities {
+                    entities.push(Entity {
+                        kind: value.to_string(),
+                        pairs: vec![],
+                    });
+                }
+                continue;
+            }
+            if section_name && code == 2 {
+                in_entities = value == "ENTITIES";
+                section_name = false;
+                continue;
+            }
+            if in_entities {
+                if let Some(entity) = entities.last_mut() {
+                    entity.pairs.push((code, value.to_string()));
+                }
+            }
+        }
+        Ok(Self { entities })
+    }
+
+    fn spline<T: FloatingPoint>(entity: &Entity) -> anyhow::Result<NurbsCurve3D<T>> {
+        let degree = entity.integer(71)? as usize;
+        let knots = entity.reals::<T>(40)?;
+        let points = entity.points::<T>(10)?;
+        if points.is_empty() {
+            // the spline only defined by the fit points
+            let fit = entity.points::<T>(11)?;
+            anyhow::ensure!(fit.len() > 1, "The spline has no control or fit points");
+            return NurbsCurve3D::try_interpolate(&fit, degree.min(fit.len() - 1));
+        }
+        let weights = entity.reals::<T>(41)?;
+        let control_points = points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                let w = weights.get(i).copied().unwrap_or(T::one());
+                Point4::new(p.x * w, p.y * w, p.z * w, w)
+            })
+            .collect();
+        NurbsCurve3D::try_new(degree, control_points, knots)
+    }
+
+    fn polyline<T: FloatingPoint>(entity: &Entity) -> anyhow::Result<CompoundCurve3D<T>> {
+        let elevation = entity.real_or(38, T::zero())?;
+        // the bulge of each vertex is the tangent of a quarter of the sweep angle of the segment starting at it
+        let mut vertices: Vec<(Point3<T>, T)> = vec![];
+        for (c, v) in entity.pairs.iter() {
+            if !matches!(c, 10 | 20 | 42) {
+                continue;
+            }
+            let value = T::from_f64(v.parse()?).unwrap();
+            match c {
+                10 => vertices.push((Point3::new(value, T::zero(), elevation), T::zero())),
+                20 => {
+                    if let Some((p, _)) = vertices.last_mut() {
+                        p.y = value;
+                    }
+                }
+                42 => {
+                    if let Some((_, bulge)) = vertices.last_mut() {
+                        *bulge = value;
+                    }
+                }
+                _ => {}
+            }
+        }
+        if entity.integer(70)? & 1 == 1 {
+            if let Some(first) = vertices.first().cloned() {
+                vertices.push(first);
+            }
+        }
+        anyhow::ensure!(vertices.len() > 1, "The polyline has less than 2 vertices");
+
+        let two = T::from_f64(2.).unwrap();
+        let spans = vertices
+            .windows(2)
+            .filter(|w| (w[1].0 - w[0].0).norm() > T::from_f64(1e-12).unwrap())
+            .map(|w| {
+                let ((p0, bulge), (p1, _)) = (w[0], w[1]);
+                if bulge == T::zero() {
+                    return Ok(NurbsCurve3D::polyline(&[p0, p1]));
+                }
+                let chord = p1 - p0;
+                let length = chord.norm();
+                let sweep = bulge.atan() * two * two;
+                let normal = Vector3::new(-chord.y, chord.x, T::zero()) / length;
+                let center = p0
+                    + chord / two
+                    + normal * (length / two * (T::one() - bulge * bulge) / (two * bulge));
+                let radius = (p0 - center).norm();
+                let start = (p0.y - center.y).atan2(p0.x - center.x);
+                arc(&center, radius, start, sweep)
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        CompoundCurve3D::try_new(spans)
+    }
+
+    fn arc<T: FloatingPoint>(entity: &Entity) -> anyhow::Result<NurbsCurve3D<T>> {
+        let radians = T::pi() / T::from_f64(180.).unwrap();
+        let start = entity.real::<T>(50)? * radians;
+        let mut end = entity.real::<T>(51)? * radians;
+        // the arc turns counterclockwise from the start angle to the end angle
+        while end <= start {
+            end += T::two_pi();
+        }
+        arc(&entity.center()?, entity.real(40)?, start, end - start)
+    }
+
+    fn circle<T: FloatingPoint>(entity: &Entity) -> anyhow::Result<NurbsCurve3D<T>> {
+        NurbsCurve3D::try_circle(
+            &entity.center()?,
+            &Vector3::x(),
+            &Vector3::y(),
+            entity.real(40)?,
+        )
+    }
+
+    /// Convert the supported entities into the compound curves in the order of the file
+    pub fn try_curves<T: FloatingPoint>(&self) -> anyhow::Result<Vec<CompoundCurve3D<T>>> {
+        self.entities
+            .iter()
+            .filter_map(|e| match e.kind.as_str() {
+                "SPLINE" => Some(Self::spline(e).map(CompoundCurve3D::from)),
+                "LWPOLYLINE" => Some(Self::polyline(e)),
+                "ARC" => Some(Self::arc(e).map(CompoundCurve3D::from)),
+                "CIRCLE" => Some(Self::circle(e).map(CompoundCurve3D::from)),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn polyline_bulge() {
+        let dxf = "0\nSECTION\n2\nENTITIES\n0\nLWPOLYLINE\n8\n0\n100\nAcDbPolyline\n90\n2\n70\n0\n10\n0.0\n20\n0.0\n42\n1.0\n10\n2.0\n20\n0.0\n0\nENDSEC\n0\nEOF\n";
+        let curves = DxfReader::try_parse(dxf)
+            .unwrap()
+            .try_curves::<f64>()
+            .unwrap();
+        assert_eq!(curves.len(), 1);
+        // the bulge of 1 is a half circle turning counterclockwise
+        let (start, end) = curves[0].knots_domain();
+        let mid = curves[0].point_at((start + end) * 0.5);
+        assert!((mid - Point3::new(1., -1., 0.)).norm() < 1e-8);
+        assert!((curves[0].point_at(end) - Point3::new(2., 0., 0.)).norm() < 1e-8);
+    }
+}
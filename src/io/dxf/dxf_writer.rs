@@ -0,0 +1,154 @@
+use std::fmt::Write as _;
+
+use nalgebra::Point3;
+
+use crate::{curve::NurbsCurve3D, misc::FloatingPoint};
+
+/// A writer to export curves as the SPLINE, LWPOLYLINE, ARC & CIRCLE entities of an ASCII DXF file
+/// # Example
+/// ```
+/// use curvo::prelude::*;
+/// use nalgebra::{Point3, Vector3};
+///
+/// let circle = NurbsCurve3D::try_circle(&Point3::origin(), &Vector3::x(), &Vector3::y(), 1.).unwrap();
+///
+/// let mut writer = DxfWriter::new();
+/// writer.add_curve(&circle);
+/// writer.add_polyline(&[Point3::new(0., 0., 0.), Point3::new(1., 0., 0.), Point3::new(1., 1., 0.)], true);
+/// writer.add_arc(&Point3::origin(), 2., 0., 90.);
+/// writer.add_circle(&Point3::origin(), 3.);
+/// let dxf = writer.to_dxf_string();
+/// assert!(dxf.contains("SPLINE"));
+/// assert!(dxf.contains("LWPOLYLINE"));
+/// assert!(dxf.contains("\nARC\n"));
+/// assert!(dxf.contains("\nCIRCLE\n"));
+/// assert!(dxf.trim_end().ends_with("EOF"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct DxfWriter {
+    /// The group code & value pairs of the entities
+    entities: Vec<(i32, String)>,
+}
+
+impl DxfWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn push<V: ToString>(&mut self, code: i32, value: V) {
+        self.entities.push((code, value.to_string()));
+    }
+
+    fn real<T: FloatingPoint>(&mut self, code: i32, value: T) {
+        self.push(code, value.to_f64().unwrap());
+    }
+
+    fn point<T: FloatingPoint>(&mut self, code: i32, p: &Point3<T>) {
+        self.real(code, p.x);
+        self.real(code + 10, p.y);
+        self.real(code + 20, p.z);
+    }
+
+    fn header(&mut self, entity: &str, subclass: &str) {
+        self.push(0, entity);
+        self.push(8, "0");
+        self.push(100, "AcDbEntity");
+        self.push(100, subclass);
+    }
+
+    /// Add the curve as a SPLINE entity
+    pub fn add_curve<T: FloatingPoint>(&mut self, curve: &NurbsCurve3D<T>) {
+        let weights = curve.weights();
+        let is_rational = weights.iter().any(|w| *w != T::one());
+        let points = curve.dehomogenized_control_points();
+        let (start, end) = curve.knots_domain();
+        let is_closed =
+            (curve.point_at(start) - curve.point_at(end)).norm() < T::from_f64(1e-8).unwrap();
+
+        self.header("SPLINE", "AcDbSpline");
+        let flags = if is_closed { 1 } else { 0 } | if is_rational { 4 } else { 0 };
+        self.push(70, flags);
+        self.push(71, curve.degree());
+        self.push(72, curve.knots().len());
+        self.push(73, points.len());
+        self.push(74, 0);
+        for k in curve.knots().iter() {
+            self.real(40, *k);
+        }
+        if is_rational {
+            for w in weights {
+                self.real(41, w);
+            }
+        }
+        for p in points.iter() {
+            self.point(10, p);
+        }
+    }
+
+    /// Add the polyline on a plane parallel to the XY plane as a LWPOLYLINE entity
+    /// The elevation of the polyline is the z coordinate of its first point.
+    pub fn add_polyline<T: FloatingPoint>(&mut self, points: &[Point3<T>], closed: bool) {
+        self.header("LWPOLYLINE", "AcDbPolyline");
+        self.push(90, points.len());
+        self.push(70, if closed { 1 } else { 0 });
+        if let Some(first) = points.first() {
+            self.real(38, first.z);
+        }
+        for p in points {
+            self.real(10, p.x);
+            self.real(20, p.y);
+        }
+    }
+
+    /// Add the counterclockwise arc on a plane parallel to the XY plane as an ARC entity
+    /// * `start_angle` - The start angle in degrees
+    /// * `end_angle` - The end angle in degrees
+    pub fn add_arc<T: FloatingPoint>(
+        &mut self,
+        center: &Point3<T>,
+        radius: T,
+        start_angle: T,
+        end_angle: T,
+    ) {
+        self.header("ARC", "AcDbCircle");
+        self.point(10, center);
+        self.real(40, radius);
+        self.push(100, "AcDbArc");
+        self.real(50, start_angle);
+        self.real(51, end_angle);
+    }
+
+    /// Add the circle on a plane parallel to the XY plane as a CIRCLE entity
+    pub fn add_circle<T: FloatingPoint>(&mut self, center: &Point3<T>, radius: T) {
+        self.header("CIRCLE", "AcDbCircle");
+        self.point(10, center);
+        self.real(40, radius);
+    }
+
+    /// Serialize the entities into an ASCII DXF file
+    pub fn to_dxf_string(&self) -> String {
+        let mut out = String::new();
+        let mut pair = |code: i32, value: &str| {
+            let _ = writeln!(out, "{:>3}\n{}", code, value);
+        };
+        pair(0, "SECTION");
+        pair(2, "HEADER");
+        pair(9, "$ACADVER");
+        pair(1, "AC1015");
+        pair(0, "ENDSEC");
+        pair(0, "SECTION");
+        pair(2, "ENTITIES");
+        for (code, value) in self.entities.iter() {
+            pair(*code, value);
+        }
+        pair(0, "ENDSEC");
+        pair(0, "EOF");
+        out
+    }
+
+    /// Write the DXF file into the writer
+    pub fn write<W: std::io::Write>(&self, mut writer: W) -> anyhow::Result<()> {
+        writer.write_all(self.to_dxf_string().as_bytes())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,5 @@
+pub mod dxf_reader;
+pub mod dxf_writer;
+
+pub use dxf_reader::*;
+pub use dxf_writer::*;
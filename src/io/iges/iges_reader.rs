@@ -0,0 +1,459 @@
+use std::collections::BTreeMap;
+
+use argmin::core::ArgminFloat;
+use nalgebra::{allocator::Allocator, DefaultAllocator, DimName, OPoint, Point2};
+
+use crate::{
+    curve::{CompoundCurve2D, NurbsCurve, NurbsCurve2D, NurbsCurve3D},
+    misc::FloatingPoint,
+    surface::{NurbsSurface3D, TrimCurve, TrimSide, TrimmedSurface},
+};
+
+/// A directory entry with its parameters
+#[derive(Clone, Debug)]
+struct Entry {
+    entity_type: usize,
+    /// The status number composed of the blank, subordinate, entity use & hierarchy flags
+    status: String,
+    /// The parameters following the entity type
+    parameters: Vec<String>,
+}
+
+impl Entry {
+    fn parameter(&self, index: usize) -> anyhow::Result<&str> {
+        self.parameters
+            .get(index)
+            .map(|p| p.as_str())
+            .ok_or(anyhow::anyhow!(
+                "The parameter {} of the entity {} is missing",
+                index,
+                self.entity_type
+            ))
+    }
+
+    fn integer(&self, index: usize) -> anyhow::Result<usize> {
+        let p = self.parameter(index)?;
+        if p.is_empty() {
+            return Ok(0);
+        }
+        Ok(p.parse()?)
+    }
+
+    fn real<T: FloatingPoint>(&self, index: usize) -> anyhow::Result<T> {
+        let p = self.parameter(index)?;
+        if p.is_empty() {
+            return Ok(T::zero());
+        }
+        let x: f64 = p.replace(['D', 'd'], "E").parse()?;
+        Ok(T::from_f64(x).unwrap())
+    }
+
+    fn reals<T: FloatingPoint>(&self, start: usize, count: usize) -> anyhow::Result<Vec<T>> {
+        (start..start + count).map(|i| self.real(i)).collect()
+    }
+
+    /// Check if the entity is used in the parameter space of a surface
+    fn is_parametric(&self) -> bool {
+        self.status.get(4..6) == Some("05")
+    }
+}
+
+/// Split the free formatted parameters by the delimiters, reading the Hollerith constants as they are
+fn split_parameters(data: &str, delimiter: char, terminator: char) -> Vec<String> {
+    let chars = data.chars().collect::<Vec<_>>();
+    let mut parameters = vec![];
+    let mut current = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == delimiter || c == terminator {
+            parameters.push(current.trim().to_string());
+            current.clear();
+            if c == terminator {
+                break;
+            }
+            i += 1;
+            continue;
+        }
+        if c == 'H'
+            && current.trim().chars().all(|c| c.is_ascii_digit())
+            && !current.trim().is_empty()
+        {
+            let n = current.trim().parse::<usize>().unwrap_or(0);
+            current = chars[i + 1..(i + 1 + n).min(chars.len())].iter().collect();
+            i += 1 + n;
+            continue;
+        }
+        current.push(c);
+        i += 1;
+    }
+    parameters
+}
+
+/// A reader to load rational B-spline curves (126), surfaces (128) & trimmed surfaces (144) from an IGES file
+/// The transformation matrices of the entities are not applied.
+/// # Example
+/// ```
+/// use curvo::prelude::*;
+/// use nalgebra::{Point3, Vector3};
+///
+/// let circle = NurbsCurve3D::try_circle(&Point3::origin(), &Vector3::x(), &Vector3::y(), 1.).unwrap();
+/// let cylinder = NurbsSurface::extrude(&circle, &Vector3::z());
+/// let mut writer = IgesWriter::new();
+/// writer.add_curve(&circle);
+/// writer.add_surface(&cylinder);
+///
+/// let reader = IgesReader::try_parse(&writer.to_iges_string("cylinder")).unwrap();
+/// let curves = reader.try_curves::<f64>().unwrap();
+/// assert_eq!(curves.len(), 1);
+/// assert!((curves[0].point_at(0.3) - circle.point_at(0.3)).norm() < 1e-8);
+///
+/// let surfaces = reader.try_surfaces::<f64>().unwrap();
+/// assert_eq!(surfaces.len(), 1);
+/// assert!((surfaces[0].point_at(0.4, 0.7) - cylinder.point_at(0.4, 0.7)).norm() < 1e-8);
+/// ```
+#[derive(Clone, Debug)]
+pub struct IgesReader {
+    /// The entries keyed by the sequence numbers of their first directory entry lines
+    entries: BTreeMap<usize, Entry>,
+}
+
+impl IgesReader {
+    /// Parse the directory entries & the parameter data of the IGES file
+    pub fn try_parse(iges: &str) -> anyhow::Result<Self> {
+        let mut global = String::new();
+        let mut directory = vec![];
+        let mut parameter_data: BTreeMap<usize, String> = BTreeMap::new();
+        for line in iges.lines() {
+            let Some(section) = line.chars().nth(72) else {
+                continue;
+            };
+            match section {
+                'G' => global.push_str(&line[..72]),
+                'D' => directory.push(line[..72].to_string()),
+                'P' => {
+                    let pointer = line[64..72].trim().parse::<usize>()?;
+                    parameter_data
+                        .entry(pointer)
+                        .or_default()
+                        .push_str(&line[..64]);
+                }
+                _ => {}
+            }
+        }
+
+        // the delimiters are defined at the head of the global section
+        let mut delimiters = [',', ';'];
+        let mut rest = global.trim_start();
+        for d in delimiters.iter_mut() {
+            if let Some(s) = rest.strip_prefix("1H") {
+                let mut chars = s.chars();
+                if let Some(c) = chars.next() {
+                    *d = c;
+                }
+                rest = chars.as_str();
+            }
+            rest = rest.strip_prefix([',', *d]).unwrap_or(rest);
+        }
+        let [delimiter, terminator] = delimiters;
+
+        anyhow::ensure!(
+            directory.len() % 2 == 0,
+            "The directory entry section has an odd number of lines"
+        );
+        let field = |line: &str, index: usize| line[index * 8..(index + 1) * 8].trim().to_string();
+        let mut entries = BTreeMap::new();
+        for (i, lines) in directory.chunks(2).enumerate() {
+            let pointer = i * 2 + 1;
+            let entity_type = field(&lines[0], 0).parse::<usize>()?;
+            let status = format!("{:0>8}", field(&lines[0], 8));
+            let parameters = parameter_data
+                .get(&pointer)
+                .map(|data| split_parameters(data, delimiter, terminator))
+                .unwrap_or_default();
+            // skip the entity type at the head of the parameters
+            let parameters = parameters.into_iter().skip(1).collect();
+            entries.insert(
+                pointer,
+                Entry {
+                    entity_type,
+                    status,
+                    parameters,
+                },
+            );
+        }
+        Ok(Self { entries })
+    }
+
+    fn entry(&self, pointer: usize) -> anyhow::Result<&Entry> {
+        self.entries
+            .get(&pointer)
+            .ok_or(anyhow::anyhow!("The entity at {} is not found", pointer))
+    }
+
+    /// Convert the rational B-spline curve (126) into the curve in the dimension
+    fn curve<T: FloatingPoint, D: DimName>(
+        &self,
+        pointer: usize,
+    ) -> anyhow::Result<NurbsCurve<T, D>>
+    where
+        DefaultAllocator: Allocator<D>,
+    {
+        let e = self.entry(pointer)?;
+        anyhow::ensure!(
+            e.entity_type == 126,
+            "The entity at {} is not a rational B-spline curve",
+            pointer
+        );
+        let k = e.integer(0)?;
+        let degree = e.integer(1)?;
+        let n = k + 1;
+        let knots_start = 6;
+        let knots = e.reals(knots_start, n + degree + 1)?;
+        let weights_start = knots_start + n + degree + 1;
+        let weights: Vec<T> = e.reals(weights_start, n)?;
+        let points_start = weights_start + n;
+        let dim = D::dim() - 1;
+        let control_points = (0..n)
+            .map(|i| {
+                let xyz = e.reals::<T>(points_start + i * 3, 3)?;
+                let w = weights[i];
+                let mut p = OPoint::<T, D>::origin();
+                for j in 0..dim {
+                    p[j] = xyz[j] * w;
+                }
+                p[dim] = w;
+                Ok(p)
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        NurbsCurve::try_new(degree, control_points, knots)
+    }
+
+    /// Convert the entity into the curves in the dimension, expanding the composite curves (102) into their members
+    fn curves<T: FloatingPoint, D: DimName>(
+        &self,
+        pointer: usize,
+    ) -> anyhow::Result<Vec<NurbsCurve<T, D>>>
+    where
+        DefaultAllocator: Allocator<D>,
+    {
+        let e = self.entry(pointer)?;
+        match e.entity_type {
+            126 => Ok(vec![self.curve(pointer)?]),
+            102 => {
+                let n = e.integer(0)?;
+                let mut curves = vec![];
+                for i in 0..n {
+                    curves.extend(self.curves(e.integer(1 + i)?)?);
+                }
+                Ok(curves)
+            }
+            110 => {
+                // a line segment
+                let dim = D::dim() - 1;
+                let xyz = e.reals::<T>(0, 6)?;
+                let point = |offset: usize| {
+                    let mut p = OPoint::<T, D>::origin();
+                    for j in 0..dim {
+                        p[j] = xyz[offset + j];
+                    }
+                    p[dim] = T::one();
+                    p
+                };
+                let (a, b) = (point(0), point(3));
+                NurbsCurve::try_new(
+                    1,
+                    vec![a, b],
+                    vec![T::zero(), T::zero(), T::one(), T::one()],
+                )
+                .map(|c| vec![c])
+            }
+            t => anyhow::bail!("The curve entity type {} is not supported", t),
+        }
+    }
+
+    fn surface<T: FloatingPoint>(&self, pointer: usize) -> anyhow::Result<NurbsSurface3D<T>> {
+        let e = self.entry(pointer)?;
+        anyhow::ensure!(
+            e.entity_type == 128,
+            "The entity at {} is not a rational B-spline surface",
+            pointer
+        );
+        let (k1, k2) = (e.integer(0)?, e.integer(1)?);
+        let (m1, m2) = (e.integer(2)?, e.integer(3)?);
+        let (n1, n2) = (k1 + 1, k2 + 1);
+        let u_start = 9;
+        let u_knots = e.reals(u_start, n1 + m1 + 1)?;
+        let v_start = u_start + n1 + m1 + 1;
+        let v_knots = e.reals(v_start, n2 + m2 + 1)?;
+        let weights_start = v_start + n2 + m2 + 1;
+        let weights: Vec<T> = e.reals(weights_start, n1 * n2)?;
+        let points_start = weights_start + n1 * n2;
+        // the first index of the weights & the control points varies fastest
+        let control_points = (0..n1)
+            .map(|i| {
+                (0..n2)
+                    .map(|j| {
+                        let index = j * n1 + i;
+                        let xyz = e.reals::<T>(points_start + index * 3, 3)?;
+                        let w = weights[index];
+                        Ok(nalgebra::Point4::new(xyz[0] * w, xyz[1] * w, xyz[2] * w, w))
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(NurbsSurface3D::new(
+            m1,
+            m2,
+            u_knots,
+            v_knots,
+            control_points,
+        ))
+    }
+
+    /// Get the trim curves of the curve on the surface (142), preferring its curve in the parameter space
+    fn boundary<T: FloatingPoint>(&self, pointer: usize) -> anyhow::Result<Vec<TrimCurve<T>>> {
+        let e = self.entry(pointer)?;
+        anyhow::ensure!(
+            e.entity_type == 142,
+            "The entity at {} is not a curve on a surface",
+            pointer
+        );
+        let (parameter, space, preference) = (e.integer(2)?, e.integer(3)?, e.integer(4)?);
+        if parameter != 0 && (preference != 2 || space == 0) {
+            Ok(self
+                .curves::<T, nalgebra::Const<3>>(parameter)?
+                .into_iter()
+                .map(TrimCurve::Parameter)
+                .collect())
+        } else {
+            anyhow::ensure!(
+                space != 0,
+                "The curve on the surface at {} is empty",
+                pointer
+            );
+            Ok(self
+                .curves::<T, nalgebra::Const<4>>(space)?
+                .into_iter()
+                .map(TrimCurve::Space)
+                .collect())
+        }
+    }
+
+    fn trimmed_surface<T: FloatingPoint + ArgminFloat>(
+        &self,
+        pointer: usize,
+    ) -> anyhow::Result<TrimmedSurface<T>> {
+        let e = self.entry(pointer)?;
+        let surface = self.surface::<T>(e.integer(0)?)?;
+        let outer = match e.integer(1)? {
+            0 => None,
+            _ => Some(self.boundary::<T>(e.integer(3)?)?),
+        };
+        let inners = (0..e.integer(2)?)
+            .map(|i| self.boundary::<T>(e.integer(4 + i)?))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let as_loop = |curves: &[TrimCurve<T>]| -> Option<Vec<NurbsCurve2D<T>>> {
+            curves
+                .iter()
+                .map(|c| match c {
+                    TrimCurve::Parameter(c) => Some(c.clone()),
+                    TrimCurve::Space(_) => None,
+                })
+                .collect()
+        };
+        let parametric = outer
+            .iter()
+            .chain(inners.iter())
+            .all(|l| as_loop(l).is_some());
+        if parametric {
+            let (u0, u1) = surface.u_knots_domain();
+            let (v0, v1) = surface.v_knots_domain();
+            let tolerance =
+                (Point2::new(u1, v1) - Point2::new(u0, v0)).norm() * T::from_f64(1e-5).unwrap();
+            let to_compound = |curves: &[TrimCurve<T>]| {
+                CompoundCurve2D::try_new_with_tolerance(as_loop(curves).unwrap(), tolerance)
+            };
+            let exterior = outer.as_deref().map(to_compound).transpose()?;
+            let interiors = inners
+                .iter()
+                .map(|l| to_compound(l))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            TrimmedSurface::try_new(surface, exterior, interiors)
+        } else {
+            // project the curves in the model space onto the surface
+            let side = if outer.is_some() {
+                TrimSide::Inside
+            } else {
+                TrimSide::Outside
+            };
+            let curves = outer
+                .into_iter()
+                .chain(inners)
+                .flatten()
+                .collect::<Vec<_>>();
+            surface.try_trim(&curves, side)
+        }
+    }
+
+    /// Get all the rational B-spline curves in the model space
+    pub fn try_curves<T: FloatingPoint>(&self) -> anyhow::Result<Vec<NurbsCurve3D<T>>> {
+        self.entries
+            .iter()
+            .filter(|(_, e)| e.entity_type == 126 && !e.is_parametric())
+            .map(|(pointer, _)| self.curve(*pointer))
+            .collect()
+    }
+
+    /// Get all the rational B-spline surfaces
+    pub fn try_surfaces<T: FloatingPoint>(&self) -> anyhow::Result<Vec<NurbsSurface3D<T>>> {
+        self.entries
+            .iter()
+            .filter(|(_, e)| e.entity_type == 128)
+            .map(|(pointer, _)| self.surface(*pointer))
+            .collect()
+    }
+
+    /// Get all the trimmed surfaces (144)
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point2, Point3, Vector2, Vector3};
+    ///
+    /// let line = NurbsCurve3D::polyline(&[Point3::origin(), Point3::new(1., 0., 0.)]);
+    /// let plane = NurbsSurface::extrude(&line, &Vector3::y());
+    /// let hole = NurbsCurve2D::try_circle(&Point2::new(0.5, 0.5), &Vector2::x(), &Vector2::y(), 0.25).unwrap();
+    /// let trimmed = TrimmedSurface::try_new(plane, None, vec![hole.into()]).unwrap();
+    /// let mut writer = IgesWriter::new();
+    /// writer.add_trimmed_surface(&trimmed);
+    ///
+    /// let reader = IgesReader::try_parse(&writer.to_iges_string("plate")).unwrap();
+    /// let faces = reader.try_trimmed_surfaces::<f64>().unwrap();
+    /// assert_eq!(faces.len(), 1);
+    /// assert!(faces[0].contains(0.1, 0.1));
+    /// assert!(!faces[0].contains(0.5, 0.5));
+    /// // the curves in the parameter space are not listed as the curves in the model space
+    /// assert!(reader.try_curves::<f64>().unwrap().is_empty());
+    /// ```
+    pub fn try_trimmed_surfaces<T: FloatingPoint + ArgminFloat>(
+        &self,
+    ) -> anyhow::Result<Vec<TrimmedSurface<T>>> {
+        self.entries
+            .iter()
+            .filter(|(_, e)| e.entity_type == 144)
+            .map(|(pointer, _)| self.trimmed_surface(*pointer))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split_parameters;
+
+    #[test]
+    fn split_hollerith() {
+        let parameters = split_parameters("1H,,1H;,4Ha,b;,1.0D-2,;", ',', ';');
+        assert_eq!(parameters, vec![",", ";", "a,b;", "1.0D-2", ""]);
+    }
+}
@@ -0,0 +1,377 @@
+use nalgebra::{allocator::Allocator, DefaultAllocator, DimName};
+
+use crate::{
+    curve::{CompoundCurve2D, NurbsCurve, NurbsCurve3D},
+    misc::FloatingPoint,
+    surface::{NurbsSurface3D, TrimmedSurface},
+};
+
+/// Format a real number in the IGES syntax which requires a decimal point in the mantissa
+fn real<T: FloatingPoint>(x: T) -> String {
+    let s = format!("{:?}", x.to_f64().unwrap());
+    match s.split_once('e') {
+        Some((mantissa, exponent)) if mantissa.contains('.') => {
+            format!("{}E{}", mantissa, exponent)
+        }
+        Some((mantissa, exponent)) => format!("{}.E{}", mantissa, exponent),
+        None if s.contains('.') => s,
+        None => format!("{}.", s),
+    }
+}
+
+/// Format a string as a Hollerith constant
+fn hollerith(s: &str) -> String {
+    format!("{}H{}", s.len(), s)
+}
+
+/// An entity to be written in the directory entry & parameter data sections
+#[derive(Clone, Debug)]
+struct Record {
+    entity_type: usize,
+    form: usize,
+    /// The status number composed of the blank, subordinate, entity use & hierarchy flags
+    status: &'static str,
+    parameters: Vec<String>,
+}
+
+/// The status of the entities referred by the other entities
+const DEPENDENT: &str = "00010000";
+/// The status of the dependent curves in the parameter space of the surfaces
+const PARAMETRIC: &str = "00010500";
+
+/// A writer to serialize curves, surfaces & trimmed surfaces into an IGES file
+/// The curves are written as the rational B-spline curves (126), the surfaces as the rational B-spline surfaces (128)
+/// and the trimmed surfaces as the trimmed surfaces (144) bounded by the curves on the surfaces (142).
+/// # Example
+/// ```
+/// use curvo::prelude::*;
+/// use nalgebra::{Point3, Vector3};
+///
+/// let circle = NurbsCurve3D::try_circle(&Point3::origin(), &Vector3::x(), &Vector3::y(), 1.).unwrap();
+/// let cylinder = NurbsSurface::extrude(&circle, &Vector3::z());
+///
+/// let mut writer = IgesWriter::new();
+/// writer.add_curve(&circle);
+/// writer.add_surface(&cylinder);
+/// let iges = writer.to_iges_string("cylinder");
+/// assert!(iges.lines().all(|l| l.len() == 80));
+/// assert_eq!(iges.lines().filter(|l| l.as_bytes()[72] == b'D').count(), 4);
+/// assert!(iges.lines().last().unwrap().starts_with("S      1G"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct IgesWriter {
+    records: Vec<Record>,
+}
+
+impl IgesWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Push the record & return its pointer, the sequence number of its first directory entry line
+    fn push(&mut self, record: Record) -> usize {
+        self.records.push(record);
+        self.records.len() * 2 - 1
+    }
+
+    fn curve<T: FloatingPoint, D: DimName>(
+        &mut self,
+        curve: &NurbsCurve<T, D>,
+        status: &'static str,
+    ) -> usize
+    where
+        DefaultAllocator: Allocator<D>,
+    {
+        let degree = curve.degree();
+        let points = curve.control_points();
+        let weights = curve.weights();
+        let (start, end) = curve.knots_domain();
+        let dim = D::dim() - 1;
+        let polynomial = weights.iter().all(|w| *w == T::one());
+
+        let mut parameters = vec![
+            (points.len() - 1).to_string(),
+            degree.to_string(),
+            // the curves in the parameter space are planar
+            if dim < 3 { "1" } else { "0" }.to_string(),
+            "0".to_string(),
+            if polynomial { "1" } else { "0" }.to_string(),
+            "0".to_string(),
+        ];
+        parameters.extend(curve.knots().iter().map(|k| real(*k)));
+        parameters.extend(weights.iter().map(|w| real(*w)));
+        for p in points.iter() {
+            let w = p[dim];
+            for i in 0..3 {
+                parameters.push(real(if i < dim { p[i] / w } else { T::zero() }));
+            }
+        }
+        parameters.push(real(start));
+        parameters.push(real(end));
+        if dim < 3 {
+            parameters.extend(["0.0", "0.0", "1.0"].map(String::from));
+        } else {
+            parameters.extend(["0.0", "0.0", "0.0"].map(String::from));
+        }
+        self.push(Record {
+            entity_type: 126,
+            form: 0,
+            status,
+            parameters,
+        })
+    }
+
+    fn surface<T: FloatingPoint>(
+        &mut self,
+        surface: &NurbsSurface3D<T>,
+        status: &'static str,
+    ) -> usize {
+        let points = surface.control_points();
+        let (nu, nv) = (points.len(), points[0].len());
+        let polynomial = points.iter().flatten().all(|p| p.w == T::one());
+        let (u0, u1) = surface.u_knots_domain();
+        let (v0, v1) = surface.v_knots_domain();
+
+        let mut parameters = vec![
+            (nu - 1).to_string(),
+            (nv - 1).to_string(),
+            surface.u_degree().to_string(),
+            surface.v_degree().to_string(),
+            "0".to_string(),
+            "0".to_string(),
+            if polynomial { "1" } else { "0" }.to_string(),
+            "0".to_string(),
+            "0".to_string(),
+        ];
+        parameters.extend(surface.u_knots().iter().map(|k| real(*k)));
+        parameters.extend(surface.v_knots().iter().map(|k| real(*k)));
+        // the first index of the weights & the control points varies fastest
+        for j in 0..nv {
+            for row in points.iter() {
+                parameters.push(real(row[j].w));
+            }
+        }
+        for j in 0..nv {
+            for row in points.iter() {
+                let p = row[j];
+                parameters.extend([p.x / p.w, p.y / p.w, p.z / p.w].map(real));
+            }
+        }
+        parameters.extend([u0, u1, v0, v1].map(real));
+        self.push(Record {
+            entity_type: 128,
+            form: 0,
+            status,
+            parameters,
+        })
+    }
+
+    /// Write the loop in the parameter space as a curve on the surface (142)
+    fn curve_on_surface<T: FloatingPoint>(
+        &mut self,
+        surface: usize,
+        curve: &CompoundCurve2D<T>,
+    ) -> usize {
+        let spans = curve
+            .spans()
+            .iter()
+            .map(|span| self.curve(span, PARAMETRIC))
+            .collect::<Vec<_>>();
+        let boundary = if spans.len() == 1 {
+            spans[0]
+        } else {
+            let mut parameters = vec![spans.len().to_string()];
+            parameters.extend(spans.iter().map(|s| s.to_string()));
+            self.push(Record {
+                entity_type: 102,
+                form: 0,
+                status: PARAMETRIC,
+                parameters,
+            })
+        };
+        self.push(Record {
+            entity_type: 142,
+            form: 0,
+            status: DEPENDENT,
+            parameters: vec![
+                // the curve is created in the parameter space
+                "1".to_string(),
+                surface.to_string(),
+                boundary.to_string(),
+                "0".to_string(),
+                // the curve in the parameter space is preferred
+                "1".to_string(),
+            ],
+        })
+    }
+
+    /// Add the curve as a rational B-spline curve (126) & return its directory entry pointer
+    pub fn add_curve<T: FloatingPoint>(&mut self, curve: &NurbsCurve3D<T>) -> usize {
+        self.curve(curve, "00000000")
+    }
+
+    /// Add the surface as a rational B-spline surface (128) & return its directory entry pointer
+    pub fn add_surface<T: FloatingPoint>(&mut self, surface: &NurbsSurface3D<T>) -> usize {
+        self.surface(surface, "00000000")
+    }
+
+    /// Add the trimmed surface as a trimmed surface (144) & return its directory entry pointer
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point2, Point3, Vector3};
+    ///
+    /// let line = NurbsCurve3D::polyline(&[Point3::origin(), Point3::new(1., 0., 0.)]);
+    /// let plane = NurbsSurface::extrude(&line, &Vector3::y());
+    /// let hole = NurbsCurve2D::try_circle(&Point2::new(0.5, 0.5), &nalgebra::Vector2::x(), &nalgebra::Vector2::y(), 0.25).unwrap();
+    /// let trimmed = TrimmedSurface::try_new(plane, None, vec![hole.into()]).unwrap();
+    ///
+    /// let mut writer = IgesWriter::new();
+    /// writer.add_trimmed_surface(&trimmed);
+    /// let iges = writer.to_iges_string("plate");
+    /// let types = iges
+    ///     .lines()
+    ///     .filter(|l| l.as_bytes()[72] == b'D')
+    ///     .step_by(2)
+    ///     .map(|l| l[0..8].trim().to_string())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(types, vec!["128", "126", "142", "144"]);
+    /// ```
+    pub fn add_trimmed_surface<T: FloatingPoint>(&mut self, trimmed: &TrimmedSurface<T>) -> usize {
+        let surface = self.surface(trimmed.surface(), DEPENDENT);
+        let outer = trimmed
+            .exterior()
+            .map(|exterior| self.curve_on_surface(surface, exterior));
+        let inners = trimmed
+            .interiors()
+            .iter()
+            .map(|interior| self.curve_on_surface(surface, interior))
+            .collect::<Vec<_>>();
+        let mut parameters = vec![
+            surface.to_string(),
+            // 0 means the outer boundary is the boundary of the surface
+            if outer.is_some() { "1" } else { "0" }.to_string(),
+            inners.len().to_string(),
+            outer.unwrap_or(0).to_string(),
+        ];
+        parameters.extend(inners.iter().map(|i| i.to_string()));
+        self.push(Record {
+            entity_type: 144,
+            form: 0,
+            status: "00000000",
+            parameters,
+        })
+    }
+
+    /// Serialize the added entities into an IGES file with the file name
+    pub fn to_iges_string(&self, name: &str) -> String {
+        let line = |content: &str, section: char, index: usize| {
+            format!("{:<72}{}{:>7}\n", content, section, index)
+        };
+        let mut out = String::new();
+
+        // start section
+        out.push_str(&line("curvo", 'S', 1));
+
+        // global section
+        let global = [
+            hollerith(","),
+            hollerith(";"),
+            hollerith(name),
+            hollerith(name),
+            hollerith("curvo"),
+            hollerith(env!("CARGO_PKG_VERSION")),
+            "32".to_string(),
+            "38".to_string(),
+            "6".to_string(),
+            "308".to_string(),
+            "15".to_string(),
+            hollerith(name),
+            "1.0".to_string(),
+            // millimeters
+            "2".to_string(),
+            hollerith("MM"),
+            "1".to_string(),
+            "1.0".to_string(),
+            hollerith("19700101.000000"),
+            "1.0E-6".to_string(),
+            "0.0".to_string(),
+            hollerith(""),
+            hollerith(""),
+            // IGES 5.3
+            "11".to_string(),
+            "0".to_string(),
+        ];
+        let global_lines = Self::pack(&global, 72);
+        for (i, l) in global_lines.iter().enumerate() {
+            out.push_str(&line(l, 'G', i + 1));
+        }
+
+        // parameter data section, each line refers back to the directory entry
+        let mut parameter_lines = vec![];
+        let mut pointers = vec![];
+        for (i, record) in self.records.iter().enumerate() {
+            let mut parameters = vec![record.entity_type.to_string()];
+            parameters.extend(record.parameters.iter().cloned());
+            let lines = Self::pack(&parameters, 64);
+            pointers.push((parameter_lines.len() + 1, lines.len()));
+            for l in lines {
+                parameter_lines.push(format!("{:<64}{:>8}", l, i * 2 + 1));
+            }
+        }
+
+        // directory entry section
+        for (i, (record, (pointer, count))) in self.records.iter().zip(pointers.iter()).enumerate()
+        {
+            let first = format!(
+                "{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}",
+                record.entity_type, pointer, 0, 0, 0, 0, 0, 0, record.status
+            );
+            out.push_str(&line(&first, 'D', i * 2 + 1));
+            let second = format!(
+                "{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}{:>8}",
+                record.entity_type, 0, 0, count, record.form, "", "", "", 0
+            );
+            out.push_str(&line(&second, 'D', i * 2 + 2));
+        }
+
+        for (i, l) in parameter_lines.iter().enumerate() {
+            out.push_str(&line(l, 'P', i + 1));
+        }
+
+        // terminate section
+        let terminate = format!(
+            "S{:>7}G{:>7}D{:>7}P{:>7}",
+            1,
+            global_lines.len(),
+            self.records.len() * 2,
+            parameter_lines.len()
+        );
+        out.push_str(&line(&terminate, 'T', 1));
+        out
+    }
+
+    /// Write the IGES file with the file name into the writer
+    pub fn write<W: std::io::Write>(&self, mut writer: W, name: &str) -> anyhow::Result<()> {
+        writer.write_all(self.to_iges_string(name).as_bytes())?;
+        Ok(())
+    }
+
+    /// Pack the parameters delimited by commas & terminated by a semicolon into the lines of the width
+    fn pack(parameters: &[String], width: usize) -> Vec<String> {
+        let mut lines = vec![];
+        let mut current = String::new();
+        for (i, p) in parameters.iter().enumerate() {
+            let delimiter = if i + 1 == parameters.len() { ';' } else { ',' };
+            let token = format!("{}{}", p, delimiter);
+            if !current.is_empty() && current.len() + token.len() > width {
+                lines.push(std::mem::take(&mut current));
+            }
+            current.push_str(&token);
+        }
+        if !current.is_empty() {
+            lines.push(current);
+        }
+        lines
+    }
+}
@@ -0,0 +1,5 @@
+pub mod iges_reader;
+pub mod iges_writer;
+
+pub use iges_reader::*;
+pub use iges_writer::*;
@@ -0,0 +1,91 @@
+use bevy::render::{
+    mesh::{Indices, Mesh, PrimitiveTopology, VertexAttributeValues},
+    render_asset::RenderAssetUsages,
+};
+use nalgebra::Point3;
+
+use crate::{
+    brep::ShellTessellation,
+    curve::{CompoundCurve3D, NurbsCurve3D},
+    io::TessellationMesh,
+    misc::FloatingPoint,
+    tessellation::surface_tessellation::SurfaceTessellation3D,
+};
+
+/// Create a line strip mesh passing through the points
+pub fn line_strip_mesh<T: FloatingPoint>(points: &[Point3<T>]) -> Mesh {
+    let f = |x: T| x.to_f32().unwrap();
+    let positions = points
+        .iter()
+        .map(|p| [f(p.x), f(p.y), f(p.z)])
+        .collect::<Vec<_>>();
+    Mesh::new(PrimitiveTopology::LineStrip, RenderAssetUsages::default()).with_inserted_attribute(
+        Mesh::ATTRIBUTE_POSITION,
+        VertexAttributeValues::Float32x3(positions),
+    )
+}
+
+/// Convert the mesh into a triangle list mesh with the available vertex attributes
+impl<'a, T: FloatingPoint> From<TessellationMesh<'a, T>> for Mesh {
+    fn from(value: TessellationMesh<'a, T>) -> Self {
+        let f = |x: T| x.to_f32().unwrap();
+        let mut mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        );
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            VertexAttributeValues::Float32x3(
+                value
+                    .points()
+                    .iter()
+                    .map(|p| [f(p.x), f(p.y), f(p.z)])
+                    .collect(),
+            ),
+        );
+        if let Some(normals) = value.normals() {
+            mesh.insert_attribute(
+                Mesh::ATTRIBUTE_NORMAL,
+                VertexAttributeValues::Float32x3(
+                    normals.iter().map(|n| [f(n.x), f(n.y), f(n.z)]).collect(),
+                ),
+            );
+        }
+        if let Some(uvs) = value.uvs() {
+            mesh.insert_attribute(
+                Mesh::ATTRIBUTE_UV_0,
+                VertexAttributeValues::Float32x2(uvs.iter().map(|uv| [f(uv.x), f(uv.y)]).collect()),
+            );
+        }
+        mesh.insert_indices(Indices::U32(
+            value.faces().iter().flatten().map(|i| *i as u32).collect(),
+        ));
+        mesh
+    }
+}
+
+impl<T: FloatingPoint> From<&SurfaceTessellation3D<T>> for Mesh {
+    fn from(value: &SurfaceTessellation3D<T>) -> Self {
+        TessellationMesh::from(value).into()
+    }
+}
+
+impl<T: FloatingPoint> From<&ShellTessellation<T>> for Mesh {
+    fn from(value: &ShellTessellation<T>) -> Self {
+        TessellationMesh::from(value).into()
+    }
+}
+
+/// Convert the curve into a line strip mesh tessellated with the default tolerance
+impl<T: FloatingPoint> From<&NurbsCurve3D<T>> for Mesh {
+    fn from(value: &NurbsCurve3D<T>) -> Self {
+        line_strip_mesh(&value.tessellate(None))
+    }
+}
+
+/// Convert the compound curve into a line strip mesh tessellated with the default tolerance
+impl<T: FloatingPoint> From<&CompoundCurve3D<T>> for Mesh {
+    fn from(value: &CompoundCurve3D<T>) -> Self {
+        line_strip_mesh(&value.tessellate(None))
+    }
+}
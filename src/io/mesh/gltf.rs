@@ -0,0 +1,297 @@
+use std::io::Write;
+
+use crate::{io::TessellationMesh, misc::FloatingPoint};
+
+/// Encode the bytes in the standard base64 alphabet with padding
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = ((b[0] as u32) << 16) | ((b[1] as u32) << 8) | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[((n >> (18 - i * 6)) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Encode the values into the little-endian bytes
+fn le_bytes<'a>(values: impl IntoIterator<Item = &'a f32>) -> Vec<u8> {
+    values.into_iter().flat_map(|x| x.to_le_bytes()).collect()
+}
+
+/// A mesh flattened into the vertex attributes & the indices in the glTF layout
+#[derive(Clone, Debug)]
+struct GltfMesh {
+    name: String,
+    positions: Vec<[f32; 3]>,
+    normals: Option<Vec<[f32; 3]>>,
+    uvs: Option<Vec<[f32; 2]>>,
+    indices: Vec<u32>,
+}
+
+/// The binary buffer with its views & accessors
+#[derive(Clone, Debug, Default)]
+struct Buffers {
+    buffer: Vec<u8>,
+    views: Vec<String>,
+    accessors: Vec<String>,
+}
+
+impl Buffers {
+    /// Append the data as a buffer view & an accessor, and return the index of the accessor
+    fn push(
+        &mut self,
+        data: Vec<u8>,
+        count: usize,
+        kind: &str,
+        component: u32,
+        target: u32,
+        bounds: Option<([f32; 3], [f32; 3])>,
+    ) -> usize {
+        self.views.push(format!(
+            "{{\"buffer\":0,\"byteOffset\":{},\"byteLength\":{},\"target\":{}}}",
+            self.buffer.len(),
+            data.len(),
+            target
+        ));
+        self.buffer.extend(data);
+        // the views are aligned to 4 bytes
+        self.buffer.resize(self.buffer.len().next_multiple_of(4), 0);
+        let bounds = bounds
+            .map(|(min, max)| {
+                let list = |v: [f32; 3]| v.map(|x| format!("{:?}", x)).join(",");
+                format!(",\"min\":[{}],\"max\":[{}]", list(min), list(max))
+            })
+            .unwrap_or_default();
+        self.accessors.push(format!(
+            "{{\"bufferView\":{},\"componentType\":{},\"count\":{},\"type\":\"{}\"{}}}",
+            self.views.len() - 1,
+            component,
+            count,
+            kind,
+            bounds
+        ));
+        self.accessors.len() - 1
+    }
+}
+
+/// A writer to export the tessellated meshes as a glTF 2.0 scene with a node for each mesh
+/// The texture coordinates are the UV parameters of the surfaces normalized into the unit square over each mesh.
+/// # Example
+/// ```
+/// use curvo::prelude::*;
+/// use nalgebra::{Point3, Vector3};
+///
+/// let line = NurbsCurve3D::polyline(&[Point3::origin(), Point3::new(1., 0., 0.)]);
+/// let plane = NurbsSurface::extrude(&line, &Vector3::y());
+/// let tess = plane.tessellate(None);
+///
+/// let mut writer = GltfWriter::new();
+/// writer.add_mesh("plane", &TessellationMesh::from(&tess));
+///
+/// let mut gltf = vec![];
+/// writer.write_gltf(&mut gltf).unwrap();
+/// let gltf = String::from_utf8(gltf).unwrap();
+/// assert!(gltf.contains("\"version\":\"2.0\""));
+/// assert!(gltf.contains("\"TEXCOORD_0\""));
+/// assert!(gltf.contains("data:application/octet-stream;base64,"));
+///
+/// let mut glb = vec![];
+/// writer.write_glb(&mut glb).unwrap();
+/// assert_eq!(&glb[0..4], b"glTF");
+/// assert_eq!(u32::from_le_bytes(glb[8..12].try_into().unwrap()) as usize, glb.len());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct GltfWriter {
+    meshes: Vec<GltfMesh>,
+}
+
+impl GltfWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the mesh as a node of the scene
+    pub fn add_mesh<T: FloatingPoint>(&mut self, name: &str, mesh: &TessellationMesh<'_, T>) {
+        let f = |x: T| x.to_f32().unwrap();
+        let uvs = mesh.uvs().map(|uvs| {
+            let (mut min, mut max) = ([f32::MAX; 2], [f32::MIN; 2]);
+            for uv in uvs.iter() {
+                for (i, x) in [f(uv.x), f(uv.y)].into_iter().enumerate() {
+                    min[i] = min[i].min(x);
+                    max[i] = max[i].max(x);
+                }
+            }
+            let normalize = |x: f32, i: usize| {
+                let range = max[i] - min[i];
+                if range > 0. {
+                    (x - min[i]) / range
+                } else {
+                    0.
+                }
+            };
+            uvs.iter()
+                .map(|uv| [normalize(f(uv.x), 0), normalize(f(uv.y), 1)])
+                .collect()
+        });
+        self.meshes.push(GltfMesh {
+            name: name.to_string(),
+            positions: mesh
+                .points()
+                .iter()
+                .map(|p| [f(p.x), f(p.y), f(p.z)])
+                .collect(),
+            normals: mesh
+                .normals()
+                .map(|n| n.iter().map(|n| [f(n.x), f(n.y), f(n.z)]).collect()),
+            uvs,
+            indices: mesh.faces().iter().flatten().map(|i| *i as u32).collect(),
+        });
+    }
+
+    /// Build the JSON document referring to the binary buffer & the buffer itself
+    fn build(&self, uri: Option<&str>) -> (String, Vec<u8>) {
+        let mut buffers = Buffers::default();
+        const FLOAT: u32 = 5126;
+        const UNSIGNED_INT: u32 = 5125;
+        const ARRAY_BUFFER: u32 = 34962;
+        const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+
+        let mut meshes = vec![];
+        for mesh in self.meshes.iter() {
+            let (mut min, mut max) = ([f32::MAX; 3], [f32::MIN; 3]);
+            for p in mesh.positions.iter() {
+                for i in 0..3 {
+                    min[i] = min[i].min(p[i]);
+                    max[i] = max[i].max(p[i]);
+                }
+            }
+            let mut attributes = vec![];
+            let position = buffers.push(
+                le_bytes(mesh.positions.iter().flatten()),
+                mesh.positions.len(),
+                "VEC3",
+                FLOAT,
+                ARRAY_BUFFER,
+                Some((min, max)),
+            );
+            attributes.push(format!("\"POSITION\":{}", position));
+            if let Some(normals) = mesh.normals.as_ref() {
+                let normal = buffers.push(
+                    le_bytes(normals.iter().flatten()),
+                    normals.len(),
+                    "VEC3",
+                    FLOAT,
+                    ARRAY_BUFFER,
+                    None,
+                );
+                attributes.push(format!("\"NORMAL\":{}", normal));
+            }
+            if let Some(uvs) = mesh.uvs.as_ref() {
+                let uv = buffers.push(
+                    le_bytes(uvs.iter().flatten()),
+                    uvs.len(),
+                    "VEC2",
+                    FLOAT,
+                    ARRAY_BUFFER,
+                    None,
+                );
+                attributes.push(format!("\"TEXCOORD_0\":{}", uv));
+            }
+            let indices = buffers.push(
+                mesh.indices.iter().flat_map(|i| i.to_le_bytes()).collect(),
+                mesh.indices.len(),
+                "SCALAR",
+                UNSIGNED_INT,
+                ELEMENT_ARRAY_BUFFER,
+                None,
+            );
+            meshes.push(format!(
+                "{{\"name\":\"{}\",\"primitives\":[{{\"attributes\":{{{}}},\"indices\":{},\"mode\":4}}]}}",
+                escape(&mesh.name),
+                attributes.join(","),
+                indices
+            ));
+        }
+        let Buffers {
+            buffer,
+            views,
+            accessors,
+        } = buffers;
+
+        let nodes = (0..meshes.len())
+            .map(|i| format!("{{\"mesh\":{}}}", i))
+            .collect::<Vec<_>>();
+        let scene_nodes = (0..meshes.len()).map(|i| i.to_string()).collect::<Vec<_>>();
+        let uri = uri
+            .map(|uri| format!(",\"uri\":\"{}\"", uri))
+            .unwrap_or_default();
+        let json = format!(
+            "{{\"asset\":{{\"version\":\"2.0\",\"generator\":\"curvo\"}},\"scene\":0,\"scenes\":[{{\"nodes\":[{}]}}],\"nodes\":[{}],\"meshes\":[{}],\"accessors\":[{}],\"bufferViews\":[{}],\"buffers\":[{{\"byteLength\":{}{}}}]}}",
+            scene_nodes.join(","),
+            nodes.join(","),
+            meshes.join(","),
+            accessors.join(","),
+            views.join(","),
+            buffer.len(),
+            uri
+        );
+        (json, buffer)
+    }
+
+    /// Write the scene as a glTF JSON file with the buffer embedded as a data URI
+    pub fn write_gltf<W: Write>(&self, mut writer: W) -> anyhow::Result<()> {
+        let (_, buffer) = self.build(None);
+        let uri = format!("data:application/octet-stream;base64,{}", base64(&buffer));
+        let (json, _) = self.build(Some(&uri));
+        writer.write_all(json.as_bytes())?;
+        Ok(())
+    }
+
+    /// Write the scene as a binary glTF (GLB) file
+    pub fn write_glb<W: Write>(&self, mut writer: W) -> anyhow::Result<()> {
+        let (json, buffer) = self.build(None);
+        let mut json = json.into_bytes();
+        // the chunks are aligned to 4 bytes, padding the JSON with spaces
+        json.resize(json.len().next_multiple_of(4), b' ');
+        let length = 12 + 8 + json.len() + 8 + buffer.len();
+        writer.write_all(b"glTF")?;
+        writer.write_all(&2u32.to_le_bytes())?;
+        writer.write_all(&(length as u32).to_le_bytes())?;
+        writer.write_all(&(json.len() as u32).to_le_bytes())?;
+        writer.write_all(b"JSON")?;
+        writer.write_all(&json)?;
+        writer.write_all(&(buffer.len() as u32).to_le_bytes())?;
+        writer.write_all(b"BIN\0")?;
+        writer.write_all(&buffer)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::base64;
+
+    #[test]
+    fn encode_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+    }
+}
@@ -0,0 +1,13 @@
+#[cfg(feature = "bevy")]
+pub mod bevy_mesh;
+#[cfg(feature = "gltf")]
+pub mod gltf;
+pub mod obj;
+pub mod ply;
+pub mod tessellation_mesh;
+
+#[cfg(feature = "bevy")]
+pub use bevy_mesh::*;
+#[cfg(feature = "gltf")]
+pub use gltf::*;
+pub use tessellation_mesh::*;
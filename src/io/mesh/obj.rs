@@ -0,0 +1,35 @@
+use std::io::Write;
+
+use crate::{io::TessellationMesh, misc::FloatingPoint};
+
+impl<T: FloatingPoint> TessellationMesh<'_, T> {
+    /// Write the mesh in the Wavefront OBJ format with the normals & texture coordinates if they exist
+    pub fn write_obj<W: Write>(&self, mut writer: W) -> anyhow::Result<()> {
+        let f = |x: T| x.to_f64().unwrap();
+        for p in self.points.iter() {
+            writeln!(writer, "v {} {} {}", f(p.x), f(p.y), f(p.z))?;
+        }
+        if let Some(uvs) = self.uvs {
+            for uv in uvs.iter() {
+                writeln!(writer, "vt {} {}", f(uv.x), f(uv.y))?;
+            }
+        }
+        if let Some(normals) = self.normals {
+            for n in normals.iter() {
+                writeln!(writer, "vn {} {} {}", f(n.x), f(n.y), f(n.z))?;
+            }
+        }
+
+        // the indices are 1-based
+        let vertex = |i: usize| match (self.uvs.is_some(), self.normals.is_some()) {
+            (true, true) => format!("{0}/{0}/{0}", i + 1),
+            (true, false) => format!("{0}/{0}", i + 1),
+            (false, true) => format!("{0}//{0}", i + 1),
+            (false, false) => format!("{}", i + 1),
+        };
+        for [a, b, c] in self.faces.iter() {
+            writeln!(writer, "f {} {} {}", vertex(*a), vertex(*b), vertex(*c))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+use std::io::Write;
+
+use crate::{io::TessellationMesh, misc::FloatingPoint};
+
+impl<T: FloatingPoint> TessellationMesh<'_, T> {
+    /// Write the mesh in the binary little endian PLY format with the normals & texture coordinates if they exist
+    pub fn write_ply<W: Write>(&self, mut writer: W) -> anyhow::Result<()> {
+        writeln!(writer, "ply")?;
+        writeln!(writer, "format binary_little_endian 1.0")?;
+        writeln!(writer, "comment curvo")?;
+        writeln!(writer, "element vertex {}", self.points.len())?;
+        for p in ["x", "y", "z"] {
+            writeln!(writer, "property float {}", p)?;
+        }
+        if self.normals.is_some() {
+            for p in ["nx", "ny", "nz"] {
+                writeln!(writer, "property float {}", p)?;
+            }
+        }
+        if self.uvs.is_some() {
+            for p in ["s", "t"] {
+                writeln!(writer, "property float {}", p)?;
+            }
+        }
+        writeln!(writer, "element face {}", self.faces.len())?;
+        writeln!(writer, "property list uchar uint vertex_indices")?;
+        writeln!(writer, "end_header")?;
+
+        let mut f = |x: T| writer.write_all(&x.to_f32().unwrap().to_le_bytes());
+        for (i, p) in self.points.iter().enumerate() {
+            f(p.x)?;
+            f(p.y)?;
+            f(p.z)?;
+            if let Some(n) = self.normals.and_then(|n| n.get(i)) {
+                f(n.x)?;
+                f(n.y)?;
+                f(n.z)?;
+            }
+            if let Some(uv) = self.uvs.and_then(|uv| uv.get(i)) {
+                f(uv.x)?;
+                f(uv.y)?;
+            }
+        }
+        for face in self.faces.iter() {
+            writer.write_all(&[3])?;
+            for i in face {
+                writer.write_all(&(*i as u32).to_le_bytes())?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use nalgebra::{Point3, Vector2, Vector3};
+
+use crate::{
+    brep::ShellTessellation, misc::FloatingPoint,
+    tessellation::surface_tessellation::SurfaceTessellation3D,
+};
+
+/// A borrowed view of the buffers of a tessellation to be written into the mesh files
+/// # Example
+/// ```
+/// use curvo::prelude::*;
+/// use nalgebra::{Point3, Vector3};
+///
+/// let line = NurbsCurve3D::polyline(&[Point3::origin(), Point3::new(1., 0., 0.)]);
+/// let plane = NurbsSurface::extrude(&line, &Vector3::y());
+/// let tess = plane.tessellate(None);
+///
+/// let mut obj = vec![];
+/// TessellationMesh::from(&tess).write_obj(&mut obj).unwrap();
+/// let obj = String::from_utf8(obj).unwrap();
+/// assert_eq!(obj.lines().filter(|l| l.starts_with("v ")).count(), tess.points().len());
+/// assert_eq!(obj.lines().filter(|l| l.starts_with("vt ")).count(), tess.uvs().len());
+/// assert_eq!(obj.lines().filter(|l| l.starts_with("f ")).count(), tess.faces().len());
+///
+/// let mut ply = vec![];
+/// TessellationMesh::from(&tess).write_ply(&mut ply).unwrap();
+/// assert!(ply.starts_with(b"ply\nformat binary_little_endian 1.0\n"));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct TessellationMesh<'a, T: FloatingPoint> {
+    pub(crate) points: &'a [Point3<T>],
+    pub(crate) normals: Option<&'a [Vector3<T>]>,
+    pub(crate) uvs: Option<&'a [Vector2<T>]>,
+    pub(crate) faces: &'a [[usize; 3]],
+}
+
+impl<'a, T: FloatingPoint> TessellationMesh<'a, T> {
+    pub fn new(points: &'a [Point3<T>], faces: &'a [[usize; 3]]) -> Self {
+        Self {
+            points,
+            normals: None,
+            uvs: None,
+            faces,
+        }
+    }
+
+    pub fn with_normals(mut self, normals: &'a [Vector3<T>]) -> Self {
+        self.normals = Some(normals);
+        self
+    }
+
+    pub fn with_uvs(mut self, uvs: &'a [Vector2<T>]) -> Self {
+        self.uvs = Some(uvs);
+        self
+    }
+
+    pub fn points(&self) -> &[Point3<T>] {
+        self.points
+    }
+
+    pub fn normals(&self) -> Option<&[Vector3<T>]> {
+        self.normals
+    }
+
+    pub fn uvs(&self) -> Option<&[Vector2<T>]> {
+        self.uvs
+    }
+
+    pub fn faces(&self) -> &[[usize; 3]] {
+        self.faces
+    }
+}
+
+impl<'a, T: FloatingPoint> From<&'a SurfaceTessellation3D<T>> for TessellationMesh<'a, T> {
+    fn from(value: &'a SurfaceTessellation3D<T>) -> Self {
+        Self::new(value.points(), value.faces())
+            .with_normals(value.normals())
+            .with_uvs(value.uvs())
+    }
+}
+
+impl<'a, T: FloatingPoint> From<&'a ShellTessellation<T>> for TessellationMesh<'a, T> {
+    fn from(value: &'a ShellTessellation<T>) -> Self {
+        Self::new(value.points(), value.faces()).with_normals(value.normals())
+    }
+}
@@ -0,0 +1,29 @@
+pub mod binary;
+#[cfg(feature = "dxf")]
+pub mod dxf;
+#[cfg(feature = "iges")]
+pub mod iges;
+pub mod mesh;
+#[cfg(feature = "rerun")]
+pub mod rerun_log;
+#[cfg(feature = "rhino")]
+pub mod rhino;
+#[cfg(feature = "step")]
+pub mod step;
+#[cfg(feature = "svg")]
+pub mod svg;
+
+pub use binary::*;
+#[cfg(feature = "dxf")]
+pub use dxf::*;
+#[cfg(feature = "iges")]
+pub use iges::*;
+pub use mesh::*;
+#[cfg(feature = "rerun")]
+pub use rerun_log::*;
+#[cfg(feature = "rhino")]
+pub use rhino::*;
+#[cfg(feature = "step")]
+pub use step::*;
+#[cfg(feature = "svg")]
+pub use svg::*;
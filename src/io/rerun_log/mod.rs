@@ -0,0 +1,2 @@
+pub mod rerun_logging;
+pub use rerun_logging::*;
@@ -0,0 +1,196 @@
+use nalgebra::{
+    allocator::Allocator, DefaultAllocator, DimName, DimNameDiff, DimNameSub, OPoint, Point3, U1,
+};
+use rerun::{LineStrips3D, Mesh3D, Points3D, RecordingStream};
+
+use crate::{
+    curve::{CompoundCurve, NurbsCurve},
+    intersection::{CurveIntersection, CurveSurfaceIntersection, SurfaceIntersection},
+    io::TessellationMesh,
+    misc::FloatingPoint,
+    surface::NurbsSurface3D,
+};
+
+/// Convert the point into the position in the viewer, where the missing coordinates of the planar point are zero
+fn position<T: FloatingPoint, D: DimName>(p: &OPoint<T, D>) -> [f32; 3]
+where
+    DefaultAllocator: Allocator<D>,
+{
+    let f = |i: usize| p.coords.get(i).map_or(0., |x| x.to_f32().unwrap());
+    [f(0), f(1), f(2)]
+}
+
+/// Log the curve tessellated with the default tolerance as a line strip
+/// # Example
+/// ```
+/// use curvo::prelude::*;
+/// use nalgebra::{Point3, Vector3};
+///
+/// let (rec, storage) = rerun::RecordingStreamBuilder::new("curvo").memory().unwrap();
+/// let circle = NurbsCurve3D::try_circle(&Point3::origin(), &Vector3::x(), &Vector3::y(), 1.).unwrap();
+/// log_curve(&rec, "circle", &circle).unwrap();
+/// log_control_polygon(&rec, "circle/control_polygon", &circle).unwrap();
+///
+/// let line = NurbsCurve3D::polyline(&[Point3::new(-2., -2., 0.), Point3::new(2., -2., 0.)]);
+/// let plane = NurbsSurface::extrude(&line, &(Vector3::y() * 4.));
+/// log_tessellation(&rec, "plane", &plane.tessellate(None)).unwrap();
+/// log_control_net(&rec, "plane/control_net", &plane).unwrap();
+///
+/// let pierce = NurbsCurve3D::polyline(&[Point3::new(0., 0., -1.), Point3::new(0., 0., 1.)]);
+/// let intersections = pierce.find_surface_intersections(&plane, None).unwrap();
+/// log_curve_surface_intersections(&rec, "intersections", &intersections).unwrap();
+///
+/// rec.flush_blocking();
+/// assert!(storage.num_msgs() > 0);
+/// ```
+pub fn log_curve<T: FloatingPoint, D: DimNameSub<U1>>(
+    rec: &RecordingStream,
+    path: &str,
+    curve: &NurbsCurve<T, D>,
+) -> anyhow::Result<()>
+where
+    DefaultAllocator: Allocator<D>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    let strip: Vec<_> = curve.tessellate(None).iter().map(position).collect();
+    rec.log(path, &LineStrips3D::new([strip]))?;
+    Ok(())
+}
+
+/// Log the compound curve tessellated with the default tolerance as a line strip
+pub fn log_compound_curve<T: FloatingPoint, D: DimNameSub<U1>>(
+    rec: &RecordingStream,
+    path: &str,
+    curve: &CompoundCurve<T, D>,
+) -> anyhow::Result<()>
+where
+    DefaultAllocator: Allocator<D>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    let strip: Vec<_> = curve.tessellate(None).iter().map(position).collect();
+    rec.log(path, &LineStrips3D::new([strip]))?;
+    Ok(())
+}
+
+/// Log the control polygon of the curve as a line strip through the dehomogenized control points
+pub fn log_control_polygon<T: FloatingPoint, D: DimNameSub<U1>>(
+    rec: &RecordingStream,
+    path: &str,
+    curve: &NurbsCurve<T, D>,
+) -> anyhow::Result<()>
+where
+    DefaultAllocator: Allocator<D>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    let points: Vec<_> = curve
+        .dehomogenized_control_points()
+        .iter()
+        .map(position)
+        .collect();
+    rec.log(path, &Points3D::new(points.clone()))?;
+    rec.log(path, &LineStrips3D::new([points]))?;
+    Ok(())
+}
+
+/// Log the control net of the surface as the line strips along the u & v directions
+pub fn log_control_net<T: FloatingPoint>(
+    rec: &RecordingStream,
+    path: &str,
+    surface: &NurbsSurface3D<T>,
+) -> anyhow::Result<()> {
+    let points = surface.dehomogenized_control_points();
+    let rows = points
+        .iter()
+        .map(|row| row.iter().map(position).collect::<Vec<_>>());
+    let columns = (0..points.first().map_or(0, |row| row.len())).map(|j| {
+        points
+            .iter()
+            .map(|row| position(&row[j]))
+            .collect::<Vec<_>>()
+    });
+    let strips: Vec<_> = rows.chain(columns).collect();
+    rec.log(
+        path,
+        &Points3D::new(points.iter().flatten().map(position).collect::<Vec<_>>()),
+    )?;
+    rec.log(path, &LineStrips3D::new(strips))?;
+    Ok(())
+}
+
+/// Log the tessellation of a surface or a shell as a triangle mesh with the available normals
+pub fn log_tessellation<'a, T: FloatingPoint>(
+    rec: &RecordingStream,
+    path: &str,
+    mesh: impl Into<TessellationMesh<'a, T>>,
+) -> anyhow::Result<()> {
+    let mesh = mesh.into();
+    let mut mesh3d = Mesh3D::new(mesh.points().iter().map(position).collect::<Vec<_>>())
+        .with_triangle_indices(
+            mesh.faces()
+                .iter()
+                .map(|f| [f[0] as u32, f[1] as u32, f[2] as u32])
+                .collect::<Vec<_>>(),
+        );
+    if let Some(normals) = mesh.normals() {
+        let f = |x: T| x.to_f32().unwrap();
+        mesh3d = mesh3d.with_vertex_normals(
+            normals
+                .iter()
+                .map(|n| [f(n.x), f(n.y), f(n.z)])
+                .collect::<Vec<_>>(),
+        );
+    }
+    rec.log(path, &mesh3d)?;
+    Ok(())
+}
+
+/// Log the intersection points between two curves, where the points on both curves are logged
+pub fn log_curve_intersections<T: FloatingPoint, D: DimName>(
+    rec: &RecordingStream,
+    path: &str,
+    intersections: &[CurveIntersection<OPoint<T, D>, T>],
+) -> anyhow::Result<()>
+where
+    DefaultAllocator: Allocator<D>,
+{
+    let points: Vec<_> = intersections
+        .iter()
+        .flat_map(|it| [position(&it.a().0), position(&it.b().0)])
+        .collect();
+    rec.log(path, &Points3D::new(points))?;
+    Ok(())
+}
+
+/// Log the intersection points between a curve & a surface, where the points on both the curve & the surface are logged
+pub fn log_curve_surface_intersections<T: FloatingPoint>(
+    rec: &RecordingStream,
+    path: &str,
+    intersections: &[CurveSurfaceIntersection<Point3<T>, T>],
+) -> anyhow::Result<()> {
+    let points: Vec<_> = intersections
+        .iter()
+        .flat_map(|it| [position(&it.curve().0), position(&it.surface().0)])
+        .collect();
+    rec.log(path, &Points3D::new(points))?;
+    Ok(())
+}
+
+/// Log the intersection curves between two surfaces as line strips
+pub fn log_surface_intersections<T: FloatingPoint>(
+    rec: &RecordingStream,
+    path: &str,
+    intersections: &[SurfaceIntersection<T>],
+) -> anyhow::Result<()> {
+    let strips: Vec<_> = intersections
+        .iter()
+        .map(|it| {
+            it.curve()
+                .tessellate(None)
+                .iter()
+                .map(position)
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    rec.log(path, &LineStrips3D::new(strips))?;
+    Ok(())
+}
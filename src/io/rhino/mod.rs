@@ -0,0 +1,5 @@
+pub mod rhino_nurbs_curve;
+pub mod rhino_nurbs_surface;
+
+pub use rhino_nurbs_curve::*;
+pub use rhino_nurbs_surface::*;
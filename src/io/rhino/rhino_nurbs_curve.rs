@@ -0,0 +1,157 @@
+use nalgebra::{allocator::Allocator, DefaultAllocator, DimName, OPoint};
+
+use crate::{curve::NurbsCurve, misc::FloatingPoint};
+
+/// A NURBS curve in the layout of `ON_NurbsCurve` of openNURBS used by Rhino & rhino3dm
+/// The knot vector omits the superfluous knots at both ends, so it has `cv_count + order - 2` knots,
+/// and the control vertices of the rational curve are stored in the homogeneous coordinates.
+/// # Example
+/// ```
+/// use curvo::prelude::*;
+/// use nalgebra::{Point3, Vector3};
+///
+/// let circle = NurbsCurve3D::try_circle(&Point3::origin(), &Vector3::x(), &Vector3::y(), 1.).unwrap();
+/// let rhino = RhinoNurbsCurve::from(&circle);
+/// assert_eq!(rhino.dimension(), 3);
+/// assert!(rhino.is_rational());
+/// assert_eq!(rhino.order(), 3);
+/// assert_eq!(rhino.knots().len(), rhino.cv_count() + rhino.order() - 2);
+///
+/// let back = NurbsCurve3D::try_from(&rhino).unwrap();
+/// assert!((back.point_at(0.3) - circle.point_at(0.3)).norm() < 1e-12);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct RhinoNurbsCurve<T: FloatingPoint> {
+    dimension: usize,
+    is_rational: bool,
+    order: usize,
+    knots: Vec<T>,
+    /// The control vertices with `dimension` coordinates, followed by the weight if the curve is rational
+    cvs: Vec<Vec<T>>,
+}
+
+impl<T: FloatingPoint> RhinoNurbsCurve<T> {
+    pub fn try_new(
+        dimension: usize,
+        is_rational: bool,
+        order: usize,
+        knots: Vec<T>,
+        cvs: Vec<Vec<T>>,
+    ) -> anyhow::Result<Self> {
+        anyhow::ensure!(order >= 2, "The order must be at least 2");
+        anyhow::ensure!(cvs.len() >= order, "Too few control vertices for curve");
+        anyhow::ensure!(
+            knots.len() == cvs.len() + order - 2,
+            "Invalid number of knots, got {}, expected {}",
+            knots.len(),
+            cvs.len() + order - 2
+        );
+        let stride = dimension + usize::from(is_rational);
+        anyhow::ensure!(
+            cvs.iter().all(|cv| cv.len() == stride),
+            "The control vertices must have {} coordinates",
+            stride
+        );
+        Ok(Self {
+            dimension,
+            is_rational,
+            order,
+            knots,
+            cvs,
+        })
+    }
+
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    pub fn is_rational(&self) -> bool {
+        self.is_rational
+    }
+
+    /// The order of the curve, the degree + 1
+    pub fn order(&self) -> usize {
+        self.order
+    }
+
+    pub fn cv_count(&self) -> usize {
+        self.cvs.len()
+    }
+
+    pub fn knots(&self) -> &[T] {
+        &self.knots
+    }
+
+    pub fn cvs(&self) -> &[Vec<T>] {
+        &self.cvs
+    }
+}
+
+impl<T: FloatingPoint, D: DimName> From<&NurbsCurve<T, D>> for RhinoNurbsCurve<T>
+where
+    DefaultAllocator: Allocator<D>,
+{
+    fn from(value: &NurbsCurve<T, D>) -> Self {
+        let dimension = D::dim() - 1;
+        let is_rational = value.weights().iter().any(|w| *w != T::one());
+        let cvs = value
+            .control_points()
+            .iter()
+            .map(|p| {
+                let n = if is_rational {
+                    dimension + 1
+                } else {
+                    dimension
+                };
+                (0..n).map(|i| p[i]).collect()
+            })
+            .collect();
+        let knots = value.knots().as_slice();
+        Self {
+            dimension,
+            is_rational,
+            order: value.degree() + 1,
+            knots: knots[1..knots.len() - 1].to_vec(),
+            cvs,
+        }
+    }
+}
+
+impl<T: FloatingPoint, D: DimName> TryFrom<&RhinoNurbsCurve<T>> for NurbsCurve<T, D>
+where
+    DefaultAllocator: Allocator<D>,
+{
+    type Error = anyhow::Error;
+
+    fn try_from(value: &RhinoNurbsCurve<T>) -> Result<Self, Self::Error> {
+        let dimension = D::dim() - 1;
+        anyhow::ensure!(
+            value.dimension == dimension,
+            "The dimension of the curve is {}, expected {}",
+            value.dimension,
+            dimension
+        );
+        let control_points = value
+            .cvs
+            .iter()
+            .map(|cv| {
+                let mut p = OPoint::<T, D>::origin();
+                for i in 0..dimension {
+                    p[i] = cv[i];
+                }
+                p[dimension] = if value.is_rational {
+                    cv[dimension]
+                } else {
+                    T::one()
+                };
+                p
+            })
+            .collect();
+        // restore the superfluous knots, which do not affect the curve
+        let mut knots = Vec::with_capacity(value.knots.len() + 2);
+        knots.push(value.knots[0]);
+        knots.extend(value.knots.iter().copied());
+        knots.push(value.knots[value.knots.len() - 1]);
+        NurbsCurve::try_new(value.order - 1, control_points, knots)
+    }
+}
@@ -0,0 +1,161 @@
+use nalgebra::Point4;
+
+use crate::{misc::FloatingPoint, surface::NurbsSurface3D};
+
+/// A NURBS surface in the layout of `ON_NurbsSurface` of openNURBS used by Rhino & rhino3dm
+/// The knot vectors omit the superfluous knots at both ends, and the control vertices are indexed by `[u][v]`.
+/// # Example
+/// ```
+/// use curvo::prelude::*;
+/// use nalgebra::{Point3, Vector3};
+///
+/// let circle = NurbsCurve3D::try_circle(&Point3::origin(), &Vector3::x(), &Vector3::y(), 1.).unwrap();
+/// let cylinder = NurbsSurface::extrude(&circle, &Vector3::z());
+/// let rhino = RhinoNurbsSurface::from(&cylinder);
+/// assert!(rhino.is_rational());
+/// assert_eq!(rhino.orders(), [cylinder.u_degree() + 1, cylinder.v_degree() + 1]);
+///
+/// let back = NurbsSurface3D::try_from(&rhino).unwrap();
+/// assert!((back.point_at(0.4, 0.7) - cylinder.point_at(0.4, 0.7)).norm() < 1e-12);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct RhinoNurbsSurface<T: FloatingPoint> {
+    is_rational: bool,
+    orders: [usize; 2],
+    knots: [Vec<T>; 2],
+    /// The control vertices with 3 coordinates, followed by the weight if the surface is rational
+    cvs: Vec<Vec<Vec<T>>>,
+}
+
+impl<T: FloatingPoint> RhinoNurbsSurface<T> {
+    pub fn try_new(
+        is_rational: bool,
+        orders: [usize; 2],
+        knots: [Vec<T>; 2],
+        cvs: Vec<Vec<Vec<T>>>,
+    ) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            orders.iter().all(|o| *o >= 2),
+            "The orders must be at least 2"
+        );
+        let counts = [cvs.len(), cvs.first().map(|r| r.len()).unwrap_or(0)];
+        anyhow::ensure!(
+            cvs.iter().all(|r| r.len() == counts[1]),
+            "The rows of the control vertices must have the same length"
+        );
+        for i in 0..2 {
+            anyhow::ensure!(
+                counts[i] >= orders[i],
+                "Too few control vertices for surface"
+            );
+            anyhow::ensure!(
+                knots[i].len() == counts[i] + orders[i] - 2,
+                "Invalid number of knots, got {}, expected {}",
+                knots[i].len(),
+                counts[i] + orders[i] - 2
+            );
+        }
+        let stride = 3 + usize::from(is_rational);
+        anyhow::ensure!(
+            cvs.iter().flatten().all(|cv| cv.len() == stride),
+            "The control vertices must have {} coordinates",
+            stride
+        );
+        Ok(Self {
+            is_rational,
+            orders,
+            knots,
+            cvs,
+        })
+    }
+
+    pub fn is_rational(&self) -> bool {
+        self.is_rational
+    }
+
+    /// The orders of the surface in the u & v directions, the degrees + 1
+    pub fn orders(&self) -> [usize; 2] {
+        self.orders
+    }
+
+    pub fn cv_counts(&self) -> [usize; 2] {
+        [self.cvs.len(), self.cvs[0].len()]
+    }
+
+    pub fn knots(&self) -> &[Vec<T>; 2] {
+        &self.knots
+    }
+
+    pub fn cvs(&self) -> &[Vec<Vec<T>>] {
+        &self.cvs
+    }
+}
+
+impl<T: FloatingPoint> From<&NurbsSurface3D<T>> for RhinoNurbsSurface<T> {
+    fn from(value: &NurbsSurface3D<T>) -> Self {
+        let is_rational = value
+            .control_points()
+            .iter()
+            .flatten()
+            .any(|p| p.w != T::one());
+        let cvs = value
+            .control_points()
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|p| {
+                        let mut cv = vec![p.x, p.y, p.z];
+                        if is_rational {
+                            cv.push(p.w);
+                        }
+                        cv
+                    })
+                    .collect()
+            })
+            .collect();
+        let trim = |knots: &[T]| knots[1..knots.len() - 1].to_vec();
+        Self {
+            is_rational,
+            orders: [value.u_degree() + 1, value.v_degree() + 1],
+            knots: [
+                trim(value.u_knots().as_slice()),
+                trim(value.v_knots().as_slice()),
+            ],
+            cvs,
+        }
+    }
+}
+
+impl<T: FloatingPoint> TryFrom<&RhinoNurbsSurface<T>> for NurbsSurface3D<T> {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &RhinoNurbsSurface<T>) -> Result<Self, Self::Error> {
+        let control_points = value
+            .cvs
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cv| {
+                        let w = if value.is_rational { cv[3] } else { T::one() };
+                        Point4::new(cv[0], cv[1], cv[2], w)
+                    })
+                    .collect()
+            })
+            .collect();
+        // restore the superfluous knots, which do not affect the surface
+        let expand = |knots: &[T]| {
+            let mut expanded = Vec::with_capacity(knots.len() + 2);
+            expanded.push(knots[0]);
+            expanded.extend(knots.iter().copied());
+            expanded.push(knots[knots.len() - 1]);
+            expanded
+        };
+        Ok(NurbsSurface3D::new(
+            value.orders[0] - 1,
+            value.orders[1] - 1,
+            expand(&value.knots[0]),
+            expand(&value.knots[1]),
+            control_points,
+        ))
+    }
+}
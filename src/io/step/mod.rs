@@ -0,0 +1,5 @@
+pub mod step_reader;
+pub mod step_writer;
+
+pub use step_reader::*;
+pub use step_writer::*;
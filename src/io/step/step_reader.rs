@@ -0,0 +1,667 @@
+use std::collections::HashMap;
+
+use argmin::core::ArgminFloat;
+use nalgebra::{Point3, Point4};
+
+use crate::{
+    curve::NurbsCurve3D,
+    misc::{FloatingPoint, Invertible},
+    surface::{NurbsSurface3D, TrimCurve, TrimSide, TrimmedSurface},
+};
+
+/// A parameter of a STEP entity
+#[derive(Clone, Debug, PartialEq)]
+enum Value {
+    Ref(usize),
+    Real(f64),
+    Integer(i64),
+    String(String),
+    Enum(String),
+    List(Vec<Value>),
+    /// A typed parameter like `LENGTH_MEASURE(1.E-06)`
+    Typed(String, Vec<Value>),
+    /// `$`
+    Unset,
+    /// `*`
+    Derived,
+}
+
+impl Value {
+    fn as_ref(&self) -> anyhow::Result<usize> {
+        match self {
+            Value::Ref(id) => Ok(*id),
+            _ => anyhow::bail!("Expected an entity reference but found {:?}", self),
+        }
+    }
+
+    fn as_real(&self) -> anyhow::Result<f64> {
+        match self {
+            Value::Real(x) => Ok(*x),
+            Value::Integer(x) => Ok(*x as f64),
+            Value::Typed(_, values) if values.len() == 1 => values[0].as_real(),
+            _ => anyhow::bail!("Expected a real number but found {:?}", self),
+        }
+    }
+
+    fn as_integer(&self) -> anyhow::Result<usize> {
+        match self {
+            Value::Integer(x) if *x >= 0 => Ok(*x as usize),
+            _ => anyhow::bail!("Expected a non-negative integer but found {:?}", self),
+        }
+    }
+
+    fn as_list(&self) -> anyhow::Result<&[Value]> {
+        match self {
+            Value::List(values) => Ok(values),
+            _ => anyhow::bail!("Expected a list but found {:?}", self),
+        }
+    }
+
+    fn as_bool(&self) -> anyhow::Result<bool> {
+        match self {
+            Value::Enum(e) if e == "T" => Ok(true),
+            Value::Enum(e) if e == "F" => Ok(false),
+            _ => anyhow::bail!("Expected a boolean but found {:?}", self),
+        }
+    }
+}
+
+/// A STEP entity instance, consisting of a single record or the records of a complex instance
+type Entity = Vec<(String, Vec<Value>)>;
+
+/// A recursive descent parser of the parameters of the entity instances
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(s: &'a str) -> Self {
+        Self {
+            chars: s.chars().peekable(),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    fn keyword(&mut self) -> String {
+        let mut keyword = String::new();
+        while let Some(c) = self
+            .chars
+            .next_if(|c| c.is_ascii_alphanumeric() || *c == '_')
+        {
+            keyword.push(c);
+        }
+        keyword
+    }
+
+    fn expect(&mut self, expected: char) -> anyhow::Result<()> {
+        self.skip_whitespace();
+        match self.chars.next() {
+            Some(c) if c == expected => Ok(()),
+            c => anyhow::bail!("Expected '{}' but found {:?}", expected, c),
+        }
+    }
+
+    /// Parse the comma separated values up to the closing parenthesis
+    fn values(&mut self) -> anyhow::Result<Vec<Value>> {
+        self.expect('(')?;
+        let mut values = vec![];
+        self.skip_whitespace();
+        if self.chars.next_if_eq(&')').is_some() {
+            return Ok(values);
+        }
+        loop {
+            values.push(self.value()?);
+            self.skip_whitespace();
+            match self.chars.next() {
+                Some(',') => continue,
+                Some(')') => return Ok(values),
+                c => anyhow::bail!("Unexpected character {:?} in the parameter list", c),
+            }
+        }
+    }
+
+    fn value(&mut self) -> anyhow::Result<Value> {
+        self.skip_whitespace();
+        let Some(&c) = self.chars.peek() else {
+            anyhow::bail!("Unexpected end of the parameter list");
+        };
+        match c {
+            '#' => {
+                self.chars.next();
+                let mut id = String::new();
+                while let Some(c) = self.chars.next_if(|c| c.is_ascii_digit()) {
+                    id.push(c);
+                }
+                Ok(Value::Ref(id.parse()?))
+            }
+            '\'' => {
+                self.chars.next();
+                let mut s = String::new();
+                loop {
+                    match self.chars.next() {
+                        Some('\'') if self.chars.next_if_eq(&'\'').is_some() => s.push('\''),
+                        Some('\'') => return Ok(Value::String(s)),
+                        Some(c) => s.push(c),
+                        None => anyhow::bail!("Unterminated string"),
+                    }
+                }
+            }
+            '.' => {
+                self.chars.next();
+                let e = self.keyword();
+                self.expect('.')?;
+                Ok(Value::Enum(e))
+            }
+            '(' => Ok(Value::List(self.values()?)),
+            '$' => {
+                self.chars.next();
+                Ok(Value::Unset)
+            }
+            '*' => {
+                self.chars.next();
+                Ok(Value::Derived)
+            }
+            c if c.is_ascii_digit() || c == '-' || c == '+' => {
+                let mut s = String::new();
+                while let Some(c) = self
+                    .chars
+                    .next_if(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'E' | 'e'))
+                {
+                    s.push(c);
+                }
+                if s.contains(['.', 'E', 'e']) {
+                    Ok(Value::Real(s.parse()?))
+                } else {
+                    Ok(Value::Integer(s.parse()?))
+                }
+            }
+            c if c.is_ascii_alphabetic() => {
+                let name = self.keyword();
+                Ok(Value::Typed(name, self.values()?))
+            }
+            c => anyhow::bail!("Unexpected character '{}' in the parameter list", c),
+        }
+    }
+
+    /// Parse the right hand side of an entity instance
+    fn entity(&mut self) -> anyhow::Result<Entity> {
+        self.skip_whitespace();
+        if self.chars.next_if_eq(&'(').is_some() {
+            // a complex instance consisting of the records of the supertypes & subtypes
+            let mut records = vec![];
+            loop {
+                self.skip_whitespace();
+                if self.chars.next_if_eq(&')').is_some() {
+                    return Ok(records);
+                }
+                let name = self.keyword();
+                anyhow::ensure!(!name.is_empty(), "Invalid complex entity instance");
+                records.push((name, self.values()?));
+            }
+        } else {
+            let name = self.keyword();
+            anyhow::ensure!(!name.is_empty(), "Invalid entity instance");
+            Ok(vec![(name, self.values()?)])
+        }
+    }
+}
+
+/// Split the data section into the statements terminated by semicolons outside the strings & remove the comments
+fn statements(data: &str) -> Vec<String> {
+    let mut statements = vec![];
+    let mut current = String::new();
+    let mut in_string = false;
+    let mut chars = data.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_string = !in_string;
+                current.push(c);
+            }
+            '/' if !in_string && chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            ';' if !in_string => statements.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    statements
+}
+
+/// A reader to load B-spline curves, surfaces & the faces trimmed by them from a STEP file
+/// Only the B-spline geometry is mapped into curvo types, so the faces on the analytic surfaces like planes & cylinders are skipped.
+/// # Example
+/// ```
+/// use curvo::prelude::*;
+/// use nalgebra::{Point3, Vector3};
+///
+/// let circle = NurbsCurve3D::try_circle(&Point3::origin(), &Vector3::x(), &Vector3::y(), 1.).unwrap();
+/// let cylinder = NurbsSurface::extrude(&circle, &Vector3::z());
+/// let mut writer = StepWriter::new();
+/// writer.add_curve(&circle);
+/// writer.add_surface(&cylinder);
+///
+/// let reader = StepReader::try_parse(&writer.to_step_string("cylinder")).unwrap();
+/// let curves = reader.try_curves::<f64>().unwrap();
+/// assert_eq!(curves.len(), 1);
+/// let p = curves[0].point_at(0.3);
+/// assert!((p - circle.point_at(0.3)).norm() < 1e-8);
+///
+/// let surfaces = reader.try_surfaces::<f64>().unwrap();
+/// assert_eq!(surfaces.len(), 1);
+/// let p = surfaces[0].point_at(0.4, 0.7);
+/// assert!((p - cylinder.point_at(0.4, 0.7)).norm() < 1e-8);
+/// ```
+#[derive(Clone, Debug)]
+pub struct StepReader {
+    entities: HashMap<usize, Entity>,
+}
+
+impl StepReader {
+    /// Parse the entity instances in the data section of the STEP file
+    pub fn try_parse(step: &str) -> anyhow::Result<Self> {
+        let start = step
+            .find("DATA;")
+            .ok_or(anyhow::anyhow!("No data section is found"))?;
+        let data = &step[start + "DATA;".len()..];
+        let data = &data[..data.find("ENDSEC;").unwrap_or(data.len())];
+
+        let mut entities = HashMap::new();
+        for statement in statements(data) {
+            let statement = statement.trim();
+            if statement.is_empty() {
+                continue;
+            }
+            let Some((id, rhs)) = statement.split_once('=') else {
+                anyhow::bail!("Invalid entity instance: {}", statement);
+            };
+            let id = id
+                .trim()
+                .strip_prefix('#')
+                .ok_or(anyhow::anyhow!("Invalid entity id: {}", id))?
+                .parse::<usize>()?;
+            entities.insert(id, Parser::new(rhs).entity()?);
+        }
+        Ok(Self { entities })
+    }
+
+    fn entity(&self, id: usize) -> anyhow::Result<&Entity> {
+        self.entities
+            .get(&id)
+            .ok_or(anyhow::anyhow!("The entity #{} is not found", id))
+    }
+
+    /// Find the parameters of the record with the name in the entity
+    fn record<'a>(entity: &'a Entity, name: &str) -> Option<&'a [Value]> {
+        entity
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, values)| values.as_slice())
+    }
+
+    fn is_simple(&self, id: usize, name: &str) -> bool {
+        self.entities
+            .get(&id)
+            .is_some_and(|e| e.len() == 1 && e[0].0 == name)
+    }
+
+    fn ids_of<'a>(&'a self, name: &'a str) -> impl Iterator<Item = usize> + 'a {
+        let mut ids = self
+            .entities
+            .iter()
+            .filter(move |(_, e)| Self::record(e, name).is_some())
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+        ids.sort();
+        ids.into_iter()
+    }
+
+    fn point<T: FloatingPoint>(&self, id: usize) -> anyhow::Result<Point3<T>> {
+        let entity = self.entity(id)?;
+        let Some(values) = Self::record(entity, "CARTESIAN_POINT") else {
+            anyhow::bail!("The entity #{} is not a cartesian point", id);
+        };
+        let coords = values
+            .get(1)
+            .ok_or(anyhow::anyhow!("Invalid cartesian point #{}", id))?
+            .as_list()?
+            .iter()
+            .map(|v| v.as_real())
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let c = |i: usize| T::from_f64(coords.get(i).copied().unwrap_or(0.)).unwrap();
+        Ok(Point3::new(c(0), c(1), c(2)))
+    }
+
+    /// Expand the distinct knots with their multiplicities into the knot vector
+    fn knots<T: FloatingPoint>(multiplicities: &Value, knots: &Value) -> anyhow::Result<Vec<T>> {
+        let multiplicities = multiplicities.as_list()?;
+        let knots = knots.as_list()?;
+        anyhow::ensure!(
+            multiplicities.len() == knots.len(),
+            "The number of the knot multiplicities does not match the number of the knots"
+        );
+        let mut vector = vec![];
+        for (m, k) in multiplicities.iter().zip(knots.iter()) {
+            let k = T::from_f64(k.as_real()?).unwrap();
+            vector.extend(std::iter::repeat(k).take(m.as_integer()?));
+        }
+        Ok(vector)
+    }
+
+    fn homogenize<T: FloatingPoint>(p: Point3<T>, w: T) -> Point4<T> {
+        Point4::new(p.x * w, p.y * w, p.z * w, w)
+    }
+
+    /// Convert the entity into a B-spline curve if it is one
+    fn curve<T: FloatingPoint>(&self, id: usize) -> anyhow::Result<Option<NurbsCurve3D<T>>> {
+        let entity = self.entity(id)?;
+        let (degree, points, multiplicities, knots) =
+            if let Some(values) = Self::record(entity, "B_SPLINE_CURVE") {
+                // a complex instance of the rational curve
+                let Some(with_knots) = Self::record(entity, "B_SPLINE_CURVE_WITH_KNOTS") else {
+                    return Ok(None);
+                };
+                anyhow::ensure!(
+                    values.len() >= 2 && with_knots.len() >= 2,
+                    "Invalid B-spline curve #{}",
+                    id
+                );
+                (&values[0], &values[1], &with_knots[0], &with_knots[1])
+            } else if let Some(values) = Self::record(entity, "B_SPLINE_CURVE_WITH_KNOTS") {
+                anyhow::ensure!(values.len() >= 8, "Invalid B-spline curve #{}", id);
+                (&values[1], &values[2], &values[6], &values[7])
+            } else {
+                return Ok(None);
+            };
+
+        let points = points
+            .as_list()?
+            .iter()
+            .map(|p| self.point::<T>(p.as_ref()?))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let weights = match Self::record(entity, "RATIONAL_B_SPLINE_CURVE") {
+            Some(values) => values
+                .first()
+                .ok_or(anyhow::anyhow!("Invalid rational B-spline curve #{}", id))?
+                .as_list()?
+                .iter()
+                .map(|w| w.as_real().map(|w| T::from_f64(w).unwrap()))
+                .collect::<anyhow::Result<Vec<_>>>()?,
+            None => vec![T::one(); points.len()],
+        };
+        anyhow::ensure!(
+            weights.len() == points.len(),
+            "The number of the weights does not match the number of the control points in #{}",
+            id
+        );
+        let control_points = points
+            .into_iter()
+            .zip(weights)
+            .map(|(p, w)| Self::homogenize(p, w))
+            .collect();
+        let knots = Self::knots(multiplicities, knots)?;
+        NurbsCurve3D::try_new(degree.as_integer()?, control_points, knots).map(Some)
+    }
+
+    /// Convert the entity into a B-spline surface if it is one
+    fn surface<T: FloatingPoint>(&self, id: usize) -> anyhow::Result<Option<NurbsSurface3D<T>>> {
+        let entity = self.entity(id)?;
+        let (u_degree, v_degree, grid, u_multiplicities, v_multiplicities, u_knots, v_knots) =
+            if let Some(values) = Self::record(entity, "B_SPLINE_SURFACE") {
+                let Some(with_knots) = Self::record(entity, "B_SPLINE_SURFACE_WITH_KNOTS") else {
+                    return Ok(None);
+                };
+                anyhow::ensure!(
+                    values.len() >= 3 && with_knots.len() >= 4,
+                    "Invalid B-spline surface #{}",
+                    id
+                );
+                (
+                    &values[0],
+                    &values[1],
+                    &values[2],
+                    &with_knots[0],
+                    &with_knots[1],
+                    &with_knots[2],
+                    &with_knots[3],
+                )
+            } else if let Some(values) = Self::record(entity, "B_SPLINE_SURFACE_WITH_KNOTS") {
+                anyhow::ensure!(values.len() >= 12, "Invalid B-spline surface #{}", id);
+                (
+                    &values[1],
+                    &values[2],
+                    &values[3],
+                    &values[8],
+                    &values[9],
+                    &values[10],
+                    &values[11],
+                )
+            } else {
+                return Ok(None);
+            };
+
+        let points = grid
+            .as_list()?
+            .iter()
+            .map(|row| {
+                row.as_list()?
+                    .iter()
+                    .map(|p| self.point::<T>(p.as_ref()?))
+                    .collect::<anyhow::Result<Vec<_>>>()
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let weights = match Self::record(entity, "RATIONAL_B_SPLINE_SURFACE") {
+            Some(values) => values
+                .first()
+                .ok_or(anyhow::anyhow!("Invalid rational B-spline surface #{}", id))?
+                .as_list()?
+                .iter()
+                .map(|row| {
+                    row.as_list()?
+                        .iter()
+                        .map(|w| w.as_real().map(|w| T::from_f64(w).unwrap()))
+                        .collect::<anyhow::Result<Vec<_>>>()
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?,
+            None => points.iter().map(|row| vec![T::one(); row.len()]).collect(),
+        };
+        anyhow::ensure!(
+            weights.len() == points.len()
+                && weights
+                    .iter()
+                    .zip(points.iter())
+                    .all(|(w, p)| w.len() == p.len()),
+            "The weights do not match the control points in #{}",
+            id
+        );
+        let control_points = points
+            .into_iter()
+            .zip(weights)
+            .map(|(row, weights)| {
+                row.into_iter()
+                    .zip(weights)
+                    .map(|(p, w)| Self::homogenize(p, w))
+                    .collect()
+            })
+            .collect();
+        Ok(Some(NurbsSurface3D::new(
+            u_degree.as_integer()?,
+            v_degree.as_integer()?,
+            Self::knots(u_multiplicities, u_knots)?,
+            Self::knots(v_multiplicities, v_knots)?,
+            control_points,
+        )))
+    }
+
+    /// Get all the B-spline curves in the file
+    pub fn try_curves<T: FloatingPoint>(&self) -> anyhow::Result<Vec<NurbsCurve3D<T>>> {
+        self.ids_of("B_SPLINE_CURVE_WITH_KNOTS")
+            .filter_map(|id| self.curve(id).transpose())
+            .collect()
+    }
+
+    /// Get all the B-spline surfaces in the file
+    pub fn try_surfaces<T: FloatingPoint>(&self) -> anyhow::Result<Vec<NurbsSurface3D<T>>> {
+        self.ids_of("B_SPLINE_SURFACE_WITH_KNOTS")
+            .filter_map(|id| self.surface(id).transpose())
+            .collect()
+    }
+
+    /// Convert the geometry of the edge curve into a curve between its vertices
+    fn edge_curve<T: FloatingPoint>(&self, id: usize) -> anyhow::Result<Option<NurbsCurve3D<T>>> {
+        let entity = self.entity(id)?;
+        let Some(values) = Self::record(entity, "EDGE_CURVE") else {
+            anyhow::bail!("The entity #{} is not an edge curve", id);
+        };
+        anyhow::ensure!(values.len() >= 5, "Invalid edge curve #{}", id);
+        let vertex = |v: &Value| -> anyhow::Result<Point3<T>> {
+            let entity = self.entity(v.as_ref()?)?;
+            let Some(values) = Self::record(entity, "VERTEX_POINT") else {
+                anyhow::bail!("Invalid vertex of the edge curve #{}", id);
+            };
+            self.point(values[1].as_ref()?)
+        };
+        let (start, end) = (vertex(&values[1])?, vertex(&values[2])?);
+
+        // unwrap the curves on the surfaces into their 3D curves
+        let mut geometry = values[3].as_ref()?;
+        while self.is_simple(geometry, "SURFACE_CURVE") || self.is_simple(geometry, "SEAM_CURVE") {
+            geometry = self.entity(geometry)?[0].1[1].as_ref()?;
+        }
+
+        let curve = if self.is_simple(geometry, "LINE") {
+            Some(NurbsCurve3D::polyline(&[start, end]))
+        } else {
+            self.curve(geometry)?.map(|curve| {
+                if values[4].as_bool().unwrap_or(true) {
+                    curve
+                } else {
+                    curve.inverse()
+                }
+            })
+        };
+        Ok(curve)
+    }
+
+    /// Convert the advanced face into a trimmed surface by projecting its edges onto the surface
+    fn face<T: FloatingPoint + ArgminFloat>(
+        &self,
+        id: usize,
+    ) -> anyhow::Result<Option<TrimmedSurface<T>>> {
+        let entity = self.entity(id)?;
+        let Some(values) = Self::record(entity, "ADVANCED_FACE") else {
+            anyhow::bail!("The entity #{} is not an advanced face", id);
+        };
+        anyhow::ensure!(values.len() >= 4, "Invalid advanced face #{}", id);
+        let Some(surface) = self.surface::<T>(values[2].as_ref()?)? else {
+            return Ok(None);
+        };
+
+        let mut curves = vec![];
+        for bound in values[1].as_list()? {
+            let bound = self.entity(bound.as_ref()?)?;
+            let Some(edge_loop) = bound.first().and_then(|(_, values)| values.get(1)) else {
+                anyhow::bail!("Invalid face bound in the advanced face #{}", id);
+            };
+            let edge_loop = self.entity(edge_loop.as_ref()?)?;
+            let Some(edges) = Self::record(edge_loop, "EDGE_LOOP") else {
+                // a vertex loop at a pole does not trim the surface
+                continue;
+            };
+            for oriented in edges[1].as_list()? {
+                let oriented = self.entity(oriented.as_ref()?)?;
+                let Some(values) = Self::record(oriented, "ORIENTED_EDGE") else {
+                    anyhow::bail!("Invalid oriented edge in the advanced face #{}", id);
+                };
+                let Some(curve) = self.edge_curve(values[3].as_ref()?)? else {
+                    return Ok(None);
+                };
+                curves.push(TrimCurve::Space(curve));
+            }
+        }
+
+        let trimmed = if curves.is_empty() {
+            TrimmedSurface::try_new(surface, None, vec![])?
+        } else {
+            surface.try_trim(&curves, TrimSide::Inside)?
+        };
+        Ok(Some(if values[3].as_bool()? {
+            trimmed
+        } else {
+            trimmed.inverse()
+        }))
+    }
+
+    /// Get all the faces on the B-spline surfaces in the file as the trimmed surfaces
+    /// The faces on the other surfaces or bounded by the edges other than lines & B-spline curves are skipped.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    ///
+    /// let p = |x: f64, y: f64, z: f64| Point3::new(x, y, z);
+    /// let face = |a: Point3<f64>, b: Point3<f64>, d: Vector3<f64>| {
+    ///     NurbsSurface::extrude(&NurbsCurve3D::polyline(&[a, b]), &d)
+    /// };
+    /// let cube = vec![
+    ///     face(p(0., 0., 0.), p(1., 0., 0.), Vector3::y()),
+    ///     face(p(0., 0., 1.), p(1., 0., 1.), Vector3::y()),
+    ///     face(p(0., 0., 0.), p(0., 1., 0.), Vector3::z()),
+    ///     face(p(1., 0., 0.), p(1., 1., 0.), Vector3::z()),
+    ///     face(p(0., 0., 0.), p(0., 0., 1.), Vector3::x()),
+    ///     face(p(0., 1., 0.), p(0., 1., 1.), Vector3::x()),
+    /// ];
+    /// let mut writer = StepWriter::new();
+    /// writer.add_shell(&Shell::try_from_surfaces(&cube, 1e-6).unwrap());
+    ///
+    /// let reader = StepReader::try_parse(&writer.to_step_string("cube")).unwrap();
+    /// let faces = reader.try_trimmed_surfaces::<f64>().unwrap();
+    /// assert_eq!(faces.len(), 6);
+    /// let shell = Shell::try_from_trimmed_surfaces(&faces, 1e-4).unwrap();
+    /// assert!(shell.is_closed());
+    /// ```
+    pub fn try_trimmed_surfaces<T: FloatingPoint + ArgminFloat>(
+        &self,
+    ) -> anyhow::Result<Vec<TrimmedSurface<T>>> {
+        self.ids_of("ADVANCED_FACE")
+            .filter_map(|id| self.face(id).transpose())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_entities() {
+        let step = "ISO-10303-21;\nHEADER;\nENDSEC;\nDATA;\n\
+            #1=CARTESIAN_POINT('a;''b',(0.,1.5E1,-2));/* comment; */\n\
+            #2=(LENGTH_UNIT() NAMED_UNIT(*) SI_UNIT(.MILLI.,.METRE.));\n\
+            #3=UNCERTAINTY_MEASURE_WITH_UNIT(LENGTH_MEASURE(1.E-06),#2,$,'');\n\
+            ENDSEC;\nEND-ISO-10303-21;\n";
+        let reader = StepReader::try_parse(step).unwrap();
+        assert_eq!(reader.entities.len(), 3);
+        assert_eq!(
+            reader.entities[&1][0].1[0],
+            Value::String("a;'b".to_string())
+        );
+        assert_eq!(reader.point::<f64>(1).unwrap(), Point3::new(0., 15., -2.));
+        assert_eq!(reader.entities[&2].len(), 3);
+        assert_eq!(reader.entities[&2][1].1, vec![Value::Derived]);
+        let uncertainty = &reader.entities[&3][0].1;
+        assert_eq!(uncertainty[0].as_real().unwrap(), 1e-6);
+        assert_eq!(uncertainty[1], Value::Ref(2));
+        assert_eq!(uncertainty[2], Value::Unset);
+    }
+}
@@ -0,0 +1,390 @@
+use std::fmt::Write as _;
+
+use argmin::core::ArgminFloat;
+use nalgebra::Point3;
+
+use crate::{
+    brep::{Loop, Shell},
+    curve::NurbsCurve3D,
+    knot::KnotVector,
+    misc::FloatingPoint,
+    surface::{NurbsSurface3D, TrimmedSurface},
+};
+
+/// A writer to serialize curves, surfaces, trimmed faces & shells into a STEP (AP214) file
+/// # Example
+/// ```
+/// use curvo::prelude::*;
+/// use nalgebra::{Point3, Vector3};
+///
+/// let circle = NurbsCurve3D::try_circle(&Point3::origin(), &Vector3::x(), &Vector3::y(), 1.).unwrap();
+/// let cylinder = NurbsSurface::extrude(&circle, &Vector3::z());
+///
+/// let mut writer = StepWriter::new();
+/// writer.add_curve(&circle);
+/// writer.add_surface(&cylinder);
+/// let step = writer.to_step_string("cylinder");
+/// assert!(step.starts_with("ISO-10303-21;"));
+/// assert!(step.contains("B_SPLINE_CURVE_WITH_KNOTS"));
+/// assert!(step.contains("RATIONAL_B_SPLINE_SURFACE"));
+/// assert!(step.trim_end().ends_with("END-ISO-10303-21;"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct StepWriter {
+    /// The entities in the data section, the id of the entity is its index + 1
+    entities: Vec<String>,
+    /// The ids of the top-level representation items
+    items: Vec<usize>,
+}
+
+/// Format a real number in the STEP syntax which requires a decimal point in the mantissa
+fn real<T: FloatingPoint>(x: T) -> String {
+    let s = format!("{:?}", x.to_f64().unwrap());
+    match s.split_once('e') {
+        Some((mantissa, exponent)) if mantissa.contains('.') => {
+            format!("{}E{}", mantissa, exponent)
+        }
+        Some((mantissa, exponent)) => format!("{}.E{}", mantissa, exponent),
+        None if s.contains('.') => s,
+        None => format!("{}.", s),
+    }
+}
+
+fn logical(b: bool) -> &'static str {
+    if b {
+        ".T."
+    } else {
+        ".F."
+    }
+}
+
+fn list<I: IntoIterator<Item = String>>(items: I) -> String {
+    format!("({})", items.into_iter().collect::<Vec<_>>().join(","))
+}
+
+fn refs<'a, I: IntoIterator<Item = &'a usize>>(ids: I) -> String {
+    list(ids.into_iter().map(|id| format!("#{}", id)))
+}
+
+/// Split the knot vector into the lists of the multiplicities & the distinct knots
+fn knots<T: FloatingPoint>(knots: &KnotVector<T>) -> (String, String) {
+    let multiplicity = knots.multiplicity();
+    (
+        list(multiplicity.iter().map(|m| m.multiplicity().to_string())),
+        list(multiplicity.iter().map(|m| real(*m.knot()))),
+    )
+}
+
+impl StepWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn push(&mut self, entity: String) -> usize {
+        self.entities.push(entity);
+        self.entities.len()
+    }
+
+    fn point<T: FloatingPoint>(&mut self, p: &Point3<T>) -> usize {
+        self.push(format!(
+            "CARTESIAN_POINT('',({},{},{}))",
+            real(p.x),
+            real(p.y),
+            real(p.z)
+        ))
+    }
+
+    fn curve<T: FloatingPoint>(&mut self, curve: &NurbsCurve3D<T>) -> usize {
+        let points = curve
+            .dehomogenized_control_points()
+            .iter()
+            .map(|p| self.point(p))
+            .collect::<Vec<_>>();
+        let weights = curve.weights();
+        let (multiplicities, knots) = knots(curve.knots());
+        let degree = curve.degree();
+        let entity = if weights.iter().all(|w| *w == T::one()) {
+            format!(
+                "B_SPLINE_CURVE_WITH_KNOTS('',{},{},.UNSPECIFIED.,.F.,.F.,{},{},.UNSPECIFIED.)",
+                degree,
+                refs(&points),
+                multiplicities,
+                knots
+            )
+        } else {
+            format!(
+                "(BOUNDED_CURVE() B_SPLINE_CURVE({},{},.UNSPECIFIED.,.F.,.F.) B_SPLINE_CURVE_WITH_KNOTS({},{},.UNSPECIFIED.) CURVE() GEOMETRIC_REPRESENTATION_ITEM() RATIONAL_B_SPLINE_CURVE({}) REPRESENTATION_ITEM(''))",
+                degree,
+                refs(&points),
+                multiplicities,
+                knots,
+                list(weights.into_iter().map(real))
+            )
+        };
+        self.push(entity)
+    }
+
+    fn surface<T: FloatingPoint>(&mut self, surface: &NurbsSurface3D<T>) -> usize {
+        let points = surface
+            .dehomogenized_control_points()
+            .iter()
+            .map(|row| row.iter().map(|p| self.point(p)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let grid = list(points.iter().map(refs));
+        let weights = surface
+            .control_points()
+            .iter()
+            .map(|row| row.iter().map(|p| p.w).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let (u_multiplicities, u_knots) = knots(surface.u_knots());
+        let (v_multiplicities, v_knots) = knots(surface.v_knots());
+        let (u_degree, v_degree) = (surface.u_degree(), surface.v_degree());
+        let entity = if weights.iter().flatten().all(|w| *w == T::one()) {
+            format!(
+                "B_SPLINE_SURFACE_WITH_KNOTS('',{},{},{},.UNSPECIFIED.,.F.,.F.,.F.,{},{},{},{},.UNSPECIFIED.)",
+                u_degree, v_degree, grid, u_multiplicities, v_multiplicities, u_knots, v_knots
+            )
+        } else {
+            format!(
+                "(BOUNDED_SURFACE() B_SPLINE_SURFACE({},{},{},.UNSPECIFIED.,.F.,.F.,.F.) B_SPLINE_SURFACE_WITH_KNOTS({},{},{},{},.UNSPECIFIED.) GEOMETRIC_REPRESENTATION_ITEM() RATIONAL_B_SPLINE_SURFACE({}) REPRESENTATION_ITEM('') SURFACE())",
+                u_degree,
+                v_degree,
+                grid,
+                u_multiplicities,
+                v_multiplicities,
+                u_knots,
+                v_knots,
+                list(
+                    weights
+                        .into_iter()
+                        .map(|row| list(row.into_iter().map(real)))
+                )
+            )
+        };
+        self.push(entity)
+    }
+
+    /// Write the loop as the oriented edges of the edge curves, skipping the degenerate edges at the poles
+    fn edge_loop<T: FloatingPoint>(
+        &mut self,
+        face_loop: &Loop<T>,
+        edges: &[usize],
+        shell: &Shell<T>,
+    ) -> usize {
+        let oriented = face_loop
+            .trims()
+            .iter()
+            .filter(|t| !shell.edges()[t.edge()].is_degenerate())
+            .map(|t| {
+                format!(
+                    "ORIENTED_EDGE('',*,*,#{},{})",
+                    edges[t.edge()],
+                    logical(!t.is_reversed())
+                )
+            })
+            .collect::<Vec<_>>();
+        let oriented = oriented
+            .into_iter()
+            .map(|e| self.push(e))
+            .collect::<Vec<_>>();
+        self.push(format!("EDGE_LOOP('',{})", refs(&oriented)))
+    }
+
+    /// Write the faces of the shell as advanced faces sharing the edge curves & return the id of the shell entity
+    fn shell<T: FloatingPoint>(&mut self, shell: &Shell<T>) -> usize {
+        let vertices = shell
+            .vertices()
+            .iter()
+            .map(|v| {
+                let p = self.point(v.point());
+                self.push(format!("VERTEX_POINT('',#{})", p))
+            })
+            .collect::<Vec<_>>();
+        let edges = shell
+            .edges()
+            .iter()
+            .map(|e| {
+                let curve = self.curve(e.curve());
+                self.push(format!(
+                    "EDGE_CURVE('',#{},#{},#{},.T.)",
+                    vertices[e.start()],
+                    vertices[e.end()],
+                    curve
+                ))
+            })
+            .collect::<Vec<_>>();
+        let faces = shell
+            .faces()
+            .iter()
+            .map(|f| {
+                let surface = self.surface(f.surface());
+                let outer = self.edge_loop(f.outer(), &edges, shell);
+                let mut bounds = vec![self.push(format!("FACE_OUTER_BOUND('',#{},.T.)", outer))];
+                for inner in f.inners() {
+                    let inner = self.edge_loop(inner, &edges, shell);
+                    bounds.push(self.push(format!("FACE_BOUND('',#{},.T.)", inner)));
+                }
+                self.push(format!(
+                    "ADVANCED_FACE('',{},#{},.T.)",
+                    refs(&bounds),
+                    surface
+                ))
+            })
+            .collect::<Vec<_>>();
+        let kind = if shell.is_closed() {
+            "CLOSED_SHELL"
+        } else {
+            "OPEN_SHELL"
+        };
+        self.push(format!("{}('',{})", kind, refs(&faces)))
+    }
+
+    /// Add the curve as a NURBS curve entity & return its id
+    pub fn add_curve<T: FloatingPoint>(&mut self, curve: &NurbsCurve3D<T>) -> usize {
+        let id = self.curve(curve);
+        self.items.push(id);
+        id
+    }
+
+    /// Add the surface as a NURBS surface entity & return its id
+    pub fn add_surface<T: FloatingPoint>(&mut self, surface: &NurbsSurface3D<T>) -> usize {
+        let id = self.surface(surface);
+        self.items.push(id);
+        id
+    }
+
+    /// Add the shell as a solid if it is closed or a surface model if it is open & return the id of the shell entity
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    ///
+    /// let p = |x: f64, y: f64, z: f64| Point3::new(x, y, z);
+    /// let face = |a: Point3<f64>, b: Point3<f64>, d: Vector3<f64>| {
+    ///     NurbsSurface::extrude(&NurbsCurve3D::polyline(&[a, b]), &d)
+    /// };
+    /// let cube = vec![
+    ///     face(p(0., 0., 0.), p(1., 0., 0.), Vector3::y()),
+    ///     face(p(0., 0., 1.), p(1., 0., 1.), Vector3::y()),
+    ///     face(p(0., 0., 0.), p(0., 1., 0.), Vector3::z()),
+    ///     face(p(1., 0., 0.), p(1., 1., 0.), Vector3::z()),
+    ///     face(p(0., 0., 0.), p(0., 0., 1.), Vector3::x()),
+    ///     face(p(0., 1., 0.), p(0., 1., 1.), Vector3::x()),
+    /// ];
+    /// let shell = Shell::try_from_surfaces(&cube, 1e-6).unwrap();
+    ///
+    /// let mut writer = StepWriter::new();
+    /// writer.add_shell(&shell);
+    /// let step = writer.to_step_string("cube");
+    /// assert!(step.contains("MANIFOLD_SOLID_BREP"));
+    /// assert!(step.contains("CLOSED_SHELL"));
+    /// assert_eq!(step.matches("ADVANCED_FACE").count(), 6);
+    /// assert_eq!(step.matches("EDGE_CURVE").count(), 12);
+    /// assert_eq!(step.matches("VERTEX_POINT").count(), 8);
+    /// ```
+    pub fn add_shell<T: FloatingPoint>(&mut self, shell: &Shell<T>) -> usize {
+        let id = self.shell(shell);
+        let item = if shell.is_closed() {
+            format!("MANIFOLD_SOLID_BREP('',#{})", id)
+        } else {
+            format!("SHELL_BASED_SURFACE_MODEL('',(#{}))", id)
+        };
+        let item = self.push(item);
+        self.items.push(item);
+        id
+    }
+
+    /// Add the trimmed surfaces as the faces of a shell sharing the coincident edges within the tolerance
+    pub fn try_add_trimmed_surfaces<T: FloatingPoint + ArgminFloat>(
+        &mut self,
+        surfaces: &[TrimmedSurface<T>],
+        tolerance: T,
+    ) -> anyhow::Result<usize> {
+        let shell = Shell::try_from_trimmed_surfaces(surfaces, tolerance)?;
+        Ok(self.add_shell(&shell))
+    }
+
+    /// Serialize the added entities into a STEP file with the product name
+    pub fn to_step_string(&self, name: &str) -> String {
+        let name = name.replace('\'', "''");
+        let mut entities = self.entities.clone();
+        let mut push = |entity: String| {
+            entities.push(entity);
+            entities.len()
+        };
+
+        let application = push(
+            "APPLICATION_CONTEXT('core data for automotive mechanical design processes')".into(),
+        );
+        push(format!(
+            "APPLICATION_PROTOCOL_DEFINITION('international standard','automotive_design',2000,#{})",
+            application
+        ));
+        let product_context = push(format!("PRODUCT_CONTEXT('',#{},'mechanical')", application));
+        let product = push(format!(
+            "PRODUCT('{}','{}','',(#{}))",
+            name, name, product_context
+        ));
+        let formation = push(format!("PRODUCT_DEFINITION_FORMATION('','',#{})", product));
+        let definition_context = push(format!(
+            "PRODUCT_DEFINITION_CONTEXT('part definition',#{},'design')",
+            application
+        ));
+        let definition = push(format!(
+            "PRODUCT_DEFINITION('design','',#{},#{})",
+            formation, definition_context
+        ));
+        let shape = push(format!("PRODUCT_DEFINITION_SHAPE('','',#{})", definition));
+        let length = push("(LENGTH_UNIT() NAMED_UNIT(*) SI_UNIT(.MILLI.,.METRE.))".into());
+        let angle = push("(NAMED_UNIT(*) PLANE_ANGLE_UNIT() SI_UNIT($,.RADIAN.))".into());
+        let solid_angle = push("(NAMED_UNIT(*) SI_UNIT($,.STERADIAN.) SOLID_ANGLE_UNIT())".into());
+        let uncertainty = push(format!(
+            "UNCERTAINTY_MEASURE_WITH_UNIT(LENGTH_MEASURE(1.E-06),#{},'distance_accuracy_value','confusion accuracy')",
+            length
+        ));
+        let context = push(format!(
+            "(GEOMETRIC_REPRESENTATION_CONTEXT(3) GLOBAL_UNCERTAINTY_ASSIGNED_CONTEXT((#{})) GLOBAL_UNIT_ASSIGNED_CONTEXT((#{},#{},#{})) REPRESENTATION_CONTEXT('',''))",
+            uncertainty, length, angle, solid_angle
+        ));
+        let representation = push(format!(
+            "SHAPE_REPRESENTATION('',{},#{})",
+            refs(&self.items),
+            context
+        ));
+        push(format!(
+            "SHAPE_DEFINITION_REPRESENTATION(#{},#{})",
+            shape, representation
+        ));
+
+        let mut out = String::new();
+        out.push_str("ISO-10303-21;\nHEADER;\n");
+        out.push_str("FILE_DESCRIPTION(('curvo'),'2;1');\n");
+        let _ = writeln!(out, "FILE_NAME('{}','',(''),(''),'curvo','','');", name);
+        out.push_str("FILE_SCHEMA(('AUTOMOTIVE_DESIGN { 1 0 10303 214 1 1 1 1 }'));\n");
+        out.push_str("ENDSEC;\nDATA;\n");
+        for (i, entity) in entities.iter().enumerate() {
+            let _ = writeln!(out, "#{}={};", i + 1, entity);
+        }
+        out.push_str("ENDSEC;\nEND-ISO-10303-21;\n");
+        out
+    }
+
+    /// Write the STEP file with the product name into the writer
+    pub fn write<W: std::io::Write>(&self, mut writer: W, name: &str) -> anyhow::Result<()> {
+        writer.write_all(self.to_step_string(name).as_bytes())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::real;
+
+    #[test]
+    fn format_real() {
+        assert_eq!(real(1.0), "1.0");
+        assert_eq!(real(-0.5), "-0.5");
+        assert_eq!(real(1e-7), "1.E-7");
+        assert_eq!(real(1.5e20), "1.5E20");
+    }
+}
@@ -0,0 +1,5 @@
+pub mod svg_path;
+pub mod svg_writer;
+
+pub use svg_path::*;
+pub use svg_writer::*;
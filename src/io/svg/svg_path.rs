@@ -0,0 +1,294 @@
+use std::f64::consts::TAU;
+
+use nalgebra::{Point2, Point3, Vector2};
+
+use crate::{
+    curve::{CompoundCurve2D, NurbsCurve2D},
+    misc::{FloatingPoint, Invertible},
+};
+
+/// A tokenizer of the SVG path data, reading the commands, numbers & arc flags
+struct Tokenizer<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+}
+
+impl<'a> Tokenizer<'a> {
+    fn new(d: &'a str) -> Self {
+        Self {
+            chars: d.chars().peekable(),
+        }
+    }
+
+    fn skip_separators(&mut self) {
+        while self
+            .chars
+            .next_if(|c| c.is_whitespace() || *c == ',')
+            .is_some()
+        {}
+    }
+
+    /// Read the next command letter if it exists
+    fn command(&mut self) -> Option<char> {
+        self.skip_separators();
+        self.chars.next_if(|c| c.is_ascii_alphabetic())
+    }
+
+    /// Check if the next token is a number
+    fn has_number(&mut self) -> bool {
+        self.skip_separators();
+        self.chars
+            .peek()
+            .is_some_and(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.'))
+    }
+
+    fn number(&mut self) -> anyhow::Result<f64> {
+        self.skip_separators();
+        let mut s = String::new();
+        if let Some(c) = self.chars.next_if(|c| matches!(c, '-' | '+')) {
+            s.push(c);
+        }
+        let mut has_dot = false;
+        while let Some(c) = self
+            .chars
+            .next_if(|c| c.is_ascii_digit() || (*c == '.' && !has_dot))
+        {
+            has_dot |= c == '.';
+            s.push(c);
+        }
+        if let Some(e) = self.chars.next_if(|c| matches!(c, 'e' | 'E')) {
+            s.push(e);
+            if let Some(c) = self.chars.next_if(|c| matches!(c, '-' | '+')) {
+                s.push(c);
+            }
+            while let Some(c) = self.chars.next_if(|c| c.is_ascii_digit()) {
+                s.push(c);
+            }
+        }
+        s.parse()
+            .map_err(|_| anyhow::anyhow!("Invalid number '{}' in the path data", s))
+    }
+
+    /// The arc flags are single digits which may not be separated
+    fn flag(&mut self) -> anyhow::Result<bool> {
+        self.skip_separators();
+        match self.chars.next() {
+            Some('0') => Ok(false),
+            Some('1') => Ok(true),
+            c => anyhow::bail!("Invalid arc flag {:?} in the path data", c),
+        }
+    }
+
+    fn point(&mut self) -> anyhow::Result<Vector2<f64>> {
+        Ok(Vector2::new(self.number()?, self.number()?))
+    }
+}
+
+fn cast<T: FloatingPoint>(p: Vector2<f64>) -> Point2<T> {
+    Point2::new(T::from_f64(p.x).unwrap(), T::from_f64(p.y).unwrap())
+}
+
+/// Create a Bézier curve from the control points
+fn bezier<T: FloatingPoint>(points: &[Vector2<f64>]) -> anyhow::Result<NurbsCurve2D<T>> {
+    let degree = points.len() - 1;
+    let control_points = points
+        .iter()
+        .map(|p| {
+            let p = cast::<T>(*p);
+            Point3::new(p.x, p.y, T::one())
+        })
+        .collect();
+    let knots = (0..=degree)
+        .map(|_| T::zero())
+        .chain((0..=degree).map(|_| T::one()))
+        .collect();
+    NurbsCurve2D::try_new(degree, control_points, knots)
+}
+
+/// Create an elliptical arc from the endpoint parameterization of SVG as a rational quadratic curve
+fn arc<T: FloatingPoint>(
+    from: Vector2<f64>,
+    radii: Vector2<f64>,
+    rotation: f64,
+    large_arc: bool,
+    sweep: bool,
+    to: Vector2<f64>,
+) -> anyhow::Result<NurbsCurve2D<T>> {
+    let (mut rx, mut ry) = (radii.x.abs(), radii.y.abs());
+    if rx == 0. || ry == 0. {
+        return Ok(NurbsCurve2D::polyline(&[cast(from), cast(to)]));
+    }
+
+    // the conversion from the endpoint to the center parameterization in the SVG specification
+    let (sin, cos) = rotation.to_radians().sin_cos();
+    let half = (from - to) * 0.5;
+    let p = Vector2::new(cos * half.x + sin * half.y, -sin * half.x + cos * half.y);
+    let lambda = (p.x * p.x) / (rx * rx) + (p.y * p.y) / (ry * ry);
+    if lambda > 1. {
+        // scale up the radii to reach the end point
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+    let numerator = rx * rx * ry * ry - rx * rx * p.y * p.y - ry * ry * p.x * p.x;
+    let denominator = rx * rx * p.y * p.y + ry * ry * p.x * p.x;
+    let mut coefficient = (numerator / denominator).max(0.).sqrt();
+    if large_arc == sweep {
+        coefficient = -coefficient;
+    }
+    let c = Vector2::new(rx * p.y / ry, -ry * p.x / rx) * coefficient;
+    let center = Vector2::new(cos * c.x - sin * c.y, sin * c.x + cos * c.y) + (from + to) * 0.5;
+
+    let angle = |v: Vector2<f64>| v.y.atan2(v.x);
+    let start = angle(Vector2::new((p.x - c.x) / rx, (p.y - c.y) / ry));
+    let mut delta = angle(Vector2::new((-p.x - c.x) / rx, (-p.y - c.y) / ry)) - start;
+    if sweep && delta < 0. {
+        delta += TAU;
+    } else if !sweep && delta > 0. {
+        delta -= TAU;
+    }
+
+    let x_axis = cast::<T>(Vector2::new(cos, sin) * rx).coords;
+    let y_axis = cast::<T>(Vector2::new(-sin, cos) * ry).coords;
+    let center = cast::<T>(center);
+    let t = |x: f64| T::from_f64(x).unwrap();
+    if delta > 0. {
+        NurbsCurve2D::try_ellipse_arc(&center, &x_axis, &y_axis, t(start), t(start + delta))
+    } else {
+        NurbsCurve2D::try_ellipse_arc(&center, &x_axis, &y_axis, t(start + delta), t(start))
+            .map(|c| c.inverse())
+    }
+}
+
+/// Parse the SVG path data into the compound curves of the subpaths
+/// Lines become degree 1 curves, quadratic & cubic Béziers become Bézier curves and elliptical arcs become rational quadratic curves exactly.
+/// The coordinates are kept in the SVG user space, so the y axis points downward.
+/// # Example
+/// ```
+/// use curvo::prelude::*;
+/// use nalgebra::Point2;
+///
+/// let paths = parse_svg_path::<f64>("M 0 0 H 2 V 2 Q 1 3 0 2 Z M 4,0 a 1 1 0 0 1 2 0").unwrap();
+/// assert_eq!(paths.len(), 2);
+/// assert!(paths[0].is_closed(None));
+/// assert_eq!(paths[0].spans().len(), 4);
+///
+/// // the arc is a half circle centered at (5, 0)
+/// let (start, end) = paths[1].knots_domain();
+/// let p = paths[1].point_at((start + end) * 0.5);
+/// assert!((p - Point2::new(5., -1.)).norm() < 1e-8);
+/// let points = paths[1].tessellate(None);
+/// assert!(points.iter().all(|p| ((p - Point2::new(5., 0.)).norm() - 1.).abs() < 1e-8));
+/// ```
+pub fn parse_svg_path<T: FloatingPoint>(d: &str) -> anyhow::Result<Vec<CompoundCurve2D<T>>> {
+    let mut tokens = Tokenizer::new(d);
+    let mut paths = vec![];
+    let mut spans: Vec<NurbsCurve2D<T>> = vec![];
+    let mut current = Vector2::zeros();
+    let mut start = Vector2::zeros();
+    // the last control point of the previous Bézier segment for the smooth commands
+    let mut last_control: Option<(char, Vector2<f64>)> = None;
+    let tolerance = 1e-9;
+
+    let finish = |spans: &mut Vec<NurbsCurve2D<T>>,
+                  paths: &mut Vec<CompoundCurve2D<T>>|
+     -> anyhow::Result<()> {
+        if !spans.is_empty() {
+            paths.push(CompoundCurve2D::try_new(std::mem::take(spans))?);
+        }
+        Ok(())
+    };
+
+    let mut command = None;
+    loop {
+        if let Some(c) = tokens.command() {
+            command = Some(c);
+        } else if !tokens.has_number() {
+            tokens.skip_separators();
+            anyhow::ensure!(
+                tokens.chars.peek().is_none(),
+                "Unexpected character {:?} in the path data",
+                tokens.chars.peek()
+            );
+            break;
+        }
+        let Some(c) = command else {
+            anyhow::bail!("The path data must start with a command");
+        };
+        let relative = c.is_ascii_lowercase();
+        let offset = if relative { current } else { Vector2::zeros() };
+        match c.to_ascii_uppercase() {
+            'M' => {
+                finish(&mut spans, &mut paths)?;
+                current = tokens.point()? + offset;
+                start = current;
+                // the following coordinate pairs are the implicit line commands
+                command = Some(if relative { 'l' } else { 'L' });
+                last_control = None;
+                continue;
+            }
+            'Z' => {
+                if (current - start).norm() > tolerance {
+                    spans.push(NurbsCurve2D::polyline(&[cast(current), cast(start)]));
+                }
+                finish(&mut spans, &mut paths)?;
+                current = start;
+                command = None;
+                last_control = None;
+                continue;
+            }
+            'L' | 'H' | 'V' => {
+                let to = match c.to_ascii_uppercase() {
+                    'L' => tokens.point()? + offset,
+                    'H' => Vector2::new(tokens.number()? + offset.x, current.y),
+                    _ => Vector2::new(current.x, tokens.number()? + offset.y),
+                };
+                spans.push(NurbsCurve2D::polyline(&[cast(current), cast(to)]));
+                current = to;
+                last_control = None;
+            }
+            'C' | 'S' => {
+                let c1 = if c.eq_ignore_ascii_case(&'C') {
+                    tokens.point()? + offset
+                } else {
+                    match last_control {
+                        Some(('C', p)) => current * 2. - p,
+                        _ => current,
+                    }
+                };
+                let c2 = tokens.point()? + offset;
+                let to = tokens.point()? + offset;
+                spans.push(bezier(&[current, c1, c2, to])?);
+                current = to;
+                last_control = Some(('C', c2));
+            }
+            'Q' | 'T' => {
+                let c1 = if c.eq_ignore_ascii_case(&'Q') {
+                    tokens.point()? + offset
+                } else {
+                    match last_control {
+                        Some(('Q', p)) => current * 2. - p,
+                        _ => current,
+                    }
+                };
+                let to = tokens.point()? + offset;
+                spans.push(bezier(&[current, c1, to])?);
+                current = to;
+                last_control = Some(('Q', c1));
+            }
+            'A' => {
+                let radii = tokens.point()?;
+                let rotation = tokens.number()?;
+                let large_arc = tokens.flag()?;
+                let sweep = tokens.flag()?;
+                let to = tokens.point()? + offset;
+                if (to - current).norm() > tolerance {
+                    spans.push(arc(current, radii, rotation, large_arc, sweep, to)?);
+                }
+                current = to;
+                last_control = None;
+            }
+            _ => anyhow::bail!("Unknown command '{}' in the path data", c),
+        }
+    }
+    finish(&mut spans, &mut paths)?;
+    Ok(paths)
+}
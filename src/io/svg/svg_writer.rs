@@ -0,0 +1,219 @@
+use std::fmt::Write as _;
+
+use nalgebra::{Point2, Vector2};
+
+use crate::{
+    curve::{CompoundCurve2D, NurbsCurve2D, Region},
+    misc::FloatingPoint,
+};
+
+/// A shape to be written as an SVG path element
+#[derive(Clone, Debug)]
+struct Shape {
+    d: String,
+    /// Whether the shape is a filled region or a stroked curve
+    filled: bool,
+}
+
+/// A writer to export planar curves & regions as SVG paths
+/// The non-rational Bézier spans up to degree 3 are written exactly, and the other spans are approximated by cubic Béziers within the tolerance.
+/// # Example
+/// ```
+/// use curvo::prelude::*;
+/// use nalgebra::{Point2, Vector2};
+///
+/// let square = NurbsCurve2D::polyline(&[
+///     Point2::new(0., 0.),
+///     Point2::new(4., 0.),
+///     Point2::new(4., 4.),
+///     Point2::new(0., 4.),
+///     Point2::new(0., 0.),
+/// ]);
+/// let hole = NurbsCurve2D::try_circle(&Point2::new(2., 2.), &Vector2::x(), &Vector2::y(), 1.).unwrap();
+///
+/// let mut writer = SvgWriter::new(1e-3);
+/// writer.add_region(&Region::new(square.into(), vec![hole.clone().into()]));
+/// writer.add_curve(&hole);
+/// let svg = writer.to_svg_string();
+/// assert!(svg.starts_with("<svg"));
+/// assert!(svg.contains("fill-rule=\"evenodd\""));
+/// assert_eq!(svg.matches("<path").count(), 2);
+///
+/// // the exported path is imported back within the tolerance
+/// let d = svg.split("d=\"").nth(2).unwrap().split('"').next().unwrap();
+/// let circle = &parse_svg_path::<f64>(d).unwrap()[0];
+/// assert!(circle.tessellate(None).iter().all(|p| ((p - Point2::new(2., 2.)).norm() - 1.).abs() < 1e-3));
+/// ```
+#[derive(Clone, Debug)]
+pub struct SvgWriter<T: FloatingPoint> {
+    tolerance: T,
+    shapes: Vec<Shape>,
+    /// The bounding box of the shapes
+    bounds: Option<(Point2<T>, Point2<T>)>,
+}
+
+impl<T: FloatingPoint> SvgWriter<T> {
+    /// Create a writer with the tolerance of the cubic Bézier approximation
+    pub fn new(tolerance: T) -> Self {
+        Self {
+            tolerance,
+            shapes: vec![],
+            bounds: None,
+        }
+    }
+
+    /// Add the curve as a stroked path
+    pub fn add_curve(&mut self, curve: &NurbsCurve2D<T>) {
+        self.add_compound_curve(&curve.clone().into());
+    }
+
+    /// Add the compound curve as a stroked path
+    pub fn add_compound_curve(&mut self, curve: &CompoundCurve2D<T>) {
+        let d = self.path(curve);
+        self.shapes.push(Shape { d, filled: false });
+    }
+
+    /// Add the region bounded by the exterior & the holes as a filled path with the even-odd fill rule
+    pub fn add_region(&mut self, region: &Region<T>) {
+        let mut d = self.path(region.exterior());
+        for interior in region.interiors() {
+            d.push(' ');
+            d.push_str(&self.path(interior));
+        }
+        self.shapes.push(Shape { d, filled: true });
+    }
+
+    /// Convert the compound curve into the path data, extending the bounding box
+    fn path(&mut self, curve: &CompoundCurve2D<T>) -> String {
+        for p in curve.tessellate(None) {
+            self.bounds = Some(match self.bounds {
+                Some((min, max)) => (min.inf(&p), max.sup(&p)),
+                None => (p, p),
+            });
+        }
+
+        let f = |x: T| x.to_f64().unwrap();
+        let p = |p: &Point2<T>| format!("{} {}", f(p.x), f(p.y));
+        let spans = curve.spans();
+        let first = &spans[0];
+        let mut d = format!("M {}", p(&first.point_at(first.knots_domain().0)));
+        for span in spans {
+            for segment in self.segments(span) {
+                let _ = match segment.len() {
+                    2 => write!(d, " L {}", p(&segment[1])),
+                    3 => write!(d, " Q {} {}", p(&segment[1]), p(&segment[2])),
+                    _ => write!(
+                        d,
+                        " C {} {} {}",
+                        p(&segment[1]),
+                        p(&segment[2]),
+                        p(&segment[3])
+                    ),
+                };
+            }
+        }
+        if curve.is_closed(None) {
+            d.push_str(" Z");
+        }
+        d
+    }
+
+    /// Convert the span into the Bézier segments, approximating the rational or high degree segments by cubics
+    fn segments(&self, span: &NurbsCurve2D<T>) -> Vec<Vec<Point2<T>>> {
+        let is_rational = span.weights().iter().any(|w| *w != T::one());
+        if !is_rational && span.degree() <= 3 {
+            if let Ok(beziers) = span.try_decompose_bezier_segments() {
+                return beziers
+                    .iter()
+                    .map(|b| b.dehomogenized_control_points())
+                    .collect();
+            }
+        }
+        let (start, end) = span.knots_domain();
+        let mut segments = vec![];
+        self.approximate(span, start, end, 0, &mut segments);
+        segments
+    }
+
+    /// Approximate the interval of the curve by a cubic Hermite segment, subdividing it until it is within the tolerance
+    fn approximate(
+        &self,
+        curve: &NurbsCurve2D<T>,
+        a: T,
+        b: T,
+        depth: usize,
+        segments: &mut Vec<Vec<Point2<T>>>,
+    ) {
+        let three = T::from_usize(3).unwrap();
+        let third = (b - a) / three;
+        let p0 = curve.point_at(a);
+        let p3 = curve.point_at(b);
+        let p1 = p0 + curve.tangent_at(a) * third;
+        let p2 = p3 - curve.tangent_at(b) * third;
+        let bezier = |t: T| {
+            let s = T::one() - t;
+            p0.coords * (s * s * s)
+                + p1.coords * (three * s * s * t)
+                + p2.coords * (three * s * t * t)
+                + p3.coords * (t * t * t)
+        };
+        let error = (1..8)
+            .map(|i| {
+                let t = T::from_usize(i).unwrap() / T::from_usize(8).unwrap();
+                (bezier(t) - curve.point_at(a + (b - a) * t).coords).norm()
+            })
+            .fold(T::zero(), |a, b| a.max(b));
+        if error <= self.tolerance || depth >= 16 {
+            segments.push(vec![p0, p1, p2, p3]);
+        } else {
+            let mid = (a + b) * T::from_f64(0.5).unwrap();
+            self.approximate(curve, a, mid, depth + 1, segments);
+            self.approximate(curve, mid, b, depth + 1, segments);
+        }
+    }
+
+    /// Serialize the shapes into an SVG document with the view box fitting them
+    pub fn to_svg_string(&self) -> String {
+        let f = |x: T| x.to_f64().unwrap();
+        let (min, max) = self
+            .bounds
+            .map(|(min, max)| {
+                (
+                    Vector2::new(f(min.x), f(min.y)),
+                    Vector2::new(f(max.x), f(max.y)),
+                )
+            })
+            .unwrap_or((Vector2::zeros(), Vector2::new(1., 1.)));
+        let size = max - min;
+        let margin = size.x.max(size.y) * 0.05;
+        // the stroke width scales with the view box to be visible at any size
+        let stroke = size.x.max(size.y).max(f64::EPSILON) * 0.005;
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">\n",
+            min.x - margin,
+            min.y - margin,
+            size.x + margin * 2.,
+            size.y + margin * 2.
+        );
+        for shape in self.shapes.iter() {
+            let style = if shape.filled {
+                format!(
+                    "fill=\"gray\" fill-rule=\"evenodd\" stroke=\"black\" stroke-width=\"{}\"",
+                    stroke
+                )
+            } else {
+                format!("fill=\"none\" stroke=\"black\" stroke-width=\"{}\"", stroke)
+            };
+            let _ = writeln!(svg, "  <path d=\"{}\" {}/>", shape.d, style);
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Write the SVG document into the writer
+    pub fn write<W: std::io::Write>(&self, mut writer: W) -> anyhow::Result<()> {
+        writer.write_all(self.to_svg_string().as_bytes())?;
+        Ok(())
+    }
+}
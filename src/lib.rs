@@ -1,9 +1,14 @@
 #![allow(clippy::needless_range_loop)]
 
+mod boolean;
 mod bounding_box;
+mod brep;
 mod closest_parameter;
 mod curve;
+mod distance;
+mod fitting;
 mod intersection;
+mod io;
 mod knot;
 mod misc;
 mod surface;
@@ -11,12 +16,22 @@ mod tessellation;
 use closest_parameter::*;
 
 pub mod prelude {
+    pub use crate::boolean::*;
     pub use crate::bounding_box::*;
+    pub use crate::brep::*;
+    pub use crate::closest_parameter::{
+        ClosestParameterOptions, CurveClosestParameters, CurveSurfaceClosestParameters,
+    };
     pub use crate::curve::*;
+    pub use crate::distance::*;
+    pub use crate::fitting::*;
     pub use crate::intersection::*;
+    pub use crate::io::*;
     pub use crate::knot::*;
     pub use crate::misc::*;
     pub use crate::surface::*;
     pub use crate::tessellation::adaptive_tessellation_option::AdaptiveTessellationOptions;
+    pub use crate::tessellation::surface_draft::*;
+    pub use crate::tessellation::surface_isophote::*;
     pub use crate::tessellation::surface_tessellation::*;
 }
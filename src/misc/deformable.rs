@@ -0,0 +1,26 @@
+use nalgebra::{Point3, Point4};
+
+use crate::misc::FloatingPoint;
+
+/// A trait for the deformations mapping the points in 3D space
+pub trait SpaceDeformation<T: FloatingPoint> {
+    fn deform_point(&self, point: &Point3<T>) -> Point3<T>;
+
+    /// Deform the control point with the homogeneous coordinates keeping its weight
+    fn deform_control_point(&self, point: &mut Point4<T>) {
+        let w = point.w;
+        let deformed = self.deform_point(&Point3::new(point.x / w, point.y / w, point.z / w));
+        *point = Point4::new(deformed.x * w, deformed.y * w, deformed.z * w, w);
+    }
+}
+
+/// A trait for the geometries deformed through their control points
+pub trait Deformable<T: FloatingPoint>: Clone {
+    fn deform<F: SpaceDeformation<T>>(&mut self, deformation: &F);
+
+    fn deformed<F: SpaceDeformation<T>>(&self, deformation: &F) -> Self {
+        let mut clone = self.clone();
+        clone.deform(deformation);
+        clone
+    }
+}
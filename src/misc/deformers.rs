@@ -0,0 +1,204 @@
+use nalgebra::{Point3, Unit, UnitQuaternion, Vector3};
+
+use crate::misc::{FloatingPoint, SpaceDeformation};
+
+/// A twist rotating the points about the axis by the angle proportional to the distance along the axis from the origin
+/// # Example
+/// ```
+/// use curvo::prelude::*;
+/// use nalgebra::{Point3, Vector3};
+/// use approx::assert_relative_eq;
+///
+/// // a quarter turn per unit length
+/// let twist = Twist::new(Point3::origin(), Vector3::z(), std::f64::consts::FRAC_PI_2);
+/// assert_relative_eq!(twist.deform_point(&Point3::new(1., 0., 0.)), Point3::new(1., 0., 0.), epsilon = 1e-10);
+/// assert_relative_eq!(twist.deform_point(&Point3::new(1., 0., 1.)), Point3::new(0., 1., 1.), epsilon = 1e-10);
+///
+/// let line = NurbsCurve3D::polyline(&[Point3::new(1., 0., 0.), Point3::new(1., 0., 2.)]);
+/// let twisted = line.deformed(&twist);
+/// let (_, end) = twisted.knots_domain();
+/// assert_relative_eq!(twisted.point_at(end), Point3::new(-1., 0., 2.), epsilon = 1e-10);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Twist<T: FloatingPoint> {
+    origin: Point3<T>,
+    axis: Unit<Vector3<T>>,
+    /// The angle of the rotation per unit length along the axis in radians
+    rate: T,
+}
+
+impl<T: FloatingPoint> Twist<T> {
+    /// Create a new twist about the axis through the origin
+    /// The axis is normalized.
+    pub fn new(origin: Point3<T>, axis: Vector3<T>, rate: T) -> Self {
+        Self {
+            origin,
+            axis: Unit::new_normalize(axis),
+            rate,
+        }
+    }
+
+    pub fn origin(&self) -> &Point3<T> {
+        &self.origin
+    }
+
+    pub fn axis(&self) -> &Vector3<T> {
+        &self.axis
+    }
+
+    pub fn rate(&self) -> T {
+        self.rate
+    }
+}
+
+impl<T: FloatingPoint> SpaceDeformation<T> for Twist<T> {
+    fn deform_point(&self, point: &Point3<T>) -> Point3<T> {
+        let offset = point - self.origin;
+        let height = offset.dot(&self.axis);
+        let rotation = UnitQuaternion::from_axis_angle(&self.axis, self.rate * height);
+        self.origin + rotation * offset
+    }
+}
+
+/// A taper scaling the distances of the points from the axis linearly along the axis
+/// The scale is 1 at the origin & changes by the rate per unit length along the axis, never falling below zero.
+/// # Example
+/// ```
+/// use curvo::prelude::*;
+/// use nalgebra::{Point3, Vector3};
+/// use approx::assert_relative_eq;
+///
+/// // the cylinder tapers into the cone with the apex at the height of 2
+/// let taper = Taper::new(Point3::origin(), Vector3::z(), -0.5);
+/// let cylinder = NurbsSurface::try_cylinder(&Point3::origin(), &(Vector3::z() * 2.), 1.).unwrap();
+/// let cone = cylinder.deformed(&taper);
+/// let (u0, u1) = cone.u_knots_domain();
+/// let (v0, v1) = cone.v_knots_domain();
+/// for i in 0..=4 {
+///     let p = cone.point_at(u0 + (u1 - u0) * i as f64 / 4., (v0 + v1) / 2.);
+///     assert_relative_eq!(p.coords.xy().norm(), 1. - p.z / 2., epsilon = 1e-10);
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Taper<T: FloatingPoint> {
+    origin: Point3<T>,
+    axis: Unit<Vector3<T>>,
+    /// The change of the scale per unit length along the axis
+    rate: T,
+}
+
+impl<T: FloatingPoint> Taper<T> {
+    /// Create a new taper along the axis through the origin
+    /// The axis is normalized.
+    pub fn new(origin: Point3<T>, axis: Vector3<T>, rate: T) -> Self {
+        Self {
+            origin,
+            axis: Unit::new_normalize(axis),
+            rate,
+        }
+    }
+
+    pub fn origin(&self) -> &Point3<T> {
+        &self.origin
+    }
+
+    pub fn axis(&self) -> &Vector3<T> {
+        &self.axis
+    }
+
+    pub fn rate(&self) -> T {
+        self.rate
+    }
+}
+
+impl<T: FloatingPoint> SpaceDeformation<T> for Taper<T> {
+    fn deform_point(&self, point: &Point3<T>) -> Point3<T> {
+        let offset = point - self.origin;
+        let along = self.axis.as_ref() * offset.dot(&self.axis);
+        let scale = (T::one() + self.rate * offset.dot(&self.axis)).max(T::zero());
+        self.origin + along + (offset - along) * scale
+    }
+}
+
+/// A bend wrapping the points along the axis around the arc of the radius
+/// The segment along the axis from the origin is mapped onto the arc of the same length, which is tangent to the axis at the origin
+/// & curves toward the direction, and the offsets toward the center of the arc shrink the radius.
+/// # Example
+/// ```
+/// use curvo::prelude::*;
+/// use nalgebra::{Point3, Vector3};
+/// use approx::assert_relative_eq;
+///
+/// // bending the line of the length of pi into the half circle of the radius 1
+/// let bend = Bend::try_new(Point3::origin(), Vector3::x(), Vector3::y(), 1.).unwrap();
+/// let pi = std::f64::consts::PI;
+/// assert_relative_eq!(bend.deform_point(&Point3::new(pi, 0., 0.)), Point3::new(0., 2., 0.), epsilon = 1e-10);
+/// assert_relative_eq!(bend.deform_point(&Point3::new(pi / 2., 0.5, 1.)), Point3::new(0.5, 1., 1.), epsilon = 1e-10);
+///
+/// // the distances from the center are kept along the bent curve
+/// let line = NurbsCurve3D::polyline(&[Point3::new(0., 0., 0.), Point3::new(0.5, 0., 0.)]);
+/// let bent = line.deformed(&bend);
+/// let (_, end) = bent.knots_domain();
+/// assert_relative_eq!((bent.point_at(end) - Point3::new(0., 1., 0.)).norm(), 1., epsilon = 1e-10);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bend<T: FloatingPoint> {
+    origin: Point3<T>,
+    axis: Unit<Vector3<T>>,
+    /// The unit direction from the origin to the center of the arc perpendicular to the axis
+    direction: Unit<Vector3<T>>,
+    radius: T,
+}
+
+impl<T: FloatingPoint> Bend<T> {
+    /// Try to create a new bend along the axis from the origin, curving toward the direction perpendicular to the axis
+    /// The direction is orthogonalized against the axis.
+    pub fn try_new(
+        origin: Point3<T>,
+        axis: Vector3<T>,
+        direction: Vector3<T>,
+        radius: T,
+    ) -> anyhow::Result<Self> {
+        anyhow::ensure!(radius > T::zero(), "The radius must be positive");
+        let axis = Unit::try_new(axis, T::default_epsilon())
+            .ok_or(anyhow::anyhow!("The axis must not be zero"))?;
+        let direction = direction - axis.as_ref() * direction.dot(&axis);
+        let direction = Unit::try_new(direction, T::default_epsilon()).ok_or(anyhow::anyhow!(
+            "The direction must not be parallel to the axis"
+        ))?;
+        Ok(Self {
+            origin,
+            axis,
+            direction,
+            radius,
+        })
+    }
+
+    pub fn origin(&self) -> &Point3<T> {
+        &self.origin
+    }
+
+    pub fn axis(&self) -> &Vector3<T> {
+        &self.axis
+    }
+
+    pub fn direction(&self) -> &Vector3<T> {
+        &self.direction
+    }
+
+    pub fn radius(&self) -> T {
+        self.radius
+    }
+}
+
+impl<T: FloatingPoint> SpaceDeformation<T> for Bend<T> {
+    fn deform_point(&self, point: &Point3<T>) -> Point3<T> {
+        let offset = point - self.origin;
+        let (x, y) = (offset.dot(&self.axis), offset.dot(&self.direction));
+        let rest = offset - self.axis.as_ref() * x - self.direction.as_ref() * y;
+        let angle = x / self.radius;
+        let center = self.origin + self.direction.as_ref() * self.radius;
+        let radial = self.axis.as_ref() * angle.sin() - self.direction.as_ref() * angle.cos();
+        center + radial * (self.radius - y) + rest
+    }
+}
@@ -0,0 +1,184 @@
+use nalgebra::{Matrix3, Point3, Vector3};
+
+use crate::{
+    knot::KnotVector,
+    misc::{FloatingPoint, SpaceDeformation},
+};
+
+/// A free-form deformation by the trivariate B-spline lattice spanning a parallelepiped
+/// The lattice points are initially placed at the Greville abscissae, where the deformation is the identity.
+/// Moving the lattice points deforms the space inside the parallelepiped, and the geometries are deformed through their control points
+/// by the `Deformable` trait, while the points outside the parallelepiped are kept.
+/// # Example
+/// ```
+/// use curvo::prelude::*;
+/// use nalgebra::{Matrix3, Point3, Vector3};
+/// use approx::assert_relative_eq;
+///
+/// // the trilinear lattice of the unit cube
+/// let mut ffd = FreeFormDeformation::try_bezier(&Point3::origin(), &Matrix3::identity(), [1, 1, 1]).unwrap();
+/// let p = Point3::new(0.2, 0.4, 0.6);
+/// assert_relative_eq!(ffd.deform_point(&p), p, epsilon = 1e-10);
+///
+/// // lifting the top corners stretches the cube twice along the z axis
+/// for i in 0..2 {
+///     for j in 0..2 {
+///         ffd.lattice_point_mut(i, j, 1).z = 2.;
+///     }
+/// }
+/// assert_relative_eq!(ffd.deform_point(&p), Point3::new(0.2, 0.4, 1.2), epsilon = 1e-10);
+///
+/// let line = NurbsCurve3D::polyline(&[Point3::new(0.5, 0.5, 0.), Point3::new(0.5, 0.5, 1.)]);
+/// let deformed = line.deformed(&ffd);
+/// let (_, end) = deformed.knots_domain();
+/// assert_relative_eq!(deformed.point_at(end), Point3::new(0.5, 0.5, 2.), epsilon = 1e-10);
+///
+/// // the plane in the middle of the cube is lifted to the height of 1
+/// let edge = NurbsCurve3D::polyline(&[Point3::new(0., 0., 0.5), Point3::new(1., 0., 0.5)]);
+/// let plane = NurbsSurface3D::extrude(&edge, &Vector3::y()).deformed(&ffd);
+/// assert_relative_eq!(plane.point_at(0.3, 0.6).z, 1., epsilon = 1e-10);
+/// ```
+#[derive(Clone, Debug)]
+pub struct FreeFormDeformation<T: FloatingPoint> {
+    origin: Point3<T>,
+    /// The edges of the parallelepiped as the columns
+    axes: Matrix3<T>,
+    inverse: Matrix3<T>,
+    degrees: [usize; 3],
+    counts: [usize; 3],
+    knots: [KnotVector<T>; 3],
+    /// The lattice points ordered by the indices in the first, second & third directions
+    points: Vec<Point3<T>>,
+}
+
+impl<T: FloatingPoint> FreeFormDeformation<T> {
+    /// Try to create the lattice of the numbers of the points & the degrees in each direction
+    /// over the parallelepiped from the origin along the edges given as the columns of the axes
+    pub fn try_new(
+        origin: &Point3<T>,
+        axes: &Matrix3<T>,
+        degrees: [usize; 3],
+        counts: [usize; 3],
+    ) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            degrees
+                .iter()
+                .zip(counts.iter())
+                .all(|(d, c)| *d > 0 && c > d),
+            "The number of the lattice points must be greater than the degree in each direction"
+        );
+        let inverse = axes
+            .try_inverse()
+            .ok_or(anyhow::anyhow!("The axes must span the volume"))?;
+
+        let knots = [0, 1, 2].map(|i| KnotVector::uniform(counts[i] - degrees[i] + 1, degrees[i]));
+        let greville = [0, 1, 2].map(|i| {
+            let scale = T::from_usize(counts[i] - degrees[i]).unwrap();
+            let degree = T::from_usize(degrees[i]).unwrap();
+            (0..counts[i])
+                .map(|j| {
+                    let sum = (j + 1..=j + degrees[i]).fold(T::zero(), |acc, k| acc + knots[i][k]);
+                    sum / degree / scale
+                })
+                .collect::<Vec<_>>()
+        });
+        let points = greville[0]
+            .iter()
+            .flat_map(|s| {
+                greville[1].iter().flat_map(|t| {
+                    greville[2]
+                        .iter()
+                        .map(|u| origin + axes * Vector3::new(*s, *t, *u))
+                })
+            })
+            .collect();
+
+        Ok(Self {
+            origin: *origin,
+            axes: *axes,
+            inverse,
+            degrees,
+            counts,
+            knots,
+            points,
+        })
+    }
+
+    /// Try to create the Bezier lattice of the degrees, which has one more points than the degree in each direction
+    pub fn try_bezier(
+        origin: &Point3<T>,
+        axes: &Matrix3<T>,
+        degrees: [usize; 3],
+    ) -> anyhow::Result<Self> {
+        Self::try_new(origin, axes, degrees, degrees.map(|d| d + 1))
+    }
+
+    pub fn origin(&self) -> &Point3<T> {
+        &self.origin
+    }
+
+    pub fn axes(&self) -> &Matrix3<T> {
+        &self.axes
+    }
+
+    pub fn degrees(&self) -> [usize; 3] {
+        self.degrees
+    }
+
+    pub fn counts(&self) -> [usize; 3] {
+        self.counts
+    }
+
+    pub fn lattice_points(&self) -> &[Point3<T>] {
+        &self.points
+    }
+
+    fn index(&self, i: usize, j: usize, k: usize) -> usize {
+        (i * self.counts[1] + j) * self.counts[2] + k
+    }
+
+    pub fn lattice_point(&self, i: usize, j: usize, k: usize) -> &Point3<T> {
+        &self.points[self.index(i, j, k)]
+    }
+
+    pub fn lattice_point_mut(&mut self, i: usize, j: usize, k: usize) -> &mut Point3<T> {
+        let index = self.index(i, j, k);
+        &mut self.points[index]
+    }
+
+    /// Compute the coordinates of the point in the parallelepiped normalized to the unit cube
+    pub fn local_coordinates(&self, point: &Point3<T>) -> Vector3<T> {
+        self.inverse * (point - self.origin)
+    }
+}
+
+/// The points outside the parallelepiped are kept
+impl<T: FloatingPoint> SpaceDeformation<T> for FreeFormDeformation<T> {
+    fn deform_point(&self, point: &Point3<T>) -> Point3<T> {
+        let local = self.local_coordinates(point);
+        let eps = T::default_epsilon() * T::from_usize(16).unwrap();
+        if local.iter().any(|c| *c < -eps || *c > T::one() + eps) {
+            return *point;
+        }
+
+        let bases = [0, 1, 2].map(|i| {
+            let (degree, n) = (self.degrees[i], self.counts[i]);
+            let s = local[i].clamp(T::zero(), T::one()) * T::from_usize(n - degree).unwrap();
+            let span = self.knots[i].find_knot_span_index(n - 1, degree, s);
+            (
+                span - degree,
+                self.knots[i].basis_functions(span, s, degree),
+            )
+        });
+        let mut deformed = Vector3::zeros();
+        for (a, ba) in bases[0].1.iter().enumerate() {
+            for (b, bb) in bases[1].1.iter().enumerate() {
+                for (c, bc) in bases[2].1.iter().enumerate() {
+                    let p = self.lattice_point(bases[0].0 + a, bases[1].0 + b, bases[2].0 + c);
+                    deformed += p.coords * (*ba * *bb * *bc);
+                }
+            }
+        }
+        deformed.into()
+    }
+}
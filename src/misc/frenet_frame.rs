@@ -65,3 +65,66 @@ impl<T: FloatingPoint> FrenetFrame<T> {
         }
     }
 }
+
+/// A Frenet frame with the curvature & the torsion of the curve at the point.
+#[derive(Debug, Clone)]
+pub struct CurvatureFrame<T: FloatingPoint> {
+    frame: FrenetFrame<T>,
+    curvature: T,
+    torsion: T,
+    /// The first, second & third derivatives of the curve with respect to the parameter
+    derivatives: [Vector3<T>; 3],
+}
+
+impl<T: FloatingPoint> CurvatureFrame<T> {
+    pub fn new(frame: FrenetFrame<T>, curvature: T, torsion: T) -> Self {
+        Self {
+            frame,
+            curvature,
+            torsion,
+            derivatives: [Vector3::zeros(); 3],
+        }
+    }
+
+    /// Set the first, second & third derivatives of the curve with respect to the parameter
+    pub fn with_derivatives(mut self, derivatives: [Vector3<T>; 3]) -> Self {
+        self.derivatives = derivatives;
+        self
+    }
+
+    pub fn frame(&self) -> &FrenetFrame<T> {
+        &self.frame
+    }
+
+    pub fn curvature(&self) -> T {
+        self.curvature
+    }
+
+    pub fn torsion(&self) -> T {
+        self.torsion
+    }
+
+    pub fn derivatives(&self) -> &[Vector3<T>; 3] {
+        &self.derivatives
+    }
+
+    /// The speed of the curve, that is the derivative of the arc length with respect to the parameter
+    pub fn speed(&self) -> T {
+        self.derivatives[0].norm()
+    }
+
+    /// The derivative of the curvature with respect to the arc length
+    pub fn curvature_derivative(&self) -> T {
+        let [d1, d2, d3] = &self.derivatives;
+        let speed = d1.norm();
+        let b = d1.cross(d2);
+        let bn = b.norm();
+        if speed <= T::default_epsilon() || bn <= T::default_epsilon() {
+            return T::zero();
+        }
+        let s3 = speed * speed * speed;
+        let dt = b.dot(&d1.cross(d3)) / (bn * s3)
+            - T::from_f64(3.).unwrap() * bn * d1.dot(d2) / (s3 * speed * speed);
+        dt / speed
+    }
+}
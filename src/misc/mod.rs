@@ -1,15 +1,23 @@
 pub mod binomial;
+pub mod deformable;
+pub mod deformers;
 pub mod floating_point;
+pub mod free_form_deformation;
 pub mod frenet_frame;
 pub mod invertible;
+pub mod plane;
 pub mod ray;
 pub mod transformable;
 pub mod trigonometry;
 
 pub use binomial::*;
+pub use deformable::*;
+pub use deformers::*;
 pub use floating_point::*;
+pub use free_form_deformation::*;
 pub use frenet_frame::*;
 pub use invertible::*;
+pub use plane::*;
 pub use ray::*;
 pub use transformable::*;
 pub use trigonometry::*;
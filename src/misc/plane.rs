@@ -0,0 +1,52 @@
+use nalgebra::{Point3, Vector3};
+
+use crate::misc::FloatingPoint;
+
+/// Represents a plane in 3D space by a point on the plane and the unit normal.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Plane<T: FloatingPoint> {
+    origin: Point3<T>,
+    normal: Vector3<T>,
+}
+
+impl<T: FloatingPoint> Plane<T> {
+    /// Create a new plane passing through the origin point with the normal vector
+    /// The normal vector is normalized.
+    pub fn new(origin: Point3<T>, normal: Vector3<T>) -> Self {
+        Self {
+            origin,
+            normal: normal.normalize(),
+        }
+    }
+
+    pub fn origin(&self) -> &Point3<T> {
+        &self.origin
+    }
+
+    pub fn normal(&self) -> &Vector3<T> {
+        &self.normal
+    }
+
+    /// Compute the signed distance from the plane to the point
+    /// The distance is positive on the side of the normal.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    ///
+    /// let plane = Plane::new(Point3::new(0., 0., 1.), Vector3::z() * 2.);
+    /// assert_eq!(plane.signed_distance(&Point3::new(1., 2., 3.)), 2.);
+    /// assert_eq!(plane.signed_distance(&Point3::new(1., 2., 0.)), -1.);
+    /// ```
+    pub fn signed_distance(&self, point: &Point3<T>) -> T {
+        (point - self.origin).dot(&self.normal)
+    }
+
+    /// Offset the plane along the normal by the distance
+    pub fn offset(&self, distance: T) -> Self {
+        Self {
+            origin: self.origin + self.normal * distance,
+            normal: self.normal,
+        }
+    }
+}
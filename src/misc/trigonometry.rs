@@ -1,4 +1,4 @@
-use nalgebra::{allocator::Allocator, DefaultAllocator, DimName, OPoint, RealField};
+use nalgebra::{allocator::Allocator, DefaultAllocator, DimName, OPoint, Point2, RealField};
 
 pub fn three_points_are_flat<T: RealField + Copy, D: DimName>(
     p1: &OPoint<T, D>,
@@ -56,3 +56,28 @@ where
         return (u0 + (u1 - u0) * do2ptr / l, (r * do2ptr + o.coords).into());
     }
 }
+
+/// Check if a point is inside a closed polygon by the even-odd rule
+/// * `pt` - point to check
+/// * `polygon` - vertices of the polygon (the last vertex is connected to the first one)
+pub fn is_point_inside_polygon<T: RealField + Copy>(pt: &Point2<T>, polygon: &[Point2<T>]) -> bool {
+    let n = polygon.len();
+    if n < 3 {
+        return false;
+    }
+
+    let mut inside = false;
+    let mut j = n - 1;
+    for i in 0..n {
+        let pi = &polygon[i];
+        let pj = &polygon[j];
+        if (pi.y > pt.y) != (pj.y > pt.y) {
+            let x = (pj.x - pi.x) * (pt.y - pi.y) / (pj.y - pi.y) + pi.x;
+            if pt.x < x {
+                inside = !inside;
+            }
+        }
+        j = i;
+    }
+    inside
+}
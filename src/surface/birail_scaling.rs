@@ -0,0 +1,9 @@
+/// The scaling of the profile curve across a sweep along two rails
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BirailScaling {
+    /// Scale the profile uniformly to fit the distance between the rails
+    #[default]
+    Uniform,
+    /// Scale the profile only along the chord between the rails, maintaining the height of the profile
+    MaintainHeight,
+}
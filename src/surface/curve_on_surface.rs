@@ -0,0 +1,255 @@
+use gauss_quad::GaussLegendre;
+use nalgebra::{Point3, Vector3};
+
+use crate::{
+    curve::NurbsCurve2D,
+    misc::FloatingPoint,
+    surface::{NurbsSurface3D, SurfaceGeodesic, TrimmedSurface},
+};
+
+/// The maximum depth of the subdivision to integrate the length & to tessellate the curve
+const MAX_DEPTH: usize = 24;
+
+/// A curve lying on a surface, defined by the curve in the parameter space of the surface
+/// # Example
+/// ```
+/// use curvo::prelude::*;
+/// use nalgebra::{Point2, Point3, Vector2, Vector3};
+/// use approx::assert_relative_eq;
+///
+/// // the line in the parameter space of the cylinder winds once around it
+/// let cylinder = NurbsSurface3D::<f64>::try_cylinder(&Point3::origin(), &Vector3::z(), 1.).unwrap();
+/// let (u0, u1) = cylinder.u_knots_domain();
+/// let (v0, v1) = cylinder.v_knots_domain();
+/// let uv = NurbsCurve2D::polyline(&[Point2::new(u0, v0), Point2::new(u1, v1)]);
+/// let helix = CurveOnSurface::new(uv, cylinder.clone());
+/// let (start, end) = helix.knots_domain();
+/// assert_relative_eq!(helix.point_at(start), cylinder.point_at(u0, v0), epsilon = 1e-10);
+/// assert_relative_eq!(helix.point_at(end), cylinder.point_at(u1, v1), epsilon = 1e-10);
+///
+/// // the chain rule agrees with the finite difference
+/// let t = (start + end) * 0.3;
+/// let h = 1e-6;
+/// let d = helix.rational_derivatives(t, 2);
+/// assert_relative_eq!(d[0], helix.point_at(t).coords, epsilon = 1e-10);
+/// assert_relative_eq!(d[1], (helix.point_at(t + h) - helix.point_at(t - h)) / (2. * h), epsilon = 1e-6);
+/// assert_relative_eq!(d[2], (helix.rational_derivatives(t + h, 1)[1] - helix.rational_derivatives(t - h, 1)[1]) / (2. * h), epsilon = 1e-5);
+/// assert_relative_eq!(helix.normal_at(t).dot(&d[1]), 0., epsilon = 1e-10);
+///
+/// // close to the length of the helix, since the rational circle is not parameterized by the angle
+/// let tau = std::f64::consts::TAU;
+/// assert_relative_eq!(helix.try_length(1e-10).unwrap(), (tau * tau + 1.).sqrt(), epsilon = 1e-2);
+/// let points = helix.tessellate(Some(1e-3));
+/// assert!(points.len() > 16);
+/// for p in points.iter() {
+///     assert_relative_eq!(p.coords.xy().norm(), 1., epsilon = 1e-10);
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct CurveOnSurface<T: FloatingPoint> {
+    /// The curve in the parameter space of the surface
+    curve: NurbsCurve2D<T>,
+    /// The surface the curve lies on
+    surface: NurbsSurface3D<T>,
+}
+
+impl<T: FloatingPoint> CurveOnSurface<T> {
+    pub fn new(curve: NurbsCurve2D<T>, surface: NurbsSurface3D<T>) -> Self {
+        Self { curve, surface }
+    }
+
+    pub fn curve(&self) -> &NurbsCurve2D<T> {
+        &self.curve
+    }
+
+    pub fn surface(&self) -> &NurbsSurface3D<T> {
+        &self.surface
+    }
+
+    pub fn into_parts(self) -> (NurbsCurve2D<T>, NurbsSurface3D<T>) {
+        (self.curve, self.surface)
+    }
+
+    pub fn knots_domain(&self) -> (T, T) {
+        self.curve.knots_domain()
+    }
+
+    /// Evaluate the point on the surface at the parameter of the curve
+    pub fn point_at(&self, t: T) -> Point3<T> {
+        let uv = self.curve.point_at(t);
+        self.surface.point_at(uv.x, uv.y)
+    }
+
+    /// Evaluate the normal of the surface at the parameter of the curve
+    pub fn normal_at(&self, t: T) -> Vector3<T> {
+        let uv = self.curve.point_at(t);
+        self.surface.normal_at(uv.x, uv.y)
+    }
+
+    /// Evaluate the point & the derivatives by the chain rule through the surface
+    /// The derivatives up to the second order are supported, so at most three vectors are returned.
+    pub fn rational_derivatives(&self, t: T, derivs: usize) -> Vec<Vector3<T>> {
+        let n = derivs.min(2);
+        let c = self.curve.rational_derivatives(t, n);
+        let s = self.surface.rational_derivatives(c[0].x, c[0].y, n);
+        let mut derivatives = vec![s[0][0]];
+        if n >= 1 {
+            derivatives.push(s[1][0] * c[1].x + s[0][1] * c[1].y);
+        }
+        if n >= 2 {
+            let (du, dv) = (c[1].x, c[1].y);
+            let two = T::from_f64(2.).unwrap();
+            derivatives.push(
+                s[2][0] * (du * du)
+                    + s[1][1] * (two * du * dv)
+                    + s[0][2] * (dv * dv)
+                    + s[1][0] * c[2].x
+                    + s[0][1] * c[2].y,
+            );
+        }
+        derivatives
+    }
+
+    /// Evaluate the tangent vector of the curve on the surface
+    pub fn tangent_at(&self, t: T) -> Vector3<T> {
+        self.rational_derivatives(t, 1)[1]
+    }
+
+    /// The distinct knots of the curve in the parameter space, where the smoothness may break
+    fn breaks(&self) -> Vec<T> {
+        let (start, end) = self.knots_domain();
+        let mut breaks = self
+            .curve
+            .knots()
+            .iter()
+            .filter(|k| **k > start && **k < end)
+            .copied()
+            .collect::<Vec<_>>();
+        breaks.dedup();
+        breaks.insert(0, start);
+        breaks.push(end);
+        breaks
+    }
+
+    /// Compute the length of the curve on the surface within the tolerance by the adaptive gauss-legendre quadrature
+    pub fn try_length(&self, tolerance: T) -> anyhow::Result<T> {
+        anyhow::ensure!(
+            tolerance > T::zero(),
+            "The tolerance must be greater than zero"
+        );
+        let gauss = GaussLegendre::new(8)?;
+        let integrate = |a: T, b: T| {
+            let sum = gauss.integrate(a.to_f64().unwrap(), b.to_f64().unwrap(), |x| {
+                self.tangent_at(T::from_f64(x).unwrap())
+                    .norm()
+                    .to_f64()
+                    .unwrap()
+            });
+            T::from_f64(sum).unwrap()
+        };
+        let (start, end) = self.knots_domain();
+        let domain = end - start;
+        let half = T::from_f64(0.5).unwrap();
+
+        let mut length = T::zero();
+        let breaks = self.breaks();
+        for w in breaks.windows(2) {
+            let tolerance = tolerance * (w[1] - w[0]) / domain;
+            let mut stack = vec![(w[0], w[1], integrate(w[0], w[1]), tolerance, 0)];
+            while let Some((a, b, whole, tolerance, depth)) = stack.pop() {
+                let mid = (a + b) * half;
+                let (left, right) = (integrate(a, mid), integrate(mid, b));
+                if (left + right - whole).abs() <= tolerance || depth >= MAX_DEPTH {
+                    length += left + right;
+                } else {
+                    stack.push((a, mid, left, tolerance * half, depth + 1));
+                    stack.push((mid, b, right, tolerance * half, depth + 1));
+                }
+            }
+        }
+        Ok(length)
+    }
+
+    /// Tessellate the curve on the surface into a polyline
+    /// Each segment is subdivided until the middle of the curve over it is within the tolerance from the segment.
+    pub fn tessellate(&self, tolerance: Option<T>) -> Vec<Point3<T>> {
+        let tolerance = tolerance.unwrap_or(T::from_f64(1e-3).unwrap());
+        let half = T::from_f64(0.5).unwrap();
+        let quarters = T::from_usize(4).unwrap();
+
+        // each span is divided into quarters beforehand not to miss the wiggles symmetric about the middle
+        let mut intervals = self
+            .breaks()
+            .windows(2)
+            .flat_map(|w| {
+                let step = (w[1] - w[0]) / quarters;
+                (0..4).map(move |i| {
+                    let a = w[0] + step * T::from_usize(i).unwrap();
+                    (a, a + step)
+                })
+            })
+            .collect::<Vec<_>>();
+        intervals.reverse();
+
+        let (start, _) = self.knots_domain();
+        let mut points = vec![self.point_at(start)];
+        let mut stack = intervals
+            .into_iter()
+            .map(|(a, b)| (a, b, 0))
+            .collect::<Vec<_>>();
+        while let Some((a, b, depth)) = stack.pop() {
+            let (p0, p1) = (self.point_at(a), self.point_at(b));
+            let mid = (a + b) * half;
+            let pm = self.point_at(mid);
+            let chord = p1 - p0;
+            let offset = pm - p0;
+            let length = chord.norm();
+            let deviation = if length > T::default_epsilon() {
+                (offset - chord * (offset.dot(&chord) / (length * length))).norm()
+            } else {
+                offset.norm()
+            };
+            if deviation > tolerance && depth < MAX_DEPTH {
+                stack.push((mid, b, depth + 1));
+                stack.push((a, mid, depth + 1));
+            } else {
+                points.push(p1);
+            }
+        }
+        points
+    }
+}
+
+impl<T: FloatingPoint> SurfaceGeodesic<T> {
+    /// Represent the geodesic as the curve on the surface it was computed on
+    pub fn to_curve_on_surface(&self, surface: &NurbsSurface3D<T>) -> CurveOnSurface<T> {
+        CurveOnSurface::new(self.uv_curve().clone(), surface.clone())
+    }
+}
+
+impl<T: FloatingPoint> TrimmedSurface<T> {
+    /// Represent the spans of the trim loops as the curves on the surface, the exterior first followed by the interiors
+    /// The boundary of the parameter domain is not included when the exterior is not given.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point2, Point3, Vector2, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let line = NurbsCurve3D::polyline(&[Point3::new(0., 0., 0.), Point3::new(1., 0., 0.)]);
+    /// let plane = NurbsSurface3D::extrude(&line, &Vector3::y());
+    /// let hole = NurbsCurve2D::try_circle(&Point2::new(0.5, 0.5), &Vector2::x(), &Vector2::y(), 0.25).unwrap();
+    /// let trimmed = TrimmedSurface::try_new(plane, None, vec![hole.into()]).unwrap();
+    /// let curves = trimmed.trim_curves_on_surface();
+    /// assert_eq!(curves.len(), 1);
+    /// assert_relative_eq!(curves[0].try_length(1e-10).unwrap(), std::f64::consts::FRAC_PI_2, epsilon = 1e-8);
+    /// ```
+    pub fn trim_curves_on_surface(&self) -> Vec<CurveOnSurface<T>> {
+        self.exterior()
+            .into_iter()
+            .chain(self.interiors().iter())
+            .flat_map(|l| l.spans().iter())
+            .map(|span| CurveOnSurface::new(span.clone(), self.surface().clone()))
+            .collect()
+    }
+}
@@ -0,0 +1,55 @@
+use crate::{curve::NurbsCurve3D, misc::FloatingPoint};
+
+/// Options for lofting section curves into a surface
+#[derive(Clone, Debug)]
+pub struct LoftOptions<T: FloatingPoint> {
+    /// The degree of the surface across the sections
+    pub degree_v: Option<usize>,
+    /// Whether the surface is closed across the sections, connecting the last section to the first one
+    pub closed: bool,
+    /// Whether the sections are reversed & the seams of the closed sections are moved to match the previous section
+    pub align_sections: bool,
+    /// The guide curves which the surface follows between the sections
+    pub guides: Vec<NurbsCurve3D<T>>,
+    /// The number of the intermediate sections generated along the guides between each pair of the sections
+    pub guide_divisions: usize,
+}
+
+impl<T: FloatingPoint> Default for LoftOptions<T> {
+    fn default() -> Self {
+        Self {
+            degree_v: None,
+            closed: false,
+            align_sections: false,
+            guides: vec![],
+            guide_divisions: 4,
+        }
+    }
+}
+
+impl<T: FloatingPoint> LoftOptions<T> {
+    pub fn with_degree_v(mut self, degree_v: usize) -> Self {
+        self.degree_v = Some(degree_v);
+        self
+    }
+
+    pub fn with_closed(mut self, closed: bool) -> Self {
+        self.closed = closed;
+        self
+    }
+
+    pub fn with_align_sections(mut self, align_sections: bool) -> Self {
+        self.align_sections = align_sections;
+        self
+    }
+
+    pub fn with_guides(mut self, guides: Vec<NurbsCurve3D<T>>) -> Self {
+        self.guides = guides;
+        self
+    }
+
+    pub fn with_guide_divisions(mut self, guide_divisions: usize) -> Self {
+        self.guide_divisions = guide_divisions;
+        self
+    }
+}
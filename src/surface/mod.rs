@@ -1,2 +1,43 @@
+pub mod birail_scaling;
+pub mod curve_on_surface;
+pub mod loft_options;
 pub mod nurbs_surface;
+pub mod surface_area;
+pub mod surface_contour;
+pub mod surface_curvature;
+pub mod surface_developability;
+pub mod surface_deviation;
+pub mod surface_fairing;
+pub mod surface_fairing_options;
+pub mod surface_fillet;
+pub mod surface_fit_options;
+pub mod surface_geodesic;
+pub(crate) mod surface_level_set;
+pub mod surface_projection;
+pub mod surface_silhouette;
+pub mod surface_trim;
+pub mod surface_unroll;
+pub mod surface_unroll_options;
+pub mod sweep_frame;
+pub mod sweep_options;
+pub mod trimmed_surface;
+pub use birail_scaling::*;
+pub use curve_on_surface::*;
+pub use loft_options::*;
 pub use nurbs_surface::*;
+pub use surface_area::*;
+pub use surface_contour::*;
+pub use surface_curvature::*;
+pub use surface_developability::*;
+pub use surface_deviation::*;
+pub use surface_fairing_options::*;
+pub use surface_fillet::*;
+pub use surface_fit_options::*;
+pub use surface_geodesic::*;
+pub use surface_silhouette::*;
+pub use surface_trim::*;
+pub use surface_unroll::*;
+pub use surface_unroll_options::*;
+pub use sweep_frame::*;
+pub use sweep_options::*;
+pub use trimmed_surface::*;
@@ -1,25 +1,44 @@
 use std::borrow::Cow;
 
 use nalgebra::{
-    allocator::Allocator, Const, DVector, DefaultAllocator, DimName, DimNameAdd, DimNameDiff,
-    DimNameSub, DimNameSum, OMatrix, OPoint, OVector, Point3, Point4, RealField, Vector2, Vector3,
-    U1,
+    allocator::Allocator, ComplexField, Const, DMatrix, DVector, DefaultAllocator, DimName,
+    DimNameAdd, DimNameDiff, DimNameSub, DimNameSum, Isometry3, Matrix2, Matrix3, Matrix4, OMatrix,
+    OPoint, OVector, Point2, Point3, Point4, RealField, UnitQuaternion, UnitVector3, Vector2,
+    Vector3, U1,
 };
 use simba::scalar::SupersetOf;
 
+use argmin::core::{ArgminFloat, Executor, State};
+
 use crate::{
+    bounding_box::{BoundingBox, BoundingBoxTraversal, SurfaceBoundingBoxTree, SurfaceBvh},
     curve::{
-        nurbs_curve::{dehomogenize, NurbsCurve, NurbsCurve3D},
-        try_interpolate_control_points,
+        nurbs_curve::{dehomogenize, initial_normal, NurbsCurve, NurbsCurve2D, NurbsCurve3D},
+        try_interpolate_control_points, try_periodic_interpolate_control_points, CompoundCurve2D,
+        KnotStyle,
+    },
+    intersection::{
+        surface_intersection_marcher::{SurfaceIntersectionMarcher, SurfaceIntersectionPolyline},
+        CurveSurfaceIntersectionSolverOptions, RaySurfaceIntersection, SurfaceIntersection,
+        SurfaceIntersectionSolverOptions,
+    },
+    misc::{
+        binomial::Binomial, is_point_inside_polygon, transformable::Transformable, Deformable,
+        FloatingPoint, Invertible, Plane, Ray, SpaceDeformation,
     },
-    misc::{binomial::Binomial, transformable::Transformable, FloatingPoint, Ray},
     prelude::{KnotVector, SurfaceTessellation},
+    surface::{
+        surface_level_set::{level_set_curves, level_set_curves_on_surface, SurfaceLevelSetTracer},
+        BirailScaling, LoftOptions, SurfaceContour, SurfaceDeviation, SurfaceFitOptions,
+        SurfaceSilhouette, SweepFrame, SweepOptions, TrimCurve, TrimSide, TrimmedSurface,
+    },
     tessellation::{
         adaptive_tessellation_node::AdaptiveTessellationNode,
         adaptive_tessellation_option::AdaptiveTessellationOptions,
         adaptive_tessellation_processor::AdaptiveTessellationProcessor,
         surface_point::SurfacePoint,
     },
+    ClosestParameterOptions, SurfaceClosestParameterNewton, SurfaceClosestParameterProblem,
 };
 
 /// NURBS surface representation
@@ -480,6 +499,160 @@ where
         v0.cross(v1)
     }
 
+    /// Check if the surface is closed in the given direction
+    /// if `v_direction` is true, check the closedness in the v direction, otherwise in the u direction
+    pub fn is_closed(&self, v_direction: bool) -> bool {
+        let eps = T::default_epsilon();
+        if v_direction {
+            self.control_points
+                .iter()
+                .all(|row| (&row[0].coords - &row[row.len() - 1].coords).norm() < eps)
+        } else {
+            let first = &self.control_points[0];
+            let last = &self.control_points[self.control_points.len() - 1];
+            first
+                .iter()
+                .zip(last.iter())
+                .all(|(a, b)| (&a.coords - &b.coords).norm() < eps)
+        }
+    }
+
+    /// Find the closest point on the surface to a given point
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let circle = NurbsCurve3D::try_circle(&Point3::origin(), &Vector3::x(), &Vector3::y(), 1.).unwrap();
+    /// let cylinder = NurbsSurface::extrude(&circle, &(Vector3::z() * 2.));
+    ///
+    /// let closest = cylinder.find_closest_point(&Point3::new(2., 2., 1.5)).unwrap();
+    /// let expected = Point3::new(std::f64::consts::FRAC_1_SQRT_2, std::f64::consts::FRAC_1_SQRT_2, 1.5);
+    /// assert_relative_eq!(closest, expected, epsilon = 1e-6);
+    /// ```
+    pub fn find_closest_point(
+        &self,
+        point: &OPoint<T, DimNameDiff<D, U1>>,
+    ) -> anyhow::Result<OPoint<T, DimNameDiff<D, U1>>>
+    where
+        T: ArgminFloat,
+    {
+        self.find_closest_parameter(point)
+            .map(|(u, v)| self.point_at(u, v))
+    }
+
+    /// Find the closest point on the surface to a given point with the options for the solver
+    pub fn find_closest_point_with_options(
+        &self,
+        point: &OPoint<T, DimNameDiff<D, U1>>,
+        options: ClosestParameterOptions<T>,
+    ) -> anyhow::Result<OPoint<T, DimNameDiff<D, U1>>>
+    where
+        T: ArgminFloat,
+    {
+        self.find_closest_parameter_with_options(point, options)
+            .map(|(u, v)| self.point_at(u, v))
+    }
+
+    /// Find the closest parameter on the surface to a given point with Newton's method
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let line = NurbsCurve3D::polyline(&[Point3::new(0., 0., 0.), Point3::new(2., 0., 0.)]);
+    /// let plane = NurbsSurface::extrude(&line, &(Vector3::y() * 2.));
+    ///
+    /// let (u, v) = plane.find_closest_parameter(&Point3::new(0.5, 1.5, 1.)).unwrap();
+    /// assert_relative_eq!(plane.point_at(u, v), Point3::new(0.5, 1.5, 0.), epsilon = 1e-8);
+    ///
+    /// // The parameter is clamped to the domain
+    /// let (u, v) = plane.find_closest_parameter(&Point3::new(-1., 1., 1.)).unwrap();
+    /// assert_relative_eq!(plane.point_at(u, v), Point3::new(0., 1., 0.), epsilon = 1e-8);
+    /// ```
+    pub fn find_closest_parameter(
+        &self,
+        point: &OPoint<T, DimNameDiff<D, U1>>,
+    ) -> anyhow::Result<(T, T)>
+    where
+        T: ArgminFloat,
+    {
+        self.find_closest_parameter_with_options(point, Default::default())
+    }
+
+    /// Find the closest parameter on the surface to a given point with Newton's method
+    /// * `point` - The point to find the closest parameter to
+    /// * `options` - Tolerances & maximum number of iterations for the solver
+    pub fn find_closest_parameter_with_options(
+        &self,
+        point: &OPoint<T, DimNameDiff<D, U1>>,
+        options: ClosestParameterOptions<T>,
+    ) -> anyhow::Result<(T, T)>
+    where
+        T: ArgminFloat,
+    {
+        let (u0, u1) = self.u_knots_domain();
+        let (v0, v1) = self.v_knots_domain();
+        let divs_u = self.control_points.len() * self.u_degree;
+        let divs_v = self.control_points[0].len() * self.v_degree;
+        let du = (u1 - u0) / T::from_usize(divs_u).unwrap();
+        let dv = (v1 - v0) / T::from_usize(divs_v).unwrap();
+
+        let distances: Vec<Vec<_>> = (0..=divs_u)
+            .map(|i| {
+                let u = u0 + du * T::from_usize(i).unwrap();
+                (0..=divs_v)
+                    .map(|j| {
+                        let v = v0 + dv * T::from_usize(j).unwrap();
+                        (Vector2::new(u, v), (self.point_at(u, v) - point).norm())
+                    })
+                    .collect()
+            })
+            .collect();
+
+        // take the local minima of the samples as the initial guesses
+        let mut minima = vec![];
+        for i in 0..=divs_u {
+            for j in 0..=divs_v {
+                let d = distances[i][j].1;
+                let is_minimum = (i.saturating_sub(1)..=(i + 1).min(divs_u)).all(|k| {
+                    (j.saturating_sub(1)..=(j + 1).min(divs_v)).all(|l| d <= distances[k][l].1)
+                });
+                if is_minimum {
+                    minima.push(distances[i][j]);
+                }
+            }
+        }
+        minima.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        // run the newton method from each seed and take the global minimum
+        let mut closest: Option<(Vector2<T>, T)> = None;
+        for (uv, _) in minima.into_iter().take(options.seeds.max(1)) {
+            let solver = SurfaceClosestParameterNewton::new(
+                ((u0, u1), (v0, v1)),
+                (self.is_closed(false), self.is_closed(true)),
+            )
+            .with_point_tolerance(options.point_tolerance)
+            .with_cosine_tolerance(options.cosine_tolerance)
+            .with_parameter_tolerance(options.parameter_tolerance);
+            let res = Executor::new(SurfaceClosestParameterProblem::new(point, self), solver)
+                .configure(|state| state.param(uv).max_iters(options.max_iters))
+                .run()?;
+            if let Some(p) = res.state().get_best_param().cloned() {
+                let d = (self.point_at(p.x, p.y) - point).norm();
+                if closest.map(|(_, min)| d < min).unwrap_or(true) {
+                    closest = Some((p, d));
+                }
+            }
+        }
+
+        closest
+            .map(|(p, _)| (p.x, p.y))
+            .ok_or(anyhow::anyhow!("No best parameter found"))
+    }
+
     /// Evaluate the rational derivatives at the given u, v parameters
     pub fn rational_derivatives(
         &self,
@@ -630,6 +803,460 @@ where
         D: DimNameAdd<U1>,
         DefaultAllocator: Allocator<DimNameSum<D, U1>>,
     {
+        Self::try_loft_sections(curves, degree_v, false)
+    }
+
+    /// Try to create a ruled surface between two curves
+    /// The curves are elevated to the same degree & their knot vectors are merged, and the surface is linear in the v direction.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let a = NurbsCurve3D::polyline(&[Point3::new(0., 0., 0.), Point3::new(2., 0., 0.)]);
+    /// let b = NurbsCurve3D::try_interpolate(&[Point3::new(0., 1., 1.), Point3::new(1., 1., 2.), Point3::new(2., 1., 1.)], 2).unwrap();
+    /// let ruled = NurbsSurface::try_ruled(&a, &b).unwrap();
+    /// assert_eq!(ruled.v_degree(), 1);
+    ///
+    /// let (u0, u1) = ruled.u_knots_domain();
+    /// let (v0, v1) = ruled.v_knots_domain();
+    /// for i in 0..=8 {
+    ///     let t = i as f64 / 8.;
+    ///     let u = u0 + (u1 - u0) * t;
+    ///     let pa = a.point_at(t);
+    ///     let pb = b.point_at(t);
+    ///     assert_relative_eq!(ruled.point_at(u, v0), pa, epsilon = 1e-8);
+    ///     assert_relative_eq!(ruled.point_at(u, v1), pb, epsilon = 1e-8);
+    ///     // the rulings are straight lines
+    ///     assert_relative_eq!(ruled.point_at(u, (v0 + v1) / 2.), pa.lerp(&pb, 0.5), epsilon = 1e-8);
+    /// }
+    /// ```
+    pub fn try_ruled(a: &NurbsCurve<T, D>, b: &NurbsCurve<T, D>) -> anyhow::Result<Self> {
+        let unified = try_unify_curve_knot_vectors(&[a.clone(), b.clone()])?;
+        let control_points = unified[0]
+            .control_points()
+            .iter()
+            .zip(unified[1].control_points().iter())
+            .map(|(a, b)| vec![a.clone(), b.clone()])
+            .collect();
+
+        Ok(Self {
+            control_points,
+            u_degree: unified[0].degree(),
+            v_degree: 1,
+            u_knots: unified[0].knots().clone(),
+            v_knots: KnotVector::new(vec![T::zero(), T::zero(), T::one(), T::one()]),
+        })
+    }
+
+    /// Try to interpolate a grid of points with the global surface interpolation (The NURBS Book A9.4)
+    /// `points[i][j]` is the point at the i-th row in the u direction & the j-th column in the v direction.
+    /// The parameters are the chord length parameters averaged over the rows & columns, & the knots average the parameters.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::Point3;
+    /// use approx::assert_relative_eq;
+    ///
+    /// let points = (0..5)
+    ///     .map(|i| {
+    ///         (0..4)
+    ///             .map(|j| {
+    ///                 let (x, y) = (i as f64, j as f64 * 1.5);
+    ///                 Point3::new(x, y, (x * 0.7).sin() * (y * 0.5).cos())
+    ///             })
+    ///             .collect::<Vec<_>>()
+    ///     })
+    ///     .collect::<Vec<_>>();
+    /// let surface = NurbsSurface3D::try_interpolate_grid(&points, 3, 2).unwrap();
+    /// assert_eq!(surface.u_degree(), 3);
+    /// assert_eq!(surface.v_degree(), 2);
+    ///
+    /// // the corners are interpolated
+    /// let (u0, u1) = surface.u_knots_domain();
+    /// let (v0, v1) = surface.v_knots_domain();
+    /// assert_relative_eq!(surface.point_at(u0, v0), points[0][0], epsilon = 1e-10);
+    /// assert_relative_eq!(surface.point_at(u1, v1), points[4][3], epsilon = 1e-10);
+    ///
+    /// // the inner points lie on the surface
+    /// let closest = surface.find_closest_point(&points[2][1]).unwrap();
+    /// assert_relative_eq!(closest, points[2][1], epsilon = 1e-6);
+    /// ```
+    pub fn try_interpolate_grid(
+        points: &[Vec<OPoint<T, DimNameDiff<D, U1>>>],
+        u_degree: usize,
+        v_degree: usize,
+    ) -> anyhow::Result<Self> {
+        let rows = points.len();
+        anyhow::ensure!(rows > u_degree, "Too few rows of points to interpolate");
+        let columns = points[0].len();
+        anyhow::ensure!(
+            points.iter().all(|row| row.len() == columns),
+            "The rows of points must have the same number of points"
+        );
+        anyhow::ensure!(
+            columns > v_degree,
+            "Too few columns of points to interpolate"
+        );
+        anyhow::ensure!(
+            u_degree > 0 && v_degree > 0,
+            "The degrees must be greater than zero"
+        );
+
+        let u_sequences = (0..columns)
+            .map(|j| points.iter().map(|row| &row[j]).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let v_sequences = points
+            .iter()
+            .map(|row| row.iter().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let u_params = averaged_chordal_parameters(&u_sequences)?;
+        let v_params = averaged_chordal_parameters(&v_sequences)?;
+        let u_knots = averaged_knots(&u_params, u_degree);
+        let v_knots = averaged_knots(&v_params, v_degree);
+
+        let u_lu = basis_matrix(&u_knots, &u_params, u_degree).lu();
+        let v_lu = basis_matrix(&v_knots, &v_params, v_degree).lu();
+
+        // the control points are the weighted ones with the unit weights
+        let dim = D::dim();
+        let mut control_points = vec![vec![OPoint::<T, D>::origin(); columns]; rows];
+        control_points
+            .iter_mut()
+            .flatten()
+            .for_each(|p| p[dim - 1] = T::one());
+        for d in 0..(dim - 1) {
+            let q = DMatrix::from_fn(rows, columns, |i, j| points[i][j][d]);
+            // solve the u direction for each column, then the v direction for each row
+            let r = u_lu
+                .solve(&q)
+                .ok_or(anyhow::anyhow!("Solve failed in the u direction"))?;
+            let p = v_lu
+                .solve(&r.transpose())
+                .ok_or(anyhow::anyhow!("Solve failed in the v direction"))?;
+            for (i, row) in control_points.iter_mut().enumerate() {
+                for (j, cp) in row.iter_mut().enumerate() {
+                    cp[d] = p[(j, i)];
+                }
+            }
+        }
+
+        Ok(Self {
+            control_points,
+            u_degree,
+            v_degree,
+            u_knots,
+            v_knots,
+        })
+    }
+
+    /// Try to elevate the degrees of the surface by the given times in the u & v directions
+    /// The control points along each direction are elevated as curves, so the shape & the parameterization are kept.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let sphere = NurbsSurface3D::try_sphere(&Point3::origin(), &Vector3::z(), 1.).unwrap();
+    /// let elevated = sphere.try_elevate_degree(1, 2).unwrap();
+    /// assert_eq!(elevated.u_degree(), sphere.u_degree() + 1);
+    /// assert_eq!(elevated.v_degree(), sphere.v_degree() + 2);
+    /// for i in 0..=8 {
+    ///     for j in 0..=8 {
+    ///         let (u, v) = (i as f64 / 8. * std::f64::consts::TAU, j as f64 / 8. * std::f64::consts::PI);
+    ///         assert_relative_eq!(elevated.point_at(u, v), sphere.point_at(u, v), epsilon = 1e-8);
+    ///     }
+    /// }
+    /// ```
+    pub fn try_elevate_degree(&self, u_times: usize, v_times: usize) -> anyhow::Result<Self> {
+        let mut surface = self.clone();
+        if u_times > 0 {
+            let curves = surface
+                .try_direction_curves(false)?
+                .iter()
+                .map(|c| c.try_elevate_degree(surface.u_degree + u_times))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            surface = surface.with_direction_curves(&curves, false);
+        }
+        if v_times > 0 {
+            let curves = surface
+                .try_direction_curves(true)?
+                .iter()
+                .map(|c| c.try_elevate_degree(surface.v_degree + v_times))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            surface = surface.with_direction_curves(&curves, true);
+        }
+        Ok(surface)
+    }
+
+    /// Try to reduce the degree of the surface by one in the direction, refusing if the deviation exceeds the tolerance
+    /// The curves of the control points along the direction are reduced in the homogeneous space as [`NurbsCurve::try_reduce_degree`].
+    /// Returns the reduced surface & the maximum deviation between the points of both surfaces at the same parameters.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::Point3;
+    ///
+    /// let points = (0..4)
+    ///     .map(|i| (0..3).map(|j| Point3::new(i as f64, j as f64, ((i + j) % 2) as f64)).collect::<Vec<_>>())
+    ///     .collect::<Vec<_>>();
+    /// let surface = NurbsSurface3D::try_interpolate_grid(&points, 2, 2).unwrap();
+    /// let elevated = surface.try_elevate_degree(1, 0).unwrap();
+    /// let (reduced, deviation) = elevated.try_reduce_degree(false, 1e-8).unwrap();
+    /// assert_eq!(reduced.u_degree(), 2);
+    /// assert!(deviation < 1e-8);
+    /// assert!(surface.try_reduce_degree(true, 1e-3).is_err());
+    /// ```
+    pub fn try_reduce_degree(&self, v_direction: bool, tolerance: T) -> anyhow::Result<(Self, T)> {
+        let curves = self
+            .try_direction_curves(v_direction)?
+            .iter()
+            .map(|c| c.try_reduce_degree_homogeneous())
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let reduced = self.with_direction_curves(&curves, v_direction);
+
+        let deviation = self.deviation(&reduced).max();
+        anyhow::ensure!(
+            deviation <= tolerance,
+            "The deviation {} of the degree reduction exceeds the tolerance {}",
+            deviation,
+            tolerance
+        );
+        Ok((reduced, deviation))
+    }
+
+    /// Try to rebuild the surface with the degrees & the numbers of the control points on the uniform knot vectors
+    /// The non-rational surface is fitted to the grid of the points sampled uniformly in the domain in the least squares sense, and the domain is kept.
+    /// Returns the rebuilt surface & the statistics of the deviation between the points of both surfaces at the same parameters.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    ///
+    /// let sphere = NurbsSurface3D::try_sphere(&Point3::origin(), &Vector3::z(), 1.).unwrap();
+    /// let (rebuilt, deviation) = sphere.try_rebuild((3, 3), (16, 12)).unwrap();
+    /// assert_eq!(rebuilt.control_points().len(), 16);
+    /// assert_eq!(rebuilt.control_points()[0].len(), 12);
+    /// assert_eq!(rebuilt.u_knots_domain(), sphere.u_knots_domain());
+    /// assert!(deviation.max() < 1e-2);
+    /// assert!(deviation.average() <= deviation.max());
+    /// ```
+    pub fn try_rebuild(
+        &self,
+        degrees: (usize, usize),
+        control_points_count: (usize, usize),
+    ) -> anyhow::Result<(Self, SurfaceDeviation<T>)> {
+        let rebuilt =
+            self.try_fit_uniform(degrees, control_points_count, |u, v| self.point_at(u, v))?;
+        let deviation = self.deviation(&rebuilt);
+        Ok((rebuilt, deviation))
+    }
+
+    /// Fit the non-rational surface with the uniform knots over the domain of the surface to the points of the function of the parameters in the least squares sense
+    /// The function is sampled on the grid dense enough for each span of both the surface & the fitted one.
+    fn try_fit_uniform<F>(
+        &self,
+        degrees: (usize, usize),
+        control_points_count: (usize, usize),
+        f: F,
+    ) -> anyhow::Result<Self>
+    where
+        F: Fn(T, T) -> OPoint<T, DimNameDiff<D, U1>>,
+    {
+        let (pu, pv) = degrees;
+        let (nu, nv) = control_points_count;
+        anyhow::ensure!(
+            pu >= 1 && pv >= 1 && nu > pu && nv > pv,
+            "The number of control points must be greater than the degree"
+        );
+
+        let uniform = |(start, end): (T, T), degree: usize, n: usize| {
+            let spans = n - degree;
+            KnotVector::new(
+                [
+                    vec![start; degree + 1],
+                    (1..spans)
+                        .map(|i| {
+                            start
+                                + (end - start) * T::from_usize(i).unwrap()
+                                    / T::from_usize(spans).unwrap()
+                        })
+                        .collect(),
+                    vec![end; degree + 1],
+                ]
+                .concat(),
+            )
+        };
+        let u_knots = uniform(self.u_knots_domain(), pu, nu);
+        let v_knots = uniform(self.v_knots_domain(), pv, nv);
+
+        // the samples dense enough for each span of both surfaces
+        let samples = |(start, end): (T, T), knots: &KnotVector<T>, degree: usize, n: usize| {
+            let count = (n - degree).max(knots.multiplicity().len() - 1) * (2 * degree + 2);
+            (0..=count)
+                .map(|i| {
+                    start
+                        + (end - start) * T::from_usize(i).unwrap() / T::from_usize(count).unwrap()
+                })
+                .collect::<Vec<_>>()
+        };
+        let us = samples(self.u_knots_domain(), &self.u_knots, pu, nu);
+        let vs = samples(self.v_knots_domain(), &self.v_knots, pv, nv);
+
+        let basis = |knots: &KnotVector<T>, parameters: &[T], degree: usize, n: usize| {
+            let mut basis = DMatrix::<T>::zeros(parameters.len(), n);
+            for (k, u) in parameters.iter().enumerate() {
+                let span = knots.find_knot_span_index(n - 1, degree, *u);
+                for (i, b) in knots
+                    .basis_functions(span, *u, degree)
+                    .into_iter()
+                    .enumerate()
+                {
+                    basis[(k, span - degree + i)] = b;
+                }
+            }
+            basis
+        };
+        let bu = basis(&u_knots, &us, pu, nu);
+        let bv = basis(&v_knots, &vs, pv, nv);
+        let lu_u = (bu.transpose() * &bu).lu();
+        let lu_v = (bv.transpose() * &bv).lu();
+
+        // the least squares of the tensor product grid is separable in the u & v directions
+        let points = us
+            .iter()
+            .map(|u| vs.iter().map(|v| f(*u, *v)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let dim = D::dim();
+        let mut control_points = vec![vec![OPoint::<T, D>::origin(); nv]; nu];
+        control_points
+            .iter_mut()
+            .flatten()
+            .for_each(|p| p[dim - 1] = T::one());
+        for d in 0..(dim - 1) {
+            let q = DMatrix::from_fn(us.len(), vs.len(), |i, j| points[i][j][d]);
+            let r = lu_u
+                .solve(&(bu.transpose() * q))
+                .ok_or(anyhow::anyhow!("Solve failed in the u direction"))?;
+            let p = lu_v
+                .solve(&(bv.transpose() * r.transpose()))
+                .ok_or(anyhow::anyhow!("Solve failed in the v direction"))?;
+            for (i, row) in control_points.iter_mut().enumerate() {
+                for (j, cp) in row.iter_mut().enumerate() {
+                    cp[d] = p[(j, i)];
+                }
+            }
+        }
+
+        Ok(Self {
+            control_points,
+            u_degree: pu,
+            v_degree: pv,
+            u_knots,
+            v_knots,
+        })
+    }
+
+    /// The statistics of the distances between the points of the surfaces at the same parameters sampled in each span of both surfaces
+    fn deviation(&self, other: &Self) -> SurfaceDeviation<T> {
+        self.deviation_from(other, |u, v| self.point_at(u, v))
+    }
+
+    /// The statistics of the distances between the points of the function & the other surface at the same parameters sampled in each span of both surfaces
+    fn deviation_from<F>(&self, other: &Self, f: F) -> SurfaceDeviation<T>
+    where
+        F: Fn(T, T) -> OPoint<T, DimNameDiff<D, U1>>,
+    {
+        let samples = |a: &KnotVector<T>, b: &KnotVector<T>| {
+            let breaks = sorted_set_union(
+                &a.multiplicity()
+                    .iter()
+                    .map(|m| *m.knot())
+                    .collect::<Vec<_>>(),
+                &b.multiplicity()
+                    .iter()
+                    .map(|m| *m.knot())
+                    .collect::<Vec<_>>(),
+            );
+            let count = 8;
+            let mut samples = breaks
+                .windows(2)
+                .flat_map(|w| {
+                    let (a, b) = (w[0], w[1]);
+                    (0..count).map(move |i| {
+                        a + (b - a) * T::from_usize(i).unwrap() / T::from_usize(count).unwrap()
+                    })
+                })
+                .collect::<Vec<_>>();
+            samples.extend(breaks.last());
+            samples
+        };
+        let us = samples(&self.u_knots, &other.u_knots);
+        let vs = samples(&self.v_knots, &other.v_knots);
+        let distances = us
+            .iter()
+            .flat_map(|u| vs.iter().map(move |v| (*u, *v)))
+            .map(|(u, v)| (f(u, v) - other.point_at(u, v)).norm())
+            .collect::<Vec<_>>();
+        SurfaceDeviation::from_distances(&distances)
+    }
+
+    /// The curves of the control points along the direction, defined by the knot vector of the direction
+    fn try_direction_curves(&self, v_direction: bool) -> anyhow::Result<Vec<NurbsCurve<T, D>>> {
+        if v_direction {
+            self.control_points
+                .iter()
+                .map(|row| NurbsCurve::try_new(self.v_degree, row.clone(), self.v_knots.to_vec()))
+                .collect()
+        } else {
+            (0..self.control_points[0].len())
+                .map(|j| {
+                    let column = self
+                        .control_points
+                        .iter()
+                        .map(|row| row[j].clone())
+                        .collect();
+                    NurbsCurve::try_new(self.u_degree, column, self.u_knots.to_vec())
+                })
+                .collect()
+        }
+    }
+
+    /// Replace the curves of the control points along the direction, which share the degree & the knot vector
+    fn with_direction_curves(&self, curves: &[NurbsCurve<T, D>], v_direction: bool) -> Self {
+        let (degree, knots) = (curves[0].degree(), curves[0].knots().clone());
+        if v_direction {
+            Self {
+                control_points: curves.iter().map(|c| c.control_points().clone()).collect(),
+                v_degree: degree,
+                v_knots: knots,
+                ..self.clone()
+            }
+        } else {
+            Self {
+                control_points: (0..curves[0].control_points().len())
+                    .map(|i| {
+                        curves
+                            .iter()
+                            .map(|c| c.control_points()[i].clone())
+                            .collect()
+                    })
+                    .collect(),
+                u_degree: degree,
+                u_knots: knots,
+                ..self.clone()
+            }
+        }
+    }
+
+    /// Loft the curves, interpolating them periodically across the sections if closed
+    fn try_loft_sections(
+        curves: &[NurbsCurve<T, D>],
+        degree_v: Option<usize>,
+        closed: bool,
+    ) -> anyhow::Result<Self> {
         let unified_curves = try_unify_curve_knot_vectors(curves)?;
 
         let degree_u = unified_curves[0].degree();
@@ -644,14 +1271,20 @@ where
                     .iter()
                     .map(|c| c.control_points()[i].clone())
                     .collect::<Vec<_>>();
-                let (control_points, knots) = try_interpolate_control_points(
-                    &points
-                        .iter()
-                        .map(|p| DVector::from_vec(p.iter().copied().collect()))
-                        .collect::<Vec<_>>(),
-                    degree_v,
-                    false,
-                )?;
+                let points = points
+                    .iter()
+                    .map(|p| DVector::from_vec(p.iter().copied().collect()))
+                    .collect::<Vec<_>>();
+                let (control_points, knots) = if closed {
+                    try_periodic_interpolate_control_points(
+                        &points,
+                        degree_v,
+                        KnotStyle::Chordal,
+                        false,
+                    )?
+                } else {
+                    try_interpolate_control_points(&points, degree_v, false)?
+                };
                 NurbsCurve::try_new(
                     degree_v,
                     control_points
@@ -679,6 +1312,9 @@ where
     }
 
     /// Try to create a iso curve from the surface
+    /// The curve is extracted exactly from the control points & weights by knot insertion, not sampled.
+    /// * `t` - The parameter of the iso curve
+    /// * `v_direction` - If true, the iso curve is at `v = t` & runs along the u direction, otherwise at `u = t` along the v direction
     /// # Example
     /// ```
     /// use curvo::prelude::*;
@@ -700,6 +1336,40 @@ where
     /// let (iso_start, iso_end) = v_iso.knots_domain();
     /// assert_relative_eq!(v_iso.point_at(iso_start), Point3::new(1.0, 0.0, 1.0), epsilon = 1e-8);
     /// assert_relative_eq!(v_iso.point_at(iso_end), Point3::new(1.0, 0.0, 0.0), epsilon = 1e-8);
+    ///
+    /// // The iso curve at an existing knot keeps the exact rational shape
+    /// let u_iso = extruded.try_isocurve(std::f64::consts::FRAC_PI_2, true).unwrap();
+    /// let (iso_start, iso_end) = u_iso.knots_domain();
+    /// assert_relative_eq!(u_iso.point_at(iso_start), Point3::new(0.0, 1.0, 1.0), epsilon = 1e-8);
+    /// assert_relative_eq!(u_iso.point_at(iso_end), Point3::new(0.0, 1.0, 0.0), epsilon = 1e-8);
+    ///
+    /// let v_iso = extruded.try_isocurve(0.5, false).unwrap();
+    /// let (iso_start, iso_end) = v_iso.knots_domain();
+    /// for i in 0..=16 {
+    ///     let t = iso_start + (iso_end - iso_start) * i as f64 / 16.;
+    ///     let p = v_iso.point_at(t);
+    ///     assert_relative_eq!(p.coords.xy().norm(), 1.0, epsilon = 1e-8);
+    ///     assert_relative_eq!(p.z, 0.5, epsilon = 1e-8);
+    /// }
+    ///
+    /// // The iso curve of the rational torus carries the exact weights,
+    /// // which are the weights of the profile scaled by the weight (1 + 2 cos(PI / 4) + 1) / 4 at the middle of the quarter arc of the revolution
+    /// let profile = NurbsCurve3D::try_circle(&Point3::new(2., 0., 0.), &Vector3::x(), &Vector3::z(), 0.5).unwrap();
+    /// let torus = NurbsSurface::try_revolve(&profile, &Point3::origin(), &Vector3::z(), std::f64::consts::TAU).unwrap();
+    /// let iso = torus.try_isocurve(0.125, false).unwrap();
+    /// let w = (2. + 2. * std::f64::consts::FRAC_1_SQRT_2) / 4.;
+    /// assert_eq!(iso.weights().len(), profile.weights().len());
+    /// for (a, b) in iso.weights().iter().zip(profile.weights()) {
+    ///     assert_relative_eq!(*a, b * w, epsilon = 1e-12);
+    /// }
+    /// let (iso_start, iso_end) = iso.knots_domain();
+    /// for i in 0..=16 {
+    ///     let t = iso_start + (iso_end - iso_start) * i as f64 / 16.;
+    ///     let p = iso.point_at(t);
+    ///     assert_relative_eq!(p, torus.point_at(0.125, t), epsilon = 1e-8);
+    ///     // the point lies on the tube of the radius 0.5 around the circle of the radius 2
+    ///     assert_relative_eq!((p.coords.xy().norm() - 2.).hypot(p.z), 0.5, epsilon = 1e-8);
+    /// }
     /// ```
     pub fn try_isocurve(&self, t: T, v_direction: bool) -> anyhow::Result<NurbsCurve<T, D>> {
         let (knots, degree) = if v_direction {
@@ -746,7 +1416,18 @@ where
                 refined.control_points.len() - 1
             }
         } else {
-            knots.find_knot_span_index(knots.len() - degree - 2, degree, t)
+            // the control points at `t` follow the ones of the knots before `t` in the refined knot vector,
+            // which keeps the index correct even if `t` already exists in the knot vector
+            let refined_knots = if v_direction {
+                &refined.v_knots
+            } else {
+                &refined.u_knots
+            };
+            refined_knots
+                .iter()
+                .filter(|k| **k < t - T::default_epsilon())
+                .count()
+                - 1
         };
 
         if v_direction {
@@ -780,6 +1461,35 @@ where
         ])
     }
 
+    /// Reverse the direction of the surface in the u or v direction, which flips the orientation of the normal
+    /// if `v_direction` is true, the v direction is reversed, otherwise the u direction
+    /// The parameter `t` of the reversed direction is mapped to `first + last - t` with the first & last knots.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let line = NurbsCurve3D::polyline(&[Point3::new(0., 0., 0.), Point3::new(1., 0., 0.)]);
+    /// let plane = NurbsSurface::extrude(&line, &Vector3::y());
+    /// let mut flipped = plane.clone();
+    /// flipped.flip(false);
+    ///
+    /// let (u0, u1) = plane.u_knots_domain();
+    /// let (u, v) = (0.2, 0.7);
+    /// assert_relative_eq!(flipped.point_at(u0 + u1 - u, v), plane.point_at(u, v), epsilon = 1e-10);
+    /// assert_relative_eq!(flipped.normal_at(u0 + u1 - u, v), -plane.normal_at(u, v), epsilon = 1e-10);
+    /// ```
+    pub fn flip(&mut self, v_direction: bool) {
+        if v_direction {
+            self.control_points.iter_mut().for_each(|row| row.reverse());
+            self.v_knots.invert();
+        } else {
+            self.control_points.reverse();
+            self.u_knots.invert();
+        }
+    }
+
     /// Try to refine the surface by inserting knots
     pub fn try_refine_knot(
         &mut self,
@@ -842,32 +1552,265 @@ where
         Ok(())
     }
 
-    /// Cast the surface to a surface with another floating point type
-    pub fn cast<F: FloatingPoint + SupersetOf<T>>(&self) -> NurbsSurface<F, D>
-    where
-        DefaultAllocator: Allocator<D>,
-    {
-        NurbsSurface {
-            control_points: self
+    /// Try to split the surface into two surfaces before and after the parameter
+    /// if `v_direction` is true, the surface is split at the v parameter, otherwise at the u parameter
+    pub(crate) fn try_split(&self, t: T, v_direction: bool) -> anyhow::Result<(Self, Self)> {
+        let (knots, degree) = if v_direction {
+            (&self.v_knots, self.v_degree)
+        } else {
+            (&self.u_knots, self.u_degree)
+        };
+
+        // insert the knot until its multiplicity reaches degree + 1
+        let multiplicity = knots.iter().filter(|k| **k == t).count();
+        let knots_to_insert: Vec<_> = (multiplicity..=degree).map(|_| t).collect();
+        let mut refined = self.clone();
+        if !knots_to_insert.is_empty() {
+            refined.try_refine_knot(knots_to_insert, v_direction)?;
+        }
+
+        // the index of the last control point before the parameter
+        let refined_knots = if v_direction {
+            &refined.v_knots
+        } else {
+            &refined.u_knots
+        };
+        let s = refined_knots.iter().filter(|k| **k < t).count() - 1;
+
+        if v_direction {
+            let knots0 = refined.v_knots.as_slice()[0..=(s + degree + 1)].to_vec();
+            let knots1 = refined.v_knots.as_slice()[s + 1..].to_vec();
+            let cpts0 = refined
                 .control_points
                 .iter()
-                .map(|row| row.iter().map(|p| p.clone().cast()).collect())
-                .collect(),
-            u_degree: self.u_degree,
-            v_degree: self.v_degree,
-            u_knots: self.u_knots.cast(),
-            v_knots: self.v_knots.cast(),
+                .map(|row| row[0..=s].to_vec())
+                .collect();
+            let cpts1 = refined
+                .control_points
+                .iter()
+                .map(|row| row[s + 1..].to_vec())
+                .collect();
+            Ok((
+                Self {
+                    control_points: cpts0,
+                    v_knots: KnotVector::new(knots0),
+                    ..refined.clone()
+                },
+                Self {
+                    control_points: cpts1,
+                    v_knots: KnotVector::new(knots1),
+                    ..refined
+                },
+            ))
+        } else {
+            let knots0 = refined.u_knots.as_slice()[0..=(s + degree + 1)].to_vec();
+            let knots1 = refined.u_knots.as_slice()[s + 1..].to_vec();
+            let cpts0 = refined.control_points[0..=s].to_vec();
+            let cpts1 = refined.control_points[s + 1..].to_vec();
+            Ok((
+                Self {
+                    control_points: cpts0,
+                    u_knots: KnotVector::new(knots0),
+                    ..refined.clone()
+                },
+                Self {
+                    control_points: cpts1,
+                    u_knots: KnotVector::new(knots1),
+                    ..refined
+                },
+            ))
         }
     }
-}
 
-/// Compute the rational derivatives of derivatives
-fn rational_derivatives<T, D>(
-    ders: &[Vec<OVector<T, D>>],
-    derivs: usize,
-) -> Vec<Vec<OVector<T, DimNameDiff<D, U1>>>>
-where
-    T: FloatingPoint,
+    /// Try to split the surface into two surfaces before and after the u parameter
+    /// The pieces are exact since the split is done by knot insertion.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let circle = NurbsCurve3D::try_circle(&Point3::origin(), &Vector3::x(), &Vector3::y(), 1.).unwrap();
+    /// let cylinder = NurbsSurface3D::extrude(&circle, &Vector3::z());
+    /// let (u0, u1) = cylinder.u_knots_domain();
+    /// let (v0, v1) = cylinder.v_knots_domain();
+    /// let u = u0 + (u1 - u0) * 0.3;
+    /// let (left, right) = cylinder.try_split_u(u).unwrap();
+    /// assert_relative_eq!(left.u_knots_domain().1, u, epsilon = 1e-10);
+    /// assert_relative_eq!(right.u_knots_domain().0, u, epsilon = 1e-10);
+    ///
+    /// // the pieces coincide with the original surface
+    /// for v in [v0, (v0 + v1) * 0.37, v1] {
+    ///     assert_relative_eq!(left.point_at(u0 + (u - u0) * 0.5, v), cylinder.point_at(u0 + (u - u0) * 0.5, v), epsilon = 1e-10);
+    ///     assert_relative_eq!(right.point_at(u + (u1 - u) * 0.5, v), cylinder.point_at(u + (u1 - u) * 0.5, v), epsilon = 1e-10);
+    /// }
+    ///
+    /// // the parameter outside the domain is rejected
+    /// assert!(cylinder.try_split_u(u1 + 1.).is_err());
+    /// ```
+    pub fn try_split_u(&self, t: T) -> anyhow::Result<(Self, Self)> {
+        let (start, end) = self.u_knots_domain();
+        anyhow::ensure!(
+            start < t && t < end,
+            "The parameter must be in the interior of the u domain"
+        );
+        self.try_split(t, false)
+    }
+
+    /// Try to split the surface into two surfaces before and after the v parameter
+    /// The pieces are exact since the split is done by knot insertion.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let circle = NurbsCurve3D::try_circle(&Point3::origin(), &Vector3::x(), &Vector3::y(), 1.).unwrap();
+    /// let cylinder = NurbsSurface3D::extrude(&circle, &Vector3::z());
+    /// let (u0, u1) = cylinder.u_knots_domain();
+    /// let (v0, v1) = cylinder.v_knots_domain();
+    /// // split at the existing knot of the circle
+    /// let v = std::f64::consts::FRAC_PI_2;
+    /// let (bottom, top) = cylinder.try_split_v(v).unwrap();
+    /// assert_relative_eq!(bottom.v_knots_domain().1, v, epsilon = 1e-10);
+    /// assert_relative_eq!(top.v_knots_domain().0, v, epsilon = 1e-10);
+    /// for u in [u0, (u0 + u1) * 0.5, u1] {
+    ///     assert_relative_eq!(bottom.point_at(u, v0 + (v - v0) * 0.4), cylinder.point_at(u, v0 + (v - v0) * 0.4), epsilon = 1e-10);
+    ///     assert_relative_eq!(top.point_at(u, v + (v1 - v) * 0.4), cylinder.point_at(u, v + (v1 - v) * 0.4), epsilon = 1e-10);
+    /// }
+    /// ```
+    pub fn try_split_v(&self, t: T) -> anyhow::Result<(Self, Self)> {
+        let (start, end) = self.v_knots_domain();
+        anyhow::ensure!(
+            start < t && t < end,
+            "The parameter must be in the interior of the v domain"
+        );
+        self.try_split(t, true)
+    }
+
+    /// Try to split the surface into four patches at the u & v parameters
+    /// The patches are ordered as `[(u0, v0), (u1, v0), (u0, v1), (u1, v1)]`
+    /// where `u0` & `v0` denote the pieces before the parameters and `u1` & `v1` the pieces after them.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let circle = NurbsCurve3D::try_circle(&Point3::origin(), &Vector3::x(), &Vector3::y(), 1.).unwrap();
+    /// let cylinder = NurbsSurface3D::extrude(&circle, &Vector3::z());
+    /// let (u0, u1) = cylinder.u_knots_domain();
+    /// let (v0, v1) = cylinder.v_knots_domain();
+    /// let (u, v) = ((u0 + u1) * 0.5, (v0 + v1) * 0.3);
+    /// let patches = cylinder.try_split_uv(u, v).unwrap();
+    /// assert_relative_eq!(patches[0].u_knots_domain().1, u, epsilon = 1e-10);
+    /// assert_relative_eq!(patches[0].v_knots_domain().1, v, epsilon = 1e-10);
+    /// assert_relative_eq!(patches[3].u_knots_domain().0, u, epsilon = 1e-10);
+    /// assert_relative_eq!(patches[3].v_knots_domain().0, v, epsilon = 1e-10);
+    ///
+    /// // the patches share the corner at the split parameters
+    /// let corner = cylinder.point_at(u, v);
+    /// patches.iter().for_each(|patch| {
+    ///     assert_relative_eq!(patch.point_at(u, v), corner, epsilon = 1e-10);
+    /// });
+    /// ```
+    pub fn try_split_uv(&self, u: T, v: T) -> anyhow::Result<[Self; 4]> {
+        let (before, after) = self.try_split_u(u)?;
+        let (p00, p01) = before.try_split_v(v)?;
+        let (p10, p11) = after.try_split_v(v)?;
+        Ok([p00, p10, p01, p11])
+    }
+
+    /// Try to decompose the surface into Bézier patches by splitting at the interior knots
+    /// The patches keep the parameters of the original surface, ordered along the u direction first.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let circle = NurbsCurve3D::try_circle(&Point3::origin(), &Vector3::x(), &Vector3::y(), 1.).unwrap();
+    /// let cylinder = NurbsSurface3D::extrude(&circle, &Vector3::z());
+    /// let patches = cylinder.try_decompose_bezier_patches().unwrap();
+    /// // the circle consists of 4 rational quadratic arcs
+    /// assert_eq!(patches.len(), 4);
+    /// for patch in patches.iter() {
+    ///     assert_eq!(patch.control_points().len(), patch.u_degree() + 1);
+    ///     assert_eq!(patch.control_points()[0].len(), patch.v_degree() + 1);
+    ///     let (u0, u1) = patch.u_knots_domain();
+    ///     let (v0, v1) = patch.v_knots_domain();
+    ///     let (u, v) = ((u0 + u1) / 2., (v0 + v1) / 2.);
+    ///     assert_relative_eq!(patch.point_at(u, v), cylinder.point_at(u, v), epsilon = 1e-10);
+    /// }
+    /// ```
+    pub fn try_decompose_bezier_patches(&self) -> anyhow::Result<Vec<Self>> {
+        let interior_knots = |v_direction: bool| {
+            let (knots, (start, end)) = if v_direction {
+                (&self.v_knots, self.v_knots_domain())
+            } else {
+                (&self.u_knots, self.u_knots_domain())
+            };
+            knots
+                .multiplicity()
+                .into_iter()
+                .map(|m| *m.knot())
+                .filter(|k| start < *k && *k < end)
+                .collect::<Vec<_>>()
+        };
+
+        let split =
+            |surfaces: Vec<Self>, knots: &[T], v_direction: bool| -> anyhow::Result<Vec<Self>> {
+                let mut patches = vec![];
+                for s in surfaces {
+                    let mut rest = s;
+                    for k in knots {
+                        let (head, tail) = rest.try_split(*k, v_direction)?;
+                        patches.push(head);
+                        rest = tail;
+                    }
+                    patches.push(rest);
+                }
+                Ok(patches)
+            };
+
+        let patches = split(vec![self.clone()], &interior_knots(false), false)?;
+        split(patches, &interior_knots(true), true)
+    }
+
+    /// Return the dehomogenized control points
+    pub fn dehomogenized_control_points(&self) -> Vec<Vec<OPoint<T, DimNameDiff<D, U1>>>> {
+        self.control_points
+            .iter()
+            .map(|row| row.iter().map(|p| dehomogenize(p).unwrap()).collect())
+            .collect()
+    }
+
+    /// Cast the surface to a surface with another floating point type
+    pub fn cast<F: FloatingPoint + SupersetOf<T>>(&self) -> NurbsSurface<F, D>
+    where
+        DefaultAllocator: Allocator<D>,
+    {
+        NurbsSurface {
+            control_points: self
+                .control_points
+                .iter()
+                .map(|row| row.iter().map(|p| p.clone().cast()).collect())
+                .collect(),
+            u_degree: self.u_degree,
+            v_degree: self.v_degree,
+            u_knots: self.u_knots.cast(),
+            v_knots: self.v_knots.cast(),
+        }
+    }
+}
+
+/// Compute the rational derivatives of derivatives
+fn rational_derivatives<T, D>(
+    ders: &[Vec<OVector<T, D>>],
+    derivs: usize,
+) -> Vec<Vec<OVector<T, DimNameDiff<D, U1>>>>
+where
+    T: FloatingPoint,
     D: DimName,
     DefaultAllocator: Allocator<D>,
     D: DimNameSub<U1>,
@@ -928,54 +1871,1185 @@ where
 /// A specialized trait for NURBS surfaces with 3D points,
 /// particularly designed for sweeping operations that require Frenet frames.
 impl<T: FloatingPoint> NurbsSurface3D<T> {
+    /// Offset the surface along its normal by the distance
+    /// The planar surface is offset exactly by translating its control points; otherwise, the offset is refitted by the bicubic surface with the uniform knots, refining the knots until the deviation from the exact offset is within the tolerance.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// // the planar surface is translated along its normal
+    /// let line = NurbsCurve3D::polyline(&[Point3::new(0., 0., 0.), Point3::new(1., 0., 0.)]);
+    /// let plane = NurbsSurface::extrude(&line, &Vector3::y());
+    /// let normal = plane.normal_at(0.5, 0.5).normalize();
+    /// let offset = plane.try_offset(0.5, 1e-6).unwrap();
+    /// assert_eq!(offset.control_points().len(), plane.control_points().len());
+    /// assert_relative_eq!(offset.point_at(0.2, 0.7), plane.point_at(0.2, 0.7) + normal * 0.5, epsilon = 1e-10);
+    ///
+    /// // the cylinder grows by the distance within the tolerance
+    /// let cylinder = NurbsSurface::try_cylinder(&Point3::origin(), &(Vector3::z() * 2.), 1.).unwrap();
+    /// let offset = cylinder.try_offset(0.25, 1e-4).unwrap();
+    /// let (u0, u1) = offset.u_knots_domain();
+    /// let (v0, v1) = offset.v_knots_domain();
+    /// for i in 0..=10 {
+    ///     for j in 0..=10 {
+    ///         let u = u0 + (u1 - u0) * i as f64 / 10.;
+    ///         let v = v0 + (v1 - v0) * j as f64 / 10.;
+    ///         assert_relative_eq!(offset.point_at(u, v).coords.xy().norm(), 1.25, epsilon = 1e-4);
+    ///     }
+    /// }
+    /// ```
+    pub fn try_offset(&self, distance: T, tolerance: T) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            tolerance > T::zero(),
+            "The tolerance must be greater than zero"
+        );
+        let (u0, u1) = self.u_knots_domain();
+        let (v0, v1) = self.v_knots_domain();
+        let half = T::from_f64(0.5).unwrap();
+        let center = ((u0 + u1) * half, (v0 + v1) * half);
+
+        // the unit normal, sampled slightly towards the center at the degenerate points such as the poles of the sphere
+        let unit_normal = |u: T, v: T| {
+            let mut n = self.normal_at(u, v);
+            let mut fraction = T::from_f64(1e-6).unwrap();
+            while n.norm() <= T::default_epsilon() && fraction < T::one() {
+                n = self.normal_at(u + (center.0 - u) * fraction, v + (center.1 - v) * fraction);
+                fraction *= T::from_f64(2.).unwrap();
+            }
+            n.normalize()
+        };
+
+        // the planar surface has the constant normal
+        let normal = unit_normal(center.0, center.1);
+        let points = self.dehomogenized_control_points();
+        let origin = points[0][0];
+        let planar = points.iter().flatten().all(|p| {
+            ComplexField::abs((p - origin).dot(&normal)) <= tolerance * T::from_f64(1e-3).unwrap()
+        });
+        if planar {
+            let mut offset = self.clone();
+            offset.control_points.iter_mut().flatten().for_each(|p| {
+                let w = p.w;
+                let translation = normal * (distance * w);
+                p.x += translation.x;
+                p.y += translation.y;
+                p.z += translation.z;
+            });
+            return Ok(offset);
+        }
+
+        let f = |u: T, v: T| self.point_at(u, v) + unit_normal(u, v) * distance;
+        let (pu, pv) = (3, 3);
+        let mut counts = (
+            self.control_points.len().max(pu + 1),
+            self.control_points[0].len().max(pv + 1),
+        );
+        for _ in 0..6 {
+            let fitted = self.try_fit_uniform((pu, pv), counts, f)?;
+            if self.deviation_from(&fitted, f).max() <= tolerance {
+                return Ok(fitted);
+            }
+            counts = ((counts.0 - pu) * 2 + pu, (counts.1 - pv) * 2 + pv);
+        }
+        Err(anyhow::anyhow!(
+            "The offset surface could not be fitted within the tolerance"
+        ))
+    }
+
+    /// Try to loft the section curves with the guide curves, the closed option & the alignment of the sections
+    /// The intermediate sections between each pair of the sections are blended from them & displaced to pass through the guides,
+    /// so the surface interpolates the sections exactly & follows the guides approximately.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let circle = |z: f64| NurbsCurve3D::try_circle(&Point3::new(0., 0., z), &Vector3::x(), &Vector3::y(), 1.).unwrap();
+    /// // the middle section is reversed, so the sections are aligned before lofting
+    /// let sections = vec![circle(0.), circle(1.).inverse(), circle(2.)];
+    /// let guide = NurbsCurve3D::try_interpolate(&[
+    ///     Point3::new(1., 0., 0.),
+    ///     Point3::new(1.2, 0., 0.5),
+    ///     Point3::new(1., 0., 1.),
+    ///     Point3::new(1.2, 0., 1.5),
+    ///     Point3::new(1., 0., 2.),
+    /// ], 3).unwrap();
+    /// let options = LoftOptions::default()
+    ///     .with_align_sections(true)
+    ///     .with_guides(vec![guide.clone()]);
+    /// let lofted = NurbsSurface::try_loft_with_options(&sections, &options).unwrap();
+    ///
+    /// // the surface follows the guide between the sections
+    /// let p = guide.point_at(0.25);
+    /// let closest = lofted.find_closest_point(&p).unwrap();
+    /// assert!((closest - p).norm() < 1e-2);
+    ///
+    /// // the closed loft connects the last section to the first one
+    /// let ring = (0..4).map(|i| {
+    ///     let angle = i as f64 * std::f64::consts::FRAC_PI_2;
+    ///     let center = Point3::new(angle.cos() * 3., angle.sin() * 3., 0.);
+    ///     NurbsCurve3D::try_circle(&center, &(center.coords.normalize()), &Vector3::z(), 1.).unwrap()
+    /// }).collect::<Vec<_>>();
+    /// let options = LoftOptions::default().with_closed(true).with_degree_v(3);
+    /// let torus = NurbsSurface::try_loft_with_options(&ring, &options).unwrap();
+    /// let (u0, u1) = torus.u_knots_domain();
+    /// let (v0, v1) = torus.v_knots_domain();
+    /// let u = (u0 + u1) * 0.3;
+    /// assert_relative_eq!(torus.point_at(u, v0), torus.point_at(u, v1), epsilon = 1e-8);
+    /// ```
+    pub fn try_loft_with_options(
+        curves: &[NurbsCurve3D<T>],
+        options: &LoftOptions<T>,
+    ) -> anyhow::Result<Self>
+    where
+        T: ArgminFloat,
+    {
+        anyhow::ensure!(curves.len() >= 2, "At least two sections are required");
+        let mut sections = curves.to_vec();
+        if options.align_sections {
+            for i in 1..sections.len() {
+                sections[i] = try_align_section(&sections[i - 1], &sections[i])?;
+            }
+        }
+        if !options.guides.is_empty() {
+            anyhow::ensure!(
+                !options.closed,
+                "The guide curves are not supported for the closed loft"
+            );
+            sections = try_guided_sections(&sections, &options.guides, options.guide_divisions)?;
+        }
+        Self::try_loft_sections(&sections, options.degree_v, options.closed)
+    }
+
+    /// Try to fit a surface to the scattered points in the least squares sense
+    /// The points are parameterized by the projection onto their best fit plane, so the points should be a height field over the plane.
+    /// The fitting is iterated with the correction of the parameters toward the closest points on the surface,
+    /// and the knots are inserted at the middle of the spans with the points farther than the tolerance.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::Point3;
+    ///
+    /// let f = |x: f64, y: f64| Point3::new(x, y, (x * 1.2).sin() * 0.3 + y * y * 0.1);
+    /// // the quasi random points over the square
+    /// let points = (0..400)
+    ///     .map(|i| {
+    ///         let x = (i as f64 * 0.618033988749895).fract() * 2.;
+    ///         let y = (i as f64 * 0.754877666246693).fract() * 2.;
+    ///         f(x, y)
+    ///     })
+    ///     .collect::<Vec<_>>();
+    /// let options = SurfaceFitOptions::default().with_tolerance(1e-3);
+    /// let surface = NurbsSurface3D::try_fit_scattered(&points, options).unwrap();
+    /// for p in points.iter() {
+    ///     let closest = surface.find_closest_point(p).unwrap();
+    ///     assert!((closest - p).norm() < 1e-3);
+    /// }
+    /// ```
+    pub fn try_fit_scattered(
+        points: &[Point3<T>],
+        options: SurfaceFitOptions<T>,
+    ) -> anyhow::Result<Self> {
+        anyhow::ensure!(points.len() >= 3, "Too few points to fit");
+        let n = T::from_usize(points.len()).unwrap();
+        let centroid = points
+            .iter()
+            .fold(Vector3::zeros(), |acc, p| acc + p.coords)
+            / n;
+        let covariance = points.iter().fold(Matrix3::zeros(), |acc, p| {
+            let d = p.coords - centroid;
+            acc + d * d.transpose()
+        });
+
+        // the axes of the plane are the principal directions with the largest variances
+        let eigen = covariance.symmetric_eigen();
+        let mut order = [0, 1, 2];
+        order.sort_by(|a, b| {
+            eigen.eigenvalues[*b]
+                .partial_cmp(&eigen.eigenvalues[*a])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let x_axis = eigen.eigenvectors.column(order[0]).into_owned();
+        let y_axis = eigen.eigenvectors.column(order[1]).into_owned();
+        let projected = points
+            .iter()
+            .map(|p| {
+                let d = p.coords - centroid;
+                (d.dot(&x_axis), d.dot(&y_axis))
+            })
+            .collect::<Vec<_>>();
+        Self::try_fit_scattered_parameters(points, normalize_parameters(&projected)?, options)
+    }
+
+    /// Try to fit a surface to the scattered points parameterized by the closest parameters on the base surface
+    /// The base surface is an approximation of the points, such as a surface fitted to the boundary of the points, which allows the points to be folded over any plane.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    ///
+    /// // the points on the curved sheet around the z axis
+    /// let f = |a: f64, z: f64| {
+    ///     let r = 1. + 0.1 * (z * 2.).sin();
+    ///     Point3::new(r * a.cos(), r * a.sin(), z)
+    /// };
+    /// let points = (0..300)
+    ///     .map(|i| {
+    ///         let a = (i as f64 * 0.618033988749895).fract() * 3.;
+    ///         let z = (i as f64 * 0.754877666246693).fract() * 2.;
+    ///         f(a, z)
+    ///     })
+    ///     .collect::<Vec<_>>();
+    /// let arc = NurbsCurve3D::try_arc(&Point3::origin(), &Vector3::x(), &Vector3::y(), 1., 0., 3.).unwrap();
+    /// let base = NurbsSurface3D::extrude(&arc, &(Vector3::z() * 2.));
+    /// let options = SurfaceFitOptions::default().with_tolerance(1e-3);
+    /// let surface = NurbsSurface3D::try_fit_scattered_to_base(&points, &base, options).unwrap();
+    /// for p in points.iter().step_by(10) {
+    ///     let closest = surface.find_closest_point(p).unwrap();
+    ///     assert!((closest - p).norm() < 1e-3);
+    /// }
+    /// ```
+    pub fn try_fit_scattered_to_base(
+        points: &[Point3<T>],
+        base: &Self,
+        options: SurfaceFitOptions<T>,
+    ) -> anyhow::Result<Self>
+    where
+        T: ArgminFloat,
+    {
+        let parameters = points
+            .iter()
+            .map(|p| base.find_closest_parameter(p))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Self::try_fit_scattered_parameters(points, normalize_parameters(&parameters)?, options)
+    }
+
+    /// Fit the surface to the points with the initial parameters in the unit square
+    fn try_fit_scattered_parameters(
+        points: &[Point3<T>],
+        mut parameters: Vec<(T, T)>,
+        options: SurfaceFitOptions<T>,
+    ) -> anyhow::Result<Self> {
+        let (pu, pv) = (options.u_degree, options.v_degree);
+        anyhow::ensure!(pu > 0 && pv > 0, "The degrees must be greater than zero");
+        anyhow::ensure!(
+            options.u_control_points_count > pu && options.v_control_points_count > pv,
+            "The number of control points must be greater than the degree"
+        );
+        anyhow::ensure!(
+            options.smoothing >= T::zero(),
+            "The smoothing must not be negative"
+        );
+        anyhow::ensure!(
+            options.tolerance > T::zero(),
+            "The tolerance must be greater than zero"
+        );
+
+        let uniform = |count: usize, degree: usize| {
+            let spans = count - degree;
+            (1..spans)
+                .map(|i| T::from_usize(i).unwrap() / T::from_usize(spans).unwrap())
+                .collect::<Vec<_>>()
+        };
+        let clamped = |inner: &[T], degree: usize| {
+            KnotVector::new(
+                [
+                    vec![T::zero(); degree + 1],
+                    inner.to_vec(),
+                    vec![T::one(); degree + 1],
+                ]
+                .concat(),
+            )
+        };
+        let mut u_inner = uniform(options.u_control_points_count, pu);
+        let mut v_inner = uniform(options.v_control_points_count, pv);
+        let half = T::from_f64(0.5).unwrap();
+
+        let mut iteration = 0;
+        loop {
+            let u_knots = clamped(&u_inner, pu);
+            let v_knots = clamped(&v_inner, pv);
+            let surface = try_fit_surface_at_parameters(
+                points,
+                &parameters,
+                u_knots,
+                v_knots,
+                pu,
+                pv,
+                options.smoothing,
+            )?;
+
+            // correct the parameters toward the closest points on the surface
+            parameters
+                .iter_mut()
+                .zip(points.iter())
+                .for_each(|(uv, p)| {
+                    *uv = surface.correct_closest_parameter(p, *uv, 4);
+                });
+            let errors = parameters
+                .iter()
+                .zip(points.iter())
+                .map(|((u, v), p)| (surface.point_at(*u, *v) - p).norm())
+                .collect::<Vec<_>>();
+            let max_error = errors.iter().fold(T::zero(), |acc, e| acc.max(*e));
+            iteration += 1;
+            if max_error <= options.tolerance || iteration >= options.max_iterations {
+                return Ok(surface);
+            }
+
+            // split the spans containing the points out of the tolerance
+            let refine = |inner: &[T], degree: usize, coord: &dyn Fn(&(T, T)) -> T| {
+                if inner.len() + degree + 1 >= options.max_control_points_count {
+                    return vec![];
+                }
+                let bounds = [vec![T::zero()], inner.to_vec(), vec![T::one()]].concat();
+                bounds
+                    .windows(2)
+                    .filter(|w| {
+                        parameters.iter().zip(errors.iter()).any(|(uv, e)| {
+                            let t = coord(uv);
+                            *e > options.tolerance && t >= w[0] && t <= w[1]
+                        })
+                    })
+                    .map(|w| (w[0] + w[1]) * half)
+                    .take(options.max_control_points_count - inner.len() - degree - 1)
+                    .collect::<Vec<_>>()
+            };
+            let u_insert = refine(&u_inner, pu, &|uv| uv.0);
+            let v_insert = refine(&v_inner, pv, &|uv| uv.1);
+            if u_insert.is_empty() && v_insert.is_empty() {
+                return Ok(surface);
+            }
+            u_inner = sorted_set_union(&u_inner, &u_insert);
+            v_inner = sorted_set_union(&v_inner, &v_insert);
+        }
+    }
+
+    /// Move the parameter toward the closest point by the newton iterations (The NURBS Book eq. 6.6), clamped to the domain
+    fn correct_closest_parameter(
+        &self,
+        point: &Point3<T>,
+        uv: (T, T),
+        iterations: usize,
+    ) -> (T, T) {
+        let (u0, u1) = self.u_knots_domain();
+        let (v0, v1) = self.v_knots_domain();
+        let (mut u, mut v) = uv;
+        for _ in 0..iterations {
+            let d = self.rational_derivatives(u, v, 2);
+            let r = d[0][0] - point.coords;
+            let (su, sv) = (&d[1][0], &d[0][1]);
+            let j = Matrix2::new(
+                su.dot(su) + r.dot(&d[2][0]),
+                su.dot(sv) + r.dot(&d[1][1]),
+                su.dot(sv) + r.dot(&d[1][1]),
+                sv.dot(sv) + r.dot(&d[0][2]),
+            );
+            let k = Vector2::new(-r.dot(su), -r.dot(sv));
+            let Some(delta) = j.lu().solve(&k) else {
+                break;
+            };
+            u = (u + delta.x).clamp(u0, u1);
+            v = (v + delta.y).clamp(v0, v1);
+        }
+        (u, v)
+    }
+
+    /// Try to create a bilinearly blended Coons patch from four boundary curves
+    /// `c0` & `c1` are the boundaries along the u direction at v = 0 & v = 1, `d0` & `d1` are the boundaries along the v direction at u = 0 & u = 1.
+    /// The curves are reversed if needed to meet at the corners, and the patch interpolates them exactly if they are non-rational.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::Point3;
+    /// use approx::assert_relative_eq;
+    ///
+    /// let c0 = NurbsCurve3D::try_interpolate(&[Point3::new(0., 0., 0.), Point3::new(1., 0., 0.5), Point3::new(2., 0., 0.)], 2).unwrap();
+    /// let c1 = NurbsCurve3D::polyline(&[Point3::new(0., 2., 0.), Point3::new(2., 2., 0.)]);
+    /// let d0 = NurbsCurve3D::polyline(&[Point3::new(0., 0., 0.), Point3::new(0., 2., 0.)]);
+    /// // the boundary in the opposite direction is reversed
+    /// let d1 = NurbsCurve3D::try_interpolate(&[Point3::new(2., 2., 0.), Point3::new(2., 1., -0.5), Point3::new(2., 0., 0.)], 2).unwrap();
+    ///
+    /// let patch = NurbsSurface::try_coons_patch(&c0, &c1, &d0, &d1).unwrap();
+    /// let (u0, u1) = patch.u_knots_domain();
+    /// let (v0, v1) = patch.v_knots_domain();
+    /// for i in 0..=8 {
+    ///     let t = i as f64 / 8.;
+    ///     let u = u0 + (u1 - u0) * t;
+    ///     let v = v0 + (v1 - v0) * t;
+    ///     let (c0s, c0e) = c0.knots_domain();
+    ///     assert_relative_eq!(patch.point_at(u, v0), c0.point_at(c0s + (c0e - c0s) * t), epsilon = 1e-8);
+    ///     assert_relative_eq!(patch.point_at(u0, v), Point3::new(0., 2. * t, 0.), epsilon = 1e-8);
+    /// }
+    /// assert_relative_eq!(patch.point_at(u1, (v0 + v1) / 2.), d1.point_at(0.5), epsilon = 1e-8);
+    /// ```
+    pub fn try_coons_patch(
+        c0: &NurbsCurve3D<T>,
+        c1: &NurbsCurve3D<T>,
+        d0: &NurbsCurve3D<T>,
+        d1: &NurbsCurve3D<T>,
+    ) -> anyhow::Result<Self> {
+        let ends = |c: &NurbsCurve3D<T>| {
+            let (s, e) = c.knots_domain();
+            (c.point_at(s), c.point_at(e))
+        };
+        let tolerance = T::from_f64(1e-6).unwrap();
+        // orient the curve to start at the point
+        let orient = |c: &NurbsCurve3D<T>, start: &Point3<T>| -> anyhow::Result<NurbsCurve3D<T>> {
+            let (s, e) = ends(c);
+            if (s - start).norm() < tolerance {
+                Ok(c.clone())
+            } else if (e - start).norm() < tolerance {
+                Ok(c.inverse())
+            } else {
+                anyhow::bail!("The boundary curves do not meet at the corners")
+            }
+        };
+        let (p00, p10) = ends(c0);
+        let d0 = orient(d0, &p00)?;
+        let d1 = orient(d1, &p10)?;
+        let (_, p01) = ends(&d0);
+        let (_, p11) = ends(&d1);
+        let c1 = orient(c1, &p01)?;
+        anyhow::ensure!(
+            (ends(&c1).1 - p11).norm() < tolerance,
+            "The boundary curves do not meet at the corners"
+        );
+
+        let cs = try_unify_curve_knot_vectors(&[c0.clone(), c1])?;
+        let ds = try_unify_curve_knot_vectors(&[d0, d1])?;
+
+        // the greville abscissae normalized into the unit interval, which reproduce the linear functions
+        let greville = |c: &NurbsCurve3D<T>| {
+            let (knots, degree) = (c.knots(), c.degree());
+            let (start, end) = c.knots_domain();
+            (0..c.control_points().len())
+                .map(|i| {
+                    let g = (1..=degree).fold(T::zero(), |acc, j| acc + knots[i + j])
+                        / T::from_usize(degree.max(1)).unwrap();
+                    (g - start) / (end - start)
+                })
+                .collect::<Vec<_>>()
+        };
+        let (gu, gv) = (greville(&cs[0]), greville(&ds[0]));
+        let corners = [c0.control_points()[0], *c0.control_points().last().unwrap()];
+        let corners = [
+            corners,
+            [
+                ds[0].control_points().last().cloned().unwrap(),
+                ds[1].control_points().last().cloned().unwrap(),
+            ],
+        ];
+
+        let control_points = (0..gu.len())
+            .map(|i| {
+                (0..gv.len())
+                    .map(|j| {
+                        let (u, v) = (gu[i], gv[j]);
+                        let ruled_u = cs[0].control_points()[i] * (T::one() - v)
+                            + cs[1].control_points()[i].coords * v;
+                        let ruled_v = ds[0].control_points()[j] * (T::one() - u)
+                            + ds[1].control_points()[j].coords * u;
+                        let bilinear = corners[0][0].coords * ((T::one() - u) * (T::one() - v))
+                            + corners[0][1].coords * (u * (T::one() - v))
+                            + corners[1][0].coords * ((T::one() - u) * v)
+                            + corners[1][1].coords * (u * v);
+                        Point4::from(ruled_u.coords + ruled_v.coords - bilinear)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Ok(Self::new(
+            cs[0].degree(),
+            ds[0].degree(),
+            cs[0].knots().to_vec(),
+            ds[0].knots().to_vec(),
+            control_points,
+        ))
+    }
+
+    /// Try to create a Gordon surface interpolating a network of curves
+    /// `u_curves` are the curves along the u direction & `v_curves` are the curves along the v direction, each family ordered across the other.
+    /// Every curve of a family must intersect all curves of the other family at a common parameter, and the first & last curves of each family bound the network.
+    /// The surface is the sum of the lofts through each family minus the tensor product interpolation of the intersection points.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::Point3;
+    /// use approx::assert_relative_eq;
+    ///
+    /// let heights = [0., 1., 0.];
+    /// let u_curves = heights
+    ///     .iter()
+    ///     .enumerate()
+    ///     .map(|(i, h)| {
+    ///         let y = i as f64;
+    ///         NurbsCurve3D::try_interpolate(&[Point3::new(0., y, 0.), Point3::new(1., y, *h), Point3::new(2., y, 0.)], 2).unwrap()
+    ///     })
+    ///     .collect::<Vec<_>>();
+    /// let v_curves = vec![
+    ///     NurbsCurve3D::polyline(&[Point3::new(0., 0., 0.), Point3::new(0., 1., 0.), Point3::new(0., 2., 0.)]),
+    ///     NurbsCurve3D::try_interpolate(&[Point3::new(1., 0., 0.), Point3::new(1., 1., 1.), Point3::new(1., 2., 0.)], 2).unwrap(),
+    ///     NurbsCurve3D::polyline(&[Point3::new(2., 0., 0.), Point3::new(2., 1., 0.), Point3::new(2., 2., 0.)]),
+    /// ];
+    ///
+    /// let gordon = NurbsSurface::try_gordon(&u_curves, &v_curves).unwrap();
+    /// let (u0, u1) = gordon.u_knots_domain();
+    /// let (v0, v1) = gordon.v_knots_domain();
+    /// for i in 0..=8 {
+    ///     let t = i as f64 / 8.;
+    ///     for (k, c) in u_curves.iter().enumerate() {
+    ///         let v = v0 + (v1 - v0) * k as f64 / 2.;
+    ///         let (s, e) = c.knots_domain();
+    ///         assert_relative_eq!(gordon.point_at(u0 + (u1 - u0) * t, v), c.point_at(s + (e - s) * t), epsilon = 1e-8);
+    ///     }
+    ///     for (l, c) in v_curves.iter().enumerate() {
+    ///         let u = u0 + (u1 - u0) * l as f64 / 2.;
+    ///         let (s, e) = c.knots_domain();
+    ///         assert_relative_eq!(gordon.point_at(u, v0 + (v1 - v0) * t), c.point_at(s + (e - s) * t), epsilon = 1e-8);
+    ///     }
+    /// }
+    /// ```
+    pub fn try_gordon(
+        u_curves: &[NurbsCurve3D<T>],
+        v_curves: &[NurbsCurve3D<T>],
+    ) -> anyhow::Result<Self>
+    where
+        T: ArgminFloat,
+    {
+        anyhow::ensure!(
+            u_curves.len() >= 2 && v_curves.len() >= 2,
+            "The network needs at least 2 curves in each direction"
+        );
+
+        let cs = try_unify_curve_knot_vectors(u_curves)?;
+        let ds = try_unify_curve_knot_vectors(v_curves)?;
+        let (u_start, u_end) = cs[0].knots_domain();
+        let (v_start, v_end) = ds[0].knots_domain();
+
+        // the parameters of the intersections on both families
+        let distance_tolerance = T::from_f64(1e-4).unwrap();
+        let parameter_tolerance = T::from_f64(1e-3).unwrap();
+        let mut intersections = vec![vec![(T::zero(), T::zero()); ds.len()]; cs.len()];
+        for (k, c) in cs.iter().enumerate() {
+            for (l, d) in ds.iter().enumerate() {
+                let closest = c.closest_parameters(d)?;
+                anyhow::ensure!(
+                    *closest.distance() < distance_tolerance,
+                    "The curve {} along the u direction does not intersect the curve {} along the v direction",
+                    k,
+                    l
+                );
+                intersections[k][l] = (closest.a().1, closest.b().1);
+            }
+        }
+        let mean = |values: Vec<T>| {
+            let n = T::from_usize(values.len()).unwrap();
+            values.into_iter().fold(T::zero(), |acc, v| acc + v) / n
+        };
+        let mut us = (0..ds.len())
+            .map(|l| mean(intersections.iter().map(|row| row[l].0).collect()))
+            .collect::<Vec<_>>();
+        let mut vs = (0..cs.len())
+            .map(|k| mean(intersections[k].iter().map(|p| p.1).collect()))
+            .collect::<Vec<_>>();
+        let compatible = intersections.iter().enumerate().all(|(k, row)| {
+            row.iter().enumerate().all(|(l, (u, v))| {
+                ComplexField::abs(*u - us[l]) < parameter_tolerance * (u_end - u_start)
+                    && ComplexField::abs(*v - vs[k]) < parameter_tolerance * (v_end - v_start)
+            })
+        });
+        anyhow::ensure!(
+            compatible,
+            "The curves of each family must intersect the other family at common parameters"
+        );
+        let increasing = |ps: &[T]| ps.windows(2).all(|w| w[0] < w[1]);
+        anyhow::ensure!(
+            increasing(&us) && increasing(&vs),
+            "The curves of each family must be ordered along the other family"
+        );
+        let bounded = ComplexField::abs(us[0] - u_start) < parameter_tolerance * (u_end - u_start)
+            && ComplexField::abs(us[us.len() - 1] - u_end)
+                < parameter_tolerance * (u_end - u_start)
+            && ComplexField::abs(vs[0] - v_start) < parameter_tolerance * (v_end - v_start)
+            && ComplexField::abs(vs[vs.len() - 1] - v_end)
+                < parameter_tolerance * (v_end - v_start);
+        anyhow::ensure!(
+            bounded,
+            "The first & last curves of each family must bound the network"
+        );
+        us[0] = u_start;
+        *us.last_mut().unwrap() = u_end;
+        vs[0] = v_start;
+        *vs.last_mut().unwrap() = v_end;
+
+        let degree_u = cs[0].degree().min(ds.len() - 1);
+        let degree_v = ds[0].degree().min(cs.len() - 1);
+
+        // the lofts through the curves of each family
+        let lofted_u = (0..cs[0].control_points().len())
+            .map(|i| {
+                let points = cs.iter().map(|c| c.control_points()[i]).collect::<Vec<_>>();
+                try_interpolate_at_parameters(&points, &vs, degree_v)
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let lofted_u = Self::new(
+            cs[0].degree(),
+            degree_v,
+            cs[0].knots().to_vec(),
+            lofted_u[0].knots().to_vec(),
+            lofted_u
+                .iter()
+                .map(|c| c.control_points().clone())
+                .collect(),
+        );
+
+        let lofted_v = (0..ds[0].control_points().len())
+            .map(|j| {
+                let points = ds.iter().map(|d| d.control_points()[j]).collect::<Vec<_>>();
+                try_interpolate_at_parameters(&points, &us, degree_u)
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let lofted_v = Self::new(
+            degree_u,
+            ds[0].degree(),
+            lofted_v[0].knots().to_vec(),
+            ds[0].knots().to_vec(),
+            (0..lofted_v[0].control_points().len())
+                .map(|i| lofted_v.iter().map(|c| c.control_points()[i]).collect())
+                .collect(),
+        );
+
+        // the tensor product interpolation of the intersection points
+        let columns = us
+            .iter()
+            .map(|u| {
+                let points = cs.iter().map(|c| c.point(*u)).collect::<Vec<_>>();
+                try_interpolate_at_parameters(&points, &vs, degree_v)
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let rows = (0..columns[0].control_points().len())
+            .map(|j| {
+                let points = columns
+                    .iter()
+                    .map(|c| c.control_points()[j])
+                    .collect::<Vec<_>>();
+                try_interpolate_at_parameters(&points, &us, degree_u)
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let tensor = Self::new(
+            degree_u,
+            degree_v,
+            rows[0].knots().to_vec(),
+            columns[0].knots().to_vec(),
+            (0..rows[0].control_points().len())
+                .map(|i| rows.iter().map(|c| c.control_points()[i]).collect())
+                .collect(),
+        );
+
+        let surfaces = try_unify_surface_knot_vectors(&[lofted_u, lofted_v, tensor], false)?;
+        let surfaces = try_unify_surface_knot_vectors(&surfaces, true)?;
+        let [lofted_u, lofted_v, tensor] = &surfaces[..] else {
+            unreachable!()
+        };
+        let control_points = lofted_u
+            .control_points()
+            .iter()
+            .zip(lofted_v.control_points().iter())
+            .zip(tensor.control_points().iter())
+            .map(|((a, b), c)| {
+                a.iter()
+                    .zip(b.iter())
+                    .zip(c.iter())
+                    .map(|((a, b), c)| Point4::from(a.coords + b.coords - c.coords))
+                    .collect()
+            })
+            .collect();
+
+        Ok(Self::new(
+            lofted_u.u_degree(),
+            lofted_u.v_degree(),
+            lofted_u.u_knots().to_vec(),
+            lofted_u.v_knots().to_vec(),
+            control_points,
+        ))
+    }
+
+    /// Try to fill an N-sided boundary loop with N quad patches meeting at the center of the loop
+    /// Each boundary curve is split at the middle, and each patch is the Coons patch bounded by the halves of the curves around a corner & the straight lines from their middle points to the center.
+    /// The curves are given in the order around the loop, and reversed if needed to connect with each other.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::Point3;
+    /// use approx::assert_relative_eq;
+    ///
+    /// let corners = (0..5)
+    ///     .map(|i| {
+    ///         let a = std::f64::consts::TAU * i as f64 / 5.;
+    ///         Point3::new(a.cos(), a.sin(), 0.)
+    ///     })
+    ///     .collect::<Vec<_>>();
+    /// let boundary = (0..5)
+    ///     .map(|i| NurbsCurve3D::polyline(&[corners[i], corners[(i + 1) % 5]]))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// let patches = NurbsSurface::try_fill_n_sided(&boundary).unwrap();
+    /// assert_eq!(patches.len(), 5);
+    /// for patch in patches.iter() {
+    ///     let (u0, u1) = patch.u_knots_domain();
+    ///     let (v0, v1) = patch.v_knots_domain();
+    ///     // each patch has a corner of the loop & the center
+    ///     let corner = patch.point_at(u0, v0);
+    ///     assert!(corners.iter().any(|c| (c - corner).norm() < 1e-8));
+    ///     assert_relative_eq!(patch.point_at(u1, v1), Point3::origin(), epsilon = 1e-8);
+    /// }
+    /// ```
+    pub fn try_fill_n_sided(boundary: &[NurbsCurve3D<T>]) -> anyhow::Result<Vec<Self>> {
+        let boundary = try_order_boundary_loop(boundary)?;
+
+        let halves = boundary
+            .iter()
+            .map(|c| {
+                let (start, end) = c.knots_domain();
+                c.try_trim((start + end) * T::from_f64(0.5).unwrap())
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let middles = halves
+            .iter()
+            .map(|(_, tail)| tail.point_at(tail.knots_domain().0))
+            .collect::<Vec<_>>();
+        let center = middles
+            .iter()
+            .fold(Vector3::zeros(), |acc, p| acc + p.coords)
+            / T::from_usize(middles.len()).unwrap();
+        let center = Point3::from(center);
+
+        // the patch around the corner at the start of each curve
+        let n = boundary.len();
+        (0..n)
+            .map(|i| {
+                let prev = (i + n - 1) % n;
+                let c0 = &halves[i].0;
+                let d0 = halves[prev].1.inverse();
+                let c1 = NurbsCurve3D::polyline(&[middles[prev], center]);
+                let d1 = NurbsCurve3D::polyline(&[middles[i], center]);
+                Self::try_coons_patch(c0, &c1, &d0, &d1)
+            })
+            .collect()
+    }
+
+    /// Try to fill a boundary loop with a plane trimmed by the loop
+    /// The plane is fitted to the loop, so the loop is expected to be nearly planar.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::Point3;
+    ///
+    /// let corners = [Point3::new(0., 0., 1.), Point3::new(2., 0., 1.), Point3::new(1., 2., 1.)];
+    /// let boundary = (0..3)
+    ///     .map(|i| NurbsCurve3D::polyline(&[corners[i], corners[(i + 1) % 3]]))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// let filled = NurbsSurface::try_fill_n_sided_trimmed(&boundary).unwrap();
+    /// let surface = filled.surface();
+    /// let (u, v) = surface.find_closest_parameter(&Point3::new(1., 0.5, 1.)).unwrap();
+    /// assert!(filled.contains(u, v));
+    /// let (u, v) = surface.find_closest_parameter(&Point3::new(0.1, 1.5, 1.)).unwrap();
+    /// assert!(!filled.contains(u, v));
+    /// ```
+    pub fn try_fill_n_sided_trimmed(
+        boundary: &[NurbsCurve3D<T>],
+    ) -> anyhow::Result<TrimmedSurface<T>>
+    where
+        T: ArgminFloat,
+    {
+        let boundary = try_order_boundary_loop(boundary)?;
+        let points = boundary
+            .iter()
+            .flat_map(|c| {
+                let mut points = c.tessellate(None);
+                points.pop();
+                points
+            })
+            .collect::<Vec<_>>();
+        let centroid = Point3::from(
+            points
+                .iter()
+                .fold(Vector3::zeros(), |acc, p| acc + p.coords)
+                / T::from_usize(points.len()).unwrap(),
+        );
+
+        // the normal of the loop by Newell's method
+        let normal = points
+            .iter()
+            .zip(points.iter().cycle().skip(1))
+            .fold(Vector3::zeros(), |acc, (p, q)| {
+                acc + (p - centroid).cross(&(q - centroid))
+            });
+        anyhow::ensure!(
+            normal.norm() > T::default_epsilon(),
+            "The boundary loop encloses no area"
+        );
+        let normal = normal.normalize();
+        let x = (points[0] - centroid).normalize();
+        let x = (x - normal * normal.dot(&x)).normalize();
+        let y = normal.cross(&x);
+
+        let projected = points
+            .iter()
+            .map(|p| {
+                let d = p - centroid;
+                Vector2::new(d.dot(&x), d.dot(&y))
+            })
+            .collect::<Vec<_>>();
+        let (min, max) = projected
+            .iter()
+            .fold((projected[0], projected[0]), |(min, max), q| {
+                (min.inf(q), max.sup(q))
+            });
+        let margin = (max - min) * T::from_f64(0.05).unwrap();
+        let (min, size) = (min - margin, max - min + margin * T::from_f64(2.).unwrap());
+        let origin = centroid + x * min.x + y * min.y;
+        let line = NurbsCurve3D::polyline(&[origin, origin + x * size.x]);
+        let plane = Self::extrude(&line, &(y * size.y));
+
+        let curves = boundary
+            .into_iter()
+            .map(TrimCurve::from)
+            .collect::<Vec<_>>();
+        plane.try_trim(&curves, TrimSide::Inside)
+    }
+
     /// Try to sweep a profile curve along a rail curve to create a surface
     /// # Example
     /// ```
     /// use curvo::prelude::*;
-    /// use nalgebra::{Point3, Translation3};
+    /// use nalgebra::{Point3, Translation3};
+    ///
+    /// // Create a collection of curves
+    /// let points: Vec<Point3<f64>> = vec![
+    ///     Point3::new(-1.0, -1.0, 0.),
+    ///     Point3::new(1.0, -1.0, 0.),
+    ///     Point3::new(1.0, 1.0, 0.),
+    ///     Point3::new(-1.0, 1.0, 0.),
+    /// ];
+    /// let profile = NurbsCurve3D::try_interpolate(&points, 3).unwrap();
+    ///
+    /// let points: Vec<Point3<f64>> = vec![
+    ///     Point3::new(1.0, 0., 0.),
+    ///     Point3::new(0.0, -1., 1.),
+    ///     Point3::new(-1.0, 0., 2.),
+    ///     Point3::new(0.0, 1., 3.),
+    /// ];
+    /// let rail= NurbsCurve3D::try_interpolate(&points, 3).unwrap();
+    ///
+    /// // Sweep the profile curve along the rail curve to create a NURBS surface
+    /// let swept = NurbsSurface::try_sweep(&profile, &rail, Some(3));
+    /// assert!(swept.is_ok());
+    /// ```
+    pub fn try_sweep(
+        profile: &NurbsCurve3D<T>,
+        rail: &NurbsCurve3D<T>,
+        degree_v: Option<usize>,
+    ) -> anyhow::Result<Self> {
+        let (start, end) = rail.knots_domain();
+        let samples = rail.control_points().len() * 2;
+        let span = (end - start) / T::from_usize(samples - 1).unwrap();
+
+        let parameters: Vec<_> = (0..samples)
+            .map(|i| start + T::from_usize(i).unwrap() * span)
+            .collect();
+
+        let frames = rail.compute_frenet_frames(&parameters);
+        let curves: Vec<_> = frames
+            .iter()
+            .map(|frame| {
+                let transform = frame.matrix();
+                profile.transformed(&transform.into())
+            })
+            .collect();
+
+        Self::try_loft(&curves, degree_v)
+    }
+
+    /// Try to sweep a profile curve along a rail curve oriented by the given frames
+    /// The profile is defined in the local coordinates of the frame, where the z axis is the tangent & the y axis is the normal of the rail.
+    /// The frames are sampled along the rail & the transformed profiles are lofted along the v direction.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let profile = NurbsCurve3D::try_circle(&Point3::origin(), &Vector3::x(), &Vector3::y(), 0.1).unwrap();
+    /// let points = vec![
+    ///     Point3::new(0., 0., 0.),
+    ///     Point3::new(1., 1., 0.),
+    ///     Point3::new(2., 0., 0.),
+    ///     Point3::new(3., -1., 0.),
+    ///     Point3::new(4., 0., 0.),
+    /// ];
+    /// let rail = NurbsCurve3D::try_interpolate(&points, 3).unwrap();
+    ///
+    /// let tube = NurbsSurface::try_sweep_with_frame(&profile, &rail, Some(3), SweepFrame::RotationMinimizing).unwrap();
+    /// // the sections at the ends of the tube are centered on the ends of the rail
+    /// let (u0, u1) = tube.u_knots_domain();
+    /// let (v0, v1) = tube.v_knots_domain();
+    /// let (start, end) = rail.knots_domain();
+    /// for i in 0..8 {
+    ///     let u = u0 + (u1 - u0) * i as f64 / 8.;
+    ///     assert_relative_eq!((tube.point_at(u, v0) - rail.point_at(start)).norm(), 0.1, epsilon = 1e-6);
+    ///     assert_relative_eq!((tube.point_at(u, v1) - rail.point_at(end)).norm(), 0.1, epsilon = 1e-6);
+    /// }
+    ///
+    /// // the Frenet frames are undefined on the straight rail
+    /// let line = NurbsCurve3D::polyline(&[Point3::origin(), Point3::new(0., 0., 1.)]);
+    /// assert!(NurbsSurface::try_sweep_with_frame(&profile, &line, None, SweepFrame::Frenet).is_err());
+    /// ```
+    pub fn try_sweep_with_frame(
+        profile: &NurbsCurve3D<T>,
+        rail: &NurbsCurve3D<T>,
+        degree_v: Option<usize>,
+        frame: SweepFrame,
+    ) -> anyhow::Result<Self> {
+        let options = SweepOptions {
+            frame,
+            degree_v,
+            ..Default::default()
+        };
+        Self::try_sweep_with_options(profile, rail, &options)
+    }
+
+    /// Try to sweep a profile curve along a rail curve with the scale & the twist laws
+    /// The profile is scaled in its local xy plane & rotated around the tangent of the rail at each section.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Const, Point1, Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let profile = NurbsCurve3D::polyline(&[Point3::new(-1., 0., 0.), Point3::new(1., 0., 0.)]);
+    /// let rail = NurbsCurve3D::polyline(&[Point3::origin(), Point3::new(0., 0., 4.)]);
+    ///
+    /// // taper to the half size & twist by 90 degrees
+    /// let scale = NurbsCurve::<f64, Const<2>>::polyline(&[Point1::new(1.), Point1::new(0.5)]);
+    /// let twist = NurbsCurve::<f64, Const<2>>::polyline(&[Point1::new(0.), Point1::new(std::f64::consts::FRAC_PI_2)]);
+    /// let options = SweepOptions::default()
+    ///     .with_degree_v(1)
+    ///     .with_samples(9)
+    ///     .with_scale(scale)
+    ///     .with_twist(twist);
+    /// let blade = NurbsSurface::try_sweep_with_options(&profile, &rail, &options).unwrap();
+    ///
+    /// let (u0, u1) = blade.u_knots_domain();
+    /// let (v0, v1) = blade.v_knots_domain();
+    /// let start = blade.point_at(u1, v0) - blade.point_at(u0, v0);
+    /// let end = blade.point_at(u1, v1) - blade.point_at(u0, v1);
+    /// assert_relative_eq!(start.norm(), 2., epsilon = 1e-8);
+    /// assert_relative_eq!(end.norm(), 1., epsilon = 1e-8);
+    /// // the end section is perpendicular to the start section
+    /// assert_relative_eq!(start.dot(&end), 0., epsilon = 1e-8);
+    /// ```
+    pub fn try_sweep_with_options(
+        profile: &NurbsCurve3D<T>,
+        rail: &NurbsCurve3D<T>,
+        options: &SweepOptions<T>,
+    ) -> anyhow::Result<Self> {
+        let (start, end) = rail.knots_domain();
+        let samples = options
+            .samples
+            .unwrap_or(rail.control_points().len() * 2)
+            .max(2);
+        let span = (end - start) / T::from_usize(samples - 1).unwrap();
+
+        let parameters: Vec<_> = (0..samples)
+            .map(|i| start + T::from_usize(i).unwrap() * span)
+            .collect();
+
+        let frames = match options.frame {
+            SweepFrame::RotationMinimizing => rail.compute_rotation_minimizing_frames(&parameters),
+            SweepFrame::Frenet => rail
+                .try_frenet_frames(&parameters)?
+                .into_iter()
+                .map(|f| f.frame().clone())
+                .collect(),
+        };
+
+        // evaluate the law at the same ratio of its domain as the parameter on the rail
+        let law = |law: &Option<NurbsCurve<T, Const<2>>>, t: T, default: T| {
+            law.as_ref().map_or(default, |law| {
+                let (l0, l1) = law.knots_domain();
+                law.point_at(l0 + (l1 - l0) * (t - start) / (end - start)).x
+            })
+        };
+
+        let curves: Vec<_> = frames
+            .iter()
+            .zip(parameters.iter())
+            .map(|(frame, t)| {
+                let scale = law(&options.scale, *t, T::one());
+                let twist = law(&options.twist, *t, T::zero());
+                let local = Matrix4::new_rotation(Vector3::z() * twist)
+                    * Matrix4::new_nonuniform_scaling(&Vector3::new(scale, scale, T::one()));
+                let transform: Matrix4<T> = frame.matrix().into();
+                profile.transformed(&(transform * local))
+            })
+            .collect();
+
+        Self::try_loft(&curves, options.degree_v)
+    }
+
+    /// Try to create a tube surface of the constant radius around a rail curve
+    /// The sections are exact circles perpendicular to the rail, which are rational in the u direction.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::Point3;
+    /// use approx::assert_relative_eq;
+    ///
+    /// let rail = NurbsCurve3D::try_interpolate(&[
+    ///     Point3::new(0., 0., 0.),
+    ///     Point3::new(1., 1., 1.),
+    ///     Point3::new(2., 0., 2.),
+    /// ], 2).unwrap();
+    /// let pipe = NurbsSurface::try_pipe(&rail, 0.25).unwrap();
+    /// let (u0, u1) = pipe.u_knots_domain();
+    /// let (v0, _) = pipe.v_knots_domain();
+    /// for i in 0..8 {
+    ///     let u = u0 + (u1 - u0) * i as f64 / 8.;
+    ///     assert_relative_eq!((pipe.point_at(u, v0) - Point3::origin()).norm(), 0.25, epsilon = 1e-8);
+    /// }
+    /// ```
+    pub fn try_pipe(rail: &NurbsCurve3D<T>, radius: T) -> anyhow::Result<Self> {
+        anyhow::ensure!(radius > T::zero(), "The radius must be greater than zero");
+        let profile =
+            NurbsCurve3D::try_circle(&Point3::origin(), &Vector3::x(), &Vector3::y(), radius)?;
+        Self::try_sweep_with_options(&profile, rail, &SweepOptions::default())
+    }
+
+    /// Try to create a tube surface around a rail curve with the radius given by a scalar curve over the domain of the rail
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Const, Point1, Point3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let rail = NurbsCurve3D::polyline(&[Point3::origin(), Point3::new(0., 0., 2.)]);
+    /// let radius = NurbsCurve::<f64, Const<2>>::polyline(&[Point1::new(1.), Point1::new(0.5)]);
+    /// let pipe = NurbsSurface::try_pipe_with_radius_law(&rail, &radius).unwrap();
+    /// let (u0, _) = pipe.u_knots_domain();
+    /// let (v0, v1) = pipe.v_knots_domain();
+    /// assert_relative_eq!(pipe.point_at(u0, v0).coords.xy().norm(), 1., epsilon = 1e-8);
+    /// assert_relative_eq!(pipe.point_at(u0, v1).coords.xy().norm(), 0.5, epsilon = 1e-8);
+    /// ```
+    pub fn try_pipe_with_radius_law(
+        rail: &NurbsCurve3D<T>,
+        radius: &NurbsCurve<T, Const<2>>,
+    ) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            radius
+                .dehomogenized_control_points()
+                .iter()
+                .all(|p| p.x > T::zero()),
+            "The radius must be greater than zero"
+        );
+        let profile =
+            NurbsCurve3D::try_circle(&Point3::origin(), &Vector3::x(), &Vector3::y(), T::one())?;
+        let options = SweepOptions::default().with_scale(radius.clone());
+        Self::try_sweep_with_options(&profile, rail, &options)
+    }
+
+    /// Try to sweep a profile curve along two rail curves to create a surface
+    /// The start & the end of the profile are placed on the first & the second rail at each section,
+    /// and the profile is oriented by the chord between the rails & the average tangent of the rails.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::Point3;
+    /// use approx::assert_relative_eq;
     ///
-    /// // Create a collection of curves
-    /// let points: Vec<Point3<f64>> = vec![
-    ///     Point3::new(-1.0, -1.0, 0.),
-    ///     Point3::new(1.0, -1.0, 0.),
-    ///     Point3::new(1.0, 1.0, 0.),
-    ///     Point3::new(-1.0, 1.0, 0.),
-    /// ];
-    /// let profile = NurbsCurve3D::try_interpolate(&points, 3).unwrap();
+    /// let profile = NurbsCurve3D::try_interpolate(&[
+    ///     Point3::new(0., 0., 0.),
+    ///     Point3::new(1., 1., 0.),
+    ///     Point3::new(2., 0., 0.),
+    /// ], 2).unwrap();
+    /// let rail0 = NurbsCurve3D::polyline(&[Point3::new(0., 0., 0.), Point3::new(0., 0., 4.)]);
+    /// let rail1 = NurbsCurve3D::polyline(&[Point3::new(2., 0., 0.), Point3::new(3., 0., 4.)]);
     ///
-    /// let points: Vec<Point3<f64>> = vec![
-    ///     Point3::new(1.0, 0., 0.),
-    ///     Point3::new(0.0, -1., 1.),
-    ///     Point3::new(-1.0, 0., 2.),
-    ///     Point3::new(0.0, 1., 3.),
-    /// ];
-    /// let rail= NurbsCurve3D::try_interpolate(&points, 3).unwrap();
+    /// let uniform = NurbsSurface::try_birail(&profile, &rail0, &rail1, BirailScaling::Uniform, None).unwrap();
+    /// let (u0, u1) = uniform.u_knots_domain();
+    /// let (_, v1) = uniform.v_knots_domain();
+    /// assert_relative_eq!(uniform.point_at(u0, v1), Point3::new(0., 0., 4.), epsilon = 1e-8);
+    /// assert_relative_eq!(uniform.point_at(u1, v1), Point3::new(3., 0., 4.), epsilon = 1e-8);
+    /// assert_relative_eq!(uniform.point_at((u0 + u1) / 2., v1), Point3::new(1.5, 1.5, 4.), epsilon = 1e-8);
     ///
-    /// // Sweep the profile curve along the rail curve to create a NURBS surface
-    /// let swept = NurbsSurface::try_sweep(&profile, &rail, Some(3));
-    /// assert!(swept.is_ok());
+    /// let height = NurbsSurface::try_birail(&profile, &rail0, &rail1, BirailScaling::MaintainHeight, None).unwrap();
+    /// assert_relative_eq!(height.point_at((u0 + u1) / 2., v1), Point3::new(1.5, 1., 4.), epsilon = 1e-8);
     /// ```
-    pub fn try_sweep(
+    pub fn try_birail(
         profile: &NurbsCurve3D<T>,
-        rail: &NurbsCurve3D<T>,
+        rail0: &NurbsCurve3D<T>,
+        rail1: &NurbsCurve3D<T>,
+        scaling: BirailScaling,
         degree_v: Option<usize>,
     ) -> anyhow::Result<Self> {
-        let (start, end) = rail.knots_domain();
-        let samples = rail.control_points().len() * 2;
-        let span = (end - start) / T::from_usize(samples - 1).unwrap();
+        let (p0, p1) = profile.knots_domain();
+        let (p0, p1) = (profile.point_at(p0), profile.point_at(p1));
+        let width = (p1 - p0).norm();
+        anyhow::ensure!(
+            width > T::default_epsilon(),
+            "The profile curve must have distinct end points"
+        );
+
+        let samples = rail0
+            .control_points()
+            .len()
+            .max(rail1.control_points().len())
+            * 2;
+        let domains = [rail0.knots_domain(), rail1.knots_domain()];
+        let at = |(start, end): (T, T), i: usize| {
+            start + (end - start) * T::from_usize(i).unwrap() / T::from_usize(samples - 1).unwrap()
+        };
 
-        let parameters: Vec<_> = (0..samples)
-            .map(|i| start + T::from_usize(i).unwrap() * span)
-            .collect();
+        // the orthonormal frame spanned by the chord & the average tangent of the rails
+        let frame = |a: &Point3<T>, b: &Point3<T>, tangent: Vector3<T>| {
+            let x = (b - a).normalize();
+            let z = (tangent - x * tangent.dot(&x)).normalize();
+            let y = z.cross(&x);
+            Matrix3::from_columns(&[x, y, z])
+        };
+        let tangent = |i: usize| {
+            rail0.tangent_at(at(domains[0], i)).normalize()
+                + rail1.tangent_at(at(domains[1], i)).normalize()
+        };
+        let local = frame(&p0, &p1, tangent(0)).transpose();
 
-        let frames = rail.compute_frenet_frames(&parameters);
-        let curves: Vec<_> = frames
-            .iter()
-            .map(|frame| {
-                let transform = frame.matrix();
-                profile.transformed(&transform.into())
+        let curves = (0..samples)
+            .map(|i| {
+                let a = rail0.point_at(at(domains[0], i));
+                let b = rail1.point_at(at(domains[1], i));
+                let ratio = (b - a).norm() / width;
+                anyhow::ensure!(
+                    ratio > T::default_epsilon(),
+                    "The rails must not intersect each other"
+                );
+                let height = match scaling {
+                    BirailScaling::Uniform => ratio,
+                    BirailScaling::MaintainHeight => T::one(),
+                };
+                let scale = Matrix3::from_diagonal(&Vector3::new(ratio, height, height));
+                let rotation = frame(&a, &b, tangent(i)) * scale * local;
+                let transform = Matrix4::new_translation(&a.coords)
+                    * rotation.to_homogeneous()
+                    * Matrix4::new_translation(&-p0.coords);
+                Ok(profile.transformed(&transform))
             })
-            .collect();
+            .collect::<anyhow::Result<Vec<_>>>()?;
 
         Self::try_loft(&curves, degree_v)
     }
@@ -1078,84 +3152,881 @@ impl<T: FloatingPoint> NurbsSurface3D<T> {
                 y *= T::one() / r;
             }
 
-            // control_points[0][j]
-            let mut p0 = prof_points[j];
-            control_points[0][j].x = p0.x;
-            control_points[0][j].y = p0.y;
-            control_points[0][j].z = p0.z;
-            control_points[0][j].w = prof_weights[j];
+            // control_points[0][j]
+            let mut p0 = prof_points[j];
+            control_points[0][j].x = p0.x;
+            control_points[0][j].y = p0.y;
+            control_points[0][j].z = p0.z;
+            control_points[0][j].w = prof_weights[j];
+
+            let mut t0 = y;
+            let mut index = 0;
+            for i in 1..=narcs {
+                let p2 = if r <= T::default_epsilon() {
+                    o
+                } else {
+                    o + x * cosines[i] * r + y * sines[i] * r
+                };
+
+                let k = index + 2;
+                control_points[k][j].x = p2.x;
+                control_points[k][j].y = p2.y;
+                control_points[k][j].z = p2.z;
+                control_points[k][j].w = prof_weights[j];
+
+                let t2 = y * cosines[i] - x * sines[i];
+
+                let l = index + 1;
+                if r <= T::default_epsilon() {
+                    control_points[l][j].x = o.x;
+                    control_points[l][j].y = o.y;
+                    control_points[l][j].z = o.z;
+                } else {
+                    let nt0 = t0.normalize();
+                    let nt2 = t2.normalize();
+                    let r0 = Ray::new(p0, nt0);
+                    let r1 = Ray::new(p2, nt2);
+                    let intersection = r0
+                        .find_intersection(&r1)
+                        .ok_or(anyhow::anyhow!("No intersection between rays"))?;
+
+                    let intersected = intersection.intersection0.0;
+                    control_points[l][j].x = intersected.x;
+                    control_points[l][j].y = intersected.y;
+                    control_points[l][j].z = intersected.z;
+                }
+
+                control_points[l][j].w = wm * prof_weights[j];
+
+                index += 2;
+
+                if i < narcs {
+                    p0 = p2;
+                    t0 = t2;
+                }
+            }
+        }
+
+        // Scale the control points by their weights to make them rational
+        control_points.iter_mut().for_each(|row| {
+            row.iter_mut().for_each(|p| {
+                let w = p.w;
+                p.x *= w;
+                p.y *= w;
+                p.z *= w;
+            });
+        });
+
+        Ok(Self {
+            control_points,
+            u_degree: 2,
+            v_degree: profile.degree(),
+            u_knots: KnotVector::new(u_knots),
+            v_knots: profile.knots().clone(),
+        })
+    }
+
+    /// Try to revolve a profile curve around an axis from the start angle to the end angle
+    /// The angles are measured counterclockwise around the axis from the position of the profile, and the revolution is split into the minimal number of rational arc spans up to a quarter turn each.
+    /// For a full revolution, the start angle locates the seam of the surface.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    /// use std::f64::consts::{FRAC_PI_2, PI, TAU};
+    ///
+    /// let profile = NurbsCurve3D::polyline(&[Point3::new(1., 0., 0.), Point3::new(1., 0., 1.)]);
+    ///
+    /// // a quarter of a cylinder from 90 to 180 degrees
+    /// let partial = NurbsSurface::try_revolve_between(&profile, &Point3::origin(), &Vector3::z(), FRAC_PI_2, PI).unwrap();
+    /// let (u0, u1) = partial.u_knots_domain();
+    /// assert_eq!(partial.control_points().len(), 3);
+    /// assert_relative_eq!(partial.point_at(u0, 0.), Point3::new(0., 1., 0.), epsilon = 1e-10);
+    /// assert_relative_eq!(partial.point_at(u1, 0.), Point3::new(-1., 0., 0.), epsilon = 1e-10);
+    ///
+    /// // a full cylinder with the seam at 180 degrees
+    /// let full = NurbsSurface::try_revolve_between(&profile, &Point3::origin(), &Vector3::z(), PI, PI + TAU).unwrap();
+    /// assert_relative_eq!(full.point_at(u0, 0.5), Point3::new(-1., 0., 0.5), epsilon = 1e-10);
+    /// assert_relative_eq!(full.point_at(u1, 0.5), Point3::new(-1., 0., 0.5), epsilon = 1e-10);
+    /// ```
+    pub fn try_revolve_between(
+        profile: &NurbsCurve3D<T>,
+        center: &Point3<T>,
+        axis: &Vector3<T>,
+        start_angle: T,
+        end_angle: T,
+    ) -> anyhow::Result<Self> {
+        let theta = end_angle - start_angle;
+        anyhow::ensure!(
+            theta > T::zero() && theta <= T::two_pi() + T::default_epsilon(),
+            "The end angle must be greater than the start angle within a full turn"
+        );
+        let axis = UnitVector3::new_normalize(*axis);
+        let rotation = Isometry3::rotation_wrt_point(
+            UnitQuaternion::from_axis_angle(&axis, start_angle),
+            *center,
+        );
+        let rotated = profile.transformed(&rotation.to_homogeneous());
+        Self::try_revolve(&rotated, center, &axis, theta.min(T::two_pi()))
+    }
+
+    /// Try to create a sphere by revolving a half circle around the axis through the center
+    /// The u direction goes around the axis counterclockwise, the v direction goes from the south pole to the north pole, and the normals face outward.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let center = Point3::new(1., 2., 3.);
+    /// let sphere = NurbsSurface::try_sphere(&center, &Vector3::z(), 2.).unwrap();
+    /// let (u0, u1) = sphere.u_knots_domain();
+    /// let (v0, v1) = sphere.v_knots_domain();
+    /// for i in 0..=4 {
+    ///     for j in 0..=4 {
+    ///         let u = u0 + (u1 - u0) * i as f64 / 4.;
+    ///         let v = v0 + (v1 - v0) * (j as f64 + 0.5) / 5.;
+    ///         let p = sphere.point_at(u, v);
+    ///         assert_relative_eq!((p - center).norm(), 2., epsilon = 1e-10);
+    ///         assert!(sphere.normal_at(u, v).dot(&(p - center)) > 0.);
+    ///     }
+    /// }
+    /// assert_relative_eq!(sphere.point_at(u0, v1), Point3::new(1., 2., 5.), epsilon = 1e-10);
+    /// ```
+    pub fn try_sphere(center: &Point3<T>, axis: &Vector3<T>, radius: T) -> anyhow::Result<Self> {
+        let axis = axis.normalize();
+        let x = initial_normal(&axis);
+        let half_pi = T::frac_pi_2();
+        let profile = NurbsCurve3D::try_arc(center, &x, &axis, radius, -half_pi, half_pi)?;
+        Self::try_revolve(&profile, center, &axis, T::two_pi())
+    }
+
+    /// Try to create the side of a cylinder standing on the base center along the axis
+    /// The length of the axis is the height of the cylinder.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let cylinder = NurbsSurface::try_cylinder(&Point3::origin(), &(Vector3::z() * 3.), 0.5).unwrap();
+    /// let (u0, u1) = cylinder.u_knots_domain();
+    /// let (v0, v1) = cylinder.v_knots_domain();
+    /// for i in 0..=8 {
+    ///     let u = u0 + (u1 - u0) * i as f64 / 8.;
+    ///     let p = cylinder.point_at(u, v1);
+    ///     assert_relative_eq!(p.z, 3., epsilon = 1e-10);
+    ///     assert_relative_eq!(p.coords.xy().norm(), 0.5, epsilon = 1e-10);
+    ///     assert!(cylinder.normal_at(u, (v0 + v1) / 2.).xy().dot(&p.coords.xy()) > 0.);
+    /// }
+    /// ```
+    pub fn try_cylinder(
+        base_center: &Point3<T>,
+        axis: &Vector3<T>,
+        radius: T,
+    ) -> anyhow::Result<Self> {
+        Self::try_cone(base_center, axis, radius, radius)
+    }
+
+    /// Try to create the side of a cone standing on the base center along the axis
+    /// The length of the axis is the height of the cone, and the top radius of zero makes the apex.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let cone = NurbsSurface::try_cone(&Point3::origin(), &(Vector3::z() * 2.), 1., 0.).unwrap();
+    /// let (u0, u1) = cone.u_knots_domain();
+    /// let (v0, v1) = cone.v_knots_domain();
+    /// assert_relative_eq!(cone.point_at(u0, v1), Point3::new(0., 0., 2.), epsilon = 1e-10);
+    /// for i in 0..=8 {
+    ///     let u = u0 + (u1 - u0) * i as f64 / 8.;
+    ///     let p = cone.point_at(u, (v0 + v1) / 2.);
+    ///     assert_relative_eq!(p.z, 1., epsilon = 1e-10);
+    ///     assert_relative_eq!(p.coords.xy().norm(), 0.5, epsilon = 1e-10);
+    /// }
+    /// ```
+    pub fn try_cone(
+        base_center: &Point3<T>,
+        axis: &Vector3<T>,
+        base_radius: T,
+        top_radius: T,
+    ) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            axis.norm() > T::default_epsilon(),
+            "The axis must not be zero"
+        );
+        anyhow::ensure!(
+            base_radius >= T::zero()
+                && top_radius >= T::zero()
+                && base_radius + top_radius > T::zero(),
+            "The radii must not be negative and one of them must be positive"
+        );
+        let direction = axis.normalize();
+        let x = initial_normal(&direction);
+        let profile = NurbsCurve3D::polyline(&[
+            base_center + x * base_radius,
+            base_center + axis + x * top_radius,
+        ]);
+        Self::try_revolve(&profile, base_center, &direction, T::two_pi())
+    }
+
+    /// Try to create a torus by revolving a circle of the minor radius around the axis through the center
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let torus = NurbsSurface::try_torus(&Point3::origin(), &Vector3::z(), 2., 0.5).unwrap();
+    /// let (u0, u1) = torus.u_knots_domain();
+    /// let (v0, v1) = torus.v_knots_domain();
+    /// for i in 0..=6 {
+    ///     for j in 0..=6 {
+    ///         let u = u0 + (u1 - u0) * i as f64 / 6.;
+    ///         let v = v0 + (v1 - v0) * j as f64 / 6.;
+    ///         let p = torus.point_at(u, v);
+    ///         // the distance from the circle of the major radius
+    ///         let ring = p.coords.xy().norm() - 2.;
+    ///         assert_relative_eq!((ring * ring + p.z * p.z).sqrt(), 0.5, epsilon = 1e-10);
+    ///     }
+    /// }
+    /// ```
+    pub fn try_torus(
+        center: &Point3<T>,
+        axis: &Vector3<T>,
+        major_radius: T,
+        minor_radius: T,
+    ) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            major_radius > minor_radius && minor_radius > T::zero(),
+            "The major radius must be greater than the minor radius which must be positive"
+        );
+        let axis = axis.normalize();
+        let x = initial_normal(&axis);
+        let profile =
+            NurbsCurve3D::try_circle(&(center + x * major_radius), &x, &axis, minor_radius)?;
+        Self::try_revolve(&profile, center, &axis, T::two_pi())
+    }
+
+    /// Try to create the six faces of a box spanned by the edge vectors from the origin corner
+    /// The faces are ordered as -z, +z, -y, +y, -x, +x for the right-handed edge vectors, and their normals face outward.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    ///
+    /// let faces = NurbsSurface::try_box(&Point3::origin(), &Vector3::x(), &(Vector3::y() * 2.), &(Vector3::z() * 3.)).unwrap();
+    /// let center = Point3::new(0.5, 1., 1.5);
+    /// for face in faces.iter() {
+    ///     let (u0, u1) = face.u_knots_domain();
+    ///     let (v0, v1) = face.v_knots_domain();
+    ///     let (u, v) = ((u0 + u1) / 2., (v0 + v1) / 2.);
+    ///     assert!(face.normal_at(u, v).dot(&(face.point_at(u, v) - center)) > 0.);
+    /// }
+    ///
+    /// let shell = Shell::try_from_surfaces(&faces, 1e-6).unwrap();
+    /// assert!(shell.is_closed());
+    /// ```
+    pub fn try_box(
+        origin: &Point3<T>,
+        x: &Vector3<T>,
+        y: &Vector3<T>,
+        z: &Vector3<T>,
+    ) -> anyhow::Result<[Self; 6]> {
+        let volume = x.cross(y).dot(z);
+        anyhow::ensure!(
+            volume.abs() > T::default_epsilon(),
+            "The edge vectors must span a volume"
+        );
+        // swap the edge vectors of the left-handed system to keep the normals outward
+        let (x, y) = if volume > T::zero() { (x, y) } else { (y, x) };
+        // the normal of the extruded face is the direction of the line crossed with the extrusion
+        let face = |o: Point3<T>, line: &Vector3<T>, extrusion: &Vector3<T>| {
+            Self::extrude(&NurbsCurve3D::polyline(&[o, o + line]), extrusion)
+        };
+        let o = *origin;
+        Ok([
+            face(o, y, x),
+            face(o + z, x, y),
+            face(o, x, z),
+            face(o + y, z, x),
+            face(o, z, y),
+            face(o + x, y, z),
+        ])
+    }
+
+    /// Section the surface by the plane and return the intersection curves
+    /// The level set of the signed distance to the plane is traced in the parameter space and interpolated by NURBS curves.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector2, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// // A cylinder with radius 1 along the z-axis
+    /// let circle = NurbsCurve3D::try_circle(&Point3::origin(), &Vector3::x(), &Vector3::y(), 1.).unwrap();
+    /// let cylinder = NurbsSurface::extrude(&circle, &(Vector3::z() * 2.));
+    ///
+    /// // A horizontal section is a closed circle
+    /// let sections = cylinder.section(&Plane::new(Point3::new(0., 0., 0.5), Vector3::z())).unwrap();
+    /// assert_eq!(sections.len(), 1);
+    /// let section = &sections[0];
+    /// let (start, end) = section.knots_domain();
+    /// assert_relative_eq!(section.point_at(start), section.point_at(end), epsilon = 1e-8);
+    /// for i in 0..=16 {
+    ///     let p = section.point_at(start + (end - start) * (i as f64) / 16.);
+    ///     assert_relative_eq!(p.z, 0.5, epsilon = 1e-8);
+    ///     assert_relative_eq!(Vector2::new(p.x, p.y).norm(), 1., epsilon = 1e-3);
+    /// }
+    ///
+    /// // A vertical section through the axis is a pair of lines
+    /// let sections = cylinder.section(&Plane::new(Point3::origin(), Vector3::x())).unwrap();
+    /// assert_eq!(sections.len(), 2);
+    ///
+    /// // The plane apart from the surface has no section
+    /// let sections = cylinder.section(&Plane::new(Point3::new(0., 0., 3.), Vector3::z())).unwrap();
+    /// assert!(sections.is_empty());
+    /// ```
+    pub fn section(&self, plane: &Plane<T>) -> anyhow::Result<Vec<NurbsCurve3D<T>>> {
+        let bb: BoundingBox<T, Const<3>> = self.into();
+        let corners = bb.corners();
+        let above = corners
+            .iter()
+            .filter(|p| plane.signed_distance(p) > T::zero())
+            .count();
+        if above == 0 || above == corners.len() {
+            return Ok(vec![]);
+        }
+
+        let tracer =
+            SurfaceLevelSetTracer::new(self, |u, v| plane.signed_distance(&self.point_at(u, v)));
+        let tolerance = bb.size().norm() * T::from_f64(1e-6).unwrap();
+        level_set_curves(self, tracer.trace(), tolerance)
+    }
+
+    /// Section the surface by the parallel planes at the offsets along the direction
+    /// The planes are placed at `origin + direction * offset` for each offset (the direction is normalized).
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let circle = NurbsCurve3D::try_circle(&Point3::origin(), &Vector3::x(), &Vector3::y(), 1.).unwrap();
+    /// let cylinder = NurbsSurface::extrude(&circle, &(Vector3::z() * 2.));
+    ///
+    /// let contours = cylinder.contour(&Vector3::z(), &[0.5, 1.0, 1.5, 2.5]).unwrap();
+    /// assert_eq!(contours.len(), 4);
+    /// for contour in contours.iter().take(3) {
+    ///     assert_eq!(contour.curves().len(), 1);
+    ///     let curve = &contour.curves()[0];
+    ///     let (start, end) = curve.knots_domain();
+    ///     assert_relative_eq!(curve.point_at(start).z, contour.offset(), epsilon = 1e-8);
+    ///
+    ///     // The loop is oriented counter-clockwise around the direction
+    ///     let p0 = curve.point_at(start);
+    ///     let p1 = curve.point_at(start + (end - start) * 0.25);
+    ///     assert!(p0.coords.cross(&p1.coords).z > 0.);
+    /// }
+    /// assert!(contours[3].curves().is_empty());
+    /// ```
+    pub fn contour(
+        &self,
+        direction: &Vector3<T>,
+        offsets: &[T],
+    ) -> anyhow::Result<Vec<SurfaceContour<T>>> {
+        let direction = direction.normalize();
+        offsets
+            .iter()
+            .map(|offset| {
+                let plane = Plane::new(Point3::origin() + direction * *offset, direction);
+                let curves = self.section(&plane)?;
+                Ok(SurfaceContour::new(
+                    *offset,
+                    plane.clone(),
+                    order_contour_curves(&plane, curves),
+                ))
+            })
+            .collect()
+    }
+
+    /// Section the surface by the parallel planes with the spacing along the direction
+    /// The levels are placed at the multiples of the spacing covering the extent of the surface along the direction.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    ///
+    /// let circle = NurbsCurve3D::try_circle(&Point3::origin(), &Vector3::x(), &Vector3::y(), 1.).unwrap();
+    /// let cylinder = NurbsSurface::extrude(&circle, &(Vector3::z() * 2.));
+    ///
+    /// let contours = cylinder.contour_by_spacing(&Vector3::z(), 0.3).unwrap();
+    /// assert_eq!(contours.len(), 6);
+    /// assert!(contours.iter().all(|c| c.curves().len() == 1));
+    /// ```
+    pub fn contour_by_spacing(
+        &self,
+        direction: &Vector3<T>,
+        spacing: T,
+    ) -> anyhow::Result<Vec<SurfaceContour<T>>> {
+        anyhow::ensure!(spacing > T::zero(), "The spacing must be positive");
+        let direction = direction.normalize();
+        let (min, max) = self
+            .dehomogenized_control_points()
+            .iter()
+            .flatten()
+            .map(|p| p.coords.dot(&direction))
+            .fold(
+                (
+                    <T as RealField>::max_value().unwrap(),
+                    <T as RealField>::min_value().unwrap(),
+                ),
+                |(min, max), d| (min.min(d), max.max(d)),
+            );
+        let start = (min / spacing).floor().to_i64().unwrap();
+        let end = (max / spacing).ceil().to_i64().unwrap();
+        let offsets: Vec<_> = (start..=end)
+            .map(|i| T::from_i64(i).unwrap() * spacing)
+            .filter(|o| min < *o && *o < max)
+            .collect();
+        let contours = self.contour(&direction, &offsets)?;
+        Ok(contours
+            .into_iter()
+            .filter(|c| !c.curves().is_empty())
+            .collect())
+    }
+
+    /// Extract the silhouette curves of the surface viewed along the direction,
+    /// where the normal of the surface is perpendicular to the view direction (parallel projection).
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let circle = NurbsCurve3D::try_circle(&Point3::origin(), &Vector3::x(), &Vector3::y(), 1.).unwrap();
+    /// let cylinder = NurbsSurface::extrude(&circle, &(Vector3::z() * 2.));
+    ///
+    /// // The silhouettes of the cylinder viewed along the x-axis are the lines at y = -1 & y = 1
+    /// let silhouettes = cylinder.silhouette(&Vector3::x()).unwrap();
+    /// assert_eq!(silhouettes.len(), 2);
+    /// for silhouette in silhouettes.iter() {
+    ///     let curve = silhouette.curve();
+    ///     let (start, end) = curve.knots_domain();
+    ///     let p = curve.point_at((start + end) * 0.5);
+    ///     assert_relative_eq!(f64::abs(p.y), 1., epsilon = 1e-8);
+    ///
+    ///     // The curve in the parameter space is on the silhouette
+    ///     let (start, end) = silhouette.uv().knots_domain();
+    ///     let uv = silhouette.uv().point_at((start + end) * 0.5);
+    ///     assert_relative_eq!(cylinder.normal_at(uv.x, uv.y).normalize().x, 0., epsilon = 1e-8);
+    /// }
+    /// ```
+    pub fn silhouette(&self, direction: &Vector3<T>) -> anyhow::Result<Vec<SurfaceSilhouette<T>>> {
+        let direction = direction.normalize();
+        self.trace_silhouette(|u, v| {
+            let n = self.normal_at(u, v);
+            let norm = n.norm();
+            if norm > T::zero() {
+                n.dot(&direction) / norm
+            } else {
+                T::zero()
+            }
+        })
+    }
+
+    /// Extract the silhouette curves of the surface viewed from the camera point,
+    /// where the normal of the surface is perpendicular to the line of sight (perspective projection).
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let circle = NurbsCurve3D::try_circle(&Point3::origin(), &Vector3::x(), &Vector3::y(), 1.).unwrap();
+    /// let cylinder = NurbsSurface::extrude(&circle, &(Vector3::z() * 2.));
+    ///
+    /// // The lines of sight from the camera are tangent to the cylinder at the silhouettes
+    /// let camera = Point3::new(-2., 0., 1.);
+    /// let silhouettes = cylinder.silhouette_from_point(&camera).unwrap();
+    /// assert_eq!(silhouettes.len(), 2);
+    /// for silhouette in silhouettes.iter() {
+    ///     let curve = silhouette.curve();
+    ///     let (start, end) = curve.knots_domain();
+    ///     let p = curve.point_at((start + end) * 0.5);
+    ///     assert_relative_eq!(p.x, -0.5, epsilon = 1e-8);
+    /// }
+    /// ```
+    pub fn silhouette_from_point(
+        &self,
+        camera: &Point3<T>,
+    ) -> anyhow::Result<Vec<SurfaceSilhouette<T>>> {
+        self.trace_silhouette(|u, v| {
+            let n = self.normal_at(u, v);
+            let sight = self.point_at(u, v) - camera;
+            let norm = n.norm() * sight.norm();
+            if norm > T::zero() {
+                n.dot(&sight) / norm
+            } else {
+                T::zero()
+            }
+        })
+    }
+
+    /// Trace the level set of the field as the silhouette curves
+    fn trace_silhouette<F: Fn(T, T) -> T>(
+        &self,
+        field: F,
+    ) -> anyhow::Result<Vec<SurfaceSilhouette<T>>> {
+        let tracer = SurfaceLevelSetTracer::new(self, field);
+        Ok(level_set_curves_on_surface(self, tracer.trace())?
+            .into_iter()
+            .map(|(curve, uv, closed)| SurfaceSilhouette::new(curve, uv, closed))
+            .collect())
+    }
 
-            let mut t0 = y;
-            let mut index = 0;
-            for i in 1..=narcs {
-                let p2 = if r <= T::default_epsilon() {
-                    o
-                } else {
-                    o + x * cosines[i] * r + y * sines[i] * r
-                };
+    /// Cast a ray to the surface and find the intersections sorted by the parameter along the ray
+    /// For repeated queries to the same surface, build a `SurfaceBvh` once and use `SurfaceBvh::cast_ray` instead.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let line = NurbsCurve3D::polyline(&[Point3::new(-2., -2., 0.), Point3::new(2., -2., 0.)]);
+    /// let plane = NurbsSurface::extrude(&line, &(Vector3::y() * 4.));
+    ///
+    /// let ray = Ray::new(Point3::new(0.5, 0.5, 2.), -Vector3::z());
+    /// let hits = plane.cast_ray(&ray, None).unwrap();
+    /// assert_eq!(hits.len(), 1);
+    /// assert_relative_eq!(hits[0].point(), &Point3::new(0.5, 0.5, 0.), epsilon = 1e-6);
+    /// let (u, v) = hits[0].uv();
+    /// assert_relative_eq!(plane.point_at(u, v), Point3::new(0.5, 0.5, 0.), epsilon = 1e-6);
+    /// ```
+    pub fn cast_ray(
+        &self,
+        ray: &Ray<T, Const<3>>,
+        options: Option<CurveSurfaceIntersectionSolverOptions<T>>,
+    ) -> anyhow::Result<Vec<RaySurfaceIntersection<T>>> {
+        SurfaceBvh::try_new(self)?.cast_ray(ray, options)
+    }
 
-                let k = index + 2;
-                control_points[k][j].x = p2.x;
-                control_points[k][j].y = p2.y;
-                control_points[k][j].z = p2.z;
-                control_points[k][j].w = prof_weights[j];
+    /// Find the intersection curves with another surface by marching along the curves
+    /// * `other` - The other surface to intersect with
+    /// * `options` - Hyperparameters for the intersection solver
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point2, Point3, Vector2, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// // A cylinder with radius 1 along the z-axis
+    /// let circle = NurbsCurve3D::try_circle(&Point3::origin(), &Vector3::x(), &Vector3::y(), 1.).unwrap();
+    /// let cylinder = NurbsSurface::extrude(&circle, &(Vector3::z() * 2.));
+    ///
+    /// // A plane at z = 1
+    /// let line = NurbsCurve3D::polyline(&[Point3::new(-2., -2., 1.), Point3::new(2., -2., 1.)]);
+    /// let plane = NurbsSurface::extrude(&line, &(Vector3::y() * 4.));
+    ///
+    /// let intersections = cylinder.find_intersection(&plane, None).unwrap();
+    /// assert_eq!(intersections.len(), 1);
+    ///
+    /// let intersection = &intersections[0];
+    /// assert!(intersection.is_closed());
+    /// let (start, end) = intersection.curve().knots_domain();
+    /// for i in 0..=16 {
+    ///     let t = start + (end - start) * (i as f64) / 16.;
+    ///     let p = intersection.curve().point_at(t);
+    ///     assert_relative_eq!(p.z, 1., epsilon = 1e-4);
+    ///     assert_relative_eq!(Vector2::new(p.x, p.y).norm(), 1., epsilon = 1e-3);
+    /// }
+    /// ```
+    pub fn find_intersection(
+        &self,
+        other: &Self,
+        options: Option<SurfaceIntersectionSolverOptions<T>>,
+    ) -> anyhow::Result<Vec<SurfaceIntersection<T>>> {
+        let options = options.unwrap_or_default();
+        let div = T::from_usize(options.knot_domain_division).unwrap();
+
+        let tolerance = |s: &Self| {
+            let (u0, u1) = s.u_knots_domain();
+            let (v0, v1) = s.v_knots_domain();
+            ((u1 - u0) / div, (v1 - v0) / div)
+        };
+        let traversed = BoundingBoxTraversal::try_traverse_hierarchies(
+            SurfaceBoundingBoxTree::new(self, Some(tolerance(self))),
+            SurfaceBoundingBoxTree::new(other, Some(tolerance(other))),
+        )?;
+
+        let step = options.marching_step.unwrap_or_else(|| {
+            let sa: BoundingBox<T, Const<3>> = self.into();
+            let sb: BoundingBox<T, Const<3>> = other.into();
+            sa.size().norm().min(sb.size().norm()) / T::from_usize(64).unwrap()
+        });
+        let marcher = SurfaceIntersectionMarcher::new(
+            self,
+            other,
+            step,
+            options.minimum_distance,
+            options.max_iters,
+            options.max_marching_steps,
+        );
+
+        let inv = T::from_f64(0.5).unwrap();
+        let center = |s: &Self| {
+            let (u0, u1) = s.u_knots_domain();
+            let (v0, v1) = s.v_knots_domain();
+            Vector2::new((u0 + u1) * inv, (v0 + v1) * inv)
+        };
+        let seeds: Vec<_> = traversed
+            .into_pairs_iter()
+            .filter_map(|(a, b)| marcher.try_refine(center(a.surface()), center(b.surface())))
+            .collect();
 
-                let t2 = y * cosines[i] - x * sines[i];
+        let mut polylines: Vec<SurfaceIntersectionPolyline<T>> = vec![];
+        for seed in seeds.iter() {
+            // skip the seed lying on the curves already traced
+            if polylines
+                .iter()
+                .any(|polyline| polyline.distance(&seed.point) < marcher.step())
+            {
+                continue;
+            }
+            let polyline = marcher.trace(seed);
+            if polyline.samples.len() > 1 {
+                polylines.push(polyline);
+            }
+        }
 
-                let l = index + 1;
-                if r <= T::default_epsilon() {
-                    control_points[l][j].x = o.x;
-                    control_points[l][j].y = o.y;
-                    control_points[l][j].z = o.z;
-                } else {
-                    let nt0 = t0.normalize();
-                    let nt2 = t2.normalize();
-                    let r0 = Ray::new(p0, nt0);
-                    let r1 = Ray::new(p2, nt2);
-                    let intersection = r0
-                        .find_intersection(&r1)
-                        .ok_or(anyhow::anyhow!("No intersection between rays"))?;
+        // drop the short polylines traced from the seeds before the curves covering them (e.g. the stubs at the pole of a sphere)
+        polylines.sort_by_key(|polyline| std::cmp::Reverse(polyline.samples.len()));
+        let mut kept: Vec<SurfaceIntersectionPolyline<T>> = vec![];
+        for polyline in polylines {
+            let covered = kept.iter().any(|other| {
+                polyline
+                    .samples
+                    .iter()
+                    .all(|s| other.distance(&s.point) < marcher.step())
+            });
+            if !covered {
+                kept.push(polyline);
+            }
+        }
 
-                    let intersected = intersection.intersection0.0;
-                    control_points[l][j].x = intersected.x;
-                    control_points[l][j].y = intersected.y;
-                    control_points[l][j].z = intersected.z;
+        kept.into_iter()
+            .filter_map(|polyline| {
+                let mut samples = polyline.samples;
+                samples.dedup_by(|x, y| (x.point - y.point).norm() < options.minimum_distance);
+                // the polyline collapsed into a point contact has no curve to fit
+                (samples.len() > 1).then_some((samples, polyline.closed))
+            })
+            .map(|(mut samples, closed)| {
+                if closed {
+                    let first = samples[0].clone();
+                    let n = samples.len() - 1;
+                    match marcher.is_near(&samples[n], &first) {
+                        (true, true) => {
+                            if (samples[n].point - first.point).norm() > options.minimum_distance {
+                                samples.push(first);
+                            }
+                        }
+                        (near_a, near_b) => {
+                            // the ends meet across the seam of a periodic surface, where the curve on it stays open
+                            let last = &mut samples[n];
+                            last.point = first.point;
+                            if near_a {
+                                last.a = first.a;
+                            }
+                            if near_b {
+                                last.b = first.b;
+                            }
+                        }
+                    }
                 }
+                let degree = (samples.len() - 1).min(3);
+                let points: Vec<_> = samples.iter().map(|s| s.point).collect();
+                let a: Vec<_> = samples.iter().map(|s| s.a_uv()).collect();
+                let b: Vec<_> = samples.iter().map(|s| s.b_uv()).collect();
+                Ok(SurfaceIntersection::new(
+                    NurbsCurve3D::try_interpolate(&points, degree)?,
+                    NurbsCurve2D::try_interpolate(&a, degree)?,
+                    NurbsCurve2D::try_interpolate(&b, degree)?,
+                    closed,
+                ))
+            })
+            .collect()
+    }
 
-                control_points[l][j].w = wm * prof_weights[j];
+    /// Trim the surface by the closed loops built from the curves
+    /// The curves in 3D space are projected onto the surface, and the curves in the parameter space are used as they are.
+    /// Open curves are joined at their end points into closed loops.
+    /// The loops must not cross the seam of a closed surface.
+    /// * `curves` - The curves to build the trim loops
+    /// * `side` - The side of the loops to keep
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point2, Point3, Vector2, Vector3};
+    ///
+    /// let line = NurbsCurve3D::polyline(&[Point3::new(0., 0., 0.), Point3::new(2., 0., 0.)]);
+    /// let plane = NurbsSurface::extrude(&line, &(Vector3::y() * 2.));
+    /// let uv = |p: Point3<f64>| plane.find_closest_parameter(&p).unwrap();
+    ///
+    /// // A circle floating above the plane is projected onto it
+    /// let circle = NurbsCurve3D::try_circle(&Point3::new(1., 1., 1.), &Vector3::x(), &Vector3::y(), 0.5).unwrap();
+    /// let inside = plane.try_trim(&[circle.clone().into()], TrimSide::Inside).unwrap();
+    /// let (u, v) = uv(Point3::new(1., 1., 0.));
+    /// assert!(inside.contains(u, v));
+    /// let (u, v) = uv(Point3::new(0.1, 0.1, 0.));
+    /// assert!(!inside.contains(u, v));
+    ///
+    /// let outside = plane.try_trim(&[circle.clone().into()], TrimSide::Outside).unwrap();
+    /// let (u, v) = uv(Point3::new(1., 1., 0.));
+    /// assert!(!outside.contains(u, v));
+    /// let (u, v) = uv(Point3::new(0.1, 0.1, 0.));
+    /// assert!(outside.contains(u, v));
+    ///
+    /// // The curve collapsed into a point by the projection is rejected by its index
+    /// let dot = NurbsCurve3D::polyline(&[Point3::new(0.3, 0.3, 1.), Point3::new(0.3, 0.3, 2.)]);
+    /// let err = plane.try_trim(&[circle.into(), dot.into()], TrimSide::Inside).unwrap_err();
+    /// assert!(err.to_string().contains("index 1"));
+    ///
+    /// // Open curves in the parameter space are joined into a loop
+    /// let a = NurbsCurve2D::polyline(&[Point2::new(0.2, 0.2), Point2::new(0.8, 0.2), Point2::new(0.8, 0.8)]);
+    /// let b = NurbsCurve2D::polyline(&[Point2::new(0.2, 0.2), Point2::new(0.2, 0.8), Point2::new(0.8, 0.8)]);
+    /// let square = plane.try_trim(&[a.into(), b.into()], TrimSide::Inside).unwrap();
+    /// assert!(square.contains(0.5, 0.5));
+    /// assert!(!square.contains(0.1, 0.5));
+    ///
+    /// // The curves not forming a loop are rejected
+    /// let c = NurbsCurve2D::polyline(&[Point2::new(0.2, 0.2), Point2::new(0.8, 0.2)]);
+    /// assert!(plane.try_trim(&[c.into()], TrimSide::Inside).is_err());
+    /// ```
+    pub fn try_trim(
+        &self,
+        curves: &[TrimCurve<T>],
+        side: TrimSide,
+    ) -> anyhow::Result<TrimmedSurface<T>>
+    where
+        T: ArgminFloat,
+    {
+        let bvh = SurfaceBvh::try_new(self)?;
+        let (u0, u1) = self.u_knots_domain();
+        let (v0, v1) = self.v_knots_domain();
+        let tolerance = Vector2::new(u1 - u0, v1 - v0).norm() * T::from_f64(1e-5).unwrap();
 
-                index += 2;
+        // project the curves onto the parameter space of the surface
+        let curves = curves
+            .iter()
+            .enumerate()
+            .map(|(i, curve)| match curve {
+                TrimCurve::Space(curve) => {
+                    let mut uvs = curve
+                        .tessellate(None)
+                        .iter()
+                        .map(|p| {
+                            bvh.find_closest_parameter(p)
+                                .map(|(u, v)| Point2::new(u, v))
+                        })
+                        .collect::<anyhow::Result<Vec<_>>>()?;
+                    uvs.dedup_by(|a, b| (*a - *b).norm() < tolerance);
+                    anyhow::ensure!(
+                        uvs.len() > 1,
+                        "The curve at index {} collapses into a point by the projection",
+                        i
+                    );
+                    let degree = (uvs.len() - 1).min(3);
+                    NurbsCurve2D::try_interpolate(&uvs, degree)
+                }
+                TrimCurve::Parameter(curve) => Ok(curve.clone()),
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
 
-                if i < narcs {
-                    p0 = p2;
-                    t0 = t2;
+        let is_closed = |spans: &[NurbsCurve2D<T>]| {
+            let head = spans[0].point_at(spans[0].knots_domain().0);
+            let last = &spans[spans.len() - 1];
+            let tail = last.point_at(last.knots_domain().1);
+            (head - tail).norm() < tolerance
+        };
+
+        // join the open curves at their end points into closed loops
+        let mut loops: Vec<CompoundCurve2D<T>> = vec![];
+        let mut open = vec![];
+        for curve in curves {
+            if is_closed(std::slice::from_ref(&curve)) {
+                loops.push(curve.into());
+            } else {
+                open.push(curve);
+            }
+        }
+        while let Some(first) = open.pop() {
+            let mut spans = vec![first];
+            while !is_closed(&spans) {
+                let last = &spans[spans.len() - 1];
+                let tail = last.point_at(last.knots_domain().1);
+                let found = open.iter().enumerate().find_map(|(i, c)| {
+                    let (start, end) = c.knots_domain();
+                    if (c.point_at(start) - tail).norm() < tolerance {
+                        Some((i, false))
+                    } else if (c.point_at(end) - tail).norm() < tolerance {
+                        Some((i, true))
+                    } else {
+                        None
+                    }
+                });
+                let Some((i, reverse)) = found else {
+                    anyhow::bail!("The trim curves do not form a closed loop");
+                };
+                let mut next = open.swap_remove(i);
+                if reverse {
+                    next.invert();
                 }
+                spans.push(next);
             }
+            loops.push(CompoundCurve2D::try_new_with_tolerance(spans, tolerance)?);
+        }
+        anyhow::ensure!(!loops.is_empty(), "No trim loops are given");
+
+        match side {
+            TrimSide::Inside => {
+                // the largest loop is the exterior and the others are the holes inside it
+                let mut loops: Vec<_> = loops
+                    .into_iter()
+                    .map(|l| {
+                        let polygon = l.tessellate(None);
+                        let area = polygon
+                            .iter()
+                            .zip(polygon.iter().cycle().skip(1))
+                            .map(|(a, b)| a.x * b.y - b.x * a.y)
+                            .fold(T::zero(), |a, b| a + b);
+                        (l, polygon, ComplexField::abs(area))
+                    })
+                    .collect();
+                loops.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal));
+                let (exterior, polygon, _) = loops.remove(0);
+                if loops
+                    .iter()
+                    .any(|(_, p, _)| !is_point_inside_polygon(&p[0], &polygon))
+                {
+                    anyhow::bail!("A trim loop lies outside the outermost loop");
+                }
+                TrimmedSurface::try_new(
+                    self.clone(),
+                    Some(exterior),
+                    loops.into_iter().map(|(l, _, _)| l).collect(),
+                )
+            }
+            TrimSide::Outside => TrimmedSurface::try_new(self.clone(), None, loops),
         }
-
-        // Scale the control points by their weights to make them rational
-        control_points.iter_mut().for_each(|row| {
-            row.iter_mut().for_each(|p| {
-                let w = p.w;
-                p.x *= w;
-                p.y *= w;
-                p.z *= w;
-            });
-        });
-
-        Ok(Self {
-            control_points,
-            u_degree: 2,
-            v_degree: profile.degree(),
-            u_knots: KnotVector::new(u_knots),
-            v_knots: profile.knots().clone(),
-        })
     }
 }
 
-/// Unify the knot vectors of a collection of NURBS curves
-///
-fn try_unify_curve_knot_vectors<T, D>(
+/// Unify the degrees, the domains & the knot vectors of a collection of NURBS curves
+pub(crate) fn try_unify_curve_knot_vectors<T, D>(
     curves: &[NurbsCurve<T, D>],
 ) -> anyhow::Result<Vec<NurbsCurve<T, D>>>
 where
@@ -1222,6 +4093,441 @@ where
     Ok(curves)
 }
 
+/// Move the seam of the closed curve to the parameter
+/// Order the boundary curves into a loop by reversing them if needed to start at the end of the previous curve
+fn try_order_boundary_loop<T: FloatingPoint>(
+    curves: &[NurbsCurve3D<T>],
+) -> anyhow::Result<Vec<NurbsCurve3D<T>>> {
+    anyhow::ensure!(
+        curves.len() >= 3,
+        "The boundary loop needs at least 3 curves"
+    );
+    let tolerance = T::from_f64(1e-6).unwrap();
+    let ends = |c: &NurbsCurve3D<T>| {
+        let (s, e) = c.knots_domain();
+        (c.point_at(s), c.point_at(e))
+    };
+    let touches = |c: &NurbsCurve3D<T>, p: &Point3<T>| {
+        let (s, e) = ends(c);
+        (s - p).norm() < tolerance || (e - p).norm() < tolerance
+    };
+
+    // the first curve is reversed if its start touches the second curve
+    let first = if touches(&curves[1], &ends(&curves[0]).1) {
+        curves[0].clone()
+    } else {
+        curves[0].inverse()
+    };
+    let mut ordered = vec![first];
+    for c in curves.iter().skip(1) {
+        let (_, end) = ends(ordered.last().unwrap());
+        let (s, e) = ends(c);
+        if (s - end).norm() < tolerance {
+            ordered.push(c.clone());
+        } else if (e - end).norm() < tolerance {
+            ordered.push(c.inverse());
+        } else {
+            anyhow::bail!("The boundary curves are not connected");
+        }
+    }
+    anyhow::ensure!(
+        (ends(ordered.last().unwrap()).1 - ends(&ordered[0]).0).norm() < tolerance,
+        "The boundary curves do not form a closed loop"
+    );
+    Ok(ordered)
+}
+
+/// Elevate the surfaces to the same degree & merge their knot vectors in the direction
+fn try_unify_surface_knot_vectors<T, D>(
+    surfaces: &[NurbsSurface<T, D>],
+    v_direction: bool,
+) -> anyhow::Result<Vec<NurbsSurface<T, D>>>
+where
+    T: FloatingPoint,
+    D: DimName + DimNameSub<U1>,
+    DefaultAllocator: Allocator<D>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    let curves = surfaces
+        .iter()
+        .map(|s| s.try_direction_curves(v_direction))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let counts = curves.iter().map(|c| c.len()).collect::<Vec<_>>();
+    let mut unified = try_unify_curve_knot_vectors(&curves.concat())?.into_iter();
+
+    Ok(surfaces
+        .iter()
+        .zip(counts)
+        .map(|(s, count)| {
+            let curves = unified.by_ref().take(count).collect::<Vec<_>>();
+            s.with_direction_curves(&curves, v_direction)
+        })
+        .collect())
+}
+
+/// Interpolate the homogeneous points at the parameters with the knot vector averaging the parameters
+fn try_interpolate_at_parameters<T: FloatingPoint>(
+    points: &[Point4<T>],
+    parameters: &[T],
+    degree: usize,
+) -> anyhow::Result<NurbsCurve3D<T>> {
+    let n = points.len();
+    anyhow::ensure!(n > degree, "Too few points to interpolate");
+
+    let knots = averaged_knots(parameters, degree);
+    let lu = basis_matrix(&knots, parameters, degree).lu();
+    let mut control_points = vec![Point4::origin(); n];
+    for d in 0..4 {
+        let b = DVector::from_iterator(n, points.iter().map(|p| p[d]));
+        let x = lu.solve(&b).ok_or(anyhow::anyhow!("Solve failed"))?;
+        control_points
+            .iter_mut()
+            .zip(x.iter())
+            .for_each(|(p, x)| p[d] = *x);
+    }
+
+    NurbsCurve3D::try_new(degree, control_points, knots.to_vec())
+}
+
+/// Normalize the parameters into the unit square by their bounds
+fn normalize_parameters<T: FloatingPoint>(parameters: &[(T, T)]) -> anyhow::Result<Vec<(T, T)>> {
+    let (first_u, first_v) = parameters[0];
+    let (u0, u1, v0, v1) = parameters.iter().fold(
+        (first_u, first_u, first_v, first_v),
+        |(u0, u1, v0, v1), (u, v)| (u0.min(*u), u1.max(*u), v0.min(*v), v1.max(*v)),
+    );
+    anyhow::ensure!(
+        u1 - u0 > T::default_epsilon() && v1 - v0 > T::default_epsilon(),
+        "The parameters of the points are degenerate"
+    );
+    Ok(parameters
+        .iter()
+        .map(|(u, v)| ((*u - u0) / (u1 - u0), (*v - v0) / (v1 - v0)))
+        .collect())
+}
+
+/// Fit the non-rational surface with the knot vectors to the points at the parameters in the least squares sense
+/// The second differences of the control points along both directions are penalized by the smoothing,
+/// which is scaled by the ratio of the traces so that it is relative to the fitting term.
+fn try_fit_surface_at_parameters<T: FloatingPoint>(
+    points: &[Point3<T>],
+    parameters: &[(T, T)],
+    u_knots: KnotVector<T>,
+    v_knots: KnotVector<T>,
+    u_degree: usize,
+    v_degree: usize,
+    smoothing: T,
+) -> anyhow::Result<NurbsSurface3D<T>> {
+    let nu = u_knots.len() - u_degree - 1;
+    let nv = v_knots.len() - v_degree - 1;
+    let count = nu * nv;
+    let index = |i: usize, j: usize| i * nv + j;
+
+    let mut basis = DMatrix::<T>::zeros(points.len(), count);
+    for (k, (u, v)) in parameters.iter().enumerate() {
+        let su = u_knots.find_knot_span_index(nu - 1, u_degree, *u);
+        let sv = v_knots.find_knot_span_index(nv - 1, v_degree, *v);
+        let bu = u_knots.basis_functions(su, *u, u_degree);
+        let bv = v_knots.basis_functions(sv, *v, v_degree);
+        for (a, bu) in bu.iter().enumerate() {
+            for (b, bv) in bv.iter().enumerate() {
+                basis[(k, index(su - u_degree + a, sv - v_degree + b))] = *bu * *bv;
+            }
+        }
+    }
+
+    let two = T::from_f64(2.).unwrap();
+    let mut rows = vec![];
+    for i in 0..nu {
+        for j in 0..nv {
+            if i + 2 < nu {
+                rows.push([
+                    (index(i, j), T::one()),
+                    (index(i + 1, j), -two),
+                    (index(i + 2, j), T::one()),
+                ]);
+            }
+            if j + 2 < nv {
+                rows.push([
+                    (index(i, j), T::one()),
+                    (index(i, j + 1), -two),
+                    (index(i, j + 2), T::one()),
+                ]);
+            }
+        }
+    }
+    let mut differences = DMatrix::<T>::zeros(rows.len(), count);
+    for (r, row) in rows.iter().enumerate() {
+        for (c, w) in row.iter() {
+            differences[(r, *c)] = *w;
+        }
+    }
+
+    let normal = basis.transpose() * &basis;
+    let penalty = differences.transpose() * &differences;
+    let scale = if penalty.trace() > T::zero() {
+        normal.trace() / penalty.trace()
+    } else {
+        T::zero()
+    };
+    let lu = (normal + penalty * (smoothing * scale)).lu();
+    let mut coords = vec![];
+    for d in 0..3 {
+        let q = DVector::from_iterator(points.len(), points.iter().map(|p| p[d]));
+        let x = lu.solve(&(basis.transpose() * q)).ok_or(anyhow::anyhow!(
+            "Solve failed, the points may be too few for the control points"
+        ))?;
+        coords.push(x);
+    }
+    let control_points = (0..nu)
+        .map(|i| {
+            (0..nv)
+                .map(|j| {
+                    let k = index(i, j);
+                    Point4::new(coords[0][k], coords[1][k], coords[2][k], T::one())
+                })
+                .collect()
+        })
+        .collect();
+
+    Ok(NurbsSurface3D::new(
+        u_degree,
+        v_degree,
+        u_knots.to_vec(),
+        v_knots.to_vec(),
+        control_points,
+    ))
+}
+
+/// Create the clamped knot vector averaging the parameters (The NURBS Book eq. 9.8)
+fn averaged_knots<T: FloatingPoint>(parameters: &[T], degree: usize) -> KnotVector<T> {
+    let n = parameters.len();
+    let knots = [
+        vec![parameters[0]; degree + 1],
+        (1..(n - degree))
+            .map(|i| {
+                parameters[i..(i + degree)]
+                    .iter()
+                    .fold(T::zero(), |acc, p| acc + *p)
+                    / T::from_usize(degree).unwrap()
+            })
+            .collect(),
+        vec![parameters[n - 1]; degree + 1],
+    ]
+    .concat();
+    KnotVector::new(knots)
+}
+
+/// Create the square matrix of the basis functions evaluated at the parameters
+fn basis_matrix<T: FloatingPoint>(
+    knots: &KnotVector<T>,
+    parameters: &[T],
+    degree: usize,
+) -> DMatrix<T> {
+    let n = parameters.len();
+    let mut m_a = DMatrix::<T>::zeros(n, n);
+    for (i, u) in parameters.iter().enumerate() {
+        let span = knots.find_knot_span_index(n - 1, degree, *u);
+        let basis = knots.basis_functions(span, *u, degree);
+        for (k, b) in basis.into_iter().enumerate() {
+            m_a[(i, span - degree + k)] = b;
+        }
+    }
+    m_a
+}
+
+/// Average the normalized chord length parameters of the point sequences (The NURBS Book eq. 9.12)
+/// The sequences with no length are skipped.
+fn averaged_chordal_parameters<T: FloatingPoint, D: DimName>(
+    sequences: &[Vec<&OPoint<T, D>>],
+) -> anyhow::Result<Vec<T>>
+where
+    DefaultAllocator: Allocator<D>,
+{
+    let n = sequences[0].len();
+    let mut parameters = vec![T::zero(); n];
+    let mut count = 0;
+    for seq in sequences.iter() {
+        let lengths = seq
+            .windows(2)
+            .map(|w| (w[1] - w[0]).norm())
+            .collect::<Vec<_>>();
+        let total = lengths.iter().fold(T::zero(), |acc, l| acc + *l);
+        if total <= T::default_epsilon() {
+            continue;
+        }
+        let mut acc = T::zero();
+        for (i, l) in lengths.iter().enumerate() {
+            acc += *l;
+            parameters[i + 1] += acc / total;
+        }
+        count += 1;
+    }
+    anyhow::ensure!(count > 0, "The points are degenerate");
+    let count = T::from_usize(count).unwrap();
+    parameters.iter_mut().for_each(|p| *p /= count);
+    parameters[n - 1] = T::one();
+    anyhow::ensure!(
+        parameters.windows(2).all(|w| w[0] < w[1]),
+        "The parameters of the points must be strictly increasing"
+    );
+    Ok(parameters)
+}
+
+fn try_reseam<T: FloatingPoint>(curve: &NurbsCurve3D<T>, t: T) -> anyhow::Result<NurbsCurve3D<T>> {
+    let (start, end) = curve.knots_domain();
+    let eps = (end - start) * T::from_f64(1e-8).unwrap();
+    if t <= start + eps || t >= end - eps {
+        return Ok(curve.clone());
+    }
+    let (head, tail) = curve.try_trim(t)?;
+    let degree = curve.degree();
+
+    // join the tail & the head at the original seam with the knots shifted to continue
+    let tail_knots = tail.knots().as_slice();
+    let head_knots = head.knots().as_slice();
+    let offset = tail_knots[tail_knots.len() - 1] - start;
+    let knots = tail_knots[..tail_knots.len() - 1]
+        .iter()
+        .copied()
+        .chain(head_knots[degree + 1..].iter().map(|k| *k + offset))
+        .collect();
+    let control_points = tail
+        .control_points()
+        .iter()
+        .chain(head.control_points().iter().skip(1))
+        .cloned()
+        .collect();
+    NurbsCurve3D::try_new(degree, control_points, knots)
+}
+
+/// Reverse the section & move its seam if closed to match the previous section
+fn try_align_section<T: FloatingPoint + ArgminFloat>(
+    previous: &NurbsCurve3D<T>,
+    section: &NurbsCurve3D<T>,
+) -> anyhow::Result<NurbsCurve3D<T>> {
+    let is_closed = |c: &NurbsCurve3D<T>| {
+        let (start, end) = c.knots_domain();
+        (c.point_at(start) - c.point_at(end)).norm() < T::from_f64(1e-8).unwrap()
+    };
+    let mut section = section.clone();
+    if is_closed(previous) && is_closed(&section) {
+        let (start, _) = previous.knots_domain();
+        let t = section.find_closest_parameter(&previous.point_at(start))?;
+        section = try_reseam(&section, t)?;
+    }
+
+    // compare the samples at the same ratios of the domains
+    let samples = 8;
+    let at = |c: &NurbsCurve3D<T>, i: usize| {
+        let (start, end) = c.knots_domain();
+        c.point_at(
+            start + (end - start) * T::from_usize(i).unwrap() / T::from_usize(samples).unwrap(),
+        )
+    };
+    let (same, reversed) = (0..=samples).fold((T::zero(), T::zero()), |(a, b), i| {
+        let p = at(previous, i);
+        (
+            a + (at(&section, i) - p).norm(),
+            b + (at(&section, samples - i) - p).norm(),
+        )
+    });
+    if reversed < same {
+        section.invert();
+    }
+    Ok(section)
+}
+
+/// Insert the intermediate sections blended between the sections & displaced to pass through the guides
+fn try_guided_sections<T: FloatingPoint>(
+    sections: &[NurbsCurve3D<T>],
+    guides: &[NurbsCurve3D<T>],
+    divisions: usize,
+) -> anyhow::Result<Vec<NurbsCurve3D<T>>> {
+    let sections = try_unify_curve_knot_vectors(sections)?;
+
+    // the parameters of the crossings of the guides on the sections & the guides
+    let crossings = sections
+        .iter()
+        .map(|section| {
+            guides
+                .iter()
+                .map(|guide| {
+                    let closest = section.closest_parameters(guide)?;
+                    Ok((closest.a().1, closest.b().1))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let degree = sections[0].degree();
+    let knots = sections[0].knots().clone();
+    let greville = (0..sections[0].control_points().len())
+        .map(|k| {
+            (1..=degree).fold(T::zero(), |acc, j| acc + knots[k + j])
+                / T::from_usize(degree.max(1)).unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    let mut result = vec![sections[0].clone()];
+    for i in 0..sections.len() - 1 {
+        let (a, b) = (&sections[i], &sections[i + 1]);
+        for k in 1..divisions.max(1) {
+            let w = T::from_usize(k).unwrap() / T::from_usize(divisions).unwrap();
+            let control_points = a
+                .control_points()
+                .iter()
+                .zip(b.control_points().iter())
+                .map(|(p, q)| p * (T::one() - w) + q.coords * w)
+                .collect::<Vec<_>>();
+            let blended = NurbsCurve3D::try_new(degree, control_points, knots.to_vec())?;
+
+            // the displacements at the crossings sorted by the parameter on the section
+            let mut displacements = guides
+                .iter()
+                .enumerate()
+                .map(|(j, guide)| {
+                    let (u0, s0) = crossings[i][j];
+                    let (u1, s1) = crossings[i + 1][j];
+                    let u = u0 + (u1 - u0) * w;
+                    let s = s0 + (s1 - s0) * w;
+                    (u, guide.point_at(s) - blended.point_at(u))
+                })
+                .collect::<Vec<_>>();
+            displacements.sort_by(|x, y| x.0.partial_cmp(&y.0).unwrap());
+            let displacement = |u: T| -> Vector3<T> {
+                let j = displacements.partition_point(|(v, _)| *v <= u);
+                if j == 0 {
+                    displacements[0].1
+                } else if j == displacements.len() {
+                    displacements[j - 1].1
+                } else {
+                    let ((v0, d0), (v1, d1)) = (displacements[j - 1], displacements[j]);
+                    let t = (u - v0) / (v1 - v0);
+                    d0 * (T::one() - t) + d1 * t
+                }
+            };
+
+            let control_points = blended
+                .control_points()
+                .iter()
+                .zip(greville.iter())
+                .map(|(p, u)| {
+                    let d = displacement(*u) * p.w;
+                    Point4::new(p.x + d.x, p.y + d.y, p.z + d.z, p.w)
+                })
+                .collect();
+            result.push(NurbsCurve3D::try_new(
+                degree,
+                control_points,
+                knots.to_vec(),
+            )?);
+        }
+        result.push(b.clone());
+    }
+    Ok(result)
+}
+
 fn sorted_set_union<T: RealField + Copy>(a: &[T], b: &[T]) -> Vec<T> {
     let mut merged = Vec::new();
     let mut ai = 0;
@@ -1286,6 +4592,16 @@ fn sorted_set_sub<T: RealField + Copy>(a: &[T], b: &[T]) -> Vec<T> {
     result
 }
 
+/// Enable to deform a NURBS surface by the deformation of its control points
+impl<T: FloatingPoint> Deformable<T> for NurbsSurface3D<T> {
+    fn deform<F: SpaceDeformation<T>>(&mut self, deformation: &F) {
+        self.control_points.iter_mut().for_each(|rows| {
+            rows.iter_mut()
+                .for_each(|p| deformation.deform_control_point(p))
+        });
+    }
+}
+
 /// Enable to transform a NURBS surface by a given DxD matrix
 impl<'a, T: FloatingPoint, const D: usize> Transformable<&'a OMatrix<T, Const<D>, Const<D>>>
     for NurbsSurface<T, Const<D>>
@@ -1311,6 +4627,44 @@ impl<'a, T: FloatingPoint, const D: usize> Transformable<&'a OMatrix<T, Const<D>
     }
 }
 
+/// Order the section curves at a contour level
+/// Closed loops come first ordered by the enclosed area in descending order and are oriented counter-clockwise around the plane normal.
+fn order_contour_curves<T: FloatingPoint>(
+    plane: &Plane<T>,
+    curves: Vec<NurbsCurve3D<T>>,
+) -> Vec<NurbsCurve3D<T>> {
+    let normal = plane.normal();
+    let tolerance = T::from_f64(1e-6).unwrap();
+    let mut loops = vec![];
+    let mut open = vec![];
+    for mut curve in curves {
+        let (start, end) = curve.knots_domain();
+        let (head, tail) = (curve.point_at(start), curve.point_at(end));
+        if (head - tail).norm() > tolerance {
+            open.push(curve);
+            continue;
+        }
+
+        // the signed area around the plane normal by the shoelace formula
+        let points = curve.tessellate(None);
+        let area = points
+            .windows(2)
+            .map(|w| {
+                (w[0] - plane.origin())
+                    .cross(&(w[1] - plane.origin()))
+                    .dot(normal)
+            })
+            .fold(T::zero(), |a, b| a + b)
+            * T::from_f64(0.5).unwrap();
+        if area < T::zero() {
+            curve.invert();
+        }
+        loops.push((curve, area.abs()));
+    }
+    loops.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    loops.into_iter().map(|(c, _)| c).chain(open).collect()
+}
+
 #[cfg(feature = "serde")]
 impl<T, D: DimName> serde::Serialize for NurbsSurface<T, D>
 where
@@ -0,0 +1,252 @@
+use gauss_quad::GaussLegendre;
+
+use crate::{
+    curve::NurbsCurve2D,
+    misc::FloatingPoint,
+    surface::{NurbsSurface3D, TrimmedSurface},
+};
+
+/// The maximum depth of the subdivision in the adaptive quadratures
+const MAX_DEPTH: usize = 16;
+
+/// The area of a surface with the estimate of the error of the quadrature
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SurfaceArea<T: FloatingPoint> {
+    area: T,
+    /// The sum of the differences between the quadratures of the subdivided cells & their halves
+    error: T,
+}
+
+impl<T: FloatingPoint> SurfaceArea<T> {
+    pub fn new(area: T, error: T) -> Self {
+        Self { area, error }
+    }
+
+    pub fn area(&self) -> T {
+        self.area
+    }
+
+    pub fn error(&self) -> T {
+        self.error
+    }
+}
+
+/// The area element of the surface at the parameter
+fn density<T: FloatingPoint>(surface: &NurbsSurface3D<T>, u: T, v: T) -> T {
+    let d = surface.rational_derivatives(u, v, 1);
+    d[1][0].cross(&d[0][1]).norm()
+}
+
+/// The distinct knots strictly inside the interval with the ends of the interval
+fn breaks<T: FloatingPoint>(knots: &[T], a: T, b: T) -> Vec<T> {
+    let (lo, hi) = (a.min(b), a.max(b));
+    let mut breaks = knots
+        .iter()
+        .filter(|k| **k > lo && **k < hi)
+        .copied()
+        .collect::<Vec<_>>();
+    breaks.dedup();
+    breaks.insert(0, lo);
+    breaks.push(hi);
+    if a > b {
+        breaks.reverse();
+    }
+    breaks
+}
+
+/// Integrate the function over the interval by the Gauss-Legendre quadrature
+fn quadrature<T: FloatingPoint, F: Fn(T) -> T>(gauss: &GaussLegendre, f: &F, a: T, b: T) -> T {
+    let half = (b - a) * T::from_f64(0.5).unwrap();
+    let mid = (a + b) * T::from_f64(0.5).unwrap();
+    gauss
+        .as_node_weight_pairs()
+        .iter()
+        .fold(T::zero(), |acc, &(x, w)| {
+            acc + f(mid + half * T::from_f64(x).unwrap()) * T::from_f64(w).unwrap() * half
+        })
+}
+
+/// Integrate the function over the interval by subdividing it adaptively until the halves agree with the whole within the tolerance
+/// Returns the integral & the estimate of its error.
+fn adaptive<T: FloatingPoint, F: Fn(T) -> T>(
+    gauss: &GaussLegendre,
+    f: &F,
+    a: T,
+    b: T,
+    tolerance: T,
+) -> (T, T) {
+    let half = T::from_f64(0.5).unwrap();
+    let (mut sum, mut error) = (T::zero(), T::zero());
+    let mut stack = vec![(a, b, quadrature(gauss, f, a, b), tolerance, 0)];
+    while let Some((a, b, whole, tolerance, depth)) = stack.pop() {
+        let mid = (a + b) * half;
+        let (left, right) = (quadrature(gauss, f, a, mid), quadrature(gauss, f, mid, b));
+        let difference = (left + right - whole).abs();
+        if difference <= tolerance || depth >= MAX_DEPTH {
+            sum += left + right;
+            error += difference;
+        } else {
+            stack.push((mid, b, right, tolerance * half, depth + 1));
+            stack.push((a, mid, left, tolerance * half, depth + 1));
+        }
+    }
+    (sum, error)
+}
+
+impl<T: FloatingPoint> NurbsSurface3D<T> {
+    /// Compute the area of the surface by the adaptive Gauss-Legendre cubature over the knot spans of the domain
+    /// Each cell is divided into the quarters until their sum agrees with the cubature of the whole cell within its share of the tolerance.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let sphere = NurbsSurface3D::<f64>::try_sphere(&Point3::origin(), &Vector3::z(), 2.).unwrap();
+    /// let area = sphere.area(1e-8);
+    /// assert_relative_eq!(area.area(), std::f64::consts::PI * 16., epsilon = 1e-8);
+    /// assert!(area.error() <= 1e-8);
+    ///
+    /// let torus = NurbsSurface3D::<f64>::try_torus(&Point3::origin(), &Vector3::z(), 2., 0.5).unwrap();
+    /// let area = torus.area(1e-8);
+    /// assert_relative_eq!(area.area(), std::f64::consts::PI.powi(2) * 4., epsilon = 1e-8);
+    /// ```
+    pub fn area(&self, tolerance: T) -> SurfaceArea<T> {
+        let gauss = GaussLegendre::new(8).unwrap();
+        let (u0, u1) = self.u_knots_domain();
+        let (v0, v1) = self.v_knots_domain();
+        let us = breaks(self.u_knots().as_slice(), u0, u1);
+        let vs = breaks(self.v_knots().as_slice(), v0, v1);
+        let domain = (u1 - u0) * (v1 - v0);
+        let half = T::from_f64(0.5).unwrap();
+
+        // the tensor product of the quadratures over the cell
+        let cubature = |(a, b): (T, T), (c, d): (T, T)| {
+            quadrature(
+                &gauss,
+                &|v| quadrature(&gauss, &|u| density(self, u, v), a, b),
+                c,
+                d,
+            )
+        };
+
+        let (mut area, mut error) = (T::zero(), T::zero());
+        for wu in us.windows(2) {
+            for wv in vs.windows(2) {
+                let (cu, cv) = ((wu[0], wu[1]), (wv[0], wv[1]));
+                let share = tolerance * (cu.1 - cu.0) * (cv.1 - cv.0) / domain;
+                let mut stack = vec![(cu, cv, cubature(cu, cv), share, 0)];
+                while let Some(((a, b), (c, d), whole, tolerance, depth)) = stack.pop() {
+                    let (mu, mv) = ((a + b) * half, (c + d) * half);
+                    let quarters = [
+                        ((a, mu), (c, mv)),
+                        ((mu, b), (c, mv)),
+                        ((a, mu), (mv, d)),
+                        ((mu, b), (mv, d)),
+                    ]
+                    .map(|(u, v)| (u, v, cubature(u, v)));
+                    let sum = quarters.iter().fold(T::zero(), |acc, q| acc + q.2);
+                    let difference = (sum - whole).abs();
+                    if difference <= tolerance || depth >= MAX_DEPTH {
+                        area += sum;
+                        error += difference;
+                    } else {
+                        let quarter = tolerance * half * half;
+                        stack.extend(quarters.map(|(u, v, q)| (u, v, q, quarter, depth + 1)));
+                    }
+                }
+            }
+        }
+        SurfaceArea::new(area, error)
+    }
+}
+
+impl<T: FloatingPoint> TrimmedSurface<T> {
+    /// Compute the area of the trimmed surface by the Green's theorem over the trimmed domain
+    /// The area is the integral along the loops of the integral of the area element from the start of the domain in the u direction,
+    /// both evaluated by the adaptive Gauss-Legendre quadrature, and the holes are subtracted regardless of the orientations of the loops.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point2, Point3, Vector2, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// // the square of 2 x 2 with the circular hole
+    /// let line = NurbsCurve3D::polyline(&[Point3::new(0., 0., 0.), Point3::new(2., 0., 0.)]);
+    /// let plane = NurbsSurface::extrude(&line, &(Vector3::y() * 2.));
+    /// let hole = NurbsCurve2D::try_circle(&Point2::new(0.5, 0.5), &Vector2::x(), &Vector2::y(), 0.25).unwrap();
+    /// let trimmed = TrimmedSurface::try_new(plane, None, vec![hole.into()]).unwrap();
+    /// let area = trimmed.area(1e-8);
+    /// assert_relative_eq!(area.area(), 4. - std::f64::consts::FRAC_PI_4, epsilon = 1e-8);
+    /// assert!(area.error() <= 1e-8);
+    ///
+    /// // the half of the cylinder
+    /// let cylinder = NurbsSurface3D::<f64>::try_cylinder(&Point3::origin(), &(Vector3::z() * 2.), 1.).unwrap();
+    /// let half = NurbsCurve2D::polyline(&[
+    ///     Point2::new(0., 0.),
+    ///     Point2::new(0.5, 0.),
+    ///     Point2::new(0.5, 1.),
+    ///     Point2::new(0., 1.),
+    ///     Point2::new(0., 0.),
+    /// ]);
+    /// let trimmed = TrimmedSurface::try_new(cylinder, Some(half.into()), vec![]).unwrap();
+    /// assert_relative_eq!(trimmed.area(1e-8).area(), std::f64::consts::TAU, epsilon = 1e-8);
+    /// ```
+    pub fn area(&self, tolerance: T) -> SurfaceArea<T> {
+        let surface = self.surface();
+        let gauss = GaussLegendre::new(8).unwrap();
+        let (u0, _) = surface.u_knots_domain();
+        let (v0, v1) = surface.v_knots_domain();
+        let u_knots = surface.u_knots().as_slice();
+        let loops = self.exterior().into_iter().count() + self.interiors().len();
+        // the share of the tolerance for the integrals in the u direction per the unit length of the loops in the v direction
+        let inner = tolerance / (T::from_usize(loops.max(1) * 16).unwrap() * (v1 - v0));
+        let outer = tolerance / T::from_usize(loops.max(1) * 2).unwrap();
+
+        // the integral of the area element from the start of the domain to the parameter in the u direction
+        let potential = |u: T, v: T| {
+            breaks(u_knots, u0, u).windows(2).fold(T::zero(), |acc, w| {
+                acc + adaptive(&gauss, &|s| density(surface, s, v), w[0], w[1], inner).0
+            })
+        };
+
+        // the signed area enclosed by the loop, positive if it turns counterclockwise
+        let enclosed = |spans: &[NurbsCurve2D<T>]| {
+            let n = T::from_usize(spans.len()).unwrap();
+            spans.iter().fold((T::zero(), T::zero()), |acc, span| {
+                let (start, end) = span.knots_domain();
+                let integrand = |t: T| {
+                    let d = span.rational_derivatives(t, 1);
+                    potential(d[0].x, d[0].y) * d[1].y
+                };
+                breaks(span.knots().as_slice(), start, end).windows(2).fold(
+                    acc,
+                    |(area, error), w| {
+                        let share = outer / n * (w[1] - w[0]) / (end - start);
+                        let (a, e) = adaptive(&gauss, &integrand, w[0], w[1], share);
+                        (area + a, error + e)
+                    },
+                )
+            })
+        };
+
+        let (area, error) = match self.exterior() {
+            Some(exterior) => {
+                let (a, e) = enclosed(exterior.spans());
+                (a.abs(), e)
+            }
+            None => {
+                let whole = surface.area(outer);
+                (whole.area(), whole.error())
+            }
+        };
+        let (area, error) =
+            self.interiors()
+                .iter()
+                .fold((area, error), |(area, error), interior| {
+                    let (a, e) = enclosed(interior.spans());
+                    (area - a.abs(), error + e)
+                });
+        SurfaceArea::new(area, error)
+    }
+}
@@ -0,0 +1,40 @@
+use crate::{curve::NurbsCurve3D, misc::FloatingPoint, misc::Plane};
+
+/// A struct representing the section curves of a surface at a contour level.
+#[derive(Clone, Debug)]
+pub struct SurfaceContour<T: FloatingPoint> {
+    /// The offset of the level along the contour direction.
+    offset: T,
+    /// The section plane at the level.
+    plane: Plane<T>,
+    /// The section curves at the level.
+    /// Closed loops come first ordered by the enclosed area in descending order,
+    /// and they are oriented counter-clockwise around the contour direction.
+    curves: Vec<NurbsCurve3D<T>>,
+}
+
+impl<T: FloatingPoint> SurfaceContour<T> {
+    pub fn new(offset: T, plane: Plane<T>, curves: Vec<NurbsCurve3D<T>>) -> Self {
+        Self {
+            offset,
+            plane,
+            curves,
+        }
+    }
+
+    pub fn offset(&self) -> T {
+        self.offset
+    }
+
+    pub fn plane(&self) -> &Plane<T> {
+        &self.plane
+    }
+
+    pub fn curves(&self) -> &[NurbsCurve3D<T>] {
+        &self.curves
+    }
+
+    pub fn into_curves(self) -> Vec<NurbsCurve3D<T>> {
+        self.curves
+    }
+}
@@ -0,0 +1,167 @@
+use nalgebra::Vector3;
+
+use crate::{misc::FloatingPoint, prelude::SurfaceTessellation3D, surface::NurbsSurface3D};
+
+/// The curvatures of a surface at a point
+/// The normal curvatures are positive where the surface bends towards the normal.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SurfaceCurvature<T: FloatingPoint> {
+    /// The maximum & minimum principal curvatures
+    principal: (T, T),
+    /// The unit directions of the maximum & minimum principal curvatures
+    directions: (Vector3<T>, Vector3<T>),
+    /// The unit normal
+    normal: Vector3<T>,
+}
+
+impl<T: FloatingPoint> SurfaceCurvature<T> {
+    pub fn new(
+        principal: (T, T),
+        directions: (Vector3<T>, Vector3<T>),
+        normal: Vector3<T>,
+    ) -> Self {
+        Self {
+            principal,
+            directions,
+            normal,
+        }
+    }
+
+    /// The maximum & minimum principal curvatures
+    pub fn principal(&self) -> (T, T) {
+        self.principal
+    }
+
+    /// The unit directions of the maximum & minimum principal curvatures
+    pub fn directions(&self) -> &(Vector3<T>, Vector3<T>) {
+        &self.directions
+    }
+
+    pub fn normal(&self) -> &Vector3<T> {
+        &self.normal
+    }
+
+    /// The Gaussian curvature, the product of the principal curvatures
+    pub fn gaussian(&self) -> T {
+        self.principal.0 * self.principal.1
+    }
+
+    /// The mean curvature, the average of the principal curvatures
+    pub fn mean(&self) -> T {
+        (self.principal.0 + self.principal.1) * T::from_f64(0.5).unwrap()
+    }
+}
+
+impl<T: FloatingPoint> NurbsSurface3D<T> {
+    /// Evaluate the principal curvatures & directions at the parameter from the first & second fundamental forms
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let cylinder = NurbsSurface3D::<f64>::try_cylinder(&Point3::origin(), &(Vector3::z() * 2.), 0.5).unwrap();
+    /// let (u0, u1) = cylinder.u_knots_domain();
+    /// let (v0, v1) = cylinder.v_knots_domain();
+    /// let curvature = cylinder.try_curvature_at((u0 + u1) * 0.3, (v0 + v1) * 0.5).unwrap();
+    /// let (k1, k2) = curvature.principal();
+    /// // the cylinder bends only around the axis
+    /// assert_relative_eq!(k1.abs().max(k2.abs()), 2., epsilon = 1e-8);
+    /// assert_relative_eq!(curvature.gaussian(), 0., epsilon = 1e-8);
+    /// assert_relative_eq!(curvature.mean().abs(), 1., epsilon = 1e-8);
+    /// let straight = if k1.abs() < k2.abs() { curvature.directions().0 } else { curvature.directions().1 };
+    /// assert_relative_eq!(straight.z.abs(), 1., epsilon = 1e-8);
+    ///
+    /// let sphere = NurbsSurface3D::<f64>::try_sphere(&Point3::origin(), &Vector3::z(), 2.).unwrap();
+    /// let (u0, u1) = sphere.u_knots_domain();
+    /// let (v0, v1) = sphere.v_knots_domain();
+    /// let curvature = sphere.try_curvature_at((u0 + u1) * 0.4, (v0 + v1) * 0.3).unwrap();
+    /// assert_relative_eq!(curvature.gaussian(), 0.25, epsilon = 1e-8);
+    /// assert_relative_eq!(curvature.mean().abs(), 0.5, epsilon = 1e-8);
+    /// ```
+    pub fn try_curvature_at(&self, u: T, v: T) -> anyhow::Result<SurfaceCurvature<T>> {
+        let d = self.rational_derivatives(u, v, 2);
+        let (su, sv) = (&d[1][0], &d[0][1]);
+        let cross = su.cross(sv);
+        let area = cross.norm();
+        let (e, f, g) = (su.dot(su), su.dot(sv), sv.dot(sv));
+        anyhow::ensure!(
+            area > T::default_epsilon().sqrt() * (e + g),
+            "The surface is degenerate at the parameter ({}, {})",
+            u,
+            v
+        );
+        let normal = cross / area;
+        let (l, m, n) = (
+            d[2][0].dot(&normal),
+            d[1][1].dot(&normal),
+            d[0][2].dot(&normal),
+        );
+
+        let det = e * g - f * f;
+        let gaussian = (l * n - m * m) / det;
+        let two = T::from_f64(2.).unwrap();
+        let mean = (e * n - two * f * m + g * l) / (two * det);
+        let discriminant = (mean * mean - gaussian).max(T::zero()).sqrt();
+        let (k1, k2) = (mean + discriminant, mean - discriminant);
+
+        // the principal direction in the parameter space is the null vector of II - k I
+        let direction = |k: T| {
+            let (a, b, c) = (l - k * e, m - k * f, n - k * g);
+            let (du, dv) = if a.abs() + b.abs() >= b.abs() + c.abs() {
+                (-b, a)
+            } else {
+                (c, -b)
+            };
+            let t = su * du + sv * dv;
+            let norm = t.norm();
+            (norm > T::default_epsilon() * (su.norm() + sv.norm())).then(|| t / norm)
+        };
+        let directions = match (direction(k1), direction(k2)) {
+            (Some(d1), Some(d2))
+                if discriminant > T::default_epsilon().sqrt() * k1.abs().max(T::one()) =>
+            {
+                (d1, d2)
+            }
+            // the principal directions are arbitrary at the umbilic point
+            _ => {
+                let d1 = su.normalize();
+                (d1, normal.cross(&d1))
+            }
+        };
+        Ok(SurfaceCurvature::new((k1, k2), directions, normal))
+    }
+
+    /// Evaluate the curvatures at the parameters of the vertices of the tessellation for the curvature maps
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let sphere = NurbsSurface3D::<f64>::try_sphere(&Point3::origin(), &Vector3::z(), 2.).unwrap();
+    /// let (u0, u1) = sphere.u_knots_domain();
+    /// let (v0, v1) = sphere.v_knots_domain();
+    /// let tessellation = sphere.regular_tessellate(8, 8);
+    /// let curvatures = sphere.try_tessellation_curvatures(&tessellation);
+    /// assert_eq!(curvatures.len(), tessellation.points().len());
+    /// for (curvature, uv) in curvatures.iter().zip(tessellation.uvs()) {
+    ///     // the poles are degenerate
+    ///     if let Some(curvature) = curvature {
+    ///         assert_relative_eq!(curvature.gaussian(), 0.25, epsilon = 1e-6);
+    ///     } else {
+    ///         assert!([u0, u1, v0, v1].iter().any(|t| (uv.x - t).abs() < 1e-8 || (uv.y - t).abs() < 1e-8));
+    ///     }
+    /// }
+    /// ```
+    pub fn try_tessellation_curvatures(
+        &self,
+        tessellation: &SurfaceTessellation3D<T>,
+    ) -> Vec<Option<SurfaceCurvature<T>>> {
+        tessellation
+            .uvs()
+            .iter()
+            .map(|uv| self.try_curvature_at(uv.x, uv.y).ok())
+            .collect()
+    }
+}
@@ -0,0 +1,236 @@
+use nalgebra::Point2;
+
+use crate::{
+    misc::FloatingPoint,
+    surface::{surface_unroll::surface_distance, NurbsSurface3D, SurfaceUnrolling},
+};
+
+/// The developability of a surface measured by the Gaussian curvature, which vanishes everywhere on developable surfaces
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SurfaceDevelopability<T: FloatingPoint> {
+    /// The maximum absolute Gaussian curvature
+    max_gaussian: T,
+    /// The integral of the absolute Gaussian curvature over the surface
+    total_curvature: T,
+    /// The area of the surface
+    area: T,
+}
+
+impl<T: FloatingPoint> SurfaceDevelopability<T> {
+    pub fn new(max_gaussian: T, total_curvature: T, area: T) -> Self {
+        Self {
+            max_gaussian,
+            total_curvature,
+            area,
+        }
+    }
+
+    /// The maximum absolute Gaussian curvature
+    pub fn max_gaussian(&self) -> T {
+        self.max_gaussian
+    }
+
+    /// The integral of the absolute Gaussian curvature over the surface,
+    /// the total angle defect in radians that no flattening can avoid
+    pub fn total_curvature(&self) -> T {
+        self.total_curvature
+    }
+
+    pub fn area(&self) -> T {
+        self.area
+    }
+
+    /// Check if the total angle defect of the surface is within the tolerance in radians
+    pub fn is_developable(&self, tolerance: T) -> bool {
+        self.total_curvature <= tolerance
+    }
+}
+
+/// The distortion of the areas & the angles of a surface by its flattening
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UnrollDistortion<T: FloatingPoint> {
+    /// The maximum relative change of the areas
+    max_area: T,
+    /// The average relative change of the areas weighted by the areas
+    mean_area: T,
+    /// The maximum change of the angles in radians
+    max_angle: T,
+    /// The average change of the angles in radians weighted by the areas
+    mean_angle: T,
+}
+
+impl<T: FloatingPoint> UnrollDistortion<T> {
+    pub fn new(max_area: T, mean_area: T, max_angle: T, mean_angle: T) -> Self {
+        Self {
+            max_area,
+            mean_area,
+            max_angle,
+            mean_angle,
+        }
+    }
+
+    pub fn max_area(&self) -> T {
+        self.max_area
+    }
+
+    pub fn mean_area(&self) -> T {
+        self.mean_area
+    }
+
+    pub fn max_angle(&self) -> T {
+        self.max_angle
+    }
+
+    pub fn mean_angle(&self) -> T {
+        self.mean_angle
+    }
+}
+
+impl<T: FloatingPoint> NurbsSurface3D<T> {
+    /// Measure the developability of the surface by integrating the Gaussian curvature at the centers of the grid over the domain
+    /// The degenerate points of the surface are skipped.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let cylinder = NurbsSurface3D::<f64>::try_cylinder(&Point3::origin(), &(Vector3::z() * 2.), 1.).unwrap();
+    /// let developability = cylinder.developability(32, 32);
+    /// assert!(developability.is_developable(1e-6));
+    /// assert_relative_eq!(developability.area(), std::f64::consts::TAU * 2., epsilon = 1e-2);
+    ///
+    /// // the total curvature of the sphere is 4 pi
+    /// let sphere = NurbsSurface3D::<f64>::try_sphere(&Point3::origin(), &Vector3::z(), 2.).unwrap();
+    /// let developability = sphere.developability(32, 32);
+    /// assert!(!developability.is_developable(1e-6));
+    /// assert_relative_eq!(developability.max_gaussian(), 0.25, epsilon = 1e-6);
+    /// assert_relative_eq!(developability.total_curvature(), std::f64::consts::PI * 4., epsilon = 5e-2);
+    /// ```
+    pub fn developability(
+        &self,
+        u_divisions: usize,
+        v_divisions: usize,
+    ) -> SurfaceDevelopability<T> {
+        let (u0, u1) = self.u_knots_domain();
+        let (v0, v1) = self.v_knots_domain();
+        let (nu, nv) = (u_divisions.max(1), v_divisions.max(1));
+        let (du, dv) = (
+            (u1 - u0) / T::from_usize(nu).unwrap(),
+            (v1 - v0) / T::from_usize(nv).unwrap(),
+        );
+        let half = T::from_f64(0.5).unwrap();
+
+        let (mut max_gaussian, mut total_curvature, mut area) = (T::zero(), T::zero(), T::zero());
+        for i in 0..nu {
+            for j in 0..nv {
+                let u = u0 + du * (T::from_usize(i).unwrap() + half);
+                let v = v0 + dv * (T::from_usize(j).unwrap() + half);
+                let d = self.rational_derivatives(u, v, 1);
+                let patch = d[1][0].cross(&d[0][1]).norm() * du * dv;
+                area += patch;
+                if let Ok(curvature) = self.try_curvature_at(u, v) {
+                    let gaussian = curvature.gaussian().abs();
+                    max_gaussian = max_gaussian.max(gaussian);
+                    total_curvature += gaussian * patch;
+                }
+            }
+        }
+        SurfaceDevelopability::new(max_gaussian, total_curvature, area)
+    }
+}
+
+impl<T: FloatingPoint> SurfaceUnrolling<T> {
+    /// Measure the distortion of the unrolling of the surface
+    /// Each triangle of the grid in the plane is compared with the triangle of the distances along the surface between its corners,
+    /// and the degenerate triangles are skipped.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Point4, Vector3};
+    ///
+    /// let cone = NurbsSurface3D::<f64>::try_cone(&Point3::origin(), &(Vector3::z() * 2.), 2., 1.).unwrap();
+    /// let unrolling = cone.try_unroll(&SurfaceUnrollOptions::default()).unwrap();
+    /// let distortion = unrolling.distortion_analysis(&cone);
+    /// assert!(distortion.max_area() < 1e-4);
+    /// assert!(distortion.max_angle() < 1e-3);
+    /// assert!(distortion.mean_angle() < 1e-4);
+    ///
+    /// // the hyperbolic paraboloid can only be flattened by stretching it
+    /// let saddle = NurbsSurface3D::new(
+    ///     1,
+    ///     1,
+    ///     vec![0., 0., 1., 1.],
+    ///     vec![0., 0., 1., 1.],
+    ///     vec![
+    ///         vec![Point4::new(0., 0., 0., 1.), Point4::new(0., 1., 0.5, 1.)],
+    ///         vec![Point4::new(1., 0., 0.5, 1.), Point4::new(1., 1., 0., 1.)],
+    ///     ],
+    /// );
+    /// let options = SurfaceUnrollOptions::default().with_max_distortion(1.);
+    /// let unrolling = saddle.try_unroll(&options).unwrap();
+    /// let distortion = unrolling.distortion_analysis(&saddle);
+    /// assert!(distortion.max_area() > 1e-2);
+    /// ```
+    pub fn distortion_analysis(&self, surface: &NurbsSurface3D<T>) -> UnrollDistortion<T> {
+        let (us, vs, points) = (self.u_parameters(), self.v_parameters(), self.points());
+        let two = T::from_f64(2.).unwrap();
+
+        // the area & the angles of the triangle from the lengths of its edges opposite to the corners
+        let shape = |[a, b, c]: [T; 3]| {
+            let s = (a + b + c) / two;
+            let area = (s * (s - a) * (s - b) * (s - c)).max(T::zero()).sqrt();
+            let angle = |a: T, b: T, c: T| {
+                ((b * b + c * c - a * a) / (two * b * c))
+                    .clamp(-T::one(), T::one())
+                    .acos()
+            };
+            (area, [angle(a, b, c), angle(b, c, a), angle(c, a, b)])
+        };
+
+        let mut max_area = T::zero();
+        let mut max_angle = T::zero();
+        let (mut area_sum, mut angle_sum, mut weight) = (T::zero(), T::zero(), T::zero());
+        let mut measure = |corners: [(usize, usize); 3]| {
+            let uv = corners.map(|(i, j)| Point2::new(us[i], vs[j]));
+            let q = corners.map(|(i, j)| points[i][j]);
+            let (expected, angles) = shape([
+                surface_distance(surface, &uv[1], &uv[2]),
+                surface_distance(surface, &uv[2], &uv[0]),
+                surface_distance(surface, &uv[0], &uv[1]),
+            ]);
+            if expected <= T::default_epsilon() {
+                return;
+            }
+            let (actual, flattened) = shape([
+                (q[2] - q[1]).norm(),
+                (q[0] - q[2]).norm(),
+                (q[1] - q[0]).norm(),
+            ]);
+            let area = (actual / expected - T::one()).abs();
+            let angle = angles
+                .iter()
+                .zip(flattened.iter())
+                .fold(T::zero(), |acc, (a, b)| acc.max((*a - *b).abs()));
+            max_area = max_area.max(area);
+            max_angle = max_angle.max(angle);
+            area_sum += area * expected;
+            angle_sum += angle * expected;
+            weight += expected;
+        };
+        for i in 0..us.len() - 1 {
+            for j in 0..vs.len() - 1 {
+                measure([(i, j), (i + 1, j), (i, j + 1)]);
+                measure([(i + 1, j), (i + 1, j + 1), (i, j + 1)]);
+            }
+        }
+        let mean = |sum: T| {
+            if weight > T::zero() {
+                sum / weight
+            } else {
+                T::zero()
+            }
+        };
+        UnrollDistortion::new(max_area, mean(area_sum), max_angle, mean(angle_sum))
+    }
+}
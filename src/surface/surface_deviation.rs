@@ -0,0 +1,32 @@
+use crate::misc::FloatingPoint;
+
+/// The statistics of the deviation between two surfaces sampled at the same parameters
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SurfaceDeviation<T: FloatingPoint> {
+    max: T,
+    average: T,
+}
+
+impl<T: FloatingPoint> SurfaceDeviation<T> {
+    /// Compute the statistics from the distances at the samples
+    pub fn from_distances(distances: &[T]) -> Self {
+        let max = distances.iter().fold(T::zero(), |acc, d| acc.max(*d));
+        let sum = distances.iter().fold(T::zero(), |acc, d| acc + *d);
+        let average = if distances.is_empty() {
+            T::zero()
+        } else {
+            sum / T::from_usize(distances.len()).unwrap()
+        };
+        Self { max, average }
+    }
+
+    /// The maximum distance
+    pub fn max(&self) -> T {
+        self.max
+    }
+
+    /// The average distance
+    pub fn average(&self) -> T {
+        self.average
+    }
+}
@@ -0,0 +1,234 @@
+use gauss_quad::GaussLegendre;
+use nalgebra::{
+    allocator::Allocator, DMatrix, DVector, DefaultAllocator, DimName, DimNameDiff, DimNameSub, U1,
+};
+
+use crate::{
+    knot::KnotVector,
+    misc::FloatingPoint,
+    surface::{NurbsSurface, SurfaceFairingOptions},
+};
+
+/// The spans of the knot vector between the distinct knots
+fn knot_spans<T: FloatingPoint>(knots: &KnotVector<T>, degree: usize) -> Vec<(usize, T, T)> {
+    (degree..knots.len() - degree - 1)
+        .map(|span| (span, knots[span], knots[span + 1]))
+        .filter(|(_, a, b)| b > a)
+        .collect()
+}
+
+impl<T: FloatingPoint, D> NurbsSurface<T, D>
+where
+    D: DimName + DimNameSub<U1>,
+    DefaultAllocator: Allocator<D>,
+    DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+{
+    /// The weights of the squared second derivatives in the thin plate energy over the domain mapped onto the unit square
+    fn thin_plate_coefficients(&self) -> (T, T, T) {
+        let (u0, u1) = self.u_knots_domain();
+        let (v0, v1) = self.v_knots_domain();
+        let (lu, lv) = (u1 - u0, v1 - v0);
+        let two = T::from_f64(2.).unwrap();
+        (lu.powi(3) / lv, two * lu * lv, lv.powi(3) / lu)
+    }
+
+    /// Compute the thin plate energy, the integral of the sum of the squared second derivatives over the domain
+    /// The domain is mapped onto the unit square so that the energy does not depend on the parameterization range.
+    pub fn thin_plate_energy(&self) -> T {
+        let gauss = GaussLegendre::new(self.u_degree().max(self.v_degree()).max(2) + 1).unwrap();
+        let (cuu, cuv, cvv) = self.thin_plate_coefficients();
+        let half = T::from_f64(0.5).unwrap();
+        let nodes = |a: T, b: T| {
+            let (mid, radius) = ((a + b) * half, (b - a) * half);
+            gauss
+                .as_node_weight_pairs()
+                .iter()
+                .map(move |(x, w)| {
+                    (
+                        mid + radius * T::from_f64(*x).unwrap(),
+                        radius * T::from_f64(*w).unwrap(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        let v_spans = knot_spans(self.v_knots(), self.v_degree());
+        knot_spans(self.u_knots(), self.u_degree())
+            .into_iter()
+            .flat_map(|(_, ua, ub)| nodes(ua, ub))
+            .flat_map(|(u, wu)| {
+                v_spans
+                    .iter()
+                    .flat_map(|(_, va, vb)| nodes(*va, *vb))
+                    .map(move |(v, wv)| (u, v, wu * wv))
+            })
+            .fold(T::zero(), |acc, (u, v, w)| {
+                let d = self.rational_derivatives(u, v, 2);
+                acc + (d[2][0].norm_squared() * cuu
+                    + d[1][1].norm_squared() * cuv
+                    + d[0][2].norm_squared() * cvv)
+                    * w
+            })
+    }
+
+    /// Try to fair the surface by minimizing the thin plate energy with the penalty on the deviation of the control points
+    /// The rows of the control points along the boundaries are pinned, while the knots & the weights are preserved.
+    /// The energy is evaluated on the homogeneous coordinates, which is exact for non-rational surfaces.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::Point4;
+    /// use approx::assert_relative_eq;
+    ///
+    /// // the plane with the bumpy control points inside, placed at the Greville abscissae to be linear in the parameters
+    /// let n = 6;
+    /// let greville = [0., 1. / 9., 1. / 3., 2. / 3., 8. / 9., 1.];
+    /// let control_points = (0..n)
+    ///     .map(|i| {
+    ///         (0..n)
+    ///             .map(|j| {
+    ///                 let inside = i > 0 && i < n - 1 && j > 0 && j < n - 1;
+    ///                 let z = if inside { if (i + j) % 2 == 0 { 0.2 } else { -0.2 } } else { 0. };
+    ///                 Point4::new(greville[i], greville[j], z, 1.)
+    ///             })
+    ///             .collect()
+    ///     })
+    ///     .collect();
+    /// let knots = vec![0., 0., 0., 0., 1. / 3., 2. / 3., 1., 1., 1., 1.];
+    /// let surface = NurbsSurface3D::new(3, 3, knots.clone(), knots, control_points);
+    ///
+    /// let faired = surface.try_fair(&SurfaceFairingOptions::default().with_smoothing(1.)).unwrap();
+    /// assert!(faired.thin_plate_energy() < surface.thin_plate_energy() * 1e-2);
+    ///
+    /// // the boundary rows are pinned
+    /// for i in 0..n {
+    ///     for (a, b) in [(i, 0), (i, n - 1), (0, i), (n - 1, i)] {
+    ///         assert_eq!(faired.control_points()[a][b], surface.control_points()[a][b]);
+    ///     }
+    /// }
+    ///
+    /// // pinning two rows leaves the tangent planes along the boundaries
+    /// let faired = surface
+    ///     .try_fair(&SurfaceFairingOptions::default().with_smoothing(1.).with_pinned_rows(2))
+    ///     .unwrap();
+    /// assert_eq!(faired.control_points()[1][3], surface.control_points()[1][3]);
+    /// assert_relative_eq!(faired.normal_at(0., 0.5).normalize(), surface.normal_at(0., 0.5).normalize(), epsilon = 1e-10);
+    /// ```
+    pub fn try_fair(&self, options: &SurfaceFairingOptions<T>) -> anyhow::Result<Self> {
+        let (pu, pv) = (self.u_degree(), self.v_degree());
+        anyhow::ensure!(
+            pu >= 2 && pv >= 2,
+            "The degrees must be at least 2 to measure the thin plate energy"
+        );
+        anyhow::ensure!(
+            options.smoothing >= T::zero(),
+            "The smoothing must not be negative"
+        );
+        anyhow::ensure!(
+            options.pinned_rows > 0,
+            "At least one row along each boundary must be pinned"
+        );
+
+        let control_points = self.control_points();
+        let (nu, nv) = (control_points.len(), control_points[0].len());
+        let pinned = options.pinned_rows;
+        let free = (0..nu)
+            .flat_map(|i| (0..nv).map(move |j| (i, j)))
+            .filter(|(i, j)| *i >= pinned && *i + pinned < nu && *j >= pinned && *j + pinned < nv)
+            .map(|(i, j)| i * nv + j)
+            .collect::<Vec<_>>();
+        if free.is_empty() {
+            return Ok(self.clone());
+        }
+
+        // the stiffness matrix of the thin plate energy over the tensor product basis functions
+        let gauss = GaussLegendre::new(pu.max(pv) + 1)?;
+        let (cuu, cuv, cvv) = self.thin_plate_coefficients();
+        let half = T::from_f64(0.5).unwrap();
+        let nodes = |a: T, b: T| {
+            let (mid, radius) = ((a + b) * half, (b - a) * half);
+            gauss
+                .as_node_weight_pairs()
+                .iter()
+                .map(|(x, w)| {
+                    (
+                        mid + radius * T::from_f64(*x).unwrap(),
+                        radius * T::from_f64(*w).unwrap(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        let mut stiffness = DMatrix::<T>::zeros(nu * nv, nu * nv);
+        let v_spans = knot_spans(self.v_knots(), pv);
+        for (u_span, ua, ub) in knot_spans(self.u_knots(), pu) {
+            for (u, wu) in nodes(ua, ub) {
+                let du = self.u_knots().derivative_basis_functions(u_span, u, pu, 2);
+                for (v_span, va, vb) in v_spans.iter() {
+                    for (v, wv) in nodes(*va, *vb) {
+                        let dv = self.v_knots().derivative_basis_functions(*v_span, v, pv, 2);
+                        let local = (0..=pu)
+                            .flat_map(|r| (0..=pv).map(move |s| (r, s)))
+                            .map(|(r, s)| {
+                                (
+                                    (u_span - pu + r) * nv + v_span - pv + s,
+                                    [
+                                        du[2][r] * dv[0][s],
+                                        du[1][r] * dv[1][s],
+                                        du[0][r] * dv[2][s],
+                                    ],
+                                )
+                            })
+                            .collect::<Vec<_>>();
+                        let w = wu * wv;
+                        for (a, da) in local.iter() {
+                            for (b, db) in local.iter() {
+                                stiffness[(*a, *b)] += (da[0] * db[0] * cuu
+                                    + da[1] * db[1] * cuv
+                                    + da[2] * db[2] * cvv)
+                                    * w;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        let stiffness = stiffness * options.smoothing;
+
+        // the free control points minimize the energy with the pinned ones as the boundary condition
+        let m = free.len();
+        let mut is_free = vec![false; nu * nv];
+        free.iter().for_each(|i| is_free[*i] = true);
+        let pinned = (0..nu * nv).filter(|i| !is_free[*i]).collect::<Vec<_>>();
+        let lhs = DMatrix::from_fn(m, m, |a, b| {
+            let k = stiffness[(free[a], free[b])];
+            if a == b {
+                k + T::one()
+            } else {
+                k
+            }
+        });
+        let coupling = DMatrix::from_fn(m, pinned.len(), |a, b| stiffness[(free[a], pinned[b])]);
+        let lu = lhs.lu();
+
+        let mut faired = control_points.clone();
+        for c in 0..D::dim() - 1 {
+            let coordinate = |i: usize| control_points[i / nv][i % nv][c];
+            let original = DVector::from_iterator(m, free.iter().map(|i| coordinate(*i)));
+            let boundary =
+                DVector::from_iterator(pinned.len(), pinned.iter().map(|i| coordinate(*i)));
+            let x = lu
+                .solve(&(original - coupling.clone() * boundary))
+                .ok_or(anyhow::anyhow!("Failed to solve the fairing system"))?;
+            free.iter()
+                .zip(x.iter())
+                .for_each(|(i, x)| faired[i / nv][i % nv][c] = *x);
+        }
+
+        Ok(Self::new(
+            pu,
+            pv,
+            self.u_knots().to_vec(),
+            self.v_knots().to_vec(),
+            faired,
+        ))
+    }
+}
@@ -0,0 +1,32 @@
+use crate::misc::FloatingPoint;
+
+/// Options for fairing a surface by minimizing the thin plate energy
+#[derive(Clone, Debug)]
+pub struct SurfaceFairingOptions<T: FloatingPoint> {
+    /// The weight of the energy against the deviation from the original control points
+    pub smoothing: T,
+    /// The number of the rows of the control points pinned along each boundary,
+    /// where 1 keeps the boundary curves & 2 also keeps the tangent planes across them
+    pub pinned_rows: usize,
+}
+
+impl<T: FloatingPoint> Default for SurfaceFairingOptions<T> {
+    fn default() -> Self {
+        Self {
+            smoothing: T::from_f64(1e-2).unwrap(),
+            pinned_rows: 1,
+        }
+    }
+}
+
+impl<T: FloatingPoint> SurfaceFairingOptions<T> {
+    pub fn with_smoothing(mut self, smoothing: T) -> Self {
+        self.smoothing = smoothing;
+        self
+    }
+
+    pub fn with_pinned_rows(mut self, pinned_rows: usize) -> Self {
+        self.pinned_rows = pinned_rows;
+        self
+    }
+}
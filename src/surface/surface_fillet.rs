@@ -0,0 +1,309 @@
+use argmin::core::ArgminFloat;
+use nalgebra::{Matrix2, Point2, Point3, Point4, Vector2};
+
+use crate::{
+    curve::{CompoundCurve2D, NurbsCurve2D, NurbsCurve3D},
+    misc::FloatingPoint,
+    surface::{NurbsSurface3D, TrimmedSurface},
+};
+
+/// The maximum number of iterations to find the point of contact of the ball on the surface
+const MAX_ITERATIONS: usize = 32;
+
+/// A struct representing a fillet surface between two surfaces with the surfaces trimmed along the fillet
+#[derive(Clone, Debug)]
+pub struct SurfaceFillet<T: FloatingPoint> {
+    /// The fillet surface, running from the first surface to the second surface in the u direction
+    surface: NurbsSurface3D<T>,
+    /// The first surface trimmed by the curve of contact
+    a: TrimmedSurface<T>,
+    /// The second surface trimmed by the curve of contact
+    b: TrimmedSurface<T>,
+}
+
+impl<T: FloatingPoint> SurfaceFillet<T> {
+    pub fn new(surface: NurbsSurface3D<T>, a: TrimmedSurface<T>, b: TrimmedSurface<T>) -> Self {
+        Self { surface, a, b }
+    }
+
+    pub fn surface(&self) -> &NurbsSurface3D<T> {
+        &self.surface
+    }
+
+    /// Get the first surface trimmed by the fillet
+    pub fn a(&self) -> &TrimmedSurface<T> {
+        &self.a
+    }
+
+    /// Get the second surface trimmed by the fillet
+    pub fn b(&self) -> &TrimmedSurface<T> {
+        &self.b
+    }
+
+    pub fn into_parts(self) -> (NurbsSurface3D<T>, TrimmedSurface<T>, TrimmedSurface<T>) {
+        (self.surface, self.a, self.b)
+    }
+}
+
+impl<T: FloatingPoint + ArgminFloat> NurbsSurface3D<T> {
+    /// Create the rolling ball fillet of the radius between the surface & the other surface intersecting it
+    /// The ball rolls on the sides the normals of the surfaces point to, so flip the surfaces to fillet the other sides.
+    /// The center of the ball follows the intersection of the offset surfaces, and the fillet surface is lofted from the circular arcs between the points of contact.
+    /// The surfaces are trimmed by the curves of contact to the sides away from their intersection,
+    /// which must not cross the seam of a closed surface.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// // the floor facing up & the wall facing towards -x
+    /// let line = NurbsCurve3D::polyline(&[Point3::new(-2., 0., 0.), Point3::new(2., 0., 0.)]);
+    /// let floor = NurbsSurface::extrude(&line, &(Vector3::y() * 2.));
+    /// let line = NurbsCurve3D::polyline(&[Point3::new(0., 0., -2.), Point3::new(0., 0., 2.)]);
+    /// let wall = NurbsSurface::extrude(&line, &(Vector3::y() * 2.));
+    ///
+    /// let fillet = floor.try_fillet(&wall, 0.5, 1e-6).unwrap();
+    /// let surface = fillet.surface();
+    /// let (u0, u1) = surface.u_knots_domain();
+    /// let (v0, v1) = surface.v_knots_domain();
+    /// for i in 0..=4 {
+    ///     for j in 0..=4 {
+    ///         let u = u0 + (u1 - u0) * i as f64 / 4.;
+    ///         let v = v0 + (v1 - v0) * j as f64 / 4.;
+    ///         // the fillet is the quarter of the cylinder around the axis at x = -0.5 & z = 0.5
+    ///         let p = surface.point_at(u, v);
+    ///         assert_relative_eq!(((p.x + 0.5).powi(2) + (p.z - 0.5).powi(2)).sqrt(), 0.5, epsilon = 1e-6);
+    ///     }
+    /// }
+    /// assert_relative_eq!(surface.point_at(u0, v0).z, 0., epsilon = 1e-6);
+    /// assert_relative_eq!(surface.point_at(u1, v0).x, 0., epsilon = 1e-6);
+    ///
+    /// // the floor is kept at x < -0.5 & the wall at z > 0.5
+    /// assert!(fillet.a().contains(0.5, 0.2));
+    /// assert!(!fillet.a().contains(0.5, 0.6));
+    /// assert!(fillet.b().contains(0.5, 0.8));
+    /// assert!(!fillet.b().contains(0.5, 0.3));
+    /// ```
+    pub fn try_fillet(
+        &self,
+        other: &Self,
+        radius: T,
+        tolerance: T,
+    ) -> anyhow::Result<SurfaceFillet<T>> {
+        anyhow::ensure!(radius > T::zero(), "The radius must be greater than zero");
+        let intersection = self
+            .find_intersection(other, None)?
+            .into_iter()
+            .next()
+            .ok_or(anyhow::anyhow!("The surfaces do not intersect"))?;
+        let spine = self
+            .try_offset(radius, tolerance)?
+            .find_intersection(&other.try_offset(radius, tolerance)?, None)?
+            .into_iter()
+            .next()
+            .ok_or(anyhow::anyhow!(
+                "The ball of the radius does not touch both surfaces"
+            ))?
+            .into_curve();
+
+        // the points of contact on both surfaces for the centers sampled along the spine
+        let (start, end) = spine.knots_domain();
+        let n = (spine.control_points().len() * 2).clamp(8, 64);
+        let first = spine.point_at(start);
+        let mut uv = (
+            self.find_closest_parameter(&first)?,
+            other.find_closest_parameter(&first)?,
+        );
+        let mut contacts = (vec![], vec![]);
+        let mut arcs = vec![];
+        for i in 0..=n {
+            let t = start + (end - start) * T::from_usize(i).unwrap() / T::from_usize(n).unwrap();
+            let center = spine.point_at(t);
+            uv = (
+                foot_parameter(self, &center, uv.0),
+                foot_parameter(other, &center, uv.1),
+            );
+            let pa = self.point_at(uv.0 .0, uv.0 .1);
+            let pb = other.point_at(uv.1 .0, uv.1 .1);
+            arcs.push(try_arc(&pa, &pb, &center)?);
+            contacts.0.push(Point2::new(uv.0 .0, uv.0 .1));
+            contacts.1.push(Point2::new(uv.1 .0, uv.1 .1));
+        }
+        let surface = Self::try_loft(&arcs, Some(3))?;
+
+        // the intersection of the surfaces lies in the parts to be removed
+        let mid = |c: &NurbsCurve2D<T>| {
+            let (s, e) = c.knots_domain();
+            c.point_at((s + e) * T::from_f64(0.5).unwrap())
+        };
+        let a = try_trim_by_contact(self, contacts.0, &mid(intersection.a()))?;
+        let b = try_trim_by_contact(other, contacts.1, &mid(intersection.b()))?;
+        Ok(SurfaceFillet::new(surface, a, b))
+    }
+}
+
+/// Find the parameter of the foot of the perpendicular from the point onto the surface by the Gauss-Newton method
+pub(crate) fn foot_parameter<T: FloatingPoint>(
+    surface: &NurbsSurface3D<T>,
+    point: &Point3<T>,
+    (mut u, mut v): (T, T),
+) -> (T, T) {
+    let (u0, u1) = surface.u_knots_domain();
+    let (v0, v1) = surface.v_knots_domain();
+    let eps = (u1 - u0).max(v1 - v0) * T::default_epsilon();
+    for _ in 0..MAX_ITERATIONS {
+        let d = surface.rational_derivatives(u, v, 1);
+        let (su, sv) = (&d[1][0], &d[0][1]);
+        let r = point.coords - d[0][0];
+        let m = Matrix2::new(su.dot(su), su.dot(sv), su.dot(sv), sv.dot(sv));
+        let Some(delta) = m.lu().solve(&Vector2::new(su.dot(&r), sv.dot(&r))) else {
+            break;
+        };
+        u = (u + delta.x).clamp(u0, u1);
+        v = (v + delta.y).clamp(v0, v1);
+        if delta.norm() <= eps {
+            break;
+        }
+    }
+    (u, v)
+}
+
+/// Create the circular arc from the point to the other point around the center as the rational quadratic curve
+fn try_arc<T: FloatingPoint>(
+    start: &Point3<T>,
+    end: &Point3<T>,
+    center: &Point3<T>,
+) -> anyhow::Result<NurbsCurve3D<T>> {
+    let (a, b) = (start - center, end - center);
+    let radius = (a.norm() + b.norm()) * T::from_f64(0.5).unwrap();
+    let cos = a.normalize().dot(&b.normalize());
+    anyhow::ensure!(
+        cos > -T::one() + T::default_epsilon().sqrt(),
+        "The points of contact are opposite to each other around the ball"
+    );
+    // the weight is the cosine of the half of the sweep angle
+    let weight = ((T::one() + cos) * T::from_f64(0.5).unwrap()).sqrt();
+    let middle = center + (a.normalize() + b.normalize()).normalize() * (radius / weight);
+    let homogeneous = |p: &Point3<T>, w: T| Point4::new(p.x * w, p.y * w, p.z * w, w);
+    NurbsCurve3D::try_new(
+        2,
+        vec![
+            homogeneous(start, T::one()),
+            homogeneous(&middle, weight),
+            homogeneous(end, T::one()),
+        ],
+        vec![
+            T::zero(),
+            T::zero(),
+            T::zero(),
+            T::one(),
+            T::one(),
+            T::one(),
+        ],
+    )
+}
+
+/// Trim the surface by the curve of contact in the parameter space, removing the side containing the parameter
+/// The open curve is extended along its tangents to the boundary of the domain, and closed along the boundary.
+fn try_trim_by_contact<T: FloatingPoint>(
+    surface: &NurbsSurface3D<T>,
+    mut uvs: Vec<Point2<T>>,
+    removed: &Point2<T>,
+) -> anyhow::Result<TrimmedSurface<T>> {
+    let (u0, u1) = surface.u_knots_domain();
+    let (v0, v1) = surface.v_knots_domain();
+    let size = Vector2::new(u1 - u0, v1 - v0).norm();
+    let eps = size * T::from_f64(1e-6).unwrap();
+    uvs.dedup_by(|a, b| (*a - *b).norm() < eps);
+    anyhow::ensure!(uvs.len() > 1, "The curve of contact is degenerate");
+    let degree = (uvs.len() - 1).min(3);
+    let n = uvs.len();
+
+    let candidates = if (uvs[0] - uvs[n - 1]).norm() < size * T::from_f64(1e-4).unwrap() {
+        uvs[n - 1] = uvs[0];
+        let curve = CompoundCurve2D::from(NurbsCurve2D::try_interpolate(&uvs, degree)?);
+        vec![
+            TrimmedSurface::try_new(surface.clone(), Some(curve.clone()), vec![])?,
+            TrimmedSurface::try_new(surface.clone(), None, vec![curve])?,
+        ]
+    } else {
+        // the point where the ray from the end of the curve leaves the domain
+        let extend = |p: &Point2<T>, direction: Vector2<T>| {
+            let bounds = [(p.x - u0, p.x - u1), (p.y - v0, p.y - v1)];
+            if bounds.iter().any(|(l, h)| l.abs() <= eps || h.abs() <= eps) {
+                return *p;
+            }
+            let t = (0..2)
+                .filter(|i| direction[*i].abs() > T::default_epsilon())
+                .map(|i| {
+                    let (l, h) = bounds[i];
+                    (-l / direction[i]).max(-h / direction[i])
+                })
+                .fold(T::max_value().unwrap(), |a, b| a.min(b));
+            let q = p + direction * t;
+            Point2::new(q.x.clamp(u0, u1), q.y.clamp(v0, v1))
+        };
+        let head = extend(&uvs[0], uvs[0] - uvs[1]);
+        let tail = extend(&uvs[n - 1], uvs[n - 1] - uvs[n - 2]);
+        let mut spans = vec![];
+        if (head - uvs[0]).norm() > eps {
+            spans.push(NurbsCurve2D::polyline(&[head, uvs[0]]));
+        }
+        spans.push(NurbsCurve2D::try_interpolate(&uvs, degree)?);
+        if (tail - uvs[n - 1]).norm() > eps {
+            spans.push(NurbsCurve2D::polyline(&[uvs[n - 1], tail]));
+        }
+
+        // the coordinate along the boundary counterclockwise from the corner at (u0, v0), with the corners at the integers
+        let perimeter = |p: &Point2<T>| {
+            let (x, y) = ((p.x - u0) / (u1 - u0), (p.y - v0) / (v1 - v0));
+            let distances = [y, T::one() - x, T::one() - y, x];
+            let edge = (0..4)
+                .min_by(|i, j| distances[*i].partial_cmp(&distances[*j]).unwrap())
+                .unwrap();
+            let along = [x, y, T::one() - x, T::one() - y][edge];
+            T::from_usize(edge).unwrap() + along
+        };
+        let corners = [
+            Point2::new(u0, v0),
+            Point2::new(u1, v0),
+            Point2::new(u1, v1),
+            Point2::new(u0, v1),
+        ];
+        // the boundary counterclockwise from the point to the other point
+        let boundary = |from: &Point2<T>, to: &Point2<T>| {
+            let (f, mut t) = (perimeter(from), perimeter(to));
+            let four = T::from_usize(4).unwrap();
+            if t <= f {
+                t += four;
+            }
+            let mut points = vec![*from];
+            let mut k = f.floor() + T::one();
+            while k < t {
+                points.push(corners[(k % four).to_usize().unwrap()]);
+                k += T::one();
+            }
+            points.push(*to);
+            points
+        };
+        let forward = boundary(&tail, &head);
+        let mut backward = boundary(&head, &tail);
+        backward.reverse();
+        [forward, backward]
+            .iter()
+            .map(|points| {
+                let mut spans = spans.clone();
+                spans.push(NurbsCurve2D::polyline(points));
+                let exterior = CompoundCurve2D::try_new(spans)?;
+                TrimmedSurface::try_new(surface.clone(), Some(exterior), vec![])
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?
+    };
+    candidates
+        .into_iter()
+        .find(|trimmed| !trimmed.contains(removed.x, removed.y))
+        .ok_or(anyhow::anyhow!(
+            "The curve of contact does not separate the intersection of the surfaces"
+        ))
+}
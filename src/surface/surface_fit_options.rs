@@ -0,0 +1,72 @@
+use crate::misc::FloatingPoint;
+
+/// Options for fitting a surface to the scattered points
+#[derive(Clone, Debug)]
+pub struct SurfaceFitOptions<T: FloatingPoint> {
+    /// The degree of the surface in the u direction
+    pub u_degree: usize,
+    /// The degree of the surface in the v direction
+    pub v_degree: usize,
+    /// The initial number of the control points in the u direction
+    pub u_control_points_count: usize,
+    /// The initial number of the control points in the v direction
+    pub v_control_points_count: usize,
+    /// The maximum number of the control points in each direction, which bounds the knot refinement
+    pub max_control_points_count: usize,
+    /// The distance from the points to the surface which stops the refinement
+    pub tolerance: T,
+    /// The weight of the penalty on the second differences of the control points relative to the fitting term,
+    /// which smooths the surface & keeps the control points without the nearby points determined
+    pub smoothing: T,
+    /// The maximum number of the iterations of the fitting, the parameter correction & the knot refinement
+    pub max_iterations: usize,
+}
+
+impl<T: FloatingPoint> Default for SurfaceFitOptions<T> {
+    fn default() -> Self {
+        Self {
+            u_degree: 3,
+            v_degree: 3,
+            u_control_points_count: 4,
+            v_control_points_count: 4,
+            max_control_points_count: 16,
+            tolerance: T::from_f64(1e-3).unwrap(),
+            smoothing: T::from_f64(1e-6).unwrap(),
+            max_iterations: 8,
+        }
+    }
+}
+
+impl<T: FloatingPoint> SurfaceFitOptions<T> {
+    pub fn with_degrees(mut self, u_degree: usize, v_degree: usize) -> Self {
+        self.u_degree = u_degree;
+        self.v_degree = v_degree;
+        self
+    }
+
+    pub fn with_control_points_count(mut self, u_count: usize, v_count: usize) -> Self {
+        self.u_control_points_count = u_count;
+        self.v_control_points_count = v_count;
+        self
+    }
+
+    pub fn with_max_control_points_count(mut self, max_control_points_count: usize) -> Self {
+        self.max_control_points_count = max_control_points_count;
+        self
+    }
+
+    pub fn with_tolerance(mut self, tolerance: T) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn with_smoothing(mut self, smoothing: T) -> Self {
+        self.smoothing = smoothing;
+        self
+    }
+
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+}
@@ -0,0 +1,180 @@
+use nalgebra::{Point2, Point3};
+
+use crate::{
+    curve::{NurbsCurve2D, NurbsCurve3D},
+    misc::FloatingPoint,
+    surface::{surface_fillet::foot_parameter, NurbsSurface3D},
+};
+
+/// The maximum number of the refinements doubling the segments of the path
+const MAX_REFINEMENTS: usize = 7;
+
+/// The maximum number of the relaxation sweeps over the path at each refinement
+const MAX_RELAXATIONS: usize = 256;
+
+/// A geodesic path on a surface between two points in the parameter space
+#[derive(Clone, Debug)]
+pub struct SurfaceGeodesic<T: FloatingPoint> {
+    /// The parameters of the vertices of the relaxed path
+    parameters: Vec<Point2<T>>,
+    /// The points of the vertices of the relaxed path
+    points: Vec<Point3<T>>,
+    /// The curve interpolating the parameters in the parameter space
+    uv_curve: NurbsCurve2D<T>,
+    /// The curve interpolating the points in 3D space
+    curve: NurbsCurve3D<T>,
+    /// The length of the relaxed path
+    length: T,
+}
+
+impl<T: FloatingPoint> SurfaceGeodesic<T> {
+    pub fn new(
+        parameters: Vec<Point2<T>>,
+        points: Vec<Point3<T>>,
+        uv_curve: NurbsCurve2D<T>,
+        curve: NurbsCurve3D<T>,
+        length: T,
+    ) -> Self {
+        Self {
+            parameters,
+            points,
+            uv_curve,
+            curve,
+            length,
+        }
+    }
+
+    pub fn parameters(&self) -> &[Point2<T>] {
+        &self.parameters
+    }
+
+    pub fn points(&self) -> &[Point3<T>] {
+        &self.points
+    }
+
+    pub fn uv_curve(&self) -> &NurbsCurve2D<T> {
+        &self.uv_curve
+    }
+
+    pub fn curve(&self) -> &NurbsCurve3D<T> {
+        &self.curve
+    }
+
+    pub fn length(&self) -> T {
+        self.length
+    }
+}
+
+impl<T: FloatingPoint> NurbsSurface3D<T> {
+    /// Try to find the geodesic path between the parameters on the surface by the relaxation of the path
+    /// Starting from the straight line in the parameter space, each vertex of the path is moved to the foot of the perpendicular
+    /// from the middle of its neighbors onto the surface, and the path is refined by doubling the segments
+    /// until the change of the length falls within the tolerance.
+    /// The relaxation finds the locally shortest path near the initial line, which is not necessarily the shortest one.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point2, Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// // the geodesic on the cylinder is the helix
+    /// let cylinder = NurbsSurface3D::<f64>::try_cylinder(&Point3::origin(), &(Vector3::z() * 2.), 1.).unwrap();
+    /// let (a, b) = (Point3::new(1., 0., 0.5), Point3::new(0., 1., 1.5));
+    /// let start = cylinder.find_closest_parameter(&a).unwrap();
+    /// let end = cylinder.find_closest_parameter(&b).unwrap();
+    /// let geodesic = cylinder
+    ///     .try_geodesic(&Point2::new(start.0, start.1), &Point2::new(end.0, end.1), 1e-5)
+    ///     .unwrap();
+    /// let quarter = std::f64::consts::FRAC_PI_2;
+    /// assert_relative_eq!(geodesic.length(), (quarter * quarter + 1.).sqrt(), epsilon = 1e-3);
+    ///
+    /// // the curve runs on the surface through the point at the middle of the helix
+    /// let (t0, t1) = geodesic.curve().knots_domain();
+    /// assert_relative_eq!(geodesic.curve().point_at(t0), a, epsilon = 1e-8);
+    /// assert_relative_eq!(geodesic.curve().point_at(t1), b, epsilon = 1e-8);
+    /// let middle = geodesic.points()[geodesic.points().len() / 2];
+    /// let angle = std::f64::consts::FRAC_PI_4;
+    /// assert_relative_eq!(middle, Point3::new(angle.cos(), angle.sin(), 1.), epsilon = 1e-3);
+    /// for p in geodesic.points() {
+    ///     assert_relative_eq!(p.coords.xy().norm(), 1., epsilon = 1e-8);
+    /// }
+    /// let (s0, s1) = geodesic.uv_curve().knots_domain();
+    /// let uv = geodesic.uv_curve().point_at((s0 + s1) / 2.);
+    /// assert_relative_eq!(cylinder.point_at(uv.x, uv.y).coords.xy().norm(), 1., epsilon = 1e-8);
+    /// ```
+    pub fn try_geodesic(
+        &self,
+        start: &Point2<T>,
+        end: &Point2<T>,
+        tolerance: T,
+    ) -> anyhow::Result<SurfaceGeodesic<T>> {
+        anyhow::ensure!(
+            tolerance > T::zero(),
+            "The tolerance must be greater than zero"
+        );
+        let evaluate = |p: &Point2<T>| self.point_at(p.x, p.y);
+        anyhow::ensure!(
+            (evaluate(start) - evaluate(end)).norm() > tolerance,
+            "The end points of the geodesic must be apart"
+        );
+
+        let half = T::from_f64(0.5).unwrap();
+        let segments = 8;
+        let mut parameters = (0..=segments)
+            .map(|i| {
+                start
+                    + (end - start) * (T::from_usize(i).unwrap() / T::from_usize(segments).unwrap())
+            })
+            .collect::<Vec<_>>();
+        let mut points = parameters.iter().map(evaluate).collect::<Vec<_>>();
+        let polyline_length = |points: &[Point3<T>]| {
+            points
+                .windows(2)
+                .fold(T::zero(), |acc, w| acc + (w[1] - w[0]).norm())
+        };
+
+        let mut length: Option<T> = None;
+        for refinement in 0..=MAX_REFINEMENTS {
+            // the Gauss-Seidel sweeps pulling each vertex onto the middle of its neighbors
+            for _ in 0..MAX_RELAXATIONS {
+                let mut shift = T::zero();
+                for i in 1..parameters.len() - 1 {
+                    let middle = points[i - 1] + (points[i + 1] - points[i - 1]) * half;
+                    let (u, v) = foot_parameter(self, &middle, (parameters[i].x, parameters[i].y));
+                    let p = self.point_at(u, v);
+                    shift = shift.max((p - points[i]).norm());
+                    parameters[i] = Point2::new(u, v);
+                    points[i] = p;
+                }
+                if shift <= tolerance * T::from_f64(1e-2).unwrap() {
+                    break;
+                }
+            }
+
+            let current = polyline_length(&points);
+            let converged = length.is_some_and(|l| (l - current).abs() <= tolerance);
+            length = Some(current);
+            if converged || refinement == MAX_REFINEMENTS {
+                break;
+            }
+
+            // insert the vertices at the middles of the segments in the parameter space
+            parameters = parameters
+                .windows(2)
+                .flat_map(|w| [w[0], w[0] + (w[1] - w[0]) * half])
+                .chain(std::iter::once(*end))
+                .collect();
+            points = parameters.iter().map(evaluate).collect();
+        }
+
+        let uv_curve = NurbsCurve2D::try_interpolate(&parameters, 3)?;
+        let curve = NurbsCurve3D::try_interpolate(&points, 3)?;
+        Ok(SurfaceGeodesic::new(
+            parameters,
+            points,
+            uv_curve,
+            curve,
+            length.unwrap(),
+        ))
+    }
+}
@@ -0,0 +1,317 @@
+use std::collections::{HashMap, HashSet};
+
+use nalgebra::{
+    allocator::Allocator, DefaultAllocator, DimName, DimNameDiff, DimNameSub, Point2, Point3, U1,
+};
+
+use crate::{
+    curve::{NurbsCurve2D, NurbsCurve3D},
+    misc::FloatingPoint,
+    surface::{NurbsSurface, NurbsSurface3D},
+};
+
+/// Number of bisection iterations to find the zero crossing on an edge of the grid
+const EDGE_BISECTION_ITERATIONS: usize = 48;
+
+/// An edge of the sampling grid in the parameter space
+/// `(false, i, j)` is the edge between the grid points (i, j) & (i + 1, j),
+/// `(true, i, j)` is the edge between the grid points (i, j) & (i, j + 1).
+type GridEdge = (bool, usize, usize);
+
+/// Tracer of the level set `field(u, v) = 0` of a scalar field in the parameter space of a surface
+/// by marching squares on a sampling grid.
+/// The zero crossings on the grid edges are refined by bisection on the exact field,
+/// so the traced points lie on the level set within the numerical precision.
+pub(crate) struct SurfaceLevelSetTracer<T, F> {
+    domain: ((T, T), (T, T)),
+    divisions: (usize, usize),
+    field: F,
+}
+
+impl<T: FloatingPoint, F: Fn(T, T) -> T> SurfaceLevelSetTracer<T, F> {
+    /// Create a tracer with the sampling grid fitted to the spans of the surface
+    pub fn new<D>(surface: &NurbsSurface<T, D>, field: F) -> Self
+    where
+        D: DimName + DimNameSub<U1>,
+        DefaultAllocator: Allocator<D>,
+        DefaultAllocator: Allocator<DimNameDiff<D, U1>>,
+    {
+        let divs = |n: usize, degree: usize| (n * degree * 4).max(32);
+        Self {
+            domain: (surface.u_knots_domain(), surface.v_knots_domain()),
+            divisions: (
+                divs(surface.control_points().len(), surface.u_degree()),
+                divs(surface.control_points()[0].len(), surface.v_degree()),
+            ),
+            field,
+        }
+    }
+
+    /// Get the parameter of the grid point
+    fn grid_point(&self, i: usize, j: usize) -> Point2<T> {
+        let ((u0, u1), (v0, v1)) = self.domain;
+        let (nu, nv) = self.divisions;
+        Point2::new(
+            u0 + (u1 - u0) * T::from_usize(i).unwrap() / T::from_usize(nu).unwrap(),
+            v0 + (v1 - v0) * T::from_usize(j).unwrap() / T::from_usize(nv).unwrap(),
+        )
+    }
+
+    /// Evaluate the field avoiding the exact zero to keep the sign classification consistent
+    fn evaluate(&self, uv: &Point2<T>) -> T {
+        let f = (self.field)(uv.x, uv.y);
+        if f == T::zero() {
+            T::default_epsilon()
+        } else {
+            f
+        }
+    }
+
+    /// Find the zero crossing on the segment between the parameters by bisection
+    fn bisect(&self, mut a: Point2<T>, mut fa: T, mut b: Point2<T>) -> Point2<T> {
+        let half = T::from_f64(0.5).unwrap();
+        for _ in 0..EDGE_BISECTION_ITERATIONS {
+            let m = a + (b - a) * half;
+            let fm = self.evaluate(&m);
+            if (fm > T::zero()) == (fa > T::zero()) {
+                a = m;
+                fa = fm;
+            } else {
+                b = m;
+            }
+        }
+        a + (b - a) * half
+    }
+
+    /// Trace the level set and return the polylines in the parameter space
+    /// Each polyline is returned with the flag whether it forms a closed loop in the grid.
+    pub fn trace(&self) -> Vec<(Vec<Point2<T>>, bool)> {
+        let (nu, nv) = self.divisions;
+        let values: Vec<Vec<T>> = (0..=nu)
+            .map(|i| {
+                (0..=nv)
+                    .map(|j| self.evaluate(&self.grid_point(i, j)))
+                    .collect()
+            })
+            .collect();
+        let positive = |i: usize, j: usize| values[i][j] > T::zero();
+
+        // find the zero crossings on the grid edges
+        let mut crossings: HashMap<GridEdge, Point2<T>> = HashMap::new();
+        for i in 0..=nu {
+            for j in 0..=nv {
+                if i < nu && positive(i, j) != positive(i + 1, j) {
+                    let p = self.bisect(
+                        self.grid_point(i, j),
+                        values[i][j],
+                        self.grid_point(i + 1, j),
+                    );
+                    crossings.insert((false, i, j), p);
+                }
+                if j < nv && positive(i, j) != positive(i, j + 1) {
+                    let p = self.bisect(
+                        self.grid_point(i, j),
+                        values[i][j],
+                        self.grid_point(i, j + 1),
+                    );
+                    crossings.insert((true, i, j), p);
+                }
+            }
+        }
+
+        // link the crossings in each cell
+        let mut links: HashMap<GridEdge, Vec<GridEdge>> = HashMap::new();
+        let mut link = |a: GridEdge, b: GridEdge| {
+            links.entry(a).or_default().push(b);
+            links.entry(b).or_default().push(a);
+        };
+        for i in 0..nu {
+            for j in 0..nv {
+                let bottom = (false, i, j);
+                let right = (true, i + 1, j);
+                let top = (false, i, j + 1);
+                let left = (true, i, j);
+                let edges: Vec<_> = [bottom, right, top, left]
+                    .into_iter()
+                    .filter(|e| crossings.contains_key(e))
+                    .collect();
+                match edges.len() {
+                    2 => link(edges[0], edges[1]),
+                    4 => {
+                        // resolve the saddle cell by the sign at the center
+                        let center = self.grid_point(i, j)
+                            + (self.grid_point(i + 1, j + 1) - self.grid_point(i, j))
+                                * T::from_f64(0.5).unwrap();
+                        let c = self.evaluate(&center) > T::zero();
+                        if c == positive(i, j) {
+                            link(bottom, right);
+                            link(top, left);
+                        } else {
+                            link(bottom, left);
+                            link(right, top);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        // walk the chains of the linked crossings
+        let mut visited: HashSet<GridEdge> = HashSet::new();
+        let mut polylines = vec![];
+        let walk = |start: GridEdge, visited: &mut HashSet<GridEdge>| {
+            let mut chain = vec![start];
+            visited.insert(start);
+            let mut current = start;
+            loop {
+                let next = links
+                    .get(&current)
+                    .and_then(|n| n.iter().find(|e| !visited.contains(*e)).cloned());
+                match next {
+                    Some(n) => {
+                        visited.insert(n);
+                        chain.push(n);
+                        current = n;
+                    }
+                    None => break,
+                }
+            }
+            let closed = chain.len() > 2
+                && links
+                    .get(&current)
+                    .map(|n| n.contains(&start))
+                    .unwrap_or(false);
+            (chain, closed)
+        };
+
+        let mut keys: Vec<_> = crossings.keys().cloned().collect();
+        keys.sort();
+
+        // the open chains start from the crossings on the boundary of the domain
+        for key in keys.iter() {
+            let degree = links.get(key).map(|n| n.len()).unwrap_or(0);
+            if degree <= 1 && !visited.contains(key) {
+                polylines.push(walk(*key, &mut visited));
+            }
+        }
+        for key in keys.iter() {
+            if !visited.contains(key) {
+                polylines.push(walk(*key, &mut visited));
+            }
+        }
+
+        polylines
+            .into_iter()
+            .filter(|(chain, _)| chain.len() > 1)
+            .map(|(chain, closed)| {
+                let mut points: Vec<_> = chain.iter().map(|e| crossings[e]).collect();
+                if closed {
+                    points.push(points[0]);
+                }
+                (points, closed)
+            })
+            .collect()
+    }
+}
+
+/// Convert the traced polylines in the parameter space into NURBS curves on the surface
+/// The open polylines connected in 3D space (e.g., across the seam of a closed surface) are joined.
+pub(crate) fn level_set_curves<T: FloatingPoint>(
+    surface: &NurbsSurface3D<T>,
+    polylines: Vec<(Vec<Point2<T>>, bool)>,
+    tolerance: T,
+) -> anyhow::Result<Vec<NurbsCurve3D<T>>> {
+    let mut closed = vec![];
+    let mut open: Vec<Vec<Point3<T>>> = vec![];
+    for (polyline, is_closed) in polylines {
+        let points: Vec<_> = polyline
+            .iter()
+            .map(|uv| surface.point_at(uv.x, uv.y))
+            .collect();
+        if is_closed {
+            closed.push(points);
+        } else {
+            open.push(points);
+        }
+    }
+
+    // join the open polylines whose end points coincide
+    let mut joined = vec![];
+    while let Some(mut current) = open.pop() {
+        loop {
+            let head = current[0];
+            let tail = current[current.len() - 1];
+            let found = open.iter().enumerate().find_map(|(i, other)| {
+                let (h, t) = (other[0], other[other.len() - 1]);
+                if (tail - h).norm() < tolerance {
+                    Some((i, false, false))
+                } else if (tail - t).norm() < tolerance {
+                    Some((i, false, true))
+                } else if (head - t).norm() < tolerance {
+                    Some((i, true, false))
+                } else if (head - h).norm() < tolerance {
+                    Some((i, true, true))
+                } else {
+                    None
+                }
+            });
+            match found {
+                Some((i, prepend, reverse)) => {
+                    let mut other = open.swap_remove(i);
+                    if reverse {
+                        other.reverse();
+                    }
+                    if prepend {
+                        other.pop();
+                        other.extend(current);
+                        current = other;
+                    } else {
+                        current.pop();
+                        current.extend(other);
+                    }
+                }
+                None => break,
+            }
+        }
+        joined.push(current);
+    }
+
+    closed
+        .into_iter()
+        .chain(joined)
+        .filter_map(|mut points| {
+            points.dedup_by(|a, b| (*a - *b).norm() < tolerance * T::from_f64(1e-3).unwrap());
+            if points.len() < 2 {
+                return None;
+            }
+            let degree = (points.len() - 1).min(3);
+            Some(NurbsCurve3D::try_interpolate(&points, degree))
+        })
+        .collect()
+}
+
+/// Convert the traced polylines in the parameter space into pairs of NURBS curves in 3D space & in the parameter space
+/// Each polyline is kept as it is to keep the curve in the parameter space continuous.
+#[allow(clippy::type_complexity)]
+pub(crate) fn level_set_curves_on_surface<T: FloatingPoint>(
+    surface: &NurbsSurface3D<T>,
+    polylines: Vec<(Vec<Point2<T>>, bool)>,
+) -> anyhow::Result<Vec<(NurbsCurve3D<T>, NurbsCurve2D<T>, bool)>> {
+    let eps = T::default_epsilon();
+    polylines
+        .into_iter()
+        .filter_map(|(mut uvs, closed)| {
+            uvs.dedup_by(|a, b| (*a - *b).norm() < eps);
+            if uvs.len() < 2 {
+                return None;
+            }
+            let degree = (uvs.len() - 1).min(3);
+            let points: Vec<_> = uvs.iter().map(|uv| surface.point_at(uv.x, uv.y)).collect();
+            Some(
+                NurbsCurve3D::try_interpolate(&points, degree).and_then(|curve| {
+                    NurbsCurve2D::try_interpolate(&uvs, degree).map(|uv| (curve, uv, closed))
+                }),
+            )
+        })
+        .collect()
+}
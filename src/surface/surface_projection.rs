@@ -0,0 +1,386 @@
+use argmin::core::ArgminFloat;
+use nalgebra::{Matrix3, Point2, Point3, Vector3};
+
+use crate::{
+    bounding_box::SurfaceBvh,
+    curve::{NurbsCurve2D, NurbsCurve3D},
+    misc::{FloatingPoint, Ray},
+    surface::{surface_fillet::foot_parameter, CurveOnSurface, NurbsSurface3D},
+};
+
+/// The maximum number of Newton's iterations to follow the projection from the neighboring sample
+const MAX_ITERATIONS: usize = 16;
+
+/// The maximum depth of the subdivision between the samples
+const MAX_DEPTH: usize = 10;
+
+/// The maximum number of the refits inserting the samples where the fitted curve deviates
+const MAX_REFITS: usize = 8;
+
+/// The projection of a point of the curve with the parameters of the surface
+/// & the signed distance along the direction or the distance to the closest point
+#[derive(Clone, Copy, Debug)]
+struct Projection<T: FloatingPoint> {
+    parameter: T,
+    uv: Point2<T>,
+    distance: T,
+    point: Point3<T>,
+}
+
+impl<T: FloatingPoint> NurbsSurface3D<T> {
+    /// Try to project the curve onto the surface along the direction
+    /// Each point of the curve is projected along the line through it parallel to the direction, onto the first surface point
+    /// seen when looking along the direction, and the projection is followed between the samples by Newton's method.
+    /// The curve is split into the pieces where the projection misses the surface or jumps across the seams & the folds,
+    /// and each piece is interpolated in the parameter space of the surface through the samples refined within the tolerance.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// // the circle above the cylinder along the x axis is projected downward onto its top
+    /// let cylinder = NurbsSurface3D::<f64>::try_cylinder(&Point3::new(-2., 0., 0.), &(Vector3::x() * 4.), 2.).unwrap();
+    /// let circle = NurbsCurve3D::try_circle(&Point3::new(0., 0., 5.), &Vector3::x(), &Vector3::y(), 1.).unwrap();
+    /// let pieces = cylinder.try_project_curve(&circle, &-Vector3::z(), 1e-4).unwrap();
+    /// assert!(!pieces.is_empty());
+    /// for piece in pieces.iter() {
+    ///     for p in piece.tessellate(Some(1e-4)) {
+    ///         assert_relative_eq!(p.y * p.y + p.z * p.z, 4., epsilon = 1e-8);
+    ///         assert_relative_eq!(p.x * p.x + p.y * p.y, 1., epsilon = 1e-3);
+    ///         assert!(p.z > 0.);
+    ///     }
+    /// }
+    ///
+    /// // only the part of the line over the cylinder is projected
+    /// let line = NurbsCurve3D::polyline(&[Point3::new(-4., 0.5, 5.), Point3::new(4., 0.5, 5.)]);
+    /// let pieces = cylinder.try_project_curve(&line, &-Vector3::z(), 1e-4).unwrap();
+    /// assert_eq!(pieces.len(), 1);
+    /// let (start, end) = pieces[0].knots_domain();
+    /// let (a, b) = (pieces[0].point_at(start), pieces[0].point_at(end));
+    /// assert_relative_eq!(a.x.min(b.x), -2., epsilon = 1e-3);
+    /// assert_relative_eq!(a.x.max(b.x), 2., epsilon = 1e-3);
+    /// assert_relative_eq!(a.z, 3.75f64.sqrt(), epsilon = 1e-8);
+    /// ```
+    pub fn try_project_curve(
+        &self,
+        curve: &NurbsCurve3D<T>,
+        direction: &Vector3<T>,
+        tolerance: T,
+    ) -> anyhow::Result<Vec<CurveOnSurface<T>>> {
+        anyhow::ensure!(
+            tolerance > T::zero(),
+            "The tolerance must be greater than zero"
+        );
+        let direction = direction
+            .try_normalize(T::default_epsilon())
+            .ok_or(anyhow::anyhow!("The direction must not be zero"))?;
+        let bvh = SurfaceBvh::try_new(self)?;
+        let Some(bb) = bvh.hierarchy().bounding_box().cloned() else {
+            return Ok(vec![]);
+        };
+        let (u0, u1) = self.u_knots_domain();
+        let (v0, v1) = self.v_knots_domain();
+
+        // the first hit of the line through the point seen from behind the bounding box
+        let global = |t: T| -> anyhow::Result<Option<Projection<T>>> {
+            let p = curve.point_at(t);
+            let reach = bb.size().norm() + (p.coords - bb.center()).norm();
+            let ray = Ray::new(p - direction * reach, direction);
+            Ok(bvh.cast_ray(&ray, None)?.first().map(|hit| Projection {
+                parameter: t,
+                uv: Point2::new(hit.uv().0, hit.uv().1),
+                distance: hit.parameter() - reach,
+                point: *hit.point(),
+            }))
+        };
+
+        // Newton's method on S(u, v) = P + sD from the neighboring projection
+        let local = |from: &Projection<T>, t: T| -> anyhow::Result<Option<Projection<T>>> {
+            let p = curve.point_at(t);
+            let mut x = Vector3::new(from.uv.x, from.uv.y, from.distance);
+            for _ in 0..MAX_ITERATIONS {
+                let s = self.rational_derivatives(x.x, x.y, 1);
+                let f = s[0][0] - (p.coords + direction * x.z);
+                if f.norm() <= tolerance * T::from_f64(1e-3).unwrap() {
+                    if x.x < u0 || u1 < x.x || x.y < v0 || v1 < x.y {
+                        break;
+                    }
+                    return Ok(Some(Projection {
+                        parameter: t,
+                        uv: Point2::new(x.x, x.y),
+                        distance: x.z,
+                        point: s[0][0].into(),
+                    }));
+                }
+                let jacobian = Matrix3::from_columns(&[s[1][0], s[0][1], -direction]);
+                let Some(delta) = jacobian.lu().solve(&f) else {
+                    break;
+                };
+                x -= delta;
+            }
+            Ok(None)
+        };
+
+        try_march(self, curve, tolerance, global, local)
+    }
+}
+
+impl<T: FloatingPoint + ArgminFloat> NurbsSurface3D<T> {
+    /// Try to pull the curve onto the surface by the closest points
+    /// The samples along the curve are pulled to their closest points on the surface and followed between them by the Gauss-Newton method,
+    /// and the pulled points are refitted in the parameter space of the surface until the fitted curve is within the tolerance.
+    /// The curve is split where the closest points jump across the seams or between the distant regions of the surface.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// // the wavy ring around the cylinder is pulled onto the circle on it
+    /// let cylinder = NurbsSurface3D::<f64>::try_cylinder(&Point3::origin(), &(Vector3::z() * 2.), 1.).unwrap();
+    /// let ring = (0..24)
+    ///     .map(|i| {
+    ///         let angle = std::f64::consts::TAU * i as f64 / 24.;
+    ///         let radius = 1.2 + 0.1 * (angle * 3.).sin();
+    ///         Point3::new(angle.cos() * radius, angle.sin() * radius, 1.)
+    ///     })
+    ///     .collect::<Vec<_>>();
+    /// let curve = NurbsCurve3D::try_periodic_interpolate(&ring, 3, KnotStyle::Centripetal).unwrap();
+    /// let pieces = cylinder.try_pull_curve(&curve, 1e-4).unwrap();
+    /// assert!(!pieces.is_empty());
+    /// let mut length = 0.;
+    /// for piece in pieces.iter() {
+    ///     for p in piece.tessellate(Some(1e-4)) {
+    ///         assert_relative_eq!(p.coords.xy().norm(), 1., epsilon = 1e-8);
+    ///         assert_relative_eq!(p.z, 1., epsilon = 1e-4);
+    ///     }
+    ///     length += piece.try_length(1e-8).unwrap();
+    /// }
+    /// assert_relative_eq!(length, std::f64::consts::TAU, epsilon = 1e-3);
+    /// ```
+    pub fn try_pull_curve(
+        &self,
+        curve: &NurbsCurve3D<T>,
+        tolerance: T,
+    ) -> anyhow::Result<Vec<CurveOnSurface<T>>> {
+        anyhow::ensure!(
+            tolerance > T::zero(),
+            "The tolerance must be greater than zero"
+        );
+        let bvh = SurfaceBvh::try_new(self)?;
+        let pulled = |t: T, (u, v): (T, T)| {
+            let p = curve.point_at(t);
+            let point = self.point_at(u, v);
+            Projection {
+                parameter: t,
+                uv: Point2::new(u, v),
+                distance: (point - p).norm(),
+                point,
+            }
+        };
+        let global = |t: T| -> anyhow::Result<Option<Projection<T>>> {
+            let uv = bvh.find_closest_parameter(&curve.point_at(t))?;
+            Ok(Some(pulled(t, uv)))
+        };
+        let local = |from: &Projection<T>, t: T| -> anyhow::Result<Option<Projection<T>>> {
+            let uv = foot_parameter(self, &curve.point_at(t), (from.uv.x, from.uv.y));
+            Ok(Some(pulled(t, uv)))
+        };
+        try_march(self, curve, tolerance, global, local)
+    }
+}
+
+/// March along the curve by the projections of the samples, split into the pieces where the projections are lost or jump,
+/// & fit the curves on the surface to the pieces refined within the tolerance
+/// * `global` - Project the point at the parameter of the curve from scratch
+/// * `local` - Follow the projection from the neighboring one to the parameter
+fn try_march<T, G, L>(
+    surface: &NurbsSurface3D<T>,
+    curve: &NurbsCurve3D<T>,
+    tolerance: T,
+    global: G,
+    local: L,
+) -> anyhow::Result<Vec<CurveOnSurface<T>>>
+where
+    T: FloatingPoint,
+    G: Fn(T) -> anyhow::Result<Option<Projection<T>>>,
+    L: Fn(&Projection<T>, T) -> anyhow::Result<Option<Projection<T>>>,
+{
+    let (u0, u1) = surface.u_knots_domain();
+    let (v0, v1) = surface.v_knots_domain();
+    let half = T::from_f64(0.5).unwrap();
+
+    let (start, end) = curve.knots_domain();
+    let samples = (curve.control_points().len() * 8).max(32);
+    let projections = (0..=samples)
+        .map(|i| {
+            let t =
+                start + (end - start) * T::from_usize(i).unwrap() / T::from_usize(samples).unwrap();
+            global(t).map(|p| (t, p))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    // a jump of the projection between the neighboring samples breaks the piece
+    let is_jump = |a: &Projection<T>, b: &Projection<T>| {
+        let (du, dv) = ((b.uv.x - a.uv.x).abs(), (b.uv.y - a.uv.y).abs());
+        let step = (curve.point_at(b.parameter) - curve.point_at(a.parameter)).norm();
+        du > (u1 - u0) * half
+            || dv > (v1 - v0) * half
+            || (b.point - a.point).norm() > step * T::from_usize(16).unwrap() + tolerance
+    };
+    let follow = |from: &Projection<T>, t: T| -> anyhow::Result<Option<Projection<T>>> {
+        Ok(local(from, t)?.filter(|p| !is_jump(from, p)))
+    };
+    let close = |a: T, b: T| (curve.point_at(a) - curve.point_at(b)).norm() <= tolerance;
+
+    // refine the samples until the middle of the projection is within the tolerance from the chord
+    let refine = |a: Projection<T>,
+                  b: Projection<T>,
+                  piece: &mut Vec<Projection<T>>|
+     -> anyhow::Result<()> {
+        let mut stack = vec![(a, b, 0)];
+        while let Some((a, b, depth)) = stack.pop() {
+            let mid = (a.parameter + b.parameter) * half;
+            let m = if depth < MAX_DEPTH {
+                follow(&a, mid)?.filter(|m| !is_jump(m, &b))
+            } else {
+                None
+            };
+            match m {
+                Some(m) if (m.point - a.point.lerp(&b.point, half)).norm() > tolerance => {
+                    stack.push((m, b, depth + 1));
+                    stack.push((a, m, depth + 1));
+                }
+                _ => piece.push(b),
+            }
+        }
+        Ok(())
+    };
+
+    // bisect toward the parameter where the projection is lost to find the end of the piece
+    let bisect_end = |mut inside: Projection<T>, mut outside: T| -> anyhow::Result<Projection<T>> {
+        for _ in 0..MAX_DEPTH * 4 {
+            if close(inside.parameter, outside) {
+                break;
+            }
+            let mid = (inside.parameter + outside) * half;
+            match follow(&inside, mid)? {
+                Some(m) => inside = m,
+                None => outside = mid,
+            }
+        }
+        Ok(inside)
+    };
+
+    // bisect the jump between the projections from scratch to find the ends of the pieces on both sides
+    let bisect_jump = |mut a: Projection<T>,
+                       mut b: Projection<T>|
+     -> anyhow::Result<(Projection<T>, Projection<T>)> {
+        for _ in 0..MAX_DEPTH * 4 {
+            if close(a.parameter, b.parameter) {
+                break;
+            }
+            let Some(m) = global((a.parameter + b.parameter) * half)? else {
+                break;
+            };
+            if is_jump(&a, &m) {
+                b = m;
+            } else {
+                a = m;
+            }
+        }
+        Ok((a, b))
+    };
+
+    let mut pieces: Vec<Vec<Projection<T>>> = vec![];
+    let mut current: Vec<Projection<T>> = vec![];
+    for w in projections.windows(2) {
+        match (&w[0], &w[1]) {
+            ((_, Some(a)), (_, Some(b))) => {
+                if current.is_empty() {
+                    current.push(*a);
+                }
+                if is_jump(a, b) {
+                    let (last, first) = bisect_jump(*a, *b)?;
+                    refine(*a, last, &mut current)?;
+                    pieces.push(std::mem::take(&mut current));
+                    current.push(first);
+                    refine(first, *b, &mut current)?;
+                } else {
+                    refine(*a, *b, &mut current)?;
+                }
+            }
+            ((_, Some(a)), (t, None)) => {
+                if current.is_empty() {
+                    current.push(*a);
+                }
+                let last = bisect_end(*a, *t)?;
+                refine(*a, last, &mut current)?;
+                pieces.push(std::mem::take(&mut current));
+            }
+            ((t, None), (_, Some(b))) => {
+                let first = bisect_end(*b, *t)?;
+                current.push(first);
+                refine(first, *b, &mut current)?;
+            }
+            _ => {}
+        }
+    }
+    if !current.is_empty() {
+        pieces.push(current);
+    }
+
+    // the ends of the pieces found by the bisection may coincide with the samples
+    pieces.iter_mut().for_each(|piece| {
+        piece.dedup_by(|a, b| (a.point - b.point).norm() <= T::default_epsilon().sqrt())
+    });
+    pieces.retain(|piece| piece.len() > 1);
+
+    pieces
+        .into_iter()
+        .map(|mut piece| {
+            let mut refit = 0;
+            loop {
+                let uv_curve = try_fit(&piece)?;
+                let fitted = CurveOnSurface::new(uv_curve, surface.clone());
+
+                // the curve interpolates the points at the chordal parameters
+                let (d0, d1) = fitted.knots_domain();
+                let chords = piece
+                    .windows(2)
+                    .map(|w| (w[1].uv - w[0].uv).norm())
+                    .collect::<Vec<_>>();
+                let total = chords.iter().fold(T::zero(), |acc, c| acc + *c);
+                let mut s = d0;
+                let mut refined = vec![piece[0]];
+                for (w, chord) in piece.windows(2).zip(chords.iter()) {
+                    let next = s + (d1 - d0) * *chord / total;
+                    let mid = (w[0].parameter + w[1].parameter) * half;
+                    if let Some(m) = follow(&w[0], mid)? {
+                        if (fitted.point_at((s + next) * half) - m.point).norm() > tolerance {
+                            refined.push(m);
+                        }
+                    }
+                    refined.push(w[1]);
+                    s = next;
+                }
+                refit += 1;
+                if refined.len() == piece.len() || refit >= MAX_REFITS {
+                    return Ok(fitted);
+                }
+                piece = refined;
+            }
+        })
+        .collect()
+}
+
+/// Fit the curve in the parameter space through the projections
+fn try_fit<T: FloatingPoint>(piece: &[Projection<T>]) -> anyhow::Result<NurbsCurve2D<T>> {
+    let uvs = piece.iter().map(|p| p.uv).collect::<Vec<_>>();
+    if uvs.len() == 2 {
+        Ok(NurbsCurve2D::polyline(&uvs))
+    } else {
+        NurbsCurve2D::try_interpolate(&uvs, 3.min(uvs.len() - 1))
+    }
+}
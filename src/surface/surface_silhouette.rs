@@ -0,0 +1,37 @@
+use crate::{
+    curve::{NurbsCurve2D, NurbsCurve3D},
+    misc::FloatingPoint,
+};
+
+/// A struct representing a silhouette curve on a surface.
+#[derive(Clone, Debug)]
+pub struct SurfaceSilhouette<T: FloatingPoint> {
+    /// The silhouette curve in 3D space.
+    curve: NurbsCurve3D<T>,
+    /// The silhouette curve in the (u, v) parameter space of the surface.
+    uv: NurbsCurve2D<T>,
+    /// Whether the curve is closed or not.
+    closed: bool,
+}
+
+impl<T: FloatingPoint> SurfaceSilhouette<T> {
+    pub fn new(curve: NurbsCurve3D<T>, uv: NurbsCurve2D<T>, closed: bool) -> Self {
+        Self { curve, uv, closed }
+    }
+
+    pub fn curve(&self) -> &NurbsCurve3D<T> {
+        &self.curve
+    }
+
+    pub fn into_curve(self) -> NurbsCurve3D<T> {
+        self.curve
+    }
+
+    pub fn uv(&self) -> &NurbsCurve2D<T> {
+        &self.uv
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+}
@@ -0,0 +1,34 @@
+use crate::{
+    curve::{NurbsCurve2D, NurbsCurve3D},
+    misc::FloatingPoint,
+};
+
+/// A curve to trim a surface
+/// The curve in 3D space is projected onto the surface,
+/// and the curve in the (u, v) parameter space of the surface is used as it is.
+#[derive(Clone, Debug)]
+pub enum TrimCurve<T: FloatingPoint> {
+    Space(NurbsCurve3D<T>),
+    Parameter(NurbsCurve2D<T>),
+}
+
+impl<T: FloatingPoint> From<NurbsCurve3D<T>> for TrimCurve<T> {
+    fn from(curve: NurbsCurve3D<T>) -> Self {
+        Self::Space(curve)
+    }
+}
+
+impl<T: FloatingPoint> From<NurbsCurve2D<T>> for TrimCurve<T> {
+    fn from(curve: NurbsCurve2D<T>) -> Self {
+        Self::Parameter(curve)
+    }
+}
+
+/// The side of the trim loops to keep
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrimSide {
+    /// Keep the region inside the outermost loop and outside the loops inside it
+    Inside,
+    /// Keep the region outside all the loops
+    Outside,
+}
@@ -0,0 +1,285 @@
+use nalgebra::{Point2, Vector2};
+
+use crate::{
+    curve::NurbsCurve2D,
+    misc::FloatingPoint,
+    surface::{NurbsSurface3D, SurfaceUnrollOptions},
+};
+
+/// The number of the samples for each knot span of the curves mapped into the plane
+const SPAN_SAMPLES: usize = 16;
+
+/// A surface unrolled into the plane as the grid of the points mapped from the parameters
+/// The points of the parameters inside the cells of the grid are interpolated linearly over the triangles splitting the cells.
+#[derive(Clone, Debug)]
+pub struct SurfaceUnrolling<T: FloatingPoint> {
+    /// The parameters of the grid in the u direction
+    u_parameters: Vec<T>,
+    /// The parameters of the grid in the v direction
+    v_parameters: Vec<T>,
+    /// The points of the grid in the plane indexed by [u][v]
+    points: Vec<Vec<Point2<T>>>,
+    /// The maximum relative change of the lengths of the edges of the grid
+    distortion: T,
+}
+
+impl<T: FloatingPoint> SurfaceUnrolling<T> {
+    pub fn new(
+        u_parameters: Vec<T>,
+        v_parameters: Vec<T>,
+        points: Vec<Vec<Point2<T>>>,
+        distortion: T,
+    ) -> Self {
+        Self {
+            u_parameters,
+            v_parameters,
+            points,
+            distortion,
+        }
+    }
+
+    pub fn u_parameters(&self) -> &[T] {
+        &self.u_parameters
+    }
+
+    pub fn v_parameters(&self) -> &[T] {
+        &self.v_parameters
+    }
+
+    pub fn points(&self) -> &[Vec<Point2<T>>] {
+        &self.points
+    }
+
+    pub fn distortion(&self) -> T {
+        self.distortion
+    }
+
+    /// Map the parameter of the surface into the plane
+    /// The parameter outside the domain is clamped to it.
+    pub fn point_at(&self, u: T, v: T) -> Point2<T> {
+        let cell = |parameters: &[T], t: T| {
+            let i = parameters
+                .partition_point(|p| *p <= t)
+                .clamp(1, parameters.len() - 1)
+                - 1;
+            let s = (t - parameters[i]) / (parameters[i + 1] - parameters[i]);
+            (i, s.clamp(T::zero(), T::one()))
+        };
+        let (i, s) = cell(&self.u_parameters, u);
+        let (j, t) = cell(&self.v_parameters, v);
+        let q = |di: usize, dj: usize| self.points[i + di][j + dj];
+        if s + t <= T::one() {
+            q(0, 0) + (q(1, 0) - q(0, 0)) * s + (q(0, 1) - q(0, 0)) * t
+        } else {
+            q(1, 1) + (q(0, 1) - q(1, 1)) * (T::one() - s) + (q(1, 0) - q(1, 1)) * (T::one() - t)
+        }
+    }
+
+    /// Try to map the curve in the parameter space of the surface into the plane
+    /// The curve is sampled along each knot span and the mapped samples are interpolated,
+    /// or connected by the polyline if the curve is linear.
+    pub fn try_unroll_curve(&self, curve: &NurbsCurve2D<T>) -> anyhow::Result<NurbsCurve2D<T>> {
+        let degree = curve.degree();
+        let mut knots = curve.knots().as_slice()[degree..curve.knots().len() - degree].to_vec();
+        knots.dedup();
+        let samples = knots.windows(2).flat_map(|w| {
+            (0..SPAN_SAMPLES).map(move |i| {
+                w[0] + (w[1] - w[0]) * T::from_usize(i).unwrap()
+                    / T::from_usize(SPAN_SAMPLES).unwrap()
+            })
+        });
+        let points = samples
+            .chain(knots.last().copied())
+            .map(|t| {
+                let uv = curve.point_at(t);
+                self.point_at(uv.x, uv.y)
+            })
+            .collect::<Vec<_>>();
+        if degree == 1 {
+            Ok(NurbsCurve2D::polyline(&points))
+        } else {
+            NurbsCurve2D::try_interpolate(&points, 3)
+        }
+    }
+
+    /// The outline of the unrolled surface along the boundary of the domain,
+    /// starting from the corner of the minimum parameters & turning counterclockwise in the parameter space
+    pub fn boundary(&self) -> Vec<Point2<T>> {
+        let (nu, nv) = (self.points.len() - 1, self.points[0].len() - 1);
+        let bottom = (0..nu).map(|i| self.points[i][0]);
+        let right = (0..nv).map(|j| self.points[nu][j]);
+        let top = (1..=nu).rev().map(|i| self.points[i][nv]);
+        let left = (1..=nv).rev().map(|j| self.points[0][j]);
+        bottom
+            .chain(right)
+            .chain(top)
+            .chain(left)
+            .chain(std::iter::once(self.points[0][0]))
+            .collect()
+    }
+}
+
+impl<T: FloatingPoint> NurbsSurface3D<T> {
+    /// Try to unroll the developable surface into the plane
+    /// The grid of the parameters is unfolded strip by strip, placing each point of the grid at the lengths along the surface
+    /// from the points already placed, and the unrolling fails if the lengths of the edges change more than the allowed distortion,
+    /// which happens when the surface is far from developable.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point2, Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// // the cylinder is unrolled into the rectangle
+    /// let cylinder = NurbsSurface3D::<f64>::try_cylinder(&Point3::origin(), &(Vector3::z() * 2.), 1.).unwrap();
+    /// let unrolling = cylinder.try_unroll(&SurfaceUnrollOptions::default()).unwrap();
+    /// assert!(unrolling.distortion() < 1e-6);
+    /// let tau = std::f64::consts::TAU;
+    /// assert_relative_eq!((unrolling.point_at(1., 0.) - unrolling.point_at(0., 0.)).norm(), tau, epsilon = 1e-5);
+    /// assert_relative_eq!((unrolling.point_at(0., 1.) - unrolling.point_at(0., 0.)).norm(), 2., epsilon = 1e-6);
+    ///
+    /// // the curve on the cylinder is unrolled along with it
+    /// let helix = NurbsCurve2D::polyline(&[Point2::new(0., 0.), Point2::new(0.25, 1.)]);
+    /// let line = unrolling.try_unroll_curve(&helix).unwrap();
+    /// let (start, end) = line.knots_domain();
+    /// let (a, b) = (line.point_at(start), line.point_at(end));
+    /// assert_relative_eq!((b - a).norm(), (tau * tau / 16. + 4.).sqrt(), epsilon = 1e-5);
+    ///
+    /// // the cone frustum is unrolled into the sector of the annulus
+    /// let cone = NurbsSurface3D::<f64>::try_cone(&Point3::origin(), &(Vector3::z() * 2.), 2., 1.).unwrap();
+    /// let unrolling = cone.try_unroll(&SurfaceUnrollOptions::default()).unwrap();
+    /// let (outer, angle) = (2. * 5f64.sqrt(), tau / 5f64.sqrt());
+    /// let chord = (unrolling.point_at(1., 0.) - unrolling.point_at(0., 0.)).norm();
+    /// assert_relative_eq!(chord, 2. * outer * (angle / 2.).sin(), epsilon = 1e-5);
+    ///
+    /// // the sphere is not developable
+    /// let sphere = NurbsSurface3D::<f64>::try_sphere(&Point3::origin(), &Vector3::z(), 1.).unwrap();
+    /// assert!(sphere.try_unroll(&SurfaceUnrollOptions::default()).is_err());
+    /// ```
+    pub fn try_unroll(
+        &self,
+        options: &SurfaceUnrollOptions<T>,
+    ) -> anyhow::Result<SurfaceUnrolling<T>> {
+        let (nu, nv) = (options.u_divisions, options.v_divisions);
+        anyhow::ensure!(
+            nu > 0 && nv > 0,
+            "The number of the divisions must be greater than zero"
+        );
+        let divide = |(start, end): (T, T), n: usize| {
+            (0..=n)
+                .map(|i| {
+                    start + (end - start) * T::from_usize(i).unwrap() / T::from_usize(n).unwrap()
+                })
+                .collect::<Vec<_>>()
+        };
+        let us = divide(self.u_knots_domain(), nu);
+        let vs = divide(self.v_knots_domain(), nv);
+
+        let length = |(i0, j0): (usize, usize), (i1, j1): (usize, usize)| {
+            surface_distance(
+                self,
+                &Point2::new(us[i0], vs[j0]),
+                &Point2::new(us[i1], vs[j1]),
+            )
+        };
+
+        // place the point at the distances from the points so that the triangle turns counterclockwise
+        let unfold = |a: (usize, usize),
+                      b: (usize, usize),
+                      c: (usize, usize),
+                      points: &[Vec<Point2<T>>]|
+         -> anyhow::Result<Point2<T>> {
+            let (pa, pb) = (points[a.0][a.1], points[b.0][b.1]);
+            let (da, db) = (length(a, c), length(b, c));
+            let ab = pb - pa;
+            let d = ab.norm();
+            anyhow::ensure!(
+                d > T::default_epsilon(),
+                "The surface has the degenerate edges at the grid of the parameters"
+            );
+            let e = ab / d;
+            let x = (da * da - db * db + d * d) / (d + d);
+            let h = (da * da - x * x).max(T::zero()).sqrt();
+            Ok(pa + e * x + Vector2::new(-e.y, e.x) * h)
+        };
+
+        let mut points = vec![vec![Point2::origin(); nv + 1]; nu + 1];
+        points[0][1] = Point2::new(T::zero(), length((0, 0), (0, 1)));
+        for j in 0..nv {
+            if j == 0 {
+                // the first strip is unfolded from its first edge
+                for i in 0..nu {
+                    points[i + 1][0] = unfold((i, 1), (i, 0), (i + 1, 0), &points)?;
+                    points[i + 1][1] = unfold((i, 1), (i + 1, 0), (i + 1, 1), &points)?;
+                }
+            } else {
+                // the following strips are unfolded from the edges of the previous strip
+                points[0][j + 1] = unfold((0, j), (1, j), (0, j + 1), &points)?;
+                for i in 0..nu {
+                    points[i + 1][j + 1] = unfold((i, j + 1), (i + 1, j), (i + 1, j + 1), &points)?;
+                }
+            }
+        }
+
+        // the relative changes of the lengths over all the edges of the triangles
+        let mut distortion = T::zero();
+        let mut measure = |a: (usize, usize), b: (usize, usize)| {
+            let expected = length(a, b);
+            let actual = (points[b.0][b.1] - points[a.0][a.1]).norm();
+            if expected > T::default_epsilon() {
+                distortion = distortion.max((actual - expected).abs() / expected);
+            }
+        };
+        for i in 0..=nu {
+            for j in 0..=nv {
+                if i < nu {
+                    measure((i, j), (i + 1, j));
+                }
+                if j < nv {
+                    measure((i, j), (i, j + 1));
+                }
+                if i < nu && j < nv {
+                    measure((i + 1, j), (i, j + 1));
+                }
+            }
+        }
+        anyhow::ensure!(
+            distortion <= options.max_distortion,
+            "The distortion of the unrolling {} exceeds the maximum {}, the surface is not developable",
+            distortion,
+            options.max_distortion
+        );
+
+        Ok(SurfaceUnrolling::new(us, vs, points, distortion))
+    }
+}
+
+/// The geodesic distance along the surface between the close parameters, approximated by the circular arc over the chord
+/// through the offset of the surface at the middle along the normal, which ignores the deviation in the tangent plane
+/// by the uneven speed & the geodesic curvature of the line in the parameter space
+pub(crate) fn surface_distance<T: FloatingPoint>(
+    surface: &NurbsSurface3D<T>,
+    a: &Point2<T>,
+    b: &Point2<T>,
+) -> T {
+    let half = T::from_f64(0.5).unwrap();
+    let (pa, pb) = (surface.point_at(a.x, a.y), surface.point_at(b.x, b.y));
+    let m = a.lerp(b, half);
+    let chord = (pb - pa).norm();
+    let sagitta = match surface
+        .normal_at(m.x, m.y)
+        .try_normalize(T::default_epsilon())
+    {
+        Some(n) => (surface.point_at(m.x, m.y) - pa.lerp(&pb, half))
+            .dot(&n)
+            .abs(),
+        None => T::zero(),
+    };
+    if chord > T::default_epsilon() && sagitta > T::default_epsilon() * chord {
+        let two = T::from_f64(2.).unwrap();
+        let radius = (chord * chord / (two * two) + sagitta * sagitta) / (two * sagitta);
+        two * radius * (chord / (two * radius)).min(T::one()).asin()
+    } else {
+        chord
+    }
+}
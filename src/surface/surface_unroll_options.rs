@@ -0,0 +1,39 @@
+use crate::misc::FloatingPoint;
+
+/// Options for unrolling a developable surface into the plane
+#[derive(Clone, Debug)]
+pub struct SurfaceUnrollOptions<T: FloatingPoint> {
+    /// The number of the divisions of the grid in the u direction
+    pub u_divisions: usize,
+    /// The number of the divisions of the grid in the v direction
+    pub v_divisions: usize,
+    /// The maximum relative change of the lengths of the edges of the grid allowed by the unrolling
+    pub max_distortion: T,
+}
+
+impl<T: FloatingPoint> Default for SurfaceUnrollOptions<T> {
+    fn default() -> Self {
+        Self {
+            u_divisions: 32,
+            v_divisions: 32,
+            max_distortion: T::from_f64(1e-2).unwrap(),
+        }
+    }
+}
+
+impl<T: FloatingPoint> SurfaceUnrollOptions<T> {
+    pub fn with_u_divisions(mut self, u_divisions: usize) -> Self {
+        self.u_divisions = u_divisions;
+        self
+    }
+
+    pub fn with_v_divisions(mut self, v_divisions: usize) -> Self {
+        self.v_divisions = v_divisions;
+        self
+    }
+
+    pub fn with_max_distortion(mut self, max_distortion: T) -> Self {
+        self.max_distortion = max_distortion;
+        self
+    }
+}
@@ -0,0 +1,9 @@
+/// The frames to orient the profile curve along the rail curve of a sweep
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SweepFrame {
+    /// The rotation minimizing frames, which do not twist the profile around the rail
+    #[default]
+    RotationMinimizing,
+    /// The Frenet frames, which follow the curvature of the rail but flip at the inflection points
+    Frenet,
+}
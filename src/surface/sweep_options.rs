@@ -0,0 +1,60 @@
+use nalgebra::Const;
+
+use crate::{curve::NurbsCurve, misc::FloatingPoint};
+
+use super::SweepFrame;
+
+/// Options for sweeping a profile curve along a rail curve
+/// The scale & the twist laws are scalar curves whose domains are mapped onto the domain of the rail.
+#[derive(Clone, Debug)]
+pub struct SweepOptions<T: FloatingPoint> {
+    /// The frames to orient the profile along the rail
+    pub frame: SweepFrame,
+    /// The degree of the surface along the rail
+    pub degree_v: Option<usize>,
+    /// The number of sections sampled along the rail, twice the number of the control points of the rail if not specified
+    pub samples: Option<usize>,
+    /// The scale of the profile along the rail
+    pub scale: Option<NurbsCurve<T, Const<2>>>,
+    /// The rotation angle in radians of the profile around the rail
+    pub twist: Option<NurbsCurve<T, Const<2>>>,
+}
+
+impl<T: FloatingPoint> Default for SweepOptions<T> {
+    fn default() -> Self {
+        Self {
+            frame: SweepFrame::default(),
+            degree_v: None,
+            samples: None,
+            scale: None,
+            twist: None,
+        }
+    }
+}
+
+impl<T: FloatingPoint> SweepOptions<T> {
+    pub fn with_frame(mut self, frame: SweepFrame) -> Self {
+        self.frame = frame;
+        self
+    }
+
+    pub fn with_degree_v(mut self, degree_v: usize) -> Self {
+        self.degree_v = Some(degree_v);
+        self
+    }
+
+    pub fn with_samples(mut self, samples: usize) -> Self {
+        self.samples = Some(samples);
+        self
+    }
+
+    pub fn with_scale(mut self, scale: NurbsCurve<T, Const<2>>) -> Self {
+        self.scale = Some(scale);
+        self
+    }
+
+    pub fn with_twist(mut self, twist: NurbsCurve<T, Const<2>>) -> Self {
+        self.twist = Some(twist);
+        self
+    }
+}
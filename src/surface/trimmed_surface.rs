@@ -0,0 +1,344 @@
+use std::sync::OnceLock;
+
+use nalgebra::{Matrix3, Matrix4, Point2, Point3, Vector2, Vector3};
+use spade::{ConstrainedDelaunayTriangulation, Point2 as SPoint2, Triangulation};
+
+use crate::{
+    curve::CompoundCurve2D,
+    misc::{
+        is_point_inside_polygon, Deformable, FloatingPoint, Invertible, SpaceDeformation,
+        Transformable,
+    },
+    prelude::{AdaptiveTessellationOptions, SurfaceTessellation3D},
+    surface::NurbsSurface3D,
+};
+
+/// A NURBS surface trimmed by closed loops defined in the (u, v) parameter space of the surface
+/// The region inside the exterior loop and outside the interior loops is kept
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrimmedSurface<T: FloatingPoint> {
+    surface: NurbsSurface3D<T>,
+    /// The outer boundary loop
+    /// if `None`, the boundary of the parameter domain is used
+    exterior: Option<CompoundCurve2D<T>>,
+    /// The inner boundary loops (holes)
+    interiors: Vec<CompoundCurve2D<T>>,
+    /// The trim loops tessellated into polygons in the parameter space, computed on the first use
+    #[cfg_attr(feature = "serde", serde(skip))]
+    polygons: OnceLock<TrimPolygons<T>>,
+}
+
+/// The exterior polygon (if any) and the interior polygons of the trim loops
+type TrimPolygons<T> = (Option<Vec<Point2<T>>>, Vec<Vec<Point2<T>>>);
+
+impl<T: FloatingPoint> TrimmedSurface<T> {
+    /// Create a new trimmed surface
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point2, Point3, Vector2, Vector3};
+    ///
+    /// let line = NurbsCurve3D::polyline(&[Point3::new(0., 0., 0.), Point3::new(1., 0., 0.)]);
+    /// let plane = NurbsSurface::extrude(&line, &Vector3::y());
+    ///
+    /// // A circular hole at the center of the surface
+    /// let hole = NurbsCurve2D::try_circle(&Point2::new(0.5, 0.5), &Vector2::x(), &Vector2::y(), 0.25).unwrap();
+    /// let trimmed = TrimmedSurface::try_new(plane, None, vec![hole.into()]).unwrap();
+    ///
+    /// assert!(trimmed.contains(0.1, 0.1));
+    /// assert!(!trimmed.contains(0.5, 0.5));
+    /// assert!(!trimmed.contains(1.5, 0.5));
+    /// ```
+    pub fn try_new(
+        surface: NurbsSurface3D<T>,
+        exterior: Option<CompoundCurve2D<T>>,
+        interiors: Vec<CompoundCurve2D<T>>,
+    ) -> anyhow::Result<Self> {
+        if let Some(exterior) = exterior.as_ref() {
+            if !exterior.is_closed(None) {
+                anyhow::bail!("The exterior loop is not closed");
+            }
+        }
+        if interiors.iter().any(|l| !l.is_closed(None)) {
+            anyhow::bail!("An interior loop is not closed");
+        }
+
+        Ok(Self {
+            surface,
+            exterior,
+            interiors,
+            polygons: OnceLock::new(),
+        })
+    }
+
+    pub fn surface(&self) -> &NurbsSurface3D<T> {
+        &self.surface
+    }
+
+    pub fn exterior(&self) -> Option<&CompoundCurve2D<T>> {
+        self.exterior.as_ref()
+    }
+
+    pub fn interiors(&self) -> &[CompoundCurve2D<T>] {
+        &self.interiors
+    }
+
+    /// Evaluate the underlying surface at the given (u, v) parameter
+    pub fn point_at(&self, u: T, v: T) -> Point3<T> {
+        self.surface.point_at(u, v)
+    }
+
+    /// Evaluate the normal of the underlying surface at the given (u, v) parameter
+    pub fn normal_at(&self, u: T, v: T) -> Vector3<T> {
+        self.surface.normal_at(u, v)
+    }
+
+    /// Tessellate the trim loops into polygons in the parameter space
+    /// The polygons are cached, so the loops are tessellated only once
+    fn trim_polygons(&self) -> &TrimPolygons<T> {
+        self.polygons.get_or_init(|| {
+            (
+                self.exterior.as_ref().map(|l| l.tessellate(None)),
+                self.interiors.iter().map(|l| l.tessellate(None)).collect(),
+            )
+        })
+    }
+
+    /// Check if the (u, v) parameter is inside the trimmed region
+    pub fn contains(&self, u: T, v: T) -> bool {
+        let (exterior, interiors) = self.trim_polygons();
+        self.contains_with_polygons(&Point2::new(u, v), exterior.as_deref(), interiors)
+    }
+
+    fn contains_with_polygons(
+        &self,
+        uv: &Point2<T>,
+        exterior: Option<&[Point2<T>]>,
+        interiors: &[Vec<Point2<T>>],
+    ) -> bool {
+        let (u0, u1) = self.surface.u_knots_domain();
+        let (v0, v1) = self.surface.v_knots_domain();
+        if uv.x < u0 || u1 < uv.x || uv.y < v0 || v1 < uv.y {
+            return false;
+        }
+
+        let inside = exterior
+            .map(|polygon| is_point_inside_polygon(uv, polygon))
+            .unwrap_or(true);
+        inside
+            && !interiors
+                .iter()
+                .any(|polygon| is_point_inside_polygon(uv, polygon))
+    }
+
+    /// Tessellate the trimmed surface into a meshable form
+    /// The parameter domain is triangulated by constrained Delaunay triangulation,
+    /// where the trim loops are inserted as constraint edges and the vertices of the (adaptive) tessellation of the underlying surface are inserted as interior points.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point2, Point3, Vector2, Vector3};
+    ///
+    /// let line = NurbsCurve3D::polyline(&[Point3::new(0., 0., 0.), Point3::new(1., 0., 0.)]);
+    /// let plane = NurbsSurface::extrude(&line, &Vector3::y());
+    /// let hole = NurbsCurve2D::try_circle(&Point2::new(0.5, 0.5), &Vector2::x(), &Vector2::y(), 0.25).unwrap();
+    /// let trimmed = TrimmedSurface::try_new(plane.clone(), None, vec![hole.into()]).unwrap();
+    ///
+    /// let options = AdaptiveTessellationOptions { min_divs_u: 16, min_divs_v: 16, ..Default::default() };
+    /// let tess = trimmed.tessellate(Some(options));
+    ///
+    /// // The triangles lie outside the hole
+    /// assert!(tess.uvs().iter().all(|uv| (uv - Vector2::new(0.5, 0.5)).norm() > 0.24));
+    ///
+    /// // The area of the mesh is close to the trimmed area (1 - pi * 0.25^2)
+    /// let area: f64 = tess.faces().iter().map(|f| {
+    ///     let p = tess.points();
+    ///     (p[f[1]] - p[f[0]]).cross(&(p[f[2]] - p[f[0]])).norm() * 0.5
+    /// }).sum();
+    /// assert!((area - (1. - std::f64::consts::PI * 0.25 * 0.25)).abs() < 1e-2);
+    ///
+    /// // The normals at the apex of the cone revolved around the z axis are finite
+    /// let profile = NurbsCurve3D::polyline(&[Point3::new(0., 0., 1.), Point3::new(1., 0., 0.)]);
+    /// let cone = NurbsSurface::try_revolve(&profile, &Point3::origin(), &Vector3::z_axis(), std::f64::consts::TAU).unwrap();
+    /// let trimmed: TrimmedSurface<f64> = TrimmedSurface::try_new(cone, None, vec![]).unwrap();
+    /// let tess = trimmed.tessellate(None);
+    /// assert!(tess.normals().iter().all(|n| (n.norm() - 1.).abs() < 1e-6));
+    /// ```
+    pub fn tessellate(
+        &self,
+        adaptive_options: Option<AdaptiveTessellationOptions<T>>,
+    ) -> SurfaceTessellation3D<T> {
+        let (exterior, interiors) = self.trim_polygons();
+
+        let mut cdt = ConstrainedDelaunayTriangulation::<SPoint2<f64>>::new();
+        let to_spade = |p: &Point2<T>| SPoint2::new(p.x.to_f64().unwrap(), p.y.to_f64().unwrap());
+
+        // insert the trim loops as constraint edges
+        let (u0, u1) = self.surface.u_knots_domain();
+        let (v0, v1) = self.surface.v_knots_domain();
+        let boundary = exterior.clone().unwrap_or_else(|| {
+            vec![
+                Point2::new(u0, v0),
+                Point2::new(u1, v0),
+                Point2::new(u1, v1),
+                Point2::new(u0, v1),
+            ]
+        });
+        std::iter::once(&boundary)
+            .chain(interiors.iter())
+            .for_each(|polygon| {
+                let handles: Vec<_> = polygon
+                    .iter()
+                    .filter_map(|p| cdt.insert(to_spade(p)).ok())
+                    .collect();
+                let n = handles.len();
+                for i in 0..n {
+                    let (a, b) = (handles[i], handles[(i + 1) % n]);
+                    if a != b {
+                        cdt.try_add_constraint(a, b);
+                    }
+                }
+            });
+
+        // insert the vertices of the tessellation of the underlying surface inside the trimmed region
+        let tess = self.surface.tessellate(adaptive_options);
+        tess.uvs.iter().for_each(|uv| {
+            let uv: Point2<T> = (*uv).into();
+            if self.contains_with_polygons(&uv, exterior.as_deref(), interiors) {
+                let _ = cdt.insert(to_spade(&uv));
+            }
+        });
+
+        let mut trimmed = SurfaceTessellation3D {
+            points: vec![],
+            normals: vec![],
+            faces: vec![],
+            uvs: vec![],
+        };
+        let normals: Vec<_> = cdt
+            .vertices()
+            .map(|v| {
+                let p = v.position();
+                let (u, v) = (T::from_f64(p.x).unwrap(), T::from_f64(p.y).unwrap());
+                let ds = self.surface.rational_derivatives(u, v, 1);
+                trimmed.points.push(ds[0][0].into());
+                trimmed.uvs.push(Vector2::new(u, v));
+                ds[0][1]
+                    .cross(&ds[1][0])
+                    .try_normalize(T::default_epsilon())
+            })
+            .collect();
+
+        // the normal at the degenerate point such as the pole of the sphere is corrected by the neighbors
+        let (u0, u1) = self.surface.u_knots_domain();
+        let (v0, v1) = self.surface.v_knots_domain();
+        let half = T::from_f64(0.5).unwrap();
+        let center = Vector2::new((u0 + u1) * half, (v0 + v1) * half);
+        trimmed.normals = cdt
+            .vertices()
+            .map(|v| {
+                let i = v.fix().index();
+                normals[i].unwrap_or_else(|| {
+                    v.out_edges()
+                        .find_map(|e| normals[e.to().fix().index()])
+                        .unwrap_or_else(|| {
+                            // sample slightly towards the center of the domain if no neighbor has the valid normal
+                            let uv = trimmed.uvs[i];
+                            let mut fraction = T::from_f64(1e-6).unwrap();
+                            loop {
+                                let p = uv + (center - uv) * fraction;
+                                let n = self.surface.normal_at(p.x, p.y);
+                                match n.try_normalize(T::default_epsilon()) {
+                                    Some(n) => break n,
+                                    None if fraction >= T::one() => break Vector3::zeros(),
+                                    None => fraction *= T::from_f64(2.).unwrap(),
+                                }
+                            }
+                        })
+                })
+            })
+            .collect();
+
+        let three = T::from_usize(3).unwrap();
+        trimmed.faces = cdt
+            .inner_faces()
+            .filter_map(|f| {
+                let [a, b, c] = f.vertices().map(|v| v.fix().index());
+                let center = (trimmed.uvs[a] + trimmed.uvs[b] + trimmed.uvs[c]) / three;
+                self.contains_with_polygons(&center.into(), exterior.as_deref(), interiors)
+                    // flip the counter-clockwise triangle in the parameter space to align with the surface normal
+                    .then_some([a, c, b])
+            })
+            .collect();
+
+        trimmed
+    }
+}
+
+/// Enable to transform a trimmed surface by a given 4x4 matrix
+/// The trim loops are defined in the parameter space, so they are not affected by the transformation
+impl<'a, T: FloatingPoint> Transformable<&'a Matrix4<T>> for TrimmedSurface<T> {
+    fn transform(&mut self, transform: &'a Matrix4<T>) {
+        self.surface.transform(transform);
+    }
+}
+
+/// Enable to deform a trimmed surface by the deformation of the control points of the underlying surface
+/// The trim loops are defined in the parameter space, so they are not affected by the deformation
+impl<T: FloatingPoint> Deformable<T> for TrimmedSurface<T> {
+    fn deform<F: SpaceDeformation<T>>(&mut self, deformation: &F) {
+        self.surface.deform(deformation);
+    }
+}
+
+/// Enable to flip the orientation of a trimmed surface
+/// The u direction of the underlying surface is reversed and the trim loops are mirrored accordingly.
+impl<T: FloatingPoint> Invertible for TrimmedSurface<T> {
+    /// Flip the orientation of the trimmed surface
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point2, Point3, Vector2, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// let line = NurbsCurve3D::polyline(&[Point3::new(0., 0., 0.), Point3::new(1., 0., 0.)]);
+    /// let plane = NurbsSurface::extrude(&line, &Vector3::y());
+    /// let hole = NurbsCurve2D::try_circle(&Point2::new(0.3, 0.5), &Vector2::x(), &Vector2::y(), 0.2).unwrap();
+    /// let trimmed = TrimmedSurface::try_new(plane, None, vec![hole.into()]).unwrap();
+    ///
+    /// let flipped = trimmed.inverse();
+    /// let (u0, u1) = trimmed.surface().u_knots_domain();
+    /// assert!(!trimmed.contains(0.3, 0.5));
+    /// assert!(!flipped.contains(u0 + u1 - 0.3, 0.5));
+    /// assert!(flipped.contains(0.3, 0.5));
+    /// assert_relative_eq!(flipped.point_at(u0 + u1 - 0.8, 0.5), trimmed.point_at(0.8, 0.5), epsilon = 1e-10);
+    /// assert_relative_eq!(flipped.normal_at(u0 + u1 - 0.8, 0.5), -trimmed.normal_at(0.8, 0.5), epsilon = 1e-10);
+    /// ```
+    fn invert(&mut self) {
+        let knots = self.surface.u_knots();
+        let offset = knots.first() + knots.last();
+        self.surface.flip(false);
+        let mirror = Matrix3::new(
+            -T::one(),
+            T::zero(),
+            offset,
+            T::zero(),
+            T::one(),
+            T::zero(),
+            T::zero(),
+            T::zero(),
+            T::one(),
+        );
+        self.exterior
+            .iter_mut()
+            .chain(self.interiors.iter_mut())
+            .for_each(|l| {
+                l.transform(&mirror);
+                // keep the orientation of the loop in the parameter space
+                l.invert();
+            });
+        // the cached polygons are stale after mirroring the loops
+        self.polygons = OnceLock::new();
+    }
+}
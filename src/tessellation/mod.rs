@@ -1,6 +1,8 @@
 pub mod adaptive_tessellation_node;
 pub mod adaptive_tessellation_option;
 pub mod adaptive_tessellation_processor;
+pub mod surface_draft;
+pub mod surface_isophote;
 pub mod surface_point;
 pub mod surface_tessellation;
 
@@ -0,0 +1,110 @@
+use nalgebra::Vector3;
+
+use crate::{misc::FloatingPoint, tessellation::surface_tessellation::SurfaceTessellation3D};
+
+/// The class of the draft angle against the threshold
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DraftClass {
+    /// The surface faces the pull direction by the threshold or more
+    Positive,
+    /// The surface is closer to parallel to the pull direction than the threshold
+    Insufficient,
+    /// The surface faces away from the pull direction by the threshold or more
+    Negative,
+}
+
+/// The draft angles of the tessellation against the pull direction
+/// The draft angle is the complement of the angle between the normal & the pull direction,
+/// positive where the normal faces the pull direction.
+#[derive(Debug, Clone)]
+pub struct DraftAnalysis<T: FloatingPoint> {
+    /// The draft angles at the vertices in radians
+    angles: Vec<T>,
+    /// The classes of the vertices
+    vertices: Vec<DraftClass>,
+    /// The classes of the faces by the average of the angles at their vertices
+    faces: Vec<DraftClass>,
+}
+
+impl<T: FloatingPoint> DraftAnalysis<T> {
+    pub fn angles(&self) -> &[T] {
+        &self.angles
+    }
+
+    pub fn vertices(&self) -> &[DraftClass] {
+        &self.vertices
+    }
+
+    pub fn faces(&self) -> &[DraftClass] {
+        &self.faces
+    }
+}
+
+impl<T: FloatingPoint> SurfaceTessellation3D<T> {
+    /// Analyze the draft angles at the vertices & the faces against the pull direction & the threshold of the angle in radians
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// // the cylinder along the pull direction has no draft on the side
+    /// let cylinder = NurbsSurface::try_cylinder(&Point3::origin(), &(Vector3::z() * 2.), 1.).unwrap();
+    /// let tessellation = cylinder.regular_tessellate(8, 4);
+    /// let analysis = tessellation.draft_analysis(&Vector3::z(), 1f64.to_radians());
+    /// assert!(analysis.angles().iter().all(|a| a.abs() < 1e-8));
+    /// assert!(analysis.faces().iter().all(|c| *c == DraftClass::Insufficient));
+    ///
+    /// // the side of the cone opening upwards has the draft of 45 degrees
+    /// let cone = NurbsSurface::try_cone(&Point3::origin(), &Vector3::z(), 1., 2.).unwrap();
+    /// let tessellation = cone.regular_tessellate(8, 4);
+    /// let analysis = tessellation.draft_analysis(&Vector3::z(), 1f64.to_radians());
+    /// let side = tessellation
+    ///     .points()
+    ///     .iter()
+    ///     .zip(tessellation.normals().iter())
+    ///     .zip(analysis.vertices().iter().zip(analysis.angles().iter()))
+    ///     .filter(|((p, _), _)| p.z > 0.1 && p.z < 0.9);
+    /// for ((_, n), (class, angle)) in side {
+    ///     let expected = if n.z > 0. { DraftClass::Positive } else { DraftClass::Negative };
+    ///     assert_relative_eq!(angle.abs(), std::f64::consts::FRAC_PI_4, epsilon = 1e-8);
+    ///     assert_eq!(*class, expected);
+    /// }
+    /// ```
+    pub fn draft_analysis(&self, direction: &Vector3<T>, threshold: T) -> DraftAnalysis<T> {
+        let direction = direction.normalize();
+        let classify = |angle: T| {
+            if angle >= threshold {
+                DraftClass::Positive
+            } else if angle <= -threshold {
+                DraftClass::Negative
+            } else {
+                DraftClass::Insufficient
+            }
+        };
+        let angles = self
+            .normals()
+            .iter()
+            .map(|n| {
+                let norm = n.norm();
+                if norm <= T::default_epsilon() {
+                    T::zero()
+                } else {
+                    (n.dot(&direction) / norm).clamp(-T::one(), T::one()).asin()
+                }
+            })
+            .collect::<Vec<_>>();
+        let vertices = angles.iter().map(|a| classify(*a)).collect();
+        let three = T::from_usize(3).unwrap();
+        let faces = self
+            .faces()
+            .iter()
+            .map(|f| classify(f.iter().fold(T::zero(), |acc, i| acc + angles[*i]) / three))
+            .collect();
+        DraftAnalysis {
+            angles,
+            vertices,
+            faces,
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+
+use nalgebra::{Point3, Vector2, Vector3};
+
+use crate::{misc::FloatingPoint, tessellation::surface_tessellation::SurfaceTessellation3D};
+
+/// A polyline of the isophote, where the angle between the normal of the surface & the light direction is constant
+#[derive(Debug, Clone)]
+pub struct Isophote<T: FloatingPoint> {
+    /// The angle between the normal & the light direction in radians
+    angle: T,
+    points: Vec<Point3<T>>,
+    /// The parameters of the points on the surface
+    uvs: Vec<Vector2<T>>,
+    closed: bool,
+}
+
+impl<T: FloatingPoint> Isophote<T> {
+    pub fn new(angle: T, points: Vec<Point3<T>>, uvs: Vec<Vector2<T>>, closed: bool) -> Self {
+        Self {
+            angle,
+            points,
+            uvs,
+            closed,
+        }
+    }
+
+    pub fn angle(&self) -> T {
+        self.angle
+    }
+
+    pub fn points(&self) -> &[Point3<T>] {
+        &self.points
+    }
+
+    pub fn uvs(&self) -> &[Vector2<T>] {
+        &self.uvs
+    }
+
+    /// Check if the polyline is a loop, where the last point is the same as the first one
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+}
+
+impl<T: FloatingPoint> SurfaceTessellation3D<T> {
+    /// Extract the isophotes of the angles in radians between the normals & the light direction
+    /// The scalar field of the cosine is interpolated linearly over the faces,
+    /// and the polylines break at the seams where the tessellation has duplicated vertices.
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    /// use approx::assert_relative_eq;
+    ///
+    /// // the isophote of the torus lit from the top is the circle at the height of the minor radius times the cosine
+    /// let torus = NurbsSurface3D::<f64>::try_torus(&Point3::origin(), &Vector3::z(), 2., 0.5).unwrap();
+    /// let tessellation = torus.regular_tessellate(16, 16);
+    /// let angle = std::f64::consts::FRAC_PI_3;
+    /// let isophotes = tessellation.isophotes(&Vector3::z(), &[angle]);
+    /// assert!(!isophotes.is_empty());
+    /// for isophote in isophotes.iter() {
+    ///     assert_relative_eq!(isophote.angle(), angle);
+    ///     assert_eq!(isophote.points().len(), isophote.uvs().len());
+    ///     for (p, uv) in isophote.points().iter().zip(isophote.uvs().iter()) {
+    ///         assert_relative_eq!(p.z.abs(), 0.5 * angle.cos(), epsilon = 1e-8);
+    ///         assert_relative_eq!(torus.normal_at(uv.x, uv.y).normalize().z.abs(), angle.cos(), epsilon = 1e-1);
+    ///     }
+    /// }
+    /// ```
+    pub fn isophotes(&self, light: &Vector3<T>, angles: &[T]) -> Vec<Isophote<T>> {
+        let light = light.normalize();
+        let cosines = self
+            .normals()
+            .iter()
+            .map(|n| {
+                let norm = n.norm();
+                // the degenerate normals at the singular points are excluded
+                (norm > T::default_epsilon()).then(|| n.dot(&light) / norm)
+            })
+            .collect::<Vec<_>>();
+        angles
+            .iter()
+            .flat_map(|angle| {
+                self.iso_lines(&cosines, angle.cos())
+                    .into_iter()
+                    .map(|(points, uvs, closed)| Isophote::new(*angle, points, uvs, closed))
+            })
+            .collect()
+    }
+
+    /// Extract the boundaries of the zebra stripes, the isophotes of the angles dividing the range from 0 to pi evenly into the stripes
+    /// # Example
+    /// ```
+    /// use curvo::prelude::*;
+    /// use nalgebra::{Point3, Vector3};
+    ///
+    /// let sphere = NurbsSurface3D::<f64>::try_sphere(&Point3::origin(), &Vector3::z(), 1.).unwrap();
+    /// let tessellation = sphere.regular_tessellate(16, 16);
+    /// let stripes = tessellation.zebra_stripes(&Vector3::x(), 6);
+    /// let mut angles = stripes.iter().map(|s| s.angle()).collect::<Vec<_>>();
+    /// angles.dedup();
+    /// assert_eq!(angles.len(), 5);
+    /// ```
+    pub fn zebra_stripes(&self, light: &Vector3<T>, stripes: usize) -> Vec<Isophote<T>> {
+        let n = T::from_usize(stripes.max(1)).unwrap();
+        let angles = (1..stripes)
+            .map(|i| T::pi() * T::from_usize(i).unwrap() / n)
+            .collect::<Vec<_>>();
+        self.isophotes(light, &angles)
+    }
+
+    /// Trace the level set of the scalar field at the vertices with the marching triangles
+    #[allow(clippy::type_complexity)]
+    fn iso_lines(
+        &self,
+        values: &[Option<T>],
+        level: T,
+    ) -> Vec<(Vec<Point3<T>>, Vec<Vector2<T>>, bool)> {
+        let above = |i: usize| values[i].is_some_and(|v| v >= level);
+        let key = |i: usize, j: usize| if i < j { (i, j) } else { (j, i) };
+
+        // the crossing points keyed by the edges & the segments connecting them in each face
+        let mut crossings: HashMap<(usize, usize), (Point3<T>, Vector2<T>)> = HashMap::new();
+        let mut segments = vec![];
+        for face in self.faces().iter() {
+            let Some(face_values) = face.iter().map(|i| values[*i]).collect::<Option<Vec<_>>>()
+            else {
+                continue;
+            };
+            let edges = (0..3)
+                .map(|k| (face[k], face[(k + 1) % 3]))
+                .enumerate()
+                .filter(|(_, (i, j))| above(*i) != above(*j))
+                .map(|(k, (i, j))| {
+                    let (vi, vj) = (face_values[k], face_values[(k + 1) % 3]);
+                    let edge = key(i, j);
+                    crossings.entry(edge).or_insert_with(|| {
+                        let t = (level - vi) / (vj - vi);
+                        let p = self.points[i] + (self.points[j] - self.points[i]) * t;
+                        let uv = self.uvs[i] + (self.uvs[j] - self.uvs[i]) * t;
+                        (p, uv)
+                    });
+                    edge
+                })
+                .collect::<Vec<_>>();
+            if let [a, b] = edges[..] {
+                segments.push((a, b));
+            }
+        }
+
+        let mut adjacency: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        segments.iter().enumerate().for_each(|(i, (a, b))| {
+            adjacency.entry(*a).or_default().push(i);
+            adjacency.entry(*b).or_default().push(i);
+        });
+
+        let mut used = vec![false; segments.len()];
+        let next = |from: (usize, usize), used: &mut Vec<bool>| {
+            let i = adjacency.get(&from)?.iter().copied().find(|i| !used[*i])?;
+            used[i] = true;
+            let (a, b) = segments[i];
+            Some(if a == from { b } else { a })
+        };
+
+        let mut lines = vec![];
+        for i in 0..segments.len() {
+            if used[i] {
+                continue;
+            }
+            used[i] = true;
+            let (a, b) = segments[i];
+            let mut chain = vec![a, b];
+            while let Some(k) = next(*chain.last().unwrap(), &mut used) {
+                chain.push(k);
+            }
+            let closed = chain.len() > 2 && chain.first() == chain.last();
+            if !closed {
+                let mut head = vec![];
+                while let Some(k) = next(*head.last().unwrap_or(&a), &mut used) {
+                    head.push(k);
+                }
+                head.reverse();
+                head.extend(chain);
+                chain = head;
+            }
+            let (points, uvs) = chain.iter().map(|k| crossings[k]).unzip();
+            lines.push((points, uvs, closed));
+        }
+        lines
+    }
+}
@@ -1,8 +1,11 @@
 #![allow(unused_imports)]
 
 use approx::assert_relative_eq;
-use curvo::prelude::{NurbsCurve3D, NurbsSurface, NurbsSurface3D};
-use nalgebra::{Point3, Vector3};
+use curvo::prelude::{
+    CompoundCurve2D, NurbsCurve2D, NurbsCurve3D, NurbsSurface, NurbsSurface3D, Region, Shell,
+    TrimmedSurface,
+};
+use nalgebra::{Point2, Point3, Vector2, Vector3};
 
 #[test]
 #[cfg(feature = "serde")]
@@ -56,3 +59,73 @@ fn test_surface_serialization() {
     assert_relative_eq!(surface.u_knots().as_slice(), der.u_knots().as_slice());
     assert_relative_eq!(surface.v_knots().as_slice(), der.v_knots().as_slice());
 }
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_compound_curve_serialization() {
+    let compound = CompoundCurve2D::try_new(vec![
+        NurbsCurve2D::polyline(&[Point2::new(0., 0.), Point2::new(1., 0.)]),
+        NurbsCurve2D::polyline(&[Point2::new(1., 0.), Point2::new(1., 1.)]),
+    ])
+    .unwrap();
+    let json = serde_json::to_string_pretty(&compound).unwrap();
+    let der: CompoundCurve2D<f64> = serde_json::from_str(&json).unwrap();
+    assert_eq!(compound, der);
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_region_serialization() {
+    let square = NurbsCurve2D::polyline(&[
+        Point2::new(0., 0.),
+        Point2::new(1., 0.),
+        Point2::new(1., 1.),
+        Point2::new(0., 1.),
+        Point2::new(0., 0.),
+    ]);
+    let hole = NurbsCurve2D::try_circle(&Point2::new(0.5, 0.5), &Vector2::x(), &Vector2::y(), 0.25)
+        .unwrap();
+    let region = Region::new(square.into(), vec![hole.into()]);
+    let json = serde_json::to_string_pretty(&region).unwrap();
+    let der: Region<f64> = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(region.exterior(), der.exterior());
+    assert_eq!(region.interiors().len(), der.interiors().len());
+    let (a, b) = (&region.interiors()[0], &der.interiors()[0]);
+    assert!((a.point_at(0.3) - b.point_at(0.3)).norm() < 1e-8);
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_trimmed_surface_serialization() {
+    let line = NurbsCurve3D::polyline(&[Point3::new(0., 0., 0.), Point3::new(1., 0., 0.)]);
+    let plane = NurbsSurface::extrude(&line, &Vector3::y());
+    let hole = NurbsCurve2D::try_circle(&Point2::new(0.5, 0.5), &Vector2::x(), &Vector2::y(), 0.25)
+        .unwrap();
+    let trimmed = TrimmedSurface::try_new(plane, None, vec![hole.into()]).unwrap();
+    let json = serde_json::to_string_pretty(&trimmed).unwrap();
+    let der: TrimmedSurface<f64> = serde_json::from_str(&json).unwrap();
+
+    assert!(der.exterior().is_none());
+    assert_eq!(trimmed.interiors().len(), der.interiors().len());
+    let (a, b) = (&trimmed.interiors()[0], &der.interiors()[0]);
+    assert!((a.point_at(0.3) - b.point_at(0.3)).norm() < 1e-8);
+    assert_eq!(trimmed.contains(0.1, 0.1), der.contains(0.1, 0.1));
+    assert_eq!(trimmed.contains(0.5, 0.5), der.contains(0.5, 0.5));
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_shell_serialization() {
+    let line = NurbsCurve3D::polyline(&[Point3::new(0., 0., 0.), Point3::new(1., 0., 0.)]);
+    let a = NurbsSurface::extrude(&line, &Vector3::y());
+    let b = NurbsSurface::extrude(&line, &Vector3::z());
+    let shell = Shell::try_from_surfaces(&[a, b], 1e-6).unwrap();
+    let json = serde_json::to_string_pretty(&shell).unwrap();
+    let der: Shell<f64> = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(shell.vertices().len(), der.vertices().len());
+    assert_eq!(shell.edges().len(), der.edges().len());
+    assert_eq!(shell.faces().len(), der.faces().len());
+    assert_eq!(shell.boundary_edges(), der.boundary_edges());
+}